@@ -0,0 +1,101 @@
+//! Benchmarks of the hot paths on large datasets
+//!
+//! Every key press in the todo list flattens the task tree for the selection math and every
+//! frame rebuilds the list items, so both are measured on trees of 1k and 5k tasks, along
+//! with saving and loading a large data file, and the start up with most of it archived.
+//! Run with `cargo bench`, no terminal is needed.
+
+use std::{cell::RefCell, hint::black_box, rc::Rc};
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use todo::app::{
+    data::{self, Datas},
+    sources::SystemClock,
+    ui::{
+        SelectAction,
+        todolistwidget::{RowOptions, SortRule, Task, TodoWidget},
+    },
+};
+
+const SIZES: [usize; 2] = [1_000, 5_000];
+
+/// Gather the tasks of every list into one tree, as if they were a single large list
+fn all_tasks(datas: &Datas) -> Vec<Rc<RefCell<Task>>> {
+    datas
+        .todolist
+        .todolists
+        .iter()
+        .flat_map(|list| list.borrow().tasks.clone())
+        .collect()
+}
+
+fn flatten(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_flattened");
+    for size in SIZES {
+        let tasks = all_tasks(&data::generate_datas(size));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &tasks, |b, tasks| {
+            b.iter(|| TodoWidget::get_flattened(black_box(tasks)))
+        });
+    }
+    group.finish();
+}
+
+fn list_items(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_task_list_item");
+    for size in SIZES {
+        let tasks = all_tasks(&data::generate_datas(size));
+        let max_len = TodoWidget::find_max_tasks_len(&tasks, 0);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &tasks, |b, tasks| {
+            b.iter(|| {
+                TodoWidget::get_task_list_item(
+                    black_box(tasks),
+                    SortRule::Manual,
+                    0,
+                    max_len,
+                    &SystemClock,
+                    RowOptions::default(),
+                    None,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+fn save_load(c: &mut Criterion) {
+    let datas = data::generate_datas(SIZES[1]);
+    let path = std::env::temp_dir().join(format!("todo-bench-{}.json", std::process::id()));
+
+    c.bench_function("save_data/5000", |b| {
+        b.iter(|| data::save_data(black_box(&path), &datas).unwrap())
+    });
+    c.bench_function("load_data/5000", |b| {
+        b.iter(|| data::load_data(black_box(&path)).unwrap())
+    });
+
+    let _ = std::fs::remove_file(&path);
+}
+
+/// Loading everything against loading the active workspaces, with 4 in 5 archived
+fn startup(c: &mut Criterion) {
+    let mut datas = data::generate_datas(SIZES[1]);
+    let archived: Vec<_> = datas.workspace.workspaces.drain(1..).collect();
+    datas.archived_ws.workspaces.extend(archived);
+    let path = std::env::temp_dir().join(format!("todo-bench-startup-{}.json", std::process::id()));
+    data::save_data(&path, &datas).unwrap();
+
+    let mut group = c.benchmark_group("startup");
+    group.bench_function("load_data/5000", |b| {
+        b.iter(|| data::load_data(black_box(&path)).unwrap())
+    });
+    group.bench_function("load_active/5000", |b| {
+        b.iter(|| data::load_active(black_box(&path)).unwrap())
+    });
+    group.finish();
+
+    let _ = std::fs::remove_file(data::archive_path(&path));
+    let _ = std::fs::remove_file(&path);
+}
+
+criterion_group!(benches, flatten, list_items, save_load, startup);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+//! Embed the git commit the binary is built from, shown by `todo --version` and the about
+//! popup, see `src/app/about.rs`
+//!
+//! Built outside of a git checkout, e.g. from a published crate, the commit is left unset.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=9", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .filter(|commit| !commit.is_empty());
+    if let Some(commit) = commit {
+        println!("cargo:rustc-env=TODO_GIT_COMMIT={}", commit);
+    }
+}
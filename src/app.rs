@@ -18,13 +18,14 @@
 //! - UI rendering
 //! - Key event handling
 //! - Message processing
+//! - A low-frequency tick that keeps time-dependent rendering (relative due dates) fresh
 //!
 //! ## Usage
 //!
-//! ```rust
-//! use crate::app::App;
+//! ```no_run
+//! use todo::app::{App, errors::Errors};
 //!
-//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! fn main() -> Result<(), Errors> {
 //!     let app = App::new();
 //!     app.run()?;
 //!     Ok(())
@@ -32,56 +33,89 @@
 //! ```
 
 use std::{
-    path::Path,
+    io,
     sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc::{self, error::TrySendError},
+    time::MissedTickBehavior,
 };
-use tokio::sync::mpsc;
 
-use crossterm::event::{self, KeyEvent, KeyModifiers};
+use crossterm::event::{self, KeyEvent};
+use ratatui::{Terminal, backend::CrosstermBackend};
 
 use crate::app::{
-    appstate::{AppState, CurrentFocus, CurrentMode, Message},
-    data::Datas,
-    ui::{SearchEvent, UiMessage, WidgetAction, todolistwidget::TaskStatus},
+    appstate::{AppState, CurrentFocus, CurrentMode, Message, StateChange},
+    macros::Recorder,
+    ui::{
+        SearchEvent, SelectBF, UiMessage, WidgetAction, keys,
+        theme::ThemedBackend,
+        todolistwidget::TaskStatus,
+        tree::{Fold, Jump},
+        workspacewidget::WorkspaceType,
+    },
 };
 
+pub mod about;
 pub mod appstate;
+pub mod check;
+pub mod cli;
+pub mod config;
 pub mod data;
+pub mod diff;
 pub mod errors;
+pub mod journal;
+pub mod links;
+pub mod macros;
+pub mod markdown;
+pub mod names;
+pub mod parse;
+pub mod plaintext;
+pub mod serve;
+pub mod share;
+pub mod sources;
 pub mod ui;
+pub mod visit;
 
 /// The Basic Structure of the App
 ///
 /// # Fields
 ///
 /// - `appstate` (`Arc<Mutex<AppState>>`) - A structure that holds the state of the app.
+/// - `cli` ([`cli::Cli`]) - The options given on the command line.
 ///
 /// # Examples
 ///
 /// just simply create a new App by
 ///
 /// ```
-/// use crate::app::App;
+/// use todo::app::App;
 /// let s = App::new();
 /// ```
 ///
 /// or
 ///
 /// ```
-/// use crate::app::App;
-/// let s = App {
-///     appstate: Arc::new(Mutex::new(AppState::new())),
-/// };
+/// use todo::app::{App, cli::Cli};
+/// let s = App::with_cli(Cli::default());
 /// ```
 #[derive(Debug)]
 pub struct App {
     appstate: Arc<Mutex<AppState>>,
+    cli: cli::Cli,
 }
 
 impl App {
     pub fn new() -> Self {
+        Self::with_cli(cli::Cli::default())
+    }
+
+    /// Create an App which runs with the options given on the command line
+    pub fn with_cli(cli: cli::Cli) -> Self {
         Self {
             appstate: Arc::new(Mutex::new(AppState::new())),
+            cli,
         }
     }
     /// The main function of the app
@@ -100,20 +134,29 @@ impl App {
     ///
     /// # Examples
     ///
-    /// ```
-    /// use crate::app::App;
+    /// ```no_run
+    /// use todo::app::App;
     ///
     /// let app = App::new();
     /// let res = app.run();
     /// ```
     pub fn run(&self) -> Result<(), errors::Errors> {
-        let mut terminal = ratatui::init();
+        // the terminal set up by ratatui, drawn through a backend adapting the colors to it
+        let _ = ratatui::init();
+        let mut terminal = Terminal::new(ThemedBackend::new(CrosstermBackend::new(io::stdout())))
+            .map_err(|_| {
+            ratatui::restore();
+            errors::Errors::UiError
+        })?;
         let (tx, rx) = mpsc::channel::<Message>(10);
         let (ui_tx, ui_rx) = mpsc::channel::<UiMessage>(10);
         let (input_tx, input_rx) = mpsc::channel::<KeyEvent>(10);
 
         let apps_in_keyhand = self.appstate.clone();
-        let key_handle = std::thread::spawn(move || {
+        let input_tx_in_msg = input_tx.clone();
+        // not joined: the ui ends after the key handler, or on its own when the user quits at
+        // start up, leaving the key handler waiting for a key
+        let _key_handle = std::thread::spawn(move || {
             let rt = tokio::runtime::Builder::new_current_thread()
                 .build()
                 .unwrap();
@@ -126,24 +169,165 @@ impl App {
             let rt = tokio::runtime::Builder::new_current_thread()
                 .build()
                 .unwrap();
-            rt.block_on(handle_msg(rx, ui_tx_in_msg, apps_in_msghand));
+            rt.block_on(handle_msg(
+                rx,
+                ui_tx_in_msg,
+                input_tx_in_msg,
+                apps_in_msghand,
+            ));
+        });
+
+        // a weak sender, so the ui channel closes once the key and message handlers are gone
+        let ui_tx_in_tick = ui_tx.downgrade();
+        let _tick_handle = std::thread::spawn(|| {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_time()
+                .build()
+                .unwrap();
+            rt.block_on(handle_tick(ui_tx_in_tick));
         });
 
         let apps_in_ui = self.appstate.clone();
+        let cli = self.cli.clone();
         let ui_handle = std::thread::spawn(move || -> Result<(), errors::Errors> {
             let mut ui = ui::Ui::new(ui_rx, input_rx);
-            let path = Path::new(
-                std::env::home_dir()
-                    .unwrap_or(std::path::PathBuf::from("~"))
-                    .as_path(),
-            )
-            .join(".todo/data.json");
-            let data = data::load_data(path.as_path())?;
+            // never touch the real data file with a generated dataset
+            let (path, first_run, mut data, bad_dues) = if let Some(count) = cli.stress {
+                let path = std::env::temp_dir().join("todo-stress.json");
+                ui.profile = about::Profile::Stress;
+                (path, false, data::generate_datas(count), Vec::new())
+            } else if cli.demo {
+                let path = std::env::temp_dir().join("todo-demo.json");
+                ui.profile = about::Profile::Demo;
+                (path, false, data::demo_datas(), Vec::new())
+            } else {
+                let (path, source) = data::data_path();
+                ui.profile = about::Profile::Data(source);
+                if source == data::DataPathSource::CurrentDir {
+                    ui.prompt
+                        .set(format!("No home directory, saving to {}", path.display()));
+                }
+                let first_run = !path.exists();
+                // the archive file is read when the archived panel is opened
+                let (data, bad_dues) = check::load(path.as_path())?;
+                // not saved without the due dates before todo check --repair backs it up
+                ui.read_only = !bad_dues.is_empty();
+                (path, first_run, data, bad_dues)
+            };
+            data.ensure_inbox();
+            // warn early, rather than letting the broken data trip a feature up later
+            let mut problems = bad_dues;
+            problems.extend(check::check_datas(&data));
+            let duplicates = problems.iter().filter(|p| p.is_duplicate()).count();
+            ui.data_path = path.clone();
+            // the file as loaded, so a program writing it meanwhile is caught on the next save
+            ui.saved_stamp = data::FileStamp::read(&path);
+            ui.appstate = apps_in_ui.clone();
             ui.workspace = data.workspace;
             ui.todolist = data.todolist;
             ui.archived_ws = data.archived_ws;
+            ui.config = config::load_config(config::config_path().as_path());
+            if let Some(language) = &ui.config.language {
+                match ui::strings::Messages::load(&config::translation_path(language)) {
+                    Ok(messages) => ui::strings::select(messages),
+                    Err(_) => ui
+                        .prompt
+                        .set(format!("Translation '{}' Not Found !", language)),
+                }
+            }
+            if ui.read_only {
+                ui.prompt.set(ui::strings::trf(
+                    "{} Problems In The Data, Read Only Until todo check --repair !",
+                    &[&problems.len()],
+                ));
+            } else if !problems.is_empty() {
+                ui.prompt.set(ui::strings::trf(
+                    "{} Problems In The Data, Run todo check !",
+                    &[&problems.len()],
+                ));
+            }
+            ui.todolist.stale_after_days = ui.config.stale_after_days;
+            ui.todolist.show_age = ui.config.show_task_age;
+            ui.todolist.absolute_due_after_days = ui.config.absolute_due_after_days;
+            ui.escalate_overdue(&sources::SystemClock);
+            if cli.plain || ui.config.plain {
+                ui::glyphs::select_set(&ui::glyphs::PLAIN);
+            } else {
+                ui::glyphs::select(
+                    ui.config
+                        .ascii_glyphs
+                        .unwrap_or(ui::glyphs::ascii_by_default()),
+                );
+            }
+            terminal.backend_mut().support = ui
+                .config
+                .color
+                .unwrap_or_else(ui::theme::ColorSupport::from_env);
 
+            if ui.archived_ws.focused {
+                ui.load_archive();
+            }
             ui.refresh_current();
+            if ui.config.terminal_title && ui::title::TerminalTitle::supported() {
+                ui.title = Some(ui::title::TerminalTitle::start());
+            }
+            // the timer drives the spinner shown while a save is in flight
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_time()
+                .build()
+                .unwrap();
+            // ask before anything is changed rather than losing the changes on exit
+            if cli.stress.is_none()
+                && !cli.demo
+                && let Err(err) = data::check_writable(&path)
+            {
+                apps_in_ui.lock().unwrap().open_popup(CurrentMode::Insert);
+                let input_rx = ui.input_rx.clone();
+                let choice = rt.block_on(ui.resolve_unwritable(input_rx, &mut terminal, &err));
+                apps_in_ui.lock().unwrap().close_popup();
+                if choice == ui::Unwritable::Quit {
+                    return Ok(());
+                }
+            }
+            // the duplicate ids break the selection, offer the repair of todo check once
+            if duplicates > 0 && !ui.read_only {
+                apps_in_ui.lock().unwrap().open_popup(CurrentMode::Insert);
+                let input_rx = ui.input_rx.clone();
+                let renew = rt.block_on(ui.confirm_renew_ids(input_rx, &mut terminal, duplicates));
+                apps_in_ui.lock().unwrap().close_popup();
+                if renew {
+                    ui.renew_duplicate_ids(&sources::RandomIds);
+                }
+            }
+            // the actions a crash kept from being saved, see the journal module
+            if !ui.read_only {
+                let entries = journal::pending(&ui.data_path);
+                let replay = !entries.is_empty() && {
+                    apps_in_ui.lock().unwrap().open_popup(CurrentMode::Insert);
+                    let input_rx = ui.input_rx.clone();
+                    let replay =
+                        rt.block_on(ui.confirm_replay(input_rx, &mut terminal, entries.len()));
+                    apps_in_ui.lock().unwrap().close_popup();
+                    replay
+                };
+                match journal::Journal::create(journal::journal_path(&ui.data_path)) {
+                    Ok(journal) => ui.journal = Some(journal),
+                    Err(err) => ui
+                        .prompt
+                        .set(ui::strings::trf("Journal Not Written: {}", &[&err])),
+                }
+                if replay {
+                    ui.replay(entries);
+                }
+            }
+            let filtered = match (&cli.search, &cli.filter) {
+                (Some(query), _) => {
+                    ui.start_search(query);
+                    false
+                }
+                (None, Some(query)) => ui.start_filter(query),
+                (None, None) => false,
+            };
             let mut apps = apps_in_ui.lock().unwrap();
             apps.current_focus = if ui.archived_ws.focused {
                 CurrentFocus::ArchivedWorkspace
@@ -152,19 +336,21 @@ impl App {
             } else {
                 CurrentFocus::Workspace
             };
+            if filtered {
+                apps.current_mode = CurrentMode::Search;
+            }
+            if first_run && !ui.config.tour_shown {
+                ui.tour.start();
+                apps.current_mode = CurrentMode::Tour;
+            }
             drop(apps);
-            let rt = tokio::runtime::Builder::new_current_thread()
-                .build()
-                .unwrap();
+            ui.announce_due(&sources::SystemClock);
 
             rt.block_on(ui.handle_uimsg(&mut terminal, apps_in_ui));
-            let datas = Datas {
-                workspace: ui.workspace,
-                todolist: ui.todolist,
-                archived_ws: ui.archived_ws,
-            };
-
-            data::save_data(path.as_path(), &datas)
+            if ui.read_only {
+                return Ok(());
+            }
+            ui.exit_save()
         });
 
         let rt = tokio::runtime::Builder::new_current_thread()
@@ -175,10 +361,6 @@ impl App {
             let _ = ui_tx.send(UiMessage::UpdateUi).await;
         });
 
-        key_handle
-            .join()
-            .map_err(|_| errors::Errors::AppError)
-            .unwrap();
         let result = ui_handle
             .join()
             .map_err(|_| errors::Errors::UiError)
@@ -200,176 +382,402 @@ impl Default for App {
 /// # Arguments
 ///
 /// - `tx` (`mpsc`) - a mpsc to send [`Message`] to the message handler
-/// - `input_tx` (`mpsc`) - a mpsc sender to send [`KeyEvent`] to the ui module for input handling
+/// - `input_tx` (`mpsc`) - a mpsc sender to have the open popup redraw on a resize, see
+///   [`Recorder::resize`]
 /// - `appstate` (`Arc<Mutex<AppState>>`) - the state of the app
-///
-/// # Examples
-///
-/// ```no_run
-/// use crate::app::*;
-///
-/// async {
-///   let result = handle_keyevt().await;
-/// };
-/// ```
 async fn handle_keyevt(
     tx: mpsc::Sender<Message>,
     input_tx: mpsc::Sender<KeyEvent>,
     appstate: Arc<Mutex<AppState>>,
 ) {
+    let mut out = Recorder::new(tx, input_tx);
+    // `q` or `@` waiting for the register letter, or `g` or `z` for the key after it
+    let mut register_for: Option<char> = None;
     loop {
         let evt = event::read().unwrap();
         if let event::Event::Key(key_evt) = evt {
-            if let event::KeyEventKind::Press = key_evt.kind {
-                let apps = appstate.lock().unwrap();
-                match apps.current_mode {
+            if keys::is_press(&key_evt) {
+                let (current_mode, current_focus, archived_visible, banner) = {
+                    let apps = appstate.lock().unwrap();
+                    (
+                        apps.current_mode,
+                        apps.current_focus.clone(),
+                        apps.archived_visible,
+                        apps.banner,
+                    )
+                };
+                if let Some(prefix) = register_for.take() {
+                    match (prefix, keys::text(&key_evt)) {
+                        ('q', Some('q')) => {
+                            let _ = out.send(Message::Exit).await;
+                            break;
+                        }
+                        ('q', Some(register)) => out.start(register).await,
+                        ('@', Some(register)) => out.replay(register).await,
+                        ('g', Some('?')) => {
+                            let _ = out.send(Message::KeyHelp).await;
+                        }
+                        ('z', Some(key @ ('M' | 'R' | 'c' | 'o'))) => {
+                            let fold = match key {
+                                'M' => Fold::CloseAll,
+                                'R' => Fold::OpenAll,
+                                'c' => Fold::Close,
+                                _ => Fold::Open,
+                            };
+                            let _ = out.send(Message::Fold(fold)).await;
+                        }
+                        _ => {}
+                    }
+                    appstate.lock().unwrap().recording = out.macros.recording();
+                    continue;
+                }
+                // `O` jumps to the task of the due banner, any other key hides it and goes on
+                if banner && matches!(current_mode, CurrentMode::Normal | CurrentMode::Search) {
+                    appstate.lock().unwrap().banner = false;
+                    if keys::text(&key_evt) == Some('O') {
+                        let _ = out.send(Message::BannerJump).await;
+                        continue;
+                    }
+                    let _ = out.send(Message::DismissBanner).await;
+                }
+                match current_mode {
                     CurrentMode::Normal | CurrentMode::Search => match key_evt.code {
                         event::KeyCode::Esc => {
-                            if let CurrentMode::Normal = apps.current_mode {
-                            } else if let CurrentMode::Search = apps.current_mode {
-                                let _ = tx.send(Message::SearchMsg(SearchEvent::Exit)).await;
+                            if let CurrentMode::Normal = current_mode {
+                                if let CurrentFocus::TodoList = current_focus {
+                                    let _ = out.send(Message::FocusBack).await;
+                                }
+                            } else if let CurrentMode::Search = current_mode {
+                                let _ = out.send(Message::SearchMsg(SearchEvent::Exit)).await;
                             }
                         }
+                        event::KeyCode::Char('n') if current_mode == CurrentMode::Search => {
+                            let _ = out.send(Message::SearchMsg(SearchEvent::Next)).await;
+                        }
+                        event::KeyCode::Char('N') if current_mode == CurrentMode::Search => {
+                            let _ = out.send(Message::SearchMsg(SearchEvent::Previous)).await;
+                        }
+                        event::KeyCode::Char('q') if out.macros.recording().is_some() => {
+                            out.stop().await;
+                        }
                         event::KeyCode::Char('q') => {
-                            let _ = tx.send(Message::Exit).await;
-                            break;
+                            out.prompt("q Again Quits, a To z Records !".to_string())
+                                .await;
+                            register_for = Some('q');
+                        }
+                        event::KeyCode::Char('@') => {
+                            register_for = Some('@');
                         }
-                        event::KeyCode::Char('s')
-                            if key_evt.modifiers.contains(KeyModifiers::CONTROL) =>
-                        {
-                            let _ = tx.send(Message::SaveData).await;
+                        event::KeyCode::Char('g') => {
+                            register_for = Some('g');
+                        }
+                        event::KeyCode::Char('z') => {
+                            register_for = Some('z');
+                        }
+                        event::KeyCode::F(1) => {
+                            let _ = out.send(Message::KeyHelp).await;
+                        }
+                        event::KeyCode::Char('s') if keys::is_ctrl(&key_evt, 's') => {
+                            let _ = out.send(Message::SaveData).await;
                         }
                         event::KeyCode::Char('s') => {
-                            let _ = tx.send(Message::Sort).await;
+                            let _ = out.send(Message::Sort).await;
+                        }
+                        event::KeyCode::Char('k') if keys::is_ctrl(&key_evt, 'k') => {
+                            let _ = out.send(Message::Palette).await;
+                        }
+                        event::KeyCode::Char(':') => {
+                            let _ = out.send(Message::Palette).await;
+                        }
+                        event::KeyCode::Char('a') if keys::is_ctrl(&key_evt, 'a') => {
+                            let _ = out.send(Message::ToggleArchived).await;
                         }
                         event::KeyCode::Char('a') => {
-                            let _ = tx.send(Message::AddItem).await;
+                            let _ = out.send(Message::AddItem).await;
                         }
                         event::KeyCode::Char('i') => {
-                            let _ = tx.send(Message::AddChild).await;
+                            let _ = out.send(Message::AddChild).await;
                         }
                         event::KeyCode::Char('j') | event::KeyCode::Down => {
-                            let _ = tx.send(Message::MoveDown).await;
+                            let _ = out.send(Message::MoveDown).await;
                         }
                         event::KeyCode::Char('k') | event::KeyCode::Up => {
-                            let _ = tx.send(Message::MoveUp).await;
+                            let _ = out.send(Message::MoveUp).await;
+                        }
+                        event::KeyCode::Char('(') => {
+                            let _ = out.send(Message::Jump(Jump::Parent)).await;
+                        }
+                        event::KeyCode::Char(')') => {
+                            let _ = out.send(Message::Jump(Jump::FirstChild)).await;
+                        }
+                        event::KeyCode::Char('{') => {
+                            let _ = out.send(Message::Jump(Jump::PrevSibling)).await;
+                        }
+                        event::KeyCode::Char('}') => {
+                            let _ = out.send(Message::Jump(Jump::NextSibling)).await;
                         }
                         event::KeyCode::Char('l') | event::KeyCode::Right => {
-                            if let CurrentFocus::Workspace = apps.current_focus {
-                                let _ = tx.send(Message::SelectWorkspace).await;
+                            if let CurrentFocus::Workspace = current_focus {
+                                let _ = out.send(Message::SelectWorkspace).await;
                             }
                         }
+                        event::KeyCode::Char('h') if keys::is_ctrl(&key_evt, 'h') => {
+                            let _ = out.send(Message::Messages).await;
+                        }
                         event::KeyCode::Char('h') | event::KeyCode::Left => {
-                            if let CurrentFocus::TodoList = apps.current_focus {
-                                let _ =
-                                    tx.send(Message::ChangeFocus(CurrentFocus::Workspace)).await;
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::FocusBack).await;
                             }
                         }
                         event::KeyCode::Char('c') => {
-                            if let CurrentFocus::TodoList = apps.current_focus {
-                                let _ = tx.send(Message::Complete).await;
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::Complete).await;
                             }
                         }
                         event::KeyCode::Char('t') => {
-                            if let CurrentFocus::TodoList = apps.current_focus {
-                                let _ = tx.send(Message::Todo).await;
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::Todo).await;
+                            }
+                        }
+                        event::KeyCode::Char('o') if keys::is_ctrl(&key_evt, 'o') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::OpenLink).await;
                             }
                         }
                         event::KeyCode::Char('p') => {
-                            if let CurrentFocus::TodoList = apps.current_focus {
-                                let _ = tx.send(Message::InProcess).await;
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::InProcess).await;
+                            }
+                        }
+                        event::KeyCode::Char('L') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::ToggleRowNumbers).await;
+                            }
+                        }
+                        event::KeyCode::Char('G') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::GoToRow).await;
+                            }
+                        }
+                        event::KeyCode::Char('F') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::ImportFile).await;
+                            }
+                        }
+                        event::KeyCode::Char('X') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::ClearDone).await;
                             }
                         }
                         event::KeyCode::Char('A') => {
-                            if let CurrentFocus::Workspace = apps.current_focus {
-                                let _ = tx.send(Message::Archive).await;
+                            if let CurrentFocus::Workspace = current_focus {
+                                let _ = out.send(Message::Archive).await;
+                            }
+                        }
+                        event::KeyCode::Char('C') => {
+                            let _ = out.send(Message::Heatmap).await;
+                        }
+                        event::KeyCode::Char('I') => {
+                            if let CurrentFocus::Workspace = current_focus {
+                                let _ = out.send(Message::WorkspaceStats).await;
+                            }
+                        }
+                        event::KeyCode::Char('H') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::TaskColor).await;
+                            }
+                        }
+                        event::KeyCode::Char('e') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::EditNotes).await;
+                            }
+                        }
+                        event::KeyCode::Char('y') => {
+                            if let CurrentFocus::Workspace = current_focus {
+                                let _ = out.send(Message::CopyWorkspace).await;
+                            }
+                        }
+                        event::KeyCode::Char('P') => {
+                            if let CurrentFocus::Workspace = current_focus {
+                                let _ = out.send(Message::ToggleProtected).await;
+                            }
+                        }
+                        event::KeyCode::Char('d') if keys::is_ctrl(&key_evt, 'd') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::DueMatching).await;
                             }
                         }
                         event::KeyCode::Char('d') => {
-                            if let CurrentFocus::TodoList = apps.current_focus {
-                                let _ = tx.send(Message::Deprecated).await;
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::Deprecated).await;
                             }
                         }
                         event::KeyCode::Char('D') => {
-                            if let CurrentFocus::TodoList = apps.current_focus {
-                                let _ = tx.send(Message::Due).await;
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::Due).await;
+                            }
+                        }
+                        event::KeyCode::Char('.') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::DueInline).await;
                             }
                         }
                         event::KeyCode::Char('x') => {
-                            let _ = tx.send(Message::DeleteItem).await;
+                            let _ = out.send(Message::DeleteItem).await;
                         }
                         event::KeyCode::Char('r') => {
-                            let _ = tx.send(Message::Rename).await;
+                            let _ = out.send(Message::Rename).await;
                         }
                         event::KeyCode::Char('R') => {
-                            let _ = tx.send(Message::Recovery).await;
+                            let _ = out.send(Message::Recovery).await;
+                        }
+                        event::KeyCode::Char('K') => {
+                            let _ = out.send(Message::ShiftUp).await;
+                        }
+                        event::KeyCode::Char('J') => {
+                            let _ = out.send(Message::ShiftDown).await;
+                        }
+                        event::KeyCode::Char('S') => {
+                            let _ = out.send(Message::SearchAll).await;
+                        }
+                        event::KeyCode::Char('V') => {
+                            let _ = out.send(Message::About).await;
                         }
                         event::KeyCode::Char('f') | event::KeyCode::Char('/') => {
-                            let _ = tx.send(Message::Filter).await;
+                            let _ = out.send(Message::Filter).await;
                         }
-                        event::KeyCode::Tab => match apps.current_focus {
+                        event::KeyCode::Tab => match current_focus {
                             CurrentFocus::TodoList => {
-                                let _ =
-                                    tx.send(Message::ChangeFocus(CurrentFocus::Workspace)).await;
+                                let _ = out
+                                    .send(Message::ChangeFocus(CurrentFocus::Workspace))
+                                    .await;
                             }
-                            CurrentFocus::Workspace => {
-                                let _ = tx
+                            CurrentFocus::Workspace if archived_visible => {
+                                let _ = out
                                     .send(Message::ChangeFocus(CurrentFocus::ArchivedWorkspace))
                                     .await;
                             }
-                            CurrentFocus::ArchivedWorkspace => {
-                                let _ = tx.send(Message::ChangeFocus(CurrentFocus::TodoList)).await;
+                            CurrentFocus::Workspace | CurrentFocus::ArchivedWorkspace => {
+                                let _ =
+                                    out.send(Message::ChangeFocus(CurrentFocus::TodoList)).await;
                             }
                         },
                         event::KeyCode::Char('1') => {
-                            let _ = tx.send(Message::ChangeFocus(CurrentFocus::Workspace)).await;
+                            let _ = out
+                                .send(Message::ChangeFocus(CurrentFocus::Workspace))
+                                .await;
                         }
                         event::KeyCode::Char('2') => {
-                            let _ = tx
+                            let _ = out
                                 .send(Message::ChangeFocus(CurrentFocus::ArchivedWorkspace))
                                 .await;
                         }
                         event::KeyCode::Char('3') => {
-                            let _ = tx.send(Message::ChangeFocus(CurrentFocus::TodoList)).await;
+                            let _ = out.send(Message::ChangeFocus(CurrentFocus::TodoList)).await;
                         }
-                        event::KeyCode::Enter => match apps.current_focus {
+                        event::KeyCode::Char('T') => {
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::Triage).await;
+                            }
+                        }
+                        event::KeyCode::Enter => match current_focus {
                             CurrentFocus::Workspace | CurrentFocus::ArchivedWorkspace => {
-                                let _ = tx.send(Message::SelectWorkspace).await;
+                                let _ = out.send(Message::SelectWorkspace).await;
+                            }
+                            CurrentFocus::TodoList => {
+                                let _ = out.send(Message::EnterTask).await;
                             }
-                            _ => (),
                         },
                         event::KeyCode::Char('?') => {
-                            let _ = tx.send(Message::Help).await;
+                            let _ = out.send(Message::Help).await;
                         }
                         event::KeyCode::Char('+') | event::KeyCode::Char('=') => {
-                            let _ = tx.send(Message::IncreseUrgency).await;
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::IncreaseUrgency).await;
+                            }
                         }
                         event::KeyCode::Char('-') | event::KeyCode::Char('_') => {
-                            let _ = tx.send(Message::DecreseUrgency).await;
+                            if let CurrentFocus::TodoList = current_focus {
+                                let _ = out.send(Message::DecreaseUrgency).await;
+                            }
                         }
                         _ => {}
                     },
                     CurrentMode::Insert | CurrentMode::Sort => {
-                        let _ = input_tx.send(key_evt).await;
+                        let _ = out.send(Message::Edit(key_evt)).await;
                     }
+                    CurrentMode::Tour => match key_evt.code {
+                        event::KeyCode::Enter | event::KeyCode::Char(' ') => {
+                            let _ = out.send(Message::TourNext).await;
+                        }
+                        event::KeyCode::Char('q') | event::KeyCode::Esc => {
+                            let _ = out.send(Message::TourSkip).await;
+                        }
+                        _ => {}
+                    },
                     CurrentMode::Help => match key_evt.code {
                         event::KeyCode::Char('j') | event::KeyCode::Down => {
-                            let _ = tx.send(Message::MoveDown).await;
+                            let _ = out.send(Message::MoveDown).await;
                         }
                         event::KeyCode::Char('k') | event::KeyCode::Up => {
-                            let _ = tx.send(Message::MoveUp).await;
+                            let _ = out.send(Message::MoveUp).await;
                         }
-                        event::KeyCode::Char('l') | event::KeyCode::Right => {}
-                        event::KeyCode::Char('h') | event::KeyCode::Left => {}
-                        event::KeyCode::Char('q') | event::KeyCode::Esc => {
-                            let _ = tx.send(Message::ExitHelp).await;
+                        event::KeyCode::Char('l') | event::KeyCode::Right | event::KeyCode::Tab => {
+                            let _ = out.send(Message::NextHelpTab).await;
+                        }
+                        event::KeyCode::Char('h')
+                        | event::KeyCode::Left
+                        | event::KeyCode::BackTab => {
+                            let _ = out.send(Message::PrevHelpTab).await;
+                        }
+                        event::KeyCode::Char('t') => {
+                            let _ = out.send(Message::Tour).await;
+                        }
+                        event::KeyCode::Char('q')
+                        | event::KeyCode::Char('?')
+                        | event::KeyCode::Esc => {
+                            let _ = out.send(Message::ExitHelp).await;
                         }
                         _ => {}
                     },
                 }
             }
+            appstate.lock().unwrap().recording = out.macros.recording();
         } else if let event::Event::Resize(_, _) = evt {
-            let _ = tx.send(Message::Update).await;
+            // an open popup holds the ui loop until it's closed, it's redrawn through its keys
+            let current_mode = appstate.lock().unwrap().current_mode;
+            if let CurrentMode::Insert | CurrentMode::Sort = current_mode {
+                out.resize();
+            } else {
+                let _ = out.send(Message::Update).await;
+            }
+        }
+    }
+}
+
+/// A function sends a periodic redraw request to the ui, running in a thread
+///
+/// The relative due date ("N day left") is computed while rendering, so without
+/// this tick an app left open overnight keeps showing yesterday's colors until a key is pressed.
+/// The tick fires once a minute and uses `try_send`, so while a popup owns the drawing loop the
+/// ticks are simply dropped instead of piling up in the channel.
+///
+/// # Arguments
+///
+/// - `ui_tx` (`mpsc::WeakSender`) - weak sender to send [`UiMessage::UpdateUi`] to the ui, it
+///   doesn't keep the ui running once the other senders are dropped
+async fn handle_tick(ui_tx: mpsc::WeakSender<UiMessage>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    // the first tick completes immediately, the ui is already drawn at startup
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let Some(ui_tx) = ui_tx.upgrade() else {
+            break;
+        };
+        if let Err(TrySendError::Closed(_)) = ui_tx.try_send(UiMessage::UpdateUi) {
+            break;
         }
     }
 }
@@ -380,240 +788,280 @@ async fn handle_keyevt(
 ///
 /// - `mut rx` (`mpsc`) - mpsc receiver to receive message from keyevent handler
 /// - `ui_tx` (`mpsc`) - mpsc sender to send message to ui
+/// - `input_tx` (`mpsc`) - mpsc sender of the keys to the open popup, see [`UiMessage::Edit`]
 /// - `appstate` (`Arc<Mutex<AppState>>`) - the state of the app
-///
-/// # Examples
-///
-/// ```no_run
-/// use crate::app::handle_msg;
-///
-/// async {
-///   let result = handle_msg().await;
-/// };
-/// ```
 async fn handle_msg(
     mut rx: mpsc::Receiver<Message>,
     ui_tx: mpsc::Sender<UiMessage>,
+    input_tx: mpsc::Sender<KeyEvent>,
     appstate: Arc<Mutex<AppState>>,
 ) {
     loop {
-        match rx.recv().await.unwrap_or(Message::Exit) {
-            Message::Exit => {
-                let mut apps = appstate.lock().unwrap();
-                apps.exit = true;
-                break;
-            }
-            Message::AddItem => {
-                let mut apps = appstate.lock().unwrap();
-                match apps.current_focus {
-                    CurrentFocus::Workspace => {
-                        apps.current_mode = CurrentMode::Insert;
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::AddWorkspace))
-                            .await;
-                    }
-                    CurrentFocus::TodoList => {
-                        apps.current_mode = CurrentMode::Insert;
-                        let _ = ui_tx.send(UiMessage::WAction(WidgetAction::AddTask)).await;
-                    }
-                    _ => {}
-                }
-            }
-            Message::AddChild => {
-                let mut apps = appstate.lock().unwrap();
-                match apps.current_focus {
-                    CurrentFocus::Workspace => {
-                        apps.current_mode = CurrentMode::Insert;
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::AddWorkspaceChild))
-                            .await;
-                    }
-                    CurrentFocus::TodoList => {
-                        apps.current_mode = CurrentMode::Insert;
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::AddTaskChild))
-                            .await;
-                    }
-                    _ => {}
-                }
-            }
-            Message::ChangeMode(mode) => {
-                let mut apps = appstate.lock().unwrap();
-                apps.current_mode = mode;
-            }
-            Message::ChangeFocus(focus) => {
-                let mut apps = appstate.lock().unwrap();
-                apps.current_focus = focus.clone();
-                let _ = ui_tx
-                    .send(match focus {
-                        CurrentFocus::Workspace => UiMessage::WAction(WidgetAction::FocusWorkspace),
-                        CurrentFocus::TodoList => UiMessage::WAction(WidgetAction::FocusTodolist),
-                        CurrentFocus::ArchivedWorkspace => {
-                            UiMessage::WAction(WidgetAction::FocusArchivedWorkspace)
-                        }
-                    })
-                    .await;
-            }
-            Message::SelectWorkspace => {
-                let app_state = appstate.lock().unwrap();
-                match app_state.current_focus {
-                    CurrentFocus::Workspace => {
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::EnterWorkspace))
-                            .await;
-                    }
-                    CurrentFocus::ArchivedWorkspace => {
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::EnterArchivedWorkspace))
-                            .await;
-                    }
-                    _ => {}
-                }
-            }
-            Message::MoveUp => {
-                let _ = ui_tx.send(UiMessage::WAction(WidgetAction::SelectUp)).await;
-            }
-            Message::MoveDown => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::SelectDown))
-                    .await;
-            }
-            Message::Update => {
-                let _ = ui_tx.send(UiMessage::UpdateUi).await;
-            }
-            Message::DeleteItem => {
-                let mut apps = appstate.lock().unwrap();
-                apps.current_mode = CurrentMode::Insert;
-                match apps.current_focus {
-                    CurrentFocus::Workspace => {
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::DeleteWorkspace))
-                            .await;
-                    }
-                    CurrentFocus::TodoList => {
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::DeleteTask))
-                            .await;
-                    }
-                    CurrentFocus::ArchivedWorkspace => {
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::DeleteArchivedWorkspace))
-                            .await;
-                    }
-                }
-            }
-            Message::Archive => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::ArchiveWS))
-                    .await;
-            }
-            Message::Recovery => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::RecoveryWS))
-                    .await;
-            }
-            Message::Complete => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::MarkTaskStatus(
-                        TaskStatus::Finished,
-                    )))
-                    .await;
-            }
-            Message::InProcess => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::MarkTaskStatus(
-                        TaskStatus::InProcess,
-                    )))
-                    .await;
-            }
-            Message::Todo => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::MarkTaskStatus(
-                        TaskStatus::Todo,
-                    )))
-                    .await;
-            }
-            Message::Deprecated => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::MarkTaskStatus(
-                        TaskStatus::Deprecated,
-                    )))
-                    .await;
-            }
-            Message::Rename => {
-                let mut app_state = appstate.lock().unwrap();
-                app_state.current_mode = CurrentMode::Insert;
-                match app_state.current_focus {
-                    CurrentFocus::Workspace => {
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::Rename(
-                                CurrentFocus::Workspace,
-                            )))
-                            .await;
-                    }
-                    CurrentFocus::TodoList => {
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::Rename(
-                                CurrentFocus::TodoList,
-                            )))
-                            .await;
-                    }
-                    CurrentFocus::ArchivedWorkspace => {
-                        let _ = ui_tx
-                            .send(UiMessage::WAction(WidgetAction::Rename(
-                                CurrentFocus::ArchivedWorkspace,
-                            )))
-                            .await;
-                    }
+        let msg = rx.recv().await.unwrap_or(Message::Exit);
+        let (ui_msgs, change) = reduce(msg, &appstate.lock().unwrap());
+        if let Some(change) = change {
+            change.apply(&mut appstate.lock().unwrap());
+        }
+        for ui_msg in ui_msgs {
+            // the popup holds the ui loop until it's closed, it reads its own key stream
+            match ui_msg {
+                UiMessage::Edit(key) => {
+                    let _ = input_tx.send(key).await;
                 }
-            }
-            Message::Filter => {
-                let mut app_state = appstate.lock().unwrap();
-                app_state.current_mode = CurrentMode::Insert;
-                drop(app_state);
-                let _ = ui_tx.send(UiMessage::WAction(WidgetAction::Filter)).await;
-            }
-            Message::SearchMsg(search_msg) => {
-                if let SearchEvent::Exit = search_msg {
-                    let mut app_state = appstate.lock().unwrap();
-                    app_state.current_mode = CurrentMode::Normal;
-                    drop(app_state);
-                    let _ = ui_tx
-                        .send(UiMessage::WAction(WidgetAction::ExitFilter))
-                        .await;
+                ui_msg => {
+                    let _ = ui_tx.send(ui_msg).await;
                 }
             }
-            Message::Help => {
-                let mut app_state = appstate.lock().unwrap();
-                app_state.current_mode = CurrentMode::Help;
-                drop(app_state);
-                let _ = ui_tx.send(UiMessage::WAction(WidgetAction::Help)).await;
-            }
-            Message::ExitHelp => {
-                let mut app_state = appstate.lock().unwrap();
-                app_state.current_mode = CurrentMode::Normal;
-                drop(app_state);
-                let _ = ui_tx.send(UiMessage::WAction(WidgetAction::ExitHelp)).await;
-            }
-            Message::Due => {
-                let _ = ui_tx.send(UiMessage::WAction(WidgetAction::Due)).await;
-            }
-            Message::SaveData => {
-                let _ = ui_tx.send(UiMessage::SaveData).await;
-            }
-            Message::IncreseUrgency => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::IncreseUrgency))
-                    .await;
-            }
-            Message::DecreseUrgency => {
-                let _ = ui_tx
-                    .send(UiMessage::WAction(WidgetAction::DecreseUrgency))
-                    .await;
-            }
-            Message::Sort => {
-                let _ = ui_tx.send(UiMessage::WAction(WidgetAction::Sort)).await;
+        }
+        if appstate.lock().unwrap().exit {
+            break;
+        }
+    }
+}
+
+/// Decide what a message does, without any I/O
+///
+/// This is the decision logic of `handle_msg`: the messages to send to the UI and the
+/// change to the app state, both depending only on the message and the current state.
+/// The state change is applied before the UI messages are sent.
+///
+/// # Arguments
+///
+/// - `msg` ([`Message`]) - the message from the keyevent handler
+/// - `state` (`&AppState`) - the current state of the app
+///
+/// # Returns
+///
+/// - `(Vec<UiMessage>, Option<StateChange>)` - the messages for the UI and the state change
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::{appstate::{AppState, CurrentFocus, CurrentMode, Message, StateChange}, reduce};
+/// use todo::app::ui::{UiMessage, WidgetAction};
+///
+/// let mut state = AppState::new();
+/// state.current_focus = CurrentFocus::TodoList;
+/// let (msgs, change) = reduce(Message::AddItem, &state);
+/// assert_eq!(msgs, vec![UiMessage::WAction(WidgetAction::AddTask)]);
+/// assert_eq!(change, Some(StateChange::Popup(CurrentMode::Insert)));
+/// ```
+pub fn reduce(msg: Message, state: &AppState) -> (Vec<UiMessage>, Option<StateChange>) {
+    let action = |action: WidgetAction| vec![UiMessage::WAction(action)];
+    // the popup takes the keys from now on, not once the UI gets to the action
+    let popup = |action: WidgetAction| {
+        let change = action.popup_mode().map(StateChange::Popup);
+        (vec![UiMessage::WAction(action)], change)
+    };
+    match msg {
+        Message::Exit => (vec![], Some(StateChange::Exit)),
+        Message::AddItem => match state.current_focus {
+            CurrentFocus::Workspace => popup(WidgetAction::AddWorkspace),
+            CurrentFocus::TodoList => popup(WidgetAction::AddTask),
+            CurrentFocus::ArchivedWorkspace => (vec![], None),
+        },
+        Message::AddChild => match state.current_focus {
+            CurrentFocus::Workspace => popup(WidgetAction::AddWorkspaceChild),
+            CurrentFocus::TodoList => popup(WidgetAction::AddTaskChild),
+            CurrentFocus::ArchivedWorkspace => (vec![], None),
+        },
+        Message::ChangeMode(mode) => (vec![], Some(StateChange::Mode(mode))),
+        Message::ChangeFocus(CurrentFocus::ArchivedWorkspace) if !state.archived_visible => {
+            (vec![], None)
+        }
+        Message::ChangeFocus(focus) => (
+            action(match focus {
+                CurrentFocus::Workspace => WidgetAction::FocusWorkspace,
+                CurrentFocus::TodoList => WidgetAction::FocusTodolist,
+                CurrentFocus::ArchivedWorkspace => WidgetAction::FocusArchivedWorkspace,
+            }),
+            Some(StateChange::Focus(focus)),
+        ),
+        Message::SelectWorkspace => match state.current_focus {
+            CurrentFocus::Workspace => (action(WidgetAction::EnterWorkspace), None),
+            CurrentFocus::ArchivedWorkspace => (action(WidgetAction::EnterArchivedWorkspace), None),
+            CurrentFocus::TodoList => (vec![], None),
+        },
+        Message::FocusBack => (action(WidgetAction::FocusBack), None),
+        Message::MoveUp => (action(WidgetAction::SelectUp), None),
+        Message::MoveDown => (action(WidgetAction::SelectDown), None),
+        Message::Update => (vec![UiMessage::UpdateUi], None),
+        Message::DeleteItem => popup(match state.current_focus {
+            CurrentFocus::Workspace => WidgetAction::DeleteWorkspace,
+            CurrentFocus::TodoList => WidgetAction::DeleteTask,
+            CurrentFocus::ArchivedWorkspace => WidgetAction::DeleteArchivedWorkspace,
+        }),
+        Message::Archive => popup(WidgetAction::ArchiveWS),
+        Message::Recovery => (action(WidgetAction::RecoveryWS), None),
+        Message::Complete => (
+            action(WidgetAction::MarkTaskStatus(TaskStatus::Finished)),
+            None,
+        ),
+        Message::InProcess => (
+            action(WidgetAction::MarkTaskStatus(TaskStatus::InProcess)),
+            None,
+        ),
+        Message::Todo => (action(WidgetAction::MarkTaskStatus(TaskStatus::Todo)), None),
+        // asks first when open subtasks are deprecated with the task
+        Message::Deprecated => popup(WidgetAction::MarkTaskStatus(TaskStatus::Deprecated)),
+        Message::Rename => popup(WidgetAction::Rename(state.current_focus.clone())),
+        Message::Filter => popup(WidgetAction::Filter),
+        Message::SearchMsg(SearchEvent::Exit) => (
+            action(WidgetAction::ExitFilter),
+            Some(StateChange::Mode(CurrentMode::Normal)),
+        ),
+        Message::SearchMsg(SearchEvent::Next) => (action(WidgetAction::SelectDown), None),
+        Message::SearchMsg(SearchEvent::Previous) => (action(WidgetAction::SelectUp), None),
+        Message::Help => (
+            action(WidgetAction::Help),
+            Some(StateChange::Mode(CurrentMode::Help)),
+        ),
+        Message::ExitHelp => (
+            action(WidgetAction::ExitHelp),
+            Some(StateChange::Mode(CurrentMode::Normal)),
+        ),
+        Message::NextHelpTab => (action(WidgetAction::NextHelpTab), None),
+        Message::PrevHelpTab => (action(WidgetAction::PrevHelpTab), None),
+        Message::Due => popup(WidgetAction::Due),
+        Message::DueInline => popup(WidgetAction::DueInline),
+        Message::DueMatching => popup(WidgetAction::DueMatching),
+        Message::WorkspaceStats => popup(WidgetAction::WorkspaceStats),
+        Message::TaskColor => popup(WidgetAction::TaskColor),
+        Message::EditNotes => popup(WidgetAction::EditNotes),
+        Message::SaveData => (vec![UiMessage::SaveData], None),
+        Message::IncreaseUrgency => match state.current_focus {
+            CurrentFocus::TodoList => (action(WidgetAction::IncreaseUrgency), None),
+            _ => (vec![], None),
+        },
+        Message::DecreaseUrgency => match state.current_focus {
+            CurrentFocus::TodoList => (action(WidgetAction::DecreaseUrgency), None),
+            _ => (vec![], None),
+        },
+        Message::Sort => match state.current_focus {
+            CurrentFocus::ArchivedWorkspace => (action(WidgetAction::ToggleArchivedOrder), None),
+            _ => popup(WidgetAction::Sort),
+        },
+        Message::ShiftUp | Message::ShiftDown => {
+            let bf = if msg == Message::ShiftUp {
+                SelectBF::Back
+            } else {
+                SelectBF::Forward
+            };
+            match state.current_focus {
+                CurrentFocus::Workspace => (
+                    action(WidgetAction::ShiftWorkspace(WorkspaceType::Normal, bf)),
+                    None,
+                ),
+                CurrentFocus::ArchivedWorkspace => (
+                    action(WidgetAction::ShiftWorkspace(WorkspaceType::Archived, bf)),
+                    None,
+                ),
+                CurrentFocus::TodoList => (vec![], None),
             }
         }
+        Message::Tour => (
+            action(WidgetAction::Tour),
+            Some(StateChange::Mode(CurrentMode::Tour)),
+        ),
+        Message::TourNext => (action(WidgetAction::TourNext), None),
+        Message::TourSkip => (action(WidgetAction::TourSkip), None),
+        Message::Messages => popup(WidgetAction::Messages),
+        Message::About => popup(WidgetAction::About),
+        Message::Jump(to) => (action(WidgetAction::Jump(to)), None),
+        Message::Fold(fold) => (action(WidgetAction::Fold(fold)), None),
+        Message::ToggleArchived => (action(WidgetAction::ToggleArchived), None),
+        Message::Triage => popup(WidgetAction::Triage),
+        Message::EnterTask => popup(WidgetAction::EnterTask),
+        Message::CopyWorkspace => (action(WidgetAction::CopyWorkspace), None),
+        Message::OpenLink => popup(WidgetAction::OpenLink),
+        Message::ClearDone => popup(WidgetAction::ClearDone),
+        Message::ToggleRowNumbers => (action(WidgetAction::ToggleRowNumbers), None),
+        Message::GoToRow => popup(WidgetAction::GoToRow),
+        Message::ImportFile => popup(WidgetAction::ImportFile),
+        Message::ToggleProtected => (action(WidgetAction::ToggleProtected), None),
+        Message::Palette => popup(WidgetAction::Palette),
+        Message::KeyHelp => popup(WidgetAction::KeyHelp),
+        Message::SearchAll => popup(WidgetAction::SearchAll),
+        Message::Heatmap => popup(WidgetAction::Heatmap),
+        Message::BannerJump => (action(WidgetAction::BannerJump), None),
+        Message::DismissBanner => (action(WidgetAction::DismissBanner), None),
+        Message::Prompt(desc) => (action(WidgetAction::Prompt(desc)), None),
+        Message::Edit(key) if state.in_popup() => (vec![UiMessage::Edit(key)], None),
+        // typed after its popup closed, e.g. cancelled, it isn't run as a command either
+        Message::Edit(_) => (vec![], None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// [`ui::Ui::handle_uimsg`] returns once the ui channel closes, so quitting must close it
+    /// while the minute tick is still waiting for its next tick
+    #[test]
+    fn the_ui_channel_closes_once_the_message_thread_exits() {
+        let (tx, rx) = mpsc::channel::<Message>(10);
+        let (ui_tx, mut ui_rx) = mpsc::channel::<UiMessage>(10);
+
+        let ui_tx_in_tick = ui_tx.downgrade();
+        let _tick_handle = std::thread::spawn(|| {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_time()
+                .build()
+                .unwrap();
+            rt.block_on(handle_tick(ui_tx_in_tick));
+        });
+        let msg_handle = std::thread::spawn(|| {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap();
+            let (input_tx, _) = mpsc::channel(1);
+            let appstate = Arc::new(Mutex::new(AppState::new()));
+            rt.block_on(handle_msg(rx, ui_tx, input_tx, appstate));
+        });
+        tx.blocking_send(Message::Exit).unwrap();
+        msg_handle.join().unwrap();
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let closed = rt.block_on(async {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while ui_rx.recv().await.is_some() {}
+            })
+            .await
+        });
+        assert!(
+            closed.is_ok(),
+            "the ui channel is still open after the exit"
+        );
+    }
+
+    /// The keys typed into a popup go through the message handler to the key stream of the
+    /// popup, not to the ui loop the popup holds
+    #[test]
+    fn popup_keys_are_handed_to_the_key_stream() {
+        let (tx, rx) = mpsc::channel::<Message>(10);
+        let (ui_tx, mut ui_rx) = mpsc::channel::<UiMessage>(10);
+        let (input_tx, mut input_rx) = mpsc::channel::<KeyEvent>(10);
+        let appstate = Arc::new(Mutex::new(AppState::new()));
+        appstate.lock().unwrap().current_focus = CurrentFocus::TodoList;
+        let key = KeyEvent::from(event::KeyCode::Char('x'));
+
+        tx.blocking_send(Message::AddItem).unwrap();
+        tx.blocking_send(Message::Edit(key)).unwrap();
+        tx.blocking_send(Message::Exit).unwrap();
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        rt.block_on(handle_msg(rx, ui_tx, input_tx, appstate));
+
+        assert_eq!(
+            ui_rx.try_recv(),
+            Ok(UiMessage::WAction(WidgetAction::AddTask))
+        );
+        assert!(ui_rx.try_recv().is_err());
+        assert_eq!(input_rx.try_recv(), Ok(key));
     }
 }
@@ -0,0 +1,107 @@
+//! What the binary is, for `todo --version` and the about popup
+//!
+//! The version comes from the crate, the commit is embedded by the build script when the
+//! binary is built from a git checkout, and [`DATA_FORMAT_VERSION`] tells which layout of the
+//! data file the binary reads and writes. The about popup, `V`, shows them with the data file
+//! and the [`Profile`] the application runs with, for bug reports.
+
+use std::fmt;
+
+use crate::app::data::DataPathSource;
+
+/// The version of the layout of the data file
+///
+/// - `1` - the archived workspaces are saved inside `data.json`
+/// - `2` - they are saved apart in `archive.json`, see [`data::archive_path`]
+///
+/// [`data::archive_path`]: crate::app::data::archive_path
+pub const DATA_FORMAT_VERSION: u32 = 2;
+
+/// The version, commit and data format of the running binary
+///
+/// # Fields
+///
+/// - `version` (`&'static str`) - the version of the crate
+/// - `commit` (`Option<&'static str>`) - the short hash of the git commit built, None when
+///   built outside of a git checkout
+/// - `data_format` (`u32`) - see [`DATA_FORMAT_VERSION`]
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::about::BuildInfo;
+///
+/// let info = BuildInfo {
+///     version: "1.2.0",
+///     commit: Some("0123abcd"),
+///     data_format: 2,
+/// };
+/// assert_eq!(info.version_text(), "todo 1.2.0 (0123abcd, data format 2)");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub commit: Option<&'static str>,
+    pub data_format: u32,
+}
+
+impl BuildInfo {
+    /// The information of the running binary
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            commit: option_env!("TODO_GIT_COMMIT"),
+            data_format: DATA_FORMAT_VERSION,
+        }
+    }
+
+    /// The commit, or `unknown` when it wasn't embedded
+    pub fn commit_or_unknown(&self) -> &'static str {
+        self.commit.unwrap_or("unknown")
+    }
+
+    /// The line printed by `todo --version`, without a line break at the end
+    pub fn version_text(&self) -> String {
+        format!(
+            "todo {} ({}, data format {})",
+            self.version,
+            self.commit_or_unknown(),
+            self.data_format
+        )
+    }
+}
+
+/// Where the data of the running application comes from
+///
+/// # Variants
+///
+/// - `Data(DataPathSource)` - the data file, found as the [`DataPathSource`] says
+/// - `Demo` - the demo dataset of `--demo`, saved to a temporary file
+/// - `Stress` - the generated dataset of `--stress`, saved to a temporary file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Data(DataPathSource),
+    Demo,
+    Stress,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile::Data(DataPathSource::Home)
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Profile::Data(DataPathSource::Env) => "data file set by TODO_DATA_PATH",
+            Profile::Data(DataPathSource::Config) => "data file picked in config.json",
+            Profile::Data(DataPathSource::Home) => "data file in the home directory",
+            Profile::Data(DataPathSource::DataDir) => "data file in the data directory",
+            Profile::Data(DataPathSource::CurrentDir) => "data file in the current directory",
+            Profile::Demo => "demo dataset",
+            Profile::Stress => "generated dataset",
+        };
+        write!(f, "{}", label)
+    }
+}
@@ -3,7 +3,13 @@
 //! This module defines the core state structures that control the application's behavior,
 //! including focus management, mode states, and message passing between components.
 
-use crate::app::ui::SearchEvent;
+use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
+
+use crate::app::ui::{
+    SearchEvent,
+    tree::{Fold, Jump},
+};
 
 /// Structure for app state
 ///
@@ -16,11 +22,15 @@ use crate::app::ui::SearchEvent;
 /// - `current_focus` ([`CurrentFocus`]) - state which is focused
 /// - `current_mode` ([`CurrentMode`]) - state which mode is active
 /// - `exit` (`bool`) - whether the app should exit
+/// - `archived_visible` (`bool`) - whether the archived panel is shown and can take the focus
+/// - `recording` (`Option<char>`) - the register a macro is being recorded into
+/// - `banner` (`bool`) - whether the banner of a due task is shown
+/// - `resume_mode` ([`CurrentMode`]) - the mode to go back to when the open popup closes
 ///
 /// # Examples
 ///
 /// ```
-/// use crate::app::appstate::AppState;
+/// use todo::app::appstate::AppState;
 ///
 /// let appstate = AppState::new();
 /// ```
@@ -32,6 +42,14 @@ pub struct AppState {
     pub current_mode: CurrentMode,
     /// Flag indicating whether the application should exit
     pub exit: bool,
+    /// Whether the archived panel is shown, kept up to date by the UI
+    pub archived_visible: bool,
+    /// The register a macro is being recorded into, kept up to date by the key handler
+    pub recording: Option<char>,
+    /// Whether the banner of a due task is shown, kept up to date by the UI
+    pub banner: bool,
+    /// The mode the open popup was opened from, see [`AppState::open_popup`]
+    pub resume_mode: CurrentMode,
 }
 
 impl AppState {
@@ -41,6 +59,9 @@ impl AppState {
     /// - Focus on the Workspace component
     /// - Normal mode
     /// - Exit flag set to false
+    /// - The archived panel shown
+    /// - No macro being recorded
+    /// - No banner shown
     ///
     /// # Returns
     ///
@@ -49,11 +70,11 @@ impl AppState {
     /// # Examples
     ///
     /// ```
-    /// use crate::app::appstate::AppState;
+    /// use todo::app::appstate::AppState;
     ///
     /// let appstate = AppState::new();
-    /// assert_eq!(appstate.current_focus, crate::app::appstate::CurrentFocus::Workspace);
-    /// assert_eq!(appstate.current_mode, crate::app::appstate::CurrentMode::Normal);
+    /// assert_eq!(appstate.current_focus, todo::app::appstate::CurrentFocus::Workspace);
+    /// assert_eq!(appstate.current_mode, todo::app::appstate::CurrentMode::Normal);
     /// assert_eq!(appstate.exit, false);
     /// ```
     pub fn new() -> Self {
@@ -61,6 +82,55 @@ impl AppState {
             current_focus: CurrentFocus::Workspace,
             current_mode: CurrentMode::Normal,
             exit: false,
+            archived_visible: true,
+            recording: None,
+            banner: false,
+            resume_mode: CurrentMode::Normal,
+        }
+    }
+
+    /// Whether a popup is open and takes the keys
+    pub fn in_popup(&self) -> bool {
+        matches!(self.current_mode, CurrentMode::Insert | CurrentMode::Sort)
+    }
+
+    /// Enter the mode of a popup, remembering the mode to resume when it closes
+    ///
+    /// Opening a popup while one is open keeps the mode remembered by the first, so a
+    /// popup opened from another one, like an action picked in the palette, still goes back
+    /// to the mode the user was in.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`AppState`])
+    /// - `mode` ([`CurrentMode`]) - `Insert`, or `Sort` for the sort popup
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::appstate::{AppState, CurrentMode};
+    ///
+    /// let mut appstate = AppState::new();
+    /// appstate.current_mode = CurrentMode::Search;
+    /// appstate.open_popup(CurrentMode::Insert);
+    /// appstate.open_popup(CurrentMode::Insert);
+    /// appstate.close_popup();
+    /// assert_eq!(appstate.current_mode, CurrentMode::Search);
+    /// ```
+    pub fn open_popup(&mut self, mode: CurrentMode) {
+        if !self.in_popup() {
+            self.resume_mode = self.current_mode;
+        }
+        self.current_mode = mode;
+    }
+
+    /// Leave the mode of the popup for the mode it was opened from
+    ///
+    /// Does nothing if no popup is open, e.g. when the action of the popup already switched
+    /// to another mode like the help.
+    pub fn close_popup(&mut self) {
+        if self.in_popup() {
+            self.current_mode = self.resume_mode;
         }
     }
 }
@@ -77,7 +147,7 @@ impl Default for AppState {
     /// # Examples
     ///
     /// ```
-    /// use crate::app::appstate::AppState;
+    /// use todo::app::appstate::AppState;
     ///
     /// let appstate = AppState::default();
     /// ```
@@ -86,6 +156,43 @@ impl Default for AppState {
     }
 }
 
+/// A change to the app state decided by [`reduce`](crate::app::reduce)
+///
+/// # Variants
+///
+/// - `Mode` - switch to another mode
+/// - `Popup` - open a popup, see [`AppState::open_popup`]
+/// - `Focus` - focus another component
+/// - `Exit` - exit the application
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateChange {
+    /// Switch to another mode
+    Mode(CurrentMode),
+    /// Open a popup taking the keys in the given mode
+    Popup(CurrentMode),
+    /// Focus another component
+    Focus(CurrentFocus),
+    /// Exit the application
+    Exit,
+}
+
+impl StateChange {
+    /// Apply the change to the app state
+    ///
+    /// # Arguments
+    ///
+    /// - `self` ([`StateChange`]) - the change to apply
+    /// - `state` (`&mut AppState`) - the state to change
+    pub fn apply(self, state: &mut AppState) {
+        match self {
+            StateChange::Mode(mode) => state.current_mode = mode,
+            StateChange::Popup(mode) => state.open_popup(mode),
+            StateChange::Focus(focus) => state.current_focus = focus,
+            StateChange::Exit => state.exit = true,
+        }
+    }
+}
+
 /// Message of the application
 ///
 /// Enum representing all possible messages that can be sent between components
@@ -98,16 +205,18 @@ impl Default for AppState {
 ///
 /// # Message Flow
 ///
-/// 1. User input (keypresses) are converted to Messages by the event handler
+/// 1. User input (keypresses) are converted to Messages by the event handler, the keys
+///    typed into a popup to [`Message::Edit`]
 /// 2. Messages are sent through a channel to the main application loop
 /// 3. The main loop processes each message and updates application state
-/// 4. UI updates are triggered based on the processed messages
+/// 4. UI updates are triggered based on the processed messages, and the keys of a popup
+///    are handed to it
 ///
 /// # Examples
 ///
 /// ```
-/// use crate::app::appstate::{Message, CurrentMode, CurrentFocus};
-/// use crate::app::ui::SearchEvent;
+/// use todo::app::appstate::{Message, CurrentMode, CurrentFocus};
+/// use todo::app::ui::SearchEvent;
 ///
 /// // Example messages that might be sent in the application
 /// let update_msg = Message::Update;
@@ -115,7 +224,7 @@ impl Default for AppState {
 /// let focus_msg = Message::ChangeFocus(CurrentFocus::TodoList);
 /// let search_msg = Message::SearchMsg(SearchEvent::Next);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Message {
     /// Request to update the application UI
     Update,
@@ -125,6 +234,8 @@ pub enum Message {
     ChangeFocus(CurrentFocus),
     /// Select a workspace to view its tasks
     SelectWorkspace,
+    /// Leave the todo list and focus the panel its workspace was entered from
+    FocusBack,
     /// Add a new item (workspace or task depending on context)
     AddItem,
     /// Add a child item (sub-workspace or sub-task)
@@ -159,19 +270,88 @@ pub enum Message {
     Help,
     /// Exit the help screen
     ExitHelp,
+    /// Show the next tab of the help screen
+    NextHelpTab,
+    /// Show the previous tab of the help screen
+    PrevHelpTab,
     /// Set due date for a task
     Due,
     /// Save application data to file
     SaveData,
 
-    /// Increse task urgency
-    IncreseUrgency,
+    /// Increase task urgency
+    IncreaseUrgency,
 
-    /// Decrese task urgency
-    DecreseUrgency,
+    /// Decrease task urgency
+    DecreaseUrgency,
 
-    /// Sort the task
+    /// Sort the task, or switch the order of the archived workspaces
     Sort,
+    /// Move the current workspace above its previous sibling
+    ShiftUp,
+    /// Move the current workspace below its next sibling
+    ShiftDown,
+
+    /// Start the guided tour
+    Tour,
+    /// Show the next card of the guided tour
+    TourNext,
+    /// Skip the rest of the guided tour
+    TourSkip,
+    /// Show the history of the prompt messages
+    Messages,
+    /// Show the version, the data file and the profile
+    About,
+    /// Show or hide the archived panel, overriding the auto-hide
+    ToggleArchived,
+    /// Move the selection to the parent, the first child or a sibling
+    Jump(Jump),
+    /// Collapse or expand the tasks or the workspaces of the focused panel
+    Fold(Fold),
+    /// Walk through the Inbox tasks one at a time
+    Triage,
+    /// Enter on a task: fold or unfold its subtasks
+    EnterTask,
+    /// Copy the pending tasks of the current workspace as a message
+    CopyWorkspace,
+    /// Open a link of the current task in the browser
+    OpenLink,
+    /// Delete the finished subtasks of the current task
+    ClearDone,
+    /// Switch the numbers before the rows of the task list
+    ToggleRowNumbers,
+    /// Go to a row of the task list by its number
+    GoToRow,
+    /// Add the tasks of a file to the todo list
+    ImportFile,
+    /// Protect the current workspace from deletion and archiving, or lift the protection
+    ToggleProtected,
+    /// Open the command palette
+    Palette,
+    /// List the keys of the focused panel, and run the one pressed
+    KeyHelp,
+    /// Search every workspace for tasks and workspace names
+    SearchAll,
+    /// Open the month view of the due dates
+    Heatmap,
+    /// Type the due date of the current task in its row
+    DueInline,
+    /// Set or shift the due date of every task matching the filter
+    DueMatching,
+    /// Show the vitals of the current workspace
+    WorkspaceStats,
+    /// Pick the color of the current task
+    TaskColor,
+    /// Write the notes of the current task
+    EditNotes,
+    /// Jump to the task of the due banner
+    BannerJump,
+    /// Hide the due banner
+    DismissBanner,
+    /// Show a message in the prompt, e.g. the feedback of a macro
+    Prompt(String),
+    /// A key typed into the popup open in Insert or Sort mode, see [`AppState::in_popup`]
+    Edit(KeyEvent),
 }
 
 /// State of which component is currently focused
@@ -185,7 +365,7 @@ pub enum Message {
 /// - `Workspace` - The main workspace list
 /// - `TodoList` - The task list for the selected workspace
 /// - `ArchivedWorkspace` - The list of archived workspaces
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CurrentFocus {
     /// Focus is on the main workspace list
     Workspace,
@@ -206,7 +386,9 @@ pub enum CurrentFocus {
 /// - `Insert` - Text input mode
 /// - `Search` - Search/filter mode
 /// - `Help` - Help screen display mode
-#[derive(Debug, Clone, Copy)]
+/// - `Sort` - Sort rule selection mode
+/// - `Tour` - First-run guided tour mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CurrentMode {
     /// Normal navigation mode where arrow keys move selection
     Normal,
@@ -218,4 +400,6 @@ pub enum CurrentMode {
     Help,
     /// Sort mode for displaying keybindings
     Sort,
+    /// Tour mode for the guided tour cards
+    Tour,
 }
@@ -0,0 +1,459 @@
+//! Integrity check of the data file
+//!
+//! `todo check` reads the data file and reports what would trip the application up later:
+//!
+//! - a due date which isn't a `%Y-%m-%d` date, which makes the whole file unreadable
+//! - a todo list whose workspace is neither active nor archived, its tasks are never shown
+//! - two workspaces or two tasks sharing an id, the second can't be selected or edited
+//! - a selection, the current workspace, todo list or task, naming an item which is gone
+//!
+//! The tasks and the sub workspaces are saved nested in their parent, so they can't lose it;
+//! the todo lists are the only items saved apart from what they belong to.
+//!
+//! The archive file, see [`archive_path`], is checked with the data file.
+//!
+//! `todo check --repair` clears the broken due dates, drops the orphan todo lists, gives the
+//! duplicates new ids and clears the dangling selections, after copying the data file and the
+//! archive file to [`backup_path`]. The same check runs on start up and warns in the prompt,
+//! see [`load`]: the broken due dates are cleared there too, and the application starts read
+//! only so the file isn't saved without them before `todo check --repair` backs it up. A file
+//! which isn't a save of the data at all stops the start up with an error. The duplicate ids
+//! quietly break the selection, so start up also offers to give them new ids, see
+//! [`repair_duplicates`], and the repaired data is saved as any change.
+//!
+//! [`archive_path`]: crate::app::data::archive_path
+
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fmt, fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::app::{
+    data::{self, Archive, Datas},
+    errors, parse,
+    sources::IdGen,
+    ui::{
+        SelectAction,
+        todolistwidget::TodoWidget,
+        workspacewidget::{Workspace, WorkspaceWidget},
+    },
+    visit::NO_WORKSPACE,
+};
+
+/// A broken invariant of the data
+///
+/// # Variants
+///
+/// - `BadDue` - a due date which doesn't parse, with the task and the saved text
+/// - `OrphanList` - a todo list whose workspace is gone, with the id and the count of tasks
+/// - `DuplicateWorkspace` - a workspace with the id of another one
+/// - `DuplicateTask` - a task with the id of another one, with the path of its workspace
+/// - `DanglingSelection` - a selection naming an item which is gone, with what it selects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    BadDue {
+        task: String,
+        due: String,
+    },
+    OrphanList {
+        workspace: Uuid,
+        tasks: usize,
+    },
+    DuplicateWorkspace {
+        id: Uuid,
+        desc: String,
+    },
+    DuplicateTask {
+        id: Uuid,
+        desc: String,
+        workspace: String,
+    },
+    DanglingSelection(String),
+}
+
+impl Problem {
+    /// Whether the problem is an id shared by two workspaces or two tasks, which start up
+    /// offers to repair, see [`repair_duplicates`]
+    pub fn is_duplicate(&self) -> bool {
+        matches!(
+            self,
+            Problem::DuplicateWorkspace { .. } | Problem::DuplicateTask { .. }
+        )
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::BadDue { task, due } => {
+                write!(f, "task '{}' is due on '{}', which isn't a date", task, due)
+            }
+            Problem::OrphanList { workspace, tasks } => write!(
+                f,
+                "the todo list of workspace {}, which doesn't exist, holds {} tasks",
+                workspace, tasks
+            ),
+            Problem::DuplicateWorkspace { id, desc } => {
+                write!(
+                    f,
+                    "workspace '{}' has the id {} of another workspace",
+                    desc, id
+                )
+            }
+            Problem::DuplicateTask {
+                id,
+                desc,
+                workspace,
+            } => write!(
+                f,
+                "task '{}' in {} has the id {} of another task",
+                desc, workspace, id
+            ),
+            Problem::DanglingSelection(what) => {
+                write!(f, "{} is selected but no longer exists", what)
+            }
+        }
+    }
+}
+
+/// Read the content of the data file, clearing the due dates which don't parse
+///
+/// A due date which isn't a date would fail the whole file, so they are found in the JSON
+/// before the data is built and cleared, to check the rest.
+///
+/// # Arguments
+///
+/// - `content` (`&str`) - the content of the data file
+///
+/// # Returns
+///
+/// - `Result<(Datas, Vec<Problem>), String>` - the data and the [`Problem::BadDue`] found, or
+///   why the content isn't a save of the data at all
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::check::{Problem, parse};
+/// use todo::app::data::{Datas, serialize_data};
+///
+/// let content = serialize_data(&Datas::default());
+/// let (_, problems) = parse(&content).unwrap();
+/// assert!(problems.is_empty());
+/// ```
+pub fn parse(content: &str) -> Result<(Datas, Vec<Problem>), String> {
+    parse_cleared(content)
+}
+
+/// Read the content of the archive file, clearing the due dates which don't parse, see
+/// [`parse()`]
+///
+/// # Arguments
+///
+/// - `content` (`&str`) - the content of the archive file
+///
+/// # Returns
+///
+/// - `Result<(Archive, Vec<Problem>), String>` - the archive and the [`Problem::BadDue`]
+///   found, or why the content isn't a save of the archive at all
+pub fn parse_archive(content: &str) -> Result<(Archive, Vec<Problem>), String> {
+    parse_cleared(content)
+}
+
+/// Load the data file on start up, see [`data::load_active`], clearing the due dates which
+/// don't parse rather than failing on them, see [`parse()`]
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file
+///
+/// # Returns
+///
+/// - `Result<(Datas, Vec<Problem>), errors::Errors>` - the data and the
+///   [`Problem::BadDue`] found
+///
+/// # Errors
+///
+/// Returns [`errors::Errors::LoadError`] if the file can't be read or isn't a save of the data
+/// at all
+pub fn load(path: &Path) -> Result<(Datas, Vec<Problem>), errors::Errors> {
+    if let Ok(datas) = data::load_active(path) {
+        return Ok((datas, Vec::new()));
+    }
+    let content = fs::read_to_string(path).map_err(|_| errors::Errors::LoadError)?;
+    let (mut datas, problems) = parse(&content).map_err(|_| errors::Errors::LoadError)?;
+    datas.archived_ws.unread = data::archive_path(path).exists();
+    Ok((datas, problems))
+}
+
+fn parse_cleared<T: DeserializeOwned>(content: &str) -> Result<(T, Vec<Problem>), String> {
+    let mut value: Value = serde_json::from_str(content).map_err(|err| err.to_string())?;
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+    clear_bad_dues(&mut value, &mut problems, &mut seen);
+    let parsed = serde_json::from_value(value).map_err(|err| err.to_string())?;
+    Ok((parsed, problems))
+}
+
+/// Clear every `due` of the JSON which isn't a date
+///
+/// The selected task is saved a second time next to its list, so a task is reported once,
+/// by its id.
+fn clear_bad_dues(value: &mut Value, problems: &mut Vec<Problem>, seen: &mut HashSet<String>) {
+    match value {
+        Value::Object(object) => {
+            let bad = match object.get("due") {
+                Some(Value::String(due)) => parse::iso_date(due).is_none().then(|| due.clone()),
+                Some(Value::Null) | None => None,
+                Some(other) => Some(other.to_string()),
+            };
+            if let Some(due) = bad {
+                let id = object.get("id").map(Value::to_string).unwrap_or_default();
+                if seen.insert(id) {
+                    let task = match object.get("desc") {
+                        Some(Value::String(desc)) => desc.clone(),
+                        _ => String::new(),
+                    };
+                    problems.push(Problem::BadDue { task, due });
+                }
+                object.insert("due".to_string(), Value::Null);
+            }
+            object
+                .values_mut()
+                .for_each(|value| clear_bad_dues(value, problems, seen));
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| clear_bad_dues(value, problems, seen)),
+        _ => {}
+    }
+}
+
+/// Check the invariants of loaded data, see the [module](self) documentation
+///
+/// # Arguments
+///
+/// - `datas` (`&Datas`) - the data
+///
+/// # Returns
+///
+/// - `Vec<Problem>` - the problems found, empty if the data is sound
+pub fn check_datas(datas: &Datas) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let active = WorkspaceWidget::get_flattened(&datas.workspace.workspaces);
+    let archived = WorkspaceWidget::get_flattened(&datas.archived_ws.workspaces);
+    let paths = datas.workspace_paths();
+
+    let mut ws_ids = HashSet::new();
+    for ws in active.iter().chain(archived.iter()) {
+        let ws = ws.borrow();
+        if !ws_ids.insert(ws.id) {
+            problems.push(Problem::DuplicateWorkspace {
+                id: ws.id,
+                desc: ws.desc.clone(),
+            });
+        }
+    }
+
+    let mut task_ids = HashSet::new();
+    for list in datas.todolist.todolists.iter() {
+        let list = list.borrow();
+        if !ws_ids.contains(&list.workspace) {
+            problems.push(Problem::OrphanList {
+                workspace: list.workspace,
+                tasks: TodoWidget::get_flattened(&list.tasks).len(),
+            });
+        }
+        let workspace = paths
+            .get(&list.workspace)
+            .map_or(NO_WORKSPACE, String::as_str);
+        for task in TodoWidget::get_flattened(&list.tasks) {
+            let task = task.borrow();
+            if !task_ids.insert(task.id) {
+                problems.push(Problem::DuplicateTask {
+                    id: task.id,
+                    desc: task.desc.clone(),
+                    workspace: workspace.to_string(),
+                });
+            }
+        }
+    }
+
+    for (selected, tree, panel) in [
+        (&datas.workspace.current_workspace, &active, "workspace"),
+        (
+            &datas.archived_ws.current_workspace,
+            &archived,
+            "archived workspace",
+        ),
+    ] {
+        if let Some(ws) = selected
+            && !contains(tree, ws.borrow().id)
+        {
+            problems.push(Problem::DanglingSelection(format!(
+                "the {} '{}'",
+                panel,
+                ws.borrow().desc
+            )));
+        }
+    }
+    if let Some(list) = &datas.todolist.current_todolist
+        && datas.todolist.list_of(list.borrow().workspace).is_none()
+    {
+        problems.push(Problem::DanglingSelection(format!(
+            "the todo list of workspace {}",
+            list.borrow().workspace
+        )));
+    }
+    for list in datas.todolist.todolists.iter() {
+        let list = list.borrow();
+        if let Some(task) = &list.current_task {
+            let id = task.borrow().id;
+            if !TodoWidget::get_flattened(&list.tasks)
+                .iter()
+                .any(|task| task.borrow().id == id)
+            {
+                let workspace = paths
+                    .get(&list.workspace)
+                    .map_or(NO_WORKSPACE, String::as_str);
+                problems.push(Problem::DanglingSelection(format!(
+                    "the task '{}' in {}",
+                    task.borrow().desc,
+                    workspace
+                )));
+            }
+        }
+    }
+    problems
+}
+
+fn contains(tree: &[Rc<RefCell<Workspace>>], id: Uuid) -> bool {
+    tree.iter().any(|ws| ws.borrow().id == id)
+}
+
+/// Repair what [`check_datas`] finds
+///
+/// The orphan todo lists are dropped with their tasks, every workspace or task after the
+/// first one with an id gets a new id, and the dangling selections are cleared. A workspace
+/// given a new id starts without a todo list, the list of the id stays with the first one.
+///
+/// # Arguments
+///
+/// - `datas` (`&mut Datas`) - the data to repair
+/// - `ids` (`&dyn IdGen`) - the source of the new ids
+pub fn repair(datas: &mut Datas, ids: &dyn IdGen) {
+    let active = WorkspaceWidget::get_flattened(&datas.workspace.workspaces);
+    let archived = WorkspaceWidget::get_flattened(&datas.archived_ws.workspaces);
+
+    let ws_ids: HashSet<Uuid> = active
+        .iter()
+        .chain(archived.iter())
+        .map(|ws| ws.borrow().id)
+        .collect();
+    datas
+        .todolist
+        .todolists
+        .retain(|list| ws_ids.contains(&list.borrow().workspace));
+    repair_duplicates(datas, ids);
+
+    for (widget, tree) in [
+        (&mut datas.workspace, &active),
+        (&mut datas.archived_ws, &archived),
+    ] {
+        if widget
+            .current_workspace
+            .as_ref()
+            .is_some_and(|ws| !contains(tree, ws.borrow().id))
+        {
+            widget.current_workspace = None;
+            widget.ws_state.select(None);
+        }
+    }
+    if datas
+        .todolist
+        .current_todolist
+        .as_ref()
+        .is_some_and(|list| datas.todolist.list_of(list.borrow().workspace).is_none())
+    {
+        datas.todolist.current_todolist = None;
+    }
+    for list in datas.todolist.todolists.iter() {
+        let mut list = list.borrow_mut();
+        let dangling = list.current_task.as_ref().is_some_and(|task| {
+            let id = task.borrow().id;
+            !TodoWidget::get_flattened(&list.tasks)
+                .iter()
+                .any(|task| task.borrow().id == id)
+        });
+        if dangling {
+            list.current_task = None;
+            list.state.select(None);
+        }
+    }
+}
+
+/// Give every workspace or task after the first one with an id a new id, the part of
+/// [`repair`] run on start up once asked, see [`Problem::is_duplicate`]
+///
+/// A workspace given a new id starts without a todo list, the list of the id stays with the
+/// first one.
+///
+/// # Arguments
+///
+/// - `datas` (`&mut Datas`) - the data to repair
+/// - `ids` (`&dyn IdGen`) - the source of the new ids
+///
+/// # Returns
+///
+/// - `usize` - the number of workspaces and tasks given a new id
+pub fn repair_duplicates(datas: &mut Datas, ids: &dyn IdGen) -> usize {
+    let active = WorkspaceWidget::get_flattened(&datas.workspace.workspaces);
+    let archived = WorkspaceWidget::get_flattened(&datas.archived_ws.workspaces);
+    let tasks = datas
+        .todolist
+        .todolists
+        .iter()
+        .flat_map(|list| TodoWidget::get_flattened(&list.borrow().tasks));
+
+    let mut renewed = 0;
+    let mut ws_ids = HashSet::new();
+    for ws in active.iter().chain(archived.iter()) {
+        let mut ws = ws.borrow_mut();
+        if !ws_ids.insert(ws.id) {
+            ws.id = ids.next_id();
+            ws_ids.insert(ws.id);
+            renewed += 1;
+        }
+    }
+    let mut task_ids = HashSet::new();
+    for task in tasks {
+        let mut task = task.borrow_mut();
+        if !task_ids.insert(task.id) {
+            task.id = ids.next_id();
+            task_ids.insert(task.id);
+            renewed += 1;
+        }
+    }
+    renewed
+}
+
+/// Where `todo check --repair` copies the data file before writing the repaired data
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file
+///
+/// # Returns
+///
+/// - `PathBuf` - the data file with a `.bak` extension added, e.g. `data.json.bak`
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
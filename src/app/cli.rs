@@ -0,0 +1,235 @@
+//! Command line module
+//!
+//! This module parses the command line arguments of the application. The arguments are few
+//! and simple, so they are parsed by hand rather than pulling in an argument parser crate.
+
+use std::path::PathBuf;
+
+use crate::app::data::OnClash;
+
+/// The usage text printed for `--help` or an invalid argument
+pub const USAGE: &str = "\
+Usage: todo [OPTIONS]
+       todo diff SNAPSHOT [CURRENT]
+       todo serve --stdio
+       todo import-md FILE [--workspace NAME] [--keep-both]
+       todo import-txt FILE [--workspace NAME] [--keep-both]
+       todo add TEXT [--workspace NAME] [--multi]
+       todo check [--repair]
+
+Commands:
+  diff        print what changed from a copy of the data file to the data file, or to CURRENT
+  serve       answer JSON requests, one per line, on stdin and stdout
+  import-md   add the tasks of a Markdown checklist to a workspace, the Inbox by default,
+              --keep-both adds them to a new workspace beside the one named NAME instead
+  import-txt  add a task per line of a text file to a workspace, made if missing, the Inbox
+              by default, --keep-both as for import-md
+  add         add a task to a workspace, made if missing, the Inbox by default, --multi
+              splits TEXT into several tasks on the delimiter, ';' by default
+  check       look for broken data in the data file, --repair fixes it after a backup
+
+Options:
+  --demo          start with a generated demo dataset, the real data file is not touched
+  --plain         spell the statuses out and draw ASCII borders, for screen readers
+  --search QUERY  start on the first task of any workspace matching QUERY
+  --filter QUERY  start with the todo list of the open workspace filtered by QUERY
+  -V, --version   print the version, the git commit and the data format version
+  -h, --help      print this help
+";
+
+/// The options given on the command line
+///
+/// # Fields
+///
+/// - `demo` (`bool`) - start with the demo dataset and save to a temporary file
+/// - `help` (`bool`) - print the usage and exit
+/// - `version` (`bool`) - print the [build information](crate::app::about::BuildInfo) and exit
+/// - `plain` (`bool`) - draw with the [`PLAIN`](crate::app::ui::glyphs::PLAIN) glyphs
+/// - `stress` (`Option<usize>`) - start with a generated dataset of this many tasks, hidden
+///   from the usage as it is only meant to feel the latency of large datasets
+/// - `diff` (`Option<(PathBuf, Option<PathBuf>)>`) - print the changes from a snapshot to the
+///   data file, or to another file, instead of starting
+/// - `serve` (`bool`) - answer JSON requests on stdin and stdout instead of starting, see
+///   [`serve`](crate::app::serve)
+/// - `import_md` (`Option<(PathBuf, Option<String>)>`) - add the tasks of a Markdown
+///   checklist to a workspace instead of starting, see [`markdown`](crate::app::markdown)
+/// - `import_txt` (`Option<(PathBuf, Option<String>)>`) - add the lines of a plain text list
+///   to a workspace instead of starting, see [`plaintext`](crate::app::plaintext)
+/// - `on_clash` ([`OnClash`]) - import into the named workspace, or into a new one beside it
+/// - `add` (`Option<(String, Option<String>, bool)>`) - add the text as a task to a workspace
+///   instead of starting, as several tasks split on the delimiter if true, see
+///   [`plaintext::add`](crate::app::plaintext::add)
+/// - `check` (`Option<bool>`) - check the data file instead of starting, and repair it if
+///   true, see [`check`](crate::app::check)
+/// - `search` (`Option<String>`) - start on the first task matching these words, see
+///   [`Ui::search_tasks`](crate::app::ui::Ui::search_tasks)
+/// - `filter` (`Option<String>`) - start with the open todo list filtered by these words
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::cli::Cli;
+///
+/// let cli = Cli::parse(["--demo".to_string()]).unwrap();
+/// assert!(cli.demo);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Cli {
+    /// Start with the demo dataset and save to a temporary file
+    pub demo: bool,
+    /// Print the usage and exit
+    pub help: bool,
+    /// Print the version, the git commit and the data format version, and exit
+    pub version: bool,
+    /// Draw with spelled out statuses, ASCII borders and a marker on the selected row
+    pub plain: bool,
+    /// Start with a generated dataset of this many tasks and save to a temporary file
+    pub stress: Option<usize>,
+    /// Compare a snapshot to the data file, or to the second path, and exit
+    pub diff: Option<(PathBuf, Option<PathBuf>)>,
+    /// Answer JSON requests on stdin and stdout until stdin ends
+    pub serve: bool,
+    /// Import a Markdown checklist into a workspace, or the Inbox, and exit
+    pub import_md: Option<(PathBuf, Option<String>)>,
+    /// Import a plain text list into a workspace, or the Inbox, and exit
+    pub import_txt: Option<(PathBuf, Option<String>)>,
+    /// Import into a new workspace beside the one named, rather than into it
+    pub on_clash: OnClash,
+    /// Add a task, or the tasks split from the text if true, to a workspace, and exit
+    pub add: Option<(String, Option<String>, bool)>,
+    /// Check the data file, and repair it if true, and exit
+    pub check: Option<bool>,
+    /// Start on the first task matching these words
+    pub search: Option<String>,
+    /// Start with the open todo list filtered by these words
+    pub filter: Option<String>,
+}
+
+impl Cli {
+    /// Parse the arguments, without the program name
+    ///
+    /// # Arguments
+    ///
+    /// - `args` (`impl IntoIterator<Item = String>`) - the arguments to parse
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Cli, String>` - the options, or a message describing the invalid argument
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Cli, String> {
+        let mut cli = Cli::default();
+        let mut args = args.into_iter().peekable();
+        if args.peek().is_some_and(|arg| arg == "diff") {
+            args.next();
+            let Some(snapshot) = args.next() else {
+                return Err("'diff' expects the path of a snapshot".to_string());
+            };
+            let current = args.next().map(PathBuf::from);
+            if let Some(arg) = args.next() {
+                return Err(format!("unexpected argument '{}' after 'diff'", arg));
+            }
+            cli.diff = Some((PathBuf::from(snapshot), current));
+            return Ok(cli);
+        }
+        if args.peek().is_some_and(|arg| arg == "serve") {
+            args.next();
+            match args.next().as_deref() {
+                Some("--stdio") => {}
+                Some(arg) => return Err(format!("unknown transport '{}' for 'serve'", arg)),
+                None => return Err("'serve' expects '--stdio'".to_string()),
+            }
+            if let Some(arg) = args.next() {
+                return Err(format!("unexpected argument '{}' after 'serve'", arg));
+            }
+            cli.serve = true;
+            return Ok(cli);
+        }
+        if let Some(command) = args.next_if(|arg| arg == "import-md" || arg == "import-txt") {
+            let (mut file, mut workspace) = (None, None);
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--workspace" => match args.next() {
+                        Some(name) => workspace = Some(name),
+                        None => return Err("'--workspace' expects a name".to_string()),
+                    },
+                    "--keep-both" => cli.on_clash = OnClash::KeepBoth,
+                    _ if file.is_none() => file = Some(PathBuf::from(arg)),
+                    _ => {
+                        return Err(format!("unexpected argument '{}' after '{}'", arg, command));
+                    }
+                }
+            }
+            let Some(file) = file else {
+                let kind = match command.as_str() {
+                    "import-md" => "Markdown",
+                    _ => "text",
+                };
+                return Err(format!("'{}' expects the path of a {} file", command, kind));
+            };
+            match command.as_str() {
+                "import-md" => cli.import_md = Some((file, workspace)),
+                _ => cli.import_txt = Some((file, workspace)),
+            }
+            return Ok(cli);
+        }
+        if args.peek().is_some_and(|arg| arg == "add") {
+            args.next();
+            let (mut text, mut workspace, mut multi) = (None, None, false);
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--workspace" => match args.next() {
+                        Some(name) => workspace = Some(name),
+                        None => return Err("'--workspace' expects a name".to_string()),
+                    },
+                    "--multi" => multi = true,
+                    _ if text.is_none() => text = Some(arg),
+                    _ => return Err(format!("unexpected argument '{}' after 'add'", arg)),
+                }
+            }
+            let Some(text) = text else {
+                return Err("'add' expects the text of a task".to_string());
+            };
+            cli.add = Some((text, workspace, multi));
+            return Ok(cli);
+        }
+        if args.peek().is_some_and(|arg| arg == "check") {
+            args.next();
+            let repair = match args.next().as_deref() {
+                Some("--repair") => true,
+                Some(arg) => return Err(format!("unexpected argument '{}' after 'check'", arg)),
+                None => false,
+            };
+            if let Some(arg) = args.next() {
+                return Err(format!("unexpected argument '{}' after 'check'", arg));
+            }
+            cli.check = Some(repair);
+            return Ok(cli);
+        }
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--demo" => cli.demo = true,
+                "--plain" => cli.plain = true,
+                "-h" | "--help" => cli.help = true,
+                "-V" | "--version" => cli.version = true,
+                "--stress" => {
+                    let count = args.next().and_then(|n| n.parse().ok());
+                    if count.is_none() {
+                        return Err("'--stress' expects a number of tasks".to_string());
+                    }
+                    cli.stress = count;
+                }
+                "--search" | "--filter" => {
+                    let Some(query) = args.next().filter(|query| !query.is_empty()) else {
+                        return Err(format!("'{}' expects the words to look for", arg));
+                    };
+                    if arg == "--search" {
+                        cli.search = Some(query);
+                    } else {
+                        cli.filter = Some(query);
+                    }
+                }
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+        Ok(cli)
+    }
+}
@@ -0,0 +1,212 @@
+//! Configuration module
+//!
+//! This module handles the user configuration of the application, which is stored as a
+//! JSON file next to the data file. Every field has a default value, so a missing file or
+//! a file written by an older version of the application still loads.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::{
+    data, errors,
+    names::EmptyName,
+    ui::{
+        Autosave,
+        theme::ColorSupport,
+        todolistwidget::{DEFAULT_ABSOLUTE_DUE_AFTER_DAYS, EscalationPolicy, ParentDone},
+    },
+};
+
+/// The days without a change after which an open task is stale when not configured
+pub const DEFAULT_STALE_AFTER_DAYS: u32 = 30;
+
+/// The most task lines a workspace copied as a message holds when not configured
+pub const DEFAULT_SHARE_MAX_ITEMS: usize = 20;
+
+/// The delimiter between the tasks typed on one line when not configured
+pub const DEFAULT_TASK_DELIMITER: char = ';';
+
+/// The user configuration of the application
+///
+/// # Fields
+///
+/// - `tour_shown` (`bool`) - whether the first-run guided tour has already been shown
+/// - `ascii_glyphs` (`Option<bool>`) - draw ASCII glyphs instead of the Nerd Font icons, unset
+///   means the platform default, see [`ascii_by_default`](crate::app::ui::glyphs::ascii_by_default)
+/// - `auto_hide_archived` (`bool`) - hide the archived panel while there is nothing archived
+/// - `confirm_archive` (`bool`) - ask before archiving a workspace with open tasks
+/// - `stale_after_days` (`u32`) - the days without a change after which an open task is
+///   marked stale, 0 to never mark them
+/// - `show_task_age` (`bool`) - show how old the open tasks are in the task list
+/// - `absolute_due_after_days` (`u32`) - the days left past which a due date is shown as a
+///   date rather than counted, 0 to always count them
+/// - `escalate_overdue` (`Option<EscalationPolicy>`) - raise the urgency of the overdue tasks,
+///   unset to leave it alone
+/// - `plain` (`bool`) - draw for screen readers, as `--plain` does, see
+///   [`PLAIN`](crate::app::ui::glyphs::PLAIN)
+/// - `language` (`Option<String>`) - the translation of the interface, e.g. `zh` for
+///   `lang/zh.json` next to the configuration file, unset for English, see
+///   [`strings`](crate::app::ui::strings)
+/// - `terminal_title` (`bool`) - show the open workspace and the tasks due today in the title
+///   of the terminal, see the `title` module of the UI
+/// - `share_max_items` (`usize`) - the most task lines of a workspace copied as a message, 0
+///   for all of them, see [`share`](crate::app::share)
+/// - `color` (`Option<ColorSupport>`) - the colors the terminal shows, `none`, `16`, `256` or
+///   `truecolor`, unset to detect them, see [`theme`](crate::app::ui::theme)
+/// - `bell` (`bool`) - ring the bell of the terminal when a due task is announced, the overdue
+///   tasks are escalated or a save fails, see [`bell`](crate::app::ui::bell)
+/// - `task_delimiter` (`char`) - the delimiter between the tasks typed on one line, see
+///   [`plaintext::split_tasks`](crate::app::plaintext::split_tasks)
+/// - `data_path` (`Option<PathBuf>`) - the data file picked when the data directory wasn't
+///   writable, unset for the default, see [`data::data_path`]
+/// - `parent_done` ([`ParentDone`]) - what happens to a parent task when its last open
+///   subtask is finished, `off`, `auto` or `ask`
+/// - `empty_name` ([`EmptyName`]) - what Enter does when adding a workspace or a task with
+///   nothing typed, `cancel` or `generate` a numbered name
+/// - `autosave` ([`Autosave`]) - when the data is saved without `ctrl-s`, `off`, `on_blur`
+///   when leaving a panel or a todo list, or `interval` once a minute
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::config::Config;
+///
+/// let config = Config::default();
+/// assert!(!config.tour_shown);
+/// assert!(config.auto_hide_archived);
+/// assert!(config.confirm_archive);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Whether the first-run guided tour has already been shown
+    pub tour_shown: bool,
+    /// Draw ASCII glyphs instead of the Nerd Font icons, the platform default if unset
+    pub ascii_glyphs: Option<bool>,
+    /// Hide the archived panel while there is nothing archived
+    pub auto_hide_archived: bool,
+    /// Ask before archiving a workspace which still has open tasks
+    pub confirm_archive: bool,
+    /// The days without a change after which an open task is stale, 0 to turn it off
+    pub stale_after_days: u32,
+    /// Show the age of the open tasks between their description and due date
+    pub show_task_age: bool,
+    /// The days left past which a due date is shown as a date, 0 to always count them
+    pub absolute_due_after_days: u32,
+    /// Raise the urgency of the overdue tasks, off unless set
+    pub escalate_overdue: Option<EscalationPolicy>,
+    /// Spell the statuses out, draw ASCII borders and mark the selected row with `>`
+    pub plain: bool,
+    /// The translation of the interface, English if unset
+    pub language: Option<String>,
+    /// Show the open workspace and the tasks due today in the title of the terminal
+    pub terminal_title: bool,
+    /// The most task lines of a workspace copied as a message, 0 for all of them
+    pub share_max_items: usize,
+    /// The colors the terminal shows, detected from the environment if unset
+    pub color: Option<ColorSupport>,
+    /// Ring the bell of the terminal on the due reminders and the failed saves
+    pub bell: bool,
+    /// The delimiter between the tasks typed on one line
+    pub task_delimiter: char,
+    /// The data file to use instead of the one in the data directory
+    pub data_path: Option<PathBuf>,
+    /// What happens to a parent task when its last open subtask is finished
+    pub parent_done: ParentDone,
+    /// Cancel the add or generate a name when nothing is typed for a new workspace or task
+    pub empty_name: EmptyName,
+    /// When the data is saved without being asked for
+    pub autosave: Autosave,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            tour_shown: false,
+            ascii_glyphs: None,
+            auto_hide_archived: true,
+            confirm_archive: true,
+            stale_after_days: DEFAULT_STALE_AFTER_DAYS,
+            show_task_age: true,
+            absolute_due_after_days: DEFAULT_ABSOLUTE_DUE_AFTER_DAYS,
+            escalate_overdue: None,
+            plain: false,
+            language: None,
+            terminal_title: true,
+            share_max_items: DEFAULT_SHARE_MAX_ITEMS,
+            color: None,
+            bell: false,
+            task_delimiter: DEFAULT_TASK_DELIMITER,
+            data_path: None,
+            parent_done: ParentDone::Off,
+            empty_name: EmptyName::Cancel,
+            autosave: Autosave::Off,
+        }
+    }
+}
+
+/// Get the path of the configuration file
+///
+/// # Returns
+///
+/// - `PathBuf` - the path of `config.json` in the data directory, see [`data::data_dir`]
+pub fn config_path() -> PathBuf {
+    data::data_dir().0.join("config.json")
+}
+
+/// Get the translation file of a language, `lang/<language>.json` next to the configuration
+///
+/// # Arguments
+///
+/// - `language` (`&str`) - the name of the language, e.g. `zh`
+pub fn translation_path(language: &str) -> PathBuf {
+    data::data_dir()
+        .0
+        .join("lang")
+        .join(format!("{}.json", language))
+}
+
+/// Load the configuration from a specific file
+///
+/// A missing or unreadable file gives the default configuration, the configuration
+/// should never prevent the application from starting.
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - The file path from which to load the configuration
+///
+/// # Returns
+///
+/// - `Config` - The loaded configuration
+pub fn load_config(path: &Path) -> Config {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Save the configuration to a specific file
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - The file path where the configuration should be saved
+/// - `config` (`&Config`) - The configuration to save
+///
+/// # Errors
+///
+/// Returns [`errors::Errors::SaveError`] with the path and the OS error if the file can't be written
+pub fn save_config(path: &Path, config: &Config) -> Result<(), errors::Errors> {
+    let res = serde_json::to_string_pretty(config).unwrap();
+
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    fs::write(path, res).map_err(|err| errors::Errors::SaveError {
+        path: path.to_path_buf(),
+        reason: err.to_string(),
+    })
+}
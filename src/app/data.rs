@@ -3,16 +3,45 @@
 //! This module handles loading and saving application data to/from JSON files.
 //! It provides serialization and deserialization functionality for the main
 //! application data structures including workspaces, todo lists, and archived items.
+//!
+//! The ids of new tasks and workspaces come from an [`IdGen`](crate::app::sources::IdGen):
+//! `Task::new` and `Workspace::new` use random ids, while `Task::with_ids` and
+//! `Workspace::with_ids` accept any source, e.g. sequential ids to build fixtures in tests.
+//!
+//! There is no explicit order field: the order of every vector, the task lists and the
+//! children of each task or workspace, is the manual order, and serde writes and reads
+//! vectors in sequence. Sorting never moves a task, a todo list saves the rule it is shown
+//! sorted by next to its tasks and applies it when drawn, so what is loaded is rendered
+//! exactly as it was saved and the manual order is never lost.
+//!
+//! The archived workspaces and their todo lists are saved apart, in the [`Archive`] file next
+//! to the data file, see [`archive_path`]. They are rarely looked at and pile up over the
+//! years, so the application starts on the data file alone, see [`load_active`], and reads
+//! the archive the first time the archived panel is focused or a workspace is archived. A data
+//! file saved before the split still holds its archived workspaces, it's read as is and the
+//! next save moves them to the archive file.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::SystemTime,
+};
 
-use std::{fs, path::Path};
+use chrono::{Days, Local, NaiveDate};
 
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::app::{
-    errors,
+    config, errors, names,
     ui::{
-        todolistwidget::TodoWidget,
-        workspacewidget::{self, WorkspaceType, WorkspaceWidget},
+        SelectAction,
+        todolistwidget::{Task, TaskStatus, TodoList, TodoWidget, Urgency},
+        workspacewidget::{self, Workspace, WorkspaceType, WorkspaceWidget},
     },
 };
 
@@ -34,9 +63,9 @@ use crate::app::{
 /// # Examples
 ///
 /// ```
-/// use crate::app::data::Datas;
-/// use crate::app::ui::workspacewidget::{WorkspaceWidget, WorkspaceType};
-/// use crate::app::ui::todolistwidget::TodoWidget;
+/// use todo::app::data::Datas;
+/// use todo::app::ui::workspacewidget::{WorkspaceWidget, WorkspaceType};
+/// use todo::app::ui::todolistwidget::TodoWidget;
 ///
 /// let data = Datas {
 ///     workspace: WorkspaceWidget::new(WorkspaceType::Normal),
@@ -54,6 +83,220 @@ pub struct Datas {
     pub archived_ws: WorkspaceWidget,
 }
 
+/// The name of the Inbox workspace when it is created
+pub const INBOX_NAME: &str = "Inbox";
+
+impl Datas {
+    /// Make sure the Inbox workspace and its todo list exist
+    ///
+    /// The id of the Inbox is saved with the todo lists. If it is missing, or the workspace it
+    /// names is no longer among the active workspaces, a new Inbox is put first in the
+    /// workspace panel. The Inbox is always protected from deletion and archiving.
+    ///
+    /// # Returns
+    ///
+    /// - `Uuid` - the id of the Inbox workspace
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::data::Datas;
+    ///
+    /// let mut datas = Datas::default();
+    /// let inbox = datas.ensure_inbox();
+    /// assert_eq!(datas.ensure_inbox(), inbox);
+    /// assert!(datas.todolist.list_of(inbox).is_some());
+    /// ```
+    pub fn ensure_inbox(&mut self) -> Uuid {
+        let existing = self.todolist.inbox.filter(|id| {
+            WorkspaceWidget::get_flattened(&self.workspace.workspaces)
+                .iter()
+                .any(|ws| ws.borrow().id == *id)
+        });
+        let inbox = existing.unwrap_or_else(|| {
+            let ws = Workspace::new(INBOX_NAME.to_string());
+            let id = ws.id;
+            self.workspace
+                .workspaces
+                .insert(0, Rc::new(RefCell::new(ws)));
+            id
+        });
+        WorkspaceWidget::get_flattened(&self.workspace.workspaces)
+            .iter()
+            .filter(|ws| ws.borrow().id == inbox)
+            .for_each(|ws| ws.borrow_mut().protected = true);
+        if self.todolist.list_of(inbox).is_none() {
+            self.todolist
+                .add_list(Rc::new(RefCell::new(TodoList::new(inbox))));
+        }
+        self.todolist.inbox = Some(inbox);
+        inbox
+    }
+
+    /// Name every workspace by its path from the top level, e.g. `Work / Backend`
+    ///
+    /// The archived workspaces are named the same way with an ` [archived]` suffix, so a
+    /// workspace and its archived namesake never share a name.
+    ///
+    /// # Returns
+    ///
+    /// - `HashMap<Uuid, String>` - the path of every active and archived workspace by id
+    pub fn workspace_paths(&self) -> HashMap<Uuid, String> {
+        let mut paths = HashMap::new();
+        name_workspaces(&self.workspace.workspaces, "", "", &mut paths);
+        name_workspaces(&self.archived_ws.workspaces, "", " [archived]", &mut paths);
+        paths
+    }
+
+    /// Find an active workspace by its path, or by its own name if no other has it
+    ///
+    /// The path of a top level workspace is its name, so a top level workspace is found
+    /// before its namesakes deeper in the tree. A path or a name several workspaces share is
+    /// an error rather than a guess.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` (`&str`) - the path of the workspace, e.g. `Work / Backend`, or its name
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Uuid, String>` - the id of the workspace, or why none or several were found
+    pub fn find_workspace(&self, name: &str) -> Result<Uuid, String> {
+        let paths = self.workspace_paths();
+        let flattened = WorkspaceWidget::get_flattened(&self.workspace.workspaces);
+        let at: Vec<Uuid> = flattened
+            .iter()
+            .map(|ws| ws.borrow().id)
+            .filter(|id| paths[id] == name)
+            .collect();
+        match at.as_slice() {
+            [id] => return Ok(*id),
+            [] => {}
+            _ => return Err(format!("several workspaces are at '{}'", name)),
+        }
+        let named: Vec<Uuid> = flattened
+            .iter()
+            .filter(|ws| ws.borrow().desc == name)
+            .map(|ws| ws.borrow().id)
+            .collect();
+        match named.as_slice() {
+            [id] => Ok(*id),
+            [] => Err(format!("no workspace named '{}'", name)),
+            _ => Err(format!(
+                "several workspaces are named '{}', give its path instead",
+                name
+            )),
+        }
+    }
+
+    /// Find an active workspace as [`Datas::find_workspace`] does, or add it at the top level
+    /// with an empty todo list if no workspace has this path or name
+    ///
+    /// # Arguments
+    ///
+    /// - `name` (`&str`) - the path of the workspace, or its name
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Uuid, String>` - the id of the workspace, or why the name is ambiguous
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::data::Datas;
+    ///
+    /// let mut datas = Datas::default();
+    /// let reading = datas.find_or_add_workspace("Reading").unwrap();
+    /// assert_eq!(datas.find_or_add_workspace("Reading"), Ok(reading));
+    /// assert!(datas.todolist.list_of(reading).is_some());
+    /// ```
+    pub fn find_or_add_workspace(&mut self, name: &str) -> Result<Uuid, String> {
+        let named = WorkspaceWidget::get_flattened(&self.workspace.workspaces)
+            .iter()
+            .any(|ws| ws.borrow().desc == name);
+        if named || self.workspace_paths().values().any(|path| path == name) {
+            return self.find_workspace(name);
+        }
+        Ok(self.add_top_workspace(name.to_string()))
+    }
+
+    /// Add a top level workspace for an import kept beside its namesake, see
+    /// [`OnClash::KeepBoth`]
+    ///
+    /// The workspace is named by [`names::imported_name`] if a top level workspace has the
+    /// name already, see [`WorkspaceWidget::namesake`], and as given otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` (`&str`) - the name of the workspace to import into
+    ///
+    /// # Returns
+    ///
+    /// - `Uuid` - the id of the added workspace
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::data::Datas;
+    ///
+    /// let mut datas = Datas::default();
+    /// let website = datas.find_or_add_workspace("Website").unwrap();
+    /// let beside = datas.add_beside("Website");
+    /// assert_ne!(beside, website);
+    /// assert_eq!(datas.workspace_paths()[&beside], "Website (imported)");
+    /// ```
+    pub fn add_beside(&mut self, name: &str) -> Uuid {
+        let level = &self.workspace.workspaces;
+        let name = match WorkspaceWidget::namesake(level, name) {
+            Some(_) => names::imported_name(name, level.iter().map(|ws| ws.borrow().desc.clone())),
+            None => name.to_string(),
+        };
+        self.add_top_workspace(name)
+    }
+
+    /// Add a workspace at the top level with an empty todo list
+    fn add_top_workspace(&mut self, name: String) -> Uuid {
+        let ws = Workspace::new(name);
+        let id = ws.id;
+        self.workspace.add_workspace(Rc::new(RefCell::new(ws)));
+        self.todolist
+            .add_list(Rc::new(RefCell::new(TodoList::new(id))));
+        id
+    }
+}
+
+/// What an import does when the workspace it names is already there
+///
+/// # Variants
+///
+/// - `Merge` - the tasks are added at the end of its todo list
+/// - `KeepBoth` - the tasks go to a new top level workspace beside it, see
+///   [`Datas::add_beside`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnClash {
+    #[default]
+    Merge,
+    KeepBoth,
+}
+
+fn name_workspaces(
+    workspaces: &[Rc<RefCell<Workspace>>],
+    parent: &str,
+    suffix: &str,
+    paths: &mut HashMap<Uuid, String>,
+) {
+    for ws in workspaces {
+        let ws = ws.borrow();
+        let path = if parent.is_empty() {
+            ws.desc.clone()
+        } else {
+            format!("{} / {}", parent, ws.desc)
+        };
+        paths.insert(ws.id, format!("{}{}", path, suffix));
+        name_workspaces(&ws.children, &path, suffix, paths);
+    }
+}
+
 impl Default for Datas {
     fn default() -> Self {
         Self {
@@ -64,11 +307,432 @@ impl Default for Datas {
     }
 }
 
+/// The archived workspaces and their todo lists, saved apart from the data file, see the
+/// [module](self) documentation
+///
+/// # Fields
+///
+/// - `archived_ws` ([`WorkspaceWidget`]) - the archived workspaces
+/// - `todolists` (`Vec<Rc<RefCell<TodoList>>>`) - the todo lists of the archived workspaces
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Archive {
+    pub archived_ws: WorkspaceWidget,
+    pub todolists: Vec<Rc<RefCell<TodoList>>>,
+}
+
+impl Default for Archive {
+    fn default() -> Self {
+        Self {
+            archived_ws: WorkspaceWidget::new(WorkspaceType::Archived),
+            todolists: Vec::new(),
+        }
+    }
+}
+
+impl Archive {
+    /// Add the archived workspaces and their todo lists to the ones in memory
+    ///
+    /// # Arguments
+    ///
+    /// - `self` ([`Archive`]) - the archive read from the archive file
+    /// - `archived_ws` (`&mut WorkspaceWidget`) - the archived workspaces in memory, e.g. the
+    ///   ones of a data file saved before the split
+    /// - `todolist` (`&mut TodoWidget`) - the todo lists in memory
+    pub fn merge_into(self, archived_ws: &mut WorkspaceWidget, todolist: &mut TodoWidget) {
+        archived_ws.workspaces.extend(self.archived_ws.workspaces);
+        if archived_ws.current_workspace.is_none() {
+            archived_ws.current_workspace = self.archived_ws.current_workspace;
+        }
+        archived_ws.order = self.archived_ws.order;
+        archived_ws.saved_order.extend(self.archived_ws.saved_order);
+        archived_ws.arrange();
+        archived_ws.refresh_current();
+        archived_ws.unread = false;
+        todolist.todolists.extend(self.todolists);
+    }
+}
+
+/// Split the data into what the data file and the archive file hold
+///
+/// The todo lists go with their workspace. The archived panel keeps its focus in the data
+/// file, so the application starts on it, and an open todo list of an archived workspace is
+/// closed. Only the `Rc`s are copied.
+///
+/// # Arguments
+///
+/// - `datas` (`&Datas`) - all the data
+///
+/// # Returns
+///
+/// - `(Datas, Archive)` - the content of the data file and of the archive file
+pub fn split_archive(datas: &Datas) -> (Datas, Archive) {
+    let archived: HashSet<Uuid> = WorkspaceWidget::get_flattened(&datas.archived_ws.workspaces)
+        .iter()
+        .map(|ws| ws.borrow().id)
+        .collect();
+    let (archived_lists, active_lists): (Vec<_>, Vec<_>) = datas
+        .todolist
+        .todolists
+        .iter()
+        .cloned()
+        .partition(|list| archived.contains(&list.borrow().workspace));
+    let mut todolist = datas.todolist.clone();
+    todolist.todolists = active_lists;
+    if todolist
+        .current_todolist
+        .as_ref()
+        .is_some_and(|list| archived.contains(&list.borrow().workspace))
+    {
+        todolist.current_todolist = None;
+    }
+    let mut archived_ws = WorkspaceWidget::new(WorkspaceType::Archived);
+    archived_ws.focused = datas.archived_ws.focused;
+    let active = Datas {
+        workspace: datas.workspace.clone(),
+        todolist,
+        archived_ws,
+    };
+    let archive = Archive {
+        archived_ws: datas.archived_ws.clone(),
+        todolists: archived_lists,
+    };
+    (active, archive)
+}
+
+/// Get the path of the archive file of a data file
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file
+///
+/// # Returns
+///
+/// - `PathBuf` - `archive.json` next to `data.json`, `<name>.archive.json` next to any other
+///   `<name>.json`
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use todo::app::data::archive_path;
+///
+/// assert_eq!(archive_path(Path::new("/d/data.json")), Path::new("/d/archive.json"));
+/// assert_eq!(archive_path(Path::new("/d/work.json")), Path::new("/d/work.archive.json"));
+/// ```
+pub fn archive_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    if stem == "data" {
+        path.with_file_name("archive.json")
+    } else {
+        path.with_file_name(format!("{}.archive.json", stem))
+    }
+}
+
+/// Get the path of the lock file of a data file, see [`lock_data`]
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use todo::app::data::lock_path;
+///
+/// assert_eq!(lock_path(Path::new("/d/data.json")), Path::new("/d/data.json.lock"));
+/// ```
+pub fn lock_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}.lock", name))
+}
+
+/// Lock the data file against the other writers, `todo serve`, the commands changing it and
+/// other runs of the application, waiting for the one holding it
+///
+/// The lock is advisory, on the file [`lock_path`] next to the data file, and it's held until
+/// the file returned is dropped. A writer reads, checks and writes the data file under it, so
+/// no write slips in between.
+///
+/// # Errors
+///
+/// Returns the I/O error if the lock file can't be created or locked. A directory the lock
+/// file can't be created in can't take the data file either, so the writers go on without
+/// the lock and report the failed write instead.
+pub fn lock_data(path: &Path) -> std::io::Result<fs::File> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = fs::File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path(path))?;
+    file.lock()?;
+    Ok(file)
+}
+
+/// Serialize the archived workspaces to the JSON content of the archive file
+pub fn serialize_archive(archive: &Archive) -> String {
+    serde_json::to_string_pretty(archive).unwrap()
+}
+
+/// Read the archive file of a data file, see [`archive_path`]
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file
+///
+/// # Returns
+///
+/// - `Result<Archive, errors::Errors>` - the archive, empty if there is no archive file
+///
+/// # Errors
+///
+/// Returns [`errors::Errors::LoadError`] if the archive file can't be read or parsed
+pub fn read_archive(path: &Path) -> Result<Archive, errors::Errors> {
+    let path = archive_path(path);
+    if !path.exists() {
+        return Ok(Archive::default());
+    }
+    let content = fs::read_to_string(path).map_err(|_| errors::Errors::LoadError)?;
+    serde_json::from_str(&content).map_err(|_| errors::Errors::LoadError)
+}
+
+/// Build a sample workspace with a few example tasks
+///
+/// It is offered at the end of the first-run guided tour, so a new user has something to
+/// play with right away.
+///
+/// # Returns
+///
+/// - `(Rc<RefCell<Workspace>>, Rc<RefCell<TodoList>>)` - the workspace and its todo list
+pub fn sample_workspace() -> (Rc<RefCell<Workspace>>, Rc<RefCell<TodoList>>) {
+    let ws = Rc::new(RefCell::new(Workspace::new("Getting Started".to_string())));
+    let mut todolist = TodoList::new(ws.borrow().id);
+    let today = Local::now().date_naive();
+
+    let explore = Rc::new(RefCell::new(Task::new(
+        "Explore the todo app".to_string(),
+        today.checked_add_days(Days::new(3)),
+    )));
+    let navigate = Task::new("Move around with j/k".to_string(), None);
+    let mut status = Task::new("Mark a task done with c".to_string(), None);
+    status.status = TaskStatus::Finished;
+    explore
+        .borrow_mut()
+        .add_child(Rc::new(RefCell::new(navigate)));
+    explore
+        .borrow_mut()
+        .add_child(Rc::new(RefCell::new(status)));
+    explore.borrow_mut().status = TaskStatus::InProcess;
+    todolist.add_task(explore);
+    todolist.add_task(Rc::new(RefCell::new(Task::new(
+        "Set a due date with D".to_string(),
+        Some(today),
+    ))));
+    todolist.add_task(Rc::new(RefCell::new(Task::new(
+        "Press ? to read the help page".to_string(),
+        None,
+    ))));
+
+    (ws, Rc::new(RefCell::new(todolist)))
+}
+
+/// Build a realistic in-memory dataset for screenshots, manual QA and tests
+///
+/// The dataset holds three nested workspaces with a mix of task statuses, urgencies and
+/// due dates spread across the color thresholds of the todo list, plus an archived workspace.
+/// It is used by `todo --demo`, which saves to a temporary file instead of the real data file.
+///
+/// # Returns
+///
+/// - `Datas` - the demo dataset
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::data::demo_datas;
+///
+/// let datas = demo_datas();
+/// assert_eq!(datas.workspace.workspaces.len(), 1);
+/// ```
+pub fn demo_datas() -> Datas {
+    let today = Local::now().date_naive();
+    let due = |days: i64| {
+        if days < 0 {
+            today.checked_sub_days(Days::new(days.unsigned_abs()))
+        } else {
+            today.checked_add_days(Days::new(days as u64))
+        }
+    };
+    let task = |desc: &str, status: TaskStatus, due: Option<NaiveDate>| {
+        let mut task = Task::new(desc.to_string(), due);
+        task.status = status;
+        Rc::new(RefCell::new(task))
+    };
+    let mut datas = Datas::default();
+    let add_list = |ws: &Rc<RefCell<Workspace>>, tasks: Vec<Rc<RefCell<Task>>>| {
+        let mut todolist = TodoList::new(ws.borrow().id);
+        tasks.into_iter().for_each(|t| todolist.add_task(t));
+        Rc::new(RefCell::new(todolist))
+    };
+
+    let work = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+    let backend = Rc::new(RefCell::new(Workspace::new("Backend".to_string())));
+    let frontend = Rc::new(RefCell::new(Workspace::new("Frontend".to_string())));
+    let home = Rc::new(RefCell::new(Workspace::new("Home".to_string())));
+    let old = Rc::new(RefCell::new(Workspace::new("Website 2024".to_string())));
+
+    let release = task("Ship release 1.2", TaskStatus::InProcess, due(2));
+    release.borrow_mut().urgency = Some(Urgency::Critical);
+    [
+        task("Write changelog", TaskStatus::Finished, None),
+        task("Tag the release", TaskStatus::Todo, due(2)),
+        task("Announce on the forum", TaskStatus::Todo, due(3)),
+    ]
+    .into_iter()
+    .for_each(|t| release.borrow_mut().add_child(t));
+    let migrate = task("Migrate the database", TaskStatus::Todo, due(-2));
+    migrate.borrow_mut().urgency = Some(Urgency::Important);
+    [
+        task("Back up production", TaskStatus::Finished, None),
+        task("Run the migration script", TaskStatus::Todo, due(-2)),
+    ]
+    .into_iter()
+    .for_each(|t| migrate.borrow_mut().add_child(t));
+    let work_list = add_list(
+        &work,
+        vec![
+            task("Weekly report", TaskStatus::Todo, due(0)),
+            task("Plan the next sprint", TaskStatus::Todo, due(5)),
+            task("Old onboarding doc", TaskStatus::Deprecated, None),
+        ],
+    );
+    let backend_list = add_list(
+        &backend,
+        vec![
+            release,
+            migrate,
+            task("Fix flaky login test", TaskStatus::InProcess, due(1)),
+            task("Review caching PR", TaskStatus::Todo, due(10)),
+            task("Remove the v1 API", TaskStatus::Deprecated, None),
+        ],
+    );
+    let frontend_list = add_list(
+        &frontend,
+        vec![
+            task("Dark mode", TaskStatus::Finished, None),
+            task("Keyboard shortcuts page", TaskStatus::Todo, due(6)),
+            task("Fix layout on small screens", TaskStatus::InProcess, due(3)),
+        ],
+    );
+    let groceries = task("Groceries", TaskStatus::Todo, due(0));
+    ["Milk", "Bread", "Coffee"].into_iter().for_each(|d| {
+        groceries
+            .borrow_mut()
+            .add_child(task(d, TaskStatus::Todo, None))
+    });
+    let home_list = add_list(
+        &home,
+        vec![
+            groceries,
+            task("Pay the rent", TaskStatus::Finished, None),
+            task("Call the plumber", TaskStatus::Todo, due(-5)),
+            task("Book summer holidays", TaskStatus::Todo, due(30)),
+        ],
+    );
+    let old_list = add_list(
+        &old,
+        vec![
+            task("Redesign the landing page", TaskStatus::Finished, None),
+            task("Migrate to the new host", TaskStatus::Finished, None),
+        ],
+    );
+
+    backend.borrow_mut().add_child(frontend.clone());
+    work.borrow_mut().add_child(backend.clone());
+    work.borrow_mut().add_child(home.clone());
+    datas.workspace.add_workspace(work);
+    datas.archived_ws.add_workspace(old);
+    [work_list, backend_list, frontend_list, home_list, old_list]
+        .into_iter()
+        .for_each(|l| datas.todolist.add_list(l));
+
+    datas
+}
+
+/// Generate a large dataset with a given number of tasks
+///
+/// The tasks are spread over ten workspaces, half of them nested in the previous one, and
+/// every fifth task holds the next four as subtasks, so the flatten and render code sees
+/// a realistic tree. It is used by the benchmarks and the hidden `todo --stress N` flag.
+///
+/// # Arguments
+///
+/// - `tasks` (`usize`) - the number of tasks to generate
+///
+/// # Returns
+///
+/// - `Datas` - the generated dataset
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::data::generate_datas;
+///
+/// let datas = generate_datas(1000);
+/// assert_eq!(datas.todolist.todolists.len(), 10);
+/// ```
+pub fn generate_datas(tasks: usize) -> Datas {
+    const WORKSPACES: usize = 10;
+    let today = Local::now().date_naive();
+    let statuses = [
+        TaskStatus::Todo,
+        TaskStatus::InProcess,
+        TaskStatus::Finished,
+        TaskStatus::Deprecated,
+    ];
+    let mut datas = Datas::default();
+    let mut parent: Option<Rc<RefCell<Workspace>>> = None;
+
+    for w in 0..WORKSPACES {
+        let ws = Rc::new(RefCell::new(Workspace::new(format!("Workspace {}", w + 1))));
+        let mut todolist = TodoList::new(ws.borrow().id);
+        let mut group: Option<Rc<RefCell<Task>>> = None;
+        for t in (w..tasks).step_by(WORKSPACES) {
+            let due = today.checked_add_days(Days::new((t % 14) as u64));
+            let mut task = Task::new(format!("Task {} of workspace {}", t + 1, w + 1), due);
+            task.status = statuses[t % statuses.len()].clone();
+            let task = Rc::new(RefCell::new(task));
+            match &group {
+                Some(top) if !(t / WORKSPACES).is_multiple_of(5) => {
+                    top.borrow_mut().add_child(task)
+                }
+                _ => {
+                    todolist.add_task(task.clone());
+                    group = Some(task);
+                }
+            }
+        }
+        datas.todolist.add_list(Rc::new(RefCell::new(todolist)));
+
+        match parent.take() {
+            Some(p) if w % 2 == 1 => p.borrow_mut().add_child(ws),
+            _ => {
+                datas.workspace.add_workspace(ws.clone());
+                parent = Some(ws);
+            }
+        }
+    }
+
+    datas
+}
+
 /// Save the application data to a specific file
 ///
 /// Serializes the application data to JSON format and writes it to the specified file path.
 /// This function is used to persist the current state of the application including all
-/// workspaces, tasks, and archived items.
+/// workspaces, tasks, and archived items. The archived ones go to the archive file, see
+/// [`split_archive`], which is written first so a failed write leaves the data file as it was.
+/// It's left alone while it's unread, see [`load_active`], and not created while nothing is
+/// archived.
 ///
 /// # Arguments
 ///
@@ -81,31 +745,343 @@ impl Default for Datas {
 ///
 /// # Errors
 ///
-/// Returns [`errors::Errors::WriteError`] if there are issues writing to the file system
+/// Returns [`errors::Errors::SaveError`] with the path and the OS error if the file can't be written
 ///
 /// # Examples
 ///
 /// ```
-/// use std::path::Path;
-/// use crate::app::data::{Datas, save_data};
-///
-/// // Create sample data
-/// let datas = Datas::default();
+/// use todo::{Datas, load_data, save_data};
 ///
-/// // Save to a file (this would typically use a proper path)
-/// // let path = Path::new("/path/to/data.json");
-/// // let result = save_data(path, &datas);
+/// let path = std::env::temp_dir().join("todo-doc-save_data.json");
+/// save_data(&path, &Datas::default()).unwrap();
+/// assert!(load_data(&path).unwrap().todolist.todolists.is_empty());
+/// # std::fs::remove_file(&path).unwrap();
 /// ```
 pub fn save_data(path: &Path, datas: &Datas) -> Result<(), errors::Errors> {
-    let res = serde_json::to_string_pretty(datas).unwrap();
+    let archive = serialize_archive(&split_archive(datas).1);
+    if !datas.archived_ws.unread
+        && (!datas.archived_ws.workspaces.is_empty() || archive_path(path).exists())
+    {
+        write_data(&archive_path(path), &archive)?;
+    }
+    write_data(path, &serialize_data(datas))
+}
+
+/// The environment variable which overrides the path of the data file
+pub const DATA_PATH_ENV: &str = "TODO_DATA_PATH";
+
+/// Where the path of the data file comes from
+///
+/// # Variants
+///
+/// - `Env` - the [`DATA_PATH_ENV`] environment variable
+/// - `Home` - `.todo` in the home directory
+/// - `DataDir` - `todo` in the data directory of the platform, `%APPDATA%` on Windows
+/// - `Config` - the [`Config::data_path`] picked when the data directory wasn't writable
+/// - `CurrentDir` - `.todo` in the current directory, the last resort the user should be warned about
+///
+/// [`Config::data_path`]: crate::app::config::Config::data_path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataPathSource {
+    Env,
+    Config,
+    Home,
+    DataDir,
+    CurrentDir,
+}
+
+/// Get the directory of the data and configuration files
+///
+/// The directory is `.todo` in the home directory, or `todo` in the data directory of the
+/// platform if there is no home directory, or `.todo` in the current directory as a last resort.
+/// On Windows the data directory, the roaming `%APPDATA%`, comes first, unless a `.todo`
+/// directory was already created in the profile by an older version.
+///
+/// # Returns
+///
+/// - `(PathBuf, DataPathSource)` - the directory and where it comes from
+pub fn data_dir() -> (PathBuf, DataPathSource) {
+    #[cfg(windows)]
+    if let Some(dir) = dirs::data_dir()
+        && !dirs::home_dir().is_some_and(|home| home.join(".todo").is_dir())
+    {
+        return (dir.join("todo"), DataPathSource::DataDir);
+    }
+    if let Some(home) = dirs::home_dir() {
+        (home.join(".todo"), DataPathSource::Home)
+    } else if let Some(dir) = dirs::data_dir() {
+        (dir.join("todo"), DataPathSource::DataDir)
+    } else {
+        (PathBuf::from(".todo"), DataPathSource::CurrentDir)
+    }
+}
+
+/// Get the path of the data file, shared by loading and saving
+///
+/// The [`DATA_PATH_ENV`] environment variable wins if it is set, then the path saved in the
+/// configuration, see [`Config::data_path`], otherwise the file is `data.json` in [`data_dir`].
+///
+/// [`Config::data_path`]: crate::app::config::Config::data_path
+///
+/// # Returns
+///
+/// - `(PathBuf, DataPathSource)` - the path and where it comes from
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::data::{data_path, DataPathSource};
+///
+/// let (path, source) = data_path();
+/// if source == DataPathSource::CurrentDir {
+///     println!("no home directory, saving to {}", path.display());
+/// }
+/// ```
+pub fn data_path() -> (PathBuf, DataPathSource) {
+    match std::env::var_os(DATA_PATH_ENV) {
+        Some(path) if !path.is_empty() => (PathBuf::from(path), DataPathSource::Env),
+        _ => match config::load_config(&config::config_path()).data_path {
+            Some(path) => (path, DataPathSource::Config),
+            None => {
+                let (dir, source) = data_dir();
+                (dir.join("data.json"), source)
+            }
+        },
+    }
+}
+
+/// Check that the data file can be written, before anything is changed
+///
+/// A directory which exists but isn't writable otherwise goes unnoticed until the save on
+/// exit, so a probe file is created next to the data file and removed again, and an
+/// existing data file is opened for writing without touching it. The directory is created
+/// if it's missing.
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file
+///
+/// # Errors
+///
+/// Returns [`errors::Errors::SaveError`] with the path and the OS error of the first step
+/// which failed
+pub fn check_writable(path: &Path) -> Result<(), errors::Errors> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let failed = |path: &Path| {
+        let path = path.to_path_buf();
+        move |err: std::io::Error| errors::Errors::SaveError {
+            path,
+            reason: err.to_string(),
+        }
+    };
+    fs::create_dir_all(dir).map_err(failed(dir))?;
+    let probe = dir.join(format!(".todo-probe-{}", std::process::id()));
+    fs::write(&probe, "").map_err(failed(dir))?;
+    fs::remove_file(&probe).map_err(failed(dir))?;
+    if path.exists() {
+        fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(failed(path))?;
+    }
+    Ok(())
+}
+
+/// Get the path of the emergency copy written when the data file can't be saved on exit
+///
+/// # Returns
+///
+/// - `PathBuf` - `todo-emergency.json` in the temporary directory
+pub fn emergency_path() -> PathBuf {
+    std::env::temp_dir().join("todo-emergency.json")
+}
+
+/// Save the application data on exit, falling back to an emergency copy
+///
+/// This is the last chance to keep the changes, so if the data file can't be written the
+/// data goes to [`emergency_path`] and the error tells the user where to find it. The
+/// emergency copy holds the archived workspaces too, a single file [`load_data`] reads as a
+/// data file saved before the archive file was split off.
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - The file path where the data should be saved
+/// - `datas` (`&Datas`) - The data structure containing all application data to be saved
+/// - `archive` (`Option<&str>`) - the content of the archive file, None to leave it alone,
+///   e.g. while it's unread or unchanged
+///
+/// # Errors
+///
+/// Returns [`errors::Errors::ExitSaveError`] if the archive file or the data file can't be
+/// written, the data file is left as it was when the archive file fails
+pub fn save_data_on_exit(
+    path: &Path,
+    datas: &Datas,
+    archive: Option<&str>,
+) -> Result<(), errors::Errors> {
+    let result = match archive {
+        Some(archive) => write_data(&archive_path(path), archive),
+        None => Ok(()),
+    };
+    match result.and_then(|()| write_data(path, &serialize_data(datas))) {
+        Err(errors::Errors::SaveError { path, reason }) => {
+            Err(save_aside_on_exit(&path, datas, reason))
+        }
+        result => result,
+    }
+}
+
+/// Write the application data to the emergency copy instead of the data file on exit, see
+/// [`emergency_path`]
+///
+/// The data file is left alone, e.g. when it can't be written or when another program
+/// changed it since the last save, see [`FileStamp::newer_than`].
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file not written
+/// - `datas` (`&Datas`) - the data of the application
+/// - `reason` (`String`) - why the data file isn't written
+///
+/// # Returns
+///
+/// - [`errors::Errors::ExitSaveError`] - the error telling where the emergency copy went
+pub fn save_aside_on_exit(path: &Path, datas: &Datas, reason: String) -> errors::Errors {
+    let content = serde_json::to_string_pretty(datas).unwrap();
+    let emergency = emergency_path();
+    errors::Errors::ExitSaveError {
+        path: path.to_path_buf(),
+        reason,
+        emergency: write_data(&emergency, &content).ok().map(|_| emergency),
+    }
+}
+
+/// Serialize the application data to the JSON content of the data file
+///
+/// Saving is split in two steps so the UI can serialize the data, which holds `Rc`s, on its
+/// own thread and hand the plain string to a blocking task for the slow part, the write.
+/// The archived workspaces are left out, see [`split_archive`].
+///
+/// # Arguments
+///
+/// - `datas` (`&Datas`) - The data structure containing all application data
+///
+/// # Returns
+///
+/// - `String` - The pretty printed JSON content
+pub fn serialize_data(datas: &Datas) -> String {
+    serde_json::to_string_pretty(&split_archive(datas).0).unwrap()
+}
+
+/// Write the serialized application data to a specific file
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - The file path where the data should be saved
+/// - `content` (`&str`) - The content given by [`serialize_data`]
+///
+/// # Errors
+///
+/// Returns [`errors::Errors::SaveError`] with the path and the OS error if the file can't be written
+pub fn write_data(path: &Path, content: &str) -> Result<(), errors::Errors> {
+    fs::write(path, content).map_err(|err| errors::Errors::SaveError {
+        path: path.to_path_buf(),
+        reason: err.to_string(),
+    })
+}
 
-    fs::write(path, res).map_err(|_| errors::Errors::WriteError)
+/// The data file as a load or a save of the session left it
+///
+/// A sync tool or a restored backup may put an older version of the data file back while the
+/// application runs, and the next save would silently bring back the tasks deleted since, or
+/// drop the ones added. Another program writing the file, like `todo serve`, leaves it newer
+/// with changes the next save would drop just the same. The UI keeps the stamp of its last
+/// load or save and compares the file to it before writing again, see
+/// [`Ui::rolled_back`](crate::app::ui::Ui::rolled_back) and
+/// [`Ui::changed_elsewhere`](crate::app::ui::Ui::changed_elsewhere).
+///
+/// # Fields
+///
+/// - `modified` (`SystemTime`) - the modification time of the file once written
+/// - `hash` (`u64`) - the hash of the content written, only compared within a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub modified: SystemTime,
+    pub hash: u64,
+}
+
+impl FileStamp {
+    /// Stamp the content of a file modified at a time
+    pub fn new(modified: SystemTime, content: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        Self {
+            modified,
+            hash: hasher.finish(),
+        }
+    }
+
+    /// Whether the file stamped is an older version than the one stamped by `saved`
+    ///
+    /// The file must be modified before the save and hold another content: a file modified
+    /// after it was changed by someone else, not rolled back, and a backup of the save itself
+    /// put back loses nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use todo::app::data::FileStamp;
+    ///
+    /// let now = SystemTime::now();
+    /// let saved = FileStamp::new(now, "new");
+    /// let hour_ago = now - Duration::from_secs(3600);
+    /// assert!(FileStamp::new(hour_ago, "old").older_than(&saved));
+    /// assert!(!FileStamp::new(hour_ago, "new").older_than(&saved));
+    /// ```
+    pub fn older_than(&self, saved: &FileStamp) -> bool {
+        self.modified < saved.modified && self.hash != saved.hash
+    }
+
+    /// Whether the file stamped was written by someone else since the load or the save
+    /// stamped by `saved`
+    ///
+    /// The file must be modified after it and hold another content, a file written again
+    /// with the same content loses nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use todo::app::data::FileStamp;
+    ///
+    /// let now = SystemTime::now();
+    /// let saved = FileStamp::new(now, "ours");
+    /// let minute_later = now + Duration::from_secs(60);
+    /// assert!(FileStamp::new(minute_later, "theirs").newer_than(&saved));
+    /// assert!(!FileStamp::new(minute_later, "ours").newer_than(&saved));
+    /// assert!(!FileStamp::new(now, "ours").newer_than(&saved));
+    /// ```
+    pub fn newer_than(&self, saved: &FileStamp) -> bool {
+        self.modified > saved.modified && self.hash != saved.hash
+    }
+
+    /// Stamp the data file as it is now, None if it can't be read
+    pub fn read(path: &Path) -> Option<Self> {
+        let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+        let content = fs::read_to_string(path).ok()?;
+        Some(Self::new(modified, &content))
+    }
 }
 
 /// Load the application data from a specific file
 ///
-/// Reads and deserializes application data from a JSON file. If the file doesn't exist,
-/// this function will create the necessary directory structure and return default data.
+/// Reads and deserializes application data from a JSON file, with the archive file next to
+/// it, see [`read_archive`]. If the file doesn't exist, this function will create the
+/// necessary directory structure and return default data.
 ///
 /// # Arguments
 ///
@@ -121,28 +1097,44 @@ pub fn save_data(path: &Path, datas: &Datas) -> Result<(), errors::Errors> {
 ///
 /// # Examples
 ///
-/// ```
-/// use std::path::Path;
-/// use crate::app::data::{Datas, load_data};
+/// ```no_run
+/// use todo::{data_path, load_data};
 ///
-/// // Load from a file (this would typically use a proper path)
-/// // let path = Path::new("/path/to/data.json");
-/// // let result = load_data(path);
+/// let (path, _) = data_path();
+/// let datas = load_data(&path).unwrap();
+/// println!("{} workspaces", datas.workspace.workspaces.len());
 /// ```
 pub fn load_data(path: &Path) -> Result<Datas, errors::Errors> {
-    if path.exists() {
+    let mut datas = load_active(path)?;
+    if datas.archived_ws.unread {
+        read_archive(path)?.merge_into(&mut datas.archived_ws, &mut datas.todolist);
+    }
+    Ok(datas)
+}
+
+/// Load the application data from a specific file, without the archive file
+///
+/// The archived panel is marked unread if there is an archive file, see
+/// [`WorkspaceWidget::unread`], and [`read_archive`] reads it when needed. A data file saved
+/// before the archive file was split off is read with its archived workspaces.
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - The file path from which to load the data
+///
+/// # Errors
+///
+/// Returns [`errors::Errors::LoadError`] if there are issues reading from the file system or parsing the JSON
+pub fn load_active(path: &Path) -> Result<Datas, errors::Errors> {
+    let mut datas = if path.exists() {
         let content = fs::read_to_string(path).map_err(|_| errors::Errors::LoadError)?;
-        // let data = serde_json::from_str(&content).map_err(|_| errors::Errors::LoadError);
-        let data = serde_json::from_str(&content).unwrap();
-        Ok(data)
+        serde_json::from_str(&content).map_err(|_| errors::Errors::LoadError)?
     } else {
-        let _ = fs::create_dir_all(
-            path.parent().unwrap_or(
-                std::env::home_dir()
-                    .unwrap_or(std::path::PathBuf::from("/home/blake"))
-                    .as_path(),
-            ),
-        );
-        Ok(Datas::default())
-    }
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        Datas::default()
+    };
+    datas.archived_ws.unread = archive_path(path).exists();
+    Ok(datas)
 }
@@ -0,0 +1,220 @@
+//! Differences between two saves of the data
+//!
+//! `todo diff SNAPSHOT [CURRENT]` compares a copy of the data file against the data file, to
+//! find out what a sync or a bug changed. The tasks of both saves are keyed by their id, so a
+//! renamed or moved task is reported as such rather than as one task removed and another added.
+//! Only what the task says is compared: the name, the status, the due date and the workspace,
+//! never the expanded or selected state.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs,
+    path::Path,
+};
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::app::{
+    data::{self, Datas},
+    errors,
+    ui::todolistwidget::TaskStatus,
+    visit::{VisitOptions, visit_tasks},
+};
+
+/// A change to a task between two saves
+///
+/// # Variants
+///
+/// - `Added` - a task only in the newer save
+/// - `Removed` - a task only in the older save
+/// - `Moved` - a task now in another workspace, `from` is the old one
+/// - `Renamed` - a task whose description changed
+/// - `Status` - a task whose status changed
+/// - `Due` - a task whose due date was set, changed or cleared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added(String),
+    Removed(String),
+    Moved {
+        desc: String,
+        from: String,
+    },
+    Renamed {
+        from: String,
+        to: String,
+    },
+    Status {
+        desc: String,
+        from: TaskStatus,
+        to: TaskStatus,
+    },
+    Due {
+        desc: String,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = |due: &Option<NaiveDate>| match due {
+            Some(due) => due.to_string(),
+            None => "none".to_string(),
+        };
+        match self {
+            Change::Added(desc) => write!(f, "+ {}", desc),
+            Change::Removed(desc) => write!(f, "- {}", desc),
+            Change::Moved { desc, from } => write!(f, "> {} (moved from {})", desc, from),
+            Change::Renamed { from, to } => write!(f, "~ {} (renamed from {})", to, from),
+            Change::Status { desc, from, to } => {
+                write!(f, "~ {}: status {:?} -> {:?}", desc, from, to)
+            }
+            Change::Due { desc, from, to } => {
+                write!(f, "~ {}: due {} -> {}", desc, date(from), date(to))
+            }
+        }
+    }
+}
+
+/// The changes between two saves, grouped by workspace
+///
+/// # Fields
+///
+/// - `workspaces` (`BTreeMap<String, Vec<Change>>`) - the changes by workspace path, e.g.
+///   `Work / Backend`, the workspace a task is in now or was in before it was removed
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DataDiff {
+    pub workspaces: BTreeMap<String, Vec<Change>>,
+}
+
+impl DataDiff {
+    /// Whether the two saves hold the same tasks
+    pub fn is_empty(&self) -> bool {
+        self.workspaces.is_empty()
+    }
+
+    fn push(&mut self, workspace: &str, change: Change) {
+        self.workspaces
+            .entry(workspace.to_string())
+            .or_default()
+            .push(change);
+    }
+}
+
+impl fmt::Display for DataDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences");
+        }
+        for (workspace, changes) in self.workspaces.iter() {
+            writeln!(f, "{}", workspace)?;
+            for change in changes {
+                writeln!(f, "  {}", change)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What is compared of a task
+struct Entry {
+    desc: String,
+    status: TaskStatus,
+    due: Option<NaiveDate>,
+    workspace: String,
+}
+
+/// Key the tasks of a save by id, in the order of the screen, see [`visit_tasks`]
+fn index(datas: &Datas) -> (Vec<Uuid>, HashMap<Uuid, Entry>) {
+    let mut order = Vec::new();
+    let mut entries = HashMap::new();
+    visit_tasks(datas, VisitOptions::everything(), |visit| {
+        let task = visit.task;
+        order.push(task.id);
+        entries.insert(
+            task.id,
+            Entry {
+                desc: task.desc.clone(),
+                status: task.status.clone(),
+                due: task.due,
+                workspace: visit.workspace_path.to_string(),
+            },
+        );
+    });
+    (order, entries)
+}
+
+/// Compare two saves of the data
+///
+/// # Arguments
+///
+/// - `old` (`&Datas`) - the older save, e.g. a snapshot
+/// - `new` (`&Datas`) - the newer save, e.g. the data file
+///
+/// # Returns
+///
+/// - [`DataDiff`] - the changes which turn `old` into `new`
+pub fn diff(old: &Datas, new: &Datas) -> DataDiff {
+    let (old_order, old_entries) = index(old);
+    let (new_order, new_entries) = index(new);
+    let mut result = DataDiff::default();
+    for id in new_order.iter() {
+        let now = &new_entries[id];
+        let Some(before) = old_entries.get(id) else {
+            result.push(&now.workspace, Change::Added(now.desc.clone()));
+            continue;
+        };
+        if before.workspace != now.workspace {
+            let change = Change::Moved {
+                desc: now.desc.clone(),
+                from: before.workspace.clone(),
+            };
+            result.push(&now.workspace, change);
+        }
+        if before.desc != now.desc {
+            let change = Change::Renamed {
+                from: before.desc.clone(),
+                to: now.desc.clone(),
+            };
+            result.push(&now.workspace, change);
+        }
+        if before.status != now.status {
+            let change = Change::Status {
+                desc: now.desc.clone(),
+                from: before.status.clone(),
+                to: now.status.clone(),
+            };
+            result.push(&now.workspace, change);
+        }
+        if before.due != now.due {
+            let change = Change::Due {
+                desc: now.desc.clone(),
+                from: before.due,
+                to: now.due,
+            };
+            result.push(&now.workspace, change);
+        }
+    }
+    for id in old_order.iter() {
+        if !new_entries.contains_key(id) {
+            let before = &old_entries[id];
+            result.push(&before.workspace, Change::Removed(before.desc.clone()));
+        }
+    }
+    result
+}
+
+/// Read a save of the data to compare, which unlike [`load_data`](crate::app::data::load_data)
+/// must exist and be valid, with the archive file next to it if there is one, see
+/// [`read_archive`](crate::app::data::read_archive)
+///
+/// # Errors
+///
+/// Returns [`errors::Errors::LoadError`] if the file can't be read or isn't a save of the data
+pub fn read_datas(path: &Path) -> Result<Datas, errors::Errors> {
+    let content = fs::read_to_string(path).map_err(|_| errors::Errors::LoadError)?;
+    let mut datas: Datas = serde_json::from_str(&content).map_err(|_| errors::Errors::LoadError)?;
+    data::read_archive(path)?.merge_into(&mut datas.archived_ws, &mut datas.todolist);
+    Ok(datas)
+}
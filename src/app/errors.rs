@@ -4,6 +4,8 @@
 //! It provides a centralized error enum for consistent error handling
 //! across all components of the application.
 
+use std::{fmt, path::PathBuf};
+
 /// The Error type of the application
 ///
 /// This enum represents all possible error conditions that can occur
@@ -16,11 +18,13 @@
 /// - `UiError` - Error originating from the UI module
 /// - `WriteError` - Error occurred while saving data to file
 /// - `LoadError` - Error occurred while loading data from file
+/// - `SaveError` - Error occurred while saving data to file, with the path and the OS error
+/// - `ExitSaveError` - The final save on exit failed, with where the emergency copy went
 ///
 /// # Examples
 ///
 /// ```
-/// use crate::app::errors::Errors;
+/// use todo::app::errors::Errors;
 ///
 /// // Example of returning different error types
 /// fn save_operation() -> Result<(), Errors> {
@@ -43,4 +47,44 @@ pub enum Errors {
     WriteError,
     /// Error while loading data from file
     LoadError,
+    /// Error while saving data to file, with the path and the reason given by the OS
+    SaveError { path: PathBuf, reason: String },
+    /// The final save on exit failed, `emergency` is where the emergency copy was written
+    ExitSaveError {
+        path: PathBuf,
+        reason: String,
+        emergency: Option<PathBuf>,
+    },
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Errors::AppError => write!(f, "application error"),
+            Errors::UiError => write!(f, "ui error"),
+            Errors::WriteError => write!(f, "failed to write the file"),
+            Errors::LoadError => write!(f, "failed to load the data"),
+            Errors::SaveError { path, reason } => {
+                write!(f, "failed to save to {}: {}", path.display(), reason)
+            }
+            Errors::ExitSaveError {
+                path,
+                reason,
+                emergency,
+            } => {
+                write!(f, "failed to save to {}: {}", path.display(), reason)?;
+                match emergency {
+                    Some(emergency) => write!(
+                        f,
+                        "\nyour data was written to {} instead, copy it back once the problem is fixed",
+                        emergency.display()
+                    ),
+                    None => write!(
+                        f,
+                        "\nwriting an emergency copy failed too, the changes are lost"
+                    ),
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,202 @@
+//! The journal of the actions since the last save
+//!
+//! Every action changing the data is appended to `journal.log` next to the data file once
+//! it's done, see [`journal_path`]: one JSON line holding the [`WidgetAction`], the selection
+//! it ran on and the keys typed into its popups, synced to the disk before the next action.
+//! A save empties the journal, so a journal left behind, newer than the data file, holds what
+//! a crash or a killed terminal kept from being saved. The next start offers to replay it
+//! against the saved data, see [`pending`].
+//!
+//! An action is replayed the way it ran: its selection is restored, the keys are fed to its
+//! popups, see [`keys::feed`], and the action goes through the UI again. The selection is
+//! saved by position rather than by id, as a workspace or a task added since the save gets a
+//! new id on replay, while the same actions replayed on the same data put every item at the
+//! same place.
+//!
+//! [`keys::feed`]: crate::app::ui::keys::feed
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{
+    appstate::CurrentFocus,
+    ui::{WidgetAction, workspacewidget::WorkspaceType},
+};
+
+/// Where an action ran, by position, see the [module](self) documentation
+///
+/// # Fields
+///
+/// - `focus` ([`CurrentFocus`]) - the focused panel
+/// - `origin` ([`WorkspaceType`]) - the panel of the workspace whose todo list is shown
+/// - `workspace` (`Option<usize>`) - the row of the selected workspace, among all the
+///   workspaces flattened
+/// - `archived` (`Option<usize>`) - the row of the selected archived workspace, likewise
+/// - `task` (`Option<usize>`) - the row of the selected task, among all the tasks of the shown
+///   todo list flattened, whether folded or not
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Selection {
+    pub focus: CurrentFocus,
+    pub origin: WorkspaceType,
+    pub workspace: Option<usize>,
+    pub archived: Option<usize>,
+    pub task: Option<usize>,
+}
+
+/// An action of the journal
+///
+/// # Fields
+///
+/// - `action` ([`WidgetAction`]) - the action
+/// - `selection` ([`Selection`]) - where it ran
+/// - `keys` (`Vec<(KeyCode, KeyModifiers)>`) - the keys typed into its popups
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub action: WidgetAction,
+    pub selection: Selection,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<(KeyCode, KeyModifiers)>,
+}
+
+impl Entry {
+    /// The keys of the entry, as pressed
+    pub fn key_events(&self) -> Vec<KeyEvent> {
+        self.keys
+            .iter()
+            .map(|(code, modifiers)| KeyEvent::new(*code, *modifiers))
+            .collect()
+    }
+}
+
+/// The journal file, opened for appending
+///
+/// # Fields
+///
+/// - `path` (`PathBuf`) - the journal file
+/// - `file` (`File`) - the file, opened for appending
+#[derive(Debug)]
+pub struct Journal {
+    pub path: PathBuf,
+    file: File,
+}
+
+impl Journal {
+    /// Start an empty journal, dropping what the file held
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the file system if the file can't be opened or emptied
+    pub fn create(path: PathBuf) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.set_len(0)?;
+        Ok(Self { path, file })
+    }
+
+    /// Append an action, synced to the disk before returning
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the file system if the line can't be written
+    pub fn append(&mut self, entry: &Entry) -> io::Result<()> {
+        let mut line = serde_json::to_string(entry).map_err(io::Error::other)?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        self.file.sync_data()
+    }
+
+    /// The length of the journal in bytes, to mark what a save holds
+    pub fn len(&self) -> u64 {
+        self.file.metadata().map_or(0, |meta| meta.len())
+    }
+
+    /// Whether the journal holds no action
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop the actions a save holds, keeping the ones appended since its snapshot
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`Journal`])
+    /// - `mark` (`u64`) - the [`Journal::len`] when the snapshot of the save was taken
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the file system if the journal can't be rewritten
+    pub fn saved(&mut self, mark: u64) -> io::Result<()> {
+        let content = fs::read(&self.path)?;
+        let rest = content.get(mark as usize..).unwrap_or_default().to_vec();
+        self.file.set_len(0)?;
+        self.file.write_all(&rest)?;
+        self.file.sync_data()
+    }
+}
+
+/// Get the path of the journal of a data file
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file
+///
+/// # Returns
+///
+/// - `PathBuf` - `journal.log` next to `data.json`, `<name>.journal.log` next to any other
+///   `<name>.json`
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use todo::app::journal::journal_path;
+///
+/// assert_eq!(
+///     journal_path(Path::new("/data/todo/data.json")),
+///     Path::new("/data/todo/journal.log")
+/// );
+/// assert_eq!(
+///     journal_path(Path::new("/tmp/todo-demo.json")),
+///     Path::new("/tmp/todo-demo.journal.log")
+/// );
+/// ```
+pub fn journal_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    if stem == "data" {
+        path.with_file_name("journal.log")
+    } else {
+        path.with_file_name(format!("{}.journal.log", stem))
+    }
+}
+
+/// Read the actions of a journal left behind by a run which didn't save them
+///
+/// A journal older than the data file was saved over, e.g. by `todo serve`, and a line which
+/// doesn't parse, the last one written as the application died, is skipped.
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file
+///
+/// # Returns
+///
+/// - `Vec<Entry>` - the actions to replay, in order, empty if there is nothing to replay
+pub fn pending(path: &Path) -> Vec<Entry> {
+    let journal = journal_path(path);
+    let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    if let (Some(data), Some(journal)) = (modified(path), modified(&journal))
+        && data > journal
+    {
+        return Vec::new();
+    }
+    fs::read_to_string(&journal)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
@@ -0,0 +1,174 @@
+//! Links in the tasks
+//!
+//! The descriptions of the tasks often hold links, e.g. to an issue. [`find`] picks the
+//! `http://` and `https://` links out of a description, the todo list underlines them and
+//! `ctrl-o` opens the one of the current task in the default browser, see [`open`], after
+//! asking which one when there are several. The links written in the notes of the task are
+//! offered too, after those of its description, see [`task_links`].
+//!
+//! A link ends at a space or a quote, and the punctuation closing the sentence around it is
+//! left out, e.g. `(see https://example.com/a.)` holds `https://example.com/a`. A closing
+//! bracket is kept when the link opens it, as in `https://en.wikipedia.org/wiki/Rust_(game)`.
+
+use std::{
+    io,
+    ops::Range,
+    process::{Command, Stdio},
+};
+
+use crate::app::ui::todolistwidget::Task;
+
+/// The schemes a link starts with
+const SCHEMES: [&str; 2] = ["https://", "http://"];
+
+/// The characters which end a link
+const ENDS: [char; 5] = ['"', '\'', '<', '>', '`'];
+
+/// The characters left out at the end of a link, which end the sentence around it
+const TRAILING: [char; 7] = ['.', ',', ';', ':', '!', '?', '*'];
+
+/// Find the links of a text, see the [module](self) documentation
+///
+/// # Arguments
+///
+/// - `text` (`&str`) - the text, e.g. the description of a task
+///
+/// # Returns
+///
+/// - `Vec<Range<usize>>` - the byte range of every link, in order
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::links;
+///
+/// let text = "fix it, see https://example.com/issues/12.";
+/// let found = links::find(text);
+/// assert_eq!(&text[found[0].clone()], "https://example.com/issues/12");
+/// ```
+pub fn find(text: &str) -> Vec<Range<usize>> {
+    let mut found = Vec::new();
+    let mut from = 0;
+    while let Some((start, scheme)) = SCHEMES
+        .iter()
+        .filter_map(|scheme| text[from..].find(scheme).map(|at| (from + at, scheme)))
+        .min_by_key(|(start, _)| *start)
+    {
+        let rest = &text[start..];
+        let mut end = start
+            + rest
+                .find(|c: char| c.is_whitespace() || ENDS.contains(&c))
+                .unwrap_or(rest.len());
+        loop {
+            let link = &text[start..end];
+            let Some(last) = link.chars().next_back() else {
+                break;
+            };
+            let unopened = |open: char| link.matches(open).count() < link.matches(last).count();
+            let trailing = TRAILING.contains(&last)
+                || (last == ')' && unopened('('))
+                || (last == ']' && unopened('['));
+            if !trailing {
+                break;
+            }
+            end -= last.len_utf8();
+        }
+        // part of a word, e.g. `xhttps://`, or a scheme alone
+        let in_word = text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric);
+        if !in_word && end > start + scheme.len() {
+            found.push(start..end);
+        }
+        from = end.max(start + scheme.len());
+    }
+    found
+}
+
+/// The links of a text, see [`find`]
+pub fn links(text: &str) -> Vec<&str> {
+    find(text).into_iter().map(|range| &text[range]).collect()
+}
+
+/// The links of a task, those of its description then those of its notes, each once
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::{links, ui::todolistwidget::Task};
+///
+/// let mut task = Task::new("fix https://example.com/1".to_string(), None);
+/// task.set_notes("see https://example.com/2 and https://example.com/1");
+/// assert_eq!(
+///     links::task_links(&task),
+///     ["https://example.com/1", "https://example.com/2"]
+/// );
+/// ```
+pub fn task_links(task: &Task) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    links(&task.desc)
+        .into_iter()
+        .chain(links(&task.notes))
+        .for_each(|link| {
+            if !found.iter().any(|other| other == link) {
+                found.push(link.to_string());
+            }
+        });
+    found
+}
+
+/// The program opening a link in the default browser, with the arguments before the link
+///
+/// None of them goes through a shell, so the link is a single argument whatever it holds. On
+/// Windows that rules `cmd /C start` out, which runs what follows a `&` or a `|` of the link.
+pub fn opener() -> (&'static str, &'static [&'static str]) {
+    if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else if cfg!(windows) {
+        ("rundll32", &["url.dll,FileProtocolHandler"])
+    } else {
+        ("xdg-open", &[])
+    }
+}
+
+/// The command opening a link with the opener of the platform, see [`opener`]
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::links;
+///
+/// let command = links::command("https://example.com/?a=1&b=2");
+/// let last = command.get_args().last().unwrap();
+/// assert_eq!(last, "https://example.com/?a=1&b=2");
+/// ```
+pub fn command(link: &str) -> Command {
+    let (program, args) = opener();
+    let mut command = Command::new(program);
+    command.args(args).arg(link);
+    command
+}
+
+/// Open a link in the default browser, without waiting for it
+///
+/// The opener of the platform, see [`opener`], is started without a terminal, so it can't
+/// draw over the interface, and waited for in a thread of its own.
+///
+/// # Arguments
+///
+/// - `link` (`&str`) - the link to open
+///
+/// # Errors
+///
+/// Returns the error of starting the opener, [`io::ErrorKind::NotFound`] if the platform has
+/// none
+pub fn open(link: &str) -> io::Result<()> {
+    let mut child = command(link)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
@@ -0,0 +1,253 @@
+//! Macros, recorded and replayed sequences of actions
+//!
+//! `q` followed by a letter starts recording into the register of the letter, `q` stops,
+//! and `@` followed by a letter replays the register against the current selection. `q q`
+//! quits, since a plain `q` now waits for a register.
+//!
+//! A macro is recorded at the level of the [`Message`]s sent by the key handler, so `j` is
+//! replayed as a move down whatever the key bindings become. The keys typed into the popups
+//! asking for a text, a date or a confirmation are messages too, [`Message::Edit`]: replaying
+//! the macro types the same text into the same popups. When a popup doesn't open on replay,
+//! e.g. the due date of a task while no task is selected, its keys are left for the next
+//! popup, so a macro is best replayed on the kind of selection it was recorded on.
+//!
+//! The registers live for the session only and hold at most [`MAX_STEPS`] steps each. A
+//! register holds the steps a replay sent rather than a reference to the replayed register,
+//! so the only way to recurse is replaying the register being recorded, which is refused.
+
+use std::collections::HashMap;
+
+use crossterm::event::KeyEvent;
+use tokio::sync::mpsc::{self, error::SendError};
+
+use crate::app::{
+    appstate::Message,
+    ui::{
+        keys,
+        strings::{tr, trf},
+    },
+};
+
+/// The most steps a register holds, a longer recording is dropped
+pub const MAX_STEPS: usize = 256;
+
+/// Why a macro couldn't be recorded or replayed
+///
+/// # Variants
+///
+/// - `NotARegister` - the key after `q` or `@` isn't a lowercase letter
+/// - `Empty` - nothing was recorded into the register
+/// - `Recursive` - the register is the one being recorded
+/// - `TooLong` - the recording went over [`MAX_STEPS`] and was dropped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroError {
+    NotARegister,
+    Empty(char),
+    Recursive(char),
+    TooLong(char),
+}
+
+impl MacroError {
+    /// The prompt message of the error
+    pub fn prompt(&self) -> String {
+        match self {
+            MacroError::NotARegister => tr("Registers Are a To z !").to_string(),
+            MacroError::Empty(register) => trf("@{} Is Empty !", &[register]),
+            MacroError::Recursive(register) => {
+                trf("@{} Can't Replay While Recording !", &[register])
+            }
+            MacroError::TooLong(register) => {
+                trf("@{} Over {} Steps, Dropped !", &[register, &MAX_STEPS])
+            }
+        }
+    }
+}
+
+/// The registers and the recording in progress
+///
+/// # Fields
+///
+/// - `registers` (`HashMap<char, Vec<Message>>`) - the recorded macros by register letter
+/// - `recording` (`Option<(char, Vec<Message>)>`) - the register being recorded and its steps so
+///   far
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::{appstate::Message, macros::Macros};
+///
+/// let mut macros = Macros::default();
+/// macros.start('a').unwrap();
+/// macros.record(Message::Complete).unwrap();
+/// assert_eq!(macros.stop(), Some(('a', 1)));
+/// assert_eq!(macros.replay('a').unwrap(), vec![Message::Complete]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Macros {
+    registers: HashMap<char, Vec<Message>>,
+    recording: Option<(char, Vec<Message>)>,
+}
+
+impl Macros {
+    /// Whether a key names a register, a lowercase letter
+    pub fn is_register(c: char) -> bool {
+        c.is_ascii_lowercase()
+    }
+
+    /// The register being recorded
+    pub fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|(register, _)| *register)
+    }
+
+    /// Start recording into a register, replacing what it held once stopped
+    ///
+    /// # Errors
+    ///
+    /// - [`MacroError::NotARegister`] - `register` isn't a lowercase letter
+    pub fn start(&mut self, register: char) -> Result<(), MacroError> {
+        if !Macros::is_register(register) {
+            return Err(MacroError::NotARegister);
+        }
+        self.recording = Some((register, Vec::new()));
+        Ok(())
+    }
+
+    /// Stop recording and keep the steps in the register
+    ///
+    /// # Returns
+    ///
+    /// - `Option<(char, usize)>` - the register and the number of steps, None if nothing was recording
+    pub fn stop(&mut self) -> Option<(char, usize)> {
+        let (register, steps) = self.recording.take()?;
+        let len = steps.len();
+        self.registers.insert(register, steps);
+        Some((register, len))
+    }
+
+    /// Add a step to the recording, if any
+    ///
+    /// The exit, redraw and prompt messages are never recorded, nor are the keys of the due
+    /// banner, which is only shown for a while.
+    ///
+    /// # Errors
+    ///
+    /// - [`MacroError::TooLong`] - the recording is over [`MAX_STEPS`], it is dropped and the
+    ///   register keeps what it held
+    pub fn record(&mut self, msg: Message) -> Result<(), MacroError> {
+        if let Message::Exit
+        | Message::Update
+        | Message::Prompt(_)
+        | Message::BannerJump
+        | Message::DismissBanner = msg
+        {
+            return Ok(());
+        }
+        let Some((register, steps)) = self.recording.as_mut() else {
+            return Ok(());
+        };
+        if steps.len() == MAX_STEPS {
+            let register = *register;
+            self.recording = None;
+            return Err(MacroError::TooLong(register));
+        }
+        steps.push(msg);
+        Ok(())
+    }
+
+    /// Get the steps to replay from a register
+    ///
+    /// # Errors
+    ///
+    /// - [`MacroError::NotARegister`] - `register` isn't a lowercase letter
+    /// - [`MacroError::Recursive`] - `register` is being recorded
+    /// - [`MacroError::Empty`] - nothing was recorded into `register`
+    pub fn replay(&self, register: char) -> Result<Vec<Message>, MacroError> {
+        if !Macros::is_register(register) {
+            return Err(MacroError::NotARegister);
+        }
+        if self.recording() == Some(register) {
+            return Err(MacroError::Recursive(register));
+        }
+        match self.registers.get(&register) {
+            Some(steps) if !steps.is_empty() => Ok(steps.clone()),
+            _ => Err(MacroError::Empty(register)),
+        }
+    }
+}
+
+/// The senders of the key handler, recording what goes through them
+///
+/// # Fields
+///
+/// - `tx` (`mpsc::Sender<Message>`) - the sender to the message handler
+/// - `input_tx` (`mpsc::Sender<KeyEvent>`) - the key stream of the open popup, only sent the
+///   resizes of the terminal, see [`Recorder::resize`]
+/// - `macros` ([`Macros`]) - the registers and the recording in progress
+#[derive(Debug)]
+pub struct Recorder {
+    pub tx: mpsc::Sender<Message>,
+    pub input_tx: mpsc::Sender<KeyEvent>,
+    pub macros: Macros,
+}
+
+impl Recorder {
+    pub fn new(tx: mpsc::Sender<Message>, input_tx: mpsc::Sender<KeyEvent>) -> Self {
+        Self {
+            tx,
+            input_tx,
+            macros: Macros::default(),
+        }
+    }
+
+    /// Send a message to the message handler, recording it
+    pub async fn send(&mut self, msg: Message) -> Result<(), SendError<Message>> {
+        self.keep(msg.clone()).await;
+        self.tx.send(msg).await
+    }
+
+    /// Have the open popup draw itself again for the new size of the terminal, never recorded
+    ///
+    /// The popup redraws on its next key anyway, so nothing is sent when keys are waiting.
+    pub fn resize(&self) {
+        let _ = self.input_tx.try_send(keys::RESIZE);
+    }
+
+    /// Show a message in the prompt, never recorded
+    pub async fn prompt(&self, desc: String) {
+        let _ = self.tx.send(Message::Prompt(desc)).await;
+    }
+
+    /// Start recording into a register
+    pub async fn start(&mut self, register: char) {
+        match self.macros.start(register) {
+            Ok(()) => self.prompt(trf("Recording @{} !", &[&register])).await,
+            Err(err) => self.prompt(err.prompt()).await,
+        }
+    }
+
+    /// Stop recording
+    pub async fn stop(&mut self) {
+        if let Some((register, len)) = self.macros.stop() {
+            self.prompt(trf("@{} Recorded, {} Steps !", &[&register, &len]))
+                .await;
+        }
+    }
+
+    /// Replay a register, recording its steps into the register being recorded if any
+    pub async fn replay(&mut self, register: char) {
+        match self.macros.replay(register) {
+            Ok(steps) => {
+                for msg in steps {
+                    let _ = self.send(msg).await;
+                }
+            }
+            Err(err) => self.prompt(err.prompt()).await,
+        }
+    }
+
+    async fn keep(&mut self, msg: Message) {
+        if let Err(err) = self.macros.record(msg) {
+            self.prompt(err.prompt()).await;
+        }
+    }
+}
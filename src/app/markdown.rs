@@ -0,0 +1,279 @@
+//! Markdown checklists
+//!
+//! `todo import-md FILE` turns a Markdown checklist into tasks, with the nesting of the list
+//! as the subtasks. Checklists come from notes, issues and other people's files, so the
+//! parser is lenient:
+//!
+//! - the bullets are `-`, `*`, `+` or numbers like `1.` and `1)`
+//! - the box is `[ ]` for a todo task, `[x]` or `[X]` for a finished one, `[~]` for one in
+//!   process and `[-]` for a deprecated one, the same marks as the ASCII glyphs, and an item
+//!   without a box is a todo task
+//! - the indentation may be spaces or tabs, a tab counting as [`TAB_WIDTH`] columns, and an
+//!   item is a subtask of the closest item above it which is less indented, whatever the
+//!   number of columns
+//! - a date in parentheses ending an item, `(2025-07-01)`, `(due 2025-07-01)` or
+//!   `(due: 2025-07-01)`, becomes its due date
+//! - every other line, headings, text or blank lines, is skipped
+//!
+//! Multi-line paste doesn't reach the add popup yet, so the import goes through the command
+//! line, or the import popup of the todo list which reads a `.md` file as a checklist and
+//! any other file as a [plain text list](crate::app::plaintext).
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::app::{
+    data::{Datas, OnClash},
+    parse,
+    ui::todolistwidget::{Task, TaskStatus, TodoList},
+};
+
+/// The columns of a tab in the indentation of a checklist
+pub const TAB_WIDTH: usize = 4;
+
+/// An item of a checklist, before it is made a task
+///
+/// # Fields
+///
+/// - `indent` (`usize`) - the columns before the bullet
+/// - `desc` (`String`) - the text of the item, without the box and the due date
+/// - `status` ([`TaskStatus`]) - the status from the box
+/// - `due` (`Option<NaiveDate>`) - the due date from the end of the item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub indent: usize,
+    pub desc: String,
+    pub status: TaskStatus,
+    pub due: Option<NaiveDate>,
+}
+
+/// Parse a line of a checklist
+///
+/// # Arguments
+///
+/// - `line` (`&str`) - the line, without the line break
+///
+/// # Returns
+///
+/// - `Option<Item>` - the item, or None for a line which isn't a list item or has no text
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::markdown::parse_item;
+/// use todo::app::ui::todolistwidget::TaskStatus;
+///
+/// let item = parse_item("\t* [x] Ship it (due 2025-07-01)").unwrap();
+/// assert_eq!((item.indent, item.desc.as_str()), (4, "Ship it"));
+/// assert_eq!(item.status, TaskStatus::Finished);
+/// ```
+pub fn parse_item(line: &str) -> Option<Item> {
+    let (indent, rest) = split_indent(line);
+    let rest = strip_bullet(rest)?;
+    let (status, rest) = match rest.get(..3) {
+        Some("[ ]") => (TaskStatus::Todo, &rest[3..]),
+        Some("[x]" | "[X]") => (TaskStatus::Finished, &rest[3..]),
+        Some("[~]") => (TaskStatus::InProcess, &rest[3..]),
+        Some("[-]") => (TaskStatus::Deprecated, &rest[3..]),
+        _ => (TaskStatus::Todo, rest),
+    };
+    let (desc, due) = split_due(rest.trim());
+    if desc.is_empty() {
+        return None;
+    }
+    Some(Item {
+        indent,
+        desc: desc.to_string(),
+        status,
+        due,
+    })
+}
+
+/// Split the indentation off a line
+///
+/// # Arguments
+///
+/// - `line` (`&str`) - the line
+///
+/// # Returns
+///
+/// - `(usize, &str)` - the columns of the spaces and tabs starting the line, a tab counting up
+///   to the next multiple of [`TAB_WIDTH`], and the rest of the line
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::markdown::split_indent;
+///
+/// assert_eq!(split_indent("  \tdone"), (4, "done"));
+/// ```
+pub fn split_indent(line: &str) -> (usize, &str) {
+    let rest = line.trim_start_matches([' ', '\t']);
+    let indent = line[..line.len() - rest.len()]
+        .chars()
+        .fold(0, |columns, c| match c {
+            '\t' => (columns / TAB_WIDTH + 1) * TAB_WIDTH,
+            _ => columns + 1,
+        });
+    (indent, rest)
+}
+
+/// Remove the bullet and the space after it
+fn strip_bullet(line: &str) -> Option<&str> {
+    let after = match line.chars().next()? {
+        '-' | '*' | '+' => &line[1..],
+        c if c.is_ascii_digit() => {
+            let digits = line.trim_start_matches(|c: char| c.is_ascii_digit());
+            digits
+                .strip_prefix('.')
+                .or_else(|| digits.strip_prefix(')'))?
+        }
+        _ => return None,
+    };
+    // `**bold**` and `---` aren't bullets
+    after
+        .starts_with([' ', '\t'])
+        .then(|| after.trim_start_matches([' ', '\t']))
+}
+
+/// Split a due date in parentheses off the end of an item
+fn split_due(text: &str) -> (&str, Option<NaiveDate>) {
+    let Some(open) = text.strip_suffix(')').and_then(|text| text.rfind('(')) else {
+        return (text, None);
+    };
+    let inside = text[open + 1..text.len() - 1].trim();
+    let date = inside
+        .strip_prefix("due")
+        .map(|date| date.trim_start_matches(':').trim())
+        .unwrap_or(inside);
+    match parse::iso_date(date) {
+        Some(due) => (text[..open].trim_end(), Some(due)),
+        None => (text, None),
+    }
+}
+
+/// Parse a checklist into task trees
+///
+/// # Arguments
+///
+/// - `text` (`&str`) - the Markdown text
+///
+/// # Returns
+///
+/// - `Vec<Rc<RefCell<Task>>>` - the top level tasks, with the nested items as their subtasks
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::markdown::parse_checklist;
+///
+/// let tasks = parse_checklist("- [ ] parent\n  - [x] child\n");
+/// assert_eq!(tasks.len(), 1);
+/// assert_eq!(tasks[0].borrow().children.len(), 1);
+/// ```
+pub fn parse_checklist(text: &str) -> Vec<Rc<RefCell<Task>>> {
+    nest(text.lines().filter_map(parse_item).map(|item| {
+        let mut task = Task::new(item.desc, item.due);
+        task.status = item.status;
+        Some((item.indent, task))
+    }))
+}
+
+/// Nest indented tasks, each a subtask of the closest task above it which is less indented
+///
+/// # Arguments
+///
+/// - `tasks` (`impl IntoIterator<Item = Option<(usize, Task)>>`) - the tasks in order with
+///   their indentation, a None ends a group so the next task is a top level one whatever its
+///   indentation
+///
+/// # Returns
+///
+/// - `Vec<Rc<RefCell<Task>>>` - the top level tasks
+pub fn nest(tasks: impl IntoIterator<Item = Option<(usize, Task)>>) -> Vec<Rc<RefCell<Task>>> {
+    let mut roots = Vec::new();
+    // the tasks which may still get subtasks, from the top level down
+    let mut open: Vec<(usize, Rc<RefCell<Task>>)> = Vec::new();
+    for entry in tasks {
+        let Some((indent, task)) = entry else {
+            open.clear();
+            continue;
+        };
+        while open.last().is_some_and(|(other, _)| *other >= indent) {
+            open.pop();
+        }
+        let task = Rc::new(RefCell::new(task));
+        match open.last() {
+            Some((_, parent)) => parent.borrow_mut().add_child(task.clone()),
+            None => roots.push(task.clone()),
+        }
+        open.push((indent, task));
+    }
+    roots
+}
+
+/// Import a checklist at the end of the todo list of a workspace
+///
+/// # Arguments
+///
+/// - `datas` (`&mut Datas`) - the data to add the tasks to
+/// - `workspace` (`Option<&str>`) - the path or the name of the workspace, the Inbox if None
+/// - `text` (`&str`) - the Markdown text
+/// - `on_clash` (`OnClash`) - import into the workspace, or into a new one beside it
+///
+/// # Returns
+///
+/// - `Result<(String, usize), String>` - the path of the workspace and the number of tasks
+///   imported, or why nothing was imported
+pub fn import(
+    datas: &mut Datas,
+    workspace: Option<&str>,
+    text: &str,
+    on_clash: OnClash,
+) -> Result<(String, usize), String> {
+    let tasks = parse_checklist(text);
+    if tasks.is_empty() {
+        return Err("no list items to import".to_string());
+    }
+    let ws_id = match (workspace, on_clash) {
+        (Some(name), OnClash::Merge) => datas.find_workspace(name)?,
+        (Some(name), OnClash::KeepBoth) => datas.add_beside(name),
+        (None, _) => datas.ensure_inbox(),
+    };
+    Ok(append(datas, ws_id, tasks))
+}
+
+/// Add task trees at the end of the todo list of a workspace, the list is made if missing
+///
+/// # Arguments
+///
+/// - `datas` (`&mut Datas`) - the data to add the tasks to
+/// - `ws_id` (`Uuid`) - the id of an active workspace
+/// - `tasks` (`Vec<Rc<RefCell<Task>>>`) - the top level tasks to add
+///
+/// # Returns
+///
+/// - `(String, usize)` - the path of the workspace and the number of tasks added, subtasks
+///   included
+pub fn append(datas: &mut Datas, ws_id: Uuid, tasks: Vec<Rc<RefCell<Task>>>) -> (String, usize) {
+    let list = datas.todolist.list_of(ws_id).unwrap_or_else(|| {
+        let list = Rc::new(RefCell::new(TodoList::new(ws_id)));
+        datas.todolist.add_list(list.clone());
+        list
+    });
+    let imported = count(&tasks);
+    tasks
+        .into_iter()
+        .for_each(|task| list.borrow_mut().add_task(task));
+    (datas.workspace_paths()[&ws_id].clone(), imported)
+}
+
+/// Count the tasks of task trees, subtasks included
+pub fn count(tasks: &[Rc<RefCell<Task>>]) -> usize {
+    tasks
+        .iter()
+        .map(|task| 1 + count(&task.borrow().children))
+        .sum()
+}
@@ -0,0 +1,120 @@
+//! The names given to the workspaces and tasks added without one
+//!
+//! Enter pressed in the popup of `a` or `A` with nothing typed either cancels the add or
+//! names the new item after its kind, numbered past its siblings, as the [`EmptyName`] of the
+//! configuration says. The workspace created for a task added with nothing selected is
+//! numbered the same way, so it never takes the name of another top level workspace.
+//!
+//! A workspace recovered beside another of the same name is renamed by [`recovered_name`],
+//! and one imported beside it by [`imported_name`].
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// What Enter does in the popup adding a workspace or a task with nothing typed
+///
+/// # Variants
+///
+/// - `Cancel` - nothing is added, the prompt says the add was cancelled
+/// - `Generate` - the item is added with a name of [`unique_name`], e.g. `Task 3`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmptyName {
+    #[default]
+    Cancel,
+    Generate,
+}
+
+/// The first of `{base} 1`, `{base} 2`, ... none of the siblings is named
+///
+/// # Arguments
+///
+/// - `base` (`&str`) - the name before the number, e.g. `Task`
+/// - `siblings` (`impl IntoIterator<Item = impl AsRef<str>>`) - the names of the items next to
+///   the new one
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::names::unique_name;
+///
+/// assert_eq!(unique_name("Task", Vec::<String>::new()), "Task 1");
+/// assert_eq!(unique_name("Task", ["Task 1", "Task 3"]), "Task 2");
+/// assert_eq!(unique_name("Workspace", ["Workspace", "Workspace 1"]), "Workspace 2");
+/// ```
+pub fn unique_name(base: &str, siblings: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    first_free(siblings, |n| format!("{} {}", base, n)).unwrap_or_else(|| base.to_string())
+}
+
+/// The name of a workspace recovered beside a namesake, `{name} (recovered)`, numbered from 2
+/// if a sibling has it already
+///
+/// # Arguments
+///
+/// - `name` (`&str`) - the name of the recovered workspace
+/// - `siblings` (`impl IntoIterator<Item = impl AsRef<str>>`) - the names of the workspaces
+///   it joins
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::names::recovered_name;
+///
+/// assert_eq!(recovered_name("Website", ["Website"]), "Website (recovered)");
+/// assert_eq!(
+///     recovered_name("Website", ["Website", "Website (recovered)"]),
+///     "Website (recovered 2)"
+/// );
+/// ```
+pub fn recovered_name(name: &str, siblings: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    first_free(siblings, |n| match n {
+        1 => format!("{} (recovered)", name),
+        n => format!("{} (recovered {})", name, n),
+    })
+    .unwrap_or_else(|| name.to_string())
+}
+
+/// The name of a workspace imported beside a namesake, `{name} (imported)`, numbered from 2
+/// if a sibling has it already
+///
+/// # Arguments
+///
+/// - `name` (`&str`) - the name given to the import
+/// - `siblings` (`impl IntoIterator<Item = impl AsRef<str>>`) - the names of the top level
+///   workspaces
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::names::imported_name;
+///
+/// assert_eq!(imported_name("Website", ["Website"]), "Website (imported)");
+/// assert_eq!(
+///     imported_name("Website", ["Website", "Website (imported)"]),
+///     "Website (imported 2)"
+/// );
+/// ```
+pub fn imported_name(name: &str, siblings: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    first_free(siblings, |n| match n {
+        1 => format!("{} (imported)", name),
+        n => format!("{} (imported {})", name, n),
+    })
+    .unwrap_or_else(|| name.to_string())
+}
+
+/// The first of the names numbered 1, 2, ... none of the siblings has
+///
+/// # Arguments
+///
+/// - `siblings` (`impl IntoIterator<Item = impl AsRef<str>>`) - the names taken
+/// - `candidate` (`impl Fn(usize) -> String`) - the name numbered `n`
+fn first_free(
+    siblings: impl IntoIterator<Item = impl AsRef<str>>,
+    candidate: impl Fn(usize) -> String,
+) -> Option<String> {
+    let taken: HashSet<String> = (siblings.into_iter())
+        .map(|name| name.as_ref().to_string())
+        .collect();
+    (1..).map(candidate).find(|name| !taken.contains(name))
+}
@@ -0,0 +1,171 @@
+//! The dates typed by the user, parsed the same way wherever they are typed
+//!
+//! The due date popup, the due date edited in the row of a task, the due date set on every
+//! task matching the filter and the dates of a Markdown checklist all go through this module,
+//! so a date means the same thing everywhere.
+//!
+//! # Grammar
+//!
+//! A due date, see [`due`], is one of, leading and trailing spaces left out:
+//!
+//! - `none` or nothing - no due date
+//! - `2025-07-01` - that date, see [`iso_date`]
+//! - `+3d`, `+2w`, `+1m` - that many days, weeks or months from today
+//! - `3 days`, `2 weeks`, `1 month` - the same, anywhere in the text, so `in 3 days` works
+//! - `tomorrow`
+//! - `fri` or `friday` - the next Friday to come, today if today is a Friday
+//! - anything else - today
+//!
+//! A reminder may end the text of the due date popup, see [`reminder`]: `remind 3d` or
+//! `remind 3` for three days before the due date, `remind none` or `remind 0d` for none.
+//!
+//! The due date set on the tasks matching the filter may also be a shift of the date each
+//! task already has, see [`due_change`]: `+7d`, `-1w` or `+1m`.
+//!
+//! Parsing never fails and never panics: a date out of the calendar, e.g. `+99999999d`, is
+//! no due date, while a shift out of it leaves the date of the task as it is, see
+//! [`DueChange::apply`].
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use regex::Regex;
+
+use crate::app::{sources::Clock, ui::todolistwidget::DueChange};
+
+/// Parse a date written `%Y-%m-%d`, e.g. `2025-07-01`, the only form of a saved due date
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::app::parse::iso_date;
+///
+/// assert_eq!(iso_date("2025-07-01"), NaiveDate::from_ymd_opt(2025, 7, 1));
+/// assert_eq!(iso_date("2025-02-30"), None);
+/// assert_eq!(iso_date("tomorrow"), None);
+/// ```
+pub fn iso_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+}
+
+/// Parse a due date against the today of a clock, see the [module](self) documentation
+///
+/// # Arguments
+///
+/// - `text` (`&str`) - the text typed
+/// - `clock` (`&dyn Clock`) - the source of today's date
+///
+/// # Returns
+///
+/// - `Option<NaiveDate>` - the due date, None for `none` or an empty text which clear it
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::app::{parse, sources::FixedClock};
+///
+/// // a Wednesday
+/// let clock = FixedClock(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap());
+/// assert_eq!(parse::due("+3d", &clock), NaiveDate::from_ymd_opt(2025, 7, 5));
+/// assert_eq!(parse::due("fri", &clock), NaiveDate::from_ymd_opt(2025, 7, 4));
+/// assert_eq!(parse::due("none", &clock), None);
+/// ```
+pub fn due(text: &str, clock: &dyn Clock) -> Option<NaiveDate> {
+    let text = text.trim();
+    if text.is_empty() || text.eq_ignore_ascii_case("none") {
+        return None;
+    }
+    if let Some(date) = iso_date(text) {
+        return Some(date);
+    }
+    let short_re = Regex::new(r"^\+(\d+)([dwm])$").unwrap();
+    let day_re = Regex::new(r"(\d+) days?").unwrap();
+    let week_re = Regex::new(r"(\d+) weeks?").unwrap();
+    let month_re = Regex::new(r"(\d+) months?").unwrap();
+    let today = clock.today();
+    let num = |caped: &regex::Captures| caped[1].parse::<u64>().unwrap_or(u64::MAX);
+    let weeks = |caped: &regex::Captures| Days::new(num(caped).saturating_mul(7));
+    let months =
+        |caped: &regex::Captures| Months::new(u32::try_from(num(caped)).unwrap_or(u32::MAX));
+    if let Some(caped) = short_re.captures(text) {
+        match &caped[2] {
+            "d" => today.checked_add_days(Days::new(num(&caped))),
+            "w" => today.checked_add_days(weeks(&caped)),
+            _ => today.checked_add_months(months(&caped)),
+        }
+    } else if let Some(caped) = day_re.captures(text) {
+        today.checked_add_days(Days::new(num(&caped)))
+    } else if let Some(caped) = week_re.captures(text) {
+        today.checked_add_days(weeks(&caped))
+    } else if let Some(caped) = month_re.captures(text) {
+        today.checked_add_months(months(&caped))
+    } else if text == "tomorrow" {
+        today.checked_add_days(Days::new(1))
+    } else if let Ok(weekday) = text.parse::<Weekday>() {
+        let ahead =
+            (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+        today.checked_add_days(Days::new(ahead as u64))
+    } else {
+        Some(today)
+    }
+}
+
+/// Split the reminder off the text typed into the due date popup, e.g. `fri remind 3d`
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::parse::reminder;
+///
+/// assert_eq!(reminder("fri remind 3d"), ("fri", Some(Some(3))));
+/// assert_eq!(reminder("remind 2"), ("", Some(Some(2))));
+/// assert_eq!(reminder("+1w remind none"), ("+1w", Some(None)));
+/// assert_eq!(reminder("tomorrow"), ("tomorrow", None));
+/// ```
+///
+/// # Returns
+///
+/// - `(&str, Option<Option<u8>>)` - the due date typed, and the reminder in days before it
+///   if one was typed, None inside for `remind none` or `remind 0d` which clear it
+pub fn reminder(typed: &str) -> (&str, Option<Option<u8>>) {
+    let remind_re = Regex::new(r"(?i)(^|\s)remind\s+(none|(\d+)d?)\s*$").unwrap();
+    let Some(caped) = remind_re.captures(typed) else {
+        return (typed.trim(), None);
+    };
+    let days = caped.get(3).map(|days| {
+        days.as_str()
+            .parse::<u64>()
+            .map_or(u8::MAX, |days| days.min(u64::from(u8::MAX)) as u8)
+    });
+    let date = typed[..caped.get(0).map_or(0, |m| m.start())].trim();
+    (date, Some(days.filter(|days| *days > 0)))
+}
+
+/// Parse the due date typed for the tasks matching the filter
+///
+/// `+7d`, `-1w` or `+1m` shift the due date each task already has, anything else is read
+/// by [`due`] as the date to set, so `7 days` is a week from today.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::app::{parse, sources::FixedClock, ui::todolistwidget::DueChange};
+///
+/// let clock = FixedClock(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap());
+/// assert_eq!(parse::due_change("+1w", &clock), DueChange::ShiftDays(7));
+/// assert_eq!(parse::due_change("none", &clock), DueChange::Set(None));
+/// ```
+pub fn due_change(text: &str, clock: &dyn Clock) -> DueChange {
+    let shift_re = Regex::new(r"^([+-])(\d+)([dwm])$").unwrap();
+    let Some(caped) = shift_re.captures(text.trim()) else {
+        return DueChange::Set(due(text, clock));
+    };
+    let sign = if &caped[1] == "-" { -1 } else { 1 };
+    let num = caped[2].parse::<i64>().unwrap_or(i64::MAX);
+    match &caped[3] {
+        "d" => DueChange::ShiftDays(sign * num),
+        "w" => DueChange::ShiftDays(sign * num.saturating_mul(7)),
+        _ => DueChange::ShiftMonths(sign as i32 * i32::try_from(num).unwrap_or(i32::MAX)),
+    }
+}
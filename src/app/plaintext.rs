@@ -0,0 +1,173 @@
+//! Plain text lists
+//!
+//! `todo import-txt FILE` and the import popup of the todo list turn a plain text file into
+//! tasks, the way a list is jotted down in any editor:
+//!
+//! - every line with some text is a task, its text taken as is without the spaces around it
+//! - an indented line is a subtask of the closest line above it which is less indented, the
+//!   indentation may be spaces or tabs, a tab counting as
+//!   [`TAB_WIDTH`](crate::app::markdown::TAB_WIDTH) columns like in a checklist
+//! - a blank line, or a line of spaces only, ends a group: the next line is a top level task
+//!   whatever its indentation, so a file of paragraphs gives a parent task per paragraph
+//!
+//! Nothing in the text is read as a status or a due date, a list with boxes and dates is a
+//! [Markdown checklist](crate::app::markdown).
+//!
+//! A list typed on one line, `write tests; update docs; bump version`, is split on a
+//! delimiter instead, see [`split_tasks`], by `todo add --multi` and the add popups of the
+//! todo list.
+
+use std::{cell::RefCell, mem, rc::Rc};
+
+use crate::app::{
+    data::{Datas, OnClash},
+    markdown,
+    ui::todolistwidget::Task,
+};
+
+/// Parse a plain text list into task trees
+///
+/// # Arguments
+///
+/// - `text` (`&str`) - the text, a task per line
+///
+/// # Returns
+///
+/// - `Vec<Rc<RefCell<Task>>>` - the top level tasks, with the indented lines as their subtasks
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::plaintext::parse_list;
+///
+/// let tasks = parse_list("Groceries\n\tmilk\n\n  Laundry\n");
+/// assert_eq!(tasks.len(), 2);
+/// assert_eq!(tasks[0].borrow().children[0].borrow().desc, "milk");
+/// assert_eq!(tasks[1].borrow().desc, "Laundry");
+/// ```
+pub fn parse_list(text: &str) -> Vec<Rc<RefCell<Task>>> {
+    markdown::nest(text.lines().map(|line| {
+        let (indent, desc) = markdown::split_indent(line.trim_end());
+        (!desc.is_empty()).then(|| (indent, Task::new(desc.to_string(), None)))
+    }))
+}
+
+/// Split a line of tasks on a delimiter
+///
+/// The tasks are trimmed and the empty ones skipped. A delimiter between double quotes is
+/// part of the task, `"a; b"; c` gives two tasks, the quotes kept as typed.
+///
+/// # Arguments
+///
+/// - `text` (`&str`) - the line, e.g. `write tests; update docs`
+/// - `delimiter` (`char`) - the delimiter between the tasks, see
+///   [`Config::task_delimiter`](crate::app::config::Config::task_delimiter)
+///
+/// # Returns
+///
+/// - `Vec<String>` - the descriptions of the tasks, in order
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::plaintext::split_tasks;
+///
+/// let tasks = split_tasks("write tests; ; update docs ;bump version;", ';');
+/// assert_eq!(tasks, ["write tests", "update docs", "bump version"]);
+/// ```
+pub fn split_tasks(text: &str, delimiter: char) -> Vec<String> {
+    let mut tasks = Vec::new();
+    let mut task = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        if c == delimiter && !quoted {
+            tasks.push(mem::take(&mut task));
+            continue;
+        }
+        if c == '"' {
+            quoted = !quoted;
+        }
+        task.push(c);
+    }
+    tasks.push(task);
+    tasks
+        .iter()
+        .map(|task| task.trim())
+        .filter(|task| !task.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Add tasks typed on the command line at the end of the todo list of a workspace
+///
+/// # Arguments
+///
+/// - `datas` (`&mut Datas`) - the data to add the tasks to
+/// - `workspace` (`Option<&str>`) - the path or the name of the workspace, added at the top
+///   level if no workspace has it, the Inbox if None
+/// - `text` (`&str`) - the task, or the tasks if `delimiter` is given
+/// - `delimiter` (`Option<char>`) - the delimiter to split the text on, see [`split_tasks`]
+///
+/// # Returns
+///
+/// - `Result<(String, usize), String>` - the path of the workspace and the number of tasks
+///   added, or why nothing was added
+pub fn add(
+    datas: &mut Datas,
+    workspace: Option<&str>,
+    text: &str,
+    delimiter: Option<char>,
+) -> Result<(String, usize), String> {
+    let descs = match delimiter {
+        Some(delimiter) => split_tasks(text, delimiter),
+        None => [text.trim()]
+            .into_iter()
+            .filter(|desc| !desc.is_empty())
+            .map(String::from)
+            .collect(),
+    };
+    if descs.is_empty() {
+        return Err("no task to add".to_string());
+    }
+    let ws_id = match workspace {
+        Some(name) => datas.find_or_add_workspace(name)?,
+        None => datas.ensure_inbox(),
+    };
+    let tasks = descs
+        .into_iter()
+        .map(|desc| Rc::new(RefCell::new(Task::new(desc, None))))
+        .collect();
+    Ok(markdown::append(datas, ws_id, tasks))
+}
+
+/// Import a plain text list at the end of the todo list of a workspace
+///
+/// # Arguments
+///
+/// - `datas` (`&mut Datas`) - the data to add the tasks to
+/// - `workspace` (`Option<&str>`) - the path or the name of the workspace, added at the top
+///   level if no workspace has it, the Inbox if None
+/// - `text` (`&str`) - the text, a task per line
+/// - `on_clash` (`OnClash`) - import into the workspace, or into a new one beside it
+///
+/// # Returns
+///
+/// - `Result<(String, usize), String>` - the path of the workspace and the number of tasks
+///   imported, or why nothing was imported
+pub fn import(
+    datas: &mut Datas,
+    workspace: Option<&str>,
+    text: &str,
+    on_clash: OnClash,
+) -> Result<(String, usize), String> {
+    let tasks = parse_list(text);
+    if tasks.is_empty() {
+        return Err("no lines to import".to_string());
+    }
+    let ws_id = match (workspace, on_clash) {
+        (Some(name), OnClash::Merge) => datas.find_or_add_workspace(name)?,
+        (Some(name), OnClash::KeepBoth) => datas.add_beside(name),
+        (None, _) => datas.ensure_inbox(),
+    };
+    Ok(markdown::append(datas, ws_id, tasks))
+}
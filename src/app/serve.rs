@@ -0,0 +1,366 @@
+//! A line based command mode for scripts and editor plugins
+//!
+//! `todo serve --stdio` reads one JSON request per line on stdin and writes one JSON response
+//! per line on stdout, so other programs can work on the tasks without driving the terminal
+//! UI. The data file is read before every request and written after every change, the same
+//! file the application uses. Both take the lock of the data file while they read and write
+//! it, see [`data::lock_data`], and a running application asks before saving over what was
+//! changed here, see [`Ui::changed_elsewhere`](crate::app::ui::Ui::changed_elsewhere).
+//!
+//! # Requests
+//!
+//! Every request is an object naming its command in `cmd`. An optional `id`, of any JSON
+//! type, is copied into the response to match the two up.
+//!
+//! - `{"cmd":"add_task","desc":"buy milk","workspace":"Home","due":"2025-07-01"}` - add a
+//!   task, `workspace` and `due` are optional and a task without a workspace goes to the Inbox,
+//!   `due` is read like the due date popup, so `"+3d"`, `"fri"` or `"tomorrow"` work too, see
+//!   [`parse::due`]
+//! - `{"cmd":"list_tasks","workspace":"Home"}` - list the tasks, of every workspace if
+//!   `workspace` is left out
+//! - `{"cmd":"complete_task","task":"<task id>"}` - mark a task and its subtasks finished
+//! - `{"cmd":"query","filter":"milk is:stale","workspace":"Home"}` - list the tasks matching
+//!   a filter of the search panel, see [`Task::is_target`]
+//!
+//! A workspace is named by its path from the top level, `Work / Backend`, or by its own name
+//! when no other active workspace has the same.
+//!
+//! # Responses
+//!
+//! - `{"ok":true,"task":{...}}` - the added or completed task
+//! - `{"ok":true,"tasks":[{...}]}` - the listed tasks in the order of the screen, see
+//!   [`visit_tasks`]
+//! - `{"ok":false,"error":"..."}` - the request failed and nothing was changed
+//!
+//! A task is written as [`TaskInfo`]: `{"id":"...","desc":"buy milk","status":"Todo",
+//! "due":"2025-07-01","urgency":null,"workspace":"Home","parent":null}`.
+
+use std::{
+    cell::RefCell,
+    io::{self, BufRead, Write},
+    path::Path,
+    rc::Rc,
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::app::{
+    data::{self, Datas},
+    parse,
+    sources::SystemClock,
+    ui::todolistwidget::{Staleness, Task, TaskStatus, TodoList, Urgency},
+    visit::{Visit, VisitOptions, visit_tasks},
+};
+
+/// A request read from a line of stdin
+///
+/// # Fields
+///
+/// - `id` (`Option<serde_json::Value>`) - the request id copied into the response
+/// - `command` ([`Command`]) - what to do
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub id: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub command: Command,
+}
+
+/// The commands of the stdio mode, see the [module](self) documentation for their fields
+///
+/// # Variants
+///
+/// - `AddTask` - add a task to a workspace, or to the Inbox
+/// - `ListTasks` - list the tasks of a workspace, or of all of them
+/// - `CompleteTask` - mark a task finished, with its subtasks
+/// - `Query` - list the tasks matching a filter
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Command {
+    AddTask {
+        desc: String,
+        #[serde(default)]
+        workspace: Option<String>,
+        #[serde(default)]
+        due: Option<String>,
+    },
+    ListTasks {
+        #[serde(default)]
+        workspace: Option<String>,
+    },
+    CompleteTask {
+        task: Uuid,
+    },
+    Query {
+        filter: String,
+        #[serde(default)]
+        workspace: Option<String>,
+    },
+}
+
+/// A task as written in the responses
+///
+/// # Fields
+///
+/// - `id` (`Uuid`) - the id of the task, which `complete_task` takes
+/// - `desc` (`String`) - the description
+/// - `status` ([`TaskStatus`]) - the status
+/// - `due` (`Option<NaiveDate>`) - the due date
+/// - `urgency` (`Option<Urgency>`) - the urgency
+/// - `workspace` (`String`) - the path of the workspace of the task
+/// - `parent` (`Option<Uuid>`) - the id of the parent task, None for a top level task
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskInfo {
+    pub id: Uuid,
+    pub desc: String,
+    pub status: TaskStatus,
+    pub due: Option<NaiveDate>,
+    pub urgency: Option<Urgency>,
+    pub workspace: String,
+    pub parent: Option<Uuid>,
+}
+
+/// A response written as a line of stdout
+///
+/// # Fields
+///
+/// - `id` (`Option<serde_json::Value>`) - the id of the request, left out if it had none
+/// - `ok` (`bool`) - whether the request succeeded
+/// - `task` (`Option<TaskInfo>`) - the task added or completed
+/// - `tasks` (`Option<Vec<TaskInfo>>`) - the tasks listed or found
+/// - `error` (`Option<String>`) - why the request failed
+#[derive(Debug, Default, Serialize)]
+pub struct Response {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<serde_json::Value>,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<TaskInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<Vec<TaskInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Response {
+    fn task(task: TaskInfo) -> Self {
+        Self {
+            ok: true,
+            task: Some(task),
+            ..Default::default()
+        }
+    }
+
+    fn tasks(tasks: Vec<TaskInfo>) -> Self {
+        Self {
+            ok: true,
+            tasks: Some(tasks),
+            ..Default::default()
+        }
+    }
+
+    fn error(error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Default::default()
+        }
+    }
+}
+
+/// Run a command against the data
+///
+/// # Arguments
+///
+/// - `datas` (`&mut Datas`) - the data to read and change
+/// - `command` ([`Command`]) - what to do
+/// - `stale` ([`Staleness`]) - when a task is stale, for the `is:stale` filter word
+///
+/// # Returns
+///
+/// - `Result<(Response, bool), String>` - the response and whether the data changed, or why
+///   the command failed
+///
+/// # Examples
+///
+/// ```
+/// use todo::{Command, Datas, dispatch};
+/// use todo::app::{sources::SystemClock, ui::todolistwidget::Staleness};
+///
+/// let mut datas = Datas::default();
+/// let command = Command::AddTask {
+///     desc: "buy milk".to_string(),
+///     workspace: None,
+///     due: None,
+/// };
+/// let (_, changed) = dispatch(&mut datas, command, Staleness::new(&SystemClock, 30)).unwrap();
+/// assert!(changed);
+/// ```
+pub fn dispatch(
+    datas: &mut Datas,
+    command: Command,
+    stale: Staleness,
+) -> Result<(Response, bool), String> {
+    match command {
+        Command::AddTask {
+            desc,
+            workspace,
+            due,
+        } => {
+            let desc = desc.trim().to_string();
+            if desc.is_empty() {
+                return Err("the description of a task can't be empty".to_string());
+            }
+            let ws_id = match workspace {
+                Some(name) => datas.find_workspace(&name)?,
+                None => datas.ensure_inbox(),
+            };
+            let list = datas.todolist.list_of(ws_id).unwrap_or_else(|| {
+                let list = Rc::new(RefCell::new(TodoList::new(ws_id)));
+                datas.todolist.add_list(list.clone());
+                list
+            });
+            let due = due.and_then(|text| parse::due(&text, &SystemClock));
+            let task = Rc::new(RefCell::new(Task::new(desc, due)));
+            list.borrow_mut().add_task(task.clone());
+            let paths = datas.workspace_paths();
+            let info = info(Visit {
+                task: &task.borrow(),
+                depth: 0,
+                parent: None,
+                workspace: ws_id,
+                workspace_path: &paths[&ws_id],
+            });
+            Ok((Response::task(info), true))
+        }
+        Command::ListTasks { workspace } => {
+            let tasks = tasks(datas, workspace.as_deref(), |_| true)?;
+            Ok((Response::tasks(tasks), false))
+        }
+        Command::CompleteTask { task: id } => {
+            let found = tasks(datas, None, |task| task.id == id)?;
+            let Some(found) = found.into_iter().next() else {
+                return Err(format!("no task with id {}", id));
+            };
+            let task = datas
+                .todolist
+                .todolists
+                .iter()
+                .find_map(|list| find_task(&list.borrow().tasks, id))
+                .expect("the task was just listed");
+            Task::set_task_status(&task, TaskStatus::Finished);
+            let info = TaskInfo {
+                status: TaskStatus::Finished,
+                ..found
+            };
+            Ok((Response::task(info), true))
+        }
+        Command::Query { filter, workspace } => {
+            let tasks = tasks(datas, workspace.as_deref(), |task| {
+                task.is_target(filter.clone(), stale)
+            })?;
+            Ok((Response::tasks(tasks), false))
+        }
+    }
+}
+
+/// Handle a line of the stdio mode against the data file
+///
+/// # Arguments
+///
+/// - `path` (`&Path`) - the data file, read before the command and written after a change,
+///   locked meanwhile
+/// - `line` (`&str`) - the JSON request
+/// - `stale` ([`Staleness`]) - when a task is stale
+///
+/// # Returns
+///
+/// - `String` - the JSON response, without the line break
+pub fn handle_line(path: &Path, line: &str, stale: Staleness) -> String {
+    let response = match serde_json::from_str::<Request>(line) {
+        Err(err) => Response::error(format!("invalid request: {}", err)),
+        Ok(request) => {
+            // held from the read to the write, so the application can't save in between
+            let _lock = data::lock_data(path).ok();
+            let mut response = data::load_data(path)
+                .map_err(|err| err.to_string())
+                .and_then(|mut datas| {
+                    let (response, changed) = dispatch(&mut datas, request.command, stale)?;
+                    if changed {
+                        data::save_data(path, &datas).map_err(|err| err.to_string())?;
+                    }
+                    Ok(response)
+                })
+                .unwrap_or_else(Response::error);
+            response.id = request.id;
+            response
+        }
+    };
+    serde_json::to_string(&response).unwrap()
+}
+
+/// Answer the requests of `input` on `output` until the input ends
+///
+/// Blank lines are skipped, and every other line gets exactly one response line.
+///
+/// # Errors
+///
+/// Returns the I/O error if `input` can't be read or `output` can't be written
+pub fn serve(
+    path: &Path,
+    stale: Staleness,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(output, "{}", handle_line(path, &line, stale))?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// List the tasks kept by `keep`, of one workspace or of all, see [`visit_tasks`]
+fn tasks(
+    datas: &Datas,
+    workspace: Option<&str>,
+    keep: impl Fn(&Task) -> bool,
+) -> Result<Vec<TaskInfo>, String> {
+    let only = workspace
+        .map(|name| datas.find_workspace(name))
+        .transpose()?;
+    let mut found = Vec::new();
+    visit_tasks(datas, VisitOptions::everything(), |visit| {
+        if only.is_none_or(|id| id == visit.workspace) && keep(visit.task) {
+            found.push(info(visit));
+        }
+    });
+    Ok(found)
+}
+
+fn find_task(tasks: &[Rc<RefCell<Task>>], id: Uuid) -> Option<Rc<RefCell<Task>>> {
+    tasks.iter().find_map(|task| {
+        if task.borrow().id == id {
+            Some(task.clone())
+        } else {
+            find_task(&task.borrow().children, id)
+        }
+    })
+}
+
+fn info(visit: Visit) -> TaskInfo {
+    let task = visit.task;
+    TaskInfo {
+        id: task.id,
+        desc: task.desc.clone(),
+        status: task.status.clone(),
+        due: task.due,
+        urgency: task.urgency.clone(),
+        workspace: visit.workspace_path.to_string(),
+        parent: visit.parent,
+    }
+}
@@ -0,0 +1,123 @@
+//! A workspace as a message to paste into a chat
+//!
+//! `y` on a workspace copies its pending tasks as plain text, the name of the workspace and
+//! then a line per open task, the subtasks indented under their parent:
+//!
+//! ```text
+//! Work
+//! • Write the report (due Fri)
+//!   • Collect the numbers
+//! • Call the bank (overdue Jul 1)
+//! …and 7 more
+//! ```
+//!
+//! The finished and deprecated tasks are left out with their subtasks, the tasks are in the
+//! order of the list and at most [`Config::share_max_items`] lines of tasks are written. The
+//! message is meant for other people, so it isn't translated.
+//!
+//! [`Config::share_max_items`]: crate::app::config::Config::share_max_items
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+
+use crate::app::ui::todolistwidget::{SortRule, Task, TaskStatus};
+
+/// The bullet of a task line
+pub const BULLET: &str = "•";
+
+/// Write the pending tasks of a workspace as a message, see the [module](self) documentation
+///
+/// # Arguments
+///
+/// - `name` (`&str`) - the name of the workspace, the first line
+/// - `tasks` (`&[Rc<RefCell<Task>>]`) - the top level tasks of its todo list
+/// - `rule` ([`SortRule`]) - the order the list is shown in
+/// - `max_items` (`usize`) - the most task lines to write, 0 for all of them
+/// - `today` (`NaiveDate`) - the day the due dates are written against
+///
+/// # Returns
+///
+/// - `String` - the message, without a line break at the end
+///
+/// # Examples
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use chrono::NaiveDate;
+/// use todo::app::{share, ui::todolistwidget::{SortRule, Task}};
+///
+/// let today = NaiveDate::from_ymd_opt(2025, 7, 2).unwrap();
+/// let task = Task::new("Write".to_string(), NaiveDate::from_ymd_opt(2025, 7, 4));
+/// let tasks = vec![Rc::new(RefCell::new(task))];
+/// assert_eq!(
+///     share::message("Work", &tasks, SortRule::Manual, 0, today),
+///     "Work\n• Write (due Fri)"
+/// );
+/// ```
+pub fn message(
+    name: &str,
+    tasks: &[Rc<RefCell<Task>>],
+    rule: SortRule,
+    max_items: usize,
+    today: NaiveDate,
+) -> String {
+    let mut lines = Vec::new();
+    pending_lines(tasks, rule, 0, today, &mut lines);
+    let mut message = name.to_string();
+    if lines.is_empty() {
+        message.push_str("\nNothing pending");
+        return message;
+    }
+    let limit = if max_items == 0 {
+        lines.len()
+    } else {
+        max_items.min(lines.len())
+    };
+    for line in &lines[..limit] {
+        message.push('\n');
+        message.push_str(line);
+    }
+    if limit < lines.len() {
+        message.push_str(&format!("\n…and {} more", lines.len() - limit));
+    }
+    message
+}
+
+fn pending_lines(
+    tasks: &[Rc<RefCell<Task>>],
+    rule: SortRule,
+    depth: usize,
+    today: NaiveDate,
+    lines: &mut Vec<String>,
+) {
+    for task in rule.arrange(tasks) {
+        let task = task.borrow();
+        if matches!(task.status, TaskStatus::Finished | TaskStatus::Deprecated) {
+            continue;
+        }
+        let mut line = format!("{}{} {}", "  ".repeat(depth), BULLET, task.desc);
+        if let Some(due) = task.due {
+            line.push_str(&format!(" ({})", due_label(due, today)));
+        }
+        lines.push(line);
+        pending_lines(&task.children, rule, depth + 1, today, lines);
+    }
+}
+
+/// Write a due date the way people say it: `due today`, `due tomorrow`, `due Fri` within the
+/// week, `due Jul 14` later and `overdue Jul 1` before today
+///
+/// # Arguments
+///
+/// - `due` (`NaiveDate`) - the due date
+/// - `today` (`NaiveDate`) - the day to write it against
+pub fn due_label(due: NaiveDate, today: NaiveDate) -> String {
+    match (due - today).num_days() {
+        ..0 => format!("overdue {}", due.format("%b %-d")),
+        0 => "due today".to_string(),
+        1 => "due tomorrow".to_string(),
+        2..7 => format!("due {}", due.format("%a")),
+        _ => format!("due {}", due.format("%b %-d")),
+    }
+}
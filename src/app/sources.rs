@@ -0,0 +1,141 @@
+//! Id, clock and storage sources
+//!
+//! Tasks and workspaces get a fresh random id when they are created, and the due date
+//! coloring compares against today. Both go through the small traits of this module so the
+//! tests can pin them: [`SequentialIds`] hands out predictable ids and [`FixedClock`]
+//! always answers the same date. The application itself uses [`RandomIds`] and
+//! [`SystemClock`], which is what the plain constructors like `Task::new` default to.
+//!
+//! The saves of the UI are written through a [`Storage`] too, the file system with
+//! [`FileStorage`], so the tests can count the writes and change the file under the UI.
+
+use std::{cell::Cell, fmt, fs, path::Path, time::SystemTime};
+
+use chrono::{Local, NaiveDate};
+use uuid::Uuid;
+
+use crate::app::{data, errors};
+
+/// A source of ids for new tasks and workspaces
+pub trait IdGen {
+    /// Get the id for the next created item
+    fn next_id(&self) -> Uuid;
+}
+
+/// The real id source, a random v4 uuid every time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomIds;
+
+impl IdGen for RandomIds {
+    fn next_id(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// An id source counting up from a start value, for reproducible ids in tests
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::sources::{IdGen, SequentialIds};
+///
+/// let ids = SequentialIds::new(1);
+/// assert_eq!(ids.next_id(), uuid::Uuid::from_u128(1));
+/// assert_eq!(ids.next_id(), uuid::Uuid::from_u128(2));
+/// ```
+#[derive(Debug, Default)]
+pub struct SequentialIds {
+    next: Cell<u128>,
+}
+
+impl SequentialIds {
+    pub fn new(start: u128) -> Self {
+        Self {
+            next: Cell::new(start),
+        }
+    }
+}
+
+impl IdGen for SequentialIds {
+    fn next_id(&self) -> Uuid {
+        let id = self.next.get();
+        self.next.set(id + 1);
+        Uuid::from_u128(id)
+    }
+}
+
+/// A source of the current date
+pub trait Clock {
+    /// Get the date of today
+    fn today(&self) -> NaiveDate;
+}
+
+/// The real clock, the local date of the system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> NaiveDate {
+        Local::now().date_naive()
+    }
+}
+
+/// A clock stopped at a given date, for tests
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub NaiveDate);
+
+impl Clock for FixedClock {
+    fn today(&self) -> NaiveDate {
+        self.0
+    }
+}
+
+/// Where the saves of the UI are written
+pub trait Storage: fmt::Debug + Send + Sync {
+    /// Write a file, replacing its content
+    ///
+    /// # Errors
+    ///
+    /// Returns [`errors::Errors::SaveError`] if the file can't be written
+    fn write(&self, path: &Path, content: &str) -> Result<(), errors::Errors>;
+
+    /// When a file was last modified, None if it's unknown, in which case the file is never
+    /// taken for rolled back, see [`data::FileStamp`]
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+
+    /// Read a file, None if it can't be read, in which case it's never taken for rolled back
+    /// or changed elsewhere, see [`data::FileStamp`]
+    fn read(&self, _path: &Path) -> Option<String> {
+        None
+    }
+
+    /// Lock a data file against the other writers until the file returned is dropped, None if
+    /// there is no lock, see [`data::lock_data`]
+    fn lock(&self, _path: &Path) -> Option<fs::File> {
+        None
+    }
+}
+
+/// The real storage, the file system, see [`data::write_data`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileStorage;
+
+impl Storage for FileStorage {
+    fn write(&self, path: &Path, content: &str) -> Result<(), errors::Errors> {
+        data::write_data(path, content)
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|meta| meta.modified()).ok()
+    }
+
+    fn read(&self, path: &Path) -> Option<String> {
+        fs::read_to_string(path).ok()
+    }
+
+    fn lock(&self, path: &Path) -> Option<fs::File> {
+        data::lock_data(path).ok()
+    }
+}
@@ -20,40 +20,108 @@
 //! 2. Messages are processed by the handle_uimsg function
 //! 3. UI state is updated accordingly
 //! 4. The display is refreshed to reflect changes
+//!
+//! # Time
+//!
+//! The due date coloring asks a [`Clock`] for today's date
+//! rather than reading the system time inline, see [`todolistwidget::TodoWidget::due_span`].
+//! The widgets render with the system clock, tests can pass a fixed one.
+//!
+//! # Popups
+//!
+//! The popups (`get_input`, `input_due_date`, `delete_item`, `confirm_delete`,
+//! `edit_notes`, `show_messages`, `filter_find`, `pick_sort`, `pick_workspace`, `triage` and
+//! `pick_command`) take the key receiver from an async mutex for as long
+//! as they are open. If the key channel closes they are cancelled as if Esc was pressed, so
+//! they never spin on a dead channel and the receiver is always released. They draw to any
+//! ratatui backend, which lets the tests drive them with a `TestBackend`. The text inputs and
+//! the confirmations hold a [`popup::PopupGuard`] while open, so a cancelled one leaves the
+//! prompt, the hint bar and the focus as they were. The confirmations, `delete_item`,
+//! `confirm_delete`, `confirm_replay`, `confirm_renew_ids` and the others asking `y/n`, all go
+//! through [`Ui::confirm`], only [`Ui::confirm_inline`] asks in the status line instead.
 
 use std::cell::RefCell;
-use std::path::Path;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::vec;
 
-use chrono::{Days, Local, Months, NaiveDate};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style, Stylize};
-use ratatui::text::{Line, Text};
-use ratatui::widgets::{Block, Clear, List, ListState, Padding, Paragraph};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Clear, List, ListItem, ListState, Padding, Paragraph, Widget, Wrap};
 use ratatui::{
-    DefaultTerminal, Frame,
+    Frame, Terminal,
+    backend::Backend,
     layout::{Constraint, Layout},
 };
-use regex::Regex;
-use tokio::sync::mpsc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex as AsyncMutex, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant, MissedTickBehavior};
 use tui_textarea::TextArea;
+use unicode_width::UnicodeWidthStr;
+use uuid::Uuid;
 
-use crate::app::appstate::{AppState, CurrentFocus, CurrentMode};
-use crate::app::data::{self, Datas};
+use crate::app::about::{BuildInfo, Profile};
+use crate::app::appstate::{AppState, CurrentFocus, CurrentMode, Message};
+use crate::app::check;
+use crate::app::config::{self, Config};
+use crate::app::data::{self, Datas, FileStamp};
+use crate::app::diff;
+use crate::app::errors;
+use crate::app::journal::{self, Journal};
+use crate::app::links;
+use crate::app::markdown;
+use crate::app::names::{self, EmptyName};
+use crate::app::parse;
+use crate::app::plaintext;
+use crate::app::reduce;
+use crate::app::share;
+use crate::app::sources::{Clock, FileStorage, IdGen, Storage, SystemClock};
+use crate::app::ui::bell::Bell;
 use crate::app::ui::calendarwidget::CalendarWidget;
+use crate::app::ui::chrome::PopupKind;
 use crate::app::ui::helpwidget::HelpWidget;
+use crate::app::ui::keymap::ListContext;
+use crate::app::ui::matcher::{FILTER_DEBOUNCE, FilterMatcher};
+use crate::app::ui::overlay::{Layer, Overlays};
+use crate::app::ui::popup::PopupGuard;
 use crate::app::ui::prompt::PromptWidget;
-use crate::app::ui::todolistwidget::{Task, TaskStatus, TodoList, TodoWidget};
-use crate::app::ui::workspacewidget::Workspace;
+use crate::app::ui::strings::{tr, trf};
+use crate::app::ui::title::TerminalTitle;
+use crate::app::ui::todolistwidget::{
+    DueEdit, DueTask, ParentDone, SortRule, TASK_COLORS, Task, TaskStats, TaskStatus, TodoList,
+    TodoWidget, Urgency,
+};
+use crate::app::ui::tourwidget::{TourTarget, TourWidget};
+use crate::app::ui::tree::{Fold, Jump};
+use crate::app::ui::workspacewidget::{Workspace, WorkspaceOrder, WorkspaceType};
 
+pub mod bell;
 pub mod calendarwidget;
+pub mod chrome;
+pub mod clipboard;
+pub mod glyphs;
 pub mod helpwidget;
 pub mod keymap;
+pub mod keys;
+pub mod matcher;
+pub mod overlay;
+pub mod popup;
 pub mod prompt;
+pub mod strings;
+pub mod theme;
+pub(crate) mod title;
 pub mod todolistwidget;
+pub(crate) mod tourwidget;
+pub mod tree;
 pub mod workspacewidget;
 use workspacewidget::WorkspaceWidget;
 
@@ -80,13 +148,15 @@ use workspacewidget::WorkspaceWidget;
 /// - `UpdateUi` - Request to refresh/redraw the UI
 /// - `SaveData` - Request to save application data to file
 /// - `WAction(WidgetAction)` - Widget-specific action to perform
+/// - `Replay(Entry)` - Action of the journal to perform again, see [`journal`]
+/// - `Edit(KeyEvent)` - Key for the open popup
 ///
 /// # Examples
 ///
 /// ```
-/// use crate::app::ui::{UiMessage, WidgetAction};
-/// use crate::app::ui::todolistwidget::TaskStatus;
-/// use crate::app::appstate::CurrentFocus;
+/// use todo::app::ui::{UiMessage, WidgetAction};
+/// use todo::app::ui::todolistwidget::TaskStatus;
+/// use todo::app::appstate::CurrentFocus;
 ///
 /// // Example messages
 /// let update_msg = UiMessage::Update;
@@ -94,7 +164,7 @@ use workspacewidget::WorkspaceWidget;
 /// let save_msg = UiMessage::SaveData;
 /// let widget_action_msg = UiMessage::WAction(WidgetAction::AddWorkspace);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum UiMessage {
     /// Request to update application data
     Update,
@@ -104,6 +174,11 @@ pub enum UiMessage {
     SaveData,
     /// Widget-specific action to perform
     WAction(WidgetAction),
+    /// Action of the journal to perform again, on its selection and with its keys
+    Replay(journal::Entry),
+    /// Key for the open popup, which the message handler hands to the key stream of the
+    /// popup. One reaching the UI loop is kept for the next popup, see [`keys::feed`]
+    Edit(KeyEvent),
 }
 
 /// Widget Action for changing widget states and performing operations
@@ -126,15 +201,15 @@ pub enum UiMessage {
 /// 6. Task status changes (MarkTaskStatus)
 /// 7. Workspace management (ArchiveWS, RecoveryWS)
 /// 8. Item management (Rename, Filter)
-/// 9. Help system (Help, ExitHelp)
+/// 9. Help system (Help, ExitHelp, NextHelpTab, PrevHelpTab)
 /// 10. Date management (Due)
 ///
 /// # Examples
 ///
 /// ```
-/// use crate::app::ui::WidgetAction;
-/// use crate::app::ui::todolistwidget::TaskStatus;
-/// use crate::app::appstate::CurrentFocus;
+/// use todo::app::ui::WidgetAction;
+/// use todo::app::ui::todolistwidget::TaskStatus;
+/// use todo::app::appstate::CurrentFocus;
 ///
 /// // Example widget actions
 /// let add_workspace = WidgetAction::AddWorkspace;
@@ -142,7 +217,7 @@ pub enum UiMessage {
 /// let mark_complete = WidgetAction::MarkTaskStatus(TaskStatus::Finished);
 /// let rename = WidgetAction::Rename(CurrentFocus::Workspace);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WidgetAction {
     /// Add a new workspace at the root level
     AddWorkspace,
@@ -164,6 +239,8 @@ pub enum WidgetAction {
     FocusTodolist,
     /// Focus on the archived workspace widget
     FocusArchivedWorkspace,
+    /// Focus back on the panel the current todo list was entered from
+    FocusBack,
 
     /// Enter a workspace to view its tasks
     EnterWorkspace,
@@ -194,16 +271,166 @@ pub enum WidgetAction {
     Help,
     /// Exit the help screen
     ExitHelp,
+    /// Show the next tab of the help screen
+    NextHelpTab,
+    /// Show the previous tab of the help screen
+    PrevHelpTab,
     /// Set due date for the current task
     Due,
 
-    /// Increse task urgency
-    IncreseUrgency,
-    /// Decrese task urgency
-    DecreseUrgency,
+    /// Increase task urgency
+    #[serde(alias = "IncreseUrgency")]
+    IncreaseUrgency,
+    /// Decrease task urgency
+    #[serde(alias = "DecreseUrgency")]
+    DecreaseUrgency,
 
     /// Sort the task
     Sort,
+    /// Switch the archived workspaces between their saved order and their names, see
+    /// [`WorkspaceWidget::toggle_order`]
+    ToggleArchivedOrder,
+    /// Move the current workspace of a panel among its siblings, see
+    /// [`WorkspaceWidget::shift_current`]
+    ShiftWorkspace(WorkspaceType, SelectBF),
+
+    /// Start the guided tour
+    Tour,
+    /// Show the next card of the guided tour
+    TourNext,
+    /// Skip the rest of the guided tour
+    TourSkip,
+    /// Show the history of the prompt messages
+    Messages,
+    /// Show the version, the commit, the data file and the profile, see [`about`](crate::app::about)
+    About,
+    /// Move the selection to the parent, the first child or a sibling
+    Jump(Jump),
+    /// Collapse or expand the items of the focused panel, see [`tree::fold`]
+    Fold(Fold),
+    /// Show or hide the archived panel, overriding the auto-hide
+    ToggleArchived,
+    /// Walk through the Inbox tasks one at a time
+    Triage,
+    /// Fold or unfold the subtasks of the current task
+    EnterTask,
+    /// Copy the pending tasks of the current workspace to the clipboard as a message
+    CopyWorkspace,
+    /// Protect the current workspace from deletion and archiving, or lift the protection
+    ToggleProtected,
+    /// Pick an action by its name and run it
+    Palette,
+    /// List the keys of the hint bar, and run the one pressed
+    KeyHelp,
+    /// Search the tasks and the workspace names of every workspace, and jump to the one picked
+    SearchAll,
+    /// Show the open tasks due each day of a month, and jump to one of them
+    Heatmap,
+    /// Edit the due date of the current task in its row, without the popup
+    DueInline,
+    /// Set or shift the due date of all the tasks matching the filter
+    DueMatching,
+    /// Show the task counts and dates of the current workspace and its sub workspaces
+    WorkspaceStats,
+    /// Pick the color of the description of the current task, or clear it
+    TaskColor,
+    /// Write the notes of the current task in a popup of several lines
+    EditNotes,
+    /// Open a link of the current task in the browser, picking one if there are several
+    OpenLink,
+    /// Delete the finished and deprecated subtasks of the current task, after a confirmation
+    ClearDone,
+    /// Switch the numbers before the rows of the task list, see
+    /// [`RowNumbers`](todolistwidget::RowNumbers)
+    ToggleRowNumbers,
+    /// Select a row of the task list by its number, typed in a popup
+    GoToRow,
+    /// Add the tasks of a text or Markdown file, its path typed in a popup, to the todo list
+    ImportFile,
+    /// Select the task of the due banner and hide the banner
+    BannerJump,
+    /// Hide the due banner
+    DismissBanner,
+    /// Show a message in the prompt
+    Prompt(String),
+}
+
+impl WidgetAction {
+    /// Whether the action may change the saved data, used to mark the data as unsaved
+    pub fn modifies_data(&self) -> bool {
+        matches!(
+            self,
+            WidgetAction::AddWorkspace
+                | WidgetAction::AddWorkspaceChild
+                | WidgetAction::AddTask
+                | WidgetAction::AddTaskChild
+                | WidgetAction::DeleteWorkspace
+                | WidgetAction::DeleteArchivedWorkspace
+                | WidgetAction::DeleteTask
+                | WidgetAction::ClearDone
+                | WidgetAction::ImportFile
+                | WidgetAction::MarkTaskStatus(_)
+                | WidgetAction::ArchiveWS
+                | WidgetAction::RecoveryWS
+                | WidgetAction::Rename(_)
+                | WidgetAction::Due
+                | WidgetAction::DueInline
+                | WidgetAction::DueMatching
+                | WidgetAction::IncreaseUrgency
+                | WidgetAction::DecreaseUrgency
+                | WidgetAction::Sort
+                | WidgetAction::ToggleArchivedOrder
+                | WidgetAction::ShiftWorkspace(..)
+                | WidgetAction::TourNext
+                | WidgetAction::Triage
+                | WidgetAction::EnterTask
+                | WidgetAction::Fold(_)
+                | WidgetAction::ToggleProtected
+                | WidgetAction::TaskColor
+                | WidgetAction::EditNotes
+        )
+    }
+
+    /// The mode of the popup the action opens, None if it opens none
+    ///
+    /// The mode is entered before the action is handled and left once it's done, see
+    /// [`AppState::open_popup`], so the keys typed meanwhile go to the popup.
+    pub fn popup_mode(&self) -> Option<CurrentMode> {
+        match self {
+            WidgetAction::AddWorkspace
+            | WidgetAction::AddWorkspaceChild
+            | WidgetAction::AddTask
+            | WidgetAction::AddTaskChild
+            | WidgetAction::DeleteWorkspace
+            | WidgetAction::DeleteArchivedWorkspace
+            | WidgetAction::DeleteTask
+            | WidgetAction::Rename(_)
+            | WidgetAction::Filter
+            | WidgetAction::ArchiveWS
+            | WidgetAction::RecoveryWS
+            | WidgetAction::Due
+            | WidgetAction::DueInline
+            | WidgetAction::DueMatching
+            | WidgetAction::Triage
+            | WidgetAction::EnterTask
+            | WidgetAction::Palette
+            | WidgetAction::KeyHelp
+            | WidgetAction::SearchAll
+            | WidgetAction::Heatmap
+            | WidgetAction::WorkspaceStats
+            | WidgetAction::TaskColor
+            | WidgetAction::EditNotes
+            | WidgetAction::OpenLink
+            | WidgetAction::ClearDone
+            | WidgetAction::GoToRow
+            | WidgetAction::ImportFile
+            | WidgetAction::MarkTaskStatus(TaskStatus::Deprecated)
+            | WidgetAction::Messages
+            | WidgetAction::About => Some(CurrentMode::Insert),
+            WidgetAction::Sort => Some(CurrentMode::Sort),
+            _ => None,
+        }
+    }
 }
 
 /// Selection direction for navigating lists
@@ -219,7 +446,7 @@ pub enum WidgetAction {
 /// # Examples
 ///
 /// ```
-/// use crate::app::ui::SelectBF;
+/// use todo::app::ui::SelectBF;
 ///
 /// // Example usage in a selection function
 /// fn move_selection(direction: SelectBF) {
@@ -229,7 +456,7 @@ pub enum WidgetAction {
 ///     }
 /// }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SelectBF {
     /// Move selection backward (up/left)
     Back,
@@ -251,7 +478,7 @@ pub enum SelectBF {
 /// # Examples
 ///
 /// ```
-/// use crate::app::ui::SearchEvent;
+/// use todo::app::ui::SearchEvent;
 ///
 /// // Example usage in a search handler
 /// fn handle_search_event(event: SearchEvent) {
@@ -262,7 +489,7 @@ pub enum SelectBF {
 ///     }
 /// }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SearchEvent {
     /// Move to the next search result
     Next,
@@ -272,6 +499,122 @@ pub enum SearchEvent {
     Exit,
 }
 
+/// What to do when the data file can't be written, picked in [`Ui::resolve_unwritable`]
+///
+/// # Variants
+///
+/// - `ReadOnly` - go on without saving, see [`Ui::read_only`]
+/// - `Moved(PathBuf)` - save to another file from now on
+/// - `Quit` - leave the app before anything is changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unwritable {
+    ReadOnly,
+    Moved(PathBuf),
+    Quit,
+}
+
+/// What to do with a data file rolled back or changed under the app, picked in
+/// [`Ui::resolve_rollback`]
+///
+/// # Variants
+///
+/// - `Keep` - save the data of the app over the file
+/// - `Reload` - drop the changes of the app and show the data of the file
+/// - `Later` - save nothing for now, the next save asks again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rollback {
+    Keep,
+    Reload,
+    Later,
+}
+
+/// What to do with a workspace recovered beside another of the same name, picked in
+/// [`Ui::resolve_clash`]
+///
+/// # Variants
+///
+/// - `KeepBoth` - recover it beside the other, named by [`names::recovered_name`]
+/// - `Merge` - add its tasks and sub workspaces to the other, which it leaves the archive for
+/// - `Cancel` - leave it in the archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    KeepBoth,
+    Merge,
+    Cancel,
+}
+
+/// How long the save requests are gathered before the data is written once for all of them,
+/// see [`Ui::handle_uimsg`]
+pub const SAVE_WINDOW: Duration = Duration::from_millis(200);
+
+/// When the data is saved without `ctrl-s`, see [`Config::autosave`]
+///
+/// # Variants
+///
+/// - `Off` - only when asked for
+/// - `OnBlur` - when the focus goes to another panel, another todo list is shown or the help
+///   page is opened, the pauses between edits
+/// - `Interval` - once a minute, on the tick redrawing the due dates
+///
+/// Either way the data is saved only if it changed, and the saves asked for meanwhile are
+/// written once, see [`SAVE_WINDOW`], so cycling through the panels writes once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Autosave {
+    #[default]
+    Off,
+    OnBlur,
+    Interval,
+}
+
+/// What a save written gives: the archive written if it changed and the stamp of the data file
+type Written = (Option<String>, Option<FileStamp>);
+
+/// A save being written in the background, see [`Ui::handle_uimsg`]
+///
+/// # Fields
+///
+/// - `handle` (`JoinHandle<Result<Written, errors::Errors>>`) - the write, see [`Written`]
+/// - `journal_mark` (`u64`) - the length of the journal when the data was taken
+/// - `changes` (`usize`) - the changes the save holds, see [`Ui::changes`]
+#[derive(Debug)]
+struct Saving {
+    handle: JoinHandle<Result<Written, errors::Errors>>,
+    journal_mark: u64,
+    changes: usize,
+}
+
+/// How long the due banner stays up without a key press, it's hidden on the next tick after
+pub const BANNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The banner across the top announcing a task that is due, see [`Ui::announce_due`]
+///
+/// # Fields
+///
+/// - `workspace` (`Uuid`) - the id of the workspace of the task
+/// - `task` (`Rc<RefCell<Task>>`) - the task, selected by `O`
+/// - `text` (`String`) - the announcement, e.g. `'ship release' is due today — press O to jump`
+/// - `shown_at` (`Instant`) - when it was shown, to hide it after [`BANNER_TIMEOUT`]
+#[derive(Debug, Clone)]
+pub struct DueBanner {
+    pub workspace: Uuid,
+    pub task: Rc<RefCell<Task>>,
+    pub text: String,
+    pub shown_at: Instant,
+}
+
+/// A result of the search of every workspace, see [`Ui::search_all`]
+///
+/// # Variants
+///
+/// - `Task` - a task of an active workspace, with the id of its workspace
+/// - `Workspace` - a workspace whose name matches, active or archived
+#[derive(Debug, Clone)]
+pub enum SearchHit {
+    Task(Uuid, Rc<RefCell<Task>>),
+    Workspace(WorkspaceType, Rc<RefCell<Workspace>>),
+}
+
 /// The Basic Structure of the UI
 ///
 /// This struct represents the main UI component that orchestrates all the
@@ -285,15 +628,24 @@ pub enum SearchEvent {
 /// - `archived_ws` ([`WorkspaceWidget`]) - The archived workspace widget for displaying archived workspaces
 /// - `helpwidget` ([`HelpWidget`]) - The help widget for displaying keybindings and help information
 /// - `prompt` ([`PromptWidget`]) - The prompt widget for displaying status messages
+/// - `tour` (`TourWidget`) - The guided tour shown on first run
+/// - `config` ([`Config`]) - The user configuration
+/// - `archived_shown` (`Option<bool>`) - Whether the archived panel was shown or hidden with the
+///   toggle key, overriding the auto-hide
+/// - `data_path` (`PathBuf`) - The file the data is saved to
+/// - `appstate` (`Arc<Mutex<AppState>>`) - The state of the app, read when rendering the mode badge
+/// - `dirty` (`bool`) - Whether the data changed since the last successful save
+/// - `changes` (`usize`) - The actions changing the data since the last save
+/// - `storage` (`Arc<dyn Storage>`) - Where the saves are written
 /// - `ui_rx` (`mpsc::Receiver<UiMessage>`) - Receiver for UI messages to process
-/// - `input_rx` (`Arc<Mutex<mpsc::Receiver<KeyEvent>>>`) - Receiver for keyboard input events
+/// - `input_rx` (`Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>`) - Receiver for keyboard input events
 ///
 /// # Examples
 ///
 /// ```
-/// use tokio::sync::mpsc;
+/// use tokio::sync::{Mutex as AsyncMutex, mpsc};
 /// use crossterm::event::KeyEvent;
-/// use crate::app::ui::{Ui, UiMessage};
+/// use todo::app::ui::{Ui, UiMessage};
 ///
 /// // Create channels for communication
 /// let (ui_tx, ui_rx) = mpsc::channel(100);
@@ -314,10 +666,53 @@ pub struct Ui {
     pub helpwidget: HelpWidget,
     /// The prompt widget for displaying status messages
     pub prompt: PromptWidget,
+    /// The guided tour shown on first run
+    pub(crate) tour: TourWidget,
+    /// The user configuration
+    pub config: Config,
+    /// The archived panel visibility chosen with the toggle key, None to auto-hide
+    pub archived_shown: Option<bool>,
+    /// The file the data is saved to
+    pub data_path: PathBuf,
+    /// Where the data comes from, shown in the about popup
+    pub profile: Profile,
+    /// The content of the archive file as last read or written, see [`Ui::archive_content`]
+    pub archive_saved: String,
+    /// The journal of the actions since the last save, None when nothing is journaled
+    pub journal: Option<Journal>,
+    /// The state of the app, read when rendering the mode badge
+    pub appstate: Arc<Mutex<AppState>>,
+    /// Whether the data changed since the last successful save
+    pub dirty: bool,
+    /// The actions changing the data since the last save, counted in the prompt once saved
+    pub changes: usize,
+    /// Where the saves are written, the file system but in the tests
+    pub storage: Arc<dyn Storage>,
+    /// The data file as the last load or save of the session left it, see [`Ui::rolled_back`]
+    /// and [`Ui::changed_elsewhere`]
+    pub saved_stamp: Option<FileStamp>,
+    /// Whether saving is turned off because the data file can't be written
+    pub read_only: bool,
+    /// Whether a message changed what is shown since the last frame, see [`Ui::handle_uimsg`]
+    pub needs_redraw: bool,
+    /// Messages of the actions run from the command palette, handled before the next received one
+    pub pending: VecDeque<UiMessage>,
+    /// The day the overdue tasks were last escalated, so the tick escalates once a day
+    pub escalated_on: Option<NaiveDate>,
+    /// The banner of a due task, shown above the panels until a key is pressed
+    pub banner: Option<DueBanner>,
+    /// The layers open over the panels, see the [overlay] module
+    pub overlays: Overlays,
+    /// The day each task was last announced in the banner, so a task is announced once a day
+    pub announced: HashMap<Uuid, NaiveDate>,
+    /// Whether the bell rings after the next frame, see [`Ui::ring`]
+    pub bell: bool,
+    /// The title of the terminal window, None when it's left alone
+    pub(crate) title: Option<TerminalTitle>,
     /// Receiver for UI messages to process
     pub ui_rx: mpsc::Receiver<UiMessage>,
     /// Receiver for keyboard input events
-    pub input_rx: Arc<Mutex<mpsc::Receiver<KeyEvent>>>,
+    pub input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
 }
 
 pub trait SelectAction<T> {
@@ -342,19 +737,19 @@ pub trait SelectAction<T> {
         bf: SelectBF,
     ) -> Option<Rc<RefCell<T>>>;
 
-    /// Get a flattened vector of T from a vector of [`T`] which might have nested [`T`] (children).
+    /// Get a flattened vector of T from a vector of `T` which might have nested `T` (children).
     ///
     /// This function recursively traverses a hierarchical structure of items (such as nested workspaces
     /// or tasks with subtasks) and returns a flat list of all items.
     ///
     /// # Arguments
     ///
-    /// - `target` (`&Vec<Rc<RefCell<T>>>`) - The target vector to be flattened
+    /// - `target` (`&[Rc<RefCell<T>>]`) - The target vector to be flattened
     ///
     /// # Returns
     ///
     /// - `Vec<Rc<RefCell<T>>>` - The flattened vector containing all items from the hierarchy
-    fn get_flattened(target: &Vec<Rc<RefCell<T>>>) -> Vec<Rc<RefCell<T>>>;
+    fn get_flattened(target: &[Rc<RefCell<T>>]) -> Vec<Rc<RefCell<T>>>;
 }
 
 impl Ui {
@@ -365,8 +760,29 @@ impl Ui {
             archived_ws: WorkspaceWidget::new(workspacewidget::WorkspaceType::Archived),
             helpwidget: HelpWidget::new(),
             prompt: PromptWidget::new(),
+            tour: TourWidget::new(),
+            config: Config::default(),
+            archived_shown: None,
+            data_path: PathBuf::new(),
+            profile: Profile::default(),
+            archive_saved: data::serialize_archive(&data::Archive::default()),
+            journal: None,
+            appstate: Arc::new(Mutex::new(AppState::new())),
+            dirty: false,
+            changes: 0,
+            storage: Arc::new(FileStorage),
+            saved_stamp: None,
+            read_only: false,
+            needs_redraw: false,
+            pending: VecDeque::new(),
+            escalated_on: None,
+            banner: None,
+            overlays: Overlays::default(),
+            announced: HashMap::new(),
+            bell: false,
+            title: None,
             ui_rx,
-            input_rx: Arc::new(Mutex::new(input_rx)),
+            input_rx: Arc::new(AsyncMutex::new(input_rx)),
         }
     }
 
@@ -374,27 +790,159 @@ impl Ui {
         let layout = Layout::vertical([Constraint::Fill(1), Constraint::Max(1)]).split(f.area());
         let layouts = Layout::horizontal([Constraint::Percentage(20), Constraint::Percentage(80)])
             .split(layout[0]);
-        let ws_layout = Layout::vertical([Constraint::Percentage(80), Constraint::Percentage(20)])
-            .split(layouts[0]);
-        let utils_layout = Layout::horizontal([
-            Constraint::Fill(1),
-            Constraint::Length(self.prompt.desc.len() as u16 + 2),
-        ])
-        .split(layout[1]);
+        let archived_visible = self.archived_visible();
+        let ws_layout = Layout::vertical(if archived_visible {
+            [Constraint::Percentage(80), Constraint::Percentage(20)]
+        } else {
+            [Constraint::Fill(1), Constraint::Length(0)]
+        })
+        .split(layouts[0]);
+        let utils_layout =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(self.prompt.width())])
+                .split(layout[1]);
+        {
+            let mut apps = self.appstate.lock().unwrap();
+            self.prompt.mode = apps.current_mode;
+            self.prompt.recording = apps.recording;
+            apps.archived_visible = archived_visible;
+            apps.banner = self.banner.is_some();
+            if !archived_visible && apps.current_focus == CurrentFocus::ArchivedWorkspace {
+                apps.current_focus = CurrentFocus::Workspace;
+                self.workspace.focused = true;
+                self.archived_ws.focused = false;
+                self.helpwidget.keymap.focus = CurrentFocus::Workspace;
+            }
+        }
+        self.prompt.dirty = self.dirty;
+        self.helpwidget.keymap.context = self.list_context();
 
+        let active = self
+            .todolist
+            .current_todolist
+            .as_ref()
+            .map(|list| list.borrow().workspace);
+        self.workspace.active = active;
+        self.archived_ws.active = active;
         f.render_widget(&mut self.workspace, ws_layout[0]);
-        f.render_widget(&mut self.archived_ws, ws_layout[1]);
+        if archived_visible {
+            f.render_widget(&mut self.archived_ws, ws_layout[1]);
+        }
         f.render_widget(&mut self.todolist, layouts[1]);
         f.render_widget(&mut self.helpwidget.keymap, utils_layout[0]);
         f.render_widget(&mut self.prompt, utils_layout[1]);
-        if let CurrentMode::Help = self.helpwidget.keymap.mode {
-            f.render_widget(&mut self.helpwidget, f.area());
+        self.overlays.set(Layer::Banner, self.banner.is_some());
+        self.overlays.set(Layer::Tour, self.tour.target().is_some());
+        // the popups draw themselves over this frame
+        for layer in self.overlays.drawn() {
+            match layer {
+                Layer::Banner => {
+                    let Some(banner) = &self.banner else {
+                        continue;
+                    };
+                    let area = Rect {
+                        height: 1,
+                        ..f.area()
+                    };
+                    f.render_widget(Clear, area);
+                    f.render_widget(
+                        Paragraph::new(format!(" {} ", banner.text))
+                            .style(Style::new().black().on_yellow().bold()),
+                        area,
+                    );
+                }
+                Layer::Help => f.render_widget(&mut self.helpwidget, f.area()),
+                Layer::Tour => {
+                    let Some(target) = self.tour.target() else {
+                        continue;
+                    };
+                    self.tour.target_area = match target {
+                        TourTarget::Workspace => ws_layout[0],
+                        TourTarget::Archived => ws_layout[1],
+                        TourTarget::TodoList => layouts[1],
+                        TourTarget::KeyBar | TourTarget::Finish => layout[1],
+                    };
+                    f.render_widget(&mut self.tour, f.area());
+                }
+                Layer::Popup | Layer::Calendar => break,
+            }
+        }
+        if self.title.is_some() {
+            let text = self.terminal_title(&SystemClock);
+            if let Some(title) = self.title.as_mut() {
+                title.set(&text);
+            }
+        }
+    }
+
+    /// The title of the terminal window, e.g. `todo — Work/Backend (3 due today)`
+    ///
+    /// The open workspace is named with its ancestors, and the tasks due today are counted
+    /// over all the active workspaces, see [`TodoWidget::due_days`].
+    ///
+    /// # Arguments
+    ///
+    /// - `clock` (`&dyn Clock`) - the source of today's date
+    pub fn terminal_title(&self, clock: &dyn Clock) -> String {
+        let mut title = "todo".to_string();
+        if let Some(ws) = &self.todolist.workspace {
+            let roots = match self.todolist.origin {
+                WorkspaceType::Normal => &self.workspace.workspaces,
+                WorkspaceType::Archived => &self.archived_ws.workspaces,
+            };
+            let mut names: Vec<String> = tree::ancestors(roots, ws)
+                .iter()
+                .map(|parent| parent.borrow().desc.clone())
+                .collect();
+            names.push(ws.borrow().desc.clone());
+            title.push_str(" — ");
+            title.push_str(&names.join("/"));
+        }
+        let due = self
+            .todolist
+            .due_days(&self.workspace.workspaces)
+            .get(&clock.today())
+            .map_or(0, Vec::len);
+        if due > 0 {
+            title.push_str(&format!(" ({})", trf("{} due today", &[&due])));
+        }
+        title
+    }
+
+    /// Whether the archived panel is shown
+    ///
+    /// The toggle key wins if it was used, otherwise the panel is hidden while nothing is
+    /// archived and [`Config::auto_hide_archived`] is on; an archive not read yet counts as
+    /// something archived. The guided tour always shows it.
+    pub fn archived_visible(&self) -> bool {
+        if self.tour.target().is_some() {
+            return true;
+        }
+        self.archived_shown.unwrap_or(
+            !self.config.auto_hide_archived
+                || self.archived_ws.unread
+                || !self.archived_ws.workspaces.is_empty(),
+        )
+    }
+
+    /// End the guided tour, optionally adding the sample workspace, and remember that it was shown
+    pub fn finish_tour(&mut self, add_sample: bool) {
+        self.tour.stop();
+        if add_sample {
+            let (ws, todolist) = data::sample_workspace();
+            self.workspace.add_workspace(ws.clone());
+            self.todolist.add_list(todolist);
+            self.workspace.select(Some(ws));
+            self.show_selected_list(WorkspaceType::Normal);
+        }
+        self.config.tour_shown = true;
+        if let Err(err) = config::save_config(config::config_path().as_path(), &self.config) {
+            self.prompt.set(trf("Config Not Saved: {}", &[&err]));
         }
     }
-    pub async fn input_due_date(
+    pub async fn input_due_date<B: Backend>(
         &mut self,
-        input_rx: Arc<Mutex<mpsc::Receiver<KeyEvent>>>,
-        terminal: &mut DefaultTerminal,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
         title: String,
         origin_due: Option<NaiveDate>,
     ) -> String {
@@ -406,23 +954,25 @@ impl Ui {
         };
         textarea.set_placeholder_text(placeholder.clone());
         let mut item = String::new();
-        let mut receiver = input_rx.lock().unwrap();
+        let mut ui = PopupGuard::open(self);
+        ui.helpwidget.keymap.mode = CurrentMode::Insert;
+        ui.helpwidget.keymap.calendar = true;
+        let mut receiver = input_rx.lock().await;
         let mut render_calendar = false;
         let mut calendar = CalendarWidget::new();
         loop {
+            calendar.refresh_today();
             let _ = terminal.draw(|f| {
-                self.prompt.desc = "In Insert Mode !".to_string();
-                if render_calendar {
-                    self.prompt.desc = "In Calendar Selection !".to_string();
-                }
-                self.update(f);
+                ui.update(f);
                 // let area = Ui::get_popup_window_center(50, 20, f);
                 let area = Ui::get_add_item_window(f);
-                let block = Block::bordered()
-                    .title(format!(" {} ", title))
+                let block = chrome::popup_block(PopupKind::Insert, format!(" {} ", title))
                     .title_bottom(
-                        Line::from(" press <ctrl-o> for calendar, input 'None' for unset ")
-                            .right_aligned(),
+                        Line::from(format!(
+                            " {} ",
+                            tr("press <ctrl-o> for calendar, input 'None' for unset")
+                        ))
+                        .right_aligned(),
                     );
                 textarea.set_block(block);
                 f.render_widget(Clear, area);
@@ -431,7 +981,7 @@ impl Ui {
                     f.render_widget(&mut calendar, f.area());
                 }
             });
-            if let Some(key_evt) = receiver.recv().await {
+            if let Some(key_evt) = keys::next_press(&mut receiver).await {
                 if !render_calendar {
                     match key_evt.code {
                         KeyCode::Esc => break,
@@ -449,12 +999,15 @@ impl Ui {
                             content.iter().for_each(|s| {
                                 item += s;
                             });
+                            ui.commit();
                             break;
                         }
-                        KeyCode::Char('o') if key_evt.modifiers.contains(KeyModifiers::CONTROL) => {
+                        KeyCode::Char('o') if keys::is_ctrl(&key_evt, 'o') => {
                             render_calendar = true;
+                            ui.overlays.open(Layer::Calendar);
+                            ui.prompt.set(tr("Calendar Selection !"));
                         }
-                        KeyCode::Char(c) => {
+                        KeyCode::Char(c) if keys::text(&key_evt).is_some() => {
                             textarea.insert_char(c);
                         }
                         _ => {}
@@ -475,49 +1028,172 @@ impl Ui {
                         }
                         KeyCode::Esc => {
                             render_calendar = false;
+                            ui.overlays.close(Layer::Calendar);
                         }
                         KeyCode::Enter => {
                             item = calendar.cursor.to_string();
+                            ui.commit();
                             break;
                         }
                         _ => {}
                     }
                 }
+            } else {
+                break;
             }
         }
         drop(receiver);
-        if item.is_empty() {
-            placeholder
-        } else if item == "None" {
-            "".to_string()
+        if item.is_empty() { placeholder } else { item }
+    }
+
+    /// Type the due date of the current task in its row, without a popup
+    ///
+    /// The input is drawn in place of the due span of the task, see [`TodoWidget::due_edit`],
+    /// and every other row stays as it is. `Enter` confirms and `Esc` cancels.
+    ///
+    /// # Arguments
+    ///
+    /// - `input_rx` (`Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>`) - the keys typed
+    /// - `terminal` (`&mut Terminal<B>`) - the terminal to draw the rows on
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - the text typed, None if cancelled, nothing was typed or no task
+    ///   is selected
+    pub async fn edit_due_inline<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+    ) -> Option<String> {
+        let task = self
+            .todolist
+            .current_todolist
+            .as_ref()
+            .and_then(|list| list.borrow().current_task.clone())?;
+        self.todolist.due_edit = Some(DueEdit {
+            task: task.borrow().id,
+            input: String::new(),
+        });
+        let mut receiver = input_rx.lock().await;
+        let typed = loop {
+            let _ = terminal.draw(|f| self.update(f));
+            let Some(key_evt) = keys::next_press(&mut receiver).await else {
+                break None;
+            };
+            let edit = self.todolist.due_edit.as_mut()?;
+            match key_evt.code {
+                KeyCode::Esc => break None,
+                KeyCode::Enter => break Some(edit.input.clone()),
+                KeyCode::Backspace => {
+                    edit.input.pop();
+                }
+                KeyCode::Char(c) if keys::text(&key_evt).is_some() => edit.input.push(c),
+                _ => {}
+            }
+        };
+        drop(receiver);
+        self.todolist.due_edit = None;
+        typed.filter(|typed| !typed.trim().is_empty())
+    }
+
+    pub async fn get_input<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        title: String,
+    ) -> String {
+        self.input_popup(input_rx, terminal, title, None)
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Type the name of a workspace or the description of a task, counting its columns
+    ///
+    /// The counter turns to a warning once the text is wider than the panel it's shown in,
+    /// where it would be cut, see [`Ui::width_counter`].
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`Ui`])
+    /// - `input_rx` (`Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>`) - the key receiver
+    /// - `terminal` (`&mut Terminal<B>`) - the terminal to draw on
+    /// - `title` (`String`) - the title of the popup
+    /// - `width` (`u16`) - the width inside the panel as last drawn, e.g. [`TodoWidget::width`]
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - the text typed, empty if Enter was pressed with nothing typed, None
+    ///   if the popup was cancelled
+    pub async fn get_name<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        title: String,
+        width: u16,
+    ) -> Option<String> {
+        self.input_popup(input_rx, terminal, title, Some(width))
+            .await
+    }
+
+    /// The columns a text typed takes, and the width of the panel once it's wider
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui::style::Color;
+    /// use todo::app::ui::Ui;
+    ///
+    /// assert_eq!(Ui::width_counter("Write", 20).content, " 5 cols ");
+    /// let over = Ui::width_counter("Write the release notes", 20);
+    /// assert_eq!(over.content, " 23/20 cols ");
+    /// assert_eq!(over.style.fg, Some(Color::Yellow));
+    /// ```
+    pub fn width_counter(text: &str, width: u16) -> Span<'static> {
+        let cols = text.width();
+        if cols > width as usize {
+            Span::styled(
+                format!(" {} ", trf("{}/{} cols", &[&cols, &width])),
+                Style::new().fg(Color::Yellow).bold(),
+            )
         } else {
-            item
+            Span::styled(
+                format!(" {} ", trf("{} cols", &[&cols])),
+                Style::new().fg(Color::DarkGray),
+            )
         }
     }
 
-    pub async fn get_input(
+    /// The popup of a line of text, with the counter of [`Ui::get_name`] given a width
+    async fn input_popup<B: Backend>(
         &mut self,
-        input_rx: Arc<Mutex<mpsc::Receiver<KeyEvent>>>,
-        terminal: &mut DefaultTerminal,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
         title: String,
-    ) -> String {
+        width: Option<u16>,
+    ) -> Option<String> {
         let mut textarea = TextArea::default();
-        let mut item = String::new();
-        let mut receiver = input_rx.lock().unwrap();
+        let mut item = None;
+        let mut ui = PopupGuard::open(self);
+        ui.helpwidget.keymap.mode = CurrentMode::Insert;
+        let mut receiver = input_rx.lock().await;
         loop {
             let _ = terminal.draw(|f| {
-                self.update(f);
+                ui.update(f);
                 // let area = Ui::get_popup_window_center(50, 20, f);
                 let area = Ui::get_add_item_window(f);
-                let block = Block::bordered().title(format!(" {} ", title));
+                let mut block = chrome::popup_block(PopupKind::Insert, format!(" {} ", title));
+                if let Some(width) = width {
+                    let counter = Ui::width_counter(&textarea.lines().concat(), width);
+                    block = block.title_bottom(Line::from(counter).right_aligned());
+                }
                 textarea.set_block(block);
                 f.render_widget(Clear, area);
                 f.render_widget(&textarea, area);
             });
-            if let Some(key_evt) = receiver.recv().await {
+            if let Some(key_evt) = keys::next_press(&mut receiver).await {
                 match key_evt.code {
                     KeyCode::Esc => break,
-                    KeyCode::Char(c) => {
+                    KeyCode::Char(c) if keys::text(&key_evt).is_some() => {
                         textarea.insert_char(c);
                     }
                     KeyCode::Backspace => {
@@ -530,14 +1206,95 @@ impl Ui {
                         textarea.move_cursor(tui_textarea::CursorMove::Back);
                     }
                     KeyCode::Enter => {
-                        let content = textarea.into_lines();
-                        content.iter().for_each(|s| {
-                            item += s;
-                        });
+                        item = Some(textarea.into_lines().concat());
+                        ui.commit();
                         break;
                     }
                     _ => {}
                 }
+            } else {
+                break;
+            }
+        }
+        drop(receiver);
+
+        item
+    }
+
+    /// The popup of the notes of a task, several lines of text
+    ///
+    /// Enter starts a new line, ctrl-s keeps what was written and Esc drops it. The notes of
+    /// an archived task are only shown, the cursor moves but no key changes them.
+    ///
+    /// # Arguments
+    ///
+    /// - `input_rx` (`Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>`) - the key receiver
+    /// - `terminal` (`&mut Terminal<B>`) - the terminal to draw to
+    /// - `title` (`String`) - the title of the popup
+    /// - `notes` (`&str`) - the notes to start from
+    /// - `read_only` (`bool`) - only show the notes, Esc closes the popup
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - the notes written, lines apart by `\n`, None if the edit was
+    ///   cancelled, the notes were only read or the key channel closed
+    pub async fn edit_notes<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        title: String,
+        notes: &str,
+        read_only: bool,
+    ) -> Option<String> {
+        let mut textarea = TextArea::new(notes.lines().map(str::to_string).collect());
+        textarea.move_cursor(tui_textarea::CursorMove::Bottom);
+        textarea.move_cursor(tui_textarea::CursorMove::End);
+        let hint = match read_only {
+            true => tr("read only, <esc> to close"),
+            false => tr("<enter> new line, <ctrl-s> save, <esc> cancel"),
+        };
+        let mut item = None;
+        let mut ui = PopupGuard::open(self);
+        ui.helpwidget.keymap.mode = CurrentMode::Insert;
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                ui.update(f);
+                let area = Ui::get_popup_window_center_by_frame(60, 50, f);
+                let block = chrome::popup_block(PopupKind::Insert, format!(" {} ", title))
+                    .title_bottom(Line::from(format!(" {} ", hint)).right_aligned());
+                textarea.set_block(block);
+                f.render_widget(Clear, area);
+                f.render_widget(&textarea, area);
+            });
+            let Some(key_evt) = keys::next_press(&mut receiver).await else {
+                break;
+            };
+            match key_evt.code {
+                KeyCode::Esc => break,
+                KeyCode::Right => textarea.move_cursor(tui_textarea::CursorMove::Forward),
+                KeyCode::Left => textarea.move_cursor(tui_textarea::CursorMove::Back),
+                KeyCode::Up => textarea.move_cursor(tui_textarea::CursorMove::Up),
+                KeyCode::Down => textarea.move_cursor(tui_textarea::CursorMove::Down),
+                KeyCode::Home => textarea.move_cursor(tui_textarea::CursorMove::Head),
+                KeyCode::End => textarea.move_cursor(tui_textarea::CursorMove::End),
+                _ if read_only => {}
+                KeyCode::Char('s') if keys::is_ctrl(&key_evt, 's') => {
+                    item = Some(textarea.lines().join("\n"));
+                    ui.commit();
+                    break;
+                }
+                KeyCode::Char(c) if keys::text(&key_evt).is_some() => {
+                    textarea.insert_char(c);
+                }
+                KeyCode::Enter => textarea.insert_newline(),
+                KeyCode::Backspace => {
+                    textarea.delete_char();
+                }
+                KeyCode::Delete => {
+                    textarea.delete_next_char();
+                }
+                _ => {}
             }
         }
         drop(receiver);
@@ -559,147 +1316,2147 @@ impl Ui {
         };
     }
 
-    pub async fn delete_item(
-        &mut self,
-        input_rx: Arc<Mutex<mpsc::Receiver<KeyEvent>>>,
-        terminal: &mut DefaultTerminal,
-    ) -> bool {
-        let _ = terminal.draw(|f| {
-            // let area = Ui::get_popup_window_center(30, 10, f);
-            let area = Ui::get_confirm_window(f);
-            let block = Block::bordered().title(" Warn ").yellow();
-            let info_line = Line::from(vec![
-                "Do you want to ".into(),
-                "Delete".red(),
-                " this item ?".into(),
-            ]);
-            let confirm_line = Line::from(vec!["y/".red(), "n".yellow()]);
-            let tip = Text::from(vec![info_line, confirm_line]).centered();
-            let para = Paragraph::new(tip).centered().block(block).bold();
-            self.update(f);
-            f.render_widget(Clear, area);
-            f.render_widget(para, area);
+    /// Read the archive file, if it's still unread, into the archived panel
+    ///
+    /// The archive file is read the first time the archived workspaces are needed rather than
+    /// on start up, see [`data::load_active`]. If it can't be read the panel stays unread, so
+    /// the file isn't overwritten, and the next time tries again.
+    pub fn load_archive(&mut self) {
+        if !self.archived_ws.unread {
+            return;
+        }
+        match data::read_archive(&self.data_path) {
+            Ok(archive) => {
+                self.archive_saved = data::serialize_archive(&archive);
+                archive.merge_into(&mut self.archived_ws, &mut self.todolist);
+            }
+            Err(err) => self.prompt.set(trf("Archive Unreadable: {}", &[&err])),
+        }
+    }
+
+    /// The content to write to the archive file, None if it's unread or unchanged
+    ///
+    /// # Arguments
+    ///
+    /// - `&self` ([`Ui`])
+    /// - `datas` (`&Datas`) - the data to save
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - the content of the archive file, see [`data::split_archive`]
+    pub fn archive_content(&self, datas: &Datas) -> Option<String> {
+        if datas.archived_ws.unread {
+            return None;
+        }
+        let content = data::serialize_archive(&data::split_archive(datas).1);
+        (content != self.archive_saved).then_some(content)
+    }
+
+    /// Where an action runs, to journal it, see [`journal::Selection`]
+    ///
+    /// # Arguments
+    ///
+    /// - `&self` ([`Ui`])
+    /// - `focus` ([`CurrentFocus`]) - the focused panel
+    pub fn selection(&self, focus: CurrentFocus) -> journal::Selection {
+        let row = |widget: &WorkspaceWidget| {
+            let current = widget.current_workspace.as_ref()?;
+            WorkspaceWidget::get_flattened(&widget.workspaces)
+                .iter()
+                .position(|ws| Rc::ptr_eq(ws, current))
+        };
+        let task = self.todolist.current_todolist.as_ref().and_then(|list| {
+            let list = list.borrow();
+            let current = list.current_task.as_ref()?;
+            TodoWidget::get_flattened(&list.tasks)
+                .iter()
+                .position(|task| Rc::ptr_eq(task, current))
         });
-        let mut receiver = input_rx.lock().unwrap();
-        loop {
-            if let Some(key_evt) = receiver.recv().await {
-                match key_evt.code {
-                    KeyCode::Char('y') => return true,
-                    KeyCode::Char('n') | KeyCode::Esc => return false,
-                    _ => {}
-                }
+        journal::Selection {
+            focus,
+            origin: self.todolist.origin,
+            workspace: row(&self.workspace),
+            archived: row(&self.archived_ws),
+            task,
+        }
+    }
+
+    /// Select again where a journaled action ran, see [`Ui::selection`]
+    ///
+    /// A row which is gone leaves nothing selected in its panel.
+    pub fn restore_selection(&mut self, selection: &journal::Selection) {
+        if selection.archived.is_some() {
+            self.load_archive();
+        }
+        let nth = |widget: &WorkspaceWidget, row: Option<usize>| {
+            row.and_then(|row| {
+                WorkspaceWidget::get_flattened(&widget.workspaces)
+                    .get(row)
+                    .cloned()
+            })
+        };
+        self.workspace
+            .select(nth(&self.workspace, selection.workspace));
+        self.archived_ws
+            .select(nth(&self.archived_ws, selection.archived));
+        self.show_selected_list(selection.origin);
+        if let Some(list) = &self.todolist.current_todolist {
+            let mut list = list.borrow_mut();
+            let task = selection
+                .task
+                .and_then(|row| TodoWidget::get_flattened(&list.tasks).get(row).cloned());
+            match task {
+                Some(task) => list.select_task(&task),
+                None => list.set_current_task_none(),
             }
         }
+        self.workspace.focused = selection.focus == CurrentFocus::Workspace;
+        self.todolist.focused = selection.focus == CurrentFocus::TodoList;
+        self.archived_ws.focused = selection.focus == CurrentFocus::ArchivedWorkspace;
+        self.helpwidget.keymap.focus = selection.focus.clone();
+    }
+
+    /// Queue the actions of a journal to perform again, see [`journal::pending`]
+    pub fn replay(&mut self, entries: Vec<journal::Entry>) {
+        let count = entries.len();
+        self.pending
+            .extend(entries.into_iter().map(UiMessage::Replay));
+        self.prompt
+            .set(trf("Replayed {} Unsaved Actions !", &[&count]));
+    }
+
+    /// Display the todo list of the workspace selected in the given panel,
+    /// or nothing when that panel has no selection
+    pub fn show_selected_list(&mut self, panel: WorkspaceType) {
+        let cur_ws = match panel {
+            WorkspaceType::Normal => self.workspace.current_workspace.clone(),
+            WorkspaceType::Archived => self.archived_ws.current_workspace.clone(),
+        };
+        if cur_ws.is_some() {
+            self.todolist.change_current_list(&cur_ws);
+        } else {
+            self.todolist.current_todolist = None;
+            self.todolist.workspace = None;
+        }
+        self.todolist.origin = panel;
     }
 
-    pub async fn confirm_delete(
+    /// Ask a yes or no question in the warning popup of the confirmations
+    ///
+    /// The popup holds a [`PopupGuard`], committed when the question is confirmed. The other
+    /// key, Esc and a closed key channel decline. A question too long for the confirm window
+    /// is wrapped in a larger one.
+    ///
+    /// # Arguments
+    ///
+    /// - `title` (`&str`) - the title of the popup
+    /// - `question` (`Line`) - the question, above the keys
+    /// - `keys` (`(char, char)`) - the key confirming and the key declining
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the confirming key was pressed
+    pub async fn confirm<B: Backend>(
         &mut self,
-        input_rx: Arc<Mutex<mpsc::Receiver<KeyEvent>>>,
-        terminal: &mut DefaultTerminal,
-        target: CurrentFocus,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        title: &str,
+        question: Line<'_>,
+        (yes, no): (char, char),
     ) -> bool {
-        let _ = terminal.draw(|f| {
-            // let area = Ui::get_popup_window_center(30, 10, f);
-            let area = Ui::get_confirm_window(f);
-            let block = Block::bordered().title(" Warn ").yellow();
-            let info_line = match target {
-                CurrentFocus::Workspace => Line::from(vec![
-                    "The Current Workspace is ".into(),
-                    "not empty ! ".red(),
-                    "still delete ?".yellow(),
-                ]),
-                CurrentFocus::TodoList => Line::from(vec![
-                    "The Todo List is ".into(),
-                    "not empty ! ".red(),
-                    "still delete ?".yellow(),
-                ]),
-                CurrentFocus::ArchivedWorkspace => Line::from(vec![
-                    "The Archived Workspace is ".into(),
-                    "has been archived ! ".red(),
-                    "still delete ?".yellow(),
-                ]),
-            };
-            let confirm_line = Line::from(vec!["y/".red(), "n".yellow()]);
-            let tip = Text::from(vec![info_line, confirm_line]).centered();
-            let para = Paragraph::new(tip).centered().block(block).bold();
-            self.update(f);
-            f.render_widget(Clear, area);
-            f.render_widget(para, area);
-        });
-        let mut receiver = input_rx.lock().unwrap();
+        let mut ui = PopupGuard::open(self);
+        let mut receiver = input_rx.lock().await;
+        let keys_line = Line::from(vec![format!("{}/", yes).red(), no.to_string().yellow()]);
+        let width = question.width();
+        let tip = Text::from(vec![question, keys_line]).centered();
         loop {
-            if let Some(key_evt) = receiver.recv().await {
-                match key_evt.code {
-                    KeyCode::Char('y') => return true,
-                    KeyCode::Char('n') | KeyCode::Esc => return false,
-                    _ => {}
+            let _ = terminal.draw(|f| {
+                let mut area = Ui::get_confirm_window(f);
+                if width + 2 > area.width as usize {
+                    area = Ui::get_popup_window_center_by_frame(60, 30, f);
+                }
+                let block =
+                    chrome::popup_block(PopupKind::Confirm, format!(" {} ", title)).yellow();
+                let para = Paragraph::new(tip.clone())
+                    .centered()
+                    .wrap(Wrap { trim: true })
+                    .block(block)
+                    .bold();
+                ui.update(f);
+                f.render_widget(Clear, area);
+                f.render_widget(para, area);
+            });
+            match keys::next_press(&mut receiver).await.map(|key| key.code) {
+                Some(KeyCode::Char(c)) if c == yes => {
+                    ui.commit();
+                    return true;
                 }
+                Some(KeyCode::Char(c)) if c == no => return false,
+                Some(KeyCode::Esc) | None => return false,
+                _ => {}
             }
         }
     }
 
-    pub async fn filter_find(
+    pub async fn delete_item<B: Backend>(
         &mut self,
-        input_rx: Arc<Mutex<mpsc::Receiver<KeyEvent>>>,
-        terminal: &mut DefaultTerminal,
-    ) -> String {
-        let mut textarea = TextArea::default();
-        let mut item = String::new();
-        let mut receiver = input_rx.lock().unwrap();
-        loop {
-            let _ = terminal.draw(|f| {
-                self.update(f);
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+    ) -> bool {
+        let question = Line::from(vec![
+            tr("Do you want to ").into(),
+            tr("Delete").red(),
+            tr(" this item ?").into(),
+        ]);
+        self.confirm(input_rx, terminal, tr("Warn"), question, ('y', 'n'))
+            .await
+    }
 
-                let search_string = textarea.to_owned().into_lines();
-                let mut tar_list = Vec::new();
+    /// Ask before archiving a workspace which still has open tasks
+    ///
+    /// # Arguments
+    ///
+    /// - `name` (`&str`) - the name of the workspace
+    /// - `open` (`usize`) - the open tasks of the workspace and its sub workspaces
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if `y` was pressed
+    pub async fn confirm_archive<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        name: &str,
+        open: usize,
+    ) -> bool {
+        let question = Line::from(vec![
+            trf("'{}' still has ", &[&name]).into(),
+            Ui::open_tasks_label(open).red(),
+            ", ".into(),
+            tr("archive ?").yellow(),
+        ]);
+        self.confirm(input_rx, terminal, tr("Warn"), question, ('y', 'n'))
+            .await
+    }
 
-                self.todolist
-                    .current_todolist
-                    .clone()
-                    .unwrap()
-                    .borrow()
-                    .tasks
-                    .iter()
-                    .for_each(|task| {
-                        if task.borrow().is_target(search_string.join(" ")) {
-                            tar_list.push(task.to_owned());
-                        }
-                    });
-                let tar_list_block = Block::bordered()
-                    .title(" <3> Todo List ")
-                    .border_style(Style::new().fg(Color::LightBlue))
-                    .padding(Padding::uniform(1));
-                let max_desc_len = TodoWidget::find_max_tasks_len(&tar_list, 1);
-                let task_list = TodoWidget::get_search_list_item(
-                    search_string.join(" "),
-                    &tar_list,
-                    0,
-                    max_desc_len,
-                );
-                let tar_list_widget = List::new(task_list).block(tar_list_block);
-                let layout =
-                    Layout::vertical([Constraint::Fill(1), Constraint::Max(1)]).split(f.area());
-                let tar_list_layout =
-                    Layout::horizontal([Constraint::Percentage(20), Constraint::Percentage(80)])
-                        .split(layout[0])[1];
-                f.render_widget(Clear, tar_list_layout);
-                f.render_widget(tar_list_widget, tar_list_layout);
+    /// The number of open tasks with the noun agreeing, e.g. `1 open task` or `12 open tasks`
+    pub fn open_tasks_label(open: usize) -> String {
+        if open == 1 {
+            trf("{} open task", &[&open])
+        } else {
+            trf("{} open tasks", &[&open])
+        }
+    }
 
-                // let find_area = Ui::get_popup_window(30, 10, 45, 0, f);
-                let find_area = Ui::get_filter_window(f);
-                let filter_block = Block::bordered().title(" find ");
+    pub async fn confirm_delete<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        target: CurrentFocus,
+    ) -> bool {
+        let question = match target {
+            CurrentFocus::Workspace => Line::from(vec![
+                tr("The Current Workspace is ").into(),
+                tr("not empty ! ").red(),
+                tr("still delete ?").yellow(),
+            ]),
+            CurrentFocus::TodoList => Line::from(vec![
+                tr("The Todo List is ").into(),
+                tr("not empty ! ").red(),
+                tr("still delete ?").yellow(),
+            ]),
+            CurrentFocus::ArchivedWorkspace => Line::from(vec![
+                tr("The Archived Workspace is ").into(),
+                tr("has been archived ! ").red(),
+                tr("still delete ?").yellow(),
+            ]),
+        };
+        self.confirm(input_rx, terminal, tr("Warn"), question, ('y', 'n'))
+            .await
+    }
+
+    /// Ask before deprecating a task which still has open subtasks, deprecated with it
+    ///
+    /// # Arguments
+    ///
+    /// - `open` (`usize`) - the open subtasks of the task, see [`TaskStats::open`]
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if `y` was pressed
+    pub async fn confirm_deprecate<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        open: usize,
+    ) -> bool {
+        let question = Line::from(vec![
+            tr("The Task still has ").into(),
+            Ui::open_tasks_label(open).red(),
+            ", ".into(),
+            tr("deprecate all ?").yellow(),
+        ]);
+        self.confirm(input_rx, terminal, tr("Warn"), question, ('y', 'n'))
+            .await
+    }
+
+    /// Ask whether the finished subtasks of the current task are deleted, see
+    /// [`WidgetAction::ClearDone`]
+    pub async fn confirm_clear_done<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        count: usize,
+    ) -> bool {
+        let question = Line::from(vec![
+            tr("Delete ").into(),
+            trf("{} finished subtasks", &[&count]).red(),
+            " ?".yellow(),
+        ]);
+        self.confirm(input_rx, terminal, tr("Warn"), question, ('y', 'n'))
+            .await
+    }
+
+    /// Ask what to do with a workspace recovered beside another of the same name
+    ///
+    /// `k` keeps both, `m` merges the recovered one into the other and `esc` cancels the
+    /// recovery, see [`Recovery`].
+    ///
+    /// # Arguments
+    ///
+    /// - `name` (`&str`) - the name the two workspaces share
+    ///
+    /// # Returns
+    ///
+    /// - [`Recovery`] - the choice, not applied yet
+    pub async fn resolve_clash<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        name: &str,
+    ) -> Recovery {
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(50, 25, f);
+                let block = chrome::popup_block(
+                    PopupKind::Confirm,
+                    format!(" {} ", tr("Name Already Taken")),
+                )
+                .yellow();
+                let tip = Text::from(vec![
+                    Line::from(trf("A workspace is already named '{}'.", &[&name])),
+                    Line::default(),
+                    Line::from(vec![
+                        "k ".light_green(),
+                        tr("keep both").into(),
+                        "  m ".light_green(),
+                        tr("merge").into(),
+                        "  esc ".light_green(),
+                        tr("cancel").into(),
+                    ]),
+                ])
+                .centered();
+                let para = Paragraph::new(tip)
+                    .centered()
+                    .wrap(Wrap { trim: true })
+                    .block(block)
+                    .bold();
+                f.render_widget(Clear, area);
+                f.render_widget(para, area);
+            });
+            match keys::next_press(&mut receiver).await.map(|key| key.code) {
+                Some(KeyCode::Char('k')) => return Recovery::KeepBoth,
+                Some(KeyCode::Char('m')) => return Recovery::Merge,
+                Some(KeyCode::Esc) | None => return Recovery::Cancel,
+                _ => {}
+            }
+        }
+    }
+
+    /// Tell the user the data file can't be written and ask what to do, before anything changes
+    ///
+    /// The choices are going on read-only, `r`, saving to another file, `p`, or quitting, `q`.
+    /// Another file must not exist yet and must pass [`data::check_writable`], otherwise the
+    /// reason is shown and the choice is asked again. Once it's picked, it's offered to be
+    /// saved in the configuration, see [`Config::data_path`], so the next runs use it too.
+    ///
+    /// # Arguments
+    ///
+    /// - `err` (`&errors::Errors`) - why the data file can't be written
+    ///
+    /// # Returns
+    ///
+    /// - [`Unwritable`] - the choice, already applied to [`Ui::read_only`] or [`Ui::data_path`]
+    pub async fn resolve_unwritable<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        err: &errors::Errors,
+    ) -> Unwritable {
+        let mut reason = err.to_string();
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(60, 30, f);
+                let block = chrome::popup_block(
+                    PopupKind::Confirm,
+                    format!(" {} ", tr("Data Not Writable")),
+                )
+                .red();
+                let tip = Text::from(vec![
+                    Line::from(reason.clone()).red(),
+                    Line::from(tr("Changes made now would be lost on exit.")).yellow(),
+                    Line::default(),
+                    Line::from(vec![
+                        "r ".light_green(),
+                        tr("read-only").into(),
+                        "  p ".light_green(),
+                        tr("another path").into(),
+                        "  q ".light_green(),
+                        tr("quit").into(),
+                    ]),
+                ])
+                .centered();
+                let para = Paragraph::new(tip)
+                    .centered()
+                    .wrap(Wrap { trim: true })
+                    .block(block)
+                    .bold();
+                f.render_widget(Clear, area);
+                f.render_widget(para, area);
+            });
+            let key = {
+                let mut receiver = input_rx.lock().await;
+                keys::next_press(&mut receiver).await.map(|key| key.code)
+            };
+            match key {
+                Some(KeyCode::Char('r')) => {
+                    self.read_only = true;
+                    self.prompt.set(tr("Read-Only, Nothing Is Saved !"));
+                    return Unwritable::ReadOnly;
+                }
+                Some(KeyCode::Char('p')) => {
+                    let typed = self
+                        .get_input(input_rx.clone(), terminal, tr("Save To").to_string())
+                        .await;
+                    let typed = typed.trim();
+                    if typed.is_empty() {
+                        continue;
+                    }
+                    let mut path = PathBuf::from(typed);
+                    if path.is_dir() {
+                        path.push("data.json");
+                    }
+                    if path.exists() {
+                        reason = trf("{} Already Exists !", &[&path.display()]);
+                        continue;
+                    }
+                    if let Err(err) = data::check_writable(&path) {
+                        reason = err.to_string();
+                        continue;
+                    }
+                    // the archive is saved next to the data, read it before it's left behind
+                    self.load_archive();
+                    self.data_path = path.clone();
+                    self.saved_stamp = None;
+                    self.archive_saved = data::serialize_archive(&data::Archive::default());
+                    self.prompt.set(trf("Saving To {} !", &[&path.display()]));
+                    if self
+                        .confirm_remember_path(input_rx.clone(), terminal, &path)
+                        .await
+                    {
+                        self.config.data_path = Some(path.clone());
+                        if let Err(err) =
+                            config::save_config(config::config_path().as_path(), &self.config)
+                        {
+                            self.prompt.set(trf("Config Not Saved: {}", &[&err]));
+                        }
+                    }
+                    return Unwritable::Moved(path);
+                }
+                Some(KeyCode::Char('q')) | Some(KeyCode::Esc) | None => return Unwritable::Quit,
+                _ => {}
+            }
+        }
+    }
+
+    /// Tell the user the data file was rolled back or changed under the app and ask what to
+    /// do, before saving over it, see [`Ui::rolled_back`] and [`Ui::changed_elsewhere`]
+    ///
+    /// `d` shows what keeping the data of the app would change in the file, as `todo diff`
+    /// prints it, scrolled with `j` and `k`, and `d` again hides it. `o` keeps the data of the
+    /// app, `t` takes the data of the file and `esc` leaves the choice to the next save.
+    /// `changed` tells a file changed elsewhere apart from a rolled back one.
+    ///
+    /// # Returns
+    ///
+    /// - [`Rollback`] - the choice, not applied yet
+    pub async fn resolve_rollback<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        changed: bool,
+    ) -> Rollback {
+        // the lines of the diff, found the first time they are shown
+        let mut changes: Option<Vec<String>> = None;
+        let mut showing = false;
+        let mut scroll: u16 = 0;
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(70, 60, f);
+                let keys = Line::from(vec![
+                    " d ".light_green(),
+                    tr("diff").into(),
+                    "  o ".light_green(),
+                    tr("keep ours").into(),
+                    "  t ".light_green(),
+                    tr("reload theirs").into(),
+                    "  esc ".light_green(),
+                    tr("later").into(),
+                    " ".into(),
+                ])
+                .right_aligned();
+                let title = if changed {
+                    tr("Data File Changed")
+                } else {
+                    tr("Data File Rolled Back")
+                };
+                let block = chrome::popup_block(PopupKind::Confirm, format!(" {} ", title))
+                .title_bottom(keys)
+                .red();
+                let text = match (&changes, showing) {
+                    (Some(lines), true) => {
+                        let mut text =
+                            vec![Line::from(tr("Keeping ours changes the file by:")).yellow()];
+                        text.extend(lines.iter().map(|line| Line::from(line.clone())));
+                        Text::from(text)
+                    }
+                    _ if changed => Text::from(vec![
+                        Line::from(tr(
+                            "The data file was changed since the last save of this session.",
+                        ))
+                        .red(),
+                        Line::from(tr("Another program, like todo serve, has written it.")),
+                        Line::default(),
+                        Line::from(tr("Saving over it loses the changes made there.")).yellow(),
+                    ])
+                    .centered(),
+                    _ => Text::from(vec![
+                        Line::from(tr(
+                            "The data file is older than the last save of this session.",
+                        ))
+                        .red(),
+                        Line::from(tr(
+                            "A sync tool or a backup may have put an old version back.",
+                        )),
+                        Line::default(),
+                        Line::from(tr(
+                            "Saving over it brings back the tasks deleted since and loses the ones added there.",
+                        ))
+                        .yellow(),
+                    ])
+                    .centered(),
+                };
+                let para = Paragraph::new(text)
+                    .wrap(Wrap { trim: false })
+                    .scroll((scroll, 0))
+                    .block(block);
+                f.render_widget(Clear, area);
+                f.render_widget(para, area);
+            });
+            match keys::next_press(&mut receiver).await.map(|key| key.code) {
+                Some(KeyCode::Char('d')) => {
+                    showing = !showing;
+                    scroll = 0;
+                    if changes.is_none() {
+                        changes = Some(self.rollback_diff());
+                    }
+                }
+                Some(KeyCode::Char('j')) if showing => scroll = scroll.saturating_add(1),
+                Some(KeyCode::Char('k')) if showing => scroll = scroll.saturating_sub(1),
+                Some(KeyCode::Char('o')) => return Rollback::Keep,
+                Some(KeyCode::Char('t')) => return Rollback::Reload,
+                Some(KeyCode::Esc) | None => return Rollback::Later,
+                _ => {}
+            }
+        }
+    }
+
+    /// The lines of the changes keeping the data of the app makes to the data file, see
+    /// [`diff::diff`], or why the file can't be compared
+    fn rollback_diff(&mut self) -> Vec<String> {
+        // the archived tasks are compared too, they're in the archive file on the other side
+        self.load_archive();
+        let ours = Datas {
+            workspace: self.workspace.clone(),
+            todolist: self.todolist.clone(),
+            archived_ws: self.archived_ws.clone(),
+        };
+        match diff::read_datas(&self.data_path) {
+            Ok(theirs) => diff::diff(&theirs, &ours)
+                .to_string()
+                .lines()
+                .map(String::from)
+                .collect(),
+            Err(err) => vec![err.to_string()],
+        }
+    }
+
+    /// Ask whether the data file picked in [`Ui::resolve_unwritable`] is kept for the next runs
+    async fn confirm_remember_path<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        path: &Path,
+    ) -> bool {
+        let question = Line::from(vec![
+            path.display().to_string().light_green(),
+            " ".into(),
+            tr("Use this file in the next runs too ?").into(),
+        ]);
+        self.confirm(input_rx, terminal, tr("Data File"), question, ('y', 'n'))
+            .await
+    }
+
+    /// Ask on start up whether the actions of a journal left behind are replayed
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`Ui`])
+    /// - `input_rx` (`Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>`) - the key receiver
+    /// - `terminal` (`&mut Terminal<B>`) - the terminal to draw on
+    /// - `count` (`usize`) - the number of actions, see [`journal::pending`]
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true to replay them, false to drop them
+    pub async fn confirm_replay<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        count: usize,
+    ) -> bool {
+        let question = Line::from(trf(
+            "{} actions of the last run weren't saved, replay them ?",
+            &[&count],
+        ));
+        self.confirm(
+            input_rx,
+            terminal,
+            tr("Unsaved Actions"),
+            question,
+            ('y', 'n'),
+        )
+        .await
+    }
+
+    /// Ask on start up whether the workspaces and tasks sharing the id of another one are given
+    /// new ids, see [`check::repair_duplicates`]
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`Ui`])
+    /// - `input_rx` (`Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>`) - the key receiver
+    /// - `terminal` (`&mut Terminal<B>`) - the terminal to draw on
+    /// - `count` (`usize`) - the number of duplicates, see [`check::Problem::is_duplicate`]
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true to give them new ids, false to keep the data as it is
+    pub async fn confirm_renew_ids<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        count: usize,
+    ) -> bool {
+        let question = Line::from(trf(
+            "{} workspaces or tasks share the id of another one and can't be selected, give them new ids ?",
+            &[&count],
+        ));
+        self.confirm(
+            input_rx,
+            terminal,
+            tr("Duplicate Ids"),
+            question,
+            ('y', 'n'),
+        )
+        .await
+    }
+
+    /// Ask a yes or no question in the status line, over the prompt message, until `y`, `n`
+    /// or Esc is pressed
+    ///
+    /// Unlike the confirm popups the panels stay in sight, for the questions asked right after
+    /// an action. The prompt message shown before comes back once answered.
+    ///
+    /// # Arguments
+    ///
+    /// - `input_rx` (`Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>`) - the keys typed
+    /// - `terminal` (`&mut Terminal<B>`) - the terminal to draw on
+    /// - `question` (`String`) - the question, ending with the keys to answer it
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - whether `y` was pressed
+    pub async fn confirm_inline<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        question: String,
+    ) -> bool {
+        let shown = mem::replace(&mut self.prompt.desc, question);
+        let mut receiver = input_rx.lock().await;
+        let confirmed = loop {
+            let _ = terminal.draw(|f| self.update(f));
+            match keys::next_press(&mut receiver).await.map(|key| key.code) {
+                Some(KeyCode::Char('y')) => break true,
+                Some(KeyCode::Char('n')) | Some(KeyCode::Esc) | None => break false,
+                _ => {}
+            }
+        };
+        self.prompt.desc = shown;
+        confirmed
+    }
+
+    /// Finish the parents left without an open subtask by finishing a task, as
+    /// [`Config::parent_done`] says
+    ///
+    /// A parent finished may leave its own parent without an open subtask, so the tree is
+    /// walked up until a parent has one, or isn't finished.
+    async fn finish_parents<B: Backend>(
+        &mut self,
+        task: &Rc<RefCell<Task>>,
+        terminal: &mut Terminal<B>,
+        appstate: &Arc<Mutex<AppState>>,
+    ) {
+        let Some(list) = self.todolist.current_todolist.clone() else {
+            return;
+        };
+        let mut task = task.clone();
+        loop {
+            let parent = Task::completed_parent(&list.borrow().tasks, &task);
+            let Some(parent) = parent else {
+                break;
+            };
+            let desc = parent.borrow().desc.clone();
+            let finish = match self.config.parent_done {
+                ParentDone::Off => false,
+                ParentDone::Auto => true,
+                ParentDone::Ask => {
+                    appstate.lock().unwrap().open_popup(CurrentMode::Insert);
+                    let question = trf(
+                        "All subtasks of '{}' done — mark parent done? y/n",
+                        &[&desc],
+                    );
+                    let input_rx = self.input_rx.clone();
+                    let confirmed = self.confirm_inline(input_rx, terminal, question).await;
+                    appstate.lock().unwrap().close_popup();
+                    confirmed
+                }
+            };
+            if !finish {
+                break;
+            }
+            // the parent alone, a deprecated subtask stays so
+            {
+                let mut parent = parent.borrow_mut();
+                parent.status = TaskStatus::Finished;
+                parent.touch();
+            }
+            self.prompt
+                .set(trf("'{}' Done With Its Subtasks !", &[&desc]));
+            task = parent;
+        }
+    }
+
+    /// Give the duplicate workspaces and tasks new ids, see [`check::repair_duplicates`], the
+    /// data is then saved as after any change
+    ///
+    /// # Arguments
+    ///
+    /// - `ids` (`&dyn IdGen`) - the source of the new ids
+    pub fn renew_duplicate_ids(&mut self, ids: &dyn IdGen) {
+        let mut datas = Datas {
+            workspace: self.workspace.clone(),
+            todolist: self.todolist.clone(),
+            archived_ws: self.archived_ws.clone(),
+        };
+        let renewed = check::repair_duplicates(&mut datas, ids);
+        if renewed > 0 {
+            self.prompt
+                .set(trf("{} Duplicate Ids Renewed !", &[&renewed]));
+            self.dirty = true;
+        }
+    }
+
+    /// Show the history of the prompt messages, newest first, until Esc or q is pressed
+    pub async fn show_messages<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+    ) {
+        let mut state = ListState::default().with_selected(Some(0));
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(60, 60, f);
+                let block = chrome::popup_block(PopupKind::View, format!(" {} ", tr("Messages")))
+                    .title_bottom(Line::from(format!(" {} ", tr("esc/q close"))).right_aligned())
+                    .padding(Padding::horizontal(1));
+                f.render_widget(Clear, area);
+                if self.prompt.history.is_empty() {
+                    Ui::render_placeholder(tr("no messages yet"), block, area, f.buffer_mut());
+                    return;
+                }
+                let items = self.prompt.history.iter().rev().map(|entry| {
+                    Line::from(vec![
+                        entry.time.format("%H:%M:%S ").to_string().dark_gray(),
+                        entry.desc.clone().into(),
+                    ])
+                });
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol(glyphs::current().selected)
+                    .highlight_style(Style::new().bg(Color::Rgb(66, 80, 102)));
+                f.render_stateful_widget(list, area, &mut state);
+            });
+            match keys::next_press(&mut receiver)
+                .await
+                .map(|key_evt| key_evt.code)
+            {
+                Some(KeyCode::Char('j')) | Some(KeyCode::Down) => state.select_next(),
+                Some(KeyCode::Char('k')) | Some(KeyCode::Up) => state.select_previous(),
+                Some(KeyCode::Esc) | Some(KeyCode::Char('q')) | None => break,
+                _ => {}
+            }
+        }
+    }
+
+    /// Show what the binary is and where the data comes from until any key is pressed
+    ///
+    /// The version, commit and data format are those of [`BuildInfo::current`], then the data
+    /// file and the [`Profile`], to copy into a bug report.
+    pub async fn show_about<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+    ) {
+        let info = BuildInfo::current();
+        let row = |label: &str, value: String| {
+            Line::from(vec![format!("{:<14}", tr(label)).dark_gray(), value.into()])
+        };
+        let lines = vec![
+            row("Version", info.version.to_string()),
+            row("Commit", info.commit_or_unknown().to_string()),
+            row("Data format", info.data_format.to_string()),
+            row("Data file", self.data_path.display().to_string()),
+            row("Profile", tr(&self.profile.to_string()).to_string()),
+        ];
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(60, 40, f);
+                let block = chrome::popup_block(PopupKind::View, format!(" {} ", tr("About")))
+                    .title_bottom(Line::from(format!(" {} ", tr("any key closes"))).right_aligned())
+                    .padding(Padding::uniform(1));
+                f.render_widget(Clear, area);
+                f.render_widget(
+                    Paragraph::new(lines.clone())
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    area,
+                );
+            });
+            match keys::next_press(&mut receiver).await {
+                Some(key) if keys::is_resize(&key) => {}
+                _ => break,
+            }
+        }
+    }
+
+    /// Show the vitals of a workspace and its sub workspaces until any key is pressed
+    ///
+    /// # Arguments
+    ///
+    /// - `input_rx` (`Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>`) - the keys typed
+    /// - `terminal` (`&mut Terminal<B>`) - the terminal to draw the popup on
+    /// - `workspace` (`&Rc<RefCell<Workspace>>`) - the workspace to show, see
+    ///   [`TodoWidget::workspace_stats`]
+    pub async fn show_workspace_stats<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        workspace: &Rc<RefCell<Workspace>>,
+    ) {
+        let stats = self.todolist.workspace_stats(workspace, &SystemClock);
+        let name = workspace.borrow().desc.clone();
+        let row = |label: &str, value: Span<'static>| {
+            Line::from(vec![format!("{:<16}", tr(label)).dark_gray(), value])
+        };
+        let overdue = stats.overdue.to_string();
+        let lines = vec![
+            row("Tasks", stats.tasks.total.to_string().into()),
+            row("Done", stats.tasks.finished.to_string().light_green()),
+            row("In process", stats.in_process.to_string().blue()),
+            row(
+                "Overdue",
+                if stats.overdue > 0 {
+                    overdue.light_red()
+                } else {
+                    overdue.into()
+                },
+            ),
+            row(
+                "Next due",
+                stats
+                    .next_due
+                    .map_or(tr("none").to_string(), |due| due.to_string())
+                    .into(),
+            ),
+            row(
+                "Last activity",
+                stats
+                    .last_activity
+                    .map_or(tr("never").to_string(), |time| {
+                        time.format("%Y-%m-%d %H:%M").to_string()
+                    })
+                    .into(),
+            ),
+            row("Sub workspaces", stats.sub_workspaces.to_string().into()),
+        ];
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(50, 50, f);
+                let block = chrome::popup_block(PopupKind::View, format!(" {} ", name))
+                    .title_bottom(Line::from(format!(" {} ", tr("any key closes"))).right_aligned())
+                    .padding(Padding::uniform(1));
+                f.render_widget(Clear, area);
+                f.render_widget(Paragraph::new(lines.clone()).block(block), area);
+            });
+            match keys::next_press(&mut receiver).await {
+                Some(key) if keys::is_resize(&key) => {}
+                _ => break,
+            }
+        }
+    }
+
+    /// Pick the rule to sort a todo list by, previewing every rule on the list behind the popup
+    ///
+    /// Enter sorts the tasks in place by the rule, at every level with ties kept in their order
+    /// and the selection on the same task, see [`TodoList::keep_sorted`]. `v` only shows the
+    /// list by the rule and leaves the manual order alone.
+    ///
+    /// # Arguments
+    ///
+    /// - `cur_list` (`&Rc<RefCell<TodoList>>`) - the list to sort
+    ///
+    /// # Returns
+    ///
+    /// - `Option<(SortRule, bool)>` - the rule picked, and whether the tasks were sorted in place
+    ///   by it, or None if the picker was cancelled and the list is back in its original order
+    pub async fn pick_sort<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        cur_list: &Rc<RefCell<TodoList>>,
+    ) -> Option<(SortRule, bool)> {
+        let applied = cur_list.borrow().sort_rule;
+        let mut state = ListState::default()
+            .with_selected(SortRule::ALL.iter().position(|rule| *rule == applied));
+        let hint_mode = mem::replace(&mut self.helpwidget.keymap.mode, CurrentMode::Sort);
+        let mut receiver = input_rx.lock().await;
+        let picked = loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(30, 40, f);
+                let block = chrome::popup_block(PopupKind::Pick, format!(" {} ", tr("Sort By")))
+                    .title_bottom(
+                        Line::from(format!(" {} ", tr("enter apply / v view / esc cancel")))
+                            .right_aligned(),
+                    )
+                    .padding(Padding::horizontal(1));
+                let marker = glyphs::current().active;
+                let blank = " ".repeat(marker.width());
+                let items = SortRule::ALL.iter().map(|rule| {
+                    if *rule == applied {
+                        Line::from(vec![
+                            marker.light_green(),
+                            tr(&rule.to_string()).to_string().into(),
+                        ])
+                    } else {
+                        Line::from(vec![
+                            blank.clone().into(),
+                            tr(&rule.to_string()).to_string().into(),
+                        ])
+                    }
+                });
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol(glyphs::current().selected)
+                    .highlight_style(Style::new().bg(Color::Rgb(66, 80, 102)));
+                f.render_widget(Clear, area);
+                f.render_stateful_widget(list, area, &mut state);
+            });
+            match keys::next_press(&mut receiver)
+                .await
+                .map(|key_evt| key_evt.code)
+            {
+                Some(KeyCode::Char('j')) | Some(KeyCode::Down) => state.select_next(),
+                Some(KeyCode::Char('k')) | Some(KeyCode::Up) => state.select_previous(),
+                Some(KeyCode::Enter) => {
+                    let rule =
+                        SortRule::ALL[state.selected().unwrap_or(0).min(SortRule::ALL.len() - 1)];
+                    cur_list.borrow_mut().keep_sorted(rule);
+                    break Some((rule, true));
+                }
+                Some(KeyCode::Char('v')) => {
+                    let rule =
+                        SortRule::ALL[state.selected().unwrap_or(0).min(SortRule::ALL.len() - 1)];
+                    cur_list.borrow_mut().set_sort_rule(rule);
+                    break Some((rule, false));
+                }
+                Some(KeyCode::Esc) | Some(KeyCode::Char('q')) | None => {
+                    cur_list.borrow_mut().set_sort_rule(applied);
+                    break None;
+                }
+                _ => continue,
+            }
+            let rule = SortRule::ALL[state.selected().unwrap_or(0).min(SortRule::ALL.len() - 1)];
+            cur_list.borrow_mut().set_sort_rule(rule);
+        };
+        drop(receiver);
+        self.helpwidget.keymap.mode = hint_mode;
+        picked
+    }
+
+    /// Pick the color of the description of a task, see [`TodoWidget::desc_style`]
+    ///
+    /// The first choice clears the color, the others are the [`TASK_COLORS`], each drawn in
+    /// its color, and the color of the task is marked.
+    ///
+    /// # Arguments
+    ///
+    /// - `task` (`&Rc<RefCell<Task>>`) - the task to color
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Option<String>>` - the picked color, `Some(None)` to clear it, or None if the
+    ///   picker was cancelled
+    pub async fn pick_color<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        task: &Rc<RefCell<Task>>,
+    ) -> Option<Option<String>> {
+        let choices: Vec<Option<&str>> = std::iter::once(None)
+            .chain(TASK_COLORS.iter().map(|color| Some(*color)))
+            .collect();
+        let current = task.borrow().color.clone();
+        let applied = choices
+            .iter()
+            .position(|choice| choice.map(str::to_string) == current)
+            .unwrap_or(0);
+        let mut state = ListState::default().with_selected(Some(applied));
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(30, 40, f);
+                let block = chrome::popup_block(PopupKind::Pick, format!(" {} ", tr("Task Color")))
+                    .title_bottom(
+                        Line::from(format!(" {} ", tr("enter apply / esc cancel"))).right_aligned(),
+                    )
+                    .padding(Padding::horizontal(1));
+                let marker = glyphs::current().active;
+                let blank = " ".repeat(marker.width());
+                let items = choices.iter().enumerate().map(|(index, choice)| {
+                    let mark = if index == applied {
+                        marker.light_green()
+                    } else {
+                        blank.clone().into()
+                    };
+                    let name = match choice {
+                        Some(color) => Span::styled(
+                            tr(color).to_string(),
+                            Style::new().fg(color.parse().unwrap_or_default()),
+                        ),
+                        None => tr("none").to_string().into(),
+                    };
+                    Line::from(vec![mark, name])
+                });
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol(glyphs::current().selected)
+                    .highlight_style(Style::new().bg(Color::Rgb(66, 80, 102)));
+                f.render_widget(Clear, area);
+                f.render_stateful_widget(list, area, &mut state);
+            });
+            match keys::next_press(&mut receiver)
+                .await
+                .map(|key_evt| key_evt.code)
+            {
+                Some(KeyCode::Char('j')) | Some(KeyCode::Down) => state.select_next(),
+                Some(KeyCode::Char('k')) | Some(KeyCode::Up) => state.select_previous(),
+                Some(KeyCode::Enter) => {
+                    let index = state.selected().unwrap_or(0).min(choices.len() - 1);
+                    return Some(choices[index].map(str::to_string));
+                }
+                Some(KeyCode::Esc) | Some(KeyCode::Char('q')) | None => return None,
+                _ => {}
+            }
+        }
+    }
+
+    /// Pick a workspace from the ones shown in the workspace panel
+    ///
+    /// # Arguments
+    ///
+    /// - `title` (`&str`) - the title of the popup
+    /// - `exclude` (`Uuid`) - a workspace left out of the choices, e.g. the one a task is in
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Uuid>` - the id of the picked workspace, or None if the picker was cancelled
+    pub async fn pick_workspace<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        title: &str,
+        exclude: Uuid,
+    ) -> Option<Uuid> {
+        let choices: Vec<(Uuid, ListItem)> =
+            WorkspaceWidget::get_ws_list(&self.workspace.workspaces, None)
+                .into_iter()
+                .filter(|(id, _)| *id != exclude)
+                .collect();
+        let mut state = ListState::default().with_selected(Some(0));
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(40, 50, f);
+                let block = chrome::popup_block(PopupKind::Pick, format!(" {} ", title))
+                    .title_bottom(
+                        Line::from(format!(" {} ", tr("enter pick / esc cancel"))).right_aligned(),
+                    )
+                    .padding(Padding::horizontal(1));
+                f.render_widget(Clear, area);
+                if choices.is_empty() {
+                    Ui::render_placeholder(tr("no other workspace"), block, area, f.buffer_mut());
+                    return;
+                }
+                let items = choices.iter().map(|(_, item)| item.clone());
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol(glyphs::current().selected)
+                    .highlight_style(Style::new().bg(Color::Rgb(66, 80, 102)));
+                f.render_stateful_widget(list, area, &mut state);
+            });
+            match keys::next_press(&mut receiver)
+                .await
+                .map(|key_evt| key_evt.code)
+            {
+                Some(KeyCode::Char('j')) | Some(KeyCode::Down) => state.select_next(),
+                Some(KeyCode::Char('k')) | Some(KeyCode::Up) => state.select_previous(),
+                Some(KeyCode::Enter) => {
+                    return state
+                        .selected()
+                        .and_then(|index| choices.get(index.min(choices.len().saturating_sub(1))))
+                        .map(|(id, _)| *id);
+                }
+                Some(KeyCode::Esc) | Some(KeyCode::Char('q')) | None => return None,
+                _ => {}
+            }
+        }
+    }
+
+    /// Pick one of the links of a task to open
+    ///
+    /// # Arguments
+    ///
+    /// - `links` (`&[&str]`) - the links of the task, see [`links::task_links`]
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - the picked link, or None if the picker was cancelled
+    pub async fn pick_link<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        links: &[&str],
+    ) -> Option<String> {
+        let mut state = ListState::default().with_selected(Some(0));
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(60, 40, f);
+                let block = chrome::popup_block(PopupKind::Pick, format!(" {} ", tr("Open Link")))
+                    .title_bottom(
+                        Line::from(format!(" {} ", tr("enter pick / esc cancel"))).right_aligned(),
+                    )
+                    .padding(Padding::horizontal(1));
+                let items = links
+                    .iter()
+                    .map(|link| Line::from(link.to_string().underlined()));
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol(glyphs::current().selected)
+                    .highlight_style(Style::new().bg(Color::Rgb(66, 80, 102)));
+                f.render_widget(Clear, area);
+                f.render_stateful_widget(list, area, &mut state);
+            });
+            match keys::next_press(&mut receiver)
+                .await
+                .map(|key_evt| key_evt.code)
+            {
+                Some(KeyCode::Char('j')) | Some(KeyCode::Down) => state.select_next(),
+                Some(KeyCode::Char('k')) | Some(KeyCode::Up) => state.select_previous(),
+                Some(KeyCode::Enter) => {
+                    let index = state.selected().unwrap_or(0).min(links.len() - 1);
+                    return Some(links[index].to_string());
+                }
+                Some(KeyCode::Esc) | Some(KeyCode::Char('q')) | None => return None,
+                _ => {}
+            }
+        }
+    }
+
+    /// Write the notes of the selected task in [`Ui::edit_notes`], and tell what became of them
+    async fn edit_current_notes<B: Backend>(&mut self, terminal: &mut Terminal<B>) {
+        let cur_task = self
+            .todolist
+            .current_todolist
+            .as_ref()
+            .and_then(|list| list.borrow().current_task.clone());
+        if let Some(cur_task) = cur_task {
+            let input_rx = self.input_rx.clone();
+            let (title, notes) = {
+                let task = cur_task.borrow();
+                (trf("Notes Of {}", &[&task.desc]), task.notes.clone())
+            };
+            let read_only = self.list_context() == ListContext::Archived;
+            let written = self
+                .edit_notes(input_rx, terminal, title, &notes, read_only)
+                .await;
+            if let Some(written) = written {
+                if !cur_task.borrow_mut().set_notes(&written) {
+                    self.prompt.set(tr("Notes Unchanged !"));
+                } else if cur_task.borrow().notes.is_empty() {
+                    self.prompt.set(tr("Notes Cleared !"));
+                } else {
+                    self.prompt.set(tr("Notes Saved !"));
+                }
+            }
+        }
+    }
+
+    /// Triage the Inbox, see [`Ui::triage`], and tell how many tasks are left
+    ///
+    /// Outside the Inbox the prompt tells where the triage works instead.
+    async fn triage_inbox<B: Backend>(&mut self, terminal: &mut Terminal<B>) {
+        let inbox = self
+            .todolist
+            .current_todolist
+            .clone()
+            .filter(|_| self.todolist.is_inbox());
+        if let Some(inbox) = inbox {
+            let input_rx = self.input_rx.clone();
+            self.triage(input_rx, terminal, &inbox).await;
+            let left = inbox.borrow().tasks.len();
+            if left == 0 {
+                self.prompt.set(tr("Inbox Zero !"));
+            } else {
+                self.prompt.set(trf("Triage Done, {} Left !", &[&left]));
+            }
+        } else {
+            self.prompt.set(tr("Triage Works From The Inbox !"));
+        }
+    }
+
+    /// Walk through the Inbox tasks one at a time, each handled with a single key
+    ///
+    /// `m` moves the task to a workspace picked in a popup, `D` sets its due date, `+` and
+    /// `-` change its urgency, `x` deletes it and `n` leaves it in the Inbox for later. The
+    /// walk ends once every task was moved, deleted or skipped, or on Esc or q.
+    ///
+    /// # Arguments
+    ///
+    /// - `inbox` (`&Rc<RefCell<TodoList>>`) - the todo list of the Inbox
+    pub async fn triage<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        inbox: &Rc<RefCell<TodoList>>,
+    ) {
+        let mut index = 0;
+        loop {
+            let Some(task) = inbox.borrow().tasks.get(index).cloned() else {
+                break;
+            };
+            {
+                let mut list = inbox.borrow_mut();
+                let shown = list
+                    .flattened()
+                    .iter()
+                    .position(|other| Rc::ptr_eq(other, &task));
+                list.state.select(shown);
+                list.current_task = Some(task.clone());
+            }
+            let left = inbox.borrow().tasks.len() - index;
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_confirm_window(f);
+                let block = chrome::popup_block(
+                    PopupKind::Pick,
+                    format!(" {} ", trf("Triage ({} left)", &[&left])),
+                );
+                let desc_line = Line::from(task.borrow().desc.clone()).bold();
+                let keys_line = Line::from(vec![
+                    "m".light_cyan(),
+                    tr(" move  ").into(),
+                    "D".light_cyan(),
+                    tr(" due  ").into(),
+                    "+/-".light_cyan(),
+                    tr(" urgency  ").into(),
+                    "x".light_cyan(),
+                    tr(" delete  ").into(),
+                    "n".light_cyan(),
+                    tr(" next").into(),
+                ]);
+                let para = Paragraph::new(Text::from(vec![desc_line, keys_line]))
+                    .centered()
+                    .block(block);
+                f.render_widget(Clear, area);
+                f.render_widget(para, area);
+            });
+            let key_code = keys::next_press(&mut *input_rx.lock().await)
+                .await
+                .map(|key_evt| key_evt.code);
+            match key_code {
+                Some(KeyCode::Char('m')) => {
+                    let exclude = inbox.borrow().workspace;
+                    let target = self
+                        .pick_workspace(input_rx.clone(), terminal, tr("Move To"), exclude)
+                        .await;
+                    if let Some(ws_id) = target {
+                        let list = self.todolist.list_of(ws_id).unwrap_or_else(|| {
+                            let list = Rc::new(RefCell::new(TodoList::new(ws_id)));
+                            self.todolist.add_list(list.clone());
+                            list
+                        });
+                        TodoList::delete_item(&task, &mut inbox.borrow_mut().tasks);
+                        list.borrow_mut().add_task(task);
+                    }
+                }
+                Some(KeyCode::Char('D')) => {
+                    let due = task.borrow().due;
+                    let date_str = self
+                        .input_due_date(
+                            input_rx.clone(),
+                            terminal,
+                            tr("Set Due Date").to_string(),
+                            due,
+                        )
+                        .await;
+                    task.borrow_mut()
+                        .set_due(parse::due(&date_str, &SystemClock));
+                }
+                Some(KeyCode::Char('+')) => task.borrow_mut().increase_urgency(),
+                Some(KeyCode::Char('-')) => task.borrow_mut().decrease_urgency(),
+                Some(KeyCode::Char('x')) => {
+                    TodoList::delete_item(&task, &mut inbox.borrow_mut().tasks)
+                }
+                Some(KeyCode::Char('n')) => index += 1,
+                Some(KeyCode::Esc) | Some(KeyCode::Char('q')) | None => break,
+                _ => {}
+            }
+        }
+        inbox.borrow_mut().set_current_task_none();
+    }
+
+    /// Show how many open tasks are due each day of a month, see [`TodoWidget::due_days`]
+    ///
+    /// The days are moved through with the keys of the date picker, and Enter on a day with
+    /// tasks due lists them to pick the one to jump to. Only the active workspaces are read.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<DueTask>` - the picked task and the id of its workspace, or
+    ///   None if the view was closed
+    pub async fn due_heatmap<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+    ) -> Option<DueTask> {
+        let days = self.todolist.due_days(&self.workspace.workspaces);
+        let heat = days
+            .iter()
+            .map(|(day, tasks)| (*day, tasks.len()))
+            .collect();
+        let mut calendar = CalendarWidget::heatmap(heat);
+        loop {
+            calendar.refresh_today();
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                f.render_widget(&mut calendar, f.area());
+            });
+            let key_code = keys::next_press(&mut *input_rx.lock().await)
+                .await
+                .map(|key_evt| key_evt.code);
+            match key_code {
+                Some(KeyCode::Char('h')) | Some(KeyCode::Left) => calendar.move_left(),
+                Some(KeyCode::Char('l')) | Some(KeyCode::Right) => calendar.move_right(),
+                Some(KeyCode::Char('j')) | Some(KeyCode::Down) => calendar.move_down(),
+                Some(KeyCode::Char('k')) | Some(KeyCode::Up) => calendar.move_up(),
+                Some(KeyCode::Enter) => match days.get(&calendar.cursor) {
+                    Some(tasks) => {
+                        let picked = self
+                            .pick_due_task(input_rx.clone(), terminal, calendar.cursor, tasks)
+                            .await;
+                        if picked.is_some() {
+                            return picked;
+                        }
+                    }
+                    None => self.prompt.set(tr("Nothing Due That Day !")),
+                },
+                Some(KeyCode::Esc) | Some(KeyCode::Char('q')) | None => return None,
+                _ => {}
+            }
+        }
+    }
+
+    /// Pick one of the tasks due on a day
+    ///
+    /// # Arguments
+    ///
+    /// - `day` (`NaiveDate`) - the day, shown in the title
+    /// - `tasks` (`&[DueTask]`) - the tasks due with the id of their workspace
+    ///
+    /// # Returns
+    ///
+    /// - `Option<DueTask>` - the picked task, or None if the list was closed
+    pub async fn pick_due_task<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        day: NaiveDate,
+        tasks: &[DueTask],
+    ) -> Option<DueTask> {
+        let workspaces = WorkspaceWidget::get_flattened(&self.workspace.workspaces);
+        let lines: Vec<Line> = tasks
+            .iter()
+            .map(|(ws_id, task)| {
+                let ws = workspaces
+                    .iter()
+                    .find(|ws| ws.borrow().id == *ws_id)
+                    .map_or(String::new(), |ws| ws.borrow().desc.clone());
+                Line::from(vec![
+                    Span::from(task.borrow().desc.clone()),
+                    Span::from(format!("  {}", ws)).dark_gray(),
+                ])
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(0));
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(40, 50, f);
+                let block =
+                    chrome::popup_block(PopupKind::Pick, format!(" {} ", trf("Due {}", &[&day])))
+                        .title_bottom(
+                            Line::from(format!(" {} ", tr("enter jump / esc back")))
+                                .right_aligned(),
+                        )
+                        .padding(Padding::horizontal(1));
+                let list = List::new(lines.clone())
+                    .block(block)
+                    .highlight_symbol(glyphs::current().selected)
+                    .highlight_style(Style::new().bg(Color::Rgb(66, 80, 102)));
+                f.render_widget(Clear, area);
+                f.render_stateful_widget(list, area, &mut state);
+            });
+            match keys::next_press(&mut receiver)
+                .await
+                .map(|key_evt| key_evt.code)
+            {
+                Some(KeyCode::Char('j')) | Some(KeyCode::Down) => state.select_next(),
+                Some(KeyCode::Char('k')) | Some(KeyCode::Up) => state.select_previous(),
+                Some(KeyCode::Enter) => {
+                    return state
+                        .selected()
+                        .and_then(|index| tasks.get(index.min(tasks.len().saturating_sub(1))))
+                        .cloned();
+                }
+                Some(KeyCode::Esc) | Some(KeyCode::Char('q')) | None => return None,
+                _ => {}
+            }
+        }
+    }
+
+    /// The tasks typed in an add popup
+    ///
+    /// A text ending with the delimiter, `write tests; update docs;`, is split into several
+    /// tasks on it, see [`plaintext::split_tasks`] and [`Config::task_delimiter`], any other
+    /// text is one task, delimiters included.
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<Rc<RefCell<Task>>>` - the tasks to add, none if nothing was typed
+    pub fn typed_tasks(&self, typed: &str) -> Vec<Rc<RefCell<Task>>> {
+        let delimiter = self.config.task_delimiter;
+        let descs = if typed.trim_end().ends_with(delimiter) {
+            plaintext::split_tasks(typed, delimiter)
+        } else if typed.is_empty() {
+            Vec::new()
+        } else {
+            vec![typed.to_string()]
+        };
+        descs
+            .into_iter()
+            .map(|desc| Rc::new(RefCell::new(Task::new(desc, None))))
+            .collect()
+    }
+
+    /// The prompt message of tasks added, e.g. `Task Added !` or `Added 3 Tasks !`
+    pub fn added_label(added: usize) -> String {
+        if added == 1 {
+            tr("Task Added !").to_string()
+        } else {
+            trf("Added {} Tasks !", &[&added])
+        }
+    }
+
+    /// The name of a workspace or a task to add, from what was typed in its popup
+    ///
+    /// With nothing typed the add is cancelled, or the item is named after `base` and
+    /// numbered past its siblings, as [`Config::empty_name`] says.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`Ui`])
+    /// - `typed` (`Option<String>`) - the text typed, see [`Ui::get_name`]
+    /// - `base` (`&str`) - the kind of the item, `Task` or `Workspace`, translated
+    /// - `siblings` (`&[String]`) - the names of the items the new one is added next to
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - the name to add, None if nothing is added
+    pub fn name_to_add(
+        &mut self,
+        typed: Option<String>,
+        base: &str,
+        siblings: &[String],
+    ) -> Option<String> {
+        let typed = typed?;
+        if !typed.trim().is_empty() {
+            return Some(typed);
+        }
+        match self.config.empty_name {
+            EmptyName::Cancel => {
+                self.prompt.set(tr("Cancelled — empty name"));
+                None
+            }
+            EmptyName::Generate => Some(names::unique_name(tr(base), siblings)),
+        }
+    }
+
+    /// Add the tasks of a file at the end of a todo list and select the first of them
+    ///
+    /// A `.md` file is read as a [Markdown checklist](crate::app::markdown), any other file
+    /// as a [plain text list](crate::app::plaintext).
+    ///
+    /// # Arguments
+    ///
+    /// - `list` (`&Rc<RefCell<TodoList>>`) - the todo list to add the tasks to
+    /// - `path` (`&Path`) - the path of the file
+    ///
+    /// # Returns
+    ///
+    /// - `io::Result<usize>` - the number of tasks added, subtasks included, or why the file
+    ///   wasn't read
+    pub fn import_file(&mut self, list: &Rc<RefCell<TodoList>>, path: &Path) -> io::Result<usize> {
+        let text = std::fs::read_to_string(path)?;
+        let tasks = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("md" | "markdown") => markdown::parse_checklist(&text),
+            _ => plaintext::parse_list(&text),
+        };
+        let Some(first) = tasks.first().cloned() else {
+            return Ok(0);
+        };
+        let imported = markdown::count(&tasks);
+        let mut list = list.borrow_mut();
+        tasks.into_iter().for_each(|task| list.add_task(task));
+        list.select_task(&first);
+        Ok(imported)
+    }
+
+    /// Open the todo list of an active workspace and select one of its tasks
+    ///
+    /// The ancestors of the workspace and of the task are expanded so both are shown, and the
+    /// todo list gets the focus.
+    ///
+    /// # Arguments
+    ///
+    /// - `ws_id` (`Uuid`) - the id of the workspace of the task
+    /// - `task` (`&Rc<RefCell<Task>>`) - the task to select
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - whether the workspace and its todo list were found
+    pub fn reveal_task(&mut self, ws_id: Uuid, task: &Rc<RefCell<Task>>) -> bool {
+        let Some(ws) = WorkspaceWidget::get_flattened(&self.workspace.workspaces)
+            .into_iter()
+            .find(|ws| ws.borrow().id == ws_id)
+        else {
+            return false;
+        };
+        let Some(list) = self.todolist.list_of(ws_id) else {
+            return false;
+        };
+        tree::ancestors(&self.workspace.workspaces, &ws)
+            .iter()
+            .for_each(|parent| parent.borrow_mut().expanded = true);
+        self.workspace.select(Some(ws));
+        self.todolist
+            .change_current_list(&self.workspace.current_workspace);
+        self.todolist.origin = WorkspaceType::Normal;
+        list.borrow_mut().select_task(task);
+        self.focus_todolist();
+        true
+    }
+
+    fn focus_todolist(&mut self) {
+        self.workspace.focused = false;
+        self.archived_ws.focused = false;
+        self.todolist.focused = true;
+        self.helpwidget.keymap.focus = CurrentFocus::TodoList;
+    }
+
+    /// The tasks of the active workspaces matching a search, subtasks included
+    ///
+    /// A task matches as it does the filter of a todo list, see [`Task::matches`]; the
+    /// workspaces are searched in the order of the tree and the tasks in the order of their
+    /// list.
+    ///
+    /// # Arguments
+    ///
+    /// - `query` (`&str`) - the words to search for
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<(Uuid, Rc<RefCell<Task>>)>` - the matching tasks, with the id of their workspace
+    pub fn search_tasks(&self, query: &str) -> Vec<(Uuid, Rc<RefCell<Task>>)> {
+        let stale = self.todolist.staleness();
+        let mut found = Vec::new();
+        for ws in WorkspaceWidget::get_flattened(&self.workspace.workspaces) {
+            let ws_id = ws.borrow().id;
+            let Some(list) = self.todolist.list_of(ws_id) else {
+                continue;
+            };
+            let list = list.borrow();
+            found.extend(
+                list.sort_rule
+                    .flatten(&list.tasks)
+                    .into_iter()
+                    .filter(|task| task.borrow().matches(query, stale))
+                    .map(|task| (ws_id, task)),
+            );
+        }
+        found
+    }
+
+    /// The tasks and the workspaces matching a search, for the search of every workspace
+    ///
+    /// The tasks are searched as by [`Ui::search_tasks`] and the names of the active and the
+    /// archived workspaces as by [`Workspace::matches`]. The tasks come first, then the
+    /// workspaces, and within each the ones starting with the search come first.
+    ///
+    /// # Arguments
+    ///
+    /// - `query` (`&str`) - the words to search for, nothing is found for blank ones
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<SearchHit>` - the matching tasks and workspaces
+    pub fn search_all(&self, query: &str) -> Vec<SearchHit> {
+        if query.trim().is_empty() {
+            return Vec::new();
+        }
+        let mut tasks = self.search_tasks(query);
+        tasks.sort_by_key(|(_, task)| !task.borrow().desc.starts_with(query));
+        let mut workspaces: Vec<_> = [
+            (WorkspaceType::Normal, &self.workspace.workspaces),
+            (WorkspaceType::Archived, &self.archived_ws.workspaces),
+        ]
+        .into_iter()
+        .flat_map(|(kind, workspaces)| {
+            WorkspaceWidget::get_flattened(workspaces)
+                .into_iter()
+                .filter(|ws| ws.borrow().matches(query))
+                .map(move |ws| (kind, ws))
+        })
+        .collect();
+        workspaces.sort_by_key(|(_, ws)| !ws.borrow().desc.starts_with(query));
+        tasks
+            .into_iter()
+            .map(|(ws_id, task)| SearchHit::Task(ws_id, task))
+            .chain(
+                workspaces
+                    .into_iter()
+                    .map(|(kind, ws)| SearchHit::Workspace(kind, ws)),
+            )
+            .collect()
+    }
+
+    /// Open the todo list of a workspace, active or archived, with its ancestors expanded
+    ///
+    /// An archived workspace is selected in the archived panel, shown if it was hidden, so
+    /// leaving the todo list goes back to it.
+    ///
+    /// # Arguments
+    ///
+    /// - `kind` ([`WorkspaceType`]) - the panel of the workspace
+    /// - `ws` (`&Rc<RefCell<Workspace>>`) - the workspace
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - whether the workspace was found in its panel, which gives the todo list the
+    ///   focus
+    pub fn reveal_workspace(&mut self, kind: WorkspaceType, ws: &Rc<RefCell<Workspace>>) -> bool {
+        let panel = match kind {
+            WorkspaceType::Normal => &mut self.workspace,
+            WorkspaceType::Archived => &mut self.archived_ws,
+        };
+        if !WorkspaceWidget::get_flattened(&panel.workspaces)
+            .iter()
+            .any(|other| Rc::ptr_eq(other, ws))
+        {
+            return false;
+        }
+        tree::ancestors(&panel.workspaces, ws)
+            .iter()
+            .for_each(|parent| parent.borrow_mut().expanded = true);
+        panel.select(Some(ws.clone()));
+        if kind == WorkspaceType::Archived && !self.archived_visible() {
+            self.archived_shown = Some(true);
+        }
+        self.show_selected_list(kind);
+        self.focus_todolist();
+        true
+    }
+
+    /// Reveal the first task matching the search given with `--search`, see
+    /// [`Ui::search_tasks`], or say in the prompt that none does
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - whether a task was revealed, which gives the todo list the focus
+    pub fn start_search(&mut self, query: &str) -> bool {
+        match self.search_tasks(query).into_iter().next() {
+            Some((ws_id, task)) => self.reveal_task(ws_id, &task),
+            None => {
+                self.prompt.set(trf("No Matches For '{}' !", &[&query]));
+                false
+            }
+        }
+    }
+
+    /// Filter the open todo list with the filter given with `--filter`, or say in the prompt
+    /// that no task matches it
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - whether the filter was applied, which gives the todo list the focus and
+    ///   starts in the search mode
+    pub fn start_filter(&mut self, query: &str) -> bool {
+        if self.todolist.current_todolist.is_none() {
+            self.prompt.set(tr("No Workspace Selected !"));
+            return false;
+        }
+        if self.todolist.apply_filter(query.to_string()) {
+            self.focus_todolist();
+            return true;
+        }
+        self.todolist.search_string.clear();
+        self.prompt.set(trf("No Matches For '{}' !", &[&query]));
+        false
+    }
+
+    /// Run a message picked in a popup the way the message handler runs a key
+    ///
+    /// # Arguments
+    ///
+    /// - `msg` ([`Message`]) - the message of the picked action
+    /// - `appstate` (`&Arc<Mutex<AppState>>`) - the state the message is reduced with
+    fn run_message(&mut self, msg: Message, appstate: &Arc<Mutex<AppState>>) {
+        let (ui_msgs, change) = reduce(msg, &appstate.lock().unwrap());
+        if let Some(change) = change {
+            change.apply(&mut appstate.lock().unwrap());
+        }
+        self.pending.extend(ui_msgs);
+    }
+
+    /// List the keys of the hint bar with their descriptions, see
+    /// [`KeymapWidget::shown`](keymap::KeymapWidget::shown)
+    ///
+    /// `Esc` or `F1` closes the list, and so does a listed key, which also runs its action
+    /// when the command palette can run it.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Message>` - the message of the key pressed, None if it runs none
+    pub async fn key_help<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+    ) -> Option<Message> {
+        let (hints, key_style) = self.helpwidget.keymap.shown();
+        let hints: Vec<keymap::Keymap> = hints.into_iter().cloned().collect();
+        let key_width = hints.iter().map(|hint| hint.key.width()).max().unwrap_or(0);
+        let lines: Vec<Line> = hints
+            .iter()
+            .map(|hint| {
+                Line::from(vec![
+                    Span::styled(format!("{:key_width$}  ", hint.key), key_style),
+                    tr(&hint.detailed).into(),
+                ])
+            })
+            .collect();
+        let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4;
+        let height = lines.len() as u16 + 2;
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let [_, area, _] = Layout::vertical([
+                    Constraint::Fill(1),
+                    Constraint::Length(height),
+                    Constraint::Fill(1),
+                ])
+                .areas(f.area());
+                let [_, area, _] = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Length(width),
+                    Constraint::Fill(1),
+                ])
+                .areas(area);
+                let block = chrome::popup_block(PopupKind::Pick, format!(" {} ", tr("Keys")))
+                    .title_bottom(Line::from(format!(" {} ", tr("esc close"))).right_aligned())
+                    .padding(Padding::horizontal(1));
+                f.render_widget(Clear, area);
+                f.render_widget(Paragraph::new(lines.clone()).block(block), area);
+            });
+            let key_evt = keys::next_press(&mut receiver).await?;
+            if matches!(key_evt.code, KeyCode::Esc | KeyCode::F(1)) {
+                return None;
+            }
+            if let Some(hint) = hints.iter().find(|hint| hint.matches(&key_evt)) {
+                return hint.message.map(|message| message());
+            }
+        }
+    }
+
+    /// Pick an action from the command palette, filtering the actions by typing their name
+    ///
+    /// # Arguments
+    ///
+    /// - `focus` (`&CurrentFocus`) - the focused panel, whose actions are offered
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Message>` - the message of the picked action, or None if the palette was cancelled
+    pub async fn pick_command<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+        focus: &CurrentFocus,
+    ) -> Option<Message> {
+        // the name, the key and the message of an action
+        type Command = (String, String, fn() -> Message);
+        let mut query = String::new();
+        let mut state = ListState::default().with_selected(Some(0));
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let mut commands: Vec<Command> = self
+                .helpwidget
+                .keymap
+                .commands(focus)
+                .into_iter()
+                .filter(|command| keymap::fuzzy_match(&query, &command.name()))
+                .filter_map(|command| {
+                    let message = command.message?;
+                    Some((command.name(), command.key.clone(), message))
+                })
+                .collect();
+            // the names starting with the query come first, e.g. "archive" before "show or hide the archived panel"
+            let prefix = query.to_lowercase();
+            commands.sort_by_key(|(name, _, _)| !name.to_lowercase().starts_with(&prefix));
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(50, 50, f);
+                let block = chrome::popup_block(PopupKind::Insert, format!(" > {} ", query))
+                    .title_bottom(
+                        Line::from(format!(" {} ", tr("enter run / esc cancel"))).right_aligned(),
+                    )
+                    .padding(Padding::horizontal(1));
+                f.render_widget(Clear, area);
+                if commands.is_empty() {
+                    Ui::render_placeholder(tr("no matching action"), block, area, f.buffer_mut());
+                    return;
+                }
+                let width = block.inner(area).width as usize;
+                let items = commands.iter().map(|(name, key, _)| {
+                    let padding = width.saturating_sub(name.width() + key.width());
+                    Line::from(vec![
+                        name.clone().into(),
+                        " ".repeat(padding).into(),
+                        key.clone().light_cyan(),
+                    ])
+                });
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol(glyphs::current().selected)
+                    .highlight_style(Style::new().bg(Color::Rgb(66, 80, 102)));
+                f.render_stateful_widget(list, area, &mut state);
+            });
+            let key_evt = keys::next_press(&mut receiver).await?;
+            match key_evt.code {
+                KeyCode::Esc => return None,
+                KeyCode::Enter => {
+                    return state
+                        .selected()
+                        .and_then(|index| commands.get(index.min(commands.len().saturating_sub(1))))
+                        .map(|(_, _, message)| message());
+                }
+                KeyCode::Down => state.select_next(),
+                KeyCode::Up => state.select_previous(),
+                KeyCode::Backspace => {
+                    query.pop();
+                    state.select(Some(0));
+                }
+                _ => {
+                    if let Some(c) = keys::text(&key_evt) {
+                        query.push(c);
+                        state.select(Some(0));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Search the tasks and the workspace names of every workspace, see [`Ui::search_all`]
+    ///
+    /// The results are listed as they are typed, the tasks and the workspaces in sections of
+    /// their own with the words found highlighted as the filter does.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<SearchHit>` - the result picked with Enter, None if the search was cancelled
+    pub async fn search_everywhere<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+    ) -> Option<SearchHit> {
+        let mut query = String::new();
+        let mut selected = 0;
+        let mut receiver = input_rx.lock().await;
+        loop {
+            let hits = self.search_all(&query);
+            selected = selected.min(hits.len().saturating_sub(1));
+            let tasks = hits
+                .iter()
+                .filter(|hit| matches!(hit, SearchHit::Task(..)))
+                .count();
+            let _ = terminal.draw(|f| {
+                self.update(f);
+                let area = Ui::get_popup_window_center_by_frame(60, 60, f);
+                let block = chrome::popup_block(PopupKind::Insert, format!(" / {} ", query))
+                    .title_bottom(
+                        Line::from(format!(" {} ", tr("enter jump / esc cancel"))).right_aligned(),
+                    )
+                    .padding(Padding::horizontal(1));
+                f.render_widget(Clear, area);
+                if hits.is_empty() {
+                    let hint = if query.trim().is_empty() {
+                        tr("type to search every workspace")
+                    } else {
+                        tr("no matching task or workspace")
+                    };
+                    Ui::render_placeholder(hint, block, area, f.buffer_mut());
+                    return;
+                }
+                let mut items: Vec<ListItem> = Vec::new();
+                for (index, hit) in hits.iter().enumerate() {
+                    if index == 0 && tasks > 0 {
+                        items.push(ListItem::new(tr("Tasks").bold().light_blue()));
+                    }
+                    if index == tasks {
+                        items.push(ListItem::new(tr("Workspaces").bold().light_green()));
+                    }
+                    items.push(ListItem::new(self.search_hit_line(hit, &query)));
+                }
+                // a header before the tasks, and one before the workspaces
+                let row = selected + usize::from(tasks > 0) + usize::from(selected >= tasks);
+                let mut state = ListState::default().with_selected(Some(row));
+                let list = List::new(items)
+                    .block(block)
+                    .highlight_symbol(glyphs::current().selected)
+                    .highlight_style(Style::new().bg(Color::Rgb(66, 80, 102)));
+                f.render_stateful_widget(list, area, &mut state);
+            });
+            let key_evt = keys::next_press(&mut receiver).await?;
+            match key_evt.code {
+                KeyCode::Esc => return None,
+                KeyCode::Enter => {
+                    if let Some(hit) = hits.get(selected) {
+                        return Some(hit.clone());
+                    }
+                }
+                KeyCode::Down => selected = (selected + 1).min(hits.len().saturating_sub(1)),
+                KeyCode::Up => selected = selected.saturating_sub(1),
+                KeyCode::Backspace => {
+                    query.pop();
+                    selected = 0;
+                }
+                _ => {
+                    if let Some(c) = keys::text(&key_evt) {
+                        query.push(c);
+                        selected = 0;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The line of a result of the search of every workspace, with the words found highlighted
+    ///
+    /// A task is followed by the name of its workspace, an archived workspace by a mark.
+    fn search_hit_line(&self, hit: &SearchHit, query: &str) -> Line<'static> {
+        let (name, style, after) = match hit {
+            SearchHit::Task(ws_id, task) => {
+                let task = task.borrow();
+                let ws_name = WorkspaceWidget::get_flattened(&self.workspace.workspaces)
+                    .into_iter()
+                    .find(|ws| ws.borrow().id == *ws_id)
+                    .map(|ws| ws.borrow().desc.clone())
+                    .unwrap_or_default();
+                (
+                    task.desc.clone(),
+                    TodoWidget::desc_style(&task),
+                    format!(" · {}", ws_name),
+                )
+            }
+            SearchHit::Workspace(kind, ws) => (
+                ws.borrow().desc.clone(),
+                Style::new(),
+                match kind {
+                    WorkspaceType::Normal => String::new(),
+                    WorkspaceType::Archived => format!(" · {}", tr("archived")),
+                },
+            ),
+        };
+        let mut spans = TodoWidget::highlight_matches(&name, query, style)
+            .unwrap_or_else(|| vec![Span::styled(name, style)]);
+        spans.push(after.dark_gray());
+        Line::from(spans)
+    }
+
+    /// Type the words to filter the todo list by, the matching tasks listed behind the popup
+    ///
+    /// The matches are computed again once no key came for [`FILTER_DEBOUNCE`], so a word
+    /// typed quickly into a large list is matched once rather than at every letter; the
+    /// popup shows the letters meanwhile over the last matches. Letters added to the last
+    /// word only check the tasks already matching, see [`FilterMatcher`].
+    ///
+    /// # Returns
+    ///
+    /// - `String` - the words typed, empty if the popup was cancelled
+    pub async fn filter_find<B: Backend>(
+        &mut self,
+        input_rx: Arc<AsyncMutex<mpsc::Receiver<KeyEvent>>>,
+        terminal: &mut Terminal<B>,
+    ) -> String {
+        let mut textarea = TextArea::default();
+        let mut item = String::new();
+        let mut ui = PopupGuard::open(self);
+        ui.helpwidget.keymap.mode = CurrentMode::Insert;
+        let list = ui.todolist.current_todolist.clone().unwrap();
+        let rule = list.borrow().sort_rule;
+        let stale = ui.todolist.staleness();
+        let mut matcher = FilterMatcher::default();
+        let mut rows: Vec<ListItem> = Vec::new();
+        // a key was typed since the tasks were last matched
+        let mut typed = false;
+        let mut receiver = input_rx.lock().await;
+        loop {
+            if !typed {
+                let query = textarea.lines().join(" ");
+                if matcher.update(&list.borrow().tasks, &query, stale) > 0 {
+                    let max_desc_len = TodoWidget::find_max_tasks_len(matcher.tasks(), 1);
+                    rows = TodoWidget::get_search_list_item(
+                        query,
+                        matcher.tasks(),
+                        rule,
+                        0,
+                        max_desc_len,
+                        &SystemClock,
+                        ui.todolist.row_options(),
+                    );
+                }
+            }
+            let _ = terminal.draw(|f| {
+                ui.update(f);
+
+                let tar_list_block = glyphs::block()
+                    .title(format!(" {} ", tr("<3> Todo List")))
+                    .border_style(Style::new().fg(Color::LightBlue))
+                    .padding(Padding::uniform(1));
+                let tar_list_widget = List::new(rows.clone()).block(tar_list_block);
+                let layout =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Max(1)]).split(f.area());
+                let tar_list_layout =
+                    Layout::horizontal([Constraint::Percentage(20), Constraint::Percentage(80)])
+                        .split(layout[0])[1];
+                f.render_widget(Clear, tar_list_layout);
+                f.render_widget(tar_list_widget, tar_list_layout);
+
+                let find_area = Ui::get_filter_window(f);
+                let filter_block =
+                    chrome::popup_block(PopupKind::Insert, format!(" {} ", tr("find")));
                 textarea.set_block(filter_block);
                 f.render_widget(Clear, find_area);
                 f.render_widget(&textarea, find_area);
             });
-            if let Some(key_evt) = receiver.recv().await {
+            let key_evt = if typed {
+                match tokio::time::timeout(FILTER_DEBOUNCE, keys::next_press(&mut receiver)).await {
+                    Ok(key_evt) => key_evt,
+                    Err(_) => {
+                        typed = false;
+                        continue;
+                    }
+                }
+            } else {
+                keys::next_press(&mut receiver).await
+            };
+            if let Some(key_evt) = key_evt {
                 match key_evt.code {
                     KeyCode::Esc => break,
-                    KeyCode::Char(c) => {
+                    KeyCode::Char(c) if keys::text(&key_evt).is_some() => {
                         textarea.insert_char(c);
+                        typed = true;
                     }
                     KeyCode::Backspace => {
-                        textarea.delete_char();
+                        typed |= textarea.delete_char();
                     }
                     KeyCode::Right => {
                         textarea.move_cursor(tui_textarea::CursorMove::Forward);
@@ -712,16 +3469,44 @@ impl Ui {
                         content.iter().for_each(|s| {
                             item += s;
                         });
+                        ui.commit();
                         break;
                     }
                     _ => {}
                 }
+            } else {
+                break;
             }
         }
         drop(receiver);
         item
     }
 
+    /// Render a bordered panel with a dim, centered hint instead of its content,
+    /// used by the widgets when they have nothing to show
+    ///
+    /// # Arguments
+    ///
+    /// - `hint` (`&str`) - the text to show in the middle of the panel
+    /// - `block` (`Block`) - the block of the panel
+    /// - `area` (`Rect`) - the area of the panel
+    /// - `buf` (`&mut Buffer`) - the buffer to render into
+    pub fn render_placeholder(hint: &str, block: Block, area: Rect, buf: &mut Buffer) {
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let hint_area = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ])
+        .split(inner)[1];
+        Paragraph::new(hint)
+            .wrap(Wrap { trim: true })
+            .centered()
+            .dark_gray()
+            .render(hint_area, buf);
+    }
+
     pub fn get_popup_window(
         percent_width: u16,
         percent_height: u16,
@@ -789,6 +3574,374 @@ impl Ui {
         h_layout[1]
     }
 
+    /// The pending tasks of a workspace as a message to paste into a chat, see [`share`]
+    ///
+    /// # Arguments
+    ///
+    /// - `ws` (`&Rc<RefCell<Workspace>>`) - the workspace
+    /// - `clock` (`&dyn Clock`) - the source of today's date
+    pub fn share_message(&self, ws: &Rc<RefCell<Workspace>>, clock: &dyn Clock) -> String {
+        let ws = ws.borrow();
+        let (tasks, rule) = match self.todolist.list_of(ws.id) {
+            Some(list) => {
+                let list = list.borrow();
+                (list.tasks.clone(), list.sort_rule)
+            }
+            None => (Vec::new(), SortRule::default()),
+        };
+        share::message(
+            &ws.desc,
+            &tasks,
+            rule,
+            self.config.share_max_items,
+            clock.today(),
+        )
+    }
+
+    /// Recover the workspace selected in the archived panel to the top level of the workspace
+    /// panel, resolving a clash with a namesake there as picked
+    ///
+    /// Without a namesake the workspace is recovered as it is, whatever the choice.
+    ///
+    /// # Arguments
+    ///
+    /// - `recovery` ([`Recovery`]) - what to do if a top level workspace has the same name
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - the prompt message, None if the workspace was recovered as it is
+    pub fn recover_current(&mut self, recovery: Recovery) -> Option<String> {
+        let recovered = self.archived_ws.current_workspace.clone()?;
+        let name = recovered.borrow().desc.clone();
+        let Some(namesake) = WorkspaceWidget::namesake(&self.workspace.workspaces, &name) else {
+            self.archived_ws.transfer_current(&mut self.workspace);
+            return None;
+        };
+        match recovery {
+            Recovery::Cancel => return Some(tr("Recovery Cancelled !").to_string()),
+            Recovery::KeepBoth => {
+                let siblings: Vec<String> = (self.workspace.workspaces.iter())
+                    .map(|ws| ws.borrow().desc.clone())
+                    .collect();
+                recovered.borrow_mut().desc = names::recovered_name(&name, siblings);
+            }
+            Recovery::Merge => {}
+        }
+        self.archived_ws.transfer_current(&mut self.workspace);
+        if recovery == Recovery::KeepBoth {
+            let renamed = recovered.borrow().desc.clone();
+            return Some(trf("Recovered As '{}' !", &[&renamed]));
+        }
+        // the recovered workspace leaves the panel, its tasks and sub workspaces stay
+        let merged = self.merge_workspace(&recovered, &namesake);
+        WorkspaceWidget::delete_item(&mut self.workspace.workspaces, &recovered);
+        self.workspace.arrange();
+        self.workspace.select(Some(namesake));
+        Some(trf("Merged {} Tasks Into '{}' !", &[&merged, &name]))
+    }
+
+    /// Merge a workspace into a namesake, see [`Recovery::Merge`]
+    ///
+    /// The tasks are added after the tasks of the namesake, subtasks kept. A sub workspace is
+    /// merged the same way into the sub workspace of the namesake with its name, or added to
+    /// them if none has it, so the merge never leaves two siblings of the same name.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` (`&Rc<RefCell<Workspace>>`) - the workspace to merge, its list is deleted and
+    ///   its sub workspaces taken
+    /// - `into` (`&Rc<RefCell<Workspace>>`) - the namesake
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - the tasks merged, subtasks and the tasks of the sub workspaces included
+    fn merge_workspace(
+        &mut self,
+        from: &Rc<RefCell<Workspace>>,
+        into: &Rc<RefCell<Workspace>>,
+    ) -> usize {
+        let id = from.borrow().id;
+        let tasks = (self.todolist.list_of(id))
+            .map(|list| mem::take(&mut list.borrow_mut().tasks))
+            .unwrap_or_default();
+        self.todolist.delete_list(id);
+        let mut merged = markdown::count(&tasks);
+        if !tasks.is_empty() {
+            let ws_id = into.borrow().id;
+            let list = self.todolist.list_of(ws_id).unwrap_or_else(|| {
+                let list = Rc::new(RefCell::new(TodoList::new(ws_id)));
+                self.todolist.add_list(list.clone());
+                list
+            });
+            tasks
+                .into_iter()
+                .for_each(|task| list.borrow_mut().add_task(task));
+        }
+        let children = mem::take(&mut from.borrow_mut().children);
+        for child in children {
+            let name = child.borrow().desc.clone();
+            let namesake = WorkspaceWidget::namesake(&into.borrow().children, &name);
+            match namesake {
+                Some(namesake) => merged += self.merge_workspace(&child, &namesake),
+                None => into.borrow_mut().add_child(child),
+            }
+        }
+        merged
+    }
+
+    /// What the todo list on screen allows, so the hints offer only the task keys which work
+    ///
+    /// # Returns
+    ///
+    /// - `ListContext` - `Archived` for the list of an archived workspace, `Empty` when no list
+    ///   is open or it has no task, `Tasks` otherwise
+    pub fn list_context(&self) -> ListContext {
+        match &self.todolist.current_todolist {
+            Some(_) if self.todolist.origin == WorkspaceType::Archived => ListContext::Archived,
+            Some(list) if !list.borrow().tasks.is_empty() => ListContext::Tasks,
+            _ => ListContext::Empty,
+        }
+    }
+
+    /// Check that an action has something selected to act on
+    ///
+    /// The actions on the current task, workspace or todo list do nothing without one, this
+    /// gives the prompt message saying what is missing instead.
+    ///
+    /// # Arguments
+    ///
+    /// - `action` (`&WidgetAction`) - the action about to be handled
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&'static str>` - the message to show and skip the action with, None to handle it
+    pub fn unmet_precondition(&self, action: &WidgetAction) -> Option<&'static str> {
+        let list = self.todolist.current_todolist.as_ref();
+        let no_task = || match list {
+            Some(list) if list.borrow().current_task.is_some() => None,
+            Some(list) if !list.borrow().tasks.is_empty() => Some("No Task Selected !"),
+            _ => Some("Todo List Is Empty, Press a To Add One !"),
+        };
+        match action {
+            WidgetAction::AddTask
+            | WidgetAction::AddTaskChild
+            | WidgetAction::ImportFile
+            | WidgetAction::MarkTaskStatus(_)
+            | WidgetAction::Due
+            | WidgetAction::DueInline
+            | WidgetAction::DueMatching
+            | WidgetAction::Rename(CurrentFocus::TodoList)
+            | WidgetAction::DeleteTask
+            | WidgetAction::TaskColor
+            | WidgetAction::ClearDone
+            | WidgetAction::IncreaseUrgency
+            | WidgetAction::DecreaseUrgency
+            | WidgetAction::Sort
+            | WidgetAction::Triage
+                if self.list_context() == ListContext::Archived =>
+            {
+                Some("Archived Tasks Are Read Only, Press R To Recover !")
+            }
+            WidgetAction::DueInline if !self.todolist.search_string.is_empty() => {
+                Some("Leave The Filter To Edit The Due Date In Place !")
+            }
+            WidgetAction::MarkTaskStatus(_)
+            | WidgetAction::Due
+            | WidgetAction::DueInline
+            | WidgetAction::Rename(CurrentFocus::TodoList)
+            | WidgetAction::DeleteTask
+            | WidgetAction::TaskColor
+            | WidgetAction::EditNotes
+            | WidgetAction::EnterTask
+            | WidgetAction::OpenLink
+            | WidgetAction::ClearDone
+            | WidgetAction::IncreaseUrgency
+            | WidgetAction::DecreaseUrgency => no_task(),
+            WidgetAction::Rename(CurrentFocus::Workspace)
+            | WidgetAction::WorkspaceStats
+            | WidgetAction::CopyWorkspace
+            | WidgetAction::DeleteWorkspace
+            | WidgetAction::ArchiveWS
+            | WidgetAction::ToggleProtected
+                if self.workspace.current_workspace.is_none() =>
+            {
+                Some("No Workspace Selected !")
+            }
+            WidgetAction::Rename(CurrentFocus::ArchivedWorkspace)
+            | WidgetAction::DeleteArchivedWorkspace
+            | WidgetAction::RecoveryWS
+                if self.archived_ws.current_workspace.is_none() =>
+            {
+                Some("No Archived Workspace Selected !")
+            }
+            WidgetAction::Sort
+            | WidgetAction::Filter
+            | WidgetAction::GoToRow
+            | WidgetAction::ImportFile
+                if list.is_none() =>
+            {
+                Some("No Todo List Open, Enter A Workspace !")
+            }
+            WidgetAction::DueMatching if self.todolist.search_string.is_empty() => {
+                Some("Filter The List First, Press f !")
+            }
+            _ => None,
+        }
+    }
+
+    /// Announce the open tasks that are due today, overdue or reminded of in a banner across
+    /// the top
+    ///
+    /// Runs at start up and on the tick, so a task is announced when it becomes due, or from
+    /// its reminder day on, see [`Task::reminding`]. Each task is announced once a day, the
+    /// ones due together share the banner of the first, e.g.
+    /// `'ship release' is due today (+2 more) — press O to jump`. A banner shown for
+    /// [`BANNER_TIMEOUT`] is hidden first, and nothing is announced during the guided tour.
+    ///
+    /// # Arguments
+    ///
+    /// - `clock` (`&dyn Clock`) - the source of today's date
+    pub fn announce_due(&mut self, clock: &dyn Clock) {
+        if self
+            .banner
+            .as_ref()
+            .is_some_and(|banner| banner.shown_at.elapsed() >= BANNER_TIMEOUT)
+        {
+            self.banner = None;
+        }
+        if self.tour.target().is_some() {
+            return;
+        }
+        let today = clock.today();
+        let due: Vec<DueTask> = self
+            .todolist
+            .due_days(&self.workspace.workspaces)
+            .into_iter()
+            .flat_map(|(day, tasks)| {
+                tasks
+                    .into_iter()
+                    .filter(move |(_, task)| day <= today || task.borrow().reminding(today))
+            })
+            .filter(|(_, task)| self.announced.get(&task.borrow().id) != Some(&today))
+            .collect();
+        let Some((workspace, task)) = due.first().cloned() else {
+            return;
+        };
+        for (_, task) in due.iter() {
+            self.announced.insert(task.borrow().id, today);
+        }
+        let mut text = {
+            let task = task.borrow();
+            match task.due.map(|due| (due - today).num_days()) {
+                Some(0) => trf("'{}' is due today", &[&task.desc]),
+                Some(1) => trf("'{}' is due tomorrow", &[&task.desc]),
+                Some(days) if days > 1 => trf("'{}' is due in {} days", &[&task.desc, &days]),
+                _ => trf("'{}' is overdue", &[&task.desc]),
+            }
+        };
+        if due.len() > 1 {
+            text.push_str(&trf(" (+{} more)", &[&(due.len() - 1)]));
+        }
+        text.push_str(tr(" — press O to jump"));
+        self.banner = Some(DueBanner {
+            workspace,
+            task,
+            text,
+            shown_at: Instant::now(),
+        });
+        self.ring();
+    }
+
+    /// Ring the bell of the terminal after the next frame, if the configuration asks for it,
+    /// see [`bell`]
+    pub fn ring(&mut self) {
+        if self.config.bell {
+            self.bell = true;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Raise the urgency of the overdue tasks once a day, if the configuration asks for it
+    ///
+    /// Runs at start up and on the tick, which only escalates again once the date changed.
+    /// Every escalated task is kept in the prompt history, see [`PromptWidget::log`], and the
+    /// prompt shows one summary, e.g. `3 Tasks Escalated To Important !`.
+    ///
+    /// # Arguments
+    ///
+    /// - `clock` (`&dyn Clock`) - the source of today's date
+    pub fn escalate_overdue(&mut self, clock: &dyn Clock) {
+        let Some(policy) = self.config.escalate_overdue else {
+            return;
+        };
+        let today = clock.today();
+        if self.escalated_on == Some(today) {
+            return;
+        }
+        self.escalated_on = Some(today);
+        let escalated = self
+            .todolist
+            .escalate_overdue(&self.workspace.workspaces, policy, clock);
+        if escalated.is_empty() {
+            return;
+        }
+        let mut counts = Vec::new();
+        for urgency in [Urgency::Common, Urgency::Important, Urgency::Critical] {
+            let count = escalated.iter().filter(|(_, to)| *to == urgency).count();
+            if count > 0 {
+                counts.push((count, urgency));
+            }
+        }
+        for (desc, urgency) in escalated.iter() {
+            self.prompt.log(trf(
+                "Overdue {} Escalated To {}",
+                &[desc, &tr(&urgency.to_string())],
+            ));
+        }
+        let plural = |count: usize| tr(if count == 1 { "Task" } else { "Tasks" });
+        self.prompt.set(match counts.as_slice() {
+            [(count, urgency)] => trf(
+                "{} {} Escalated To {} !",
+                &[count, &plural(*count), &tr(&urgency.to_string())],
+            ),
+            _ => trf(
+                "{} {} Escalated, {} !",
+                &[
+                    &escalated.len(),
+                    &plural(escalated.len()),
+                    &counts
+                        .iter()
+                        .map(|(count, urgency)| {
+                            trf("{} To {}", &[count, &tr(&urgency.to_string())])
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ],
+            ),
+        });
+        self.ring();
+        self.dirty = true;
+    }
+
+    /// Refuse to delete or archive a protected workspace, saying why in the prompt
+    ///
+    /// # Arguments
+    ///
+    /// - `workspace` (`Option<Rc<RefCell<Workspace>>>`) - the workspace to delete or archive
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the workspace, or one of its sub workspaces, is protected
+    fn refuse_protected(&mut self, workspace: Option<Rc<RefCell<Workspace>>>) -> bool {
+        match workspace.and_then(|ws| Workspace::find_protected(&ws)) {
+            Some(name) => {
+                self.prompt.set(trf("{} Is Protected !", &[&name]));
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn get_popup_window_center_by_frame(percent_x: u16, percent_y: u16, f: &mut Frame) -> Rect {
         let layout1 = Layout::horizontal([
             Constraint::Percentage((100 - percent_x) / 2),
@@ -821,35 +3974,450 @@ impl Ui {
         .split(layout1[1])[1]
     }
 
-    pub async fn handle_uimsg(
+    /// Start writing the data in the background, see [`Ui::handle_uimsg`]
+    fn start_save(&mut self) -> Saving {
+        let path = self.data_path.clone();
+        let datas = Datas {
+            workspace: self.workspace.clone(),
+            todolist: self.todolist.clone(),
+            archived_ws: self.archived_ws.clone(),
+        };
+        let content = data::serialize_data(&datas);
+        let archive = self.archive_content(&datas);
+        let storage = self.storage.clone();
+        // the file as checked in Ui::start_checked_save
+        let checked = storage.modified(&path);
+        self.dirty = false;
+        self.prompt.spin(tr("Saving ..."));
+        self.needs_redraw = true;
+        Saving {
+            journal_mark: self.journal.as_ref().map_or(0, Journal::len),
+            changes: mem::take(&mut self.changes),
+            handle: tokio::task::spawn_blocking(move || {
+                let _lock = storage.lock(&path);
+                // written by another program since the check, the next save checks it again
+                if storage.modified(&path) != checked {
+                    return Err(errors::Errors::SaveError {
+                        path,
+                        reason: "the file was changed meanwhile".to_string(),
+                    });
+                }
+                // the archive first, so a failed write leaves the data file as it was
+                if let Some(archive) = &archive {
+                    storage.write(&data::archive_path(&path), archive)?;
+                }
+                storage.write(path.as_path(), &content)?;
+                let stamp = storage
+                    .modified(&path)
+                    .map(|modified| FileStamp::new(modified, &content));
+                Ok((archive, stamp))
+            }),
+        }
+    }
+
+    /// Whether the data file was rolled back since the last save of the session
+    ///
+    /// The file is rolled back if it's now older than the save and holds something else, see
+    /// [`FileStamp::older_than`]. It's only read when its modification time went back.
+    pub fn rolled_back(&self) -> bool {
+        let Some(saved) = &self.saved_stamp else {
+            return false;
+        };
+        let Some(modified) = self.storage.modified(&self.data_path) else {
+            return false;
+        };
+        modified < saved.modified
+            && self
+                .storage
+                .read(&self.data_path)
+                .is_some_and(|content| FileStamp::new(modified, &content).older_than(saved))
+    }
+
+    /// Whether another program, like `todo serve`, changed the data file since the last load
+    /// or save of the session
+    ///
+    /// The file is changed if it's now newer than the load or the save and holds something
+    /// else, see [`FileStamp::newer_than`].
+    pub fn changed_elsewhere(&self) -> bool {
+        let Some(saved) = &self.saved_stamp else {
+            return false;
+        };
+        let Some(modified) = self.storage.modified(&self.data_path) else {
+            return false;
+        };
+        modified > saved.modified
+            && self
+                .storage
+                .read(&self.data_path)
+                .is_some_and(|content| FileStamp::new(modified, &content).newer_than(saved))
+    }
+
+    /// Save the data on exit, see [`data::save_data_on_exit`]
+    ///
+    /// The keys are no longer read on exit, so a data file rolled back or changed elsewhere
+    /// isn't saved over, where a save of the session would ask, see [`Ui::resolve_rollback`]:
+    /// the changes of the session, if any, go to the emergency copy instead, see
+    /// [`data::save_aside_on_exit`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`errors::Errors::ExitSaveError`] if the data file isn't written while there
+    /// are changes, with where the emergency copy went
+    pub fn exit_save(&mut self) -> Result<(), errors::Errors> {
+        let datas = Datas {
+            workspace: self.workspace.clone(),
+            todolist: self.todolist.clone(),
+            archived_ws: self.archived_ws.clone(),
+        };
+        let archive = self.archive_content(&datas);
+
+        let _lock = self.storage.lock(&self.data_path);
+        // the file of another program is kept, and ours too if there are any changes
+        let reason = if self.changed_elsewhere() {
+            Some("it was changed by another program since the last save")
+        } else if self.rolled_back() {
+            Some("it was rolled back to an older version since the last save")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            if !self.dirty {
+                return Ok(());
+            }
+            return Err(data::save_aside_on_exit(
+                &self.data_path,
+                &datas,
+                reason.to_string(),
+            ));
+        }
+        data::save_data_on_exit(self.data_path.as_path(), &datas, archive.as_deref())?;
+        if let Some(journal) = &mut self.journal {
+            let _ = journal.saved(journal.len());
+        }
+        Ok(())
+    }
+
+    /// Start writing the data unless the data file was rolled back or changed elsewhere, in
+    /// which case the user picks what to do first, see [`Ui::resolve_rollback`]
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Saving>` - the save started, None if nothing is written
+    async fn start_checked_save<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        appstate: &Arc<Mutex<AppState>>,
+    ) -> Option<Saving> {
+        let changed = self.changed_elsewhere();
+        if !changed && !self.rolled_back() {
+            return Some(self.start_save());
+        }
+        appstate.lock().unwrap().open_popup(CurrentMode::Insert);
+        let input_rx = self.input_rx.clone();
+        let choice = self.resolve_rollback(input_rx, terminal, changed).await;
+        appstate.lock().unwrap().close_popup();
+        self.needs_redraw = true;
+        match choice {
+            Rollback::Keep => Some(self.start_save()),
+            Rollback::Reload => {
+                match self.reload_data() {
+                    Ok(()) => self.prompt.set(tr("Data Reloaded From The File !")),
+                    Err(err) => {
+                        self.prompt.set(trf("Reload Failed: {}", &[&err]));
+                        self.ring();
+                    }
+                }
+                None
+            }
+            Rollback::Later => {
+                if changed {
+                    self.prompt
+                        .set(tr("Not Saved, The Data File Was Changed !"));
+                } else {
+                    self.prompt
+                        .set(tr("Not Saved, The Data File Was Rolled Back !"));
+                }
+                None
+            }
+        }
+    }
+
+    /// Replace the data of the app with the data file, dropping the changes since the last
+    /// save
+    ///
+    /// The panels keep their focus and their settings, and the workspace and the task
+    /// selected stay so if the file has them. The archive file is read again when needed, see
+    /// [`Ui::load_archive`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`errors::Errors::LoadError`] if the file can't be read or isn't a save of the
+    /// data, the data of the app is then left as it was
+    pub fn reload_data(&mut self) -> Result<(), errors::Errors> {
+        let content =
+            std::fs::read_to_string(&self.data_path).map_err(|_| errors::Errors::LoadError)?;
+        let mut datas: Datas =
+            serde_json::from_str(&content).map_err(|_| errors::Errors::LoadError)?;
+        datas.ensure_inbox();
+        datas.archived_ws.unread = data::archive_path(&self.data_path).exists();
+        datas.workspace.focused = self.workspace.focused;
+        datas.archived_ws.focused = self.archived_ws.focused;
+        let ws_id = self
+            .workspace
+            .current_workspace
+            .as_ref()
+            .map(|ws| ws.borrow().id);
+        let task_id = self
+            .todolist
+            .current_todolist
+            .as_ref()
+            .and_then(|list| list.borrow().current_task.clone())
+            .map(|task| task.borrow().id);
+        let old = mem::replace(&mut self.todolist, datas.todolist);
+        self.todolist.focused = old.focused;
+        self.todolist.origin = old.origin;
+        self.todolist.stale_after_days = old.stale_after_days;
+        self.todolist.show_age = old.show_age;
+        self.todolist.absolute_due_after_days = old.absolute_due_after_days;
+        self.todolist.row_numbers = old.row_numbers;
+        self.workspace = datas.workspace;
+        self.archived_ws = datas.archived_ws;
+        self.archive_saved = String::new();
+        if let Some(ws) = WorkspaceWidget::get_flattened(&self.workspace.workspaces)
+            .into_iter()
+            .find(|ws| Some(ws.borrow().id) == ws_id)
+        {
+            self.workspace.select(Some(ws));
+        }
+        self.refresh_current();
+        if let Some(list) = &self.todolist.current_todolist {
+            let task = TodoWidget::get_flattened(&list.borrow().tasks)
+                .into_iter()
+                .find(|task| Some(task.borrow().id) == task_id);
+            if let Some(task) = task {
+                list.borrow_mut().select_task(&task);
+            }
+        }
+        self.dirty = false;
+        self.changes = 0;
+        self.saved_stamp = self
+            .storage
+            .modified(&self.data_path)
+            .map(|modified| FileStamp::new(modified, &content));
+        if let Some(journal) = &mut self.journal
+            && let Err(err) = journal.saved(journal.len())
+        {
+            self.prompt.set(trf("Journal Not Written: {}", &[&err]));
+            self.journal = None;
+        }
+        self.needs_redraw = true;
+        Ok(())
+    }
+
+    /// Wait for a save to be written, see [`Ui::saved`]
+    async fn finish_save(&mut self, mut saving: Saving) {
+        let result = (&mut saving.handle)
+            .await
+            .unwrap_or(Err(errors::Errors::WriteError));
+        self.saved(saving, result);
+    }
+
+    /// Tell how a save went, and drop what it holds from the journal
+    fn saved(&mut self, saving: Saving, result: Result<Written, errors::Errors>) {
+        match result {
+            Ok((archive, stamp)) => {
+                if let Some(archive) = archive {
+                    self.archive_saved = archive;
+                }
+                self.saved_stamp = stamp;
+                match saving.changes {
+                    0 => self.prompt.set(tr("Data Saved !")),
+                    1 => self.prompt.set(tr("Data Saved (1 Change) !")),
+                    changes => self
+                        .prompt
+                        .set(trf("Data Saved ({} Changes) !", &[&changes])),
+                }
+                if let Some(journal) = &mut self.journal
+                    && let Err(err) = journal.saved(saving.journal_mark)
+                {
+                    self.prompt.set(trf("Journal Not Written: {}", &[&err]));
+                    self.journal = None;
+                }
+            }
+            Err(err) => {
+                // the data on disk is still the old one
+                self.dirty = true;
+                self.changes += saving.changes;
+                self.prompt.set(trf("Save Failed: {}", &[&err]));
+                self.ring();
+            }
+        }
+        self.needs_redraw = true;
+    }
+
+    /// The focused panel and the workspace of the todo list shown, saved on leaving them
+    /// when [`Config::autosave`] is [`Autosave::OnBlur`]
+    fn blur_point(&self) -> (CurrentFocus, Option<Uuid>) {
+        let shown = (self.todolist.current_todolist.as_ref()).map(|list| list.borrow().workspace);
+        (self.helpwidget.keymap.focus.clone(), shown)
+    }
+
+    /// Whether the data is saved now as [`Config::autosave`] says, given the point reached
+    ///
+    /// Nothing is saved while nothing changed or the data file can't be written.
+    fn autosaves(&self, point: Autosave) -> bool {
+        self.config.autosave == point && point != Autosave::Off && self.dirty && !self.read_only
+    }
+
+    /// Handle the UI messages until the channel is closed
+    ///
+    /// The handlers only mark the UI as needing a redraw, see [`Ui::needs_redraw`], and one
+    /// frame is drawn once no message is left waiting, so a burst of messages, e.g. a macro
+    /// replay or a command palette action, is drawn once rather than once per message. The
+    /// popups still draw themselves while they are open.
+    ///
+    /// The saves are batched the same way: the data is written [`SAVE_WINDOW`] after the first
+    /// request, once for every request made meanwhile, and a request made while a write is
+    /// running waits for it, so one write at most runs and an older snapshot never overwrites
+    /// a newer one. The data is taken when the write starts, so the last state is the one
+    /// written, and a requested save is still written when the channel closes.
+    pub async fn handle_uimsg<B: Backend + Bell>(
         &mut self,
-        terminal: &mut DefaultTerminal,
+        terminal: &mut Terminal<B>,
         appstate: Arc<Mutex<AppState>>,
     ) {
-        while let Some(msg) = self.ui_rx.recv().await {
+        let mut saving: Option<Saving> = None;
+        // when the requested save is written
+        let mut save_at: Option<Instant> = None;
+        let mut spinner = tokio::time::interval(Duration::from_millis(100));
+        spinner.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            if self.needs_redraw && self.pending.is_empty() && self.ui_rx.is_empty() {
+                self.needs_redraw = false;
+                let _ = terminal.draw(|f| self.update(f));
+                if self.bell {
+                    self.bell = false;
+                    let _ = terminal.backend_mut().ring();
+                }
+            }
+            let msg = if let Some(msg) = self.pending.pop_front() {
+                msg
+            } else {
+                tokio::select! {
+                msg = self.ui_rx.recv() => match msg {
+                    Some(msg) => msg,
+                    None => {
+                        // the saves are written before the data is saved one last time on exit
+                        if let Some(previous) = saving.take() {
+                            self.finish_save(previous).await;
+                        }
+                        if save_at.take().is_some()
+                            && let Some(last) = self.start_checked_save(terminal, &appstate).await
+                        {
+                            self.finish_save(last).await;
+                        }
+                        break;
+                    }
+                },
+                result = async { (&mut saving.as_mut().unwrap().handle).await }, if saving.is_some() => {
+                    if let Some(done) = saving.take() {
+                        self.saved(done, result.unwrap_or(Err(errors::Errors::WriteError)));
+                    }
+                    continue;
+                }
+                _ = async { tokio::time::sleep_until(save_at.unwrap()).await }, if save_at.is_some() && saving.is_none() => {
+                    save_at = None;
+                    saving = self.start_checked_save(terminal, &appstate).await;
+                    continue;
+                }
+                _ = spinner.tick(), if saving.is_some() => {
+                    self.prompt.spin(tr("Saving ..."));
+                    self.needs_redraw = true;
+                    continue;
+                }
+                }
+            };
+            // the popup of the action takes the keys until the action is done, whether it's
+            // confirmed, cancelled or skipped, the arms below don't switch in and out of it
+            let popup = match &msg {
+                UiMessage::WAction(waction) => waction.popup_mode(),
+                _ => None,
+            };
+            if let Some(mode) = popup {
+                appstate.lock().unwrap().open_popup(mode);
+            }
+            let skipped = match &msg {
+                UiMessage::WAction(waction) => match self.unmet_precondition(waction) {
+                    Some(reason) => {
+                        self.prompt.set(tr(reason));
+                        true
+                    }
+                    None => {
+                        *waction == WidgetAction::DeleteWorkspace
+                            && self.refuse_protected(self.workspace.current_workspace.clone())
+                    }
+                },
+                _ => false,
+            };
+            if let UiMessage::WAction(waction) = &msg
+                && !skipped
+                && waction.modifies_data()
+            {
+                self.dirty = true;
+                self.changes += 1;
+            }
+            // the keys typed into the popups of the action are journaled with it
+            let journaled = match &msg {
+                UiMessage::WAction(waction)
+                    if !skipped && waction.modifies_data() && self.journal.is_some() =>
+                {
+                    keys::tap();
+                    let focus = appstate.lock().unwrap().current_focus.clone();
+                    Some(journal::Entry {
+                        action: waction.clone(),
+                        selection: self.selection(focus),
+                        keys: Vec::new(),
+                    })
+                }
+                _ => None,
+            };
+            // the keys fed for the popups of the next action
+            let feeding = matches!(msg, UiMessage::Replay(_) | UiMessage::Edit(_));
+            // where the user was, to save on leaving it
+            let blurred_from = self.blur_point();
+            let opens_help = matches!(msg, UiMessage::WAction(WidgetAction::Help));
             match msg {
+                _ if skipped => {
+                    self.needs_redraw = true;
+                }
                 UiMessage::Update => {
-                    let _result = terminal.draw(|f| self.update(f));
+                    self.needs_redraw = true;
                 }
                 UiMessage::UpdateUi => {
-                    let _result = terminal.draw(|f| self.update(f));
+                    self.escalate_overdue(&SystemClock);
+                    self.announce_due(&SystemClock);
+                    if self.autosaves(Autosave::Interval) {
+                        self.pending.push_back(UiMessage::SaveData);
+                    }
+                    self.needs_redraw = true;
+                }
+                UiMessage::Replay(entry) => {
+                    self.restore_selection(&entry.selection);
+                    appstate.lock().unwrap().current_focus = entry.selection.focus.clone();
+                    keys::feed(entry.key_events());
+                    self.pending.push_front(UiMessage::WAction(entry.action));
+                    self.needs_redraw = true;
+                }
+                UiMessage::Edit(key) => keys::feed([key]),
+                UiMessage::SaveData if self.read_only => {
+                    self.prompt.set(tr("Read-Only, Nothing Is Saved !"));
+                    self.needs_redraw = true;
                 }
                 UiMessage::SaveData => {
-                    let path = Path::new(
-                        std::env::home_dir()
-                            .unwrap_or(std::path::PathBuf::from("~"))
-                            .as_path(),
-                    )
-                    .join(".todo/data.json");
-                    let datas = Datas {
-                        workspace: self.workspace.clone(),
-                        todolist: self.todolist.clone(),
-                        archived_ws: self.archived_ws.clone(),
-                    };
-
-                    let _ = data::save_data(path.as_path(), &datas);
-                    self.prompt.desc = "Data Saved !".to_string();
-                    let _ = terminal.draw(|f| self.update(f));
+                    // the requests until then are written at once
+                    save_at.get_or_insert_with(|| Instant::now() + SAVE_WINDOW);
+                    self.prompt.spin(tr("Saving ..."));
+                    self.needs_redraw = true;
                 }
                 UiMessage::WAction(waction) => match waction {
                     WidgetAction::FocusWorkspace => {
@@ -857,137 +4425,222 @@ impl Ui {
                         self.todolist.focused = false;
                         self.archived_ws.focused = false;
                         self.helpwidget.keymap.focus = CurrentFocus::Workspace;
-                        let _result = terminal.draw(|f| self.update(f));
+                        self.needs_redraw = true;
                     }
                     WidgetAction::FocusTodolist => {
                         self.workspace.focused = false;
                         self.todolist.focused = true;
                         self.archived_ws.focused = false;
                         self.helpwidget.keymap.focus = CurrentFocus::TodoList;
-                        let _result = terminal.draw(|f| self.update(f));
+                        self.needs_redraw = true;
                     }
                     WidgetAction::FocusArchivedWorkspace => {
+                        self.load_archive();
                         self.archived_ws.focused = true;
                         self.todolist.focused = false;
                         self.workspace.focused = false;
                         self.helpwidget.keymap.focus = CurrentFocus::ArchivedWorkspace;
-                        let _result = terminal.draw(|f| self.update(f));
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::FocusBack => {
+                        let focus = match self.todolist.origin {
+                            WorkspaceType::Normal => CurrentFocus::Workspace,
+                            WorkspaceType::Archived => CurrentFocus::ArchivedWorkspace,
+                        };
+                        self.workspace.focused = matches!(focus, CurrentFocus::Workspace);
+                        self.archived_ws.focused = matches!(focus, CurrentFocus::ArchivedWorkspace);
+                        self.todolist.focused = false;
+                        self.helpwidget.keymap.focus = focus.clone();
+                        appstate.lock().unwrap().current_focus = focus;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::AddWorkspace => {
                         let input_rx = self.input_rx.clone();
                         let result = self
-                            .get_input(input_rx, terminal, "Add Workspace".to_string())
+                            .get_name(
+                                input_rx,
+                                terminal,
+                                tr("Add Workspace").to_string(),
+                                self.workspace.width,
+                            )
                             .await;
-                        if !result.is_empty() {
-                            let ws = Rc::new(RefCell::new(Workspace::new(result)));
+                        let siblings: Vec<String> = (self.workspace.workspaces.iter())
+                            .map(|ws| ws.borrow().desc.clone())
+                            .collect();
+                        if let Some(name) = self.name_to_add(result, "Workspace", &siblings) {
+                            let ws = Rc::new(RefCell::new(Workspace::new(name)));
                             let ws_id = ws.borrow().id;
                             self.workspace.add_workspace(ws);
                             self.todolist
                                 .add_list(Rc::new(RefCell::new(TodoList::new(ws_id))));
+                            self.prompt.set(tr("Workspace Added !"));
                         }
-                        self.prompt.desc = "Workspace Added !".to_string();
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::AddWorkspaceChild => {
                         let input_rx = self.input_rx.clone();
                         let result = self
-                            .get_input(input_rx, terminal, "Add Subworkspace".to_string())
+                            .get_name(
+                                input_rx,
+                                terminal,
+                                tr("Add Subworkspace").to_string(),
+                                self.workspace.width,
+                            )
                             .await;
-                        if !result.is_empty() {
-                            let workspace = Rc::new(RefCell::new(Workspace::new(result)));
+                        let siblings: Vec<String> = match &self.workspace.current_workspace {
+                            Some(parent) => parent.borrow().children.clone(),
+                            None => self.workspace.workspaces.clone(),
+                        }
+                        .iter()
+                        .map(|ws| ws.borrow().desc.clone())
+                        .collect();
+                        if let Some(name) = self.name_to_add(result, "Workspace", &siblings) {
+                            let workspace = Rc::new(RefCell::new(Workspace::new(name)));
                             let ws_id = workspace.borrow().id.to_owned();
                             self.workspace.add_child_workspace(workspace);
                             self.todolist
                                 .add_list(Rc::new(RefCell::new(TodoList::new(ws_id))));
+                            self.prompt.set(tr("Workspace Added !"));
                         }
-                        self.prompt.desc = "Workspace Added !".to_string();
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::AddTask => {
                         let input_rx = self.input_rx.clone();
                         let result = self
-                            .get_input(input_rx, terminal, "Add Task".to_string())
+                            .get_name(
+                                input_rx,
+                                terminal,
+                                tr("Add Task").to_string(),
+                                self.todolist.width,
+                            )
                             .await;
-                        if !result.is_empty() {
+                        let inbox = self.todolist.inbox.and_then(|id| {
+                            WorkspaceWidget::get_flattened(&self.workspace.workspaces)
+                                .into_iter()
+                                .find(|ws| ws.borrow().id == id)
+                        });
+                        // the tasks are added to the open list, or the Inbox when none is open
+                        let siblings: Vec<String> = (self.todolist.current_todolist.clone())
+                            .or_else(|| {
+                                let inbox = inbox.as_ref()?.borrow().id;
+                                self.todolist.list_of(inbox)
+                            })
+                            .map(|list| {
+                                (list.borrow().tasks.iter())
+                                    .map(|task| task.borrow().desc.clone())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        let tasks = match self.name_to_add(result, "Task", &siblings) {
+                            Some(name) => self.typed_tasks(&name),
+                            None => Vec::new(),
+                        };
+                        if !tasks.is_empty() {
+                            if self.todolist.current_todolist.is_none() {
+                                if let Some(inbox) = inbox {
+                                    // nothing selected, capture the task into the Inbox
+                                    self.workspace.select(Some(inbox.clone()));
+                                    self.todolist.change_current_list(&Some(inbox));
+                                    self.todolist.origin = WorkspaceType::Normal;
+                                } else {
+                                    let name = names::unique_name(
+                                        tr("Workspace"),
+                                        (self.workspace.workspaces.iter())
+                                            .map(|ws| ws.borrow().desc.clone()),
+                                    );
+                                    let ws = Rc::new(RefCell::new(Workspace::new(name)));
+                                    let ws_id = ws.borrow().id;
+                                    let todolist = Rc::new(RefCell::new(TodoList::new(ws_id)));
+                                    self.workspace.add_workspace(ws.clone());
+                                    self.todolist.add_list(todolist.clone());
+                                    self.workspace.current_workspace = Some(ws.clone());
+                                    self.todolist.current_todolist = Some(todolist);
+                                    self.todolist.workspace = Some(ws);
+                                }
+                            }
                             if let Some(ctl) = &self.todolist.current_todolist {
                                 let mut ctl_mut = ctl.borrow_mut();
-                                ctl_mut.add_task(Rc::new(RefCell::new(Task::new(result, None))));
-                            } else {
-                                let ws =
-                                    Rc::new(RefCell::new(Workspace::new("Workspace".to_string())));
-                                let ws_id = ws.borrow().id;
-                                let todolist = Rc::new(RefCell::new(TodoList::new(ws_id)));
-                                todolist
-                                    .borrow_mut()
-                                    .add_task(Rc::new(RefCell::new(Task::new(result, None))));
-                                self.workspace.add_workspace(ws.clone());
-                                self.todolist.add_list(todolist.clone());
-                                self.workspace.current_workspace = Some(ws);
-                                self.todolist.current_todolist = Some(todolist);
+                                tasks.iter().for_each(|task| ctl_mut.add_task(task.clone()));
+                                if tasks.len() > 1 {
+                                    ctl_mut.select_task(&tasks[0]);
+                                }
                             }
+                            self.prompt.set(Ui::added_label(tasks.len()));
                         }
-                        self.prompt.desc = "Task Added !".to_string();
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::AddTaskChild => {
                         let input_rx = self.input_rx.clone();
                         let result = self
-                            .get_input(input_rx, terminal, "Add Subtask".to_string())
+                            .get_name(
+                                input_rx,
+                                terminal,
+                                tr("Add Subtask").to_string(),
+                                self.todolist.width,
+                            )
                             .await;
-                        if !result.is_empty()
+                        let siblings: Vec<String> = (self.todolist.current_todolist.clone())
+                            .map(|list| {
+                                let list = list.borrow();
+                                match &list.current_task {
+                                    Some(parent) => parent.borrow().children.clone(),
+                                    None => list.tasks.clone(),
+                                }
+                                .iter()
+                                .map(|task| task.borrow().desc.clone())
+                                .collect()
+                            })
+                            .unwrap_or_default();
+                        let tasks = match self.name_to_add(result, "Task", &siblings) {
+                            Some(name) => self.typed_tasks(&name),
+                            None => Vec::new(),
+                        };
+                        if !tasks.is_empty()
                             && let Some(ctl) = &self.todolist.current_todolist
                         {
                             let mut ctl_mut = ctl.borrow_mut();
-                            ctl_mut.add_child_task(Rc::new(RefCell::new(Task::new(result, None))));
+                            tasks
+                                .iter()
+                                .for_each(|task| ctl_mut.add_child_task(task.clone()));
+                            if tasks.len() > 1 {
+                                ctl_mut.select_task(&tasks[0]);
+                            }
+                            self.prompt.set(Ui::added_label(tasks.len()));
                         }
-                        self.prompt.desc = "Task Added !".to_string();
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::EnterWorkspace => {
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_focus = CurrentFocus::TodoList;
+                        appstate.lock().unwrap().current_focus = CurrentFocus::TodoList;
                         self.workspace.focused = false;
                         self.todolist.focused = true;
                         self.helpwidget.keymap.focus = CurrentFocus::TodoList;
                         self.todolist
                             .change_current_list(&self.workspace.current_workspace);
-                        let _result = terminal.draw(|f| self.update(f));
+                        self.todolist.origin = WorkspaceType::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::EnterArchivedWorkspace => {
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_focus = CurrentFocus::TodoList;
+                        appstate.lock().unwrap().current_focus = CurrentFocus::TodoList;
                         self.workspace.focused = false;
                         self.archived_ws.focused = false;
                         self.todolist.focused = true;
                         self.helpwidget.keymap.focus = CurrentFocus::TodoList;
                         self.todolist
                             .change_current_list(&self.archived_ws.current_workspace);
-                        let _result = terminal.draw(|f| self.update(f));
+                        self.todolist.origin = WorkspaceType::Archived;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::SelectUp => {
-                        let apps = appstate.lock().unwrap();
-                        if let CurrentMode::Help = apps.current_mode {
+                        let (current_mode, current_focus) = {
+                            let apps = appstate.lock().unwrap();
+                            (apps.current_mode, apps.current_focus.clone())
+                        };
+                        if let CurrentMode::Help = current_mode {
                             self.helpwidget.scroll = self.helpwidget.scroll.saturating_sub(1);
                             self.helpwidget.state =
                                 self.helpwidget.state.position(self.helpwidget.scroll);
                         } else {
-                            match apps.current_focus {
+                            match current_focus {
                                 CurrentFocus::Workspace => {
                                     // self.workspace.current_workspace = Workspace::get_selected_bf(
                                     //     &self.workspace.current_workspace,
@@ -999,6 +4652,7 @@ impl Ui {
                                         self.workspace.get_selected_bf(SelectBF::Back);
                                     self.todolist
                                         .change_current_list(&self.workspace.current_workspace);
+                                    self.todolist.origin = WorkspaceType::Normal;
                                 }
                                 CurrentFocus::TodoList => {
                                     let cur_task = self.todolist.get_selected_bf(SelectBF::Back);
@@ -1029,14 +4683,152 @@ impl Ui {
                                         self.archived_ws.get_selected_bf(SelectBF::Back);
                                     self.todolist
                                         .change_current_list(&self.archived_ws.current_workspace);
+                                    self.todolist.origin = WorkspaceType::Archived;
+                                }
+                            }
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::Triage => {
+                        self.triage_inbox(terminal).await;
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::EnterTask => {
+                        let toggled = (self.todolist.current_todolist.as_ref())
+                            .and_then(|list| list.borrow_mut().toggle_current_task());
+                        // a task without subtasks opens its notes instead
+                        if toggled.is_none() {
+                            self.edit_current_notes(terminal).await;
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::ToggleProtected => {
+                        if let Some(cur_ws) = &self.workspace.current_workspace {
+                            let mut cur_ws_mut = cur_ws.borrow_mut();
+                            if Some(cur_ws_mut.id) == self.todolist.inbox {
+                                self.prompt.set(tr("The Inbox Is Always Protected !"));
+                            } else {
+                                cur_ws_mut.protected = !cur_ws_mut.protected;
+                                self.prompt.set(tr(if cur_ws_mut.protected {
+                                    "Workspace Protected !"
+                                } else {
+                                    "Workspace Unprotected !"
+                                }));
+                            }
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::Palette => {
+                        let focus = appstate.lock().unwrap().current_focus.clone();
+                        let input_rx = self.input_rx.clone();
+                        let picked = self.pick_command(input_rx, terminal, &focus).await;
+                        if let Some(msg) = picked {
+                            self.run_message(msg, &appstate);
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::SearchAll => {
+                        self.load_archive();
+                        let input_rx = self.input_rx.clone();
+                        let revealed = match self.search_everywhere(input_rx, terminal).await {
+                            Some(SearchHit::Task(ws_id, task)) => self.reveal_task(ws_id, &task),
+                            Some(SearchHit::Workspace(kind, ws)) => {
+                                self.reveal_workspace(kind, &ws)
+                            }
+                            None => false,
+                        };
+                        if revealed {
+                            appstate.lock().unwrap().current_focus = CurrentFocus::TodoList;
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::KeyHelp => {
+                        let input_rx = self.input_rx.clone();
+                        if let Some(msg) = self.key_help(input_rx, terminal).await {
+                            self.run_message(msg, &appstate);
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::Heatmap => {
+                        let input_rx = self.input_rx.clone();
+                        let picked = self.due_heatmap(input_rx, terminal).await;
+                        let mut apps = appstate.lock().unwrap();
+                        if let Some((ws_id, task)) = picked
+                            && self.reveal_task(ws_id, &task)
+                        {
+                            apps.current_focus = CurrentFocus::TodoList;
+                        }
+                        drop(apps);
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::BannerJump => {
+                        if let Some(banner) = self.banner.take()
+                            && self.reveal_task(banner.workspace, &banner.task)
+                        {
+                            appstate.lock().unwrap().current_focus = CurrentFocus::TodoList;
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::DismissBanner => {
+                        self.banner = None;
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::Prompt(desc) => {
+                        self.prompt.set(desc);
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::ToggleArchived => {
+                        self.archived_shown = Some(!self.archived_visible());
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::Jump(to) => {
+                        let current_focus = appstate.lock().unwrap().current_focus.clone();
+                        match current_focus {
+                            CurrentFocus::Workspace => {
+                                self.workspace.jump(to);
+                                self.todolist
+                                    .change_current_list(&self.workspace.current_workspace);
+                                self.todolist.origin = WorkspaceType::Normal;
+                            }
+                            CurrentFocus::TodoList => self.todolist.jump(to),
+                            CurrentFocus::ArchivedWorkspace => {
+                                self.archived_ws.jump(to);
+                                self.todolist
+                                    .change_current_list(&self.archived_ws.current_workspace);
+                                self.todolist.origin = WorkspaceType::Archived;
+                            }
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::Fold(fold) => {
+                        let current_focus = appstate.lock().unwrap().current_focus.clone();
+                        match current_focus {
+                            CurrentFocus::Workspace => {
+                                self.workspace.fold(fold);
+                                self.todolist
+                                    .change_current_list(&self.workspace.current_workspace);
+                                self.todolist.origin = WorkspaceType::Normal;
+                            }
+                            CurrentFocus::TodoList => {
+                                if let Some(cur_list) = &self.todolist.current_todolist {
+                                    cur_list.borrow_mut().fold(fold);
                                 }
                             }
+                            CurrentFocus::ArchivedWorkspace => {
+                                self.archived_ws.fold(fold);
+                                self.todolist
+                                    .change_current_list(&self.archived_ws.current_workspace);
+                                self.todolist.origin = WorkspaceType::Archived;
+                            }
                         }
-                        let _ = terminal.draw(|f| self.update(f));
+                        self.needs_redraw = true;
                     }
                     WidgetAction::SelectDown => {
-                        let apps = appstate.lock().unwrap();
-                        if let CurrentMode::Help = apps.current_mode {
+                        let (current_mode, current_focus) = {
+                            let apps = appstate.lock().unwrap();
+                            (apps.current_mode, apps.current_focus.clone())
+                        };
+                        if let CurrentMode::Help = current_mode {
                             self.helpwidget.scroll = self
                                 .helpwidget
                                 .scroll
@@ -1045,7 +4837,7 @@ impl Ui {
                             self.helpwidget.state =
                                 self.helpwidget.state.position(self.helpwidget.scroll);
                         } else {
-                            match apps.current_focus {
+                            match current_focus {
                                 CurrentFocus::Workspace => {
                                     // self.workspace.current_workspace = Workspace::get_selected_bf(
                                     //     &self.workspace.current_workspace,
@@ -1057,6 +4849,7 @@ impl Ui {
                                         self.workspace.get_selected_bf(SelectBF::Forward);
                                     self.todolist
                                         .change_current_list(&self.workspace.current_workspace);
+                                    self.todolist.origin = WorkspaceType::Normal;
                                 }
                                 CurrentFocus::TodoList => {
                                     let cur_task = self.todolist.get_selected_bf(SelectBF::Forward);
@@ -1087,10 +4880,11 @@ impl Ui {
                                         self.archived_ws.get_selected_bf(SelectBF::Forward);
                                     self.todolist
                                         .change_current_list(&self.archived_ws.current_workspace);
+                                    self.todolist.origin = WorkspaceType::Archived;
                                 }
                             }
                         }
-                        let _ = terminal.draw(|f| self.update(f));
+                        self.needs_redraw = true;
                     }
                     WidgetAction::DeleteWorkspace => {
                         let input_rx = self.input_rx.clone();
@@ -1099,14 +4893,15 @@ impl Ui {
                             let cur_ws_opt = self.workspace.current_workspace.clone();
                             let mut second_confirm = true;
                             if let Some(cur_ws) = &cur_ws_opt {
-                                let cur_ws_bo = cur_ws.borrow();
-                                if !cur_ws_bo.children.is_empty() {
+                                let has_children = !cur_ws.borrow().children.is_empty();
+                                let has_todolist = cur_ws.borrow().has_todolist(&self.todolist);
+                                if has_children {
                                     let input_rx = self.input_rx.clone();
                                     second_confirm = self
                                         .confirm_delete(input_rx, terminal, CurrentFocus::Workspace)
                                         .await;
                                 }
-                                if cur_ws_bo.has_todolist(&self.todolist) && second_confirm {
+                                if has_todolist && second_confirm {
                                     let input_rx = self.input_rx.clone();
                                     second_confirm = self
                                         .confirm_delete(input_rx, terminal, CurrentFocus::TodoList)
@@ -1117,17 +4912,17 @@ impl Ui {
                                         &mut self.workspace.workspaces,
                                         cur_ws,
                                     );
-                                    let tar_ws = cur_ws_bo.id;
                                     self.workspace.current_workspace = None;
                                     self.workspace.ws_state.select(None);
-                                    self.todolist.delete_list(tar_ws);
+                                    // the lists of sub workspaces go away with their parent
+                                    WorkspaceWidget::get_flattened(std::slice::from_ref(cur_ws))
+                                        .iter()
+                                        .for_each(|ws| self.todolist.delete_list(ws.borrow().id));
                                 }
                             }
                         }
-                        self.prompt.desc = "Workspace Deleted !".to_string();
-                        let _ = terminal.draw(|f| self.update(f));
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.prompt.set(tr("Workspace Deleted !"));
+                        self.needs_redraw = true;
                     }
                     WidgetAction::DeleteArchivedWorkspace => {
                         let input_rx = self.input_rx.clone();
@@ -1136,8 +4931,9 @@ impl Ui {
                             let cur_ws_opt = self.archived_ws.current_workspace.clone();
                             let mut second_confirm = true;
                             if let Some(cur_ws) = &cur_ws_opt {
-                                let cur_ws_bo = cur_ws.borrow();
-                                if !cur_ws_bo.children.is_empty() {
+                                let has_children = !cur_ws.borrow().children.is_empty();
+                                let has_todolist = cur_ws.borrow().has_todolist(&self.todolist);
+                                if has_children {
                                     let input_rx = self.input_rx.clone();
                                     second_confirm = self
                                         .confirm_delete(
@@ -1147,7 +4943,7 @@ impl Ui {
                                         )
                                         .await;
                                 }
-                                if cur_ws_bo.has_todolist(&self.todolist) && second_confirm {
+                                if has_todolist && second_confirm {
                                     let input_rx = self.input_rx.clone();
                                     second_confirm = self
                                         .confirm_delete(input_rx, terminal, CurrentFocus::TodoList)
@@ -1158,71 +4954,94 @@ impl Ui {
                                         &mut self.archived_ws.workspaces,
                                         cur_ws,
                                     );
-                                    let tar_ws = cur_ws_bo.id;
                                     self.archived_ws.current_workspace = None;
                                     self.archived_ws.ws_state.select(None);
-                                    self.todolist.delete_list(tar_ws);
+                                    // the lists of sub workspaces go away with their parent
+                                    WorkspaceWidget::get_flattened(std::slice::from_ref(cur_ws))
+                                        .iter()
+                                        .for_each(|ws| self.todolist.delete_list(ws.borrow().id));
                                 }
                             }
                         }
-                        self.prompt.desc = "Workspace Deleted !".to_string();
-                        let _ = terminal.draw(|f| self.update(f));
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.prompt.set(tr("Workspace Deleted !"));
+                        self.needs_redraw = true;
                     }
                     WidgetAction::DeleteTask => {
-                        let input_rx = self.input_rx.clone();
-                        let result = self.delete_item(input_rx, terminal).await;
-                        if result {
-                            let cur_list_opt = self.todolist.current_todolist.clone();
-                            let mut to_second_confirm = false;
-                            if let Some(cur_list) = cur_list_opt {
-                                let cur_list = cur_list.borrow();
-                                let cur_task_opt = cur_list.current_task.clone();
-                                if let Some(cur_task) = cur_task_opt {
-                                    let cur_task = cur_task.borrow();
-                                    if !cur_task.children.is_empty() {
-                                        to_second_confirm = true;
-                                    }
-                                }
-                            }
-                            if to_second_confirm {
+                        // the task is found again by its id after the popups, no borrow of the
+                        // list or of the task is held across them
+                        let cur_list = self.todolist.current_todolist.clone();
+                        let target = cur_list.as_ref().and_then(|list| {
+                            let id = list.borrow().current_task_id()?;
+                            let has_children =
+                                !list.borrow().task(id)?.borrow().children.is_empty();
+                            Some((id, has_children))
+                        });
+                        if let (Some(cur_list), Some((id, has_children))) = (cur_list, target) {
+                            let input_rx = self.input_rx.clone();
+                            let mut confirmed = self.delete_item(input_rx, terminal).await;
+                            if confirmed && has_children {
                                 let input_rx = self.input_rx.clone();
-                                let second_confirm = self
+                                confirmed = self
                                     .confirm_delete(input_rx, terminal, CurrentFocus::TodoList)
                                     .await;
-                                if second_confirm {
-                                    let cur_list_opt = self.todolist.current_todolist.clone();
-                                    if let Some(cur_list) = cur_list_opt {
-                                        let mut cur_list_mut = cur_list.borrow_mut();
-                                        cur_list_mut.delete_task();
-                                    }
-                                }
-                            } else {
-                                let cur_list_opt = self.todolist.current_todolist.clone();
-                                if let Some(cur_list) = cur_list_opt {
-                                    let mut cur_list_mut = cur_list.borrow_mut();
-                                    cur_list_mut.delete_task();
-                                }
+                            }
+                            if confirmed && cur_list.borrow_mut().remove_task(id).is_some() {
+                                cur_list.borrow_mut().set_current_task_none();
+                                self.prompt.set(tr("Task Deleted !"));
                             }
                         }
-                        self.prompt.desc = "Task Deleted !".to_string();
-                        let _ = terminal.draw(|f| self.update(f));
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::MarkTaskStatus(status) => {
-                        if let Some(cur_list) = &self.todolist.current_todolist
-                            && let Some(cur_task) = &cur_list.borrow().current_task
-                        {
-                            Task::set_task_status(cur_task, status);
+                        let cur_task = self
+                            .todolist
+                            .current_todolist
+                            .as_ref()
+                            .and_then(|list| list.borrow().current_task.clone());
+                        if let Some(cur_task) = cur_task {
+                            // deprecating takes the open subtasks along, as deleting does
+                            let open = TaskStats::of(&cur_task.borrow().children).open();
+                            let confirmed = status != TaskStatus::Deprecated
+                                || open == 0
+                                || self
+                                    .confirm_deprecate(self.input_rx.clone(), terminal, open)
+                                    .await;
+                            if confirmed {
+                                let was_open = matches!(
+                                    cur_task.borrow().status,
+                                    TaskStatus::Todo | TaskStatus::InProcess
+                                );
+                                Task::set_task_status(&cur_task, status.clone());
+                                if was_open && status == TaskStatus::Finished {
+                                    self.finish_parents(&cur_task, terminal, &appstate).await;
+                                }
+                            }
                         }
                         // if let Some(cur_list) = &self.todolist.current_todolist {
                         //     if let Some(cur_task) = &cur_list.borrow().current_task {
                         //         Task::set_task_status(cur_task, status);
                         //     }
                         // }
-                        let _ = terminal.draw(|f| self.update(f));
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::ToggleArchivedOrder => {
+                        self.load_archive();
+                        let order = self.archived_ws.toggle_order();
+                        self.prompt
+                            .set(trf("Archived Sorted By {} !", &[&tr(&order.to_string())]));
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::ShiftWorkspace(kind, bf) => {
+                        let panel = match kind {
+                            WorkspaceType::Normal => &mut self.workspace,
+                            WorkspaceType::Archived => &mut self.archived_ws,
+                        };
+                        if panel.order == WorkspaceOrder::Name {
+                            self.prompt
+                                .set(tr("Sorted By Name, s Switches To The Saved Order !"));
+                        } else if panel.shift_current(bf) {
+                            self.needs_redraw = true;
+                        }
                     }
                     WidgetAction::Rename(cur_focus) => {
                         match cur_focus {
@@ -1231,40 +5050,40 @@ impl Ui {
                                 if let Some(cur_ws) = &cur_ws_opt {
                                     let input_rx = self.input_rx.clone();
                                     let new_name = self
-                                        .get_input(input_rx, terminal, "Rename".to_string())
-                                        .await;
+                                        .get_name(
+                                            input_rx,
+                                            terminal,
+                                            tr("Rename").to_string(),
+                                            self.workspace.width,
+                                        )
+                                        .await
+                                        .unwrap_or_default();
                                     if !new_name.is_empty() {
-                                        let mut cur_ws_mut = cur_ws.borrow_mut();
-                                        cur_ws_mut.rename(new_name);
+                                        cur_ws.borrow_mut().rename(new_name);
+                                        self.workspace.arrange();
                                     }
                                 }
                             }
                             CurrentFocus::TodoList => {
-                                let mut can_renmae = false;
-                                let cur_todolist_opt = self.todolist.current_todolist.clone();
-                                if let Some(cur_todolist) = cur_todolist_opt {
-                                    let cur_todolist_bor = cur_todolist.borrow();
-                                    let cur_task_opt = cur_todolist_bor.current_task.clone();
-                                    if cur_task_opt.is_some() {
-                                        can_renmae = true;
-                                    }
-                                }
-
-                                if can_renmae {
+                                let cur_list = self.todolist.current_todolist.clone();
+                                let target = cur_list
+                                    .as_ref()
+                                    .and_then(|list| list.borrow().current_task_id());
+                                if let (Some(cur_list), Some(id)) = (cur_list, target) {
                                     let input_rx = self.input_rx.clone();
                                     let new_name = self
-                                        .get_input(input_rx, terminal, "Rename".to_string())
-                                        .await;
+                                        .get_name(
+                                            input_rx,
+                                            terminal,
+                                            tr("Rename").to_string(),
+                                            self.todolist.width,
+                                        )
+                                        .await
+                                        .unwrap_or_default();
                                     if !new_name.is_empty() {
-                                        let cur_list_opt = self.todolist.current_todolist.clone();
-                                        if let Some(cur_list) = cur_list_opt {
-                                            let cur_list_bor = cur_list.borrow();
-                                            let cur_task_opt = cur_list_bor.current_task.clone();
-                                            if let Some(cur_task) = cur_task_opt {
-                                                let mut cur_task_mut = cur_task.borrow_mut();
-                                                cur_task_mut.rename(new_name);
-                                            }
-                                        }
+                                        cur_list
+                                            .borrow()
+                                            .edit_task(id, |task| task.rename(new_name));
                                     }
                                 }
                             }
@@ -1273,192 +5092,382 @@ impl Ui {
                                 if let Some(cur_ws) = &cur_ws_opt {
                                     let input_rx = self.input_rx.clone();
                                     let new_name = self
-                                        .get_input(input_rx, terminal, "Rename".to_string())
-                                        .await;
+                                        .get_name(
+                                            input_rx,
+                                            terminal,
+                                            tr("Rename").to_string(),
+                                            self.archived_ws.width,
+                                        )
+                                        .await
+                                        .unwrap_or_default();
                                     if !new_name.is_empty() {
-                                        let mut cur_ws_mut = cur_ws.borrow_mut();
-                                        cur_ws_mut.rename(new_name);
+                                        cur_ws.borrow_mut().rename(new_name);
+                                        self.archived_ws.arrange();
                                     }
                                 }
                             }
                         }
-                        let _ = terminal.draw(|f| self.update(f));
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::Filter => {
                         let cur_list_opt = self.todolist.current_todolist.clone();
                         if cur_list_opt.is_some() {
                             let input_rx = self.input_rx.clone();
                             let result = self.filter_find(input_rx, terminal).await;
-                            self.todolist.search_string = result;
-                            if let Some(cur_list) = &self.todolist.current_todolist {
-                                let mut cur_list_mut = cur_list.borrow_mut();
-                                cur_list_mut.state.select_first();
-                                for task in cur_list_mut.tasks.iter() {
-                                    if task.borrow().is_target(self.todolist.search_string.clone())
-                                    {
-                                        cur_list_mut.current_task = Some(task.to_owned());
-                                        break;
-                                    }
-                                }
-                            }
+                            self.todolist.apply_filter(result);
                         }
-                        self.prompt.desc = "In Search Mode !".to_string();
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Search;
+                        // the popup closes into the search mode
+                        appstate.lock().unwrap().resume_mode = CurrentMode::Search;
+                        self.helpwidget.keymap.mode = CurrentMode::Search;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::ExitFilter => {
                         self.todolist.search_string = String::new();
                         if let Some(cur_list) = &self.todolist.current_todolist {
                             let mut cur_list_mut = cur_list.borrow_mut();
                             cur_list_mut.state = ListState::default();
+                            // the filter shows the subtasks of the folded tasks too
+                            if let Some(task) = &cur_list_mut.current_task {
+                                tree::ancestors(&cur_list_mut.tasks, task)
+                                    .iter()
+                                    .for_each(|parent| parent.borrow_mut().expanded = true);
+                            }
                         }
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        appstate.lock().unwrap().current_mode = CurrentMode::Normal;
+                        self.helpwidget.keymap.mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::ArchiveWS => {
-                        let cur_ws_opt = self.workspace.current_workspace.clone();
-                        if let Some(cur_ws) = &cur_ws_opt {
-                            self.archived_ws.workspaces.push(cur_ws.to_owned());
-                            WorkspaceWidget::delete_item(&mut self.workspace.workspaces, cur_ws);
-                            self.workspace.current_workspace = None;
-                            self.workspace.ws_state.select(None);
+                        // an archive which can't be read would be overwritten without it
+                        self.load_archive();
+                        let current = self
+                            .workspace
+                            .current_workspace
+                            .clone()
+                            .filter(|_| !self.archived_ws.unread);
+                        if let Some(ws) =
+                            current.filter(|ws| !self.refuse_protected(Some(ws.clone())))
+                        {
+                            let name = ws.borrow().desc.clone();
+                            let open = self.todolist.open_tasks(&ws);
+                            let confirmed = if open > 0 && self.config.confirm_archive {
+                                let input_rx = self.input_rx.clone();
+                                self.confirm_archive(input_rx, terminal, &name, open).await
+                            } else {
+                                true
+                            };
+                            if confirmed {
+                                self.workspace.transfer_current(&mut self.archived_ws);
+                                self.show_selected_list(WorkspaceType::Normal);
+                                self.prompt.set(if open > 0 {
+                                    trf("Archived '{}' ({})", &[&name, &Ui::open_tasks_label(open)])
+                                } else {
+                                    trf("Archived '{}'", &[&name])
+                                });
+                            } else {
+                                self.prompt.set(tr("Archive Cancelled !"));
+                            }
                         }
-                        let _ = terminal.draw(|f| self.update(f));
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::RecoveryWS => {
-                        let cur_ws_opt = self.archived_ws.current_workspace.clone();
-                        if let Some(cur_ws) = &cur_ws_opt {
-                            self.workspace.workspaces.push(cur_ws.to_owned());
-                            WorkspaceWidget::delete_item(&mut self.archived_ws.workspaces, cur_ws);
-                            self.archived_ws.current_workspace = None;
-                            self.archived_ws.ws_state.select(None);
+                        if let Some(cur_ws) = self.archived_ws.current_workspace.clone() {
+                            let name = cur_ws.borrow().desc.clone();
+                            let recovery = match WorkspaceWidget::namesake(
+                                &self.workspace.workspaces,
+                                &name,
+                            ) {
+                                Some(_) => {
+                                    let input_rx = self.input_rx.clone();
+                                    self.resolve_clash(input_rx, terminal, &name).await
+                                }
+                                None => Recovery::KeepBoth,
+                            };
+                            if let Some(msg) = self.recover_current(recovery) {
+                                self.prompt.set(msg);
+                            }
+                            self.show_selected_list(WorkspaceType::Archived);
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::Tour => {
+                        self.helpwidget.keymap.mode = CurrentMode::Normal;
+                        self.tour.start();
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::TourNext => {
+                        if self.tour.advance() {
+                            self.finish_tour(true);
+                            self.prompt.set(tr("Sample Workspace Added !"));
+                            appstate.lock().unwrap().current_mode = CurrentMode::Normal;
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::TourSkip => {
+                        self.finish_tour(false);
+                        appstate.lock().unwrap().current_mode = CurrentMode::Normal;
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::WorkspaceStats => {
+                        if let Some(ws) = self.workspace.current_workspace.clone() {
+                            let input_rx = self.input_rx.clone();
+                            self.show_workspace_stats(input_rx, terminal, &ws).await;
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::ToggleRowNumbers => {
+                        self.todolist.row_numbers = self.todolist.row_numbers.next();
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::GoToRow => {
+                        let input_rx = self.input_rx.clone();
+                        let typed = self
+                            .get_input(input_rx, terminal, tr("Go To Row").to_string())
+                            .await;
+                        let selected = self
+                            .todolist
+                            .current_todolist
+                            .as_ref()
+                            .and_then(|list| list.borrow().state.selected());
+                        let rows = self.todolist.shown_tasks().len();
+                        let typed = typed.trim();
+                        if !typed.is_empty() {
+                            match TodoWidget::target_row(typed, selected, rows) {
+                                Some(row) => {
+                                    self.todolist.select_row(row);
+                                }
+                                None => self.prompt.set(trf("No Row {} !", &[&typed])),
+                            }
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::ImportFile => {
+                        let input_rx = self.input_rx.clone();
+                        let typed = self
+                            .get_input(input_rx, terminal, tr("Import File").to_string())
+                            .await;
+                        // a file dropped on the terminal is typed as its path, often quoted
+                        let typed = typed.trim().trim_matches(['\'', '"']);
+                        if !typed.is_empty()
+                            && let Some(list) = self.todolist.current_todolist.clone()
+                        {
+                            match self.import_file(&list, Path::new(typed)) {
+                                Ok(0) => self.prompt.set(tr("Nothing To Import !")),
+                                Ok(imported) => {
+                                    self.prompt.set(trf("Imported {} Tasks !", &[&imported]))
+                                }
+                                Err(err) => self.prompt.set(trf("File Not Read: {}", &[&err])),
+                            }
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::ClearDone => {
+                        let task = self
+                            .todolist
+                            .current_todolist
+                            .as_ref()
+                            .and_then(|list| list.borrow().current_task.clone());
+                        if let Some(task) = task {
+                            let count = task.borrow().done_subtasks();
+                            let input_rx = self.input_rx.clone();
+                            if count == 0 {
+                                self.prompt.set(tr("No Finished Subtasks !"));
+                            } else if self.confirm_clear_done(input_rx, terminal, count).await {
+                                let removed = task.borrow_mut().clear_done();
+                                self.prompt
+                                    .set(trf("Deleted {} Finished Subtasks !", &[&removed]));
+                            } else {
+                                self.prompt.set(tr("Nothing Deleted !"));
+                            }
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::OpenLink => {
+                        let found = self
+                            .todolist
+                            .current_todolist
+                            .as_ref()
+                            .and_then(|list| list.borrow().current_task.clone())
+                            .map(|task| links::task_links(&task.borrow()))
+                            .unwrap_or_default();
+                        let picked = match found.as_slice() {
+                            [] => {
+                                self.prompt.set(tr("No Link In The Task !"));
+                                None
+                            }
+                            [link] => Some(link.clone()),
+                            _ => {
+                                let input_rx = self.input_rx.clone();
+                                let found: Vec<&str> = found.iter().map(String::as_str).collect();
+                                self.pick_link(input_rx, terminal, &found).await
+                            }
+                        };
+                        if let Some(link) = picked {
+                            match links::open(&link) {
+                                Ok(()) => self.prompt.set(trf("Opening {} !", &[&link])),
+                                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                                    self.prompt.set(tr("No Program To Open Links !"))
+                                }
+                                Err(err) => {
+                                    self.prompt.set(trf("Can't Open The Link: {}", &[&err]))
+                                }
+                            }
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::CopyWorkspace => {
+                        if let Some(ws) = self.workspace.current_workspace.clone() {
+                            let message = self.share_message(&ws, &SystemClock);
+                            let count = message.chars().count();
+                            if clipboard::copy(&message) {
+                                self.prompt.set(trf("Copied {} Characters !", &[&count]));
+                            } else {
+                                self.prompt.set(tr("No Terminal To Copy To !"));
+                            }
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::TaskColor => {
+                        let cur_task = self
+                            .todolist
+                            .current_todolist
+                            .as_ref()
+                            .and_then(|list| list.borrow().current_task.clone());
+                        if let Some(cur_task) = cur_task {
+                            let input_rx = self.input_rx.clone();
+                            match self.pick_color(input_rx, terminal, &cur_task).await {
+                                Some(Some(color)) => {
+                                    self.prompt.set(trf("Colored {} !", &[&tr(&color)]));
+                                    cur_task.borrow_mut().color = Some(color);
+                                }
+                                Some(None) => {
+                                    cur_task.borrow_mut().color = None;
+                                    self.prompt.set(tr("Color Cleared !"));
+                                }
+                                None => self.prompt.set(tr("Color Unchanged !")),
+                            }
                         }
-                        let _ = terminal.draw(|f| self.update(f));
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::EditNotes => {
+                        self.edit_current_notes(terminal).await;
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::Messages => {
+                        let input_rx = self.input_rx.clone();
+                        self.show_messages(input_rx, terminal).await;
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::About => {
+                        let input_rx = self.input_rx.clone();
+                        self.show_about(input_rx, terminal).await;
+                        self.needs_redraw = true;
                     }
                     WidgetAction::Help => {
                         self.helpwidget.keymap.mode = CurrentMode::Help;
-                        self.prompt.desc = "In Help Mode !".to_string();
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
+                        self.overlays.set(Layer::Help, true);
+                        self.helpwidget.open();
+                        self.needs_redraw = true;
                     }
                     WidgetAction::ExitHelp => {
                         self.helpwidget.keymap.mode = CurrentMode::Normal;
-                        self.prompt.desc = "In Normal Mode !".to_string();
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
+                        self.overlays.set(Layer::Help, false);
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::NextHelpTab => {
+                        self.helpwidget.show(self.helpwidget.tab.next());
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::PrevHelpTab => {
+                        self.helpwidget.show(self.helpwidget.tab.prev());
+                        self.needs_redraw = true;
                     }
                     WidgetAction::Due => {
-                        let mut is_to_set = false;
-                        let mut origin_due = None;
-                        let mut apps = appstate.lock().unwrap();
-                        let origin_mode = apps.current_mode;
-                        apps.current_mode = CurrentMode::Insert;
-                        drop(apps);
-
-                        let cur_list_opt = self.todolist.current_todolist.clone();
-                        if let Some(cur_list) = cur_list_opt {
-                            let cur_task_opt = &cur_list.borrow().current_task;
-                            if let Some(cur_task) = cur_task_opt {
-                                is_to_set = true;
-                                origin_due = cur_task.borrow().due;
-                            }
-                        }
-                        if is_to_set {
+                        let cur_list = self.todolist.current_todolist.clone();
+                        let target = cur_list.as_ref().and_then(|list| {
+                            let list = list.borrow();
+                            let id = list.current_task_id()?;
+                            Some((id, list.task(id)?.borrow().due))
+                        });
+                        if let (Some(cur_list), Some((id, origin_due))) = (cur_list, target) {
                             let input_rx = self.input_rx.clone();
                             let date_str = self
                                 .input_due_date(
                                     input_rx,
                                     terminal,
-                                    "Set Due Date".to_string(),
+                                    tr("Set Due Date").to_string(),
                                     origin_due,
                                 )
                                 .await;
-                            if let Some(cur_list) = &self.todolist.current_todolist {
-                                let cur_task_opt = &cur_list.borrow().current_task;
-                                if let Some(cur_task) = cur_task_opt {
-                                    if date_str.is_empty() {
-                                        cur_task.borrow_mut().due = None;
-                                    } else {
-                                        let date_result = NaiveDate::parse_from_str(
-                                            date_str.as_str(),
-                                            "%Y-%m-%d",
-                                        );
-                                        if let Ok(date) = date_result {
-                                            cur_task.borrow_mut().due = Some(date);
-                                        } else {
-                                            let day_re = Regex::new(r"(\d+) days?").unwrap();
-                                            let week_re = Regex::new(r"(\d+) weeks?").unwrap();
-                                            let month_re = Regex::new(r"(\d+) months?").unwrap();
-
-                                            if let Some(caped) =
-                                                day_re.captures_at(date_str.as_str(), 0)
-                                            {
-                                                let date = Local::now()
-                                                    .checked_add_days(Days::new(
-                                                        caped[1].parse().unwrap_or_default(),
-                                                    ))
-                                                    .unwrap()
-                                                    .date_naive();
-                                                cur_task.borrow_mut().due = Some(date);
-                                            } else if let Some(caped) =
-                                                week_re.captures_at(date_str.as_str(), 0)
-                                            {
-                                                let day =
-                                                    caped[1].parse::<i64>().unwrap_or_default() * 7;
-                                                let date = Local::now()
-                                                    .checked_add_days(Days::new(day as u64))
-                                                    .unwrap()
-                                                    .date_naive();
-                                                cur_task.borrow_mut().due = Some(date);
-                                            } else if let Some(caped) =
-                                                month_re.captures_at(date_str.as_str(), 0)
-                                            {
-                                                let date = Local::now()
-                                                    .checked_add_months(Months::new(
-                                                        caped[1].parse().unwrap_or_default(),
-                                                    ))
-                                                    .unwrap()
-                                                    .date_naive();
-                                                cur_task.borrow_mut().due = Some(date);
-                                            } else if date_str == "today" {
-                                                cur_task.borrow_mut().due =
-                                                    Some(Local::now().date_naive());
-                                            } else if date_str == "tomorrow" {
-                                                cur_task.borrow_mut().due = Some(
-                                                    Local::now()
-                                                        .checked_add_days(Days::new(1))
-                                                        .unwrap()
-                                                        .date_naive(),
-                                                );
-                                            } else {
-                                                cur_task.borrow_mut().due =
-                                                    Some(Local::now().date_naive());
-                                            }
-                                        }
+                            cur_list
+                                .borrow()
+                                .edit_task(id, |task| task.set_due_typed(&date_str, &SystemClock));
+                        }
+                        self.prompt.set(tr("Set Due Date !"));
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::DueMatching => {
+                        let count = self.todolist.matching_tasks().len();
+                        if count == 0 {
+                            self.prompt.set(tr("No Task Matches The Filter !"));
+                        } else {
+                            let input_rx = self.input_rx.clone();
+                            let typed = self
+                                .input_due_date(
+                                    input_rx,
+                                    terminal,
+                                    trf("Due Date Of {} Tasks, +7d Shifts", &[&count]),
+                                    None,
+                                )
+                                .await;
+                            if typed.trim().is_empty() {
+                                self.prompt.set(tr("Due Dates Unchanged !"));
+                            } else {
+                                let change = parse::due_change(&typed, &SystemClock);
+                                let (changed, undated, out) =
+                                    self.todolist.set_due_matching(change);
+                                self.prompt.set(match (change.is_shift(), undated, out) {
+                                    (false, _, _) => {
+                                        trf("Set The Due Date Of {} Tasks !", &[&changed])
                                     }
-                                }
+                                    (true, 0, 0) => trf("Shifted {} Due Dates !", &[&changed]),
+                                    (true, _, 0) => trf(
+                                        "Shifted {} Due Dates, Skipped {} Without One !",
+                                        &[&changed, &undated],
+                                    ),
+                                    (true, 0, _) => trf(
+                                        "Shifted {} Due Dates, Skipped {} Out Of The Calendar !",
+                                        &[&changed, &out],
+                                    ),
+                                    (true, _, _) => trf(
+                                        "Shifted {} Due Dates, Skipped {} Without One And {} Out Of The Calendar !",
+                                        &[&changed, &undated, &out],
+                                    ),
+                                });
                             }
                         }
-                        let mut apps = appstate.lock().unwrap();
-                        apps.current_mode = origin_mode;
-                        self.prompt.desc = "Set Due Date !".to_string();
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
+                        self.needs_redraw = true;
                     }
-                    WidgetAction::IncreseUrgency => {
+                    WidgetAction::DueInline => {
+                        let cur_list = self.todolist.current_todolist.clone();
+                        let target = cur_list
+                            .as_ref()
+                            .and_then(|list| list.borrow().current_task_id());
+                        let input_rx = self.input_rx.clone();
+                        let typed = self.edit_due_inline(input_rx, terminal).await;
+                        let set = match (typed, cur_list, target) {
+                            (Some(typed), Some(cur_list), Some(id)) => cur_list
+                                .borrow()
+                                .edit_task(id, |task| task.set_due_typed(&typed, &SystemClock)),
+                            _ => None,
+                        };
+                        match set {
+                            Some(()) => self.prompt.set(tr("Set Due Date !")),
+                            None => self.prompt.set(tr("Due Date Unchanged !")),
+                        }
+                        self.needs_redraw = true;
+                    }
+                    WidgetAction::IncreaseUrgency => {
                         if let Some(cur_list) = &self.todolist.current_todolist {
                             let cur_list_bor = cur_list.borrow();
                             if let Some(cur_task) = &cur_list_bor.current_task {
@@ -1466,11 +5475,9 @@ impl Ui {
                                 cur_task_mut.increase_urgency();
                             }
                         }
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
+                        self.needs_redraw = true;
                     }
-                    WidgetAction::DecreseUrgency => {
+                    WidgetAction::DecreaseUrgency => {
                         if let Some(cur_list) = &self.todolist.current_todolist {
                             let cur_list_bor = cur_list.borrow();
                             if let Some(cur_task) = &cur_list_bor.current_task {
@@ -1478,63 +5485,48 @@ impl Ui {
                                 cur_task_mut.decrease_urgency();
                             }
                         }
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
+                        self.needs_redraw = true;
                     }
                     WidgetAction::Sort => {
-                        let mut apps = appstate.lock().unwrap();
-                        let origin_mode = apps.current_mode;
-                        apps.current_mode = CurrentMode::Sort;
-                        let input_rx_arc = self.input_rx.clone();
-                        let mut input_rx = input_rx_arc.lock().unwrap();
-                        let mut sort_method = "".to_string();
-                        loop {
-                            let _ = terminal.draw(|f| {
-                                self.update(f);
-                            });
-                            if let Some(key_evt) = input_rx.recv().await {
-                                if sort_method.is_empty() {
-                                    match key_evt.code {
-                                        KeyCode::Char('d') => {
-                                            sort_method += "d";
-                                        }
-                                        KeyCode::Char('u') => {
-                                            sort_method += "u";
-                                        }
-                                        _ => {
-                                            break;
-                                        }
-                                    }
-                                } else {
-                                    match key_evt.code {
-                                        KeyCode::Char('a') => {
-                                            sort_method += "a";
-                                        }
-                                        KeyCode::Char('d') => {
-                                            sort_method += "d";
-                                        }
-                                        _ => {
-                                            break;
-                                        }
-                                    }
-                                }
+                        if let Some(cur_list) = self.todolist.current_todolist.clone() {
+                            let input_rx = self.input_rx.clone();
+                            match self.pick_sort(input_rx, terminal, &cur_list).await {
+                                Some((rule, true)) => self
+                                    .prompt
+                                    .set(trf("Sorted By {} !", &[&tr(&rule.to_string())])),
+                                Some((rule, false)) => self
+                                    .prompt
+                                    .set(trf("Shown By {} !", &[&tr(&rule.to_string())])),
+                                None => {}
                             }
                         }
-                        match sort_method.as_str() {
-                            "da" => {}
-                            "dd" => {}
-                            "ua" => {}
-                            "ud" => {}
-                            _ => {}
-                        }
-                        apps.current_mode = origin_mode;
-                        let _ = terminal.draw(|f| {
-                            self.update(f);
-                        });
+                        self.needs_redraw = true;
                     }
                 },
             }
+            if (opens_help || self.blur_point() != blurred_from) && self.autosaves(Autosave::OnBlur)
+            {
+                self.pending.push_back(UiMessage::SaveData);
+            }
+            if !feeding {
+                // the keys fed for an action its popups didn't take
+                keys::unfeed();
+            }
+            if let Some(mut entry) = journaled {
+                entry.keys = keys::untap()
+                    .iter()
+                    .map(|key| (key.code, key.modifiers))
+                    .collect();
+                if let Some(journal) = &mut self.journal
+                    && let Err(err) = journal.append(&entry)
+                {
+                    self.prompt.set(trf("Journal Not Written: {}", &[&err]));
+                    self.journal = None;
+                }
+            }
+            if popup.is_some() {
+                appstate.lock().unwrap().close_popup();
+            }
         }
     }
 }
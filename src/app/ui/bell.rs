@@ -0,0 +1,46 @@
+//! The bell of the terminal
+//!
+//! With [`Config::bell`] on, the due banner, the escalation of the overdue tasks and a failed
+//! save ring the bell, so they are noticed from another window or tmux pane. They only ask
+//! for it, see [`Ui::ring`](crate::app::ui::Ui::ring), and the main loop rings it once the next
+//! frame is drawn: a bell asked for while a popup draws itself waits for the popup to close.
+//! The bell is written through the backend of the terminal, next to the frames, rather than
+//! printed.
+//!
+//! [`Config::bell`]: crate::app::config::Config::bell
+
+use std::io::{self, Write};
+
+use ratatui::backend::{Backend, CrosstermBackend, TestBackend};
+
+use crate::app::ui::theme::ThemedBackend;
+
+/// The BEL control character
+pub const BEL: &[u8] = b"\x07";
+
+/// A backend which can ring the bell of its terminal
+pub trait Bell {
+    /// Ring the bell
+    fn ring(&mut self) -> io::Result<()>;
+}
+
+impl<W: Write> Bell for CrosstermBackend<W> {
+    fn ring(&mut self) -> io::Result<()> {
+        self.write_all(BEL)?;
+        Write::flush(self)
+    }
+}
+
+impl<B: Backend + Bell> Bell for ThemedBackend<B> {
+    fn ring(&mut self) -> io::Result<()> {
+        self.inner.ring()
+    }
+}
+
+/// The test backend has no terminal, [`Ui::bell`](crate::app::ui::Ui::bell) tells whether it
+/// would ring
+impl Bell for TestBackend {
+    fn ring(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
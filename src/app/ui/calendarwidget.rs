@@ -1,17 +1,42 @@
-use std::vec;
+use std::{collections::HashMap, vec};
 
 use chrono::{Datelike, Duration, Local, NaiveDate};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Clear, Row, Table, Widget},
+    widgets::{Clear, Row, Table, Widget},
 };
 
+use crate::app::ui::chrome::{self, PopupKind};
+
 #[derive(Debug)]
 pub struct CalendarWidget {
     pub today: NaiveDate,
     pub cursor: NaiveDate,
+    /// The number of open tasks due each day, shown as a heatmap, None for the date picker
+    pub heat: Option<HashMap<NaiveDate, usize>>,
+}
+
+/// The background of a day with tasks due, from dim red for one task to bright red for four
+/// or more
+///
+/// # Arguments
+///
+/// - `due` (`usize`) - the number of open tasks due that day
+///
+/// # Returns
+///
+/// - `Style` - the style of the day cell, the default one for a day without tasks due
+pub fn heat_style(due: usize) -> Style {
+    let bg = match due {
+        0 => return Style::default(),
+        1 => Color::Rgb(90, 30, 30),
+        2 => Color::Rgb(140, 35, 35),
+        3 => Color::Rgb(190, 40, 40),
+        _ => Color::Rgb(240, 50, 50),
+    };
+    Style::default().bg(bg).fg(Color::White)
 }
 
 impl CalendarWidget {
@@ -20,9 +45,30 @@ impl CalendarWidget {
         Self {
             today,
             cursor: today,
+            heat: None,
         }
     }
 
+    /// Get a calendar showing how many open tasks are due each day
+    ///
+    /// # Arguments
+    ///
+    /// - `heat` (`HashMap<NaiveDate, usize>`) - the number of open tasks due by day
+    pub fn heatmap(heat: HashMap<NaiveDate, usize>) -> Self {
+        Self {
+            heat: Some(heat),
+            ..Self::new()
+        }
+    }
+
+    /// The number of open tasks due on the day under the cursor, 0 for the date picker
+    pub fn due_at_cursor(&self) -> usize {
+        self.heat
+            .as_ref()
+            .and_then(|heat| heat.get(&self.cursor).copied())
+            .unwrap_or(0)
+    }
+
     pub fn move_up(&mut self) {
         for _ in 0..7 {
             self.cursor = self.cursor.pred_opt().unwrap_or(self.cursor);
@@ -39,6 +85,10 @@ impl CalendarWidget {
     pub fn move_right(&mut self) {
         self.cursor = self.cursor.succ_opt().unwrap_or(self.cursor);
     }
+    /// Re-read the local date, so a calendar kept open across midnight highlights the new day
+    pub fn refresh_today(&mut self) {
+        self.today = Local::now().date_naive();
+    }
     pub fn same_month(&self) -> bool {
         self.cursor.month() == self.today.month() && self.cursor.year() == self.today.year()
     }
@@ -75,7 +125,13 @@ impl Widget for &mut CalendarWidget {
         let layouts = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)])
             .margin(1)
             .split(center_layout);
-        let block = Block::bordered().title(Line::from(" Calendar ").centered());
+        let block = match self.heat {
+            Some(_) => {
+                chrome::popup_block(PopupKind::Calendar, Line::from(" Due Dates ").centered())
+                    .title_bottom(Line::from(format!(" {} due ", self.due_at_cursor())).centered())
+            }
+            None => chrome::popup_block(PopupKind::Calendar, Line::from(" Calendar ").centered()),
+        };
 
         Widget::render(Clear, center_layout, buf);
         Widget::render(block, center_layout, buf);
@@ -90,7 +146,12 @@ impl Widget for &mut CalendarWidget {
             for _day in 0..7 {
                 let is_today = day == self.today;
                 let is_cursor = day == self.cursor;
-                let mut style = Style::default();
+                let due = self
+                    .heat
+                    .as_ref()
+                    .and_then(|heat| heat.get(&day).copied())
+                    .unwrap_or(0);
+                let mut style = heat_style(due);
 
                 if day.month() != self.cursor.month() {
                     style = style.fg(Color::DarkGray);
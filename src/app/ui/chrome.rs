@@ -0,0 +1,88 @@
+//! The border of the popups
+//!
+//! A popup takes every key until it's closed, which is easy to forget with the list still
+//! showing behind it, e.g. `ctrl-o` opens the calendar in the due date popup rather than a
+//! link. So the popups are drawn in the color of what they wait for, with a badge naming it in
+//! their bottom border, like the badge of the mode in the prompt:
+//!
+//! - [`PopupKind::Insert`] - a text is typed, the input, the due date, the filter, the palette
+//!   and the search popups
+//! - [`PopupKind::Calendar`] - a day is picked in the calendar
+//! - [`PopupKind::Confirm`] - `y` or `n` is pressed
+//! - [`PopupKind::Pick`] - an item of a list is picked, the sort, the color, the workspace, the
+//!   link and the due task pickers, the triage of the inbox and the keys popup, whose keys run
+//!   their action
+//! - [`PopupKind::View`] - something is shown until a key closes it, the messages, the
+//!   statistics and the about popups
+//!
+//! Every popup gets its border from [`popup_block`], so they stay the same.
+
+use ratatui::{
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::Block,
+};
+
+use crate::app::ui::glyphs;
+
+/// What a popup waits for, see the [module](self) documentation
+///
+/// # Variants
+///
+/// - `Insert` - a text
+/// - `Calendar` - a day of the calendar
+/// - `Confirm` - a `y` or a `n`
+/// - `Pick` - an item of a list, or what to do with one
+/// - `View` - a key closing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupKind {
+    Insert,
+    Calendar,
+    Confirm,
+    Pick,
+    View,
+}
+
+impl PopupKind {
+    /// The short name shown in the badge and the color of the popup
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui::style::Color;
+    /// use todo::app::ui::chrome::PopupKind;
+    ///
+    /// assert_eq!(PopupKind::Insert.badge(), ("INS", Color::Cyan));
+    /// ```
+    pub fn badge(self) -> (&'static str, Color) {
+        match self {
+            PopupKind::Insert => ("INS", Color::Cyan),
+            PopupKind::Calendar => ("CAL", Color::Magenta),
+            PopupKind::Confirm => ("CFM", Color::Yellow),
+            PopupKind::Pick => ("PCK", Color::LightGreen),
+            PopupKind::View => ("VEW", Color::LightCyan),
+        }
+    }
+}
+
+/// Get the bordered block of a popup, in the color of its kind with its badge in the bottom
+/// left corner
+///
+/// # Arguments
+///
+/// - `kind` ([`PopupKind`]) - what the popup waits for
+/// - `title` (`impl Into<Line<'a>>`) - the title of the popup
+///
+/// # Returns
+///
+/// - `Block<'a>` - the block, more titles can be added to it, e.g. a hint on the right of the
+///   bottom border
+pub fn popup_block<'a>(kind: PopupKind, title: impl Into<Line<'a>>) -> Block<'a> {
+    let (name, color) = kind.badge();
+    glyphs::block()
+        .border_style(Style::new().fg(color))
+        .title(title)
+        .title_bottom(
+            Line::from(format!(" {} ", name).fg(Color::Black).bg(color).bold()).left_aligned(),
+        )
+}
@@ -0,0 +1,53 @@
+//! Copy to the clipboard of the terminal
+//!
+//! The text is sent to the terminal in an OSC 52 sequence, which most terminals and tmux,
+//! with `set-clipboard on`, put on the system clipboard. It works over ssh as well, and needs
+//! no clipboard tool on the machine running the app.
+
+use std::io::{self, IsTerminal, Write};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The OSC 52 sequence putting a text on the clipboard
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::ui::clipboard::osc52;
+///
+/// assert_eq!(osc52("hi"), "\x1b]52;c;aGk=\x07");
+/// ```
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, byte)| acc | (*byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64[(triple >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Put a text on the clipboard, see the [module](self) documentation
+///
+/// # Returns
+///
+/// - `bool` - whether the sequence was written, false when the output isn't a terminal
+pub fn copy(text: &str) -> bool {
+    let mut stdout = io::stdout();
+    if !stdout.is_terminal() {
+        return false;
+    }
+    write!(stdout, "{}", osc52(text)).is_ok() && stdout.flush().is_ok()
+}
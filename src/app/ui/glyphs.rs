@@ -0,0 +1,204 @@
+//! Glyph sets
+//!
+//! The task list uses Nerd Font icons for the urgency and box drawing characters for the
+//! status. Terminals without a patched font, which is the common case of Windows Terminal
+//! and the classic console, show them as boxes, so an ASCII set is kept next to it. The set
+//! is picked once at start up from the configuration, see [`Config::ascii_glyphs`], and
+//! defaults to ASCII on Windows.
+//!
+//! The [`PLAIN`] set goes further for screen readers and very simple terminals: the statuses
+//! are spelled out, e.g. `[TODO]`, the borders are ASCII and the selected row is marked with
+//! `>` rather than only with a background color, a `>` no other glyph of the set uses. It is picked with `--plain` or
+//! [`Config::plain`].
+//!
+//! [`Config::ascii_glyphs`]: crate::app::config::Config::ascii_glyphs
+//! [`Config::plain`]: crate::app::config::Config::plain
+
+use std::sync::OnceLock;
+
+use ratatui::{symbols::border, widgets::Block};
+
+/// The glyphs drawn in the task and workspace lists
+///
+/// The glyphs of the statuses have the same width within a set, as do the ones of the
+/// urgencies, so the columns stay aligned.
+///
+/// # Fields
+///
+/// - `todo`, `in_process`, `finished`, `deprecated` (`&str`) - the task status markers
+/// - `common`, `important`, `critical` (`&str`) - the urgency markers
+/// - `expanded`, `collapsed` (`&str`) - the markers of workspaces with children
+/// - `active` (`&str`) - the marker of the active workspace
+/// - `locked` (`&str`) - the marker after the name of a protected workspace
+/// - `reminder` (`&str`) - the marker after the due date of a task with a reminder
+/// - `notes` (`&str`) - the marker after the description of a task with notes
+/// - `breadcrumb` (`&str`) - the separator between the ancestors in the todo list header
+/// - `gauge_full`, `gauge_empty` (`&str`) - the cells of the progress gauge in the todo list title
+/// - `guide_branch`, `guide_last` (`&str`) - the tree guides before a sub workspace, the
+///   second for the last child of its parent
+/// - `guide_bar`, `guide_blank` (`&str`) - the tree guides of a level whose parent has more
+///   children below, or has none
+/// - `border` (`border::Set`) - the lines of the borders, see [`block`]
+/// - `selected` (`&str`) - the marker before the selected row of a list, empty when the
+///   background color is enough
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    pub todo: &'static str,
+    pub in_process: &'static str,
+    pub finished: &'static str,
+    pub deprecated: &'static str,
+    pub common: &'static str,
+    pub important: &'static str,
+    pub critical: &'static str,
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    pub active: &'static str,
+    pub locked: &'static str,
+    pub reminder: &'static str,
+    pub notes: &'static str,
+    pub breadcrumb: &'static str,
+    pub gauge_full: &'static str,
+    pub gauge_empty: &'static str,
+    pub guide_branch: &'static str,
+    pub guide_last: &'static str,
+    pub guide_bar: &'static str,
+    pub guide_blank: &'static str,
+    pub border: border::Set,
+    pub selected: &'static str,
+}
+
+/// The default glyphs, which need a Nerd Font for the urgency icons
+pub const NERD: Glyphs = Glyphs {
+    todo: "▢",
+    in_process: "▣",
+    finished: "✓",
+    deprecated: "",
+    common: "󰌶",
+    important: "󰋽",
+    critical: "󰀪",
+    expanded: "∨ ",
+    collapsed: "﹥ ",
+    active: "● ",
+    locked: " \u{f023}",
+    reminder: "\u{f009a}",
+    notes: "\u{f0219}",
+    breadcrumb: " ▸ ",
+    gauge_full: "▰",
+    gauge_empty: "▱",
+    guide_branch: "├─",
+    guide_last: "└─",
+    guide_bar: "│ ",
+    guide_blank: "  ",
+    border: border::PLAIN,
+    selected: "",
+};
+
+/// Plain ASCII glyphs, readable with any font
+pub const ASCII: Glyphs = Glyphs {
+    todo: "o",
+    in_process: "~",
+    finished: "x",
+    deprecated: "-",
+    common: ".",
+    important: "!",
+    critical: "#",
+    expanded: "v ",
+    collapsed: "> ",
+    active: "* ",
+    locked: " L",
+    reminder: "@",
+    notes: "=",
+    breadcrumb: " > ",
+    gauge_full: "#",
+    gauge_empty: "-",
+    guide_branch: "|-",
+    guide_last: "`-",
+    guide_bar: "| ",
+    guide_blank: "  ",
+    border: border::PLAIN,
+    selected: "",
+};
+
+/// Borders drawn with `+`, `-` and `|`
+pub const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Spelled out statuses, ASCII borders and a marker on the selected row, for screen readers
+///
+/// The selected row is marked with the `>` of [`ASCII`], so a collapsed item and the
+/// breadcrumb separator are drawn with `+` and `/` to be told apart from it.
+pub const PLAIN: Glyphs = Glyphs {
+    todo: "[TODO] ",
+    in_process: "[DOING]",
+    finished: "[DONE] ",
+    deprecated: "[DROP] ",
+    collapsed: "+ ",
+    breadcrumb: " / ",
+    border: ASCII_BORDER,
+    selected: "> ",
+    ..ASCII
+};
+
+static CURRENT: OnceLock<&'static Glyphs> = OnceLock::new();
+
+/// Whether the ASCII set is used when the configuration doesn't say, true on Windows
+pub const fn ascii_by_default() -> bool {
+    cfg!(windows)
+}
+
+/// Pick the glyph set for the rest of the run
+///
+/// Only the first call has an effect, the set can't change once something has been drawn.
+///
+/// # Arguments
+///
+/// - `ascii` (`bool`) - use [`ASCII`] rather than [`NERD`]
+pub fn select(ascii: bool) {
+    select_set(if ascii { &ASCII } else { &NERD });
+}
+
+/// Pick any glyph set for the rest of the run, e.g. [`PLAIN`]
+///
+/// Only the first call has an effect, as with [`select`].
+///
+/// # Arguments
+///
+/// - `glyphs` (`&'static Glyphs`) - the set to draw with
+pub fn select_set(glyphs: &'static Glyphs) {
+    let _ = CURRENT.set(glyphs);
+}
+
+/// Get the glyph set in use, the platform default if none was selected
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::ui::glyphs;
+///
+/// glyphs::select(true);
+/// assert_eq!(glyphs::current().finished, "x");
+/// ```
+pub fn current() -> &'static Glyphs {
+    CURRENT.get_or_init(|| if ascii_by_default() { &ASCII } else { &NERD })
+}
+
+/// Get a bordered block drawn with the borders of the glyph set in use
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::ui::glyphs;
+///
+/// let block = glyphs::block().title(" Tasks ");
+/// ```
+pub fn block() -> Block<'static> {
+    Block::bordered().border_set(current().border)
+}
@@ -1,18 +1,114 @@
+//! The help page
+//!
+//! The help page lists the key bindings of one panel at a time, in tabs switched with `h`/`l`
+//! or `Tab`. It opens on the tab of the focused panel, see [`HelpWidget::open`], and scrolls
+//! within the tab shown.
+
 use ratatui::{
     layout::{Constraint, Layout},
-    style::Stylize,
+    style::{Color, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Clear, Paragraph, Scrollbar, ScrollbarState, StatefulWidget, Widget},
+    widgets::{Clear, Paragraph, Scrollbar, ScrollbarState, StatefulWidget, Tabs, Widget},
+};
+
+use crate::app::{
+    appstate::CurrentFocus,
+    ui::{
+        glyphs,
+        keymap::{Keymap, KeymapWidget},
+        strings::tr,
+    },
 };
 
-use crate::app::ui::keymap::KeymapWidget;
+/// A tab of the help page
+///
+/// # Variants
+///
+/// - `General` - the keys working in every panel
+/// - `Workspace` - the keys of the workspace panel
+/// - `TodoList` - the keys of the todo list panel
+/// - `Archived` - the keys of the archived workspace panel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HelpTab {
+    #[default]
+    General,
+    Workspace,
+    TodoList,
+    Archived,
+}
+
+impl HelpTab {
+    /// The tabs in the order they are shown
+    pub const ALL: [HelpTab; 4] = [
+        HelpTab::General,
+        HelpTab::Workspace,
+        HelpTab::TodoList,
+        HelpTab::Archived,
+    ];
+
+    /// The tab of the keys of a panel
+    pub fn of(focus: &CurrentFocus) -> Self {
+        match focus {
+            CurrentFocus::Workspace => HelpTab::Workspace,
+            CurrentFocus::TodoList => HelpTab::TodoList,
+            CurrentFocus::ArchivedWorkspace => HelpTab::Archived,
+        }
+    }
 
+    /// The tab on the right, the first one after the last one
+    pub fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    /// The tab on the left, the last one before the first one
+    pub fn prev(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|tab| *tab == self)
+            .unwrap_or_default()
+    }
+
+    /// The header of the tab
+    pub fn title(self) -> &'static str {
+        match self {
+            HelpTab::General => "General",
+            HelpTab::Workspace => "Workspace",
+            HelpTab::TodoList => "Todo List",
+            HelpTab::Archived => "Archived",
+        }
+    }
+
+    /// The accent color of the tab, the one of its panel
+    pub fn color(self) -> Color {
+        match self {
+            HelpTab::General => Color::LightCyan,
+            HelpTab::Workspace => Color::LightGreen,
+            HelpTab::TodoList => Color::LightBlue,
+            HelpTab::Archived => Color::LightYellow,
+        }
+    }
+}
+
+/// The help page, see the [module](self) documentation
+///
+/// # Fields
+///
+/// - `scroll` (`usize`) - the first line of the tab shown
+/// - `scroll_max` (`usize`) - the last value of `scroll`, set when the tab is drawn
+/// - `state` (`ScrollbarState`) - the state of the scrollbar
+/// - `keymap` (`KeymapWidget`) - the key bindings listed
+/// - `tab` ([`HelpTab`]) - the tab shown
 #[derive(Debug, Default)]
 pub struct HelpWidget {
     pub scroll: usize,
     pub scroll_max: usize,
     pub state: ScrollbarState,
     pub keymap: KeymapWidget,
+    pub tab: HelpTab,
 }
 
 impl HelpWidget {
@@ -21,6 +117,32 @@ impl HelpWidget {
             ..Default::default()
         }
     }
+
+    /// Show the tab of the focused panel from its top
+    pub fn open(&mut self) {
+        self.show(HelpTab::of(&self.keymap.focus));
+    }
+
+    /// Show a tab from its top
+    ///
+    /// # Arguments
+    ///
+    /// - `tab` ([`HelpTab`]) - the tab to show
+    pub fn show(&mut self, tab: HelpTab) {
+        self.tab = tab;
+        self.scroll = 0;
+        self.state = self.state.position(0);
+    }
+
+    /// The key bindings listed in a tab
+    pub fn hints(&self, tab: HelpTab) -> &[Keymap] {
+        match tab {
+            HelpTab::General => &self.keymap.general_hint,
+            HelpTab::Workspace => &self.keymap.workspace_hint,
+            HelpTab::TodoList => &self.keymap.tasklist_hint,
+            HelpTab::Archived => &self.keymap.archived_ws_hint,
+        }
+    }
 }
 
 impl Widget for &mut HelpWidget {
@@ -41,66 +163,46 @@ impl Widget for &mut HelpWidget {
         ])
         .split(v_layouts[1]);
 
-        let block = Block::bordered()
-            .title(" Help Page ")
-            // .border_style(Style::new().light_cyan())
+        let block = glyphs::block()
+            .title(format!(" {} ", tr("Help Page")))
             .title_alignment(ratatui::layout::Alignment::Center);
+        let inner = block.inner(h_layout[1]);
+        let [header_area, body_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+
+        let tabs = Tabs::new(
+            HelpTab::ALL
+                .iter()
+                .map(|tab| Line::from(tr(tab.title())).fg(tab.color())),
+        )
+        .select(HelpTab::ALL.iter().position(|tab| *tab == self.tab))
+        .highlight_style(Style::new().bold().reversed())
+        .divider(glyphs::current().guide_bar.trim());
 
-        let keymap_widget = &self.keymap;
-        let mut general_keys: Vec<Line> = vec![Line::from("General Keymaps").bold().light_cyan()];
-        let mut workspace_keys: Vec<Line> =
-            vec![Line::from("Workspace Keymaps").bold().light_green()];
-        let mut todolist_keys: Vec<Line> =
-            vec![Line::from("Todo List Keymaps").bold().light_blue()];
-        let mut archived_ws_keys: Vec<Line> = vec![
-            Line::from("Archived Workspace Keymaps")
-                .bold()
-                .light_yellow(),
-        ];
-
-        keymap_widget.general_hint.iter().for_each(|hint| {
-            general_keys.push(Line::from(vec![
-                Span::from(format!("{:12}", hint.key.to_owned())).light_cyan(),
-                Span::from(format!("{:12}", hint.detailed.to_owned())),
-            ]));
-        });
-        general_keys.push(Line::from(""));
-        keymap_widget.workspace_hint.iter().for_each(|hint| {
-            workspace_keys.push(Line::from(vec![
-                Span::from(format!("{:12}", hint.key.to_owned())).light_green(),
-                Span::from(format!("{:12}", hint.detailed.to_owned())),
-            ]));
-        });
-        workspace_keys.push(Line::from(""));
-        keymap_widget.tasklist_hint.iter().for_each(|hint| {
-            todolist_keys.push(Line::from(vec![
-                Span::from(format!("{:12}", hint.key.to_owned())).light_blue(),
-                Span::from(format!("{:12}", hint.detailed.to_owned())),
-            ]));
-        });
-        todolist_keys.push(Line::from(""));
-        keymap_widget.archived_ws_hint.iter().for_each(|hint| {
-            archived_ws_keys.push(Line::from(vec![
-                Span::from(format!("{:12}", hint.key.to_owned())).light_yellow(),
-                Span::from(format!("{:12}", hint.detailed.to_owned())),
-            ]));
-        });
-        archived_ws_keys.push(Line::from(""));
-
-        let mut para_lines = Vec::new();
-        para_lines.extend(general_keys);
-        para_lines.extend(workspace_keys);
-        para_lines.extend(todolist_keys);
-        para_lines.extend(archived_ws_keys);
-        self.state = self.state.content_length(para_lines.len() - 5 - 1);
-        self.scroll_max = para_lines.len() - 5 - 1;
-        let para = Paragraph::new(para_lines)
-            .block(block)
-            .scroll((self.scroll as u16, 0));
+        let color = self.tab.color();
+        let lines: Vec<Line> = self
+            .hints(self.tab)
+            .iter()
+            .map(|hint| {
+                Line::from(vec![
+                    Span::from(format!("{:12}", hint.key.to_owned())).fg(color),
+                    Span::from(format!("{:12}", tr(&hint.detailed))),
+                ])
+            })
+            .collect();
+        self.scroll_max = lines.len().saturating_sub(body_area.height as usize);
+        self.scroll = self.scroll.min(self.scroll_max);
+        self.state = self
+            .state
+            .content_length(self.scroll_max)
+            .position(self.scroll);
+        let para = Paragraph::new(lines).scroll((self.scroll as u16, 0));
         let scrollbar = Scrollbar::new(ratatui::widgets::ScrollbarOrientation::VerticalRight);
 
         Widget::render(Clear, h_layout[1], buf);
-        Widget::render(para, h_layout[1], buf);
+        Widget::render(block, h_layout[1], buf);
+        Widget::render(tabs, header_area, buf);
+        Widget::render(para, body_area, buf);
         StatefulWidget::render(scrollbar, h_layout[1], buf, &mut self.state);
     }
 }
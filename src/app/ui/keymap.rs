@@ -6,13 +6,62 @@ use ratatui::{
     widgets::Widget,
 };
 
-use crate::app::appstate::{CurrentFocus, CurrentMode};
+use crossterm::event::KeyEvent;
 
-#[derive(Debug)]
+use crate::app::{
+    appstate::{CurrentFocus, CurrentMode, Message},
+    ui::{keys, strings::tr},
+};
+
+/// What the todo list on screen allows, which decides the task keys the hints show
+///
+/// # Variants
+///
+/// - `Tasks` - a list of the workspace panel with tasks, every key works
+/// - `Empty` - a list of the workspace panel without tasks, or no list, only adding works
+/// - `Archived` - the list of an archived workspace, which is read only until it's recovered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListContext {
+    #[default]
+    Tasks,
+    Empty,
+    Archived,
+}
+
+/// What a binding of the task list needs from the list to work, see [`Keymap::is_enabled`]
+///
+/// # Variants
+///
+/// - `Nothing` - works in any list, e.g. the row numbers
+/// - `Tasks` - reads the tasks, so it needs some, archived or not, e.g. the filter
+/// - `Writable` - adds to the list, so it needs a list which isn't archived, empty or not
+/// - `WritableTasks` - changes the tasks, so it needs some in a list which isn't archived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Needs {
+    #[default]
+    Nothing,
+    Tasks,
+    Writable,
+    WritableTasks,
+}
+
+/// A key binding, shown in the key hints and the help page
+///
+/// # Fields
+///
+/// - `key` (`String`) - the keys of the binding
+/// - `desc` (`String`) - the short description of the key hints
+/// - `detailed` (`String`) - the description of the help page, also the name in the command palette
+/// - `message` (`Option<fn() -> Message>`) - the message the binding sends, None for bindings
+///   which can't run from the command palette, e.g. the ones which move between popups
+/// - `needs` ([`Needs`]) - what the todo list must allow for the binding to show
+#[derive(Debug, Clone)]
 pub struct Keymap {
     pub key: String,
     pub desc: String,
     pub detailed: String,
+    pub message: Option<fn() -> Message>,
+    pub needs: Needs,
 }
 
 impl Keymap {
@@ -21,19 +70,136 @@ impl Keymap {
             key: key.to_string(),
             desc: desc.to_string(),
             detailed: detail.to_string(),
+            message: None,
+            needs: Needs::Nothing,
         }
     }
+
+    /// Make the binding runnable from the command palette
+    fn runs(mut self, message: fn() -> Message) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Show the binding only in the todo lists allowing what it needs
+    fn needs(mut self, needs: Needs) -> Self {
+        self.needs = needs;
+        self
+    }
+
+    /// Whether the binding works in a todo list, so the hints and the palette offer it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::ui::keymap::{KeymapWidget, ListContext};
+    ///
+    /// let keymap = KeymapWidget::default();
+    /// let hint = |key: &str| keymap.tasklist_hint.iter().find(|hint| hint.key == key).unwrap();
+    /// assert!(hint("a").is_enabled(ListContext::Empty));
+    /// assert!(!hint("a").is_enabled(ListContext::Archived));
+    /// assert!(hint("f /").is_enabled(ListContext::Archived));
+    /// assert!(!hint("c").is_enabled(ListContext::Empty));
+    /// ```
+    pub fn is_enabled(&self, context: ListContext) -> bool {
+        match self.needs {
+            Needs::Nothing => true,
+            Needs::Tasks => context != ListContext::Empty,
+            Needs::Writable => context != ListContext::Archived,
+            Needs::WritableTasks => context == ListContext::Tasks,
+        }
+    }
+
+    /// Whether a key is one of the keys of the binding, written apart by `/` or a space
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    /// use todo::app::ui::keymap::KeymapWidget;
+    ///
+    /// let keymap = KeymapWidget::default();
+    /// let filter = keymap.tasklist_hint.iter().find(|hint| hint.key == "f /").unwrap();
+    /// let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+    /// assert!(filter.matches(&key('f')) && filter.matches(&key('/')));
+    /// assert!(!filter.matches(&key('F')));
+    /// ```
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let Some(name) = keys::name(key) else {
+            return false;
+        };
+        self.key
+            .split_whitespace()
+            .flat_map(|keys| {
+                // a lone `/` is a key, not a separator
+                if keys.len() > 1 {
+                    keys.split('/').collect()
+                } else {
+                    vec![keys]
+                }
+            })
+            .any(|key| key == name)
+    }
+
+    /// The name of the binding in the command palette, its detailed description translated
+    /// and capitalized
+    pub fn name(&self) -> String {
+        let mut chars = tr(&self.detailed).chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    }
 }
 
+/// Whether the characters of a query appear in a name in order, ignoring the case
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::ui::keymap::fuzzy_match;
+///
+/// assert!(fuzzy_match("arws", "Archive current workspace"));
+/// assert!(!fuzzy_match("wsar", "Archive current workspace"));
+/// ```
+pub fn fuzzy_match(query: &str, name: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+        .all(|c| name.any(|n| n == c))
+}
+
+/// The key hints at the bottom, for the focused panel or the mode
+///
+/// # Fields
+///
+/// - `focus` ([`CurrentFocus`]) - the focused panel, whose hints show in the normal mode
+/// - `mode` ([`CurrentMode`]) - the mode, set by the popups while they are open so the hints
+///   follow them
+/// - `calendar` (`bool`) - whether the open popup opens the calendar, which adds
+///   `calendar_hint` to the hints of the insert mode
+/// - `context` ([`ListContext`]) - what the todo list on screen allows, the task keys which
+///   don't work in it are left out of the hints and the palette
+/// - `general_hint`, `workspace_hint`, `tasklist_hint`, `archived_ws_hint` (`Vec<Keymap>`) -
+///   the bindings of each panel, also listed in the help page
+/// - `sort_hint`, `search_hint`, `insert_hint`, `calendar_hint` (`Vec<Keymap>`) - the keys of
+///   each mode
 #[derive(Debug)]
 pub struct KeymapWidget {
     pub focus: CurrentFocus,
     pub mode: CurrentMode,
+    pub calendar: bool,
+    pub context: ListContext,
     pub general_hint: Vec<Keymap>,
     pub workspace_hint: Vec<Keymap>,
     pub tasklist_hint: Vec<Keymap>,
     pub archived_ws_hint: Vec<Keymap>,
     pub sort_hint: Vec<Keymap>,
+    pub search_hint: Vec<Keymap>,
+    pub insert_hint: Vec<Keymap>,
+    pub calendar_hint: Vec<Keymap>,
 }
 
 impl KeymapWidget {
@@ -43,6 +209,92 @@ impl KeymapWidget {
             ..Default::default()
         }
     }
+
+    /// Get the bindings the command palette offers for a focus
+    ///
+    /// These are the runnable general bindings followed by the ones of the focused panel, so a
+    /// new binding shows up in the palette as soon as it is in the hints. The task keys which
+    /// don't work in the todo list on screen are left out, see [`Keymap::is_enabled`].
+    ///
+    /// # Arguments
+    ///
+    /// - `&self` ([`KeymapWidget`])
+    /// - `focus` (`&CurrentFocus`) - the focused panel
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<&Keymap>` - the bindings, each action once
+    pub fn commands(&self, focus: &CurrentFocus) -> Vec<&Keymap> {
+        let panel = match focus {
+            CurrentFocus::Workspace => &self.workspace_hint,
+            CurrentFocus::TodoList => &self.tasklist_hint,
+            CurrentFocus::ArchivedWorkspace => &self.archived_ws_hint,
+        };
+        let mut commands: Vec<&Keymap> = Vec::new();
+        self.general_hint
+            .iter()
+            .chain(panel.iter())
+            .filter(|hint| hint.message.is_some() && hint.is_enabled(self.context))
+            .for_each(|hint| {
+                if !commands.iter().any(|other| other.key == hint.key) {
+                    commands.push(hint);
+                }
+            });
+        commands
+    }
+
+    /// The bindings of the task list which work in the todo list on screen
+    fn tasklist_hint(&self) -> impl Iterator<Item = &Keymap> {
+        self.tasklist_hint
+            .iter()
+            .filter(|hint| hint.is_enabled(self.context))
+    }
+
+    /// Get the bindings the hint bar shows, those of the mode or the focused panel
+    ///
+    /// # Returns
+    ///
+    /// - `(Vec<&Keymap>, Style)` - the bindings, and the style of their keys
+    pub fn shown(&self) -> (Vec<&Keymap>, Style) {
+        match self.mode {
+            CurrentMode::Help => (
+                self.general_hint.iter().collect(),
+                Style::new().light_cyan(),
+            ),
+            CurrentMode::Sort => (
+                self.sort_hint.iter().collect(),
+                Style::new().light_magenta(),
+            ),
+            // the task list takes the keys too while searching, its hints follow
+            CurrentMode::Search => (
+                self.search_hint
+                    .iter()
+                    .chain(self.tasklist_hint())
+                    .collect(),
+                Style::new().light_yellow(),
+            ),
+            CurrentMode::Insert => (
+                self.insert_hint
+                    .iter()
+                    .chain(self.calendar_hint.iter().filter(|_| self.calendar))
+                    .collect(),
+                Style::new().cyan(),
+            ),
+            CurrentMode::Normal | CurrentMode::Tour => match self.focus {
+                CurrentFocus::TodoList => {
+                    (self.tasklist_hint().collect(), Style::new().light_blue())
+                }
+                CurrentFocus::Workspace => (
+                    self.workspace_hint.iter().collect(),
+                    Style::new().light_green(),
+                ),
+                CurrentFocus::ArchivedWorkspace => (
+                    self.archived_ws_hint.iter().collect(),
+                    Style::new().light_yellow(),
+                ),
+            },
+        }
+    }
 }
 
 impl Default for KeymapWidget {
@@ -50,62 +302,271 @@ impl Default for KeymapWidget {
         KeymapWidget {
             focus: CurrentFocus::Workspace,
             mode: CurrentMode::Normal,
+            calendar: false,
+            context: ListContext::default(),
             general_hint: vec![
-                Keymap::new("h/left", "left", "focus on left part(workspace)"),
-                Keymap::new("l/right", "right", "focus on right part(tasks)"),
-                Keymap::new("j/down", "down", "select item bellow"),
-                Keymap::new("k/up", "up", "select item above"),
+                Keymap::new(
+                    "h/left",
+                    "left",
+                    "focus back on the workspace panel of the list",
+                )
+                .runs(|| Message::FocusBack),
+                Keymap::new("l/right", "right", "focus on right part(tasks)")
+                    .runs(|| Message::SelectWorkspace),
+                Keymap::new("j/down", "down", "select item bellow").runs(|| Message::MoveDown),
+                Keymap::new("k/up", "up", "select item above").runs(|| Message::MoveUp),
+                Keymap::new(
+                    "( )",
+                    "parent/child",
+                    "select the parent or the first child",
+                ),
+                Keymap::new("{ }", "sibling", "select the previous or next sibling"),
+                Keymap::new(
+                    "zM zR",
+                    "fold all",
+                    "collapse or expand every item of the panel",
+                ),
+                Keymap::new(
+                    "zc zo",
+                    "fold",
+                    "collapse the current item or its parent, or expand it",
+                ),
                 Keymap::new("tab", "focus", "change focus between 3 parts"),
                 Keymap::new(
                     "enter",
                     "enter workspace",
                     "enter into the tasks of the workspace",
                 ),
-                Keymap::new("esc", "exit current mode", "exit search or help"),
-                Keymap::new("q", "quit", "quit the application"),
-                Keymap::new("ctrl-s", "save", "save the data"),
+                Keymap::new(
+                    "esc",
+                    "exit current mode",
+                    "exit search or help, or leave the list",
+                ),
+                Keymap::new("q q", "quit", "quit the application"),
+                Keymap::new("q a-z", "record", "record a macro into a register, q stops"),
+                Keymap::new("@ a-z", "replay", "replay the macro of a register"),
+                Keymap::new("ctrl-s", "save", "save the data").runs(|| Message::SaveData),
+                Keymap::new("ctrl-h", "messages", "show the last prompt messages")
+                    .runs(|| Message::Messages),
+                Keymap::new(
+                    "V",
+                    "about",
+                    "show the version, the data file and the profile",
+                )
+                .runs(|| Message::About),
+                Keymap::new("ctrl-a", "archived", "show or hide the archived panel")
+                    .runs(|| Message::ToggleArchived),
                 Keymap::new("1/2/3", "focus", "focus target part"),
+                Keymap::new("ctrl-k :", "palette", "run any action by its name"),
+                Keymap::new(
+                    "S",
+                    "search all",
+                    "search the tasks and the workspace names of every workspace",
+                )
+                .runs(|| Message::SearchAll),
+                Keymap::new(
+                    "g?/f1",
+                    "keys",
+                    "list the keys of the panel, a listed key runs",
+                )
+                .runs(|| Message::KeyHelp),
+                Keymap::new("C", "due dates", "show the tasks due each day of the month")
+                    .runs(|| Message::Heatmap),
+                Keymap::new("t", "tour", "in the help page, start the guided tour"),
             ],
             workspace_hint: vec![
-                Keymap::new("a", "add", "add new workspace"),
-                Keymap::new("x", "delete", "delete current workspace"),
-                Keymap::new("i", "subworkspace", "insert a subworkspace to current"),
-                Keymap::new("A", "archive", "archive current workspace"),
-                Keymap::new("r", "rename", "rename current workspace"),
-                Keymap::new("ctrl-s", "save", "save the data"),
-                Keymap::new("?", "help", "open the help page"),
+                Keymap::new("a", "add", "add new workspace").runs(|| Message::AddItem),
+                Keymap::new("x", "delete", "delete current workspace").runs(|| Message::DeleteItem),
+                Keymap::new("i", "subworkspace", "insert a subworkspace to current")
+                    .runs(|| Message::AddChild),
+                Keymap::new("A", "archive", "archive current workspace").runs(|| Message::Archive),
+                Keymap::new(
+                    "I",
+                    "stats",
+                    "show the task counts of the workspace and below",
+                )
+                .runs(|| Message::WorkspaceStats),
+                Keymap::new("y", "copy", "copy the pending tasks as a message")
+                    .runs(|| Message::CopyWorkspace),
+                Keymap::new("P", "protect", "protect from deletion and archiving")
+                    .runs(|| Message::ToggleProtected),
+                Keymap::new("r", "rename", "rename current workspace").runs(|| Message::Rename),
+                Keymap::new(
+                    "K",
+                    "move up",
+                    "move the workspace above its previous sibling",
+                )
+                .runs(|| Message::ShiftUp),
+                Keymap::new(
+                    "J",
+                    "move down",
+                    "move the workspace below its next sibling",
+                )
+                .runs(|| Message::ShiftDown),
+                Keymap::new("ctrl-s", "save", "save the data").runs(|| Message::SaveData),
+                Keymap::new("?", "help", "open the help page").runs(|| Message::Help),
             ],
             tasklist_hint: vec![
-                Keymap::new("a", "add", "add new task"),
-                Keymap::new("x", "delete", "delete current task"),
-                Keymap::new("i", "subtask", "insert a subtask to current"),
-                Keymap::new("c", "complete", "mark the task as completed"),
-                Keymap::new("p", "inprocess", "mark the task as in process"),
-                Keymap::new("t", "todo", "mark the task as todo"),
-                Keymap::new("d", "deprecate", "mark the task as deprecated"),
-                Keymap::new("D", "due", "set the due date of current task"),
-                Keymap::new("r", "rename", "rename the current task"),
-                // TODO: Implement sort functionality
-                Keymap::new("s", "sort", "sort the current task by rule (in dev)"),
-                Keymap::new("f /", "filter", "search tasks in current workspace"),
-                Keymap::new("+/=", "increase", "increase the urgency"),
-                Keymap::new("-/_", "decrease", "decrease the urgency"),
-                Keymap::new("ctrl-s", "save", "save the data"),
-                Keymap::new("?", "help", "open the help page"),
+                Keymap::new("a", "add", "add new task")
+                    .needs(Needs::Writable)
+                    .runs(|| Message::AddItem),
+                Keymap::new("x", "delete", "delete current task")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::DeleteItem),
+                Keymap::new("i", "subtask", "insert a subtask to current")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::AddChild),
+                Keymap::new("c", "complete", "mark the task as completed")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::Complete),
+                Keymap::new("p", "inprocess", "mark the task as in process")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::InProcess),
+                Keymap::new("t", "todo", "mark the task as todo")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::Todo),
+                Keymap::new("d", "deprecate", "mark the task as deprecated")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::Deprecated),
+                Keymap::new("D", "due", "set the due date of current task")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::Due),
+                Keymap::new(
+                    ".",
+                    "due in place",
+                    "type the due date in the row, e.g. +3d or fri",
+                )
+                .needs(Needs::WritableTasks)
+                .runs(|| Message::DueInline),
+                Keymap::new(
+                    "ctrl-d",
+                    "due all",
+                    "set the due date of the filtered tasks, or shift it by +7d",
+                )
+                .needs(Needs::WritableTasks)
+                .runs(|| Message::DueMatching),
+                Keymap::new("r", "rename", "rename the current task")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::Rename),
+                Keymap::new("s", "sort", "pick the rule to sort the list by")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::Sort),
+                Keymap::new(
+                    "enter",
+                    "fold",
+                    "fold the subtasks, or open the notes if there are none",
+                )
+                .needs(Needs::Tasks)
+                .runs(|| Message::EnterTask),
+                Keymap::new("T", "triage", "walk through the Inbox tasks")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::Triage),
+                Keymap::new("f /", "filter", "search tasks in current workspace")
+                    .needs(Needs::Tasks)
+                    .runs(|| Message::Filter),
+                Keymap::new("H", "color", "pick the color of the task, or clear it")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::TaskColor),
+                Keymap::new(
+                    "e",
+                    "notes",
+                    "write the notes of the task, or read archived ones",
+                )
+                .needs(Needs::Tasks)
+                .runs(|| Message::EditNotes),
+                Keymap::new(
+                    "ctrl-o",
+                    "open link",
+                    "open a link of the task in the browser",
+                )
+                .needs(Needs::Tasks)
+                .runs(|| Message::OpenLink),
+                Keymap::new(
+                    "X",
+                    "clear done",
+                    "delete the finished subtasks of the task",
+                )
+                .needs(Needs::WritableTasks)
+                .runs(|| Message::ClearDone),
+                Keymap::new(
+                    "L",
+                    "numbers",
+                    "number the rows, absolute, relative or none",
+                )
+                .runs(|| Message::ToggleRowNumbers),
+                Keymap::new(
+                    "G",
+                    "go to",
+                    "go to a row by its number, or +n/-n rows away",
+                )
+                .needs(Needs::Tasks)
+                .runs(|| Message::GoToRow),
+                Keymap::new(
+                    "F",
+                    "import",
+                    "add the tasks of a text or Markdown file, a task per line",
+                )
+                .needs(Needs::Writable)
+                .runs(|| Message::ImportFile),
+                Keymap::new("+/=", "increase", "increase the urgency")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::IncreaseUrgency),
+                Keymap::new("-/_", "decrease", "decrease the urgency")
+                    .needs(Needs::WritableTasks)
+                    .runs(|| Message::DecreaseUrgency),
+                Keymap::new("ctrl-s", "save", "save the data").runs(|| Message::SaveData),
+                Keymap::new("?", "help", "open the help page").runs(|| Message::Help),
             ],
             archived_ws_hint: vec![
-                Keymap::new("x", "delete", "delete current workspace"),
-                Keymap::new("r", "rename", "rename current workspace"),
-                Keymap::new("R", "recovery", "recovery the current workspace"),
-                Keymap::new("ctrl-s", "save", "save the data"),
-                Keymap::new("?", "help", "open the help page"),
+                Keymap::new("x", "delete", "delete current workspace").runs(|| Message::DeleteItem),
+                Keymap::new("r", "rename", "rename current workspace").runs(|| Message::Rename),
+                Keymap::new("R", "recovery", "recovery the current workspace")
+                    .runs(|| Message::Recovery),
+                Keymap::new("s", "order", "list by name or in the saved order")
+                    .runs(|| Message::Sort),
+                Keymap::new(
+                    "K",
+                    "move up",
+                    "move the workspace above its previous sibling",
+                )
+                .runs(|| Message::ShiftUp),
+                Keymap::new(
+                    "J",
+                    "move down",
+                    "move the workspace below its next sibling",
+                )
+                .runs(|| Message::ShiftDown),
+                Keymap::new("ctrl-s", "save", "save the data").runs(|| Message::SaveData),
+                Keymap::new("?", "help", "open the help page").runs(|| Message::Help),
             ],
             sort_hint: vec![
-                Keymap::new("da", "due ascent", "by due date ascent"),
-                Keymap::new("dd", "due descent", "by due date descent"),
-                Keymap::new("ur", "urgency ascent", "by urgency ascent"),
-                Keymap::new("ud", "urgency descent", "by urgency descent"),
+                Keymap::new("j/k", "select", "preview the list sorted by the rule"),
+                Keymap::new(
+                    "enter",
+                    "apply",
+                    "sort the tasks in place by the selected rule",
+                ),
+                Keymap::new(
+                    "v",
+                    "view",
+                    "only show the list by the selected rule, the manual order is kept",
+                ),
+                Keymap::new("esc/q", "cancel", "keep the original order"),
             ],
+            search_hint: vec![
+                Keymap::new("n", "next", "select the next matching task"),
+                Keymap::new("N", "previous", "select the previous matching task"),
+                Keymap::new("esc", "exit", "clear the filter"),
+            ],
+            insert_hint: vec![
+                Keymap::new("enter", "confirm", "confirm the input"),
+                Keymap::new("esc", "cancel", "close the popup without a change"),
+            ],
+            calendar_hint: vec![Keymap::new(
+                "ctrl-o",
+                "calendar",
+                "pick the day in the calendar",
+            )],
         }
     }
 }
@@ -113,59 +574,19 @@ impl Default for KeymapWidget {
 impl Widget for &mut KeymapWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Render the key map widget
+        let (hints, key_style) = self.shown();
         let mut hint_span: Vec<Span> = Vec::new();
-        if let CurrentMode::Help = &self.mode {
-            self.general_hint.iter().for_each(|hint| {
-                hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-                hint_span.push(Span::styled("<".to_string(), Style::new().white()));
-                hint_span.push(Span::styled(hint.key.clone(), Style::new().light_cyan()));
-                hint_span.push(Span::styled(">".to_string(), Style::new().white()));
-                hint_span.push(Span::styled(hint.desc.clone(), Style::new().white()));
-                hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-            });
-        } else if let CurrentMode::Sort = &self.mode {
-            self.sort_hint.iter().for_each(|hint| {
-                hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-                hint_span.push(Span::styled("<".to_string(), Style::new().white()));
-                hint_span.push(Span::styled(hint.key.clone(), Style::new().light_magenta()));
-                hint_span.push(Span::styled(">".to_string(), Style::new().white()));
-                hint_span.push(Span::styled(hint.desc.clone(), Style::new().white()));
-                hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-            });
-        } else {
-            match self.focus {
-                CurrentFocus::TodoList => {
-                    self.tasklist_hint.iter().for_each(|hint| {
-                        hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled("<".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled(hint.key.clone(), Style::new().light_blue()));
-                        hint_span.push(Span::styled(">".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled(hint.desc.clone(), Style::new().white()));
-                        hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-                    });
-                }
-                CurrentFocus::Workspace => {
-                    self.workspace_hint.iter().for_each(|hint| {
-                        hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled("<".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled(hint.key.clone(), Style::new().light_green()));
-                        hint_span.push(Span::styled(">".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled(hint.desc.clone(), Style::new().white()));
-                        hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-                    });
-                }
-                CurrentFocus::ArchivedWorkspace => {
-                    self.archived_ws_hint.iter().for_each(|hint| {
-                        hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled("<".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled(hint.key.clone(), Style::new().light_yellow()));
-                        hint_span.push(Span::styled(">".to_string(), Style::new().white()));
-                        hint_span.push(Span::styled(hint.desc.clone(), Style::new().white()));
-                        hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
-                    });
-                }
-            };
-        }
+        hints.iter().for_each(|hint| {
+            hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
+            hint_span.push(Span::styled("<".to_string(), Style::new().white()));
+            hint_span.push(Span::styled(hint.key.clone(), key_style));
+            hint_span.push(Span::styled(">".to_string(), Style::new().white()));
+            hint_span.push(Span::styled(
+                tr(&hint.desc).to_string(),
+                Style::new().white(),
+            ));
+            hint_span.push(Span::styled(" ".to_string(), Style::new().white()));
+        });
 
         let hint_line = Line::from(hint_span);
         Widget::render(hint_line, area, buf);
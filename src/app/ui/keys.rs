@@ -0,0 +1,175 @@
+//! Key event helpers
+//!
+//! Windows reports the release and repeat of every key next to the press, and terminals
+//! differ in which modifiers they report with a character. All the key handling sites go
+//! through these helpers so a key is acted on once, on its press, and a Ctrl or Alt chord is
+//! never typed into a text field as a plain character.
+//!
+//! The popups read their keys with [`next_press`], which is also where the
+//! [journal](crate::app::journal) taps the keys of an action, see [`tap`], and feeds them back
+//! when the action is replayed, see [`feed`].
+//!
+//! A popup draws itself before reading each key, and only then, so the key thread sends
+//! [`RESIZE`] down the key channel of an open popup when the terminal is resized. The popups
+//! ignore it as any key they don't use, which draws them again for the new size at once.
+
+use std::{cell::RefCell, collections::VecDeque};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use tokio::sync::mpsc;
+
+thread_local! {
+    /// The keys read since [`tap`], None while nothing taps them
+    static TAPPED: RefCell<Option<Vec<KeyEvent>>> = const { RefCell::new(None) };
+    /// The keys [`next_press`] returns before reading the channel
+    static FED: RefCell<VecDeque<KeyEvent>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// The event sent to the open popup when the terminal is resized, see [`is_resize`]
+pub const RESIZE: KeyEvent = KeyEvent::new(KeyCode::Null, KeyModifiers::NONE);
+
+/// Whether the event is [`RESIZE`] rather than a key typed
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+/// use todo::app::ui::keys;
+///
+/// assert!(keys::is_resize(&keys::RESIZE));
+/// assert!(!keys::is_resize(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+/// ```
+pub fn is_resize(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Null
+}
+
+/// Whether the event is a key press, rather than a release or a repeat
+pub(crate) fn is_press(key: &KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+}
+
+/// Whether the event is the Ctrl chord of a character, e.g. `ctrl-s`
+///
+/// # Arguments
+///
+/// - `key` (`&KeyEvent`) - the event
+/// - `c` (`char`) - the character of the chord
+pub(crate) fn is_ctrl(key: &KeyEvent, c: char) -> bool {
+    key.code == KeyCode::Char(c) && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Get the character to type into a text field, None for chords and other keys
+///
+/// Shift is part of typing, Ctrl and Alt make a chord.
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+/// use todo::app::ui::keys;
+///
+/// assert_eq!(keys::text(&KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT)), Some('A'));
+/// assert_eq!(keys::text(&KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL)), None);
+/// ```
+pub fn text(key: &KeyEvent) -> Option<char> {
+    match key.code {
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            Some(c)
+        }
+        _ => None,
+    }
+}
+
+/// Get the name of a key as the key hints write it, e.g. `a`, `ctrl-s` or `enter`
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+/// use todo::app::ui::keys;
+///
+/// let name = |code, modifiers| keys::name(&KeyEvent::new(code, modifiers));
+/// assert_eq!(name(KeyCode::Char('G'), KeyModifiers::SHIFT).as_deref(), Some("G"));
+/// assert_eq!(name(KeyCode::Char('s'), KeyModifiers::CONTROL).as_deref(), Some("ctrl-s"));
+/// assert_eq!(name(KeyCode::Left, KeyModifiers::NONE).as_deref(), Some("left"));
+/// assert_eq!(name(KeyCode::Home, KeyModifiers::NONE), None);
+/// ```
+pub fn name(key: &KeyEvent) -> Option<String> {
+    if let Some(c) = text(key) {
+        return Some(c.to_string());
+    }
+    let name = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return Some(format!("ctrl-{}", c));
+        }
+        KeyCode::Enter => "enter",
+        KeyCode::Esc => "esc",
+        KeyCode::Tab => "tab",
+        KeyCode::Left => "left",
+        KeyCode::Right => "right",
+        KeyCode::Up => "up",
+        KeyCode::Down => "down",
+        KeyCode::F(n) => return Some(format!("f{}", n)),
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+/// Wait for the next key press, skipping releases and repeats
+///
+/// [`RESIZE`] is returned as a press, but never tapped.
+///
+/// # Returns
+///
+/// - `Option<KeyEvent>` - the press, or None once the key channel is closed
+pub async fn next_press(receiver: &mut mpsc::Receiver<KeyEvent>) -> Option<KeyEvent> {
+    let key = match FED.with_borrow_mut(VecDeque::pop_front) {
+        Some(key) => key,
+        None => loop {
+            let key = receiver.recv().await?;
+            if is_press(&key) {
+                break key;
+            }
+        },
+    };
+    TAPPED.with_borrow_mut(|tapped| {
+        if let Some(tapped) = tapped
+            && !is_resize(&key)
+        {
+            tapped.push(key);
+        }
+    });
+    Some(key)
+}
+
+/// Start keeping the keys read by [`next_press`] on this thread, dropping the ones kept so far
+pub fn tap() {
+    TAPPED.set(Some(Vec::new()));
+}
+
+/// Stop keeping the keys read by [`next_press`]
+///
+/// # Returns
+///
+/// - `Vec<KeyEvent>` - the keys read since [`tap`], in order
+pub fn untap() -> Vec<KeyEvent> {
+    TAPPED.take().unwrap_or_default()
+}
+
+/// Have [`next_press`] on this thread return these keys before reading its channel
+///
+/// # Arguments
+///
+/// - `keys` (`impl IntoIterator<Item = KeyEvent>`) - the keys, in order
+pub fn feed(keys: impl IntoIterator<Item = KeyEvent>) {
+    FED.with_borrow_mut(|fed| fed.extend(keys));
+}
+
+/// Drop the fed keys [`next_press`] didn't return
+pub fn unfeed() {
+    FED.with_borrow_mut(VecDeque::clear);
+}
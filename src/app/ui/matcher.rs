@@ -0,0 +1,100 @@
+//! The tasks matching the query typed in the find popup, kept from one key to the next
+//!
+//! Every word of a query matches any part of a description, see [`Task::matches`], so typing
+//! more letters at the end of the last word only ever drops tasks: the tasks matching the
+//! longer query are among those matching the shorter one. [`FilterMatcher`] keeps the
+//! matches of the last query and, when the next one only extends its last word, checks those
+//! again rather than the whole list. Any other edit, a new word, a deleted letter or the
+//! [`STALE_FILTER`] word, matches the whole list again.
+//!
+//! [`Task::matches`]: crate::app::ui::todolistwidget::Task::matches
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::app::ui::todolistwidget::{STALE_FILTER, Staleness, Task};
+
+/// The time the find popup waits after a key before matching the query again
+pub const FILTER_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Whether the tasks matching `query` are all among those matching `previous`
+///
+/// True when `query` adds letters, and no space, to the last word of `previous`, neither
+/// word being [`STALE_FILTER`].
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::ui::matcher::narrows;
+///
+/// assert!(narrows("rep", "report"));
+/// assert!(narrows("", "r"));
+/// assert!(!narrows("report", "report x"));
+/// assert!(!narrows("report", "repo"));
+/// assert!(!narrows("is:stal", "is:stale"));
+/// ```
+pub fn narrows(previous: &str, query: &str) -> bool {
+    let Some(added) = query.strip_prefix(previous) else {
+        return false;
+    };
+    let last_word = |text: &str| text.rsplit(' ').next().unwrap_or_default().to_string();
+    !added.contains(' ') && last_word(previous) != STALE_FILTER && last_word(query) != STALE_FILTER
+}
+
+/// The top level tasks of a list matching the last query, see the [module](self) documentation
+///
+/// # Examples
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use todo::app::{sources::SystemClock, ui::{matcher::FilterMatcher, todolistwidget::{Staleness, Task}}};
+///
+/// let tasks: Vec<_> = ["report", "repair", "call"]
+///     .map(|desc| Rc::new(RefCell::new(Task::new(desc.to_string(), None))))
+///     .into();
+/// let stale = Staleness::new(&SystemClock, 0);
+/// let mut matcher = FilterMatcher::default();
+/// assert_eq!(matcher.update(&tasks, "rep", stale), 3);
+/// // only the two matching "rep" are checked again
+/// assert_eq!(matcher.update(&tasks, "repo", stale), 2);
+/// assert_eq!(matcher.tasks().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FilterMatcher {
+    /// The query the tasks were matched against, None before the first one
+    query: Option<String>,
+    /// The top level tasks matching it, in the order of the list
+    tasks: Vec<Rc<RefCell<Task>>>,
+}
+
+impl FilterMatcher {
+    /// Match the tasks against a query, from the last matches when it narrows them
+    ///
+    /// # Arguments
+    ///
+    /// - `all` (`&[Rc<RefCell<Task>>]`) - the top level tasks of the list
+    /// - `query` (`&str`) - the words typed
+    /// - `stale` ([`Staleness`]) - when a task is stale, for the [`STALE_FILTER`] word
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - the number of top level tasks checked, 0 when the query didn't change
+    pub fn update(&mut self, all: &[Rc<RefCell<Task>>], query: &str, stale: Staleness) -> usize {
+        let candidates = match self.query.as_deref() {
+            Some(previous) if previous == query => return 0,
+            Some(previous) if narrows(previous, query) => std::mem::take(&mut self.tasks),
+            _ => all.to_vec(),
+        };
+        let checked = candidates.len();
+        self.tasks = candidates
+            .into_iter()
+            .filter(|task| task.borrow().is_target(query.to_string(), stale))
+            .collect();
+        self.query = Some(query.to_string());
+        checked
+    }
+
+    /// The top level tasks matching the last query, with their subtasks
+    pub fn tasks(&self) -> &[Rc<RefCell<Task>>] {
+        &self.tasks
+    }
+}
@@ -0,0 +1,96 @@
+//! The layers drawn over the panels
+//!
+//! [`Ui::update`] draws the panels first, then the open layers it draws itself from the bottom
+//! to the top of [`Layer`]: the due banner, the help page, then the guided tour, in this order
+//! whichever opened first.
+//!
+//! The popups and the calendar a popup opens aren't drawn from here. A popup draws itself
+//! right after `update`, over the frame it drew, so it's over the other layers anyway. Their
+//! layers only record that they are open, and the layers opened meanwhile are closed with the
+//! popup, see [`PopupGuard`].
+//!
+//! [`Ui::update`]: crate::app::ui::Ui::update
+//! [`PopupGuard`]: crate::app::ui::popup::PopupGuard
+
+/// A layer drawn over the panels, declared from the bottom to the top
+///
+/// # Variants
+///
+/// - `Banner` - the banner of a due task, over the first row
+/// - `Help` - the help page, over the whole frame
+/// - `Tour` - the guided tour, pointing at a panel
+/// - `Popup` - a popup waiting for keys, which draws itself
+/// - `Calendar` - the calendar opened from a popup, which draws itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Banner,
+    Help,
+    Tour,
+    Popup,
+    Calendar,
+}
+
+/// The layers open over the panels, in the order they were opened
+///
+/// A popup may open over another one, so a layer can be open more than once.
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::ui::overlay::{Layer, Overlays};
+///
+/// let mut overlays = Overlays::default();
+/// overlays.open(Layer::Popup);
+/// overlays.open(Layer::Help);
+/// assert_eq!(overlays.drawn(), [Layer::Help, Layer::Popup]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overlays {
+    stack: Vec<Layer>,
+}
+
+impl Overlays {
+    /// Open a layer over the ones already open
+    pub fn open(&mut self, layer: Layer) {
+        self.stack.push(layer);
+    }
+
+    /// Close the last opening of a layer, nothing if it isn't open
+    pub fn close(&mut self, layer: Layer) {
+        if let Some(index) = self.stack.iter().rposition(|open| *open == layer) {
+            self.stack.remove(index);
+        }
+    }
+
+    /// Open a layer if it's closed or close it if it's open, as the state it shows says
+    pub fn set(&mut self, layer: Layer, open: bool) {
+        if open && !self.is_open(layer) {
+            self.open(layer);
+        } else if !open {
+            self.stack.retain(|other| *other != layer);
+        }
+    }
+
+    /// Whether a layer is open
+    pub fn is_open(&self, layer: Layer) -> bool {
+        self.stack.contains(&layer)
+    }
+
+    /// The number of layers open, to close those opened after with [`Overlays::truncate`]
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Close the layers opened after the given depth
+    pub fn truncate(&mut self, depth: usize) {
+        self.stack.truncate(depth);
+    }
+
+    /// The open layers in the order of [`Layer`], each once
+    pub fn drawn(&self) -> Vec<Layer> {
+        let mut layers = self.stack.clone();
+        layers.sort();
+        layers.dedup();
+        layers
+    }
+}
@@ -0,0 +1,117 @@
+//! The state a popup borrows while it's open
+//!
+//! A popup changes what the rest of the UI shows while it waits for the keys: the hint bar
+//! shows the keys of the popup, and a popup may tell something in the prompt, e.g. the due
+//! date popup when the calendar is opened. Cancelled, the popup must leave the UI as it found
+//! it, whichever path it returns through, so every popup holds a [`PopupGuard`] for as long as
+//! it's open. The guard also holds the popup layer, see [`overlay`], so the popup is drawn over
+//! the help page and the other layers drawn by [`Ui::update`].
+//!
+//! [`overlay`]: crate::app::ui::overlay
+
+use std::ops::{Deref, DerefMut};
+
+use crate::app::appstate::{CurrentFocus, CurrentMode};
+use crate::app::ui::Ui;
+use crate::app::ui::overlay::Layer;
+
+/// The UI lent to an open popup, put back as it was when the guard drops
+///
+/// The prompt message and the focus of the panels are put back unless the popup commits, see
+/// [`PopupGuard::commit`], when what it did is kept. The hints of the popup are its own and
+/// always go, the hint bar shows the mode and the panel it was opened from again, and so do the
+/// popup layer and the layers opened over it, e.g. the calendar.
+///
+/// # Fields
+///
+/// - `ui` (`&mut Ui`) - the UI, the guard derefs to it
+/// - `desc` (`String`) - the prompt message when the popup opened
+/// - `hint_mode` ([`CurrentMode`]) - the mode of the hint bar when the popup opened
+/// - `calendar` (`bool`) - whether the hint bar showed the keys of the calendar
+/// - `focus` ([`CurrentFocus`]) - the panel of the hint bar when the popup opened
+/// - `focused` (`[bool; 3]`) - whether the workspace, the todo list and the archived panels
+///   were focused
+/// - `depth` (`usize`) - the number of layers open before the popup, see
+///   [`Overlays::depth`](crate::app::ui::overlay::Overlays::depth)
+/// - `committed` (`bool`) - whether the popup keeps what it changed
+#[derive(Debug)]
+pub struct PopupGuard<'a> {
+    ui: &'a mut Ui,
+    desc: String,
+    hint_mode: CurrentMode,
+    calendar: bool,
+    focus: CurrentFocus,
+    focused: [bool; 3],
+    depth: usize,
+    committed: bool,
+}
+
+impl<'a> PopupGuard<'a> {
+    /// Open a popup on the UI, remembering what it shows
+    ///
+    /// # Arguments
+    ///
+    /// - `ui` (`&mut Ui`) - the UI the popup is drawn over
+    pub fn open(ui: &'a mut Ui) -> Self {
+        let keymap = &ui.helpwidget.keymap;
+        let hint_mode = keymap.mode;
+        let calendar = keymap.calendar;
+        let focus = keymap.focus.clone();
+        let desc = ui.prompt.desc.clone();
+        let focused = [
+            ui.workspace.focused,
+            ui.todolist.focused,
+            ui.archived_ws.focused,
+        ];
+        let depth = ui.overlays.depth();
+        ui.overlays.open(Layer::Popup);
+        Self {
+            ui,
+            desc,
+            hint_mode,
+            calendar,
+            focus,
+            focused,
+            depth,
+            committed: false,
+        }
+    }
+
+    /// Keep the prompt message and the focus the popup left, when it's confirmed
+    pub fn commit(&mut self) {
+        self.committed = true;
+    }
+}
+
+impl Deref for PopupGuard<'_> {
+    type Target = Ui;
+
+    fn deref(&self) -> &Ui {
+        self.ui
+    }
+}
+
+impl DerefMut for PopupGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Ui {
+        self.ui
+    }
+}
+
+impl Drop for PopupGuard<'_> {
+    fn drop(&mut self) {
+        let keymap = &mut self.ui.helpwidget.keymap;
+        keymap.mode = self.hint_mode;
+        keymap.calendar = self.calendar;
+        self.ui.overlays.truncate(self.depth);
+        if self.committed {
+            return;
+        }
+        keymap.focus = self.focus.clone();
+        self.ui.prompt.desc = std::mem::take(&mut self.desc);
+        [
+            self.ui.workspace.focused,
+            self.ui.todolist.focused,
+            self.ui.archived_ws.focused,
+        ] = self.focused;
+    }
+}
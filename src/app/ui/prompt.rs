@@ -1,18 +1,136 @@
-use ratatui::{style::Stylize, text::Line, widgets::Widget};
+use std::collections::VecDeque;
 
+use chrono::{Local, NaiveTime};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::Widget,
+};
+
+use crate::app::appstate::CurrentMode;
+
+/// The number of prompt messages kept in the history
+pub const HISTORY_LEN: usize = 50;
+
+/// The width of the mode badge, e.g. ` NOR `
+const BADGE_WIDTH: u16 = 5;
+
+/// The width of the unsaved changes marker ` [+] `, reserved even when hidden
+const DIRTY_WIDTH: u16 = 5;
+
+/// The minimum width of the message area, so short messages don't resize the key bar
+const MIN_MESSAGE_WIDTH: u16 = 24;
+
+/// The frames of the spinner shown while a slow operation is in flight
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// A prompt message kept in the history
+///
+/// # Fields
+///
+/// - `time` (`NaiveTime`) - the local time the message was shown
+/// - `desc` (`String`) - the message
+#[derive(Debug, Clone)]
+pub struct PromptEntry {
+    pub time: NaiveTime,
+    pub desc: String,
+}
+
+/// The Widget to show the mode badge and the status messages in the bottom right corner
+///
+/// # Fields
+///
+/// - `padding` (`String`) - the padding before the message
+/// - `desc` (`String`) - the shown message, feedback of the last action
+/// - `history` (`VecDeque<PromptEntry>`) - the last [`HISTORY_LEN`] messages, oldest first
+/// - `mode` ([`CurrentMode`]) - the mode shown in the badge, set from the app state before rendering
+/// - `spinner_frame` (`usize`) - the current frame of the spinner
+/// - `dirty` (`bool`) - whether the data has unsaved changes, shown as `[+]` before the badge
+/// - `recording` (`Option<char>`) - the register of the macro being recorded, shown as `@a` in
+///   place of the padding
 #[derive(Debug)]
 pub struct PromptWidget {
     pub padding: String,
     pub desc: String,
+    pub history: VecDeque<PromptEntry>,
+    pub mode: CurrentMode,
+    pub spinner_frame: usize,
+    pub dirty: bool,
+    pub recording: Option<char>,
 }
 
 impl PromptWidget {
     pub fn new() -> Self {
         Self {
             padding: String::from("  "),
-            desc: String::from("In Normal Mode"),
+            desc: String::new(),
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            mode: CurrentMode::Normal,
+            spinner_frame: 0,
+            dirty: false,
+            recording: None,
+        }
+    }
+
+    /// Show a progress message behind the next frame of the spinner
+    ///
+    /// Unlike [`PromptWidget::set`] the message isn't kept in the history, it is replaced by
+    /// the result of the operation once it is done.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`PromptWidget`])
+    /// - `desc` (`&str`) - the progress message
+    pub fn spin(&mut self, desc: &str) {
+        self.desc = format!("{} {}", SPINNER[self.spinner_frame], desc);
+        self.spinner_frame = (self.spinner_frame + 1) % SPINNER.len();
+    }
+
+    /// The width the widget needs: the badge and the message area
+    pub fn width(&self) -> u16 {
+        let message_width = (self.padding.len() + self.desc.chars().count()) as u16;
+        DIRTY_WIDTH + BADGE_WIDTH + message_width.max(MIN_MESSAGE_WIDTH)
+    }
+
+    /// The short name of a mode shown in the badge
+    pub fn badge(mode: CurrentMode) -> (&'static str, Color) {
+        match mode {
+            CurrentMode::Normal => ("NOR", Color::LightBlue),
+            CurrentMode::Insert => ("INS", Color::LightGreen),
+            CurrentMode::Search => ("SEA", Color::LightYellow),
+            CurrentMode::Help => ("HLP", Color::LightMagenta),
+            CurrentMode::Sort => ("SRT", Color::LightCyan),
+            CurrentMode::Tour => ("TUR", Color::LightMagenta),
         }
     }
+
+    /// Show a message and keep it in the history
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`PromptWidget`])
+    /// - `desc` (`impl Into<String>`) - the message to show
+    pub fn set(&mut self, desc: impl Into<String>) {
+        self.desc = desc.into();
+        self.log(self.desc.clone());
+    }
+
+    /// Keep a message in the history without showing it, for the details of a summary
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`PromptWidget`])
+    /// - `desc` (`impl Into<String>`) - the message to keep
+    pub fn log(&mut self, desc: impl Into<String>) {
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(PromptEntry {
+            time: Local::now().time(),
+            desc: desc.into(),
+        });
+    }
 }
 
 impl Default for PromptWidget {
@@ -26,13 +144,31 @@ impl Widget for &mut PromptWidget {
     where
         Self: Sized,
     {
+        let layout = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(DIRTY_WIDTH),
+            Constraint::Length(BADGE_WIDTH),
+        ])
+        .split(area);
         Widget::render(
             Line::from(vec![
-                self.padding.clone().into(),
+                match self.recording {
+                    Some(register) => format!("@{}", register).light_red().bold(),
+                    None => self.padding.clone().into(),
+                },
                 self.desc.clone().light_cyan(),
             ]),
-            area,
+            layout[0],
+            buf,
+        );
+        let (name, color) = PromptWidget::badge(self.mode);
+        Widget::render(
+            Line::from(format!(" {} ", name)).style(Style::new().fg(Color::Black).bg(color).bold()),
+            layout[2],
             buf,
         );
+        if self.dirty {
+            Widget::render(Line::from(" [+] ").light_yellow().bold(), layout[1], buf);
+        }
     }
 }
@@ -0,0 +1,148 @@
+//! Translations of the user interface
+//!
+//! The strings shown to the user, the prompt messages, the popup titles, the confirmations
+//! and the descriptions of the key bindings, are written in English in the code and looked
+//! up in a catalog of [`Messages`] before they are shown. The English string is its own key,
+//! so a translation file is a JSON object from the English strings to the translated ones:
+//!
+//! ```json
+//! {
+//!     "Workspace Added !": "工作区已添加 !",
+//!     "{} Is Protected !": "{} 受保护 !"
+//! }
+//! ```
+//!
+//! A `{}` stands for a value filled in when the string is shown, in the order of the English
+//! string. A string the file doesn't translate is shown in English, so a translation can be
+//! partial and stays usable when new strings are added.
+//!
+//! The file is `lang/<language>.json` in the configuration directory, see
+//! [`translation_path`], the language is picked by [`Config::language`] and the file is loaded
+//! once at start up, see [`select`]. The Chinese translation shipped in the `lang` directory of
+//! the repository is an example to start from.
+//!
+//! [`Config::language`]: crate::app::config::Config::language
+//! [`translation_path`]: crate::app::config::translation_path
+
+use std::{collections::HashMap, fmt::Display, fs, path::Path, sync::OnceLock};
+
+use crate::app::errors;
+
+/// A catalog of translated strings
+///
+/// # Fields
+///
+/// - `translations` (`HashMap<String, String>`) - the translation of every translated English
+///   string, empty for English
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Messages {
+    pub translations: HashMap<String, String>,
+}
+
+impl Messages {
+    /// The English catalog, which shows every string as written
+    pub fn english() -> Self {
+        Self::default()
+    }
+
+    /// Read a translation file, see the [module](self) documentation
+    ///
+    /// # Arguments
+    ///
+    /// - `path` (`&Path`) - the JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`errors::Errors::LoadError`] if the file can't be read or isn't a JSON object of
+    /// strings
+    pub fn load(path: &Path) -> Result<Self, errors::Errors> {
+        let content = fs::read_to_string(path).map_err(|_| errors::Errors::LoadError)?;
+        let translations = serde_json::from_str(&content).map_err(|_| errors::Errors::LoadError)?;
+        Ok(Self { translations })
+    }
+
+    /// Translate a string, the string itself if it has no translation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::ui::strings::Messages;
+    ///
+    /// let mut messages = Messages::english();
+    /// assert_eq!(messages.get("Task Added !"), "Task Added !");
+    /// messages
+    ///     .translations
+    ///     .insert("Task Added !".to_string(), "任务已添加 !".to_string());
+    /// assert_eq!(messages.get("Task Added !"), "任务已添加 !");
+    /// ```
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.translations
+            .get(key)
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// Translate a string and fill its `{}` in with the values, in order
+    ///
+    /// A `{}` without a value is left as is and a value without a `{}` is dropped, so a
+    /// translation with a placeholder too many or too few still shows.
+    ///
+    /// # Arguments
+    ///
+    /// - `&self` ([`Messages`])
+    /// - `key` (`&str`) - the English string
+    /// - `values` (`&[&dyn Display]`) - the values of the placeholders
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::ui::strings::Messages;
+    ///
+    /// let messages = Messages::english();
+    /// assert_eq!(
+    ///     messages.format("Triage Done, {} Left !", &[&3]),
+    ///     "Triage Done, 3 Left !"
+    /// );
+    /// ```
+    pub fn format(&self, key: &str, values: &[&dyn Display]) -> String {
+        let mut values = values.iter();
+        let mut parts = self.get(key).split("{}");
+        let mut result = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            match values.next() {
+                Some(value) => result.push_str(&value.to_string()),
+                None => result.push_str("{}"),
+            }
+            result.push_str(part);
+        }
+        result
+    }
+}
+
+static CURRENT: OnceLock<Messages> = OnceLock::new();
+
+/// Pick the catalog for the rest of the run
+///
+/// Only the first call has an effect, like [`glyphs::select`](crate::app::ui::glyphs::select).
+///
+/// # Arguments
+///
+/// - `messages` ([`Messages`]) - the catalog
+pub fn select(messages: Messages) {
+    let _ = CURRENT.set(messages);
+}
+
+/// Get the catalog in use, English if none was selected
+pub fn current() -> &'static Messages {
+    CURRENT.get_or_init(Messages::english)
+}
+
+/// Translate a string with the catalog in use, see [`Messages::get`]
+pub fn tr(key: &str) -> &str {
+    current().get(key)
+}
+
+/// Translate a string with the catalog in use and fill it in, see [`Messages::format`]
+pub fn trf(key: &str, values: &[&dyn Display]) -> String {
+    current().format(key, values)
+}
@@ -0,0 +1,284 @@
+//! Colors for the terminal in use
+//!
+//! The widgets are styled with the 16 named colors and a few RGB ones, e.g. the background of
+//! the selected row. Not every terminal shows them: an 8 or 16 color terminal drops the RGB
+//! colors or shows them black, and a user may ask for no color at all with the `NO_COLOR`
+//! environment variable. Rather than every widget checking, the frames go through a
+//! [`ThemedBackend`] which adapts the colors of each cell to the [`ColorSupport`] of the
+//! terminal:
+//!
+//! - `truecolor` - the colors are drawn as styled
+//! - `256` - the RGB colors become the closest of the 256 indexed colors
+//! - `16` - every color becomes the closest of the 16 named ones, and a background the closest
+//!   of the 6 normal colors which are neither black nor white: 8 color terminals show them
+//!   too, and they stand out from the default background, which a dark RGB one wouldn't
+//! - `none` - the colors are dropped and a cell with a background, e.g. the selected row, is
+//!   drawn reversed so it still stands out
+//!
+//! The support is detected from the environment, see [`ColorSupport::detect`], unless
+//! [`Config::color`] sets it.
+//!
+//! [`Config::color`]: crate::app::config::Config::color
+
+use std::io;
+
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::Cell,
+    layout::{Position, Size},
+    style::{Color, Modifier},
+};
+use serde::{Deserialize, Serialize};
+
+/// The colors a terminal can show
+///
+/// # Variants
+///
+/// - `NoColor` - none, the attributes only
+/// - `Ansi16` - the 16 named colors
+/// - `Ansi256` - the 256 indexed colors
+/// - `TrueColor` - any RGB color
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorSupport {
+    #[serde(rename = "none")]
+    NoColor,
+    #[serde(rename = "16")]
+    Ansi16,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[default]
+    #[serde(rename = "truecolor")]
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Detect the colors of the terminal from the environment
+    ///
+    /// A non empty `NO_COLOR` turns the colors off, `COLORTERM` set to `truecolor` or `24bit`
+    /// allows any color, a `TERM` ending with `256color` the indexed colors and a dumb `TERM`
+    /// none. Any other terminal gets the 16 named colors, which all of them show.
+    ///
+    /// # Arguments
+    ///
+    /// - `var` (`impl Fn(&str) -> Option<String>`) - read an environment variable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::ui::theme::ColorSupport;
+    ///
+    /// let env = |name: &str| (name == "TERM").then(|| "xterm-256color".to_string());
+    /// assert_eq!(ColorSupport::detect(env), ColorSupport::Ansi256);
+    /// ```
+    pub fn detect(var: impl Fn(&str) -> Option<String>) -> Self {
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return ColorSupport::NoColor;
+        }
+        if var("COLORTERM").is_some_and(|value| value == "truecolor" || value == "24bit") {
+            return ColorSupport::TrueColor;
+        }
+        match var("TERM") {
+            Some(term) if term == "dumb" => ColorSupport::NoColor,
+            Some(term) if term.ends_with("256color") => ColorSupport::Ansi256,
+            // the Windows console sets no TERM and shows any color
+            None if cfg!(windows) => ColorSupport::TrueColor,
+            _ => ColorSupport::Ansi16,
+        }
+    }
+
+    /// Detect the colors of the terminal from the environment of the process
+    pub fn from_env() -> Self {
+        Self::detect(|name| std::env::var(name).ok())
+    }
+
+    /// Adapt the colors of a cell to the terminal, see the [module](self) documentation
+    pub fn adapt(self, cell: &Cell) -> Cell {
+        let mut cell = cell.clone();
+        match self {
+            ColorSupport::TrueColor => {}
+            ColorSupport::Ansi256 => {
+                cell.fg = to_256(cell.fg);
+                cell.bg = to_256(cell.bg);
+            }
+            ColorSupport::Ansi16 => {
+                cell.fg = nearest(cell.fg, &ANSI16);
+                cell.bg = nearest(cell.bg, &ANSI16[1..7]);
+            }
+            ColorSupport::NoColor => {
+                if cell.bg != Color::Reset {
+                    cell.modifier |= Modifier::REVERSED;
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+        }
+        cell
+    }
+}
+
+/// The named colors with the RGB values of the xterm palette, the 8 normal ones first,
+/// black to gray
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The levels of the 6x6x6 color cube of the indexed colors
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The RGB value of a color, None for the default color
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index) if index < 16 => Some(ANSI16[index as usize].1),
+        Color::Indexed(index) if index < 232 => {
+            let index = index - 16;
+            Some((
+                CUBE[(index / 36) as usize],
+                CUBE[(index / 6 % 6) as usize],
+                CUBE[(index % 6) as usize],
+            ))
+        }
+        Color::Indexed(index) => {
+            let level = 8 + 10 * (index - 232);
+            Some((level, level, level))
+        }
+        Color::Reset => None,
+        named => ANSI16
+            .iter()
+            .find(|(color, _)| *color == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    [(r1, r2), (g1, g2), (b1, b2)]
+        .iter()
+        .map(|(a, b)| (*a as i32 - *b as i32).pow(2) as u32)
+        .sum()
+}
+
+/// The closest color of a palette, the default color staying the default
+fn nearest(color: Color, palette: &[(Color, (u8, u8, u8))]) -> Color {
+    let Some(target) = rgb(color) else {
+        return color;
+    };
+    palette
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, target))
+        .map_or(color, |(color, _)| *color)
+}
+
+/// The closest indexed color of an RGB color, the other colors are shown by every such
+/// terminal already
+fn to_256(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let level = |value: u8| {
+        CUBE.iter()
+            .enumerate()
+            .min_by_key(|(_, level)| (**level as i32 - value as i32).abs())
+            .map_or(0, |(index, _)| index as u8)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = 16 + 36 * ri + 6 * gi + bi;
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = 232 + ((average.saturating_sub(8) + 5) / 10).min(23) as u8;
+    [cube, gray]
+        .into_iter()
+        .min_by_key(|index| distance(rgb(Color::Indexed(*index)).unwrap_or_default(), (r, g, b)))
+        .map_or(color, Color::Indexed)
+}
+
+/// A backend drawing the cells with the colors the terminal shows, see the
+/// [module](self) documentation
+///
+/// # Fields
+///
+/// - `inner` (`B`) - the backend drawing to the terminal
+/// - `support` ([`ColorSupport`]) - the colors of the terminal, any color until it's known
+#[derive(Debug)]
+pub struct ThemedBackend<B: Backend> {
+    pub inner: B,
+    pub support: ColorSupport,
+}
+
+impl<B: Backend> ThemedBackend<B> {
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            support: ColorSupport::TrueColor,
+        }
+    }
+}
+
+impl<B: Backend> Backend for ThemedBackend<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if self.support == ColorSupport::TrueColor {
+            return self.inner.draw(content);
+        }
+        let cells: Vec<(u16, u16, Cell)> = content
+            .map(|(x, y, cell)| (x, y, self.support.adapt(cell)))
+            .collect();
+        self.inner
+            .draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,72 @@
+//! The title of the terminal window
+//!
+//! The title names the open workspace and counts the tasks due today, e.g.
+//! `todo — Work/Backend (3 due today)`, so the app can be checked at a glance from a
+//! background tmux pane or terminal tab. It is only changed on a terminal, not a dumb one,
+//! and can be turned off with [`Config::terminal_title`].
+//!
+//! The title shown before is saved on the title stack of xterm, which most terminals and
+//! tmux keep, and brought back when the [`TerminalTitle`] is dropped on exit. The Windows
+//! console has no such stack, there the title is left to the shell.
+//!
+//! [`Config::terminal_title`]: crate::app::config::Config::terminal_title
+
+use std::io::{self, IsTerminal, Write};
+
+use crossterm::{execute, terminal::SetTitle};
+
+/// Push the title on the title stack
+const PUSH_TITLE: &str = "\x1b[22;0t";
+/// Pop the title from the title stack
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// The title written to the terminal, see the [module](self) documentation
+///
+/// # Fields
+///
+/// - `shown` (`Option<String>`) - the title last written, so an unchanged title isn't written
+///   again on every redraw
+#[derive(Debug)]
+pub struct TerminalTitle {
+    pub shown: Option<String>,
+}
+
+impl TerminalTitle {
+    /// Whether the title can be changed: the output is a terminal which isn't dumb
+    pub fn supported() -> bool {
+        io::stdout().is_terminal()
+            && std::env::var("TERM")
+                .map_or(cfg!(windows), |term| !term.is_empty() && term != "dumb")
+    }
+
+    /// Save the title of the terminal, to take it over until dropped
+    pub fn start() -> Self {
+        stack(PUSH_TITLE);
+        Self { shown: None }
+    }
+
+    /// Write the title, unless it's already shown
+    pub fn set(&mut self, title: &str) {
+        if self.shown.as_deref() == Some(title) {
+            return;
+        }
+        let _ = execute!(io::stdout(), SetTitle(title));
+        self.shown = Some(title.to_string());
+    }
+}
+
+impl Drop for TerminalTitle {
+    /// Bring the saved title back
+    fn drop(&mut self) {
+        stack(POP_TITLE);
+    }
+}
+
+fn stack(sequence: &str) {
+    if cfg!(windows) {
+        return;
+    }
+    let mut stdout = io::stdout();
+    let _ = write!(stdout, "{}", sequence);
+    let _ = stdout.flush();
+}
@@ -1,14 +1,27 @@
-use chrono::{Local, NaiveDate};
+use chrono::{Days, Local, Months, NaiveDate, NaiveDateTime, TimeDelta};
 use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style, Styled, Stylize},
     text::{Line, Span},
     widgets::{Block, List, ListItem, ListState, Padding, StatefulWidget, Widget},
 };
 use serde::{Deserialize, Serialize};
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, collections::BTreeMap, fmt, rc::Rc};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use uuid::Uuid;
 
-use crate::app::ui::{SelectAction, SelectBF, workspacewidget::Workspace};
+use crate::app::{
+    config::DEFAULT_STALE_AFTER_DAYS,
+    links, parse,
+    sources::{Clock, IdGen, RandomIds, SystemClock},
+    ui::{
+        SelectAction, SelectBF, Ui, glyphs,
+        strings::trf,
+        tree::{self, Fold, Jump, TreeNode},
+        workspacewidget::{Workspace, WorkspaceType, WorkspaceWidget},
+    },
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum TaskStatus {
@@ -18,13 +31,61 @@ pub enum TaskStatus {
     Deprecated,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl TaskStatus {
+    /// The position of the status in the smart sort, the work in hand comes first
+    fn sort_rank(&self) -> u8 {
+        match self {
+            TaskStatus::InProcess => 0,
+            TaskStatus::Todo => 1,
+            TaskStatus::Finished => 2,
+            TaskStatus::Deprecated => 3,
+        }
+    }
+}
+
+/// The colors a task can be given, the base colors of the terminal but black, which
+/// disappears on a dark background
+pub const TASK_COLORS: [&str; 7] = ["red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Urgency {
     Critical,
     Important,
     Common,
 }
 
+impl Urgency {
+    /// The position of the urgency in the urgency sort, the most urgent comes first
+    fn sort_rank(urgency: &Option<Urgency>) -> u8 {
+        match urgency {
+            Some(Urgency::Critical) => 0,
+            Some(Urgency::Important) => 1,
+            Some(Urgency::Common) => 2,
+            None => 3,
+        }
+    }
+
+    /// The urgency one level up, Critical staying Critical
+    pub fn raised(urgency: &Option<Urgency>) -> Urgency {
+        match urgency {
+            None => Urgency::Common,
+            Some(Urgency::Common) => Urgency::Important,
+            Some(Urgency::Important | Urgency::Critical) => Urgency::Critical,
+        }
+    }
+}
+
+impl fmt::Display for Urgency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Urgency::Critical => "Critical",
+            Urgency::Important => "Important",
+            Urgency::Common => "Common",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Task {
     pub desc: String,
@@ -34,27 +95,127 @@ pub struct Task {
     pub children: Vec<Rc<RefCell<Task>>>,
     pub id: Uuid,
     pub urgency: Option<Urgency>,
+    /// When the task was created, None for tasks saved before it was recorded
+    #[serde(default)]
+    pub created: Option<NaiveDateTime>,
+    /// When the task was last changed, see [`Task::touch`], None if never changed
+    #[serde(default)]
+    pub updated: Option<NaiveDateTime>,
+    /// The times the overdue escalation raised the urgency since the due date was set, see
+    /// [`EscalationPolicy`]
+    #[serde(default)]
+    pub escalations: u32,
+    /// The color of the description, one of [`TASK_COLORS`], see [`TodoWidget::desc_style`]
+    #[serde(default)]
+    pub color: Option<String>,
+    /// The days before the due date the task is reminded of, see [`Task::reminding`]
+    #[serde(default)]
+    pub remind_days_before: Option<u8>,
+    /// The notes written about the task, lines apart by `\n`, empty when it has none
+    #[serde(default)]
+    pub notes: String,
 }
 
 impl Task {
     pub fn new(desc: String, due: Option<NaiveDate>) -> Self {
+        Self::with_ids(desc, due, &RandomIds)
+    }
+
+    /// Create a task which takes its id from a given id source
+    ///
+    /// # Arguments
+    ///
+    /// - `desc` (`String`) - the description of the task
+    /// - `due` (`Option<NaiveDate>`) - the due date of the task
+    /// - `ids` (`&dyn IdGen`) - the source of the id
+    pub fn with_ids(desc: String, due: Option<NaiveDate>, ids: &dyn IdGen) -> Self {
         Self {
             desc,
             status: TaskStatus::Todo,
             expanded: true,
             due,
             children: Vec::new(),
-            id: Uuid::new_v4(),
+            id: ids.next_id(),
             urgency: None,
+            created: Some(Local::now().naive_local()),
+            updated: None,
+            escalations: 0,
+            color: None,
+            remind_days_before: None,
+            notes: String::new(),
         }
     }
 
+    /// Record that the task was just changed
+    ///
+    /// Every method changing what the task says, its status, name, due date, urgency or notes,
+    /// calls this, so a task is stale only when nobody has worked on it.
+    pub fn touch(&mut self) {
+        self.updated = Some(Local::now().naive_local());
+    }
+
+    /// When the task was last changed, or created if it never was
+    pub fn last_touched(&self) -> Option<NaiveDateTime> {
+        self.updated.or(self.created)
+    }
+
     pub fn add_child(&mut self, task: Rc<RefCell<Task>>) {
         self.children.push(task);
     }
+
+    /// Replace the notes, the trailing blank lines left out
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - whether the notes changed
+    pub fn set_notes(&mut self, notes: &str) -> bool {
+        let notes = notes.trim_end();
+        if notes == self.notes {
+            return false;
+        }
+        self.notes = notes.to_string();
+        self.touch();
+        true
+    }
+
+    /// Count the subtasks [`Task::clear_done`] removes
+    pub fn done_subtasks(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| {
+                let child = child.borrow();
+                let below = child.done_subtasks();
+                let done = matches!(child.status, TaskStatus::Finished | TaskStatus::Deprecated)
+                    && below == TodoWidget::get_flattened(&child.children).len();
+                below + usize::from(done)
+            })
+            .sum()
+    }
+
+    /// Remove the finished and deprecated subtasks, at any depth, keeping the open ones
+    ///
+    /// A done subtask holding an open one stays, so the open tasks keep their place in the
+    /// tree.
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - the number of subtasks removed, see [`Task::done_subtasks`]
+    pub fn clear_done(&mut self) -> usize {
+        let mut removed = 0;
+        self.children.retain(|child| {
+            let mut child = child.borrow_mut();
+            removed += child.clear_done();
+            let done = matches!(child.status, TaskStatus::Finished | TaskStatus::Deprecated)
+                && child.children.is_empty();
+            removed += usize::from(done);
+            !done
+        });
+        removed
+    }
     pub fn set_task_status(task: &Rc<RefCell<Task>>, status: TaskStatus) {
         let mut task_mut = task.borrow_mut();
         task_mut.status = status.clone();
+        task_mut.touch();
         if !task_mut.children.is_empty()
             && (status == TaskStatus::Finished || status == TaskStatus::Deprecated)
         {
@@ -66,21 +227,102 @@ impl Task {
         }
     }
 
+    /// Get the parent left without an open subtask once a task is finished
+    ///
+    /// # Arguments
+    ///
+    /// - `roots` (`&[Rc<RefCell<Task>>]`) - the top level tasks of the list
+    /// - `task` (`&Rc<RefCell<Task>>`) - the task just finished
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Rc<RefCell<Task>>>` - the parent, None for a top level task, a parent which
+    ///   isn't open or one with subtasks still to do, see [`TaskStats::open`]
+    pub fn completed_parent(
+        roots: &[Rc<RefCell<Task>>],
+        task: &Rc<RefCell<Task>>,
+    ) -> Option<Rc<RefCell<Task>>> {
+        let parent = tree::find_parent(roots, task)?;
+        let done = {
+            let parent = parent.borrow();
+            matches!(parent.status, TaskStatus::Todo | TaskStatus::InProcess)
+                && TaskStats::of(&parent.children).open() == 0
+        };
+        done.then_some(parent)
+    }
+
     pub fn rename(&mut self, new_name: String) {
         self.desc = new_name;
+        self.touch();
+    }
+
+    /// Set or clear the due date, which starts the overdue escalation over
+    pub fn set_due(&mut self, due: Option<NaiveDate>) {
+        self.due = due;
+        self.escalations = 0;
+        self.touch();
+    }
+
+    /// Set the due date and the reminder typed into the due date popup, e.g. `fri remind 3d`
+    ///
+    /// The reminder is kept when none is typed, and `remind 3d` alone only sets the reminder.
+    /// `remind none` or `remind 0d` clears it, see [`parse::reminder`].
+    ///
+    /// # Arguments
+    ///
+    /// - `typed` (`&str`) - the text typed
+    /// - `clock` (`&dyn Clock`) - the source of today's date
+    pub fn set_due_typed(&mut self, typed: &str, clock: &dyn Clock) {
+        let (date, remind) = parse::reminder(typed);
+        if remind.is_none() || !date.is_empty() {
+            self.set_due(parse::due(date, clock));
+        }
+        if let Some(remind) = remind {
+            self.remind_days_before = remind;
+            self.touch();
+        }
+    }
+
+    /// The day the reminder of the task starts, `remind_days_before` days before its due date
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use todo::app::ui::todolistwidget::Task;
+    ///
+    /// let mut task = Task::new("pay rent".to_string(), NaiveDate::from_ymd_opt(2025, 3, 2));
+    /// assert_eq!(task.remind_on(), None);
+    /// task.remind_days_before = Some(3);
+    /// assert_eq!(task.remind_on(), NaiveDate::from_ymd_opt(2025, 2, 27));
+    /// ```
+    pub fn remind_on(&self) -> Option<NaiveDate> {
+        let days = self.remind_days_before?;
+        self.due?.checked_sub_days(Days::new(u64::from(days)))
+    }
+
+    /// Whether an open task is reminded of today, from its reminder day, see
+    /// [`Task::remind_on`], until the day before it's due
+    pub fn reminding(&self, today: NaiveDate) -> bool {
+        matches!(self.status, TaskStatus::Todo | TaskStatus::InProcess)
+            && self.remind_on().is_some_and(|day| day <= today)
+            && self.due.is_some_and(|due| today < due)
     }
 
     // TODO: use regex to completed the search functionality
-    pub fn is_target(&self, search_string: String) -> bool {
-        let search_strings = search_string.split(" ");
-        let mut result = false;
-        search_strings.into_iter().for_each(|s| {
-            if self.desc.contains(s) {
-                result = true;
-            }
-        });
+    /// Whether the task, or one of its subtasks, matches a filter
+    ///
+    /// The words of the filter match any part of the description. The `is:stale` word keeps
+    /// only the tasks which are stale by `stale`, and matches them all when it is alone.
+    ///
+    /// # Arguments
+    ///
+    /// - `search_string` (`String`) - the words of the filter, separated by spaces
+    /// - `stale` ([`Staleness`]) - when a task is stale
+    pub fn is_target(&self, search_string: String, stale: Staleness) -> bool {
+        let mut result = self.matches(&search_string, stale);
         for task in self.children.iter() {
-            if task.borrow().is_target(search_string.to_owned()) {
+            if task.borrow().is_target(search_string.to_owned(), stale) {
                 result = true;
                 break;
             }
@@ -88,7 +330,21 @@ impl Task {
         result
     }
 
+    /// Whether the task itself matches a filter, whatever its subtasks, see [`Task::is_target`]
+    pub fn matches(&self, search_string: &str, stale: Staleness) -> bool {
+        let mut words: Vec<&str> = search_string.split(" ").collect();
+        let stale_only = words.contains(&STALE_FILTER);
+        words.retain(|word| *word != STALE_FILTER);
+        let result = if stale_only && words.iter().all(|word| word.is_empty()) {
+            true
+        } else {
+            words.iter().any(|word| self.desc.contains(word))
+        };
+        result && !(stale_only && stale.days(self).is_none())
+    }
+
     pub fn increase_urgency(&mut self) {
+        self.touch();
         if let Some(urgency) = &mut self.urgency {
             match urgency {
                 Urgency::Common => {
@@ -105,6 +361,7 @@ impl Task {
     }
 
     pub fn decrease_urgency(&mut self) {
+        self.touch();
         let ug = self.urgency.clone();
         if let Some(urgency) = ug {
             match urgency {
@@ -116,6 +373,529 @@ impl Task {
     }
 }
 
+/// A change of the due date applied to many tasks at once, see [`TodoWidget::set_due_matching`]
+///
+/// # Variants
+///
+/// - `Set` - give every task this due date, or clear it
+/// - `ShiftDays` - move the due date of every task by a number of days, earlier if negative
+/// - `ShiftMonths` - move the due date of every task by a number of months
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueChange {
+    Set(Option<NaiveDate>),
+    ShiftDays(i64),
+    ShiftMonths(i32),
+}
+
+impl DueChange {
+    /// The new due date of a task, None if a shift skips a task without a due date, or one
+    /// it would move out of the calendar, which keeps its due date
+    pub fn apply(&self, due: Option<NaiveDate>) -> Option<Option<NaiveDate>> {
+        let shifted = match self {
+            DueChange::Set(date) => return Some(*date),
+            DueChange::ShiftDays(days) => due?.checked_add_signed(TimeDelta::try_days(*days)?),
+            DueChange::ShiftMonths(months) => {
+                let by = Months::new(months.unsigned_abs());
+                if *months < 0 {
+                    due?.checked_sub_months(by)
+                } else {
+                    due?.checked_add_months(by)
+                }
+            }
+        };
+        shifted.map(Some)
+    }
+
+    /// Whether the change moves due dates rather than setting them
+    pub fn is_shift(&self) -> bool {
+        !matches!(self, DueChange::Set(_))
+    }
+}
+
+/// A task due on a day and the id of its workspace, see [`TodoWidget::due_days`]
+pub type DueTask = (Uuid, Rc<RefCell<Task>>);
+
+/// The filter word keeping only the stale tasks, see [`Task::is_target`]
+pub const STALE_FILTER: &str = "is:stale";
+
+/// When an open task counts as stale
+///
+/// A todo or in process task is stale once it has gone `after_days` days without being
+/// changed, counting from its creation if it never was. Tasks saved before the times were
+/// recorded are never stale, and an `after_days` of 0 turns the check off.
+///
+/// # Fields
+///
+/// - `today` (`NaiveDate`) - the date to count the days to
+/// - `after_days` (`u32`) - the days without a change after which a task is stale
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, Local};
+/// use todo::app::sources::SystemClock;
+/// use todo::app::ui::todolistwidget::{Staleness, Task};
+///
+/// let mut task = Task::new("write".to_string(), None);
+/// task.created = Some(Local::now().naive_local() - Duration::days(45));
+/// assert_eq!(Staleness::new(&SystemClock, 30).days(&task), Some(45));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Staleness {
+    pub today: NaiveDate,
+    pub after_days: u32,
+}
+
+impl Staleness {
+    pub fn new(clock: &dyn Clock, after_days: u32) -> Self {
+        Self {
+            today: clock.today(),
+            after_days,
+        }
+    }
+
+    /// Get the days a task has gone without a change, None if it isn't stale
+    pub fn days(&self, task: &Task) -> Option<i64> {
+        if self.after_days == 0 || !matches!(task.status, TaskStatus::Todo | TaskStatus::InProcess)
+        {
+            return None;
+        }
+        let days = (self.today - task.last_touched()?.date()).num_days();
+        (days >= self.after_days as i64).then_some(days)
+    }
+
+    /// Count the stale tasks of a task tree, subtasks included
+    pub fn count(&self, tasks: &[Rc<RefCell<Task>>]) -> usize {
+        tasks
+            .iter()
+            .map(|task| {
+                let task = task.borrow();
+                usize::from(self.days(&task).is_some()) + self.count(&task.children)
+            })
+            .sum()
+    }
+}
+
+/// What happens to a parent task when its last open subtask is finished
+///
+/// # Variants
+///
+/// - `Off` - nothing, the parent stays open
+/// - `Auto` - the parent is finished too, and so on up the tree
+/// - `Ask` - the status line asks whether to finish the parent, `y` or `n`
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::ui::todolistwidget::ParentDone;
+///
+/// let mode: ParentDone = serde_json::from_str("\"ask\"").unwrap();
+/// assert_eq!(mode, ParentDone::Ask);
+/// assert_eq!(ParentDone::default(), ParentDone::Off);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParentDone {
+    #[default]
+    Off,
+    Auto,
+    Ask,
+}
+
+/// When the urgency of an overdue task goes up
+///
+/// An open task is raised one level the day after its due date, and one more level every
+/// `again_after_days` days after that, up to Critical. The escalations are counted on the
+/// task, so scanning twice the same day raises nothing, and lowering the urgency by hand
+/// holds until the next step. Setting the due date again starts over.
+///
+/// # Fields
+///
+/// - `again_after_days` (`u32`) - the days between two escalations of the same task, 0 to
+///   escalate only once
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use todo::app::ui::todolistwidget::EscalationPolicy;
+///
+/// let policy = EscalationPolicy { again_after_days: 3 };
+/// let due = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+/// assert_eq!(policy.steps(due, NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()), 0);
+/// assert_eq!(policy.steps(due, NaiveDate::from_ymd_opt(2025, 7, 2).unwrap()), 1);
+/// assert_eq!(policy.steps(due, NaiveDate::from_ymd_opt(2025, 7, 5).unwrap()), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EscalationPolicy {
+    pub again_after_days: u32,
+}
+
+impl Default for EscalationPolicy {
+    fn default() -> Self {
+        Self {
+            again_after_days: 3,
+        }
+    }
+}
+
+impl EscalationPolicy {
+    /// The escalations a task due on `due` should have had by `today`
+    pub fn steps(&self, due: NaiveDate, today: NaiveDate) -> u32 {
+        let overdue = (today - due).num_days();
+        if overdue < 1 {
+            return 0;
+        }
+        match self.again_after_days {
+            0 => 1,
+            again => 1 + ((overdue - 1) / again as i64) as u32,
+        }
+    }
+
+    /// Raise the urgency of a task for the escalations it is owed
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Urgency>` - the new urgency, None if it didn't change
+    pub fn escalate(&self, task: &mut Task, today: NaiveDate) -> Option<Urgency> {
+        let due = task
+            .due
+            .filter(|_| matches!(task.status, TaskStatus::Todo | TaskStatus::InProcess))?;
+        let before = task.urgency.clone();
+        while task.escalations < self.steps(due, today) {
+            task.escalations += 1;
+            if task.urgency == Some(Urgency::Critical) {
+                // nothing above, the rest of the steps are owed to nobody
+                task.escalations = self.steps(due, today);
+                break;
+            }
+            task.urgency = Some(Urgency::raised(&task.urgency));
+        }
+        (task.urgency != before)
+            .then(|| task.urgency.clone())
+            .flatten()
+    }
+}
+
+impl TreeNode for Task {
+    fn children(&self) -> &[Rc<RefCell<Task>>] {
+        &self.children
+    }
+
+    fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    fn set_expanded(&mut self, expanded: bool) {
+        self.expanded = expanded;
+    }
+}
+
+/// The counts of finished and open tasks in a task tree
+///
+/// Deprecated tasks are left out of both counts with their subtasks, whatever their status,
+/// so a tree is complete once everything that is still meant to be done is finished. The
+/// count badges of parent tasks and the progress of a todo list are both computed here, and
+/// the open tasks of the overdue counts and of the due days are found by
+/// [`TaskStats::open_tasks`], so they can't disagree.
+///
+/// # Fields
+///
+/// - `finished` (`usize`) - the number of finished tasks
+/// - `total` (`usize`) - the number of tasks which are not deprecated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskStats {
+    pub finished: usize,
+    pub total: usize,
+}
+
+impl TaskStats {
+    /// Count the tasks and all their subtasks
+    ///
+    /// # Arguments
+    ///
+    /// - `tasks` (`&[Rc<RefCell<Task>>]`) - the tasks to count
+    ///
+    /// # Returns
+    ///
+    /// - `TaskStats` - the counts of the whole tree
+    pub fn of(tasks: &[Rc<RefCell<Task>>]) -> Self {
+        tasks.iter().fold(TaskStats::default(), |mut stats, task| {
+            let task = task.borrow();
+            match task.status {
+                TaskStatus::Finished => {
+                    stats.finished += 1;
+                    stats.total += 1;
+                }
+                TaskStatus::Deprecated => return stats,
+                _ => stats.total += 1,
+            }
+            let children = TaskStats::of(&task.children);
+            stats.finished += children.finished;
+            stats.total += children.total;
+            stats
+        })
+    }
+
+    /// Find the open tasks, todo or in process, and all their open subtasks
+    ///
+    /// The subtasks of a deprecated task are left out with it, as in [`TaskStats::of`].
+    ///
+    /// # Arguments
+    ///
+    /// - `tasks` (`&[Rc<RefCell<Task>>]`) - the tasks to look through
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<Rc<RefCell<Task>>>` - the open tasks, in tree order
+    pub fn open_tasks(tasks: &[Rc<RefCell<Task>>]) -> Vec<Rc<RefCell<Task>>> {
+        let mut open = Vec::new();
+        for task in tasks {
+            let status = task.borrow().status.clone();
+            if status == TaskStatus::Deprecated {
+                continue;
+            }
+            if matches!(status, TaskStatus::Todo | TaskStatus::InProcess) {
+                open.push(task.clone());
+            }
+            open.extend(TaskStats::open_tasks(&task.borrow().children));
+        }
+        open
+    }
+
+    /// The number of tasks still to do, neither finished nor deprecated
+    pub fn open(&self) -> usize {
+        self.total - self.finished
+    }
+
+    /// Whether there is something to do and all of it is finished
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.finished == self.total
+    }
+
+    /// The finished share of the tasks in percent, rounded down, or None with nothing to do
+    pub fn percent(&self) -> Option<usize> {
+        (self.total > 0).then(|| self.finished * 100 / self.total)
+    }
+}
+
+/// The vitals of a workspace and its sub workspaces, see [`TodoWidget::workspace_stats`]
+///
+/// # Fields
+///
+/// - `tasks` ([`TaskStats`]) - the finished and not deprecated tasks
+/// - `in_process` (`usize`) - the tasks in process
+/// - `overdue` (`usize`) - the open tasks due before today
+/// - `next_due` (`Option<NaiveDate>`) - the earliest due date of an open task, today or later
+/// - `last_activity` (`Option<NaiveDateTime>`) - when a task was last created or changed
+/// - `sub_workspaces` (`usize`) - the sub workspaces at any depth
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkspaceStats {
+    pub tasks: TaskStats,
+    pub in_process: usize,
+    pub overdue: usize,
+    pub next_due: Option<NaiveDate>,
+    pub last_activity: Option<NaiveDateTime>,
+    pub sub_workspaces: usize,
+}
+
+/// Compare two tasks by the smart rule
+///
+/// Tasks are ordered by status first: in process, todo, finished, then deprecated. Within a
+/// status the earlier due date comes first and tasks without a due date come last.
+///
+/// # Arguments
+///
+/// - `a` ([`Task`]) - the first task
+/// - `b` ([`Task`]) - the second task
+///
+/// # Returns
+///
+/// - `Ordering` - the order of `a` relative to `b`
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::ui::todolistwidget::{cmp_tasks, Task, TaskStatus};
+///
+/// let todo = Task::new("write".to_string(), None);
+/// let mut doing = Task::new("read".to_string(), None);
+/// doing.status = TaskStatus::InProcess;
+/// assert_eq!(cmp_tasks(&doing, &todo), std::cmp::Ordering::Less);
+/// ```
+pub fn cmp_tasks(a: &Task, b: &Task) -> Ordering {
+    a.status
+        .sort_rank()
+        .cmp(&b.status.sort_rank())
+        .then_with(|| cmp_none_last(&a.due, &b.due))
+}
+
+/// Order the values of an option, with None last
+fn cmp_none_last<T: Ord>(a: &Option<T>, b: &Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// A rule to sort a todo list by, chosen per list in the sort picker
+///
+/// # Variants
+///
+/// - `Smart` - by status, then due date, see [`cmp_tasks`]
+/// - `Due` - the earliest due date first, tasks without one last
+/// - `Status` - in process, todo, finished, then deprecated
+/// - `Urgency` - the most urgent first, tasks without an urgency last
+/// - `Name` - alphabetically, ignoring the case
+/// - `Created` - the oldest first, tasks without a creation time last
+/// - `Manual` - the order the tasks were added or arranged in
+///
+/// The rule only changes the order the list is shown in, see [`SortRule::arrange`], unless
+/// the list is sorted into its manual order, see [`TodoList::keep_sorted`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortRule {
+    Smart,
+    Due,
+    Status,
+    Urgency,
+    Name,
+    Created,
+    #[default]
+    Manual,
+}
+
+impl SortRule {
+    /// Every rule, in the order of the sort picker
+    pub const ALL: [SortRule; 7] = [
+        SortRule::Smart,
+        SortRule::Due,
+        SortRule::Status,
+        SortRule::Urgency,
+        SortRule::Name,
+        SortRule::Created,
+        SortRule::Manual,
+    ];
+
+    /// Get the comparison of the rule
+    ///
+    /// # Returns
+    ///
+    /// - `Option<fn(&Task, &Task) -> Ordering>` - the comparison, None for the manual order
+    pub fn compare(self) -> Option<fn(&Task, &Task) -> Ordering> {
+        match self {
+            SortRule::Smart => Some(cmp_tasks),
+            SortRule::Due => Some(|a, b| cmp_none_last(&a.due, &b.due)),
+            SortRule::Status => Some(|a, b| a.status.sort_rank().cmp(&b.status.sort_rank())),
+            SortRule::Urgency => {
+                Some(|a, b| Urgency::sort_rank(&a.urgency).cmp(&Urgency::sort_rank(&b.urgency)))
+            }
+            SortRule::Name => Some(|a, b| a.desc.to_lowercase().cmp(&b.desc.to_lowercase())),
+            SortRule::Created => Some(|a, b| cmp_none_last(&a.created, &b.created)),
+            SortRule::Manual => None,
+        }
+    }
+
+    /// Arrange one level of tasks in the order shown under the rule
+    ///
+    /// The vector itself always holds the manual order, a sorted rule only permutes the copy
+    /// returned here, so switching back to the manual rule shows the original arrangement.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` ([`SortRule`])
+    /// - `tasks` (`&[Rc<RefCell<Task>>]`) - the tasks of one level, in the manual order
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<Rc<RefCell<Task>>>` - the same tasks, equal tasks kept in the manual order
+    pub fn arrange(self, tasks: &[Rc<RefCell<Task>>]) -> Vec<Rc<RefCell<Task>>> {
+        let mut arranged = tasks.to_vec();
+        if let Some(cmp) = self.compare() {
+            arranged.sort_by(|a, b| cmp(&a.borrow(), &b.borrow()));
+        }
+        arranged
+    }
+
+    /// Sort a task tree in place, every level arranged by the rule
+    ///
+    /// Unlike [`SortRule::arrange`] the vectors themselves are reordered, so the order becomes
+    /// the manual order, equal tasks kept as they were.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` ([`SortRule`])
+    /// - `tasks` (`&mut Vec<Rc<RefCell<Task>>>`) - the top level tasks
+    pub fn rearrange(self, tasks: &mut Vec<Rc<RefCell<Task>>>) {
+        *tasks = self.arrange(tasks);
+        tasks
+            .iter()
+            .for_each(|task| self.rearrange(&mut task.borrow_mut().children));
+    }
+
+    /// Flatten a task tree depth first, every level arranged by the rule
+    ///
+    /// # Arguments
+    ///
+    /// - `self` ([`SortRule`])
+    /// - `tasks` (`&[Rc<RefCell<Task>>]`) - the top level tasks
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<Rc<RefCell<Task>>>` - every task in the order the list shows them
+    pub fn flatten(self, tasks: &[Rc<RefCell<Task>>]) -> Vec<Rc<RefCell<Task>>> {
+        let mut result = Vec::new();
+        self.arrange(tasks).into_iter().for_each(|task| {
+            let children = self.flatten(&task.borrow().children);
+            result.push(task);
+            result.extend(children);
+        });
+        result
+    }
+
+    /// Flatten the rows of a task tree, like [`SortRule::flatten`] without the subtasks of
+    /// the folded tasks
+    ///
+    /// # Arguments
+    ///
+    /// - `self` ([`SortRule`])
+    /// - `tasks` (`&[Rc<RefCell<Task>>]`) - the top level tasks
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<Rc<RefCell<Task>>>` - the tasks with a row in the list, in order
+    pub fn flatten_shown(self, tasks: &[Rc<RefCell<Task>>]) -> Vec<Rc<RefCell<Task>>> {
+        let mut result = Vec::new();
+        self.arrange(tasks).into_iter().for_each(|task| {
+            let children = if task.borrow().expanded {
+                self.flatten_shown(&task.borrow().children)
+            } else {
+                Vec::new()
+            };
+            result.push(task);
+            result.extend(children);
+        });
+        result
+    }
+}
+
+impl fmt::Display for SortRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SortRule::Smart => "smart",
+            SortRule::Due => "due date",
+            SortRule::Status => "status",
+            SortRule::Urgency => "urgency",
+            SortRule::Name => "name",
+            SortRule::Created => "creation date",
+            SortRule::Manual => "manual",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TodoList {
     pub workspace: Uuid,
@@ -123,6 +903,9 @@ pub struct TodoList {
     pub current_task: Option<Rc<RefCell<Task>>>,
     #[serde(default)]
     pub state: ListState,
+    /// The rule the list is shown sorted by
+    #[serde(default)]
+    pub sort_rule: SortRule,
 }
 
 impl TodoList {
@@ -132,6 +915,7 @@ impl TodoList {
             tasks: Vec::new(),
             current_task: None,
             state: ListState::default(),
+            sort_rule: SortRule::default(),
         }
     }
 
@@ -141,7 +925,9 @@ impl TodoList {
 
     pub fn add_child_task(&mut self, task: Rc<RefCell<Task>>) {
         if let Some(ctask) = &self.current_task {
-            ctask.borrow_mut().add_child(task);
+            let mut ctask_mut = ctask.borrow_mut();
+            ctask_mut.add_child(task);
+            ctask_mut.expand();
         } else {
             self.add_task(task.clone());
             // self.current_task = Some(task);
@@ -167,6 +953,93 @@ impl TodoList {
         }
     }
 
+    /// Get the tasks with a row in the list, in order, see [`SortRule::flatten_shown`]
+    pub fn flattened(&self) -> Vec<Rc<RefCell<Task>>> {
+        self.sort_rule.flatten_shown(&self.tasks)
+    }
+
+    /// Fold the subtasks of the current task, or unfold them
+    ///
+    /// The row of the current task doesn't move, only the rows of its subtasks come and go.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<bool>` - whether the subtasks are now shown, None if no task is selected or
+    ///   it has no subtasks
+    pub fn toggle_current_task(&mut self) -> Option<bool> {
+        let task = self.current_task.clone()?;
+        let mut task_mut = task.borrow_mut();
+        if task_mut.children.is_empty() {
+            return None;
+        }
+        task_mut.expanded = !task_mut.expanded;
+        Some(task_mut.expanded)
+    }
+
+    /// Fold the tasks of the list, selecting the task hiding the current one if it's hidden
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`TodoList`])
+    /// - `fold` ([`Fold`]) - what to fold, see [`tree::fold`]
+    pub fn fold(&mut self, fold: Fold) {
+        let current = self.current_task.clone();
+        if let Some(shown) = tree::fold(&self.tasks, current.as_ref(), fold) {
+            self.select_task(&shown);
+        }
+    }
+
+    /// Show the list sorted by a rule and keep the selection on the current task
+    ///
+    /// The tasks stay in their manual order, only the order they are shown in changes.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`TodoList`])
+    /// - `rule` ([`SortRule`]) - the rule to show the list by
+    pub fn set_sort_rule(&mut self, rule: SortRule) {
+        self.sort_rule = rule;
+        if let Some(cur_task) = &self.current_task {
+            let index = self
+                .flattened()
+                .iter()
+                .position(|task| Rc::ptr_eq(task, cur_task));
+            self.state.select(index);
+        }
+    }
+
+    /// Make the order a rule shows the list in its manual order, and show it manually
+    ///
+    /// The tasks are sorted in place at every level by [`SortRule::rearrange`], so the order
+    /// stays when the rule changes again, and the selection stays on the current task.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`TodoList`])
+    /// - `rule` ([`SortRule`]) - the rule to sort the tasks by
+    pub fn keep_sorted(&mut self, rule: SortRule) {
+        rule.rearrange(&mut self.tasks);
+        self.set_sort_rule(SortRule::Manual);
+    }
+
+    /// Select a task of the list, expanding its ancestors so it is shown
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`TodoList`])
+    /// - `task` (`&Rc<RefCell<Task>>`) - the task to select, compared by pointer
+    pub fn select_task(&mut self, task: &Rc<RefCell<Task>>) {
+        tree::ancestors(&self.tasks, task)
+            .iter()
+            .for_each(|parent| parent.borrow_mut().expand());
+        let index = self
+            .flattened()
+            .iter()
+            .position(|other| Rc::ptr_eq(other, task));
+        self.state.select(index);
+        self.current_task = index.map(|_| task.clone());
+    }
+
     pub fn delete_item(cur_task: &Rc<RefCell<Task>>, tasks: &mut Vec<Rc<RefCell<Task>>>) {
         let mut res = None;
         for (i, task) in tasks.iter().enumerate() {
@@ -186,12 +1059,168 @@ impl TodoList {
     }
 
     pub fn delete_task(&mut self) {
-        if let Some(cur_task) = &self.current_task {
-            TodoList::delete_item(cur_task, &mut self.tasks);
+        if let Some(id) = self.current_task_id() {
+            self.remove_task(id);
         }
         self.current_task = None;
         self.state.select(None);
     }
+
+    /// The id of the current task, to find it again once a popup is closed
+    pub fn current_task_id(&self) -> Option<Uuid> {
+        self.current_task.as_ref().map(|task| task.borrow().id)
+    }
+
+    /// Find a task of the list by its id, subtasks included
+    pub fn task(&self, id: Uuid) -> Option<Rc<RefCell<Task>>> {
+        TodoWidget::get_flattened(&self.tasks)
+            .into_iter()
+            .find(|task| task.borrow().id == id)
+    }
+
+    /// Change a task of the list found by its id
+    ///
+    /// The task is found first, and borrowed mutably only while `change` runs, with no other
+    /// task of the list borrowed, so a task can be changed whichever of its ancestors or the
+    /// list is selected.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<R>` - what `change` returns, None if no task has the id
+    pub fn edit_task<R>(&self, id: Uuid, change: impl FnOnce(&mut Task) -> R) -> Option<R> {
+        let task = self.task(id)?;
+        let mut task = task.borrow_mut();
+        Some(change(&mut task))
+    }
+
+    /// Remove a task of the list found by its id, with its subtasks
+    ///
+    /// Only the level holding the task is borrowed, mutably, once the task is found. The
+    /// selection is cleared if it was the task or one of its subtasks.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Rc<RefCell<Task>>>` - the task removed, None if no task has the id
+    pub fn remove_task(&mut self, id: Uuid) -> Option<Rc<RefCell<Task>>> {
+        let task = self.task(id)?;
+        match tree::find_parent(&self.tasks, &task) {
+            Some(parent) => parent
+                .borrow_mut()
+                .children
+                .retain(|child| !Rc::ptr_eq(child, &task)),
+            None => self.tasks.retain(|other| !Rc::ptr_eq(other, &task)),
+        }
+        let selected_gone = self.current_task.as_ref().is_some_and(|current| {
+            TodoWidget::get_flattened(std::slice::from_ref(&task))
+                .iter()
+                .any(|gone| Rc::ptr_eq(gone, current))
+        });
+        if selected_gone {
+            self.set_current_task_none();
+        }
+        Some(task)
+    }
+}
+
+/// The number of cells of the progress gauge in the title of the todo list
+pub const GAUGE_CELLS: usize = 5;
+
+/// The width of the age column, see [`TodoWidget::age_span`]
+pub const AGE_WIDTH: usize = 4;
+
+/// The days left past which a due date is shown rather than counted when not configured, the
+/// last of the day ranges of [`TodoWidget::due_span`]
+pub const DEFAULT_ABSOLUTE_DUE_AFTER_DAYS: u32 = 14;
+
+/// The numbers drawn before the rows of the task list, like the line numbers of vim
+///
+/// The rows are the ones shown, the subtasks of a folded task have none and only the matching
+/// tasks are numbered while filtering, so a number is the row seen on screen.
+///
+/// # Variants
+///
+/// - `Off` - no numbers
+/// - `Absolute` - the number of each row, from 1
+/// - `Relative` - the distance of each row from the selected one, which shows its own number
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RowNumbers {
+    #[default]
+    Off,
+    Absolute,
+    Relative,
+}
+
+impl RowNumbers {
+    /// The numbering after this one, from `Off` to `Absolute` to `Relative` and back
+    pub fn next(self) -> Self {
+        match self {
+            RowNumbers::Off => RowNumbers::Absolute,
+            RowNumbers::Absolute => RowNumbers::Relative,
+            RowNumbers::Relative => RowNumbers::Off,
+        }
+    }
+
+    /// The number drawn before a row
+    ///
+    /// # Arguments
+    ///
+    /// - `self` ([`RowNumbers`])
+    /// - `row` (`usize`) - the row, from 0
+    /// - `selected` (`Option<usize>`) - the selected row
+    ///
+    /// # Returns
+    ///
+    /// - `Option<usize>` - the number, None without numbers
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::ui::todolistwidget::RowNumbers;
+    ///
+    /// assert_eq!(RowNumbers::Absolute.label(4, Some(1)), Some(5));
+    /// assert_eq!(RowNumbers::Relative.label(4, Some(1)), Some(3));
+    /// assert_eq!(RowNumbers::Relative.label(1, Some(1)), Some(2));
+    /// assert_eq!(RowNumbers::Off.label(4, Some(1)), None);
+    /// ```
+    pub fn label(self, row: usize, selected: Option<usize>) -> Option<usize> {
+        match self {
+            RowNumbers::Off => None,
+            RowNumbers::Relative if selected.is_some_and(|selected| selected != row) => {
+                selected.map(|selected| selected.abs_diff(row))
+            }
+            RowNumbers::Absolute | RowNumbers::Relative => Some(row + 1),
+        }
+    }
+}
+
+/// The optional parts of the task rows, set from the configuration
+///
+/// The default draws neither, as the rows were before the options.
+///
+/// # Fields
+///
+/// - `stale_after` (`u32`) - the days after which an open task is marked stale, 0 to never
+///   mark them, see [`Staleness`]
+/// - `show_age` (`bool`) - draw the age of the open tasks before their due date
+/// - `absolute_due_after` (`u32`) - the days left past which the due date is drawn rather
+///   than counted, 0 to always count them, see [`TodoWidget::due_span`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RowOptions {
+    pub stale_after: u32,
+    pub show_age: bool,
+    pub absolute_due_after: u32,
+}
+
+/// A due date being typed in the row of a task, see [`TodoWidget::due_edit`]
+///
+/// # Fields
+///
+/// - `task` (`Uuid`) - the id of the task whose row shows the input
+/// - `input` (`String`) - what was typed so far, parsed by [`parse::due`] once confirmed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DueEdit {
+    pub task: Uuid,
+    pub input: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -203,6 +1232,52 @@ pub struct TodoWidget {
     #[serde(skip)]
     #[serde(default)]
     pub search_string: String,
+
+    /// The panel the current todo list was entered from, used for the title and `h`/Esc
+    #[serde(skip)]
+    #[serde(default)]
+    pub origin: WorkspaceType,
+
+    /// The workspace of the current todo list, named in the breadcrumb of top level tasks
+    #[serde(skip)]
+    #[serde(default)]
+    pub workspace: Option<Rc<RefCell<Workspace>>>,
+
+    /// The id of the Inbox workspace, which tasks added without a list are captured into
+    #[serde(default)]
+    pub inbox: Option<Uuid>,
+
+    /// The days without a change after which an open task is stale, from the configuration
+    #[serde(skip)]
+    #[serde(default)]
+    pub stale_after_days: u32,
+
+    /// Whether the rows show the age of the open tasks, from the configuration
+    #[serde(skip)]
+    #[serde(default)]
+    pub show_age: bool,
+
+    /// The days left past which the due dates are shown rather than counted, from the
+    /// configuration
+    #[serde(skip)]
+    #[serde(default)]
+    pub absolute_due_after_days: u32,
+
+    /// The due date typed in the row of a task with `.`, drawn in place of its due span
+    #[serde(skip)]
+    #[serde(default)]
+    pub due_edit: Option<DueEdit>,
+
+    /// The numbers before the rows, switched with `L`
+    #[serde(skip)]
+    #[serde(default)]
+    pub row_numbers: RowNumbers,
+
+    /// The width inside the panel as last drawn, which a description typed longer than
+    /// overflows
+    #[serde(skip)]
+    #[serde(default)]
+    pub width: u16,
 }
 
 impl TodoWidget {
@@ -212,13 +1287,227 @@ impl TodoWidget {
             current_todolist: None,
             focused: false,
             search_string: String::new(),
+            origin: WorkspaceType::Normal,
+            workspace: None,
+            inbox: None,
+            stale_after_days: DEFAULT_STALE_AFTER_DAYS,
+            show_age: true,
+            absolute_due_after_days: DEFAULT_ABSOLUTE_DUE_AFTER_DAYS,
+            due_edit: None,
+            row_numbers: RowNumbers::Off,
+            width: 0,
         }
     }
 
+    /// The tasks with a row in the current todo list, in order
+    ///
+    /// These are the tasks shown, see [`TodoList::flattened`], or while filtering the tasks
+    /// matching the filter and all their subtasks.
+    pub fn shown_tasks(&self) -> Vec<Rc<RefCell<Task>>> {
+        let Some(list) = &self.current_todolist else {
+            return Vec::new();
+        };
+        let list = list.borrow();
+        if self.search_string.is_empty() {
+            return list.flattened();
+        }
+        let stale = self.staleness();
+        let targets: Vec<Rc<RefCell<Task>>> = list
+            .tasks
+            .iter()
+            .filter(|task| task.borrow().is_target(self.search_string.clone(), stale))
+            .cloned()
+            .collect();
+        list.sort_rule.flatten(&targets)
+    }
+
+    /// Read the row typed to go to, see [`TodoWidget::select_row`]
+    ///
+    /// # Arguments
+    ///
+    /// - `typed` (`&str`) - a row number from 1, or a distance from the selected row, `+3`
+    ///   down or `-2` up, as the relative [`RowNumbers`] show
+    /// - `selected` (`Option<usize>`) - the selected row, from 0
+    /// - `rows` (`usize`) - the number of rows
+    ///
+    /// # Returns
+    ///
+    /// - `Option<usize>` - the row from 0, None if it isn't a row of the list
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::ui::todolistwidget::TodoWidget;
+    ///
+    /// assert_eq!(TodoWidget::target_row("3", Some(0), 5), Some(2));
+    /// assert_eq!(TodoWidget::target_row("+2", Some(1), 5), Some(3));
+    /// assert_eq!(TodoWidget::target_row("-2", Some(1), 5), None);
+    /// assert_eq!(TodoWidget::target_row("6", None, 5), None);
+    /// ```
+    pub fn target_row(typed: &str, selected: Option<usize>, rows: usize) -> Option<usize> {
+        let typed = typed.trim();
+        let row = if let Some(down) = typed.strip_prefix('+') {
+            selected.unwrap_or(0).checked_add(down.parse().ok()?)?
+        } else if let Some(up) = typed.strip_prefix('-') {
+            selected.unwrap_or(0).checked_sub(up.parse().ok()?)?
+        } else {
+            typed.parse::<usize>().ok()?.checked_sub(1)?
+        };
+        (row < rows).then_some(row)
+    }
+
+    /// Select a row of the current todo list, see [`TodoWidget::shown_tasks`]
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - false if the list has no such row
+    pub fn select_row(&mut self, row: usize) -> bool {
+        let Some(task) = self.shown_tasks().get(row).cloned() else {
+            return false;
+        };
+        if let Some(list) = &self.current_todolist {
+            let mut list = list.borrow_mut();
+            list.state.select(Some(row));
+            list.current_task = Some(task);
+        }
+        true
+    }
+
+    /// Draw the rows of the task list, with their numbers before them, see [`RowNumbers`]
+    fn render_rows(
+        &self,
+        items: Vec<ListItem>,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ListState,
+    ) {
+        let rows = items.len();
+        let width = match self.row_numbers {
+            RowNumbers::Off => 0,
+            _ => rows.to_string().len() as u16 + 1,
+        };
+        let [gutter, list_area] =
+            Layout::horizontal([Constraint::Length(width), Constraint::Fill(1)]).areas(area);
+        let listwidget = List::new(items)
+            .highlight_symbol(glyphs::current().selected)
+            .highlight_style(if self.focused {
+                Style::new().bg(Color::Rgb(66, 80, 102))
+            } else {
+                Style::new()
+            });
+        StatefulWidget::render(listwidget, list_area, buf, state);
+
+        let selected = state.selected();
+        (state.offset()..rows)
+            .zip(gutter.rows())
+            .for_each(|(row, line)| {
+                if let Some(number) = self.row_numbers.label(row, selected) {
+                    let number = format!("{:>1$} ", number, width as usize - 1);
+                    if selected == Some(row) {
+                        Line::from(number.yellow()).render(line, buf);
+                    } else {
+                        Line::from(number.dark_gray()).render(line, buf);
+                    }
+                }
+            });
+    }
+
+    /// When an open task is stale today, see [`Staleness`]
+    pub fn staleness(&self) -> Staleness {
+        Staleness::new(&SystemClock, self.stale_after_days)
+    }
+
+    /// The optional parts of the rows, as configured
+    pub fn row_options(&self) -> RowOptions {
+        RowOptions {
+            stale_after: self.stale_after_days,
+            show_age: self.show_age,
+            absolute_due_after: self.absolute_due_after_days,
+        }
+    }
+
+    /// The tasks of the current todo list which match the filter themselves, subtasks included
+    ///
+    /// A parent shown only because one of its subtasks matches isn't part of them. There are
+    /// none while no filter is typed.
+    pub fn matching_tasks(&self) -> Vec<Rc<RefCell<Task>>> {
+        let Some(list) = &self.current_todolist else {
+            return Vec::new();
+        };
+        if self.search_string.is_empty() {
+            return Vec::new();
+        }
+        let stale = self.staleness();
+        let list = list.borrow();
+        list.sort_rule
+            .flatten(&list.tasks)
+            .into_iter()
+            .filter(|task| task.borrow().matches(&self.search_string, stale))
+            .collect()
+    }
+
+    /// Filter the current todo list and select the first task shown
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`TodoWidget`])
+    /// - `search_string` (`String`) - the words of the filter, see [`Task::is_target`]
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - whether a task is shown by the filter
+    pub fn apply_filter(&mut self, search_string: String) -> bool {
+        self.search_string = search_string;
+        let stale = self.staleness();
+        let Some(cur_list) = &self.current_todolist else {
+            return false;
+        };
+        let mut cur_list_mut = cur_list.borrow_mut();
+        cur_list_mut.state.select_first();
+        let rule = cur_list_mut.sort_rule;
+        let first = rule
+            .arrange(&cur_list_mut.tasks)
+            .into_iter()
+            .find(|task| task.borrow().is_target(self.search_string.clone(), stale));
+        if first.is_some() {
+            cur_list_mut.current_task = first;
+            return true;
+        }
+        false
+    }
+
+    /// Change the due date of every task matching the filter, see [`TodoWidget::matching_tasks`]
+    ///
+    /// # Arguments
+    ///
+    /// - `change` ([`DueChange`]) - the date to set or the shift to apply
+    ///
+    /// # Returns
+    ///
+    /// - `(usize, usize, usize)` - the number of tasks changed, of tasks skipped by a shift for
+    ///   having no due date, and of tasks it would move out of the calendar
+    pub fn set_due_matching(&self, change: DueChange) -> (usize, usize, usize) {
+        let (mut changed, mut undated, mut out) = (0, 0, 0);
+        for task in self.matching_tasks() {
+            let due = task.borrow().due;
+            match change.apply(due) {
+                Some(due) => {
+                    task.borrow_mut().set_due(due);
+                    changed += 1;
+                }
+                None if due.is_none() => undated += 1,
+                None => out += 1,
+            }
+        }
+        (changed, undated, out)
+    }
+
     pub fn find_max_tasks_len(task_list: &[Rc<RefCell<Task>>], dep: usize) -> usize {
         let mut max_len = 0;
         task_list.iter().for_each(|item| {
-            max_len = max_len.max(item.borrow().desc.len() + dep * 2_usize);
+            let badge_len = TodoWidget::count_badge(&item.borrow()).width()
+                + TodoWidget::notes_span(&item.borrow()).width();
+            max_len = max_len.max(item.borrow().desc.len() + badge_len + dep * 2_usize);
             if !item.borrow().children.is_empty() {
                 max_len = max_len.max(TodoWidget::find_max_tasks_len(
                     &item.borrow().children,
@@ -230,92 +1519,466 @@ impl TodoWidget {
         max_len
     }
 
+    /// Build the title of the block, with the progress of the current todo list
+    ///
+    /// The progress is a gauge of [`GAUGE_CELLS`] cells and a percentage, e.g.
+    /// ` <3> Todo List ▰▰▰▱▱ 60% `, computed by [`TaskStats`]. It is left out when there is
+    /// no list or nothing to do in it. While a filter is typed the title counts the tasks
+    /// matching it among all the tasks of the list, e.g. `filtered: 3/34`, see
+    /// [`TodoWidget::matching_tasks`].
+    ///
+    /// # Arguments
+    ///
+    /// - `&self` ([`TodoWidget`])
+    ///
+    /// # Returns
+    ///
+    /// - `Line<'static>` - the title
+    pub fn title(&self) -> Line<'static> {
+        let name = match self.origin {
+            WorkspaceType::Normal => " <3> Todo List ",
+            WorkspaceType::Archived => " <3> Todo List [archived] ",
+        };
+        let mut title = vec![name.blue()];
+        let stats = self
+            .current_todolist
+            .as_ref()
+            .map(|list| TaskStats::of(&list.borrow().tasks))
+            .unwrap_or_default();
+        if let Some(percent) = stats.percent() {
+            let glyphs = glyphs::current();
+            let full = percent * GAUGE_CELLS / 100;
+            title.push(
+                format!(
+                    "{}{}",
+                    glyphs.gauge_full.repeat(full),
+                    glyphs.gauge_empty.repeat(GAUGE_CELLS - full)
+                )
+                .light_green(),
+            );
+            title.push(format!(" {}% ", percent).blue());
+        }
+        let stale = self
+            .current_todolist
+            .as_ref()
+            .map(|list| self.staleness().count(&list.borrow().tasks))
+            .unwrap_or_default();
+        if stale > 0 {
+            title.push(format!("{} stale ", stale).dark_gray());
+        }
+        if let Some(list) = &self.current_todolist
+            && !self.search_string.is_empty()
+        {
+            let total = TodoWidget::get_flattened(&list.borrow().tasks).len();
+            let matching = self.matching_tasks().len();
+            title.push(format!("{} ", trf("filtered: {}/{}", &[&matching, &total])).yellow());
+        }
+        Line::from(title)
+    }
+
+    /// The style of the description of a task
+    ///
+    /// The first rule which applies gives the style:
+    ///
+    /// 1. the status: a finished task is light green, a deprecated one red and crossed out
+    /// 2. the color picked for the task, see [`Task::color`]
+    /// 3. the urgency, which is shown in its own column and leaves the description as is
+    /// 4. the default style
+    ///
+    /// A color which isn't a color name, e.g. from an edited data file, is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `task` ([`Task`]) - the task to style the description of
+    ///
+    /// # Returns
+    ///
+    /// - `Style` - the style of the description
+    pub fn desc_style(task: &Task) -> Style {
+        match &task.status {
+            TaskStatus::Finished => Style::new().fg(Color::LightGreen),
+            TaskStatus::Deprecated => Style::new()
+                .add_modifier(Modifier::CROSSED_OUT)
+                .fg(Color::Red),
+            TaskStatus::Todo | TaskStatus::InProcess => match task
+                .color
+                .as_deref()
+                .and_then(|color| color.parse::<Color>().ok())
+            {
+                Some(color) => Style::new().fg(color),
+                None => Style::default(),
+            },
+        }
+    }
+
+    /// Build the spans of the description of a task, in its style, see
+    /// [`TodoWidget::desc_style`], with the links underlined, see [`links::find`]
+    pub fn desc_spans(task: &Task) -> Vec<Span<'static>> {
+        let style = TodoWidget::desc_style(task);
+        let mut spans = Vec::new();
+        let mut cursor = 0;
+        for link in links::find(&task.desc) {
+            if link.start > cursor {
+                spans.push(Span::styled(
+                    task.desc[cursor..link.start].to_string(),
+                    style,
+                ));
+            }
+            spans.push(Span::styled(
+                task.desc[link.clone()].to_string(),
+                style.add_modifier(Modifier::UNDERLINED),
+            ));
+            cursor = link.end;
+        }
+        if cursor < task.desc.len() || spans.is_empty() {
+            spans.push(Span::styled(task.desc[cursor..].to_string(), style));
+        }
+        spans
+    }
+
+    /// Build the `(finished/total)` badge shown after the description of a parent task
+    ///
+    /// The badge counts all the subtasks, see [`TaskStats`], and turns green once they are
+    /// all finished. A folded task gets the collapsed glyph after it, its subtasks are hidden.
+    /// Tasks without subtasks to count, deprecated tasks among them, get an empty span.
+    ///
+    /// # Arguments
+    ///
+    /// - `task` ([`Task`]) - the task to build the badge of
+    ///
+    /// # Returns
+    ///
+    /// - `Span<'static>` - the badge, with a leading space
+    pub fn count_badge(task: &Task) -> Span<'static> {
+        if task.status == TaskStatus::Deprecated {
+            return Span::raw("");
+        }
+        let stats = TaskStats::of(&task.children);
+        if stats.total == 0 {
+            return Span::raw("");
+        }
+        let mut badge = format!(" ({}/{})", stats.finished, stats.total);
+        if !task.expanded {
+            badge = format!("{} {}", badge, glyphs::current().collapsed.trim_end());
+        }
+        if stats.is_complete() {
+            badge.light_green()
+        } else {
+            badge.dark_gray()
+        }
+    }
+
+    /// Build the marker after the description of a task with notes, see [`Task::notes`]
+    ///
+    /// # Returns
+    ///
+    /// - `Span<'static>` - the marker, with a leading space, empty for a task without notes
+    pub fn notes_span(task: &Task) -> Span<'static> {
+        if task.notes.is_empty() {
+            return Span::raw("");
+        }
+        format!(" {}", glyphs::current().notes).dark_gray()
+    }
+
+    /// Build the marker of the urgency of a task, shown between its status and description
+    ///
+    /// The marker is the glyph of the urgency between two spaces, or three spaces for a task
+    /// without an urgency, so the descriptions and the due dates of all the rows line up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::ui::{glyphs, todolistwidget::{Task, TodoWidget, Urgency}};
+    ///
+    /// glyphs::select_set(&glyphs::PLAIN);
+    /// let mut task = Task::new("ship".to_string(), None);
+    /// assert_eq!(TodoWidget::urgency_span(&task).content, "   ");
+    /// task.urgency = Some(Urgency::Critical);
+    /// assert_eq!(TodoWidget::urgency_span(&task).content, " # ");
+    /// ```
+    pub fn urgency_span(task: &Task) -> Span<'static> {
+        let glyphs = glyphs::current();
+        match &task.urgency {
+            Some(Urgency::Common) => format!(" {:1} ", glyphs.common).light_green(),
+            Some(Urgency::Important) => format!(" {:1} ", glyphs.important).light_blue(),
+            Some(Urgency::Critical) => format!(" {:1} ", glyphs.critical).light_red(),
+            None => "   ".into(),
+        }
+    }
+
+    /// Build the dim ` · stale 45d` span of a task untouched for too long, see [`Staleness`]
+    pub fn stale_span(task: &Task, stale: Staleness) -> Span<'static> {
+        match stale.days(task) {
+            Some(days) => format!(" · stale {}d", days).dark_gray(),
+            None => Span::raw(""),
+        }
+    }
+
+    /// Build the dim age of an open task, e.g. `  3d`, right aligned in [`AGE_WIDTH`] cells
+    ///
+    /// The age counts from the creation of the task, in days for the first week, then in
+    /// weeks, then in years. Tasks created today, finished, deprecated or saved before the
+    /// creation time was kept get blank cells, so the due column stays aligned.
+    ///
+    /// # Arguments
+    ///
+    /// - `task` ([`Task`]) - the task to build the age of
+    /// - `today` (`NaiveDate`) - the date to count to
+    ///
+    /// # Returns
+    ///
+    /// - `Span<'static>` - the age, [`AGE_WIDTH`] cells wide
+    pub fn age_span(task: &Task, today: NaiveDate) -> Span<'static> {
+        let days = task
+            .created
+            .filter(|_| matches!(task.status, TaskStatus::Todo | TaskStatus::InProcess))
+            .map(|created| (today - created.date()).num_days())
+            .unwrap_or_default();
+        let age = match days {
+            ..1 => String::new(),
+            1..7 => format!("{}d", days),
+            7..365 => format!("{}w", days / 7),
+            _ => format!("{}y", days / 365),
+        };
+        format!("{:>AGE_WIDTH$}", age).dark_gray()
+    }
+
+    /// Build the colored "n day left" span of a due date
+    ///
+    /// Only todo and in-process tasks get a span, the color goes from green for a far due
+    /// date to red for today, and overdue tasks are yellow. Past `absolute_after` days, see
+    /// [`DEFAULT_ABSOLUTE_DUE_AFTER_DAYS`], the date itself is shown in the color of its
+    /// range, and the overdue tasks show the date they were due too, e.g.
+    /// ` 3d over · was 2025-06-12 `. 0 keeps the day counts. A task with a reminder is yellow from its reminder day on, see
+    /// [`Task::reminding`], with the days left counted.
+    ///
+    /// # Arguments
+    ///
+    /// - `due` (`NaiveDate`) - the due date of the task
+    /// - `status` (`&TaskStatus`) - the status of the task
+    /// - `clock` (`&dyn Clock`) - the source of today's date
+    /// - `absolute_after` (`u32`) - the days left past which the date is shown, 0 for never
+    /// - `remind` (`Option<u8>`) - the days before the due date the task is reminded of
+    ///
+    /// # Returns
+    ///
+    /// - `Span<'static>` - the span to show after the description, empty if not relevant
+    pub fn due_span(
+        due: NaiveDate,
+        status: &TaskStatus,
+        clock: &dyn Clock,
+        absolute_after: u32,
+        remind: Option<u8>,
+    ) -> Span<'static> {
+        let num_days = (due - clock.today()).num_days();
+        let absolute = absolute_after > 0;
+        let reminded = remind.is_some_and(|remind| num_days <= i64::from(remind));
+        if !matches!(status, TaskStatus::Todo | TaskStatus::InProcess) {
+            return Span::raw("");
+        }
+        let color = match num_days {
+            ..0 => Color::Yellow,
+            0 => Color::Red,
+            1 => Color::LightRed,
+            _ if reminded => Color::Yellow,
+            2..4 => Color::Yellow,
+            4..7 => Color::LightBlue,
+            7.. => Color::LightGreen,
+        };
+        // the date replaces the count past the threshold, whichever range the day is in
+        let text = match num_days {
+            ..0 if absolute => trf("{}d over · was {}", &[&num_days.abs(), &due]),
+            ..0 => format!("{} day over !", num_days.abs()),
+            days if absolute && !reminded && days > i64::from(absolute_after) => due.to_string(),
+            days => format!("{} day left !", days),
+        };
+        format!(" {} ", text).set_style(Style::new().fg(color))
+    }
+
+    /// Build the bell after the due date of a task with a reminder, yellow while it's reminded
+    /// of, see [`Task::reminding`]
+    ///
+    /// # Arguments
+    ///
+    /// - `task` (`&Task`) - the task
+    /// - `today` (`NaiveDate`) - the date to check the reminder against
+    ///
+    /// # Returns
+    ///
+    /// - `Span<'static>` - the bell, empty without a reminder or for a done task
+    pub fn reminder_span(task: &Task, today: NaiveDate) -> Span<'static> {
+        let open = matches!(task.status, TaskStatus::Todo | TaskStatus::InProcess);
+        if !open || task.remind_on().is_none() {
+            return Span::raw("");
+        }
+        let bell = format!("{} ", glyphs::current().reminder);
+        if task.reminding(today) {
+            bell.yellow()
+        } else {
+            bell.dark_gray()
+        }
+    }
+
+    /// Split a text into spans with the words of a search highlighted, the way the filter shows
+    /// the matching descriptions
+    ///
+    /// # Arguments
+    ///
+    /// - `text` (`&str`) - the text shown
+    /// - `search` (`&str`) - the words searched, apart by spaces
+    /// - `style` (`Style`) - the style of the parts which don't match
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Vec<Span<'static>>>` - the spans, None if no word of the search is in the text
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui::style::Style;
+    /// use todo::app::ui::todolistwidget::TodoWidget;
+    ///
+    /// let spans = TodoWidget::highlight_matches("ship release", "rel", Style::new()).unwrap();
+    /// let parts: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+    /// assert_eq!(parts, ["ship ", "rel", "ease"]);
+    /// assert!(TodoWidget::highlight_matches("ship", "plan", Style::new()).is_none());
+    /// ```
+    pub fn highlight_matches(text: &str, search: &str, style: Style) -> Option<Vec<Span<'static>>> {
+        let mut found: Vec<(usize, &str)> = search
+            .split(" ")
+            .filter(|word| !word.is_empty())
+            .flat_map(|word| text.match_indices(word))
+            .collect();
+        if found.is_empty() {
+            return None;
+        }
+        found.sort_by_key(|a| a.0);
+        let mut merged: Vec<(usize, usize)> = Vec::new();
+        for (idx, s) in found {
+            if let Some(last) = merged.last_mut()
+                && idx < last.1
+            {
+                last.1 = last.1.max(idx + s.len());
+                continue;
+            }
+            merged.push((idx, idx + s.len()));
+        }
+        let mut spans = Vec::new();
+        let mut cursor = 0;
+        for (s, e) in merged {
+            if s > cursor {
+                spans.push(text[cursor..s].to_owned().set_style(style));
+            }
+            spans.push(
+                text[s..e]
+                    .to_owned()
+                    .light_yellow()
+                    .add_modifier(Modifier::ITALIC),
+            );
+            cursor = e;
+        }
+        if cursor < text.len() {
+            spans.push(text[cursor..].to_owned().set_style(style));
+        }
+        Some(spans)
+    }
+
+    /// Build the span of a due date being typed, which takes the place of the due span
+    ///
+    /// # Arguments
+    ///
+    /// - `edit` (`&DueEdit`) - the edit in progress
+    ///
+    /// # Returns
+    ///
+    /// - `Span<'static>` - the input with a cursor, or the accepted forms while it is empty
+    pub fn due_edit_span(edit: &DueEdit) -> Span<'static> {
+        if edit.input.is_empty() {
+            return " due: +3d, fri, none _ ".dark_gray().italic();
+        }
+        format!(" due: {}_ ", edit.input)
+            .light_yellow()
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    }
+
     pub fn get_task_list_item<'a>(
         task_list: &[Rc<RefCell<Task>>],
+        rule: SortRule,
         dep: usize,
         max_desc_len: usize,
+        clock: &dyn Clock,
+        options: RowOptions,
+        due_edit: Option<&DueEdit>,
     ) -> Vec<ListItem<'a>> {
         let mut task_item = Vec::<ListItem>::new();
-        task_list.iter().for_each(|item| {
+        let stale = Staleness::new(clock, options.stale_after);
+        rule.arrange(task_list).iter().for_each(|item| {
             let task = item.borrow();
             let desc = task.desc.to_owned();
+            let glyphs = glyphs::current();
             let prefix = match &task.status {
-                TaskStatus::Todo => "▢".white(),
-                TaskStatus::InProcess => "▣".blue(),
-                TaskStatus::Finished => "✓".green(),
-                TaskStatus::Deprecated => "".red(),
+                TaskStatus::Todo => glyphs.todo.white(),
+                TaskStatus::InProcess => glyphs.in_process.blue(),
+                TaskStatus::Finished => glyphs.finished.green(),
+                TaskStatus::Deprecated => glyphs.deprecated.red(),
             };
-            let urgency = if let Some(urgen) = &task.urgency {
-                match urgen {
-                    Urgency::Common => format!(" {:1} ", "󰌶").light_green(),
-                    Urgency::Important => format!(" {:1} ", "󰋽").light_blue(),
-                    Urgency::Critical => format!(" {:1} ", "󰀪").light_red(),
-                }
+            let urgency = TodoWidget::urgency_span(&task);
+
+            let due_span = match (due_edit.filter(|edit| edit.task == task.id), task.due) {
+                (Some(edit), _) => TodoWidget::due_edit_span(edit),
+                (None, Some(due)) => TodoWidget::due_span(
+                    due,
+                    &task.status,
+                    clock,
+                    options.absolute_due_after,
+                    task.remind_days_before,
+                ),
+                (None, None) => Span::raw(""),
+            };
+            let stale_span = TodoWidget::stale_span(&task, stale);
+            let age_span = if options.show_age {
+                TodoWidget::age_span(&task, stale.today)
             } else {
-                "   ".into()
+                Span::raw("")
             };
-
-            let mut due_span = Span::raw("");
-            if let Some(due) = item.borrow().due {
-                let delta = due - Local::now().date_naive();
-                let num_days = delta.num_days();
-                match &task.status {
-                    TaskStatus::Todo | TaskStatus::InProcess => {
-                        due_span = match num_days {
-                            ..0 => format!(" {} day over ! ", num_days.abs())
-                                .to_string()
-                                .set_style(Style::new().fg(Color::Yellow)),
-                            0 => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::Red)),
-                            1 => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::LightRed)),
-                            2..4 => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::Yellow)),
-                            4..7 => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::LightBlue)),
-                            7.. => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::LightGreen)),
-                        };
-                    }
-                    _ => {}
-                }
-            }
-            let padding_len = max_desc_len - desc.len() - dep * 2 + 1;
-            let it = ListItem::new(Line::from(vec![
-                prefix,
-                urgency,
-                "  ".repeat(dep).into(),
-                //     .set_style(match &task.status {
-                //     // TaskStatus::Finished => Style::new()
-                //     //     .add_modifier(Modifier::CROSSED_OUT)
-                //     //     .fg(Color::LightGreen),
-                //     TaskStatus::Deprecated => Style::new()
-                //         .add_modifier(Modifier::CROSSED_OUT)
-                //         .fg(Color::Red),
-                //     _ => Style::default(),
-                // }),
-                desc.set_style(match &task.status {
-                    TaskStatus::Finished => Style::new()
-                        // .add_modifier(Modifier::CROSSED_OUT)
-                        .fg(Color::LightGreen),
-                    TaskStatus::Deprecated => Style::new()
-                        .add_modifier(Modifier::CROSSED_OUT)
-                        .fg(Color::Red),
-                    _ => Style::default(),
-                }),
+            let badge = TodoWidget::count_badge(&task);
+            let notes = TodoWidget::notes_span(&task);
+            let padding_len =
+                max_desc_len - desc.len() - badge.width() - notes.width() - dep * 2 + 1;
+            let mut contents = vec![prefix, urgency, "  ".repeat(dep).into()];
+            //     .set_style(match &task.status {
+            //     // TaskStatus::Finished => Style::new()
+            //     //     .add_modifier(Modifier::CROSSED_OUT)
+            //     //     .fg(Color::LightGreen),
+            //     TaskStatus::Deprecated => Style::new()
+            //         .add_modifier(Modifier::CROSSED_OUT)
+            //         .fg(Color::Red),
+            //     _ => Style::default(),
+            // }),
+            contents.extend(TodoWidget::desc_spans(&task));
+            contents.extend(vec![
+                badge,
+                notes,
                 format!("{:padding_len$}", " ").into(),
+                age_span,
                 "    ".into(),
                 due_span,
-            ]));
+                TodoWidget::reminder_span(&task, clock.today()),
+                stale_span,
+            ]);
+            let it = ListItem::new(Line::from(contents));
             task_item.push(it);
 
             if task.expanded {
-                let child = TodoWidget::get_task_list_item(&task.children, dep + 1, max_desc_len);
+                let child = TodoWidget::get_task_list_item(
+                    &task.children,
+                    rule,
+                    dep + 1,
+                    max_desc_len,
+                    clock,
+                    options,
+                    due_edit,
+                );
                 task_item.extend(child);
             }
         });
@@ -326,143 +1989,66 @@ impl TodoWidget {
     pub fn get_search_list_item<'a>(
         search_string: String,
         task_list: &[Rc<RefCell<Task>>],
+        rule: SortRule,
         dep: usize,
         max_desc_len: usize,
+        clock: &dyn Clock,
+        options: RowOptions,
     ) -> Vec<ListItem<'a>> {
         let mut task_item = Vec::<ListItem>::new();
-        task_list.iter().for_each(|item| {
+        let stale = Staleness::new(clock, options.stale_after);
+        rule.arrange(task_list).iter().for_each(|item| {
             let task = item.borrow();
             let desc = task.desc.to_owned();
+            let glyphs = glyphs::current();
             let prefix = match &task.status {
-                TaskStatus::Todo => "▢".white(),
-                TaskStatus::InProcess => "▣".blue(),
-                TaskStatus::Finished => "✓".green(),
-                TaskStatus::Deprecated => "".red(),
-            };
-
-            let urgency = if let Some(urgen) = &task.urgency {
-                match urgen {
-                    Urgency::Common => format!(" {:1} ", "󰌶").light_green(),
-                    Urgency::Important => format!(" {:1} ", "󰋽").light_blue(),
-                    Urgency::Critical => format!(" {:1} ", "󰀪").light_red(),
-                }
-            } else {
-                "   ".into()
+                TaskStatus::Todo => glyphs.todo.white(),
+                TaskStatus::InProcess => glyphs.in_process.blue(),
+                TaskStatus::Finished => glyphs.finished.green(),
+                TaskStatus::Deprecated => glyphs.deprecated.red(),
             };
 
+            let urgency = TodoWidget::urgency_span(&task);
             let mut contents = vec![prefix, urgency, "  ".repeat(dep).into()];
 
-            let mut due_span = Span::raw("");
-            if let Some(due) = item.borrow().due {
-                let delta = due - Local::now().date_naive();
-                let num_days = delta.num_days();
-                match &task.status {
-                    TaskStatus::Todo | TaskStatus::InProcess => {
-                        due_span = match num_days {
-                            ..0 => format!(" {} day over ! ", num_days.abs())
-                                .to_string()
-                                .set_style(Style::new().fg(Color::Yellow)),
-                            0 => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::Red)),
-                            1 => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::LightRed)),
-                            2..4 => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::Yellow)),
-                            4..7 => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::LightBlue)),
-                            7.. => format!(" {} day left ! ", num_days)
-                                .to_string()
-                                .set_style(Style::new().fg(Color::LightGreen)),
-                        };
-                    }
-                    _ => {}
-                }
-            }
-            let padding_len = max_desc_len - desc.len() - dep * 2 + 1;
-
-            if !search_string.is_empty() {
-                let search_strings = search_string.split(" ");
-                let mut idx_str: Vec<(usize, &str)> = Vec::new();
-                search_strings.for_each(|s| {
-                    let mut v: Vec<(usize, &str)> = desc.match_indices(s).collect();
-                    idx_str.append(&mut v);
-                });
-                if !idx_str.is_empty() {
-                    idx_str.sort_by(|a, b| a.0.cmp(&b.0));
-                    let mut idx_str_merged: Vec<(usize, usize)> = Vec::new();
-                    for (idx, s) in idx_str {
-                        if let Some(last) = idx_str_merged.last_mut()
-                            && idx < last.1
-                        {
-                            last.1 = last.1.max(idx + s.len());
-                            continue;
-                        }
-                        idx_str_merged.push((idx, idx + s.len()));
-                    }
-                    let mut cursor = 0;
-                    for (s, e) in idx_str_merged {
-                        if s > cursor {
-                            contents.push(
-                                desc[cursor..s].to_owned().set_style(match &task.status {
-                                    TaskStatus::Finished => Style::new().fg(Color::LightGreen),
-                                    TaskStatus::Deprecated => Style::new()
-                                        .add_modifier(Modifier::CROSSED_OUT)
-                                        .fg(Color::Red),
-                                    _ => Style::default(),
-                                }),
-                            );
-                        }
-                        contents.push(
-                            desc[s..e]
-                                .to_owned()
-                                .light_yellow()
-                                .add_modifier(Modifier::ITALIC),
-                        );
-                        cursor = e;
-                    }
-                    if cursor < desc.len() {
-                        contents.push(
-                            desc[cursor..].to_owned().set_style(match &task.status {
-                                TaskStatus::Finished => Style::new()
-                                    // .add_modifier(Modifier::CROSSED_OUT)
-                                    .fg(Color::LightGreen),
-                                TaskStatus::Deprecated => Style::new()
-                                    .add_modifier(Modifier::CROSSED_OUT)
-                                    .fg(Color::Red),
-                                _ => Style::default(),
-                            }),
-                        );
-                    }
-                } else {
-                    contents.push(
-                        desc.set_style(match &task.status {
-                            TaskStatus::Finished => Style::new().fg(Color::LightGreen),
-                            TaskStatus::Deprecated => Style::new()
-                                .add_modifier(Modifier::CROSSED_OUT)
-                                .fg(Color::Red),
-                            _ => Style::default(),
-                        }),
-                    );
-                }
+            let due_span = match task.due {
+                Some(due) => TodoWidget::due_span(
+                    due,
+                    &task.status,
+                    clock,
+                    options.absolute_due_after,
+                    task.remind_days_before,
+                ),
+                None => Span::raw(""),
+            };
+            let stale_span = TodoWidget::stale_span(&task, stale);
+            let age_span = if options.show_age {
+                TodoWidget::age_span(&task, stale.today)
             } else {
-                contents.push(
-                    desc.set_style(match &task.status {
-                        TaskStatus::Finished => Style::new().fg(Color::LightGreen),
-                        TaskStatus::Deprecated => Style::new()
-                            .add_modifier(Modifier::CROSSED_OUT)
-                            .fg(Color::Red),
-                        _ => Style::default(),
-                    }),
-                );
+                Span::raw("")
+            };
+            let badge = TodoWidget::count_badge(&task);
+            let notes = TodoWidget::notes_span(&task);
+            let padding_len =
+                max_desc_len - desc.len() - badge.width() - notes.width() - dep * 2 + 1;
+
+            match TodoWidget::highlight_matches(
+                &desc,
+                &search_string,
+                TodoWidget::desc_style(&task),
+            ) {
+                Some(spans) => contents.extend(spans),
+                None => contents.extend(TodoWidget::desc_spans(&task)),
             }
             contents.extend(vec![
+                badge,
+                notes,
                 format!("{:padding_len$}", " ").into(),
+                age_span,
                 "    ".into(),
                 due_span,
+                TodoWidget::reminder_span(&task, clock.today()),
+                stale_span,
             ]);
 
             let it = ListItem::new(Line::from(contents.clone()));
@@ -470,8 +2056,11 @@ impl TodoWidget {
             let child = TodoWidget::get_search_list_item(
                 search_string.to_owned(),
                 &task.children,
+                rule,
                 dep + 1,
                 max_desc_len,
+                clock,
+                options,
             );
             task_item.extend(child);
 
@@ -499,18 +2088,256 @@ impl TodoWidget {
         task_item
     }
 
+    /// Display the todo list of the given workspace
+    ///
+    /// When there is no workspace (or it has no todo list) the displayed list is cleared,
+    /// so the tasks of a previously selected workspace can't reappear.
+    /// Find the todo list of a workspace
+    pub fn list_of(&self, ws_id: Uuid) -> Option<Rc<RefCell<TodoList>>> {
+        self.todolists
+            .iter()
+            .find(|&l| l.borrow().workspace == ws_id)
+            .cloned()
+    }
+
+    /// Gather the vitals of a workspace, the todo lists of its sub workspaces included
+    ///
+    /// # Arguments
+    ///
+    /// - `workspace` (`&Rc<RefCell<Workspace>>`) - the top of the workspace tree
+    /// - `clock` (`&dyn Clock`) - the source of today's date, for the overdue tasks
+    ///
+    /// # Returns
+    ///
+    /// - [`WorkspaceStats`] - the counts and dates of the whole tree
+    pub fn workspace_stats(
+        &self,
+        workspace: &Rc<RefCell<Workspace>>,
+        clock: &dyn Clock,
+    ) -> WorkspaceStats {
+        let today = clock.today();
+        let workspaces = WorkspaceWidget::get_flattened(std::slice::from_ref(workspace));
+        let mut stats = WorkspaceStats {
+            sub_workspaces: workspaces.len() - 1,
+            ..Default::default()
+        };
+        for list in workspaces
+            .iter()
+            .filter_map(|ws| self.list_of(ws.borrow().id))
+        {
+            let list = list.borrow();
+            let tasks = TaskStats::of(&list.tasks);
+            stats.tasks.finished += tasks.finished;
+            stats.tasks.total += tasks.total;
+            for task in TodoWidget::get_flattened(&list.tasks) {
+                stats.last_activity = stats.last_activity.max(task.borrow().last_touched());
+            }
+            for task in TaskStats::open_tasks(&list.tasks) {
+                let task = task.borrow();
+                if task.status == TaskStatus::InProcess {
+                    stats.in_process += 1;
+                }
+                match task.due {
+                    Some(due) if due < today => stats.overdue += 1,
+                    Some(due) => {
+                        stats.next_due = Some(stats.next_due.map_or(due, |next| next.min(due)))
+                    }
+                    None => {}
+                }
+            }
+        }
+        stats
+    }
+
+    /// Count the open tasks of a workspace and of all its sub workspaces
+    ///
+    /// # Arguments
+    ///
+    /// - `workspace` (`&Rc<RefCell<Workspace>>`) - the top of the workspace tree
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - the tasks neither finished nor deprecated, see [`TaskStats::open`]
+    pub fn open_tasks(&self, workspace: &Rc<RefCell<Workspace>>) -> usize {
+        WorkspaceWidget::get_flattened(std::slice::from_ref(workspace))
+            .iter()
+            .filter_map(|ws| self.list_of(ws.borrow().id))
+            .map(|list| TaskStats::of(&list.borrow().tasks).open())
+            .sum()
+    }
+
+    /// Bucket the open tasks with a due date by day, subtasks included, see
+    /// [`TaskStats::open_tasks`]
+    ///
+    /// Only the todo lists of the given workspaces are read, so the archived workspaces are
+    /// left out by passing the active ones.
+    ///
+    /// # Arguments
+    ///
+    /// - `workspaces` (`&[Rc<RefCell<Workspace>>]`) - the workspace trees to read the tasks of
+    ///
+    /// # Returns
+    ///
+    /// - `BTreeMap<NaiveDate, Vec<DueTask>>` - the todo and in process tasks due each day, in
+    ///   the order of the workspaces
+    pub fn due_days(
+        &self,
+        workspaces: &[Rc<RefCell<Workspace>>],
+    ) -> BTreeMap<NaiveDate, Vec<DueTask>> {
+        let mut days: BTreeMap<NaiveDate, Vec<_>> = BTreeMap::new();
+        for ws in WorkspaceWidget::get_flattened(workspaces) {
+            let ws_id = ws.borrow().id;
+            let Some(list) = self.list_of(ws_id) else {
+                continue;
+            };
+            for task in TaskStats::open_tasks(&list.borrow().tasks) {
+                let due = task.borrow().due;
+                if let Some(due) = due {
+                    days.entry(due).or_default().push((ws_id, task));
+                }
+            }
+        }
+        days
+    }
+
+    /// Raise the urgency of the overdue open tasks, see [`EscalationPolicy`]
+    ///
+    /// Escalating doesn't count as a change of the task, so it doesn't reset its staleness.
+    ///
+    /// # Arguments
+    ///
+    /// - `workspaces` (`&[Rc<RefCell<Workspace>>]`) - the workspace trees to escalate the
+    ///   tasks of, the active ones
+    /// - `policy` ([`EscalationPolicy`]) - when to escalate
+    /// - `clock` (`&dyn Clock`) - the source of today's date
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<(String, Urgency)>` - the description and the new urgency of the tasks raised
+    pub fn escalate_overdue(
+        &self,
+        workspaces: &[Rc<RefCell<Workspace>>],
+        policy: EscalationPolicy,
+        clock: &dyn Clock,
+    ) -> Vec<(String, Urgency)> {
+        let today = clock.today();
+        self.due_days(workspaces)
+            .range(..today)
+            .flat_map(|(_, tasks)| tasks)
+            .filter_map(|(_, task)| {
+                let mut task = task.borrow_mut();
+                let urgency = policy.escalate(&mut task, today)?;
+                Some((task.desc.clone(), urgency))
+            })
+            .collect()
+    }
+
+    /// Whether the current todo list is the Inbox
+    pub fn is_inbox(&self) -> bool {
+        self.current_todolist
+            .as_ref()
+            .is_some_and(|list| Some(list.borrow().workspace) == self.inbox)
+    }
+
     pub fn change_current_list(&mut self, workspace: &Option<Rc<RefCell<Workspace>>>) {
+        self.current_todolist = None;
+        self.workspace = workspace.clone();
         if let Some(cws) = workspace {
-            let ws_id = cws.borrow().id.to_string();
-            let target = self
-                .todolists
-                .iter()
-                .find(|&l| l.borrow().workspace.to_string() == ws_id)
-                .unwrap()
-                .to_owned();
-            target.borrow_mut().refresh_current_task();
-            self.current_todolist = Some(target);
+            let target = self.list_of(cws.borrow().id);
+            if let Some(target) = target {
+                target.borrow_mut().refresh_current_task();
+                self.current_todolist = Some(target);
+            }
+        }
+    }
+
+    /// Move the selection to the parent, the first child or a sibling of the current task
+    ///
+    /// Nothing happens while the list is filtered, as the shown tree is a different one.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`TodoWidget`])
+    /// - `to` ([`Jump`]) - where to move
+    pub fn jump(&mut self, to: Jump) {
+        if !self.search_string.is_empty() {
+            return;
         }
+        if let Some(cur_list) = &self.current_todolist {
+            let mut cur_list_mut = cur_list.borrow_mut();
+            if let Some(cur_task) = cur_list_mut.current_task.clone()
+                && let Some(target) =
+                    tree::jump_arranged(&cur_list_mut.tasks, &cur_task, to, |tasks| {
+                        cur_list_mut.sort_rule.arrange(tasks)
+                    })
+            {
+                let index = cur_list_mut
+                    .flattened()
+                    .iter()
+                    .position(|task| Rc::ptr_eq(task, &target));
+                cur_list_mut.state.select(index);
+                cur_list_mut.current_task = Some(target);
+            }
+        }
+    }
+
+    /// Get the breadcrumb of the current task, `Parent ▸ Child ▸ Selected task`
+    ///
+    /// A top level task, or no selection at all, shows the name of the workspace instead. A
+    /// breadcrumb wider than the header is cut from the left so the selected task stays visible.
+    ///
+    /// # Arguments
+    ///
+    /// - `&self` ([`TodoWidget`])
+    /// - `width` (`usize`) - the width of the header in columns
+    ///
+    /// # Returns
+    ///
+    /// - `String` - the breadcrumb, empty without a current todo list
+    pub fn breadcrumb(&self, width: usize) -> String {
+        let Some(todolist) = &self.current_todolist else {
+            return String::new();
+        };
+        let todolist = todolist.borrow();
+        let ws_name = self
+            .workspace
+            .as_ref()
+            .map(|ws| ws.borrow().desc.clone())
+            .unwrap_or_default();
+        let crumb = match &todolist.current_task {
+            Some(cur_task) => {
+                let chain = tree::ancestors(&todolist.tasks, cur_task);
+                if chain.is_empty() {
+                    ws_name
+                } else {
+                    chain
+                        .iter()
+                        .chain(std::iter::once(cur_task))
+                        .map(|task| task.borrow().desc.clone())
+                        .collect::<Vec<_>>()
+                        .join(glyphs::current().breadcrumb)
+                }
+            }
+            None => ws_name,
+        };
+        truncate_left(&crumb, width)
+    }
+
+    /// Render the block with the breadcrumb on its first line
+    ///
+    /// # Returns
+    ///
+    /// - `Rect` - the area left for the task list
+    fn render_block(&self, block: Block, area: Rect, buf: &mut Buffer) -> Rect {
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [header, list] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+        Line::from(self.breadcrumb(header.width as usize))
+            .dark_gray()
+            .italic()
+            .render(header, buf);
+        list
     }
 
     pub fn set_cur_task_none(&mut self) {
@@ -534,6 +2361,7 @@ impl TodoWidget {
             self.todolists.remove(i);
         }
         self.current_todolist = None;
+        self.workspace = None;
     }
 }
 
@@ -548,73 +2376,114 @@ impl Widget for &mut TodoWidget {
     where
         Self: Sized,
     {
-        let block = Block::bordered()
-            .title(" <3> Todo List ".blue())
+        let block = glyphs::block()
+            .title(self.title())
             .border_style(if self.focused {
                 Style::new().fg(Color::Blue)
             } else {
                 Style::new().fg(Color::DarkGray)
             })
             .padding(Padding::uniform(1));
+        self.width = block.inner(area).width;
 
-        let todo_listitems = Vec::<ListItem>::new();
         if let Some(todolist) = &self.current_todolist {
-            if self.search_string.is_empty() {
-                let tasks = todolist.borrow().tasks.to_owned();
+            if todolist.borrow().tasks.is_empty() && self.is_inbox() {
+                Ui::render_placeholder("Inbox zero 🎉", block, area, buf);
+            } else if todolist.borrow().tasks.is_empty() {
+                Ui::render_placeholder("press a to add a task", block, area, buf);
+            } else if self.search_string.is_empty() {
+                let (tasks, rule) = {
+                    let todolist = todolist.borrow();
+                    (todolist.tasks.to_owned(), todolist.sort_rule)
+                };
                 let max_desc_len = TodoWidget::find_max_tasks_len(&tasks, 1);
-                let task_list = TodoWidget::get_task_list_item(&tasks, 0, max_desc_len);
-                let listwidget =
-                    List::new(task_list)
-                        .block(block)
-                        .highlight_style(if self.focused {
-                            Style::new().bg(Color::Rgb(66, 80, 102))
-                        } else {
-                            Style::new()
-                        });
+                let task_list = TodoWidget::get_task_list_item(
+                    &tasks,
+                    rule,
+                    0,
+                    max_desc_len,
+                    &SystemClock,
+                    self.row_options(),
+                    self.due_edit.as_ref(),
+                );
+                let list_area = self.render_block(block, area, buf);
                 let state = &mut todolist.borrow_mut().state;
 
-                StatefulWidget::render(listwidget, area, buf, state);
+                self.render_rows(task_list, list_area, buf, state);
             } else {
                 let mut tar_list = Vec::new();
 
                 todolist.borrow().tasks.iter().for_each(|task| {
-                    if task.borrow().is_target(self.search_string.clone()) {
+                    if task
+                        .borrow()
+                        .is_target(self.search_string.clone(), self.staleness())
+                    {
                         tar_list.push(task.to_owned());
                     }
                 });
+                // an empty box would look like the tasks are gone
+                if tar_list.is_empty() {
+                    let hint = trf(
+                        "No tasks match '{}' — press Esc to clear the filter",
+                        &[&self.search_string],
+                    );
+                    Ui::render_placeholder(&hint, block, area, buf);
+                    return;
+                }
                 let max_desc_len = TodoWidget::find_max_tasks_len(&tar_list, 1);
                 let task_list = TodoWidget::get_search_list_item(
                     self.search_string.clone(),
                     &tar_list,
+                    todolist.borrow().sort_rule,
                     0,
                     max_desc_len,
+                    &SystemClock,
+                    self.row_options(),
                 );
-                let listwidget =
-                    List::new(task_list)
-                        .block(block)
-                        .highlight_style(if self.focused {
-                            Style::new().bg(Color::Rgb(66, 80, 102))
-                        } else {
-                            Style::new()
-                        });
+                let list_area = self.render_block(block, area, buf);
                 let state = &mut todolist.borrow_mut().state;
 
-                StatefulWidget::render(listwidget, area, buf, state);
+                self.render_rows(task_list, list_area, buf, state);
             }
+        } else if self.todolists.is_empty() {
+            Ui::render_placeholder(
+                "select a workspace, then press a to add a task",
+                block,
+                area,
+                buf,
+            );
         } else {
-            let listwidget =
-                List::new(todo_listitems)
-                    .block(block)
-                    .highlight_style(if self.focused {
-                        Style::new().bg(Color::Rgb(80, 100, 109))
-                    } else {
-                        Style::new()
-                    });
-            Widget::render(listwidget, area, buf);
+            Ui::render_placeholder(
+                "No workspace selected — press 1 and Enter to choose one",
+                block,
+                area,
+                buf,
+            );
         }
     }
 }
 
+/// Cut a text from the left to fit a width, marking the cut with `…`
+fn truncate_left(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut used = 1;
+    let mut kept = Vec::new();
+    for c in text.chars().rev() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > width {
+            break;
+        }
+        used += char_width;
+        kept.push(c);
+    }
+    std::iter::once('…').chain(kept.into_iter().rev()).collect()
+}
+
 impl SelectAction<Task> for TodoWidget {
     fn get_selected_bf(
         &mut self,
@@ -625,15 +2494,16 @@ impl SelectAction<Task> for TodoWidget {
     ) -> Option<Rc<RefCell<Task>>> {
         if let Some(cur_list) = &self.current_todolist {
             if self.search_string.is_empty() {
-                let task_list = TodoWidget::get_flattened(&cur_list.borrow().tasks);
+                let task_list = cur_list.borrow().flattened();
                 if !task_list.is_empty() {
                     let mut cur_list_mut = cur_list.borrow_mut();
-                    if let Some(cur_task) = &cur_list_mut.current_task {
-                        let (mut target, _) = task_list
+                    // a task hidden in a folded one has no row to move from
+                    let shown = cur_list_mut.current_task.as_ref().and_then(|cur_task| {
+                        task_list
                             .iter()
-                            .enumerate()
-                            .find(|(_, task)| task.borrow().id == cur_task.borrow().id)
-                            .unwrap();
+                            .position(|task| task.borrow().id == cur_task.borrow().id)
+                    });
+                    if let Some(mut target) = shown {
                         match bf {
                             SelectBF::Forward => {
                                 target = (target + 1).min(task_list.len() - 1);
@@ -664,11 +2534,14 @@ impl SelectAction<Task> for TodoWidget {
             } else {
                 let mut task_list = Vec::new();
                 cur_list.borrow().tasks.iter().for_each(|task| {
-                    if task.borrow().is_target(self.search_string.clone()) {
+                    if task
+                        .borrow()
+                        .is_target(self.search_string.clone(), self.staleness())
+                    {
                         task_list.push(task.to_owned());
                     }
                 });
-                let tar_list = TodoWidget::get_flattened(&task_list);
+                let tar_list = cur_list.borrow().sort_rule.flatten(&task_list);
                 if !tar_list.is_empty() {
                     let mut cur_list_mut = cur_list.borrow_mut();
                     if let Some(cur_task) = &cur_list_mut.current_task {
@@ -722,17 +2595,7 @@ impl SelectAction<Task> for TodoWidget {
         None
     }
 
-    fn get_flattened(target: &Vec<Rc<RefCell<Task>>>) -> Vec<Rc<RefCell<Task>>> {
-        let mut result = Vec::<Rc<RefCell<Task>>>::new();
-        target.iter().for_each(|task| {
-            result.push(task.clone());
-            let task_bor = task.borrow();
-            if !task_bor.children.is_empty() {
-                let child = TodoWidget::get_flattened(&task_bor.children);
-                result.extend(child);
-            }
-        });
-
-        result
+    fn get_flattened(target: &[Rc<RefCell<Task>>]) -> Vec<Rc<RefCell<Task>>> {
+        SortRule::Manual.flatten(target)
     }
 }
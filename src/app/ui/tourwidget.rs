@@ -0,0 +1,158 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::app::ui::glyphs;
+
+/// The part of the UI a tour card is pointing at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourTarget {
+    Workspace,
+    Archived,
+    TodoList,
+    KeyBar,
+    /// The final card, which isn't about a specific panel
+    Finish,
+}
+
+const TOUR_CARDS: [(TourTarget, &str, &str); 5] = [
+    (
+        TourTarget::Workspace,
+        "Workspaces",
+        "Workspaces group your tasks. Press a to add one, i to add a sub-workspace and Enter to open its tasks.",
+    ),
+    (
+        TourTarget::Archived,
+        "Archived",
+        "Finished projects live here. Press A on a workspace to archive it and R here to recover it.",
+    ),
+    (
+        TourTarget::TodoList,
+        "Todo List",
+        "The tasks of the opened workspace. a adds a task, i a subtask, c/p/t/d set the status and D the due date.",
+    ),
+    (
+        TourTarget::KeyBar,
+        "Key Bar",
+        "The bottom bar always shows the keys of the focused panel. Press ? for the full help page.",
+    ),
+    (
+        TourTarget::Finish,
+        "All Set",
+        "Create a sample workspace with a few example tasks ?",
+    ),
+];
+
+/// A first-run guided tour, layered over the normal UI like the help page
+///
+/// # Fields
+///
+/// - `step` (`Option<usize>`) - the index of the shown card, or None when the tour isn't running
+/// - `target_area` (`Rect`) - the area of the panel the current card points at, set before rendering
+#[derive(Debug, Default)]
+pub struct TourWidget {
+    pub step: Option<usize>,
+    pub target_area: Rect,
+}
+
+impl TourWidget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(&mut self) {
+        self.step = Some(0);
+    }
+
+    /// Advance to the next card
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - true if the tour was on its last card and is now finished
+    pub fn advance(&mut self) -> bool {
+        match self.step {
+            Some(step) if step + 1 < TOUR_CARDS.len() => {
+                self.step = Some(step + 1);
+                false
+            }
+            Some(_) => {
+                self.step = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.step = None;
+    }
+
+    pub fn target(&self) -> Option<TourTarget> {
+        self.step.map(|step| TOUR_CARDS[step].0)
+    }
+}
+
+impl Widget for &mut TourWidget {
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer)
+    where
+        Self: Sized,
+    {
+        let Some(step) = self.step else {
+            return;
+        };
+        let (target, title, desc) = TOUR_CARDS[step];
+
+        if target != TourTarget::Finish {
+            glyphs::block()
+                .border_style(Style::new().fg(Color::LightMagenta))
+                .render(self.target_area, buf);
+        }
+
+        let h_layout = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Max(50),
+            Constraint::Fill(1),
+        ])
+        .split(area);
+        let card_area = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+        ])
+        .split(h_layout[1])[1];
+
+        let hint = if target == TourTarget::Finish {
+            Line::from(vec![
+                "enter".light_cyan(),
+                " yes  ".into(),
+                "esc".light_cyan(),
+                " no".into(),
+            ])
+        } else {
+            Line::from(vec![
+                "enter/space".light_cyan(),
+                " next  ".into(),
+                "esc".light_cyan(),
+                " skip".into(),
+            ])
+        };
+        let block = glyphs::block()
+            .title(format!(
+                " Tour {}/{}: {} ",
+                step + 1,
+                TOUR_CARDS.len(),
+                title
+            ))
+            .title_bottom(hint.right_aligned())
+            .border_style(Style::new().fg(Color::LightMagenta));
+        let para = Paragraph::new(Text::from(desc))
+            .wrap(Wrap { trim: true })
+            .block(block);
+
+        Widget::render(Clear, card_area, buf);
+        Widget::render(para, card_area, buf);
+    }
+}
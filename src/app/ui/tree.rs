@@ -0,0 +1,283 @@
+//! Tree navigation helpers
+//!
+//! Tasks and workspaces are both trees of `Rc<RefCell<_>>` nodes without a link to their
+//! parent. The helpers of this module find the parent and the siblings of a node by walking
+//! down from the roots, for any node type implementing [`TreeNode`], so the task list and the
+//! workspace panels share the same navigation.
+
+use std::{cell::RefCell, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+
+/// A node of a task or workspace tree
+pub trait TreeNode: Sized {
+    /// The children of the node, in the order shown
+    fn children(&self) -> &[Rc<RefCell<Self>>];
+
+    /// Whether the children of the node are shown
+    fn is_expanded(&self) -> bool;
+
+    /// Show or hide the children of the node
+    fn set_expanded(&mut self, expanded: bool);
+
+    /// Show the children of the node
+    fn expand(&mut self) {
+        self.set_expanded(true);
+    }
+}
+
+/// Where to move the selection in a tree
+///
+/// # Variants
+///
+/// - `Parent` - the node the current one is a child of
+/// - `FirstChild` - the first child of the current node, which is expanded if needed
+/// - `PrevSibling` - the previous node on the same level, skipping over its descendants
+/// - `NextSibling` - the next node on the same level, skipping over the current descendants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Jump {
+    Parent,
+    FirstChild,
+    PrevSibling,
+    NextSibling,
+}
+
+/// How to fold the nodes of a tree, after the fold commands of vim
+///
+/// # Variants
+///
+/// - `CloseAll` - `zM`, hide the children of every node
+/// - `OpenAll` - `zR`, show the children of every node
+/// - `Close` - `zc`, hide the children of the current node, or of its parent when it has none
+/// - `Open` - `zo`, show the children of the current node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Fold {
+    CloseAll,
+    OpenAll,
+    Close,
+    Open,
+}
+
+/// Find the parent of a node
+///
+/// # Arguments
+///
+/// - `roots` (`&[Rc<RefCell<T>>]`) - the top level nodes of the tree
+/// - `target` (`&Rc<RefCell<T>>`) - the node to look for, compared by pointer
+///
+/// # Returns
+///
+/// - `Option<Rc<RefCell<T>>>` - the parent, or None for a top level node or a node not in the tree
+pub fn find_parent<T: TreeNode>(
+    roots: &[Rc<RefCell<T>>],
+    target: &Rc<RefCell<T>>,
+) -> Option<Rc<RefCell<T>>> {
+    for node in roots {
+        let node_ref = node.borrow();
+        if node_ref
+            .children()
+            .iter()
+            .any(|child| Rc::ptr_eq(child, target))
+        {
+            return Some(node.clone());
+        }
+        if let Some(parent) = find_parent(node_ref.children(), target) {
+            return Some(parent);
+        }
+    }
+    None
+}
+
+/// Get the chain of ancestors of a node
+///
+/// # Arguments
+///
+/// - `roots` (`&[Rc<RefCell<T>>]`) - the top level nodes of the tree
+/// - `target` (`&Rc<RefCell<T>>`) - the node whose ancestors are wanted
+///
+/// # Returns
+///
+/// - `Vec<Rc<RefCell<T>>>` - the ancestors from the top level node down to the parent, empty
+///   for a top level node
+pub fn ancestors<T: TreeNode>(
+    roots: &[Rc<RefCell<T>>],
+    target: &Rc<RefCell<T>>,
+) -> Vec<Rc<RefCell<T>>> {
+    let mut chain = Vec::new();
+    let mut node = target.clone();
+    while let Some(parent) = find_parent(roots, &node) {
+        chain.push(parent.clone());
+        node = parent;
+    }
+    chain.reverse();
+    chain
+}
+
+/// Get the nodes on the same level as a node, the node included
+///
+/// # Arguments
+///
+/// - `roots` (`&[Rc<RefCell<T>>]`) - the top level nodes of the tree
+/// - `target` (`&Rc<RefCell<T>>`) - the node whose level is wanted
+///
+/// # Returns
+///
+/// - `Vec<Rc<RefCell<T>>>` - the children of the parent, or the roots for a top level node
+pub fn siblings<T: TreeNode>(
+    roots: &[Rc<RefCell<T>>],
+    target: &Rc<RefCell<T>>,
+) -> Vec<Rc<RefCell<T>>> {
+    match find_parent(roots, target) {
+        Some(parent) => parent.borrow().children().to_vec(),
+        None => roots.to_vec(),
+    }
+}
+
+/// Find the node to move the selection to
+///
+/// # Arguments
+///
+/// - `roots` (`&[Rc<RefCell<T>>]`) - the top level nodes of the tree
+/// - `current` (`&Rc<RefCell<T>>`) - the selected node
+/// - `jump` ([`Jump`]) - where to move
+///
+/// # Returns
+///
+/// - `Option<Rc<RefCell<T>>>` - the node to select, or None if there is nowhere to go
+///
+/// # Examples
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use todo::app::ui::todolistwidget::Task;
+/// use todo::app::ui::tree::{jump, Jump};
+///
+/// let child = Rc::new(RefCell::new(Task::new("child".to_string(), None)));
+/// let mut parent = Task::new("parent".to_string(), None);
+/// parent.add_child(child.clone());
+/// let roots = vec![Rc::new(RefCell::new(parent))];
+///
+/// assert!(Rc::ptr_eq(&jump(&roots, &child, Jump::Parent).unwrap(), &roots[0]));
+/// ```
+pub fn jump<T: TreeNode>(
+    roots: &[Rc<RefCell<T>>],
+    current: &Rc<RefCell<T>>,
+    jump: Jump,
+) -> Option<Rc<RefCell<T>>> {
+    jump_arranged(roots, current, jump, <[_]>::to_vec)
+}
+
+/// Find the node to move the selection to in a tree shown in another order than stored
+///
+/// # Arguments
+///
+/// - `roots` (`&[Rc<RefCell<T>>]`) - the top level nodes of the tree
+/// - `current` (`&Rc<RefCell<T>>`) - the selected node
+/// - `jump` ([`Jump`]) - where to move
+/// - `arrange` (`impl Fn(&[Rc<RefCell<T>>]) -> Vec<Rc<RefCell<T>>>`) - put the nodes of one
+///   level in the order shown, e.g. [`SortRule::arrange`]
+///
+/// # Returns
+///
+/// - `Option<Rc<RefCell<T>>>` - the node to select, or None if there is nowhere to go
+///
+/// [`SortRule::arrange`]: crate::app::ui::todolistwidget::SortRule::arrange
+pub fn jump_arranged<T: TreeNode>(
+    roots: &[Rc<RefCell<T>>],
+    current: &Rc<RefCell<T>>,
+    jump: Jump,
+    arrange: impl Fn(&[Rc<RefCell<T>>]) -> Vec<Rc<RefCell<T>>>,
+) -> Option<Rc<RefCell<T>>> {
+    match jump {
+        Jump::Parent => find_parent(roots, current),
+        Jump::FirstChild => {
+            let first = arrange(current.borrow().children()).first().cloned();
+            if first.is_some() {
+                current.borrow_mut().expand();
+            }
+            first
+        }
+        Jump::PrevSibling | Jump::NextSibling => {
+            let level = arrange(&siblings(roots, current));
+            let index = level.iter().position(|node| Rc::ptr_eq(node, current))?;
+            let target = if jump == Jump::PrevSibling {
+                index.checked_sub(1)?
+            } else {
+                index + 1
+            };
+            level.get(target).cloned()
+        }
+    }
+}
+
+/// Fold the nodes of a tree and find the node to select so the selection stays shown
+///
+/// # Arguments
+///
+/// - `roots` (`&[Rc<RefCell<T>>]`) - the top level nodes of the tree
+/// - `current` (`Option<&Rc<RefCell<T>>>`) - the selected node, if any
+/// - `fold` ([`Fold`]) - what to fold
+///
+/// # Returns
+///
+/// - `Option<Rc<RefCell<T>>>` - the current node, or its outermost ancestor now hiding it,
+///   None if nothing is selected
+///
+/// # Examples
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use todo::app::ui::todolistwidget::Task;
+/// use todo::app::ui::tree::{fold, Fold};
+///
+/// let child = Rc::new(RefCell::new(Task::new("child".to_string(), None)));
+/// let mut parent = Task::new("parent".to_string(), None);
+/// parent.add_child(child.clone());
+/// let roots = vec![Rc::new(RefCell::new(parent))];
+///
+/// let shown = fold(&roots, Some(&child), Fold::CloseAll).unwrap();
+/// assert!(Rc::ptr_eq(&shown, &roots[0]));
+/// assert!(!roots[0].borrow().expanded);
+/// ```
+pub fn fold<T: TreeNode>(
+    roots: &[Rc<RefCell<T>>],
+    current: Option<&Rc<RefCell<T>>>,
+    fold: Fold,
+) -> Option<Rc<RefCell<T>>> {
+    match fold {
+        Fold::CloseAll | Fold::OpenAll => set_expanded_all(roots, fold == Fold::OpenAll),
+        Fold::Close => {
+            let folded = current.and_then(|node| {
+                if node.borrow().children().is_empty() {
+                    find_parent(roots, node)
+                } else {
+                    Some(node.clone())
+                }
+            });
+            if let Some(folded) = folded {
+                folded.borrow_mut().set_expanded(false);
+            }
+        }
+        Fold::Open => {
+            if let Some(node) = current {
+                node.borrow_mut().expand();
+            }
+        }
+    }
+    let current = current?;
+    let hidden_by = ancestors(roots, current)
+        .into_iter()
+        .find(|node| !node.borrow().is_expanded());
+    Some(hidden_by.unwrap_or_else(|| current.clone()))
+}
+
+/// Show or hide the children of every node having some
+fn set_expanded_all<T: TreeNode>(nodes: &[Rc<RefCell<T>>], expanded: bool) {
+    for node in nodes {
+        let children = node.borrow().children().to_vec();
+        if !children.is_empty() {
+            node.borrow_mut().set_expanded(expanded);
+            set_expanded_all(&children, expanded);
+        }
+    }
+}
@@ -1,20 +1,55 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
 
 use ratatui::{
     style::{Color, Style, Stylize},
-    widgets::{Block, List, ListItem, ListState, Padding, StatefulWidget, Widget},
+    text::Line,
+    widgets::{
+        List, ListItem, ListState, Padding, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        StatefulWidget, Widget,
+    },
 };
 use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthStr;
 use uuid::Uuid;
 
-use crate::app::ui::{SelectAction, SelectBF, todolistwidget::TodoWidget};
+use crate::app::{
+    sources::{IdGen, RandomIds},
+    ui::{
+        SelectAction, SelectBF, Ui, glyphs,
+        todolistwidget::TodoWidget,
+        tree::{self, Fold, Jump, TreeNode},
+    },
+};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WorkspaceType {
+    #[default]
     Normal,
     Archived,
 }
 
+/// The order the workspaces of a panel are listed in, at every level of the tree
+///
+/// # Variants
+///
+/// - `Saved` - the order they were added or archived in, as moved with `J`/`K`
+/// - `Name` - sorted by name, ignoring the case
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkspaceOrder {
+    #[default]
+    Saved,
+    Name,
+}
+
+impl fmt::Display for WorkspaceOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WorkspaceOrder::Saved => "Saved Order",
+            WorkspaceOrder::Name => "Name",
+        })
+    }
+}
+
 /// The Workspace Structure to store the basic information of a workspace
 ///
 /// # Fields
@@ -23,23 +58,74 @@ pub enum WorkspaceType {
 /// - `id` (`Uuid`) - unique id of the workspace.
 /// - `expanded` (`bool`) - whether the workspace is expanded or not.
 /// - `children` (`Vec<Rc<RefCell<Workspace>>>`) - the children/sub ws of the workspace, is a vector of workspace.
+/// - `protected` (`bool`) - whether the workspace refuses to be deleted or archived.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Workspace {
     pub desc: String,
     pub id: Uuid,
     pub expanded: bool,
     pub children: Vec<Rc<RefCell<Workspace>>>,
+    #[serde(default)]
+    pub protected: bool,
 }
 
 impl Workspace {
     pub fn new(desc: String) -> Self {
+        Self::with_ids(desc, &RandomIds)
+    }
+
+    /// Create a workspace which takes its id from a given id source
+    ///
+    /// # Arguments
+    ///
+    /// - `desc` (`String`) - the description of the workspace
+    /// - `ids` (`&dyn IdGen`) - the source of the id
+    pub fn with_ids(desc: String, ids: &dyn IdGen) -> Self {
         Self {
             desc,
-            id: Uuid::new_v4(),
+            id: ids.next_id(),
             expanded: true,
             children: Vec::<Rc<RefCell<Workspace>>>::new(),
+            protected: false,
         }
     }
+
+    /// Whether the name of the workspace contains one of the words of a search, as a task
+    /// matches the filter, see [`Task::matches`](crate::app::ui::todolistwidget::Task::matches)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use todo::app::ui::workspacewidget::Workspace;
+    ///
+    /// let ws = Workspace::new("Backend".to_string());
+    /// assert!(ws.matches("front end"));
+    /// assert!(!ws.matches("back"));
+    /// ```
+    pub fn matches(&self, search: &str) -> bool {
+        search
+            .split(" ")
+            .any(|word| !word.is_empty() && self.desc.contains(word))
+    }
+
+    /// Find a protected workspace among a workspace and its sub workspaces
+    ///
+    /// Deleting or archiving a workspace takes its sub workspaces along, so a protected sub
+    /// workspace protects its parents too.
+    ///
+    /// # Arguments
+    ///
+    /// - `workspace` (`&Rc<RefCell<Workspace>>`) - the workspace to delete or archive
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - the name of the first protected workspace, None if there is none
+    pub fn find_protected(workspace: &Rc<RefCell<Workspace>>) -> Option<String> {
+        WorkspaceWidget::get_flattened(std::slice::from_ref(workspace))
+            .iter()
+            .find(|ws| ws.borrow().protected)
+            .map(|ws| ws.borrow().desc.clone())
+    }
     /// add the child to the [`Workspace::children`] component of [`Workspace`]
     ///
     /// # Arguments
@@ -85,6 +171,15 @@ impl Workspace {
 /// - `focused` (`bool`) - whether the widget is focused or not.
 /// - `#[serde(default)] ws_state` (`ListState`) - The [`ListState`] of the [`List`] widget, which is used to select the workspace
 ///   because the workspaces are displayed in a [`List`] widget.
+/// - `#[serde(skip)] active` (`Option<Uuid>`) - the id of the workspace whose tasks are displayed in the todo list,
+///   marked with `●` regardless of the focus.
+/// - `#[serde(skip)] unread` (`bool`) - the archived workspaces are saved apart and not read
+///   yet, see [`Archive`](crate::app::data::Archive)
+/// - `#[serde(skip)] width` (`u16`) - the width inside the panel as last drawn, which a name
+///   typed longer than overflows
+/// - `#[serde(default)] order` ([`WorkspaceOrder`]) - the order the workspaces are listed in
+/// - `#[serde(default)] saved_order` (`Vec<Uuid>`) - the ids in the saved order while the
+///   workspaces are sorted by name, so switching back puts them back
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WorkspaceWidget {
     pub workspaces: Vec<Rc<RefCell<Workspace>>>,
@@ -93,9 +188,78 @@ pub struct WorkspaceWidget {
     #[serde(default)]
     pub ws_state: ListState,
     pub ws_type: WorkspaceType,
+    #[serde(skip)]
+    pub active: Option<Uuid>,
+    #[serde(skip)]
+    pub unread: bool,
+    #[serde(skip)]
+    pub width: u16,
+    #[serde(default)]
+    pub order: WorkspaceOrder,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub saved_order: Vec<Uuid>,
+}
+
+impl TreeNode for Workspace {
+    fn children(&self) -> &[Rc<RefCell<Workspace>>] {
+        &self.children
+    }
+
+    fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    fn set_expanded(&mut self, expanded: bool) {
+        self.expanded = expanded;
+    }
 }
 
 impl WorkspaceWidget {
+    /// Move the selection to the parent, the first child or a sibling of the current workspace
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`WorkspaceWidget`])
+    /// - `to` ([`Jump`]) - where to move
+    pub fn jump(&mut self, to: Jump) {
+        if let Some(cur_ws) = &self.current_workspace
+            && let Some(target) = tree::jump(&self.workspaces, cur_ws, to)
+        {
+            let index = self.shown().iter().position(|ws| Rc::ptr_eq(ws, &target));
+            self.ws_state.select(index);
+            self.current_workspace = Some(target);
+        }
+    }
+
+    /// The workspaces shown in the panel, in order, the children of the collapsed ones left out
+    pub fn shown(&self) -> Vec<Rc<RefCell<Workspace>>> {
+        fn walk(level: &[Rc<RefCell<Workspace>>], shown: &mut Vec<Rc<RefCell<Workspace>>>) {
+            for ws in level {
+                shown.push(ws.clone());
+                if ws.borrow().expanded {
+                    walk(&ws.borrow().children, shown);
+                }
+            }
+        }
+        let mut shown = Vec::new();
+        walk(&self.workspaces, &mut shown);
+        shown
+    }
+
+    /// Fold the workspaces of the panel, selecting the workspace hiding the current one if
+    /// it's hidden
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`WorkspaceWidget`])
+    /// - `fold` ([`Fold`]) - what to fold, see [`tree::fold`]
+    pub fn fold(&mut self, fold: Fold) {
+        let current = self.current_workspace.clone();
+        if let Some(shown) = tree::fold(&self.workspaces, current.as_ref(), fold) {
+            self.select(Some(shown));
+        }
+    }
+
     pub fn new(ws_type: WorkspaceType) -> Self {
         Self {
             workspaces: Vec::<Rc<RefCell<Workspace>>>::new(),
@@ -103,6 +267,118 @@ impl WorkspaceWidget {
             focused: true,
             ws_state: ListState::default(),
             ws_type,
+            active: None,
+            unread: false,
+            width: 0,
+            order: WorkspaceOrder::Saved,
+            saved_order: Vec::new(),
+        }
+    }
+
+    /// Switch between the saved order of the workspaces and their names
+    ///
+    /// Sorted by name, the saved order is kept aside and put back when switching again; a
+    /// workspace added meanwhile goes after the others of its level.
+    ///
+    /// # Returns
+    ///
+    /// - [`WorkspaceOrder`] - the order switched to
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{cell::RefCell, rc::Rc};
+    /// use todo::app::ui::workspacewidget::{Workspace, WorkspaceOrder, WorkspaceType, WorkspaceWidget};
+    ///
+    /// let mut widget = WorkspaceWidget::new(WorkspaceType::Archived);
+    /// for name in ["Taxes", "garden", "Move"] {
+    ///     widget.add_workspace(Rc::new(RefCell::new(Workspace::new(name.to_string()))));
+    /// }
+    /// let names = |widget: &WorkspaceWidget| -> Vec<String> {
+    ///     widget.workspaces.iter().map(|ws| ws.borrow().desc.clone()).collect()
+    /// };
+    /// assert_eq!(widget.toggle_order(), WorkspaceOrder::Name);
+    /// assert_eq!(names(&widget), ["garden", "Move", "Taxes"]);
+    /// assert_eq!(widget.toggle_order(), WorkspaceOrder::Saved);
+    /// assert_eq!(names(&widget), ["Taxes", "garden", "Move"]);
+    /// ```
+    pub fn toggle_order(&mut self) -> WorkspaceOrder {
+        match self.order {
+            WorkspaceOrder::Saved => {
+                self.saved_order = WorkspaceWidget::get_flattened(&self.workspaces)
+                    .iter()
+                    .map(|ws| ws.borrow().id)
+                    .collect();
+                self.order = WorkspaceOrder::Name;
+                self.arrange();
+            }
+            WorkspaceOrder::Name => {
+                let positions: HashMap<Uuid, usize> = self
+                    .saved_order
+                    .drain(..)
+                    .enumerate()
+                    .map(|(position, id)| (id, position))
+                    .collect();
+                WorkspaceWidget::sort_levels(&mut self.workspaces, &|ws| {
+                    positions.get(&ws.id).copied().unwrap_or(usize::MAX)
+                });
+                self.order = WorkspaceOrder::Saved;
+            }
+        }
+        self.order
+    }
+
+    /// Sort the workspaces again once one is added or renamed, if they are sorted by name
+    pub fn arrange(&mut self) {
+        if self.order == WorkspaceOrder::Name {
+            WorkspaceWidget::sort_levels(&mut self.workspaces, &|ws| ws.desc.to_lowercase());
+        }
+    }
+
+    /// Sort every level of a tree of workspaces by a key, keeping the order of equal keys
+    fn sort_levels<K: Ord>(
+        workspaces: &mut [Rc<RefCell<Workspace>>],
+        key: &dyn Fn(&Workspace) -> K,
+    ) {
+        workspaces.sort_by_cached_key(|ws| key(&ws.borrow()));
+        for ws in workspaces.iter() {
+            WorkspaceWidget::sort_levels(&mut ws.borrow_mut().children, key);
+        }
+    }
+
+    /// Move the current workspace before its previous or after its next sibling
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`WorkspaceWidget`])
+    /// - `bf` ([`SelectBF`]) - `Back` to move it up, `Forward` to move it down
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - whether it moved, not when it's already first or last of its level
+    pub fn shift_current(&mut self, bf: SelectBF) -> bool {
+        let Some(cur_ws) = self.current_workspace.clone() else {
+            return false;
+        };
+        let parent = tree::find_parent(&self.workspaces, &cur_ws);
+        let mut parent_ref = parent.as_ref().map(|parent| parent.borrow_mut());
+        let level = match parent_ref.as_mut() {
+            Some(parent) => &mut parent.children,
+            None => &mut self.workspaces,
+        };
+        let Some(index) = level.iter().position(|ws| Rc::ptr_eq(ws, &cur_ws)) else {
+            return false;
+        };
+        let other = match bf {
+            SelectBF::Back => index.checked_sub(1),
+            SelectBF::Forward => Some(index + 1).filter(|other| *other < level.len()),
+        };
+        match other {
+            Some(other) => {
+                level.swap(index, other);
+                true
+            }
+            None => false,
         }
     }
 
@@ -135,7 +411,7 @@ impl WorkspaceWidget {
     /// Refresh the [`WorkspaceWidget::current_workspace`] to make it consistent with the
     /// [`WorkspaceWidget::workspaces`] in memory while first load the data from file
     ///
-    /// This is because that I use the `.json` file to store the data, and [`Rc<RefCell<Workspace>`] to store the data in memory.
+    /// This is because that I use the `.json` file to store the data, and `Rc<RefCell<Workspace>>` to store the data in memory.
     /// When first time load the data from file, `current_workspace` will be a completely different object from any one of the
     /// `workspaces` in memory, although they have the same value.
     pub fn refresh_current(&mut self) {
@@ -150,38 +426,142 @@ impl WorkspaceWidget {
         }
     }
 
-    /// a function to get the workspaces' desc list in the form of a list of strings,
-    /// whose return value is used to construct a [`List`] widget to display the workspaces
+    /// Get the rows of the workspace panel, one per shown workspace
+    ///
+    /// The sub workspaces hang from tree guides, dimmed so the names stand out, and the
+    /// children of a collapsed workspace are left out. A workspace with children starts with
+    /// the expanded or collapsed marker, and the active one with the active marker and a bold
+    /// name. Every glyph comes from the [`glyphs`] set in use, so the ASCII set draws the
+    /// guides too.
     ///
     /// # Arguments
     ///
-    /// - `workspaces` (`&Vec<Rc<RefCell<Workspace>>>`) - the workspaces to get the desc list from
-    /// - `dep` (`usize`) - a helper parameter to determine the depth of the workspaces, because each worksapce might
-    ///   have children and this function will be called recursively
+    /// - `workspaces` (`&[Rc<RefCell<Workspace>>]`) - the top level workspaces
+    /// - `active` (`Option<Uuid>`) - the id of the workspace whose tasks are displayed
     ///
     /// # Returns
     ///
-    /// - `Vec<String>` - the desc list of the workspaces, which is indented
-    pub fn get_ws_list(workspaces: &[Rc<RefCell<Workspace>>], dep: usize) -> Vec<String> {
-        let mut list_item = Vec::<String>::new();
-        workspaces.iter().for_each(|item| {
+    /// - `Vec<(Uuid, ListItem<'static>)>` - the id and the row of each shown workspace, in
+    ///   the order of [`SelectAction::get_flattened`] without the hidden children
+    pub fn get_ws_list(
+        workspaces: &[Rc<RefCell<Workspace>>],
+        active: Option<Uuid>,
+    ) -> Vec<(Uuid, ListItem<'static>)> {
+        let mut rows = Vec::new();
+        WorkspaceWidget::push_rows(workspaces, active, &mut Vec::new(), &mut rows);
+        rows
+    }
+
+    /// Push the rows of one level of the tree, `guides` holding whether each ancestor has
+    /// more workspaces below it, the top level one first
+    fn push_rows(
+        workspaces: &[Rc<RefCell<Workspace>>],
+        active: Option<Uuid>,
+        guides: &mut Vec<bool>,
+        rows: &mut Vec<(Uuid, ListItem<'static>)>,
+    ) {
+        let glyphs = glyphs::current();
+        for (index, item) in workspaces.iter().enumerate() {
             let ws = item.borrow();
-            let desc = ws.desc.clone();
-            let prefix = if !ws.children.is_empty() {
-                if ws.expanded { "∨ " } else { "﹥ " }
+            let last = index + 1 == workspaces.len();
+            let is_active = active == Some(ws.id);
+            let mut spans = vec![if is_active {
+                glyphs.active.light_green()
             } else {
-                ""
-            };
-            let it = "  ".repeat(dep) + prefix + desc.as_str();
-            list_item.push(it);
+                " ".repeat(glyphs.active.width()).into()
+            }];
+            // the top level hangs from no guide, so its column is never drawn
+            if !guides.is_empty() {
+                let mut guide: String = guides
+                    .iter()
+                    .skip(1)
+                    .map(|more| {
+                        if *more {
+                            glyphs.guide_bar
+                        } else {
+                            glyphs.guide_blank
+                        }
+                    })
+                    .collect();
+                guide += if last {
+                    glyphs.guide_last
+                } else {
+                    glyphs.guide_branch
+                };
+                spans.push(guide.dark_gray());
+            }
+            if !ws.children.is_empty() {
+                spans.push(if ws.expanded {
+                    glyphs.expanded.light_cyan()
+                } else {
+                    glyphs.collapsed.light_yellow()
+                });
+            }
+            spans.push(if is_active {
+                ws.desc.clone().bold()
+            } else {
+                ws.desc.clone().into()
+            });
+            if ws.protected {
+                spans.push(glyphs.locked.into());
+            }
+            rows.push((ws.id, ListItem::new(Line::from(spans))));
 
             if ws.expanded {
-                let children_list = WorkspaceWidget::get_ws_list(&ws.children, dep + 2);
-                list_item.extend(children_list);
+                guides.push(!last);
+                WorkspaceWidget::push_rows(&ws.children, active, guides, rows);
+                guides.pop();
             }
-        });
+        }
+    }
+
+    /// Select a workspace, keeping [`WorkspaceWidget::ws_state`] consistent with it
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`WorkspaceWidget`])
+    /// - `workspace` (`Option<Rc<RefCell<Workspace>>>`) - the workspace to select, or None to clear the selection
+    pub fn select(&mut self, workspace: Option<Rc<RefCell<Workspace>>>) {
+        if let Some(ws) = &workspace {
+            tree::ancestors(&self.workspaces, ws)
+                .iter()
+                .for_each(|parent| parent.borrow_mut().expand());
+        }
+        let ws_list = self.shown();
+        let idx = workspace
+            .as_ref()
+            .and_then(|tar| ws_list.iter().position(|ws| Rc::ptr_eq(ws, tar)));
+        self.ws_state.select(idx);
+        self.current_workspace = idx.and(workspace);
+    }
 
-        list_item
+    /// Move the current workspace into another widget, used by archive and recovery
+    ///
+    /// The workspace which takes the place of the moved one (or the previous one at the end
+    /// of the list) becomes selected here, and the moved workspace becomes selected in `target`.
+    ///
+    /// # Arguments
+    ///
+    /// - `&mut self` ([`WorkspaceWidget`])
+    /// - `target` (`&mut WorkspaceWidget`) - the widget to move the current workspace into
+    pub fn transfer_current(&mut self, target: &mut WorkspaceWidget) {
+        if let Some(cur_ws) = self.current_workspace.clone() {
+            let idx = self
+                .shown()
+                .iter()
+                .position(|ws| Rc::ptr_eq(ws, &cur_ws))
+                .unwrap_or_default();
+            WorkspaceWidget::delete_item(&mut self.workspaces, &cur_ws);
+            target.add_workspace(cur_ws.clone());
+            target.arrange();
+
+            let ws_list = self.shown();
+            let next = ws_list
+                .get(idx.min(ws_list.len().saturating_sub(1)))
+                .cloned();
+            self.select(next);
+            target.select(Some(cur_ws));
+        }
     }
 
     /// Delete a workspace from the [`WorkspaceWidget::workspaces`] field
@@ -211,6 +591,45 @@ impl WorkspaceWidget {
             workspaces.remove(i);
         }
     }
+
+    /// Find the workspace of a level named like a workspace about to join it
+    ///
+    /// A workspace recovered or imported at the top level must not sit beside another of the
+    /// same name, the two could not be told apart. The recovery asks what to do with the
+    /// namesake, the import adds its tasks to it.
+    ///
+    /// # Arguments
+    ///
+    /// - `workspaces` (`&[Rc<RefCell<Workspace>>]`) - the workspaces of the level, not their
+    ///   sub workspaces
+    /// - `name` (`&str`) - the name of the workspace joining the level
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Rc<RefCell<Workspace>>>` - the first workspace with this name
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{cell::RefCell, rc::Rc};
+    /// use todo::app::ui::workspacewidget::{Workspace, WorkspaceWidget};
+    ///
+    /// let website = Rc::new(RefCell::new(Workspace::new("Website".to_string())));
+    /// let docs = Rc::new(RefCell::new(Workspace::new("Docs".to_string())));
+    /// website.borrow_mut().add_child(docs);
+    /// let level = [website.clone()];
+    /// assert!(Rc::ptr_eq(&WorkspaceWidget::namesake(&level, "Website").unwrap(), &website));
+    /// assert!(WorkspaceWidget::namesake(&level, "Docs").is_none());
+    /// ```
+    pub fn namesake(
+        workspaces: &[Rc<RefCell<Workspace>>],
+        name: &str,
+    ) -> Option<Rc<RefCell<Workspace>>> {
+        workspaces
+            .iter()
+            .find(|ws| ws.borrow().desc == name)
+            .cloned()
+    }
 }
 
 impl Default for WorkspaceWidget {
@@ -224,16 +643,27 @@ impl Widget for &mut WorkspaceWidget {
     where
         Self: Sized,
     {
-        let ws_list = WorkspaceWidget::get_ws_list(&self.workspaces, 0);
-        let mut workspace_list = Vec::<ListItem>::new();
-        ws_list.iter().for_each(|desc| {
-            workspace_list.push(ListItem::new(desc.to_owned()));
-        });
+        let (ids, workspace_list): (Vec<Uuid>, Vec<ListItem>) =
+            WorkspaceWidget::get_ws_list(&self.workspaces, self.active)
+                .into_iter()
+                .unzip();
+        // the selection counts the hidden children of collapsed workspaces, the rows don't
+        let current = self.current_workspace.as_ref().map(|ws| ws.borrow().id);
+        self.ws_state
+            .select(current.and_then(|id| ids.iter().position(|other| *other == id)));
 
-        let workspace_block = Block::bordered()
+        // the archived panel is small, the count tells how many are out of sight
+        let workspace_block = glyphs::block()
             .title(match self.ws_type {
                 WorkspaceType::Normal => " <1> Workspace ".light_green(),
-                WorkspaceType::Archived => " <2> Archived ".light_yellow(),
+                WorkspaceType::Archived if self.unread || self.workspaces.is_empty() => {
+                    " <2> Archived ".light_yellow()
+                }
+                WorkspaceType::Archived => format!(
+                    " <2> Archived ({}) ",
+                    WorkspaceWidget::get_flattened(&self.workspaces).len()
+                )
+                .light_yellow(),
             })
             .border_style(if self.focused {
                 Style::new().fg(match self.ws_type {
@@ -245,8 +675,22 @@ impl Widget for &mut WorkspaceWidget {
             })
             .padding(Padding::uniform(1));
 
+        self.width = workspace_block.inner(area).width;
+        if self.workspaces.is_empty() {
+            let hint = match self.ws_type {
+                WorkspaceType::Normal => "press a to create your first workspace",
+                WorkspaceType::Archived if self.unread => "press 2 to read the archive",
+                WorkspaceType::Archived => "archived workspaces appear here (A to archive)",
+            };
+            Ui::render_placeholder(hint, workspace_block, area, buf);
+            return;
+        }
+
+        let rows = workspace_list.len();
+        let height = workspace_block.inner(area).height as usize;
         let list_widget = List::new(workspace_list)
             .block(workspace_block)
+            .highlight_symbol(glyphs::current().selected)
             .highlight_style(if self.focused {
                 Style::new()
                     // .fg(Color::LightGreen)
@@ -255,6 +699,18 @@ impl Widget for &mut WorkspaceWidget {
                 Style::new().fg(Color::LightGreen)
             });
         StatefulWidget::render(list_widget, area, buf, &mut self.ws_state);
+        if rows > height {
+            let mut state =
+                ScrollbarState::new(rows.saturating_sub(height)).position(self.ws_state.offset());
+            StatefulWidget::render(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None),
+                area,
+                buf,
+                &mut state,
+            );
+        }
     }
 }
 
@@ -266,7 +722,7 @@ impl SelectAction<Workspace> for WorkspaceWidget {
         // state: &mut ListState,
         bf: super::SelectBF,
     ) -> Option<Rc<RefCell<Workspace>>> {
-        let ws_list = WorkspaceWidget::get_flattened(&self.workspaces);
+        let ws_list = self.shown();
         if !ws_list.is_empty() {
             if self.current_workspace.is_none() {
                 match bf {
@@ -309,7 +765,7 @@ impl SelectAction<Workspace> for WorkspaceWidget {
         }
     }
 
-    fn get_flattened(target: &Vec<Rc<RefCell<Workspace>>>) -> Vec<Rc<RefCell<Workspace>>> {
+    fn get_flattened(target: &[Rc<RefCell<Workspace>>]) -> Vec<Rc<RefCell<Workspace>>> {
         let mut result = Vec::<Rc<RefCell<Workspace>>>::new();
         target.iter().for_each(|ws| {
             result.push(ws.clone());
@@ -0,0 +1,177 @@
+//! The one order every reader of the data walks the tasks in
+//!
+//! Whatever writes the tasks out, the stdio mode, the diff of two saves or an exporter, goes
+//! through [`visit_tasks`] so they all list the same tasks in the same order: the order of
+//! the screen. The workspaces come as the workspace panel shows them, each parent before its
+//! sub workspaces, then the archived workspaces when asked for, then the lists whose workspace
+//! is gone. The tasks of a workspace come as its list shows them, arranged by the sort rule of
+//! the list, every parent before its subtasks, and a collapsed task still with its subtasks.
+
+use std::{cell::RefCell, rc::Rc};
+
+use uuid::Uuid;
+
+use crate::app::{
+    data::Datas,
+    ui::{
+        SelectAction,
+        todolistwidget::{SortRule, Task, TaskStatus, TodoList},
+        workspacewidget::WorkspaceWidget,
+    },
+};
+
+/// The name of the workspace of the tasks whose workspace is gone
+pub const NO_WORKSPACE: &str = "(no workspace)";
+
+/// What [`visit_tasks`] walks through
+///
+/// # Fields
+///
+/// - `archived` (`bool`) - also visit the tasks of the archived workspaces
+/// - `deprecated` (`bool`) - also visit the deprecated tasks, a deprecated task is left out
+///   with its subtasks otherwise
+/// - `max_depth` (`Option<usize>`) - the deepest level visited, 0 for the top level tasks
+///   only, None for every level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisitOptions {
+    pub archived: bool,
+    pub deprecated: bool,
+    pub max_depth: Option<usize>,
+}
+
+impl VisitOptions {
+    /// Visit every task of the data, archived and deprecated included
+    pub fn everything() -> Self {
+        Self {
+            archived: true,
+            deprecated: true,
+            max_depth: None,
+        }
+    }
+}
+
+impl Default for VisitOptions {
+    /// The tasks of the active workspaces, deprecated included, at every level
+    fn default() -> Self {
+        Self {
+            archived: false,
+            deprecated: true,
+            max_depth: None,
+        }
+    }
+}
+
+/// A task met by [`visit_tasks`]
+///
+/// # Fields
+///
+/// - `task` (`&Task`) - the task
+/// - `depth` (`usize`) - its level, 0 for a top level task
+/// - `parent` (`Option<Uuid>`) - the id of its parent task, None for a top level task
+/// - `workspace` (`Uuid`) - the id of the workspace of its list
+/// - `workspace_path` (`&str`) - the path of that workspace, see [`Datas::workspace_paths`],
+///   or [`NO_WORKSPACE`]
+#[derive(Debug, Clone, Copy)]
+pub struct Visit<'a> {
+    pub task: &'a Task,
+    pub depth: usize,
+    pub parent: Option<Uuid>,
+    pub workspace: Uuid,
+    pub workspace_path: &'a str,
+}
+
+/// Walk the tasks of the data in the order of the screen, see the [module](self) documentation
+///
+/// # Arguments
+///
+/// - `datas` (`&Datas`) - the data
+/// - `options` ([`VisitOptions`]) - which tasks to visit
+/// - `visit` (`impl FnMut(Visit)`) - called once for every task visited
+///
+/// # Examples
+///
+/// ```
+/// use todo::app::{
+///     data::Datas,
+///     visit::{VisitOptions, visit_tasks},
+/// };
+///
+/// let mut datas = Datas::default();
+/// datas.ensure_inbox();
+/// let mut count = 0;
+/// visit_tasks(&datas, VisitOptions::default(), |_| count += 1);
+/// assert_eq!(count, 0);
+/// ```
+pub fn visit_tasks(datas: &Datas, options: VisitOptions, mut visit: impl FnMut(Visit)) {
+    let paths = datas.workspace_paths();
+    let mut workspaces = WorkspaceWidget::get_flattened(&datas.workspace.workspaces);
+    if options.archived {
+        workspaces.extend(WorkspaceWidget::get_flattened(
+            &datas.archived_ws.workspaces,
+        ));
+    }
+    for ws in workspaces.iter() {
+        let id = ws.borrow().id;
+        if let Some(list) = datas.todolist.list_of(id) {
+            visit_list(&list, &paths[&id], options, &mut visit);
+        }
+    }
+    if options.archived {
+        for list in datas.todolist.todolists.iter() {
+            if !paths.contains_key(&list.borrow().workspace) {
+                visit_list(list, NO_WORKSPACE, options, &mut visit);
+            }
+        }
+    }
+}
+
+fn visit_list(
+    list: &Rc<RefCell<TodoList>>,
+    path: &str,
+    options: VisitOptions,
+    visit: &mut impl FnMut(Visit),
+) {
+    let list = list.borrow();
+    visit_level(
+        &list.tasks,
+        list.sort_rule,
+        (0, None),
+        (list.workspace, path),
+        options,
+        visit,
+    );
+}
+
+fn visit_level(
+    tasks: &[Rc<RefCell<Task>>],
+    rule: SortRule,
+    (depth, parent): (usize, Option<Uuid>),
+    (workspace, workspace_path): (Uuid, &str),
+    options: VisitOptions,
+    visit: &mut impl FnMut(Visit),
+) {
+    if options.max_depth.is_some_and(|max| depth > max) {
+        return;
+    }
+    for task in rule.arrange(tasks) {
+        let task = task.borrow();
+        if !options.deprecated && task.status == TaskStatus::Deprecated {
+            continue;
+        }
+        visit(Visit {
+            task: &task,
+            depth,
+            parent,
+            workspace,
+            workspace_path,
+        });
+        visit_level(
+            &task.children,
+            rule,
+            (depth + 1, Some(task.id)),
+            (workspace, workspace_path),
+            options,
+            visit,
+        );
+    }
+}
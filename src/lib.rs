@@ -0,0 +1,45 @@
+//! A Tui Todo Application Based on Ratatui
+//!
+//! The library side of the application, shared by the `todo` binary and the benchmarks
+//! under `benches/`. Everything lives in the [`app`] module: the data model in
+//! [`app::data`] and the widgets, the terminal UI in [`app::ui`] and the [`app::App`]
+//! which ties them together.
+//!
+//! Another frontend can be built on the same data model without the terminal UI. The types
+//! and functions it needs are re-exported here:
+//!
+//! - the data model, [`Datas`] holding the [`Workspace`]s and their [`TodoList`]s of
+//!   [`Task`]s, read and written with [`load_data`] and [`save_data`] at [`data_path`]
+//! - the headless action layer, [`reduce`] deciding what a [`Message`] does to the
+//!   [`AppState`], and [`dispatch`] running the [`Command`]s of `todo serve` on the data
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{cell::RefCell, rc::Rc};
+//! use todo::{Datas, Task, TaskStatus, TodoList, Workspace};
+//!
+//! let mut datas = Datas::default();
+//! let work = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+//! let mut list = TodoList::new(work.borrow().id);
+//! let task = Rc::new(RefCell::new(Task::new("Write".to_string(), None)));
+//! list.add_task(task.clone());
+//! datas.workspace.add_workspace(work);
+//! datas.todolist.add_list(Rc::new(RefCell::new(list)));
+//!
+//! assert_eq!(task.borrow().status, TaskStatus::Todo);
+//! ```
+
+pub mod app;
+
+pub use app::{
+    appstate::{AppState, CurrentFocus, CurrentMode, Message, StateChange},
+    data::{Datas, data_path, load_data, save_data},
+    errors::Errors,
+    reduce,
+    serve::{Command, Request, Response, dispatch},
+    ui::{
+        todolistwidget::{Task, TaskStatus, TodoList},
+        workspacewidget::Workspace,
+    },
+};
@@ -18,6 +18,48 @@
 //! cargo run
 //! ```
 //!
+//! To try it out on a generated dataset without touching your data file:
+//!
+//! ```bash
+//! cargo run -- --demo
+//! ```
+//!
+//! To see what changed since a copy of the data file was taken:
+//!
+//! ```bash
+//! cargo run -- diff ~/data-copy.json
+//! ```
+//!
+//! Scripts and editor plugins can drive the data file with JSON requests, one per line:
+//!
+//! ```bash
+//! echo '{"cmd":"list_tasks"}' | cargo run -- serve --stdio
+//! ```
+//!
+//! To add the tasks of a Markdown checklist to a workspace:
+//!
+//! ```bash
+//! cargo run -- import-md notes.md --workspace Home
+//! ```
+//!
+//! Or a task per line of a text file, the indented lines as subtasks:
+//!
+//! ```bash
+//! cargo run -- import-txt list.txt --workspace Home
+//! ```
+//!
+//! To print the version, the git commit it was built from and the data format version:
+//!
+//! ```bash
+//! cargo run -- --version
+//! ```
+//!
+//! To look for broken data in the data file, and fix it:
+//!
+//! ```bash
+//! cargo run -- check --repair
+//! ```
+//!
 //! The application will start in your terminal and provide keyboard-driven controls for
 //! managing your tasks and workspaces.
 //!
@@ -27,7 +69,7 @@
 //! [`app::App`] and running it. It handles any errors that may occur during execution
 //! and displays them to the user.
 
-pub mod app;
+use todo::app;
 
 /// The main entry point for the Rust Todo application.
 ///
@@ -46,13 +88,168 @@ pub mod app;
 /// # Errors
 ///
 /// If the application encounters an error during execution, it will be printed to stdout
-/// in the format: "The app end with error: {:?}", err
+/// in the format: "The app end with error: {}", err
 pub fn main() {
-    let app = app::App::new();
+    let cli = match app::cli::Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("todo: {}\n\n{}", err, app::cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if cli.help {
+        print!("{}", app::cli::USAGE);
+        return;
+    }
+    // before anything reads the data file or the configuration
+    if cli.version {
+        println!("{}", app::about::BuildInfo::current().version_text());
+        return;
+    }
+    if let Some((snapshot, current)) = &cli.diff {
+        let current = current.clone().unwrap_or_else(|| app::data::data_path().0);
+        let read = |path: &std::path::Path| {
+            app::diff::read_datas(path).unwrap_or_else(|err| {
+                eprintln!("todo: {}: {}", path.display(), err);
+                std::process::exit(1);
+            })
+        };
+        let (old, new) = (read(snapshot), read(&current));
+        print!("{}", app::diff::diff(&old, &new));
+        return;
+    }
+    if cli.serve {
+        let config = app::config::load_config(&app::config::config_path());
+        let stale = app::ui::todolistwidget::Staleness::new(
+            &app::sources::SystemClock,
+            config.stale_after_days,
+        );
+        let (input, output) = (std::io::stdin().lock(), std::io::stdout().lock());
+        if let Err(err) = app::serve::serve(&app::data::data_path().0, stale, input, output) {
+            eprintln!("todo: {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+    let import = match (&cli.import_md, &cli.import_txt) {
+        (Some(args), _) => Some((args, true)),
+        (None, Some(args)) => Some((args, false)),
+        (None, None) => None,
+    };
+    if let Some(((file, workspace), markdown)) = import {
+        let text = std::fs::read_to_string(file)
+            .unwrap_or_else(|err| fail(format!("{}: {}", file.display(), err)));
+        let path = app::data::data_path().0;
+        // held from the read to the write, so a running application or serve can't write in
+        // between
+        let _lock = app::data::lock_data(&path).ok();
+        let mut datas = app::data::load_data(&path).unwrap_or_else(|err| fail(err));
+        let imported = if markdown {
+            app::markdown::import(&mut datas, workspace.as_deref(), &text, cli.on_clash)
+        } else {
+            app::plaintext::import(&mut datas, workspace.as_deref(), &text, cli.on_clash)
+        };
+        let (workspace, imported) = imported.unwrap_or_else(|err| fail(err));
+        if let Err(err) = app::data::save_data(&path, &datas) {
+            fail(err);
+        }
+        println!("Imported {} tasks into '{}'", imported, workspace);
+        return;
+    }
+    if let Some((text, workspace, multi)) = &cli.add {
+        let config = app::config::load_config(&app::config::config_path());
+        let delimiter = multi.then_some(config.task_delimiter);
+        let path = app::data::data_path().0;
+        let _lock = app::data::lock_data(&path).ok();
+        let mut datas = app::data::load_data(&path).unwrap_or_else(|err| fail(err));
+        let (workspace, added) =
+            app::plaintext::add(&mut datas, workspace.as_deref(), text, delimiter)
+                .unwrap_or_else(|err| fail(err));
+        if let Err(err) = app::data::save_data(&path, &datas) {
+            fail(err);
+        }
+        println!("Added {} tasks to '{}'", added, workspace);
+        return;
+    }
+    if let Some(repair) = cli.check {
+        let path = app::data::data_path().0;
+        if !path.exists() {
+            println!("{}: no data file yet, nothing to check", path.display());
+            return;
+        }
+        // only a repair writes, but it must write what it read
+        let _lock = repair.then(|| app::data::lock_data(&path).ok());
+        let content = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| fail(format!("{}: {}", path.display(), err)));
+        let (mut datas, mut problems) = app::check::parse(&content).unwrap_or_else(|err| {
+            fail(format!(
+                "{}: not a save of the data: {}",
+                path.display(),
+                err
+            ))
+        });
+        let archive = app::data::archive_path(&path);
+        if archive.exists() {
+            let content = std::fs::read_to_string(&archive)
+                .unwrap_or_else(|err| fail(format!("{}: {}", archive.display(), err)));
+            let (archived, archived_problems) =
+                app::check::parse_archive(&content).unwrap_or_else(|err| {
+                    fail(format!(
+                        "{}: not a save of the archive: {}",
+                        archive.display(),
+                        err
+                    ))
+                });
+            archived.merge_into(&mut datas.archived_ws, &mut datas.todolist);
+            problems.extend(archived_problems);
+        }
+        problems.extend(app::check::check_datas(&datas));
+        if problems.is_empty() {
+            println!("{}: no problems found", path.display());
+            return;
+        }
+        println!("{}: {} problems found", path.display(), problems.len());
+        problems
+            .iter()
+            .for_each(|problem| println!("  - {}", problem));
+        if !repair {
+            println!("run `todo check --repair` to fix them, the data file is backed up first");
+            std::process::exit(1);
+        }
+        let backup = app::check::backup_path(&path);
+        if let Err(err) = std::fs::copy(&path, &backup) {
+            fail(format!("{}: {}", backup.display(), err));
+        }
+        if archive.exists() {
+            let backup = app::check::backup_path(&archive);
+            if let Err(err) = std::fs::copy(&archive, &backup) {
+                fail(format!("{}: {}", backup.display(), err));
+            }
+        }
+        app::check::repair(&mut datas, &app::sources::RandomIds);
+        if let Err(err) = app::data::save_data(&path, &datas) {
+            fail(err);
+        }
+        println!(
+            "Repaired, the data file was backed up to {}",
+            backup.display()
+        );
+        return;
+    }
+    let app = app::App::with_cli(cli);
     let appresult = app.run();
     if let Err(err) = appresult {
-        println!("The app end with error: {:?}", err);
+        println!("The app end with error: {}", err);
+        if matches!(err, app::errors::Errors::LoadError) {
+            println!("run `todo check` to see what is wrong with the data file");
+        }
     }
 
     println!("The Application is End !");
 }
+
+/// Print the error of a command line action and exit with an error status
+fn fail(err: impl std::fmt::Display) -> ! {
+    eprintln!("todo: {}", err);
+    std::process::exit(1);
+}
@@ -0,0 +1,103 @@
+//! Tests of the version flag and the about popup
+
+mod common;
+
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use common::{key, rows, ui_with};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    about::{BuildInfo, DATA_FORMAT_VERSION, Profile},
+    appstate::{AppState, CurrentMode},
+    cli::Cli,
+    data::DataPathSource,
+    ui::{UiMessage, WidgetAction},
+};
+
+fn args(args: &[&str]) -> Result<Cli, String> {
+    Cli::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn the_version_flag_is_parsed_in_both_spellings() {
+    assert!(args(&["--version"]).unwrap().version);
+    assert!(args(&["-V"]).unwrap().version);
+    assert!(!args(&["--demo"]).unwrap().version);
+    assert!(args(&["--version", "extra"]).is_err());
+}
+
+#[test]
+fn the_version_text_names_the_crate_version_and_the_data_format() {
+    let info = BuildInfo::current();
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(info.data_format, DATA_FORMAT_VERSION);
+    let text = info.version_text();
+    assert!(
+        text.starts_with(&format!("todo {} (", info.version)),
+        "{}",
+        text
+    );
+    assert!(text.ends_with(&format!(", data format {})", DATA_FORMAT_VERSION)));
+
+    let unknown = BuildInfo {
+        commit: None,
+        ..info
+    };
+    assert_eq!(
+        unknown.version_text(),
+        format!("todo {} (unknown, data format 2)", info.version)
+    );
+}
+
+#[tokio::test]
+async fn the_about_popup_shows_the_build_and_the_data_file() {
+    let (mut ui, ui_tx, _input_tx) = ui_with(vec![], vec![]);
+    ui.data_path = PathBuf::from("/tmp/todo/tasks.json");
+    ui.profile = Profile::Data(DataPathSource::Env);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::About))
+        .await
+        .unwrap();
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    // no key comes, so the popup stays open
+    let open = tokio::time::timeout(
+        std::time::Duration::from_millis(200),
+        ui.handle_uimsg(&mut terminal, appstate),
+    )
+    .await;
+    assert!(open.is_err());
+
+    let rows = rows(&terminal);
+    let row = |label: &str| rows.iter().find(|row| row.contains(label)).unwrap().clone();
+    let info = BuildInfo::current();
+    assert!(row(" About ").contains(" About "));
+    assert!(row("Version").contains(info.version));
+    assert!(row("Commit").contains(info.commit_or_unknown()));
+    assert!(row("Data format").contains(&DATA_FORMAT_VERSION.to_string()));
+    assert!(row("Data file").contains("/tmp/todo/tasks.json"));
+    assert!(row("Profile").contains("data file set by TODO_DATA_PATH"));
+}
+
+#[tokio::test]
+async fn the_about_popup_closes_on_any_key() {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![], vec![]);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::About))
+        .await
+        .unwrap();
+    input_tx.send(key('x')).await.unwrap();
+    drop(ui_tx);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+
+    assert_eq!(appstate.lock().unwrap().current_mode, CurrentMode::Normal);
+    assert!(!ui.dirty);
+    assert!(!rows(&terminal).concat().contains("Data format"));
+}
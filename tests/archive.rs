@@ -0,0 +1,110 @@
+//! Tests of the confirmation and the feedback of archiving a workspace
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{key, ui_with, workspace};
+use crossterm::event::KeyEvent;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{Task, TaskStatus, TodoList},
+    },
+};
+use tokio::sync::mpsc;
+
+fn task(status: TaskStatus) -> Rc<RefCell<Task>> {
+    let mut task = Task::new("task".to_string(), None);
+    task.status = status;
+    Rc::new(RefCell::new(task))
+}
+
+/// Set up a `Backend` workspace with the tasks, and a `Docs` sub workspace with one open task
+fn backend(tasks: Vec<Rc<RefCell<Task>>>) -> (Ui, mpsc::Sender<UiMessage>, mpsc::Sender<KeyEvent>) {
+    let (ws, child) = (workspace("Backend"), workspace("Docs"));
+    ws.borrow_mut().add_child(child.clone());
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![ws], tasks);
+    let mut child_list = TodoList::new(child.borrow().id);
+    child_list.add_task(task(TaskStatus::InProcess));
+    ui.todolist.add_list(Rc::new(RefCell::new(child_list)));
+    (ui, ui_tx, input_tx)
+}
+
+/// Archive the current workspace with the keys queued for the popup
+async fn archive(
+    mut ui: Ui,
+    ui_tx: mpsc::Sender<UiMessage>,
+    input_tx: mpsc::Sender<KeyEvent>,
+    keys: &[char],
+) -> Ui {
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::ArchiveWS))
+        .await
+        .unwrap();
+    for c in keys {
+        input_tx.send(key(*c)).await.unwrap();
+    }
+    drop(ui_tx);
+    drop(input_tx);
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+    ui
+}
+
+#[test]
+fn open_tasks_count_the_sub_workspaces_but_not_finished_or_deprecated() {
+    let (ui, _, _) = backend(vec![
+        task(TaskStatus::Todo),
+        task(TaskStatus::Finished),
+        task(TaskStatus::Deprecated),
+    ]);
+    let ws = ui.workspace.current_workspace.clone().unwrap();
+    assert_eq!(ui.todolist.open_tasks(&ws), 2);
+    assert_eq!(Ui::open_tasks_label(1), "1 open task");
+}
+
+#[tokio::test]
+async fn declining_keeps_the_workspace() {
+    let (ui, ui_tx, input_tx) = backend(vec![task(TaskStatus::Todo)]);
+    let ui = archive(ui, ui_tx, input_tx, &['n']).await;
+
+    assert_eq!(ui.workspace.workspaces.len(), 1);
+    assert!(ui.archived_ws.workspaces.is_empty());
+    assert_eq!(ui.prompt.desc, "Archive Cancelled !");
+}
+
+#[tokio::test]
+async fn confirming_archives_and_says_how_many_tasks_were_open() {
+    let (ui, ui_tx, input_tx) = backend(vec![task(TaskStatus::Todo), task(TaskStatus::Todo)]);
+    let ui = archive(ui, ui_tx, input_tx, &['y']).await;
+
+    assert!(ui.workspace.workspaces.is_empty());
+    assert_eq!(ui.archived_ws.workspaces[0].borrow().desc, "Backend");
+    assert_eq!(ui.prompt.desc, "Archived 'Backend' (3 open tasks)");
+}
+
+#[tokio::test]
+async fn no_popup_without_open_tasks_or_when_disabled() {
+    let (mut ui, ui_tx, input_tx) = backend(vec![task(TaskStatus::Finished)]);
+    // nothing open once the sub workspace task is done too
+    let child = ui.workspace.workspaces[0].borrow().children[0].clone();
+    let child_list = ui.todolist.list_of(child.borrow().id).unwrap();
+    child_list.borrow().tasks[0].borrow_mut().status = TaskStatus::Finished;
+    ui.config.confirm_archive = true;
+    let ui = archive(ui, ui_tx, input_tx, &[]).await;
+    assert_eq!(ui.prompt.desc, "Archived 'Backend'");
+
+    let (mut ui, ui_tx, input_tx) = backend(vec![task(TaskStatus::Todo)]);
+    ui.config.confirm_archive = false;
+    let ui = archive(ui, ui_tx, input_tx, &[]).await;
+    assert_eq!(ui.archived_ws.workspaces.len(), 1);
+    assert_eq!(ui.prompt.desc, "Archived 'Backend' (2 open tasks)");
+}
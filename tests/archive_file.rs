@@ -0,0 +1,157 @@
+//! Tests of the archive file saved apart from the data file
+
+mod common;
+
+use std::{cell::RefCell, fs, path::PathBuf, rc::Rc};
+
+use common::ui_with;
+use todo::app::{
+    data::{self, Datas},
+    ui::{Ui, workspacewidget::Workspace},
+};
+use uuid::Uuid;
+
+/// A `data.json` in a directory of its own
+fn data_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("todo-archive-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("data.json")
+}
+
+fn archived_ids(datas: &Datas) -> Vec<Uuid> {
+    datas
+        .archived_ws
+        .workspaces
+        .iter()
+        .map(|ws| ws.borrow().id)
+        .collect()
+}
+
+#[test]
+fn the_archived_workspaces_go_with_their_lists() {
+    let datas = data::demo_datas();
+    let archived = archived_ids(&datas);
+    assert!(!archived.is_empty());
+
+    let (active, archive) = data::split_archive(&datas);
+
+    assert!(active.archived_ws.workspaces.is_empty());
+    assert_eq!(
+        archive.archived_ws.workspaces.len(),
+        datas.archived_ws.workspaces.len()
+    );
+    assert!(
+        active
+            .todolist
+            .todolists
+            .iter()
+            .all(|list| !archived.contains(&list.borrow().workspace))
+    );
+    assert!(
+        archive
+            .todolists
+            .iter()
+            .all(|list| archived.contains(&list.borrow().workspace))
+    );
+    assert_eq!(
+        active.todolist.todolists.len() + archive.todolists.len(),
+        datas.todolist.todolists.len()
+    );
+}
+
+#[test]
+fn the_archive_is_read_only_when_asked_for() {
+    let path = data_file("lazy");
+    let datas = data::demo_datas();
+    data::save_data(&path, &datas).unwrap();
+    assert!(data::archive_path(&path).exists());
+
+    let active = data::load_active(&path).unwrap();
+    assert!(active.archived_ws.unread);
+    assert!(active.archived_ws.workspaces.is_empty());
+
+    let all = data::load_data(&path).unwrap();
+    assert!(!all.archived_ws.unread);
+    assert_eq!(archived_ids(&all), archived_ids(&datas));
+    assert_eq!(all.todolist.todolists.len(), datas.todolist.todolists.len());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn a_data_file_with_the_archive_inside_is_split_on_save() {
+    let path = data_file("legacy");
+    let datas = data::demo_datas();
+    fs::write(&path, serde_json::to_string_pretty(&datas).unwrap()).unwrap();
+
+    let legacy = data::load_active(&path).unwrap();
+    assert!(!legacy.archived_ws.unread);
+    assert_eq!(archived_ids(&legacy), archived_ids(&datas));
+
+    data::save_data(&path, &legacy).unwrap();
+
+    let saved: Datas = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert!(saved.archived_ws.workspaces.is_empty());
+    assert_eq!(
+        archived_ids(&data::load_data(&path).unwrap()),
+        archived_ids(&datas)
+    );
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn saving_without_reading_the_archive_leaves_it_alone() {
+    let path = data_file("unread");
+    let datas = data::demo_datas();
+    data::save_data(&path, &datas).unwrap();
+    let archive = fs::read_to_string(data::archive_path(&path)).unwrap();
+
+    let mut active = data::load_active(&path).unwrap();
+    active
+        .workspace
+        .add_workspace(Rc::new(RefCell::new(Workspace::new("New".to_string()))));
+    data::save_data(&path, &active).unwrap();
+    data::save_data_on_exit(&path, &active, None).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(data::archive_path(&path)).unwrap(),
+        archive
+    );
+    let all = data::load_data(&path).unwrap();
+    assert_eq!(archived_ids(&all), archived_ids(&datas));
+    assert!(
+        all.workspace
+            .workspaces
+            .iter()
+            .any(|ws| ws.borrow().desc == "New")
+    );
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn the_ui_writes_the_archive_only_once_read_and_changed() {
+    let path = data_file("ui");
+    data::save_data(&path, &data::demo_datas()).unwrap();
+    let (mut ui, _, _) = ui_with(vec![], vec![]);
+    let active = data::load_active(&path).unwrap();
+    ui.data_path = path.clone();
+    ui.workspace = active.workspace;
+    ui.todolist = active.todolist;
+    ui.archived_ws = active.archived_ws;
+    let datas = |ui: &Ui| Datas {
+        workspace: ui.workspace.clone(),
+        todolist: ui.todolist.clone(),
+        archived_ws: ui.archived_ws.clone(),
+    };
+
+    assert!(ui.archive_content(&datas(&ui)).is_none());
+    ui.load_archive();
+    assert!(!ui.archived_ws.unread);
+    assert!(!ui.archived_ws.workspaces.is_empty());
+    assert!(ui.archive_content(&datas(&ui)).is_none());
+
+    ui.archived_ws
+        .add_workspace(Rc::new(RefCell::new(Workspace::new("Old".to_string()))));
+    assert!(ui.archive_content(&datas(&ui)).is_some());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
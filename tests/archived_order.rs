@@ -0,0 +1,142 @@
+//! Tests of the order of the archived workspaces, sorted by name or moved with `J`/`K`
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{ui_with, ws_node};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    data::{self, Archive},
+    ui::{
+        SelectBF, UiMessage, WidgetAction,
+        todolistwidget::TodoWidget,
+        workspacewidget::{Workspace, WorkspaceOrder, WorkspaceType, WorkspaceWidget},
+    },
+};
+
+/// `Taxes` with `2024` and `2023`, `garden`, then `Move`
+fn archived() -> WorkspaceWidget {
+    let mut widget = WorkspaceWidget::new(WorkspaceType::Archived);
+    widget.add_workspace(ws_node(
+        "Taxes",
+        vec![ws_node("2024", vec![]), ws_node("2023", vec![])],
+    ));
+    widget.add_workspace(ws_node("garden", vec![]));
+    widget.add_workspace(ws_node("Move", vec![]));
+    widget
+}
+
+/// The names of the workspaces from the top to the bottom of the panel
+fn names(widget: &WorkspaceWidget) -> Vec<String> {
+    fn walk(level: &[Rc<RefCell<Workspace>>], names: &mut Vec<String>) {
+        for ws in level {
+            names.push(ws.borrow().desc.clone());
+            walk(&ws.borrow().children, names);
+        }
+    }
+    let mut names = Vec::new();
+    walk(&widget.workspaces, &mut names);
+    names
+}
+
+#[test]
+fn the_name_order_sorts_every_level_and_is_saved_with_the_archive() {
+    let mut widget = archived();
+    widget.toggle_order();
+    assert_eq!(names(&widget), ["garden", "Move", "Taxes", "2023", "2024"]);
+
+    let content = data::serialize_archive(&Archive {
+        archived_ws: widget,
+        todolists: Vec::new(),
+    });
+    let archive: Archive = serde_json::from_str(&content).unwrap();
+    let mut loaded = WorkspaceWidget::new(WorkspaceType::Archived);
+    archive.merge_into(&mut loaded, &mut TodoWidget::new());
+    assert_eq!(loaded.order, WorkspaceOrder::Name);
+
+    // archived while sorted by name, it goes after the others once switched back
+    let mut moved = Workspace::new("Attic".to_string());
+    moved.expanded = false;
+    let mut active = WorkspaceWidget::new(WorkspaceType::Normal);
+    active.add_workspace(Rc::new(RefCell::new(moved)));
+    active.select(active.workspaces.first().cloned());
+    active.transfer_current(&mut loaded);
+    assert_eq!(names(&loaded)[0], "Attic");
+
+    assert_eq!(loaded.toggle_order(), WorkspaceOrder::Saved);
+    assert_eq!(
+        names(&loaded),
+        ["Taxes", "2024", "2023", "garden", "Move", "Attic"]
+    );
+    assert!(loaded.saved_order.is_empty());
+}
+
+#[test]
+fn a_workspace_moves_among_its_siblings_only() {
+    let mut widget = archived();
+    let taxes = widget.workspaces[0].clone();
+    let older = taxes.borrow().children[1].clone();
+
+    widget.select(Some(older.clone()));
+    assert!(widget.shift_current(SelectBF::Back));
+    assert_eq!(names(&widget), ["Taxes", "2023", "2024", "garden", "Move"]);
+    assert!(!widget.shift_current(SelectBF::Back));
+
+    widget.select(Some(taxes));
+    assert!(!widget.shift_current(SelectBF::Back));
+    assert!(widget.shift_current(SelectBF::Forward));
+    assert!(widget.shift_current(SelectBF::Forward));
+    assert!(!widget.shift_current(SelectBF::Forward));
+    assert_eq!(names(&widget), ["garden", "Move", "Taxes", "2023", "2024"]);
+}
+
+#[test]
+fn the_title_counts_the_archived_workspaces_and_a_scrollbar_shows_the_rest() {
+    let mut widget = archived();
+    let mut terminal = Terminal::new(TestBackend::new(24, 7)).unwrap();
+    terminal
+        .draw(|f| f.render_widget(&mut widget, f.area()))
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    let title: String = (0..24).map(|x| buffer[(x, 0)].symbol()).collect();
+    assert!(title.contains(" <2> Archived (5) "), "{}", title);
+    // 5 rows in a panel showing 3, the thumb is drawn on the right border
+    let right: String = (1..6).map(|y| buffer[(23, y)].symbol()).collect();
+    assert!(right.contains('█'), "{}", right);
+}
+
+#[tokio::test]
+async fn the_saved_order_is_kept_while_sorted_by_name() {
+    let (mut ui, ui_tx, _) = ui_with(vec![], vec![]);
+    ui.archived_ws = archived();
+    let garden = ui.archived_ws.workspaces[1].clone();
+    ui.archived_ws.select(Some(garden));
+    for action in [
+        WidgetAction::ToggleArchivedOrder,
+        WidgetAction::ShiftWorkspace(WorkspaceType::Archived, SelectBF::Back),
+    ] {
+        ui_tx.send(UiMessage::WAction(action)).await.unwrap();
+    }
+    drop(ui_tx);
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, appstate).await;
+
+    assert_eq!(ui.archived_ws.order, WorkspaceOrder::Name);
+    assert_eq!(names(&ui.archived_ws)[0], "garden");
+    assert_eq!(
+        ui.prompt.desc,
+        "Sorted By Name, s Switches To The Saved Order !"
+    );
+    assert!(ui.dirty);
+
+    ui.archived_ws.toggle_order();
+    assert!(ui.archived_ws.shift_current(SelectBF::Back));
+    assert_eq!(names(&ui.archived_ws)[0], "garden");
+}
@@ -0,0 +1,94 @@
+//! Tests of the bell rung on the due reminders and the failed saves
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{day, ui_with, workspace};
+use ratatui::{Terminal, TerminalOptions, Viewport, backend::CrosstermBackend, layout::Rect};
+use todo::app::{
+    appstate::AppState,
+    config::Config,
+    sources::FixedClock,
+    ui::{Ui, UiMessage, bell::BEL, theme::ThemedBackend, todolistwidget::Task},
+};
+use tokio::sync::mpsc;
+
+/// A `Work` workspace with `Review` due on the 3rd
+fn ui(bell: bool) -> (Ui, mpsc::Sender<UiMessage>) {
+    let review = Rc::new(RefCell::new(Task::new("Review".to_string(), Some(day(3)))));
+    let (mut ui, ui_tx, _) = ui_with(vec![workspace("Work")], vec![review]);
+    ui.config.bell = bell;
+    (ui, ui_tx)
+}
+
+/// What the backend writes to the terminal
+#[derive(Clone, Default)]
+struct Written(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Written {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn the_bell_is_off_unless_configured() {
+    assert!(!Config::default().bell);
+    let config: Config = serde_json::from_str(r#"{"bell": true}"#).unwrap();
+    assert!(config.bell);
+
+    let (mut ui, _) = ui(false);
+    ui.announce_due(&FixedClock(day(3)));
+    assert!(ui.banner.is_some());
+    assert!(!ui.bell);
+}
+
+#[test]
+fn a_due_reminder_asks_for_the_bell_once() {
+    let (mut ui, _) = ui(true);
+
+    ui.announce_due(&FixedClock(day(2)));
+    assert!(!ui.bell);
+    ui.announce_due(&FixedClock(day(3)));
+    assert!(ui.bell);
+
+    // the task was announced today already
+    ui.bell = false;
+    ui.announce_due(&FixedClock(day(3)));
+    assert!(!ui.bell);
+}
+
+#[tokio::test]
+async fn the_bell_is_written_through_the_backend_after_the_frame() {
+    let (mut ui, ui_tx) = ui(true);
+    ui.announce_due(&FixedClock(day(3)));
+    drop(ui_tx);
+    let written = Written::default();
+    let backend = ThemedBackend::new(CrosstermBackend::new(written.clone()));
+    let viewport = Viewport::Fixed(Rect::new(0, 0, 80, 24));
+    let mut terminal = Terminal::with_options(backend, TerminalOptions { viewport }).unwrap();
+
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+
+    assert!(!ui.bell);
+    let written = written.0.lock().unwrap();
+    let bell = written
+        .windows(BEL.len())
+        .position(|window| window == BEL)
+        .unwrap();
+    // the frame is written first
+    assert!(bell > 0);
+    assert_eq!(written.windows(BEL.len()).filter(|w| *w == BEL).count(), 1);
+}
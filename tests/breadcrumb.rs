@@ -0,0 +1,65 @@
+//! Tests of the breadcrumb above the todo list
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::task;
+use todo::app::ui::{
+    glyphs,
+    todolistwidget::{Task, TodoList, TodoWidget},
+    workspacewidget::Workspace,
+};
+
+/// A widget showing `Project` with the tasks `Release` > `Docs` > `Changelog`
+fn widget() -> (TodoWidget, Vec<Rc<RefCell<Task>>>) {
+    glyphs::select(true);
+    let ws = Rc::new(RefCell::new(Workspace::new("Project".to_string())));
+    let (release, docs, changelog) = (task("Release"), task("Docs"), task("Changelog"));
+    docs.borrow_mut().add_child(changelog.clone());
+    release.borrow_mut().add_child(docs.clone());
+    let mut list = TodoList::new(ws.borrow().id);
+    list.add_task(release.clone());
+
+    let mut widget = TodoWidget::new();
+    widget.add_list(Rc::new(RefCell::new(list)));
+    widget.change_current_list(&Some(ws));
+    (widget, vec![release, docs, changelog])
+}
+
+fn select(widget: &TodoWidget, task: &Rc<RefCell<Task>>) {
+    widget
+        .current_todolist
+        .as_ref()
+        .unwrap()
+        .borrow_mut()
+        .current_task = Some(task.clone());
+}
+
+#[test]
+fn top_level_task_shows_the_workspace() {
+    let (widget, tasks) = widget();
+    assert_eq!(widget.breadcrumb(80), "Project");
+    select(&widget, &tasks[0]);
+    assert_eq!(widget.breadcrumb(80), "Project");
+}
+
+#[test]
+fn nested_task_shows_its_ancestry() {
+    let (widget, tasks) = widget();
+    select(&widget, &tasks[2]);
+    assert_eq!(widget.breadcrumb(80), "Release > Docs > Changelog");
+}
+
+#[test]
+fn narrow_header_cuts_from_the_left() {
+    let (widget, tasks) = widget();
+    select(&widget, &tasks[2]);
+    assert_eq!(widget.breadcrumb(12), "…> Changelog");
+    assert_eq!(widget.breadcrumb(0), "");
+}
+
+#[test]
+fn no_list_no_breadcrumb() {
+    assert_eq!(TodoWidget::new().breadcrumb(80), "");
+}
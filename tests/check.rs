@@ -0,0 +1,311 @@
+//! Tests of the integrity check of the data file
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use chrono::NaiveDate;
+use common::{code, ui_with};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    check::{self, Problem},
+    cli::Cli,
+    data::{self, Datas},
+    errors::Errors,
+    sources::SequentialIds,
+    ui::{
+        todolistwidget::{Task, TodoList},
+        workspacewidget::Workspace,
+    },
+};
+use uuid::Uuid;
+
+/// `Work` holding a selected `Write` with a `Review` subtask, and a `Home` workspace
+fn sound() -> Datas {
+    let ids = SequentialIds::new(1);
+    let mut datas = Datas::default();
+    let work = Rc::new(RefCell::new(Workspace::with_ids("Work".to_string(), &ids)));
+    let home = Rc::new(RefCell::new(Workspace::with_ids("Home".to_string(), &ids)));
+    let due = NaiveDate::from_ymd_opt(2025, 7, 1);
+    let write = Rc::new(RefCell::new(Task::with_ids("Write".to_string(), due, &ids)));
+    let review = Task::with_ids("Review".to_string(), None, &ids);
+    write.borrow_mut().add_child(Rc::new(RefCell::new(review)));
+    let mut list = TodoList::new(work.borrow().id);
+    list.add_task(write.clone());
+    list.current_task = Some(write);
+    datas.todolist.add_list(Rc::new(RefCell::new(list)));
+    datas.workspace.add_workspace(work.clone());
+    datas.workspace.add_workspace(home);
+    datas.workspace.current_workspace = Some(work);
+    datas
+}
+
+#[test]
+fn sound_data_has_no_problems() {
+    let datas = sound();
+    assert!(check::check_datas(&datas).is_empty());
+    let (datas, problems) = check::parse(&data::serialize_data(&datas)).unwrap();
+    assert!(problems.is_empty());
+    assert!(check::check_datas(&datas).is_empty());
+    assert!(check::check_datas(&data::demo_datas()).is_empty());
+}
+
+#[test]
+fn a_due_date_which_isnt_a_date_is_reported_once_and_cleared() {
+    let content = data::serialize_data(&sound()).replace("2025-07-01", "2025-13-45");
+    assert!(serde_json::from_str::<Datas>(&content).is_err());
+
+    let (datas, problems) = check::parse(&content).unwrap();
+
+    // the selected task is saved twice, in the list and as the selection
+    assert_eq!(
+        problems,
+        [Problem::BadDue {
+            task: "Write".to_string(),
+            due: "2025-13-45".to_string()
+        }]
+    );
+    let list = datas.todolist.todolists[0].borrow();
+    assert_eq!(list.tasks[0].borrow().due, None);
+}
+
+#[test]
+fn content_which_isnt_a_save_is_refused() {
+    assert!(check::parse("{\"workspace\": ").is_err());
+    assert!(check::parse("[1, 2]").is_err());
+}
+
+#[test]
+fn broken_invariants_are_found_and_repaired() {
+    let mut datas = sound();
+    let write = datas.todolist.todolists[0].borrow().tasks[0].clone();
+    let write_id = write.borrow().id;
+    // a copy of Write keeping its id
+    let mut twin = Task::new("Twin".to_string(), None);
+    twin.id = write_id;
+    datas.todolist.todolists[0]
+        .borrow_mut()
+        .add_task(Rc::new(RefCell::new(twin)));
+    // a list whose workspace is gone, with its selection
+    let gone = Uuid::from_u128(99);
+    let mut orphan = TodoList::new(gone);
+    orphan.add_task(Rc::new(RefCell::new(Task::new("Lost".to_string(), None))));
+    let orphan = Rc::new(RefCell::new(orphan));
+    datas.todolist.add_list(orphan.clone());
+    datas.todolist.current_todolist = Some(orphan);
+    // a selected task which isn't in its list
+    datas.todolist.todolists[0].borrow_mut().current_task =
+        Some(Rc::new(RefCell::new(Task::new("Gone".to_string(), None))));
+
+    let problems = check::check_datas(&datas);
+
+    assert_eq!(
+        problems,
+        [
+            Problem::DuplicateTask {
+                id: write_id,
+                desc: "Twin".to_string(),
+                workspace: "Work".to_string()
+            },
+            Problem::OrphanList {
+                workspace: gone,
+                tasks: 1
+            },
+            Problem::DanglingSelection("the task 'Gone' in Work".to_string()),
+        ]
+    );
+    assert!(problems[1].to_string().contains("holds 1 tasks"));
+
+    check::repair(&mut datas, &SequentialIds::new(1000));
+
+    assert!(check::check_datas(&datas).is_empty());
+    assert_eq!(datas.todolist.todolists.len(), 1);
+    // the open list went with the orphans
+    assert!(datas.todolist.current_todolist.is_none());
+    assert!(datas.todolist.todolists[0].borrow().current_task.is_none());
+    // the first one keeps the id, the copy gets a new one
+    assert_eq!(write.borrow().id, write_id);
+    let twin = datas.todolist.todolists[0].borrow().tasks[1].clone();
+    assert_eq!(twin.borrow().id, Uuid::from_u128(1000));
+}
+
+#[test]
+fn a_duplicate_workspace_gets_a_new_id_without_the_list() {
+    let mut datas = sound();
+    let work = datas.workspace.workspaces[0].clone();
+    let work_id = work.borrow().id;
+    let mut copy = Workspace::new("Copy".to_string());
+    copy.id = work_id;
+    let copy = Rc::new(RefCell::new(copy));
+    datas.archived_ws.add_workspace(copy.clone());
+
+    assert_eq!(
+        check::check_datas(&datas),
+        [Problem::DuplicateWorkspace {
+            id: work_id,
+            desc: "Copy".to_string()
+        }]
+    );
+    check::repair(&mut datas, &SequentialIds::new(1000));
+
+    assert!(check::check_datas(&datas).is_empty());
+    assert_eq!(work.borrow().id, work_id);
+    assert_eq!(copy.borrow().id, Uuid::from_u128(1000));
+    assert!(datas.todolist.list_of(work_id).is_some());
+}
+
+#[test]
+fn only_the_duplicates_are_repaired_on_start_up() {
+    let mut datas = sound();
+    let write = datas.todolist.todolists[0].borrow().tasks[0].clone();
+    let mut twin = Task::new("Twin".to_string(), None);
+    twin.id = write.borrow().id;
+    let twin = Rc::new(RefCell::new(twin));
+    write.borrow_mut().add_child(twin.clone());
+    let work_id = datas.workspace.workspaces[0].borrow().id;
+    datas.workspace.workspaces[1].borrow_mut().id = work_id;
+    datas.workspace.current_workspace =
+        Some(Rc::new(RefCell::new(Workspace::new("Old".to_string()))));
+
+    let problems = check::check_datas(&datas);
+    assert_eq!(
+        problems.iter().filter(|p| p.is_duplicate()).count(),
+        2,
+        "{:?}",
+        problems
+    );
+    assert_eq!(
+        check::repair_duplicates(&mut datas, &SequentialIds::new(1000)),
+        2
+    );
+
+    assert_eq!(datas.workspace.workspaces[0].borrow().id, work_id);
+    assert_eq!(
+        datas.workspace.workspaces[1].borrow().id,
+        Uuid::from_u128(1000)
+    );
+    assert_eq!(twin.borrow().id, Uuid::from_u128(1001));
+    // the rest is left to todo check --repair
+    assert_eq!(
+        check::check_datas(&datas),
+        [Problem::DanglingSelection(
+            "the workspace 'Old'".to_string()
+        )]
+    );
+    assert_eq!(
+        check::repair_duplicates(&mut datas, &SequentialIds::new(1000)),
+        0
+    );
+}
+
+#[tokio::test]
+async fn the_duplicates_get_new_ids_once_confirmed_and_are_saved() {
+    for (key, renewed) in [(KeyCode::Char('y'), true), (KeyCode::Esc, false)] {
+        let (mut ui, _, input_tx) = ui_with(vec![], vec![]);
+        let datas = sound();
+        let write = datas.todolist.todolists[0].borrow().tasks[0].clone();
+        let review = write.borrow().children[0].clone();
+        review.borrow_mut().id = write.borrow().id;
+        ui.workspace = datas.workspace;
+        ui.todolist = datas.todolist;
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        input_tx.send(code(key)).await.unwrap();
+
+        let rx = ui.input_rx.clone();
+        if ui.confirm_renew_ids(rx, &mut terminal, 1).await {
+            ui.renew_duplicate_ids(&SequentialIds::new(1000));
+        }
+
+        assert_eq!(review.borrow().id == Uuid::from_u128(1000), renewed);
+        assert_eq!(ui.dirty, renewed);
+        assert_eq!(write.borrow().id, Uuid::from_u128(3));
+    }
+}
+
+#[test]
+fn a_selection_of_a_gone_workspace_is_cleared() {
+    let mut datas = sound();
+    datas.workspace.current_workspace =
+        Some(Rc::new(RefCell::new(Workspace::new("Old".to_string()))));
+
+    assert_eq!(
+        check::check_datas(&datas),
+        [Problem::DanglingSelection(
+            "the workspace 'Old'".to_string()
+        )]
+    );
+    check::repair(&mut datas, &SequentialIds::new(1000));
+    assert!(datas.workspace.current_workspace.is_none());
+}
+
+#[test]
+fn check_is_a_command_with_an_optional_repair() {
+    assert_eq!(
+        Cli::parse(["check".to_string()]).unwrap().check,
+        Some(false)
+    );
+    let repair = Cli::parse(["check".to_string(), "--repair".to_string()]).unwrap();
+    assert_eq!(repair.check, Some(true));
+    assert!(Cli::parse(["check".to_string(), "--fix".to_string()]).is_err());
+    assert_eq!(Cli::parse([]).unwrap().check, None);
+    assert_eq!(
+        check::backup_path(Path::new("/data/todo/data.json")),
+        Path::new("/data/todo/data.json.bak")
+    );
+}
+
+/// A data file in a directory of its own holding `content`
+fn data_file(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("todo-check-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("data.json");
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn start_up_clears_a_bad_due_date_and_reports_it() {
+    let content = data::serialize_data(&sound()).replace("2025-07-01", "soon");
+    let path = data_file("start-due", &content);
+
+    let (datas, problems) = check::load(&path).unwrap();
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    assert_eq!(
+        problems,
+        [Problem::BadDue {
+            task: "Write".to_string(),
+            due: "soon".to_string()
+        }]
+    );
+    let list = datas.todolist.todolists[0].borrow();
+    assert_eq!(list.tasks[0].borrow().desc, "Write");
+    assert_eq!(list.tasks[0].borrow().due, None);
+}
+
+#[test]
+fn start_up_fails_on_a_file_which_isnt_a_save_without_panicking() {
+    let path = data_file("start-malformed", "{\"workspace\": [");
+
+    let loaded = check::load(&path);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    assert!(matches!(loaded, Err(Errors::LoadError)));
+}
+
+#[test]
+fn start_up_loads_a_sound_file_without_problems() {
+    let path = data_file("start-sound", &data::serialize_data(&sound()));
+
+    let (datas, problems) = check::load(&path).unwrap();
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    assert!(problems.is_empty());
+    assert_eq!(datas.workspace.workspaces.len(), 2);
+}
@@ -0,0 +1,122 @@
+//! Tests of the deletion of the finished subtasks of a task
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{code, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    ui::{
+        SelectAction, UiMessage, WidgetAction,
+        todolistwidget::{Task, TaskStatus, TodoWidget},
+    },
+};
+
+fn task(desc: &str, status: TaskStatus, children: Vec<Rc<RefCell<Task>>>) -> Rc<RefCell<Task>> {
+    let mut task = Task::new(desc.to_string(), None);
+    task.status = status;
+    children.into_iter().for_each(|child| task.add_child(child));
+    Rc::new(RefCell::new(task))
+}
+
+/// `Release` with a finished and a deprecated subtask, an open one, and a finished one holding
+/// a finished and an open subtask
+fn release() -> Rc<RefCell<Task>> {
+    task(
+        "Release",
+        TaskStatus::Todo,
+        vec![
+            task("Tag", TaskStatus::Finished, vec![]),
+            task("Blog", TaskStatus::Deprecated, vec![]),
+            task("Notes", TaskStatus::InProcess, vec![]),
+            task(
+                "Build",
+                TaskStatus::Finished,
+                vec![
+                    task("Linux", TaskStatus::Finished, vec![]),
+                    task("Mac", TaskStatus::Todo, vec![]),
+                ],
+            ),
+        ],
+    )
+}
+
+fn descs(tasks: &[Rc<RefCell<Task>>]) -> Vec<String> {
+    TodoWidget::get_flattened(tasks)
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+#[test]
+fn the_done_subtasks_go_and_the_open_ones_keep_their_place() {
+    let release = release();
+    assert_eq!(release.borrow().done_subtasks(), 3);
+
+    assert_eq!(release.borrow_mut().clear_done(), 3);
+
+    assert_eq!(
+        descs(std::slice::from_ref(&release)),
+        ["Release", "Notes", "Build", "Mac"]
+    );
+    assert_eq!(release.borrow().done_subtasks(), 0);
+}
+
+#[test]
+fn a_done_branch_goes_whole() {
+    let release = task(
+        "Release",
+        TaskStatus::Todo,
+        vec![task(
+            "Build",
+            TaskStatus::Finished,
+            vec![task("Linux", TaskStatus::Deprecated, vec![])],
+        )],
+    );
+    assert_eq!(release.borrow().done_subtasks(), 2);
+
+    assert_eq!(release.borrow_mut().clear_done(), 2);
+    assert!(release.borrow().children.is_empty());
+}
+
+async fn clear_done(keys: &str) -> (Vec<String>, String, bool) {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![workspace("Work")], vec![release()]);
+    let list = ui.todolist.current_todolist.clone().unwrap();
+
+    for c in keys.chars().map(KeyCode::Char) {
+        input_tx.send(code(c)).await.unwrap();
+    }
+    drop(input_tx);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::ClearDone))
+        .await
+        .unwrap();
+    drop(ui_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(appstate)))
+        .await;
+
+    let tasks = descs(&list.borrow().tasks);
+    (tasks, ui.prompt.desc.clone(), ui.dirty)
+}
+
+#[tokio::test]
+async fn the_deletion_is_confirmed_first() {
+    let (tasks, prompt, dirty) = clear_done("y").await;
+    assert_eq!(tasks, ["Release", "Notes", "Build", "Mac"]);
+    assert_eq!(prompt, "Deleted 3 Finished Subtasks !");
+    assert!(dirty);
+
+    let (tasks, prompt, _) = clear_done("n").await;
+    assert_eq!(tasks.len(), 7);
+    assert_eq!(prompt, "Nothing Deleted !");
+}
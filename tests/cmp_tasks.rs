@@ -0,0 +1,61 @@
+//! Tests of the smart sort rule
+
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+
+use chrono::NaiveDate;
+use todo::app::ui::todolistwidget::{SortRule, Task, TaskStatus, cmp_tasks};
+
+fn task(desc: &str, status: TaskStatus, due: Option<(i32, u32, u32)>) -> Task {
+    let due = due.map(|(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap());
+    let mut task = Task::new(desc.to_string(), due);
+    task.status = status;
+    task
+}
+
+#[test]
+fn status_comes_before_due_date() {
+    let doing = task("doing", TaskStatus::InProcess, None);
+    let todo = task("todo", TaskStatus::Todo, Some((2025, 1, 1)));
+    let done = task("done", TaskStatus::Finished, Some((2024, 1, 1)));
+    let dropped = task("dropped", TaskStatus::Deprecated, Some((2020, 1, 1)));
+
+    assert_eq!(cmp_tasks(&doing, &todo), Ordering::Less);
+    assert_eq!(cmp_tasks(&todo, &done), Ordering::Less);
+    assert_eq!(cmp_tasks(&done, &dropped), Ordering::Less);
+}
+
+#[test]
+fn earlier_due_first_and_no_due_last() {
+    let early = task("early", TaskStatus::Todo, Some((2025, 1, 1)));
+    let late = task("late", TaskStatus::Todo, Some((2025, 6, 1)));
+    let none = task("none", TaskStatus::Todo, None);
+
+    assert_eq!(cmp_tasks(&early, &late), Ordering::Less);
+    assert_eq!(cmp_tasks(&late, &none), Ordering::Less);
+    assert_eq!(cmp_tasks(&none, &early), Ordering::Greater);
+    assert_eq!(cmp_tasks(&none, &none), Ordering::Equal);
+}
+
+#[test]
+fn smart_view_is_recursive_and_stable() {
+    let rc = |t: Task| Rc::new(RefCell::new(t));
+    let mut parent = task("parent", TaskStatus::Todo, None);
+    parent.add_child(rc(task("b", TaskStatus::Todo, None)));
+    parent.add_child(rc(task("a", TaskStatus::Todo, None)));
+    parent.add_child(rc(task("first", TaskStatus::InProcess, None)));
+    let tasks = vec![
+        rc(parent),
+        rc(task("due", TaskStatus::Todo, Some((2025, 3, 1)))),
+    ];
+
+    let descs = |tasks: &[Rc<RefCell<Task>>]| -> Vec<String> {
+        tasks.iter().map(|t| t.borrow().desc.clone()).collect()
+    };
+    assert_eq!(
+        descs(&SortRule::Smart.flatten(&tasks)),
+        ["due", "parent", "first", "b", "a"]
+    );
+    // the vectors keep the manual order
+    assert_eq!(descs(&tasks), ["parent", "due"]);
+    assert_eq!(descs(&tasks[0].borrow().children), ["b", "a", "first"]);
+}
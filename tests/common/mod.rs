@@ -0,0 +1,108 @@
+//! Helpers shared by the integration tests
+//!
+//! Every test file using them declares `mod common;`, and none uses all of them.
+#![allow(dead_code)]
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::ui::{
+    Ui, UiMessage,
+    todolistwidget::{Task, TaskStatus, TodoList},
+    workspacewidget::Workspace,
+};
+use tokio::sync::mpsc;
+
+/// The rows of the last frame drawn on a test terminal
+pub fn rows(terminal: &Terminal<TestBackend>) -> Vec<String> {
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content()
+        .chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+        .collect()
+}
+
+/// A key pressed without a modifier
+pub fn code(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+/// A character key pressed without a modifier
+pub fn key(c: char) -> KeyEvent {
+    code(KeyCode::Char(c))
+}
+
+/// A UI holding the workspaces at the top level, each with its todo list, and the tasks in
+/// the list of the first one
+///
+/// The first workspace is selected with its list shown and focused, on its first task if
+/// there is one. The senders of the UI messages and of the keys are returned with the UI, with
+/// room for what a test sends before the UI reads it.
+pub fn ui_with(
+    workspaces: Vec<Rc<RefCell<Workspace>>>,
+    tasks: Vec<Rc<RefCell<Task>>>,
+) -> (Ui, mpsc::Sender<UiMessage>, mpsc::Sender<KeyEvent>) {
+    let (ui_tx, ui_rx) = mpsc::channel(64);
+    let (input_tx, input_rx) = mpsc::channel(64);
+    let mut ui = Ui::new(ui_rx, input_rx);
+    for ws in &workspaces {
+        ui.todolist
+            .add_list(Rc::new(RefCell::new(TodoList::new(ws.borrow().id))));
+        ui.workspace.add_workspace(ws.clone());
+    }
+    if let Some(ws) = workspaces.first() {
+        ui.workspace.select(Some(ws.clone()));
+        ui.todolist.change_current_list(&Some(ws.clone()));
+        ui.todolist.focused = true;
+        let list = ui.todolist.current_todolist.clone().unwrap();
+        let first = tasks.first().cloned();
+        tasks
+            .into_iter()
+            .for_each(|task| list.borrow_mut().add_task(task));
+        if let Some(task) = first {
+            list.borrow_mut().select_task(&task);
+        }
+    }
+    (ui, ui_tx, input_tx)
+}
+
+/// A workspace named `name`
+pub fn workspace(name: &str) -> Rc<RefCell<Workspace>> {
+    Rc::new(RefCell::new(Workspace::new(name.to_string())))
+}
+
+/// An open task described `desc`, without a due date
+pub fn task(desc: &str) -> Rc<RefCell<Task>> {
+    Rc::new(RefCell::new(Task::new(desc.to_string(), None)))
+}
+
+/// A workspace named `name` holding the `children`
+pub fn ws_node(name: &str, children: Vec<Rc<RefCell<Workspace>>>) -> Rc<RefCell<Workspace>> {
+    let ws = workspace(name);
+    ws.borrow_mut().add_children(children);
+    ws
+}
+
+/// An open task described `desc` holding the `children`, without a due date
+pub fn node(desc: &str, children: Vec<Rc<RefCell<Task>>>) -> Rc<RefCell<Task>> {
+    let task = task(desc);
+    children
+        .into_iter()
+        .for_each(|child| task.borrow_mut().add_child(child));
+    task
+}
+
+/// A day of July 2025, the month the tests of the due dates are set in
+pub fn day(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 7, day).unwrap()
+}
+
+/// A task described `desc` in the `status`, due on the `due` day of July 2025
+pub fn task_due(desc: &str, status: TaskStatus, due: u32) -> Rc<RefCell<Task>> {
+    let task = Rc::new(RefCell::new(Task::new(desc.to_string(), Some(day(due)))));
+    task.borrow_mut().status = status;
+    task
+}
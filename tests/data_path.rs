@@ -0,0 +1,48 @@
+//! Tests of resolving the data file path
+//!
+//! The environment is shared by the whole test binary, so every case runs in the one test.
+//! Windows finds the profile through the shell rather than `HOME`, so it is Unix only.
+
+#![cfg(unix)]
+
+use std::path::PathBuf;
+
+use todo::app::{
+    config,
+    data::{self, DATA_PATH_ENV, DataPathSource},
+};
+
+#[test]
+fn data_path_follows_the_environment() {
+    let home = std::env::temp_dir().join("todo-home");
+    // SAFETY: this is the only test of the binary, nothing reads the environment concurrently
+    unsafe {
+        std::env::set_var("HOME", &home);
+        std::env::remove_var(DATA_PATH_ENV);
+    }
+    assert_eq!(
+        data::data_path(),
+        (home.join(".todo/data.json"), DataPathSource::Home)
+    );
+    assert_eq!(config::config_path(), home.join(".todo/config.json"));
+
+    let custom = PathBuf::from("/tmp/elsewhere/tasks.json");
+    unsafe { std::env::set_var(DATA_PATH_ENV, &custom) };
+    assert_eq!(data::data_path(), (custom.clone(), DataPathSource::Env));
+    assert_eq!(config::config_path(), home.join(".todo/config.json"));
+
+    unsafe { std::env::set_var(DATA_PATH_ENV, "") };
+    assert_eq!(data::data_path().1, DataPathSource::Home);
+
+    // a path picked because the data directory wasn't writable comes after the environment
+    let picked = PathBuf::from("/mnt/todo.json");
+    let config = config::Config {
+        data_path: Some(picked.clone()),
+        ..Default::default()
+    };
+    config::save_config(&config::config_path(), &config).unwrap();
+    assert_eq!(data::data_path(), (picked, DataPathSource::Config));
+    unsafe { std::env::set_var(DATA_PATH_ENV, &custom) };
+    assert_eq!(data::data_path().1, DataPathSource::Env);
+    std::fs::remove_file(config::config_path()).unwrap();
+}
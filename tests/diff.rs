@@ -0,0 +1,166 @@
+//! Tests of the comparison of two saves of the data
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+use todo::app::{
+    cli::Cli,
+    data::{self, Datas},
+    diff::{self, Change},
+    ui::{
+        todolistwidget::{Task, TaskStatus, TodoList},
+        workspacewidget::Workspace,
+    },
+};
+
+/// Two workspaces, `Work` with a `Backend` child holding two tasks and `Home` holding one
+fn datas() -> Datas {
+    let mut datas = Datas::default();
+    let work = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+    let backend = Rc::new(RefCell::new(Workspace::new("Backend".to_string())));
+    let home = Rc::new(RefCell::new(Workspace::new("Home".to_string())));
+    work.borrow_mut().add_child(backend.clone());
+    datas.workspace.add_workspace(work);
+    datas.workspace.add_workspace(home.clone());
+
+    let list = |ws: &Rc<RefCell<Workspace>>, descs: &[&str]| {
+        let mut list = TodoList::new(ws.borrow().id);
+        descs.iter().for_each(|desc| {
+            list.add_task(Rc::new(RefCell::new(Task::new(desc.to_string(), None))))
+        });
+        Rc::new(RefCell::new(list))
+    };
+    datas
+        .todolist
+        .todolists
+        .push(list(&backend, &["Migrate", "Deploy"]));
+    datas.todolist.todolists.push(list(&home, &["Groceries"]));
+    datas
+}
+
+fn copy(datas: &Datas) -> Datas {
+    serde_json::from_str(&data::serialize_data(datas)).unwrap()
+}
+
+fn task(datas: &Datas, list: usize, index: usize) -> Rc<RefCell<Task>> {
+    datas.todolist.todolists[list].borrow().tasks[index].clone()
+}
+
+#[test]
+fn identical_saves_have_no_differences() {
+    let old = datas();
+    let diff = diff::diff(&old, &copy(&old));
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "No differences\n");
+}
+
+#[test]
+fn added_and_removed_tasks_are_grouped_by_workspace() {
+    let old = datas();
+    let new = copy(&old);
+    new.todolist.todolists[0].borrow_mut().tasks.remove(1);
+    new.todolist.todolists[1]
+        .borrow_mut()
+        .add_task(Rc::new(RefCell::new(Task::new(
+            "Laundry".to_string(),
+            None,
+        ))));
+
+    let diff = diff::diff(&old, &new);
+
+    assert_eq!(
+        diff.workspaces["Work / Backend"],
+        [Change::Removed("Deploy".to_string())]
+    );
+    assert_eq!(
+        diff.workspaces["Home"],
+        [Change::Added("Laundry".to_string())]
+    );
+    assert_eq!(
+        diff.to_string(),
+        "Home\n  + Laundry\nWork / Backend\n  - Deploy\n"
+    );
+}
+
+#[test]
+fn a_task_moved_to_another_workspace_is_one_change() {
+    let old = datas();
+    let new = copy(&old);
+    let moved = new.todolist.todolists[0].borrow_mut().tasks.remove(0);
+    new.todolist.todolists[1].borrow_mut().add_task(moved);
+
+    let diff = diff::diff(&old, &new);
+
+    assert_eq!(diff.workspaces.len(), 1);
+    assert_eq!(
+        diff.workspaces["Home"],
+        [Change::Moved {
+            desc: "Migrate".to_string(),
+            from: "Work / Backend".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn renames_status_and_due_changes_are_reported() {
+    let old = datas();
+    let new = copy(&old);
+    let due = NaiveDate::from_ymd_opt(2025, 3, 1);
+    task(&new, 0, 0).borrow_mut().desc = "Migrate the database".to_string();
+    task(&new, 0, 1).borrow_mut().status = TaskStatus::Finished;
+    task(&new, 1, 0).borrow_mut().set_due(due);
+
+    let diff = diff::diff(&old, &new);
+
+    assert_eq!(
+        diff.workspaces["Work / Backend"],
+        [
+            Change::Renamed {
+                from: "Migrate".to_string(),
+                to: "Migrate the database".to_string(),
+            },
+            Change::Status {
+                desc: "Deploy".to_string(),
+                from: TaskStatus::Todo,
+                to: TaskStatus::Finished,
+            },
+        ]
+    );
+    assert_eq!(
+        diff.workspaces["Home"][0].to_string(),
+        "~ Groceries: due none -> 2025-03-01"
+    );
+}
+
+#[test]
+fn subtasks_are_compared_too() {
+    let old = datas();
+    let new = copy(&old);
+    task(&new, 1, 0)
+        .borrow_mut()
+        .add_child(Rc::new(RefCell::new(Task::new("Milk".to_string(), None))));
+
+    let diff = diff::diff(&old, &new);
+
+    assert_eq!(diff.workspaces["Home"], [Change::Added("Milk".to_string())]);
+}
+
+#[test]
+fn reading_a_missing_snapshot_fails() {
+    let path = std::env::temp_dir().join(format!("todo-no-snapshot-{}.json", std::process::id()));
+
+    assert!(diff::read_datas(&path).is_err());
+}
+
+#[test]
+fn diff_subcommand_takes_one_or_two_paths() {
+    let args = |args: &[&str]| Cli::parse(args.iter().map(|arg| arg.to_string()));
+
+    let cli = args(&["diff", "old.json"]).unwrap();
+    assert_eq!(cli.diff, Some(("old.json".into(), None)));
+    let cli = args(&["diff", "old.json", "new.json"]).unwrap();
+    assert_eq!(cli.diff, Some(("old.json".into(), Some("new.json".into()))));
+    assert!(args(&["diff"]).is_err());
+    assert!(args(&["diff", "a", "b", "c"]).is_err());
+}
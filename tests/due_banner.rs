@@ -0,0 +1,156 @@
+//! Tests of the banner announcing the due tasks
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{day, task_due, ui_with};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    sources::FixedClock,
+    ui::{
+        BANNER_TIMEOUT, Ui, UiMessage, WidgetAction,
+        todolistwidget::{TaskStatus, TodoList},
+        workspacewidget::Workspace,
+    },
+};
+use tokio::{sync::mpsc, time::Instant};
+
+/// A `Work` workspace with `Review` due on the 3rd, `Ship release` on the 10th, a finished
+/// `Blog` on the 1st and `Plan` on the 20th, and an archived workspace with a task due on the 1st
+fn ui() -> (Ui, mpsc::Sender<UiMessage>) {
+    let (mut ui, ui_tx, _) = ui_with(vec![], vec![]);
+    let work = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+    let mut list = TodoList::new(work.borrow().id);
+    list.add_task(task_due("Plan", TaskStatus::Todo, 20));
+    list.add_task(task_due("Blog", TaskStatus::Finished, 1));
+    list.add_task(task_due("Ship release", TaskStatus::InProcess, 10));
+    list.add_task(task_due("Review", TaskStatus::Todo, 3));
+    ui.todolist.add_list(Rc::new(RefCell::new(list)));
+    ui.workspace.add_workspace(work);
+
+    let old = Rc::new(RefCell::new(Workspace::new("Old".to_string())));
+    let mut old_list = TodoList::new(old.borrow().id);
+    old_list.add_task(task_due("Forgotten", TaskStatus::Todo, 1));
+    ui.todolist.add_list(Rc::new(RefCell::new(old_list)));
+    ui.archived_ws.add_workspace(old);
+    (ui, ui_tx)
+}
+
+fn text(ui: &Ui) -> Option<String> {
+    ui.banner.as_ref().map(|banner| banner.text.clone())
+}
+
+#[test]
+fn the_overdue_tasks_are_announced_once_a_day() {
+    let (mut ui, _) = ui();
+
+    ui.announce_due(&FixedClock(day(10)));
+    assert_eq!(
+        text(&ui).unwrap(),
+        "'Review' is overdue (+1 more) — press O to jump"
+    );
+
+    // the same day, nothing is announced again
+    ui.banner = None;
+    ui.announce_due(&FixedClock(day(10)));
+    assert_eq!(text(&ui), None);
+
+    // the next day, the tasks still open are announced again
+    ui.announce_due(&FixedClock(day(11)));
+    assert!(
+        text(&ui)
+            .unwrap()
+            .starts_with("'Review' is overdue (+1 more)")
+    );
+}
+
+#[test]
+fn a_task_is_announced_when_it_becomes_due() {
+    let (mut ui, _) = ui();
+
+    ui.announce_due(&FixedClock(day(2)));
+    assert_eq!(text(&ui), None);
+
+    ui.announce_due(&FixedClock(day(3)));
+    assert_eq!(
+        text(&ui).unwrap(),
+        "'Review' is due today — press O to jump"
+    );
+    ui.banner = None;
+    ui.announce_due(&FixedClock(day(3)));
+    assert_eq!(text(&ui), None);
+}
+
+#[test]
+fn the_banner_is_hidden_after_the_timeout() {
+    let (mut ui, _) = ui();
+    ui.announce_due(&FixedClock(day(3)));
+
+    ui.announce_due(&FixedClock(day(3)));
+    assert!(ui.banner.is_some());
+
+    ui.banner.as_mut().unwrap().shown_at = Instant::now() - BANNER_TIMEOUT;
+    ui.announce_due(&FixedClock(day(3)));
+    assert!(ui.banner.is_none());
+}
+
+#[test]
+fn the_banner_is_drawn_across_the_top() {
+    let (mut ui, _) = ui();
+    ui.announce_due(&FixedClock(day(3)));
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+    terminal.draw(|f| ui.update(f)).unwrap();
+    let top: String = terminal.backend().buffer().content()[..80]
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect();
+    assert!(
+        top.starts_with(" 'Review' is due today — press O to jump "),
+        "{}",
+        top
+    );
+    assert!(ui.appstate.lock().unwrap().banner);
+}
+
+async fn handle(ui: &mut Ui, ui_tx: mpsc::Sender<UiMessage>, action: WidgetAction) -> AppState {
+    ui_tx.send(UiMessage::WAction(action)).await.unwrap();
+    drop(ui_tx);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+    Arc::try_unwrap(appstate).unwrap().into_inner().unwrap()
+}
+
+#[tokio::test]
+async fn o_jumps_to_the_announced_task() {
+    let (mut ui, ui_tx) = ui();
+    ui.announce_due(&FixedClock(day(3)));
+
+    let appstate = handle(&mut ui, ui_tx, WidgetAction::BannerJump).await;
+
+    assert!(ui.banner.is_none());
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let selected = list.borrow().current_task.clone().unwrap();
+    assert_eq!(selected.borrow().desc, "Review");
+    assert!(ui.todolist.focused);
+    assert_eq!(appstate.current_focus, CurrentFocus::TodoList);
+}
+
+#[tokio::test]
+async fn another_key_hides_the_banner() {
+    let (mut ui, ui_tx) = ui();
+    ui.announce_due(&FixedClock(day(3)));
+
+    let appstate = handle(&mut ui, ui_tx, WidgetAction::DismissBanner).await;
+
+    assert!(ui.banner.is_none());
+    assert!(ui.todolist.current_todolist.is_none());
+    assert_eq!(appstate.current_focus, CurrentFocus::Workspace);
+}
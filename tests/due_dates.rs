@@ -0,0 +1,108 @@
+//! Tests of the due date coloring and of the id sources, pinned with a fixed clock
+
+use chrono::{Days, NaiveDate};
+use ratatui::style::Color;
+use todo::app::{
+    sources::{FixedClock, SequentialIds},
+    ui::{
+        todolistwidget::{DEFAULT_ABSOLUTE_DUE_AFTER_DAYS, Task, TaskStatus, TodoWidget},
+        workspacewidget::Workspace,
+    },
+};
+use uuid::Uuid;
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 14).unwrap()
+}
+
+fn in_days(days: i64) -> NaiveDate {
+    if days < 0 {
+        today().checked_sub_days(Days::new(days.unsigned_abs()))
+    } else {
+        today().checked_add_days(Days::new(days as u64))
+    }
+    .unwrap()
+}
+
+#[test]
+fn due_span_colors_follow_the_thresholds() {
+    let clock = FixedClock(today());
+    let cases = [
+        (-2i64, " 2 day over ! ", Color::Yellow),
+        (0, " 0 day left ! ", Color::Red),
+        (1, " 1 day left ! ", Color::LightRed),
+        (3, " 3 day left ! ", Color::Yellow),
+        (5, " 5 day left ! ", Color::LightBlue),
+        (10, " 10 day left ! ", Color::LightGreen),
+        (45, " 45 day left ! ", Color::LightGreen),
+    ];
+
+    for (days, text, color) in cases {
+        let span = TodoWidget::due_span(in_days(days), &TaskStatus::Todo, &clock, 0, None);
+        assert_eq!(span.content, text, "{} days", days);
+        assert_eq!(span.style.fg, Some(color), "{} days", days);
+    }
+}
+
+#[test]
+fn far_and_overdue_due_dates_are_shown_as_dates_past_the_threshold() {
+    let clock = FixedClock(today());
+    let cases = [
+        (-3i64, " 3d over · was 2025-03-11 ", Color::Yellow),
+        (0, " 0 day left ! ", Color::Red),
+        (5, " 5 day left ! ", Color::LightBlue),
+        (10, " 10 day left ! ", Color::LightGreen),
+        (14, " 14 day left ! ", Color::LightGreen),
+        (15, " 2025-03-29 ", Color::LightGreen),
+        (45, " 2025-04-28 ", Color::LightGreen),
+    ];
+
+    for (days, text, color) in cases {
+        let span = TodoWidget::due_span(
+            in_days(days),
+            &TaskStatus::Todo,
+            &clock,
+            DEFAULT_ABSOLUTE_DUE_AFTER_DAYS,
+            None,
+        );
+        assert_eq!(span.content, text, "{} days", days);
+        assert_eq!(span.style.fg, Some(color), "{} days", days);
+    }
+    // a threshold within the colored ranges shows the date in the color of the range
+    let span = TodoWidget::due_span(in_days(3), &TaskStatus::Todo, &clock, 2, None);
+    assert_eq!(span.content, " 2025-03-17 ");
+    assert_eq!(span.style.fg, Some(Color::Yellow));
+    let span = TodoWidget::due_span(in_days(5), &TaskStatus::Todo, &clock, 4, None);
+    assert_eq!(span.content, " 2025-03-19 ");
+    assert_eq!(span.style.fg, Some(Color::LightBlue));
+    let span = TodoWidget::due_span(in_days(2), &TaskStatus::Todo, &clock, 2, None);
+    assert_eq!(span.content, " 2 day left ! ");
+}
+
+#[test]
+fn due_span_is_empty_for_closed_tasks() {
+    let clock = FixedClock(today());
+    for status in [TaskStatus::Finished, TaskStatus::Deprecated] {
+        assert!(
+            TodoWidget::due_span(
+                today(),
+                &status,
+                &clock,
+                DEFAULT_ABSOLUTE_DUE_AFTER_DAYS,
+                None
+            )
+            .content
+            .is_empty()
+        );
+    }
+}
+
+#[test]
+fn sequential_ids_are_shared_by_tasks_and_workspaces() {
+    let ids = SequentialIds::new(1);
+    let ws = Workspace::with_ids("Work".to_string(), &ids);
+    let task = Task::with_ids("Write tests".to_string(), None, &ids);
+
+    assert_eq!(ws.id, Uuid::from_u128(1));
+    assert_eq!(task.id, Uuid::from_u128(2));
+}
@@ -0,0 +1,155 @@
+//! Tests of typing the due date in the row of a task
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{Days, Local, NaiveDate};
+use common::{code, ui_with, workspace};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, layout::Rect, widgets::Widget};
+use todo::app::{
+    appstate::{AppState, CurrentMode},
+    parse,
+    sources::FixedClock,
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{DueEdit, Task},
+    },
+};
+use tokio::sync::mpsc;
+
+fn on(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(y, m, d)
+}
+
+/// A UI in a workspace with two tasks due on `due`, the first one selected
+fn ui(
+    due: Option<NaiveDate>,
+) -> (
+    Ui,
+    Rc<RefCell<Task>>,
+    mpsc::Sender<UiMessage>,
+    mpsc::Sender<KeyEvent>,
+) {
+    let first = Rc::new(RefCell::new(Task::new("Write".to_string(), due)));
+    let second = Rc::new(RefCell::new(Task::new("Review".to_string(), due)));
+    let (ui, ui_tx, input_tx) = ui_with(vec![workspace("ws")], vec![first.clone(), second]);
+    (ui, first, ui_tx, input_tx)
+}
+
+/// Run the inline edit of the selected task with the given keys
+async fn edit(due: Option<NaiveDate>, keys: &[KeyCode]) -> (Ui, Rc<RefCell<Task>>, AppState) {
+    let (mut ui, task, ui_tx, input_tx) = ui(due);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::DueInline))
+        .await
+        .unwrap();
+    for key in keys {
+        input_tx.send(code(*key)).await.unwrap();
+    }
+    drop(ui_tx);
+    drop(input_tx);
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+    let appstate = Arc::try_unwrap(appstate).unwrap().into_inner().unwrap();
+    (ui, task, appstate)
+}
+
+fn typed(text: &str) -> Vec<KeyCode> {
+    text.chars().map(KeyCode::Char).collect()
+}
+
+#[test]
+fn short_forms_weekdays_and_none_are_parsed() {
+    // a Wednesday
+    let clock = FixedClock(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap());
+    for (input, expected) in [
+        ("+3d", on(2025, 7, 5)),
+        ("+2w", on(2025, 7, 16)),
+        ("+1m", on(2025, 8, 2)),
+        ("fri", on(2025, 7, 4)),
+        ("Friday", on(2025, 7, 4)),
+        ("wed", on(2025, 7, 2)),
+        ("mon", on(2025, 7, 7)),
+        ("none", None),
+        ("None", None),
+        ("", None),
+        ("2025-12-24", on(2025, 12, 24)),
+        ("3 days", on(2025, 7, 5)),
+        ("tomorrow", on(2025, 7, 3)),
+        ("whenever", on(2025, 7, 2)),
+    ] {
+        assert_eq!(parse::due(input, &clock), expected, "{:?}", input);
+    }
+}
+
+#[tokio::test]
+async fn enter_sets_the_typed_due_date() {
+    let mut keys = typed("+3x");
+    keys.push(KeyCode::Backspace);
+    keys.push(KeyCode::Char('d'));
+    keys.push(KeyCode::Enter);
+
+    let (ui, task, appstate) = edit(None, &keys).await;
+
+    let today = Local::now().date_naive();
+    assert_eq!(task.borrow().due, today.checked_add_days(Days::new(3)));
+    assert_eq!(ui.todolist.due_edit, None);
+    assert_eq!(appstate.current_mode, CurrentMode::Normal);
+    assert!(ui.dirty);
+}
+
+#[tokio::test]
+async fn none_clears_the_due_date() {
+    let mut keys = typed("none");
+    keys.push(KeyCode::Enter);
+
+    let (_, task, _) = edit(on(2025, 7, 2), &keys).await;
+
+    assert_eq!(task.borrow().due, None);
+}
+
+#[tokio::test]
+async fn esc_and_an_empty_input_keep_the_due_date() {
+    let mut keys = typed("fri");
+    keys.push(KeyCode::Esc);
+    let (ui, task, _) = edit(on(2025, 7, 2), &keys).await;
+    assert_eq!(task.borrow().due, on(2025, 7, 2));
+    assert_eq!(ui.todolist.due_edit, None);
+
+    let (_, task, _) = edit(on(2025, 7, 2), &[KeyCode::Enter]).await;
+    assert_eq!(task.borrow().due, on(2025, 7, 2));
+}
+
+#[test]
+fn only_the_edited_row_shows_the_input() {
+    let (mut ui, task, _, _) = ui(Local::now().date_naive().checked_add_days(Days::new(9)));
+    ui.todolist.due_edit = Some(DueEdit {
+        task: task.borrow().id,
+        input: "+3d".to_string(),
+    });
+
+    let area = Rect::new(0, 0, 60, 8);
+    let mut buf = Buffer::empty(area);
+    (&mut ui.todolist).render(area, &mut buf);
+
+    let row = |desc: &str| -> String {
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .find(|row| row.contains(desc))
+            .unwrap()
+    };
+    assert!(row("Write").contains("due: +3d_"), "{}", row("Write"));
+    assert!(!row("Write").contains("day left"));
+    assert!(row("Review").contains("9 day left"), "{}", row("Review"));
+}
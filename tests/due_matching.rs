@@ -0,0 +1,182 @@
+//! Tests of setting or shifting the due date of all the tasks matching the filter
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use chrono::NaiveDate;
+use common::{code, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    parse,
+    sources::FixedClock,
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{DueChange, Task},
+    },
+};
+use tokio::sync::mpsc;
+
+fn on(m: u32, d: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(2025, m, d)
+}
+
+fn task(desc: &str, due: Option<NaiveDate>) -> Rc<RefCell<Task>> {
+    Rc::new(RefCell::new(Task::new(desc.to_string(), due)))
+}
+
+/// The tasks of a list filtered by `release`: a parent shown for its subtask only, the
+/// subtask, a top level task without a due date and one left out by the filter
+struct Fixture {
+    ui: Ui,
+    parent: Rc<RefCell<Task>>,
+    child: Rc<RefCell<Task>>,
+    undated: Rc<RefCell<Task>>,
+    other: Rc<RefCell<Task>>,
+}
+
+fn fixture() -> Fixture {
+    let parent = task("Ship v2", on(7, 1));
+    let child = task("Write release notes", on(7, 3));
+    parent.borrow_mut().add_child(child.clone());
+    let undated = task("Tag the release", None);
+    let other = task("Water plants", on(7, 2));
+    let tasks = vec![parent.clone(), undated.clone(), other.clone()];
+    let (mut ui, _, _) = ui_with(vec![workspace("ws")], tasks);
+    ui.todolist.search_string = "release".to_string();
+    Fixture {
+        ui,
+        parent,
+        child,
+        undated,
+        other,
+    }
+}
+
+/// Send the action with the keys typed into the due date popup, returning the prompt
+async fn run(ui: &mut Ui, typed: &str) -> String {
+    let (ui_tx, ui_rx) = mpsc::channel(4);
+    let (input_tx, input_rx) = mpsc::channel(32);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::DueMatching))
+        .await
+        .unwrap();
+    let keys = typed.chars().map(KeyCode::Char).chain([KeyCode::Enter]);
+    for key in keys {
+        input_tx.send(code(key)).await.unwrap();
+    }
+    drop(ui_tx);
+    drop(input_tx);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+    ui.prompt.desc.clone()
+}
+
+#[test]
+fn shifts_and_dates_are_told_apart() {
+    let clock = FixedClock(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap());
+    assert_eq!(parse::due_change("+7d", &clock), DueChange::ShiftDays(7));
+    assert_eq!(parse::due_change("-2w", &clock), DueChange::ShiftDays(-14));
+    assert_eq!(parse::due_change("+1m", &clock), DueChange::ShiftMonths(1));
+    assert_eq!(
+        parse::due_change("7 days", &clock),
+        DueChange::Set(on(7, 9))
+    );
+    assert_eq!(parse::due_change("fri", &clock), DueChange::Set(on(7, 4)));
+    assert_eq!(parse::due_change("none", &clock), DueChange::Set(None));
+
+    assert_eq!(DueChange::ShiftDays(-3).apply(on(7, 2)), Some(on(6, 29)));
+    assert_eq!(DueChange::ShiftMonths(-1).apply(on(7, 31)), Some(on(6, 30)));
+    assert_eq!(DueChange::ShiftDays(7).apply(None), None);
+    assert_eq!(DueChange::ShiftMonths(i32::MAX).apply(on(7, 2)), None);
+    assert_eq!(DueChange::Set(on(8, 1)).apply(None), Some(on(8, 1)));
+}
+
+#[test]
+fn only_the_tasks_matching_themselves_are_changed() {
+    let f = fixture();
+
+    let matching: Vec<String> =
+        f.ui.todolist
+            .matching_tasks()
+            .iter()
+            .map(|task| task.borrow().desc.clone())
+            .collect();
+    assert_eq!(matching, ["Write release notes", "Tag the release"]);
+
+    assert_eq!(
+        f.ui.todolist.set_due_matching(DueChange::ShiftDays(7)),
+        (1, 1, 0)
+    );
+    assert_eq!(f.child.borrow().due, on(7, 10));
+    assert_eq!(f.undated.borrow().due, None);
+    assert_eq!(f.parent.borrow().due, on(7, 1));
+    assert_eq!(f.other.borrow().due, on(7, 2));
+}
+
+#[tokio::test]
+async fn a_shift_reports_the_tasks_skipped() {
+    let mut f = fixture();
+
+    let prompt = run(&mut f.ui, "+7d").await;
+
+    assert_eq!(prompt, "Shifted 1 Due Dates, Skipped 1 Without One !");
+    assert_eq!(f.child.borrow().due, on(7, 10));
+    assert!(f.ui.dirty);
+}
+
+#[tokio::test]
+async fn a_shift_out_of_the_calendar_keeps_the_due_dates() {
+    let mut f = fixture();
+
+    let prompt = run(&mut f.ui, "+99999999d").await;
+
+    assert_eq!(
+        prompt,
+        "Shifted 0 Due Dates, Skipped 1 Without One And 1 Out Of The Calendar !"
+    );
+    assert_eq!(f.child.borrow().due, on(7, 3));
+    assert_eq!(f.undated.borrow().due, None);
+}
+
+#[tokio::test]
+async fn a_date_is_given_to_every_matching_task() {
+    let mut f = fixture();
+
+    let prompt = run(&mut f.ui, "2025-09-01").await;
+
+    assert_eq!(prompt, "Set The Due Date Of 2 Tasks !");
+    assert_eq!(f.child.borrow().due, on(9, 1));
+    assert_eq!(f.undated.borrow().due, on(9, 1));
+    assert_eq!(f.other.borrow().due, on(7, 2));
+}
+
+#[tokio::test]
+async fn an_empty_input_changes_nothing() {
+    let mut f = fixture();
+
+    let prompt = run(&mut f.ui, "").await;
+
+    assert_eq!(prompt, "Due Dates Unchanged !");
+    assert_eq!(f.child.borrow().due, on(7, 3));
+}
+
+#[tokio::test]
+async fn without_a_filter_the_action_asks_for_one() {
+    let mut f = fixture();
+    f.ui.todolist.search_string.clear();
+
+    let prompt = run(&mut f.ui, "+7d").await;
+
+    assert_eq!(prompt, "Filter The List First, Press f !");
+    assert_eq!(f.child.borrow().due, on(7, 3));
+}
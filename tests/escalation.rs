@@ -0,0 +1,163 @@
+//! Tests of raising the urgency of the overdue tasks
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::{day, task_due, ui_with, workspace};
+use todo::app::{
+    config::Config,
+    sources::FixedClock,
+    ui::{
+        Ui,
+        todolistwidget::{EscalationPolicy, Task, TaskStatus, TodoList, Urgency},
+        workspacewidget::Workspace,
+    },
+};
+
+const POLICY: EscalationPolicy = EscalationPolicy {
+    again_after_days: 3,
+};
+
+/// A UI escalating every 3 days, with a task due on the 1st in an active workspace, a
+/// finished one and an archived one due the same day
+struct Fixture {
+    ui: Ui,
+    open: Rc<RefCell<Task>>,
+    finished: Rc<RefCell<Task>>,
+    archived: Rc<RefCell<Task>>,
+}
+
+fn fixture() -> Fixture {
+    let open = task_due("Report", TaskStatus::InProcess, 1);
+    let finished = task_due("Slides", TaskStatus::Finished, 1);
+    let tasks = vec![open.clone(), finished.clone()];
+    let (mut ui, _, _) = ui_with(vec![workspace("Work")], tasks);
+    ui.config.escalate_overdue = Some(POLICY);
+
+    let old = Rc::new(RefCell::new(Workspace::new("Old".to_string())));
+    let archived = task_due("Forgotten", TaskStatus::Todo, 1);
+    let mut old_list = TodoList::new(old.borrow().id);
+    old_list.add_task(archived.clone());
+    ui.todolist.add_list(Rc::new(RefCell::new(old_list)));
+    ui.archived_ws.add_workspace(old);
+    Fixture {
+        ui,
+        open,
+        finished,
+        archived,
+    }
+}
+
+#[test]
+fn the_steps_start_the_day_after_the_due_date() {
+    let steps: Vec<u32> = (1..=11).map(|d| POLICY.steps(day(1), day(d))).collect();
+    assert_eq!(steps, [0, 1, 1, 1, 2, 2, 2, 3, 3, 3, 4]);
+
+    let once = EscalationPolicy {
+        again_after_days: 0,
+    };
+    assert_eq!(once.steps(day(1), day(30)), 1);
+}
+
+#[test]
+fn the_urgency_crosses_each_threshold_once() {
+    let mut f = fixture();
+
+    let mut urgency_on = |d: u32| {
+        f.ui.escalate_overdue(&FixedClock(day(d)));
+        f.open.borrow().urgency.clone()
+    };
+    assert_eq!(urgency_on(1), None);
+    assert_eq!(urgency_on(2), Some(Urgency::Common));
+    assert_eq!(urgency_on(4), Some(Urgency::Common));
+    assert_eq!(urgency_on(5), Some(Urgency::Important));
+    assert_eq!(urgency_on(8), Some(Urgency::Critical));
+    assert_eq!(urgency_on(30), Some(Urgency::Critical));
+
+    assert_eq!(f.finished.borrow().urgency, None);
+    assert_eq!(f.archived.borrow().urgency, None);
+}
+
+#[test]
+fn a_late_scan_catches_up_in_one_go() {
+    let mut f = fixture();
+
+    f.ui.escalate_overdue(&FixedClock(day(8)));
+
+    assert_eq!(f.open.borrow().urgency, Some(Urgency::Critical));
+    assert_eq!(f.ui.prompt.desc, "1 Task Escalated To Critical !");
+    let logged: Vec<&str> =
+        f.ui.prompt
+            .history
+            .iter()
+            .map(|entry| entry.desc.as_str())
+            .collect();
+    assert_eq!(
+        logged,
+        [
+            "Overdue Report Escalated To Critical",
+            "1 Task Escalated To Critical !"
+        ]
+    );
+    assert!(f.ui.dirty);
+}
+
+#[test]
+fn a_scan_on_the_same_day_changes_nothing() {
+    let mut f = fixture();
+    f.ui.escalate_overdue(&FixedClock(day(2)));
+    f.open.borrow_mut().urgency = None;
+    let history = f.ui.prompt.history.len();
+
+    f.ui.escalate_overdue(&FixedClock(day(2)));
+    f.ui.escalate_overdue(&FixedClock(day(3)));
+
+    assert_eq!(f.open.borrow().urgency, None);
+    assert_eq!(f.ui.prompt.history.len(), history);
+}
+
+#[test]
+fn the_summary_groups_the_levels_reached() {
+    let mut f = fixture();
+    let later = task_due("Invoice", TaskStatus::Todo, 4);
+    let other = task_due("Taxes", TaskStatus::Todo, 4);
+    other.borrow_mut().urgency = Some(Urgency::Common);
+    let list = f.ui.todolist.todolists[0].clone();
+    list.borrow_mut().add_task(later.clone());
+    list.borrow_mut().add_task(other.clone());
+
+    f.ui.escalate_overdue(&FixedClock(day(5)));
+
+    assert_eq!(
+        f.ui.prompt.desc,
+        "3 Tasks Escalated, 1 To Common, 2 To Important !"
+    );
+}
+
+#[test]
+fn escalating_is_off_by_default_and_leaves_the_task_fresh() {
+    let mut f = fixture();
+    f.ui.config.escalate_overdue = Config::default().escalate_overdue;
+    f.ui.escalate_overdue(&FixedClock(day(8)));
+    assert_eq!(f.open.borrow().urgency, None);
+    assert!(!f.ui.dirty);
+
+    f.ui.config.escalate_overdue = Some(POLICY);
+    f.ui.escalate_overdue(&FixedClock(day(8)));
+    assert_eq!(f.open.borrow().updated, None);
+}
+
+#[test]
+fn a_new_due_date_starts_over() {
+    let mut f = fixture();
+    f.ui.escalate_overdue(&FixedClock(day(5)));
+    assert_eq!(f.open.borrow().escalations, 2);
+
+    f.open.borrow_mut().set_due(Some(day(10)));
+    f.open.borrow_mut().urgency = None;
+    f.ui.escalate_overdue(&FixedClock(day(11)));
+
+    assert_eq!(f.open.borrow().escalations, 1);
+    assert_eq!(f.open.borrow().urgency, Some(Urgency::Common));
+}
@@ -0,0 +1,117 @@
+//! Tests of the find popup on large lists, matched again only after a pause and from the last
+//! matches when the query narrows them
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use common::{key, rows, task, ui_with, workspace};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    data,
+    sources::SystemClock,
+    ui::{
+        UiMessage, WidgetAction,
+        matcher::FilterMatcher,
+        todolistwidget::{Staleness, Task},
+    },
+};
+
+/// The top level tasks of every list of the stress dataset, as if they were one list
+fn stress_tasks(count: usize) -> Vec<Rc<RefCell<Task>>> {
+    data::generate_datas(count)
+        .todolist
+        .todolists
+        .iter()
+        .flat_map(|list| list.borrow().tasks.clone())
+        .collect()
+}
+
+fn descs(tasks: &[Rc<RefCell<Task>>]) -> Vec<String> {
+    tasks
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+#[test]
+fn a_narrowing_query_checks_only_the_last_matches() {
+    let tasks = stress_tasks(20_000);
+    let stale = Staleness::new(&SystemClock, 0);
+    let mut matcher = FilterMatcher::default();
+    assert_eq!(matcher.update(&tasks, "7", stale), tasks.len());
+    for query in ["77", "777", "7777"] {
+        let before = matcher.tasks().len();
+        assert_eq!(matcher.update(&tasks, query, stale), before, "{}", query);
+
+        let mut fresh = FilterMatcher::default();
+        fresh.update(&tasks, query, stale);
+        assert_eq!(descs(matcher.tasks()), descs(fresh.tasks()), "{}", query);
+    }
+    assert!(matcher.tasks().len() < tasks.len() / 10);
+    assert_eq!(matcher.update(&tasks, "7777", stale), 0);
+
+    // any word matches, so a new one widens the matches and the whole list is checked
+    assert_eq!(matcher.update(&tasks, "7777 Task", stale), tasks.len());
+    assert_eq!(matcher.tasks().len(), tasks.len());
+    assert_eq!(matcher.update(&tasks, "777", stale), tasks.len());
+}
+
+#[test]
+fn typing_into_a_large_list_is_faster_from_the_last_matches() {
+    let tasks = stress_tasks(20_000);
+    let stale = Staleness::new(&SystemClock, 0);
+    let queries = ["7", "77", "777", "7777"];
+
+    let mut matcher = FilterMatcher::default();
+    matcher.update(&tasks, queries[0], stale);
+    let started = Instant::now();
+    for query in &queries[1..] {
+        matcher.update(&tasks, query, stale);
+    }
+    let narrowed = started.elapsed();
+
+    let started = Instant::now();
+    for query in &queries[1..] {
+        FilterMatcher::default().update(&tasks, query, stale);
+    }
+    let full = started.elapsed();
+    assert!(narrowed < full, "{:?} narrowed, {:?} full", narrowed, full);
+}
+
+/// `Work` open with `Paint` and `Plan`, the find popup opened on it and `zz` typed at once
+async fn typed_zz(wait: Duration) -> Vec<String> {
+    let (mut ui, ui_tx, input_tx) =
+        ui_with(vec![workspace("Work")], vec![task("Paint"), task("Plan")]);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::Filter))
+        .await
+        .unwrap();
+    for _ in 0..2 {
+        input_tx.send(key('z')).await.unwrap();
+    }
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    // no Enter comes, so the popup stays open
+    let open = tokio::time::timeout(wait, ui.handle_uimsg(&mut terminal, appstate)).await;
+    assert!(open.is_err());
+    rows(&terminal)
+}
+
+#[tokio::test]
+async fn the_matches_wait_for_a_pause_in_the_typing() {
+    // the letters are shown at once, over the matches of the empty query
+    let rows = typed_zz(Duration::from_millis(50)).await.concat();
+    assert!(rows.contains("zz"));
+    assert!(rows.contains("Paint"));
+
+    let rows = typed_zz(Duration::from_millis(400)).await.concat();
+    assert!(rows.contains("zz"));
+    assert!(!rows.contains("Paint"));
+}
@@ -0,0 +1,185 @@
+//! Tests of folding the subtasks of a task with Enter, and the tasks or workspaces with `z`
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::node;
+use todo::app::ui::{
+    SelectAction, SelectBF, glyphs,
+    todolistwidget::{SortRule, Task, TodoList, TodoWidget},
+    tree::Fold,
+    workspacewidget::{Workspace, WorkspaceType, WorkspaceWidget},
+};
+
+type Node = Rc<RefCell<Task>>;
+
+fn descs(tasks: &[Node]) -> Vec<String> {
+    tasks
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+/// A todo list of `a` with `a1` (with `a1x`) and `a2`, then `b`, with `a` selected
+fn widget() -> TodoWidget {
+    let ws = Workspace::new("Work".to_string());
+    let a1 = node("a1", vec![node("a1x", vec![])]);
+    let a = node("a", vec![a1, node("a2", vec![])]);
+    let mut list = TodoList::new(ws.id);
+    list.add_task(a.clone());
+    list.add_task(node("b", vec![]));
+    list.current_task = Some(a);
+    list.state.select(Some(0));
+    let mut widget = TodoWidget::new();
+    widget.current_todolist = Some(Rc::new(RefCell::new(list)));
+    widget
+}
+
+fn list(widget: &TodoWidget) -> Rc<RefCell<TodoList>> {
+    widget.current_todolist.clone().unwrap()
+}
+
+#[test]
+fn a_folded_task_hides_its_subtasks_from_the_rows() {
+    let widget = widget();
+    let list = list(&widget);
+    assert_eq!(
+        descs(&list.borrow().flattened()),
+        ["a", "a1", "a1x", "a2", "b"]
+    );
+
+    assert_eq!(list.borrow_mut().toggle_current_task(), Some(false));
+
+    assert_eq!(descs(&list.borrow().flattened()), ["a", "b"]);
+    // every task is still there for the rest of the app
+    let tasks = list.borrow().tasks.clone();
+    assert_eq!(SortRule::Manual.flatten(&tasks).len(), 5);
+    assert_eq!(list.borrow_mut().toggle_current_task(), Some(true));
+    assert_eq!(list.borrow().flattened().len(), 5);
+}
+
+#[test]
+fn a_task_without_subtasks_isnt_folded() {
+    let widget = widget();
+    let list = list(&widget);
+    let b = list.borrow().tasks[1].clone();
+    list.borrow_mut().current_task = Some(b.clone());
+
+    assert_eq!(list.borrow_mut().toggle_current_task(), None);
+    assert!(b.borrow().expanded);
+    list.borrow_mut().current_task = None;
+    assert_eq!(list.borrow_mut().toggle_current_task(), None);
+}
+
+#[test]
+fn moving_down_skips_the_folded_subtasks() {
+    let mut widget = widget();
+    let list = list(&widget);
+    list.borrow_mut().toggle_current_task();
+
+    let next = widget.get_selected_bf(SelectBF::Forward).unwrap();
+
+    assert_eq!(next.borrow().desc, "b");
+    assert_eq!(list.borrow().state.selected(), Some(1));
+}
+
+#[test]
+fn moving_from_a_hidden_task_starts_over() {
+    let mut widget = widget();
+    let list = list(&widget);
+    let a1x = SortRule::Manual.flatten(&list.borrow().tasks)[2].clone();
+    list.borrow().tasks[0].borrow_mut().expanded = false;
+    list.borrow_mut().current_task = Some(a1x);
+
+    let next = widget.get_selected_bf(SelectBF::Forward).unwrap();
+
+    assert_eq!(next.borrow().desc, "a");
+}
+
+#[test]
+fn adding_a_subtask_unfolds_the_task() {
+    let widget = widget();
+    let list = list(&widget);
+    list.borrow_mut().toggle_current_task();
+
+    list.borrow_mut().add_child_task(node("a3", vec![]));
+
+    assert_eq!(list.borrow().flattened().len(), 6);
+}
+
+#[test]
+fn the_badge_of_a_folded_task_is_marked() {
+    glyphs::select_set(&glyphs::PLAIN);
+    let widget = widget();
+    let list = list(&widget);
+    let a = list.borrow().tasks[0].clone();
+    assert_eq!(TodoWidget::count_badge(&a.borrow()).content, " (0/3)");
+
+    list.borrow_mut().toggle_current_task();
+
+    assert_eq!(TodoWidget::count_badge(&a.borrow()).content, " (0/3) +");
+}
+
+#[test]
+fn closing_all_moves_a_hidden_selection_to_its_top_level_task() {
+    let widget = widget();
+    let list = list(&widget);
+    let a1x = SortRule::Manual.flatten(&list.borrow().tasks)[2].clone();
+    list.borrow_mut().select_task(&a1x);
+
+    list.borrow_mut().fold(Fold::CloseAll);
+
+    assert_eq!(descs(&list.borrow().flattened()), ["a", "b"]);
+    let current = list.borrow().current_task.clone().unwrap();
+    assert_eq!(current.borrow().desc, "a");
+    assert_eq!(list.borrow().state.selected(), Some(0));
+    // the tasks below are closed too, opening `a` alone shows `a1` closed
+    list.borrow_mut().fold(Fold::Open);
+    assert_eq!(descs(&list.borrow().flattened()), ["a", "a1", "a2", "b"]);
+
+    list.borrow_mut().fold(Fold::OpenAll);
+
+    assert_eq!(list.borrow().flattened().len(), 5);
+    assert_eq!(list.borrow().state.selected(), Some(0));
+}
+
+#[test]
+fn closing_a_task_without_subtasks_closes_its_parent() {
+    let widget = widget();
+    let list = list(&widget);
+    let a1x = SortRule::Manual.flatten(&list.borrow().tasks)[2].clone();
+    list.borrow_mut().select_task(&a1x);
+
+    list.borrow_mut().fold(Fold::Close);
+
+    assert_eq!(descs(&list.borrow().flattened()), ["a", "a1", "a2", "b"]);
+    let current = list.borrow().current_task.clone().unwrap();
+    assert_eq!(current.borrow().desc, "a1");
+    assert_eq!(list.borrow().state.selected(), Some(1));
+}
+
+#[test]
+fn closing_all_the_workspaces_hides_their_children_from_the_panel() {
+    let mut panel = WorkspaceWidget::new(WorkspaceType::Normal);
+    let work = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+    let team = Rc::new(RefCell::new(Workspace::new("Team".to_string())));
+    work.borrow_mut().add_child(team.clone());
+    panel.add_workspace(work.clone());
+    panel.add_workspace(Rc::new(RefCell::new(Workspace::new("Home".to_string()))));
+    panel.select(Some(team));
+
+    panel.fold(Fold::CloseAll);
+
+    assert!(!work.borrow().expanded);
+    assert_eq!(panel.shown().len(), 2);
+    let current = panel.current_workspace.clone().unwrap();
+    assert!(Rc::ptr_eq(&current, &work));
+    assert_eq!(panel.ws_state.selected(), Some(0));
+    let next = panel.get_selected_bf(SelectBF::Forward).unwrap();
+    assert_eq!(next.borrow().desc, "Home");
+
+    panel.fold(Fold::OpenAll);
+
+    assert_eq!(panel.shown().len(), 3);
+}
@@ -0,0 +1,68 @@
+//! Tests of the glyph sets
+
+use todo::app::ui::glyphs::{self, ASCII, Glyphs, NERD, PLAIN};
+
+fn fields(g: &Glyphs) -> [&'static str; 18] {
+    [
+        g.todo,
+        g.in_process,
+        g.finished,
+        g.deprecated,
+        g.common,
+        g.important,
+        g.critical,
+        g.expanded,
+        g.collapsed,
+        g.active,
+        g.locked,
+        g.breadcrumb,
+        g.gauge_full,
+        g.gauge_empty,
+        g.guide_branch,
+        g.guide_last,
+        g.guide_bar,
+        g.guide_blank,
+    ]
+}
+
+#[test]
+fn ascii_set_is_ascii_and_aligned_with_nerd() {
+    for (ascii, nerd) in fields(&ASCII).into_iter().zip(fields(&NERD)) {
+        assert!(ascii.is_ascii(), "{:?}", ascii);
+        assert_eq!(ascii.chars().count(), nerd.chars().count(), "{:?}", ascii);
+    }
+}
+
+#[test]
+fn first_selection_wins() {
+    glyphs::select(true);
+    glyphs::select(false);
+    assert_eq!(*glyphs::current(), ASCII);
+}
+
+#[test]
+fn plain_set_is_ascii_with_spelled_out_statuses() {
+    for glyph in fields(&PLAIN) {
+        assert!(glyph.is_ascii(), "{:?}", glyph);
+    }
+    let statuses = [
+        PLAIN.todo,
+        PLAIN.in_process,
+        PLAIN.finished,
+        PLAIN.deprecated,
+    ];
+    assert!(
+        statuses
+            .iter()
+            .all(|status| status.len() == PLAIN.todo.len())
+    );
+    assert_eq!(PLAIN.todo.trim_end(), "[TODO]");
+    assert_eq!(PLAIN.finished.trim_end(), "[DONE]");
+    assert_eq!(PLAIN.selected, "> ");
+    // nothing else is drawn with the marker of the selection
+    for glyph in fields(&PLAIN) {
+        assert!(!glyph.contains('>'), "{:?}", glyph);
+    }
+    assert_eq!(NERD.selected, "");
+    assert_eq!(PLAIN.border.top_left, "+");
+}
@@ -0,0 +1,168 @@
+//! Tests of the month view of the due dates
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{Days, Local, NaiveDate};
+use common::{code, ui_with};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        calendarwidget::{CalendarWidget, heat_style},
+        todolistwidget::{Task, TaskStatus, TodoList},
+        workspacewidget::Workspace,
+    },
+};
+use tokio::sync::mpsc;
+
+fn task(desc: &str, status: TaskStatus, due: Option<NaiveDate>) -> Rc<RefCell<Task>> {
+    let mut task = Task::new(desc.to_string(), due);
+    task.status = status;
+    Rc::new(RefCell::new(task))
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// A `Work` workspace with a collapsed `Backend` child whose list holds a collapsed parent
+/// task with a subtask due today, and an archived workspace with a task due today
+fn ui() -> (Ui, mpsc::Sender<UiMessage>, mpsc::Sender<KeyEvent>) {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![], vec![]);
+
+    let work = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+    let backend = Rc::new(RefCell::new(Workspace::new("Backend".to_string())));
+    work.borrow_mut().add_child(backend.clone());
+    work.borrow_mut().expanded = false;
+    let parent = task("Release", TaskStatus::InProcess, None);
+    parent
+        .borrow_mut()
+        .add_child(task("Tag", TaskStatus::Todo, Some(today())));
+    parent
+        .borrow_mut()
+        .add_child(task("Changelog", TaskStatus::Finished, Some(today())));
+    parent.borrow_mut().expanded = false;
+    let mut list = TodoList::new(backend.borrow().id);
+    list.add_task(parent);
+    list.add_task(task(
+        "Deploy",
+        TaskStatus::Todo,
+        today().checked_add_days(Days::new(1)),
+    ));
+    ui.todolist.add_list(Rc::new(RefCell::new(list)));
+    ui.workspace.add_workspace(work.clone());
+    ui.workspace.select(Some(work));
+
+    let old = Rc::new(RefCell::new(Workspace::new("Old".to_string())));
+    let mut old_list = TodoList::new(old.borrow().id);
+    old_list.add_task(task("Forgotten", TaskStatus::Todo, Some(today())));
+    ui.todolist.add_list(Rc::new(RefCell::new(old_list)));
+    ui.archived_ws.add_workspace(old);
+    (ui, ui_tx, input_tx)
+}
+
+#[test]
+fn only_open_tasks_of_active_workspaces_are_bucketed() {
+    let (ui, _, _) = ui();
+
+    let days = ui.todolist.due_days(&ui.workspace.workspaces);
+
+    let descs = |day: NaiveDate| -> Vec<String> {
+        days[&day]
+            .iter()
+            .map(|(_, task)| task.borrow().desc.clone())
+            .collect()
+    };
+    assert_eq!(days.len(), 2);
+    assert_eq!(descs(today()), ["Tag"]);
+    assert_eq!(
+        descs(today().checked_add_days(Days::new(1)).unwrap()),
+        ["Deploy"]
+    );
+}
+
+#[test]
+fn heat_gets_brighter_with_the_tasks_due() {
+    assert_eq!(heat_style(0), Default::default());
+    let backgrounds: Vec<_> = (1..=5).map(|due| heat_style(due).bg).collect();
+    assert!(backgrounds.iter().all(Option::is_some));
+    assert_ne!(backgrounds[0], backgrounds[1]);
+    assert_ne!(backgrounds[2], backgrounds[3]);
+    assert_eq!(backgrounds[3], backgrounds[4]);
+
+    let calendar = CalendarWidget::heatmap([(today(), 3)].into_iter().collect());
+    assert_eq!(calendar.due_at_cursor(), 3);
+    assert_eq!(CalendarWidget::new().due_at_cursor(), 0);
+}
+
+async fn run(
+    mut ui: Ui,
+    ui_tx: mpsc::Sender<UiMessage>,
+    input_tx: mpsc::Sender<KeyEvent>,
+    keys: &[KeyCode],
+) -> (Ui, Arc<Mutex<AppState>>) {
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::Heatmap))
+        .await
+        .unwrap();
+    for c in keys {
+        input_tx.send(code(*c)).await.unwrap();
+    }
+    drop(ui_tx);
+    drop(input_tx);
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+    (ui, appstate)
+}
+
+#[tokio::test]
+async fn enter_on_a_day_jumps_to_the_picked_task() {
+    let (ui, ui_tx, input_tx) = ui();
+
+    let (ui, appstate) = run(ui, ui_tx, input_tx, &[KeyCode::Enter, KeyCode::Enter]).await;
+
+    let ws = ui.workspace.current_workspace.clone().unwrap();
+    assert_eq!(ws.borrow().desc, "Backend");
+    assert!(ui.workspace.workspaces[0].borrow().expanded);
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let selected = list.borrow().current_task.clone().unwrap();
+    assert_eq!(selected.borrow().desc, "Tag");
+    assert!(list.borrow().tasks[0].borrow().expanded);
+    assert_eq!(list.borrow().state.selected(), Some(1));
+    assert!(ui.todolist.focused);
+    assert_eq!(
+        appstate.lock().unwrap().current_focus,
+        CurrentFocus::TodoList
+    );
+}
+
+#[tokio::test]
+async fn closing_the_view_keeps_the_selection() {
+    let (ui, ui_tx, input_tx) = ui();
+
+    let (ui, appstate) = run(
+        ui,
+        ui_tx,
+        input_tx,
+        &[KeyCode::Right, KeyCode::Right, KeyCode::Enter, KeyCode::Esc],
+    )
+    .await;
+
+    assert_eq!(ui.prompt.desc, "Nothing Due That Day !");
+    let ws = ui.workspace.current_workspace.clone().unwrap();
+    assert_eq!(ws.borrow().desc, "Work");
+    assert!(!ui.todolist.focused);
+    assert_eq!(
+        appstate.lock().unwrap().current_focus,
+        CurrentFocus::Workspace
+    );
+}
@@ -0,0 +1,86 @@
+//! Tests of the tabs of the help page
+
+mod common;
+
+use common::rows;
+use ratatui::{Terminal, backend::TestBackend, style::Color};
+use todo::app::{
+    appstate::CurrentFocus,
+    ui::helpwidget::{HelpTab, HelpWidget},
+};
+
+/// Draw the help page on a 100x40 screen, as rows of text
+fn draw(help: &mut HelpWidget) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    terminal
+        .draw(|f| f.render_widget(&mut *help, f.area()))
+        .unwrap();
+    rows(&terminal)
+}
+
+#[test]
+fn the_help_opens_on_the_tab_of_the_focused_panel() {
+    let mut help = HelpWidget::new();
+    for (focus, tab) in [
+        (CurrentFocus::Workspace, HelpTab::Workspace),
+        (CurrentFocus::TodoList, HelpTab::TodoList),
+        (CurrentFocus::ArchivedWorkspace, HelpTab::Archived),
+    ] {
+        help.keymap.focus = focus;
+        help.scroll = 3;
+        help.open();
+        assert_eq!(help.tab, tab);
+        assert_eq!(help.scroll, 0);
+    }
+}
+
+#[test]
+fn the_tabs_go_round() {
+    assert_eq!(HelpTab::General.next(), HelpTab::Workspace);
+    assert_eq!(HelpTab::Archived.next(), HelpTab::General);
+    assert_eq!(HelpTab::General.prev(), HelpTab::Archived);
+    for tab in HelpTab::ALL {
+        assert_eq!(tab.next().prev(), tab);
+    }
+    assert_eq!(HelpTab::Workspace.color(), Color::LightGreen);
+    assert_eq!(HelpTab::Archived.color(), Color::LightYellow);
+}
+
+#[test]
+fn only_the_keys_of_the_tab_are_listed() {
+    let mut help = HelpWidget::new();
+    help.keymap.focus = CurrentFocus::TodoList;
+    help.open();
+    let screen = draw(&mut help).join("\n");
+
+    assert!(screen.contains("General"), "{}", screen);
+    assert!(screen.contains("Archived"), "{}", screen);
+    // a key of the todo list panel, not of the archived one
+    assert!(screen.contains("pick the color of the task"), "{}", screen);
+    assert!(
+        !screen.contains("recovery the current workspace"),
+        "{}",
+        screen
+    );
+}
+
+#[test]
+fn the_scroll_is_bound_by_the_tab_shown() {
+    let mut help = HelpWidget::new();
+    help.keymap.focus = CurrentFocus::TodoList;
+    help.open();
+    draw(&mut help);
+    let todolist_max = help.scroll_max;
+    assert!(todolist_max > 0);
+
+    help.scroll = usize::MAX;
+    help.show(HelpTab::Archived);
+    draw(&mut help);
+    assert_eq!(help.scroll, 0);
+    assert!(help.scroll_max < todolist_max);
+
+    help.show(HelpTab::TodoList);
+    help.scroll = usize::MAX;
+    draw(&mut help);
+    assert_eq!(help.scroll, todolist_max);
+}
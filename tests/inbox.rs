@@ -0,0 +1,139 @@
+//! Tests of the Inbox and its triage
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{code, task, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    data::{self, Datas, INBOX_NAME},
+    ui::{
+        UiMessage, WidgetAction,
+        todolistwidget::{TodoList, Urgency},
+    },
+};
+
+fn descs(list: &Rc<RefCell<TodoList>>) -> Vec<String> {
+    list.borrow()
+        .tasks
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+#[test]
+fn inbox_is_created_once_and_first() {
+    let mut datas = data::demo_datas();
+    let inbox = datas.ensure_inbox();
+
+    assert_eq!(datas.ensure_inbox(), inbox);
+    let first = datas.workspace.workspaces[0].borrow();
+    assert_eq!(first.id, inbox);
+    assert_eq!(first.desc, INBOX_NAME);
+    assert!(datas.todolist.list_of(inbox).is_some());
+}
+
+#[test]
+fn inbox_id_survives_a_round_trip_and_a_deleted_inbox_comes_back() {
+    let path = std::env::temp_dir().join(format!("todo-inbox-{}.json", std::process::id()));
+    let mut datas = Datas::default();
+    let inbox = datas.ensure_inbox();
+    data::save_data(&path, &datas).unwrap();
+    let mut loaded = data::load_data(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(loaded.ensure_inbox(), inbox);
+    assert_eq!(loaded.workspace.workspaces.len(), 1);
+
+    loaded.workspace.workspaces.clear();
+    let recreated = loaded.ensure_inbox();
+    assert_ne!(recreated, inbox);
+    assert_eq!(loaded.workspace.workspaces[0].borrow().id, recreated);
+}
+
+#[tokio::test]
+async fn triage_moves_deletes_and_skips_until_the_end() {
+    let (mut ui, _ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+    let mut datas = Datas::default();
+    let inbox_id = datas.ensure_inbox();
+    let work = workspace("Work");
+    let work_id = work.borrow().id;
+    datas.workspace.add_workspace(work);
+    datas
+        .todolist
+        .add_list(Rc::new(RefCell::new(TodoList::new(work_id))));
+    let inbox = datas.todolist.list_of(inbox_id).unwrap();
+    for desc in ["report", "spam", "later"] {
+        inbox.borrow_mut().add_task(task(desc));
+    }
+    ui.workspace = datas.workspace;
+    ui.todolist = datas.todolist;
+
+    // move the report to the only other workspace, delete the spam, raise and skip the last
+    for key in [
+        KeyCode::Char('m'),
+        KeyCode::Enter,
+        KeyCode::Char('x'),
+        KeyCode::Char('+'),
+        KeyCode::Char('n'),
+    ] {
+        input_tx.send(code(key)).await.unwrap();
+    }
+    let rx = ui.input_rx.clone();
+    tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        ui.triage(rx, &mut terminal, &inbox),
+    )
+    .await
+    .expect("the triage should end after the last task");
+
+    assert_eq!(descs(&inbox), ["later"]);
+    assert!(matches!(
+        inbox.borrow().tasks[0].borrow().urgency,
+        Some(Urgency::Common)
+    ));
+    assert_eq!(descs(&ui.todolist.list_of(work_id).unwrap()), ["report"]);
+    assert!(inbox.borrow().current_task.is_none());
+}
+
+#[tokio::test]
+async fn enter_folds_the_subtasks_in_the_inbox() {
+    let (mut ui, ui_tx, _input_tx) = ui_with(vec![], vec![]);
+
+    let mut datas = Datas::default();
+    let inbox_id = datas.ensure_inbox();
+    let inbox = datas.todolist.list_of(inbox_id).unwrap();
+    let parent = task("report");
+    parent.borrow_mut().add_child(task("draft"));
+    inbox.borrow_mut().add_task(parent.clone());
+    inbox.borrow_mut().current_task = Some(parent.clone());
+    inbox.borrow_mut().state.select(Some(0));
+    ui.workspace = datas.workspace;
+    ui.todolist = datas.todolist;
+    ui.todolist.current_todolist = Some(inbox.clone());
+    ui.todolist.focused = true;
+    assert!(ui.todolist.is_inbox());
+
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::EnterTask))
+        .await
+        .unwrap();
+    drop(ui_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(appstate)))
+        .await;
+
+    assert!(!parent.borrow().expanded);
+    assert_eq!(descs(&inbox), ["report"]);
+}
@@ -0,0 +1,197 @@
+//! Tests of the journal of the actions since the last save
+
+mod common;
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use common::{code, task, ui_with, workspace};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    journal::{self, Entry, Journal, Selection},
+    ui::{
+        SelectAction, Ui, UiMessage, WidgetAction,
+        todolistwidget::{TaskStatus, TodoWidget},
+        workspacewidget::WorkspaceType,
+    },
+};
+use tokio::sync::mpsc;
+
+/// A `data.json` in a directory of its own, which doesn't exist yet
+fn data_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("todo-journal-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("data.json")
+}
+
+fn entry(action: WidgetAction, task: Option<usize>, keys: &str) -> Entry {
+    Entry {
+        action,
+        selection: Selection {
+            focus: CurrentFocus::TodoList,
+            origin: WorkspaceType::Normal,
+            workspace: Some(0),
+            archived: None,
+            task,
+        },
+        keys: keys
+            .chars()
+            .map(|c| (KeyCode::Char(c), KeyModifiers::NONE))
+            .collect(),
+    }
+}
+
+/// `Work` open in the todo list, with a selected `Write` task
+fn ui_with_a_task() -> (Ui, mpsc::Sender<UiMessage>, mpsc::Sender<KeyEvent>) {
+    ui_with(vec![workspace("Work")], vec![task("Write")])
+}
+
+fn tasks(ui: &Ui) -> Vec<(String, TaskStatus)> {
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    TodoWidget::get_flattened(&list.borrow().tasks)
+        .iter()
+        .map(|task| (task.borrow().desc.clone(), task.borrow().status.clone()))
+        .collect()
+}
+
+#[test]
+fn actions_are_appended_and_read_back() {
+    let path = data_file("append");
+    let mut journal = Journal::create(journal::journal_path(&path)).unwrap();
+    assert!(journal.is_empty());
+    let entries = [
+        entry(WidgetAction::AddTask, Some(0), "Ship\r"),
+        entry(
+            WidgetAction::MarkTaskStatus(TaskStatus::Finished),
+            Some(1),
+            "",
+        ),
+    ];
+    entries
+        .iter()
+        .for_each(|entry| journal.append(entry).unwrap());
+
+    assert_eq!(journal::pending(&path), entries);
+    // one line per action, the keys left out when there are none
+    let content = fs::read_to_string(&journal.path).unwrap();
+    assert_eq!(content.lines().count(), 2);
+    assert!(!content.lines().nth(1).unwrap().contains("keys"));
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn a_torn_line_is_skipped_and_an_older_journal_is_ignored() {
+    let path = data_file("torn");
+    let mut journal = Journal::create(journal::journal_path(&path)).unwrap();
+    let first = entry(WidgetAction::DeleteTask, Some(0), "y");
+    journal.append(&first).unwrap();
+    fs::write(
+        &journal.path,
+        format!(
+            "{}{{\"action\":\"AddT",
+            fs::read_to_string(&journal.path).unwrap()
+        ),
+    )
+    .unwrap();
+    assert_eq!(journal::pending(&path), [first]);
+
+    // the data file was saved over after the journal, e.g. by todo serve
+    fs::write(&path, "{}").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    assert!(journal::pending(&path).is_empty());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn a_save_drops_what_it_holds_and_keeps_the_rest() {
+    let path = data_file("saved");
+    let mut journal = Journal::create(journal::journal_path(&path)).unwrap();
+    journal
+        .append(&entry(WidgetAction::IncreaseUrgency, Some(0), ""))
+        .unwrap();
+    let mark = journal.len();
+    let after = entry(WidgetAction::DecreaseUrgency, Some(0), "");
+    journal.append(&after).unwrap();
+
+    journal.saved(mark).unwrap();
+    assert_eq!(journal::pending(&path), std::slice::from_ref(&after));
+    // appending goes on after the kept actions
+    journal.append(&after).unwrap();
+    assert_eq!(journal::pending(&path), [after.clone(), after]);
+
+    journal.saved(journal.len()).unwrap();
+    assert!(journal.is_empty());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[tokio::test]
+async fn replaying_the_journal_repeats_the_actions() {
+    let path = data_file("replay");
+    let (mut ui, ui_tx, input_tx) = ui_with_a_task();
+    ui.journal = Some(Journal::create(journal::journal_path(&path)).unwrap());
+    for c in "Ship".chars().map(KeyCode::Char).chain([KeyCode::Enter]) {
+        input_tx.send(code(c)).await.unwrap();
+    }
+    drop(input_tx);
+    for action in [
+        WidgetAction::AddTask,
+        WidgetAction::SelectDown,
+        WidgetAction::MarkTaskStatus(TaskStatus::Finished),
+    ] {
+        ui_tx.send(UiMessage::WAction(action)).await.unwrap();
+    }
+    drop(ui_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(appstate)))
+        .await;
+    let done = tasks(&ui);
+    assert_eq!(
+        done,
+        [
+            ("Write".to_string(), TaskStatus::Todo),
+            ("Ship".to_string(), TaskStatus::Finished)
+        ]
+    );
+
+    // the moves aren't journaled, the selection of each action is
+    let entries = journal::pending(&path);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].key_events().len(), 5);
+
+    let (mut replayed, ui_tx, _input_tx) = ui_with_a_task();
+    replayed.replay(entries);
+    drop(ui_tx);
+    replayed
+        .handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+
+    assert_eq!(tasks(&replayed), done);
+    assert!(replayed.dirty);
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn the_urgency_actions_of_older_journals_are_still_read() {
+    let read = |json: &str| serde_json::from_str::<WidgetAction>(json).unwrap();
+
+    assert_eq!(read(r#""IncreseUrgency""#), WidgetAction::IncreaseUrgency);
+    assert_eq!(read(r#""DecreseUrgency""#), WidgetAction::DecreaseUrgency);
+    assert_eq!(
+        serde_json::to_string(&WidgetAction::IncreaseUrgency).unwrap(),
+        r#""IncreaseUrgency""#
+    );
+}
@@ -0,0 +1,84 @@
+//! Tests of the list of the keys of the focused panel, opened with `g?` or `F1`
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{code, rows, task, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus, CurrentMode, Message},
+    ui::{UiMessage, WidgetAction, todolistwidget::TaskStatus},
+};
+
+/// The keys pressed in the list of a panel, and the message it gives with the frame drawn
+async fn pressed(focus: CurrentFocus, keys: &[KeyCode]) -> (Option<Message>, Vec<String>) {
+    let (mut ui, _ui_tx, input_tx) = ui_with(vec![], vec![]);
+    ui.helpwidget.keymap.focus = focus;
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    for key in keys {
+        input_tx.send(code(*key)).await.unwrap();
+    }
+    drop(input_tx);
+
+    let rx = ui.input_rx.clone();
+    let msg = ui.key_help(rx, &mut terminal).await;
+    (msg, rows(&terminal))
+}
+
+#[tokio::test]
+async fn only_the_keys_of_the_focused_panel_are_listed() {
+    let (_, rows) = pressed(CurrentFocus::TodoList, &[KeyCode::Esc]).await;
+    let screen = rows.concat();
+    assert!(screen.contains("mark the task as completed"));
+    assert!(!screen.contains("add new workspace"));
+
+    let (_, rows) = pressed(CurrentFocus::Workspace, &[KeyCode::Esc]).await;
+    let screen = rows.concat();
+    assert!(screen.contains("add new workspace"));
+    assert!(!screen.contains("mark the task as completed"));
+}
+
+#[tokio::test]
+async fn a_listed_key_closes_the_list_and_gives_its_message() {
+    let (msg, _) = pressed(CurrentFocus::TodoList, &[KeyCode::Char('c')]).await;
+    assert!(matches!(msg, Some(Message::Complete)));
+
+    let (msg, _) = pressed(CurrentFocus::TodoList, &[KeyCode::Char('/')]).await;
+    assert!(matches!(msg, Some(Message::Filter)));
+
+    // not a key of the workspace panel, it waits for another key
+    let (msg, _) = pressed(
+        CurrentFocus::Workspace,
+        &[KeyCode::Char('c'), KeyCode::Char('A')],
+    )
+    .await;
+    assert!(matches!(msg, Some(Message::Archive)));
+
+    let (msg, _) = pressed(CurrentFocus::Workspace, &[KeyCode::F(1)]).await;
+    assert!(msg.is_none());
+}
+
+#[tokio::test]
+async fn the_key_pressed_runs_on_the_selected_task() {
+    let task = task("Ship");
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![workspace("Work")], vec![task.clone()]);
+    ui.helpwidget.keymap.focus = CurrentFocus::TodoList;
+
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::KeyHelp))
+        .await
+        .unwrap();
+    input_tx.send(code(KeyCode::Char('c'))).await.unwrap();
+    drop(ui_tx);
+    drop(input_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let appstate = Arc::new(Mutex::new(appstate));
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+
+    assert_eq!(task.borrow().status, TaskStatus::Finished);
+    assert_eq!(appstate.lock().unwrap().current_mode, CurrentMode::Normal);
+}
@@ -0,0 +1,171 @@
+//! Tests of the key hints at the bottom, which follow the mode and the open popup
+
+mod common;
+
+use common::{rows, task, ui_with, workspace};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{CurrentFocus, CurrentMode},
+    ui::{
+        Ui,
+        keymap::{KeymapWidget, ListContext},
+        workspacewidget::WorkspaceType,
+    },
+};
+
+/// The hints drawn on a line 120 wide
+fn hints(keymap: &mut KeymapWidget) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(120, 1)).unwrap();
+    terminal
+        .draw(|f| f.render_widget(&mut *keymap, f.area()))
+        .unwrap();
+    rows(&terminal).concat()
+}
+
+#[test]
+fn the_hints_follow_the_mode() {
+    let mut keymap = KeymapWidget::new(CurrentFocus::TodoList);
+    assert!(hints(&mut keymap).starts_with(" <a>"));
+
+    keymap.mode = CurrentMode::Search;
+    let search = hints(&mut keymap);
+    assert!(
+        search.starts_with(" <n>next  <N>previous  <esc>exit  <a>"),
+        "{}",
+        search
+    );
+
+    keymap.mode = CurrentMode::Insert;
+    let insert = hints(&mut keymap);
+    assert!(insert.starts_with(" <enter>confirm  <esc>cancel "));
+    assert!(!insert.contains("ctrl-o"));
+    keymap.calendar = true;
+    assert!(hints(&mut keymap).contains("<ctrl-o>calendar"));
+
+    keymap.mode = CurrentMode::Sort;
+    assert!(hints(&mut keymap).starts_with(" <j/k>select"));
+}
+
+/// The keys of the task list the hint bar shows in a context
+fn task_keys(context: ListContext) -> Vec<String> {
+    let mut keymap = KeymapWidget::new(CurrentFocus::TodoList);
+    keymap.context = context;
+    let (shown, _) = keymap.shown();
+    shown.iter().map(|hint| hint.key.clone()).collect()
+}
+
+#[test]
+fn a_list_with_tasks_shows_every_task_key() {
+    let keymap = KeymapWidget::new(CurrentFocus::TodoList);
+    let all: Vec<String> = (keymap.tasklist_hint.iter())
+        .map(|hint| hint.key.clone())
+        .collect();
+    assert_eq!(task_keys(ListContext::Tasks), all);
+}
+
+#[test]
+fn an_empty_list_shows_adding_and_moving_around_only() {
+    let keys = task_keys(ListContext::Empty);
+    assert_eq!(keys, ["a", "L", "F", "ctrl-s", "?"]);
+}
+
+#[test]
+fn an_archived_list_shows_reading_keys_only() {
+    let keys = task_keys(ListContext::Archived);
+    for key in ["enter", "f /", "e", "L", "G", "ctrl-s", "?"] {
+        assert!(keys.contains(&key.to_string()), "{} in {:?}", key, keys);
+    }
+    for key in ["a", "x", "d", "D", "r", "s", "F", "+ =", "- _"] {
+        assert!(!keys.contains(&key.to_string()), "{} in {:?}", key, keys);
+    }
+
+    // the filter keeps the keys of the list it filters
+    let mut keymap = KeymapWidget::new(CurrentFocus::TodoList);
+    keymap.context = ListContext::Archived;
+    keymap.mode = CurrentMode::Search;
+    let (shown, _) = keymap.shown();
+    assert!(shown.iter().any(|hint| hint.key == "f /"));
+    assert!(!shown.iter().any(|hint| hint.key == "a"));
+}
+
+#[test]
+fn the_palette_offers_the_task_commands_which_work() {
+    let mut keymap = KeymapWidget::new(CurrentFocus::TodoList);
+    let names = |keymap: &KeymapWidget| -> Vec<String> {
+        (keymap.commands(&CurrentFocus::TodoList).iter())
+            .map(|hint| hint.key.clone())
+            .collect()
+    };
+    assert!(names(&keymap).contains(&"x".to_string()));
+    keymap.context = ListContext::Archived;
+    assert!(!names(&keymap).contains(&"x".to_string()));
+    assert!(!names(&keymap).contains(&"a".to_string()));
+    keymap.context = ListContext::Empty;
+    assert!(names(&keymap).contains(&"a".to_string()));
+    assert!(!names(&keymap).contains(&"x".to_string()));
+    // the general commands don't depend on the list
+    for hint in keymap
+        .general_hint
+        .iter()
+        .filter(|hint| hint.message.is_some())
+    {
+        assert!(names(&keymap).contains(&hint.key), "{}", hint.key);
+    }
+}
+
+#[test]
+fn the_context_follows_the_list_on_screen() {
+    let (mut ui, mut terminal) = setup();
+    assert_eq!(ui.list_context(), ListContext::Tasks);
+
+    ui.todolist.origin = WorkspaceType::Archived;
+    terminal.draw(|f| ui.update(f)).unwrap();
+    assert_eq!(ui.helpwidget.keymap.context, ListContext::Archived);
+
+    ui.todolist.origin = WorkspaceType::Normal;
+    (ui.todolist.current_todolist.as_ref().unwrap())
+        .borrow_mut()
+        .tasks
+        .clear();
+    assert_eq!(ui.list_context(), ListContext::Empty);
+    ui.todolist.current_todolist = None;
+    assert_eq!(ui.list_context(), ListContext::Empty);
+}
+
+/// A UI whose key sender is dropped, so every popup draws once and is cancelled
+fn setup() -> (Ui, Terminal<TestBackend>) {
+    let (ui, _, _) = ui_with(vec![workspace("Work")], vec![task("Write")]);
+    (ui, Terminal::new(TestBackend::new(120, 24)).unwrap())
+}
+
+fn shown(terminal: &Terminal<TestBackend>, hint: &str) -> bool {
+    rows(terminal).iter().any(|row| row.contains(hint))
+}
+
+#[tokio::test]
+async fn the_popups_show_their_hints_while_open() {
+    let (mut ui, mut terminal) = setup();
+
+    let rx = ui.input_rx.clone();
+    ui.get_input(rx, &mut terminal, "Add Task".to_string())
+        .await;
+    assert!(shown(&terminal, "<enter>confirm"));
+    assert!(!shown(&terminal, "<ctrl-o>calendar"));
+    assert_eq!(ui.helpwidget.keymap.mode, CurrentMode::Normal);
+
+    let rx = ui.input_rx.clone();
+    ui.input_due_date(rx, &mut terminal, "Due".to_string(), None)
+        .await;
+    assert!(shown(&terminal, "<ctrl-o>calendar"));
+    assert!(!ui.helpwidget.keymap.calendar);
+
+    let rx = ui.input_rx.clone();
+    ui.filter_find(rx, &mut terminal).await;
+    assert!(shown(&terminal, "<esc>cancel"));
+
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let rx = ui.input_rx.clone();
+    ui.pick_sort(rx, &mut terminal, &list).await;
+    assert!(shown(&terminal, "<enter>apply"));
+    assert_eq!(ui.helpwidget.keymap.mode, CurrentMode::Normal);
+}
@@ -0,0 +1,113 @@
+//! Tests of the links found in the tasks
+
+use ratatui::style::Modifier;
+use todo::app::{
+    links,
+    ui::todolistwidget::{Task, TodoWidget},
+};
+
+#[test]
+fn a_link_leaves_the_punctuation_around_it_out() {
+    for (text, link) in [
+        ("see https://example.com/a.", "https://example.com/a"),
+        ("(see https://example.com/a)", "https://example.com/a"),
+        ("https://example.com/a, then", "https://example.com/a"),
+        ("done? https://example.com/a?", "https://example.com/a"),
+        ("\"https://example.com/a\"", "https://example.com/a"),
+        ("<http://example.com/a>", "http://example.com/a"),
+        ("**https://example.com/a**", "https://example.com/a"),
+    ] {
+        assert_eq!(links::links(text), [link], "{}", text);
+    }
+}
+
+#[test]
+fn the_inside_of_a_link_is_kept() {
+    for link in [
+        "https://en.wikipedia.org/wiki/Rust_(game)",
+        "https://example.com/search?q=a,b&page=2#top",
+        "https://example.com/a.html",
+        "https://github.com/o/r/issues/12#issuecomment-3",
+        "https://例子.测试/路径",
+    ] {
+        assert_eq!(links::links(&format!("fix {}", link)), [link]);
+    }
+}
+
+#[test]
+fn every_link_is_found_in_order() {
+    let text = "fix https://a.example/1 and http://b.example/2; see https://c.example.";
+    assert_eq!(
+        links::links(text),
+        [
+            "https://a.example/1",
+            "http://b.example/2",
+            "https://c.example"
+        ]
+    );
+    let found = links::find(text);
+    assert_eq!(found[0], 4..23);
+}
+
+#[test]
+fn what_isnt_a_link_is_left_alone() {
+    for text in [
+        "no link here",
+        "https://",
+        "https:// example",
+        "xhttps://example.com",
+        "http:/example.com",
+        "",
+    ] {
+        assert!(links::links(text).is_empty(), "{}", text);
+    }
+}
+
+#[test]
+fn the_links_of_a_row_are_underlined() {
+    let task = Task::new("see https://example.com/1 now".to_string(), None);
+    let spans = TodoWidget::desc_spans(&task);
+
+    let text: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+    assert_eq!(text, ["see ", "https://example.com/1", " now"]);
+    let underlined: Vec<bool> = spans
+        .iter()
+        .map(|span| span.style.add_modifier.contains(Modifier::UNDERLINED))
+        .collect();
+    assert_eq!(underlined, [false, true, false]);
+
+    let plain = TodoWidget::desc_spans(&Task::new("Write".to_string(), None));
+    assert_eq!(plain.len(), 1);
+    assert_eq!(plain[0].content, "Write");
+}
+
+#[test]
+fn a_link_is_passed_to_the_opener_as_a_single_argument() {
+    let link = "https://example.com/?q=a&calc.exe|x^y";
+    let command = links::command(link);
+    let (program, args) = links::opener();
+
+    assert_eq!(command.get_program(), program);
+    assert_ne!(program, "cmd");
+    let passed: Vec<_> = command.get_args().collect();
+    assert_eq!(passed.len(), args.len() + 1);
+    assert_eq!(passed.last().unwrap(), &link);
+}
+
+#[test]
+fn the_links_of_the_notes_follow_those_of_the_description() {
+    let mut task = Task::new("plain task".to_string(), None);
+    assert!(links::task_links(&task).is_empty());
+
+    task.set_notes("docs at https://example.com/docs.\nissue https://example.com/1");
+    assert_eq!(
+        links::task_links(&task),
+        ["https://example.com/docs", "https://example.com/1"]
+    );
+
+    task.rename("fix https://example.com/1".to_string());
+    assert_eq!(
+        links::task_links(&task),
+        ["https://example.com/1", "https://example.com/docs"]
+    );
+}
@@ -0,0 +1,143 @@
+//! Tests of the macro registers and of the replay through the key handler senders
+
+mod common;
+
+use common::key;
+use todo::app::{
+    appstate::Message,
+    macros::{MAX_STEPS, MacroError, Macros, Recorder},
+};
+use tokio::sync::mpsc;
+
+#[test]
+fn registers_are_lowercase_letters() {
+    let mut macros = Macros::default();
+    assert_eq!(macros.start('A'), Err(MacroError::NotARegister));
+    assert_eq!(macros.start('1'), Err(MacroError::NotARegister));
+    assert_eq!(macros.recording(), None);
+    assert_eq!(macros.replay('@'), Err(MacroError::NotARegister));
+}
+
+#[test]
+fn nothing_is_recorded_outside_a_recording() {
+    let mut macros = Macros::default();
+    macros.record(Message::Complete).unwrap();
+    assert_eq!(macros.stop(), None);
+    assert_eq!(macros.replay('a'), Err(MacroError::Empty('a')));
+}
+
+#[test]
+fn exit_redraw_and_prompt_messages_are_skipped() {
+    let mut macros = Macros::default();
+    macros.start('a').unwrap();
+    for msg in [
+        Message::Update,
+        Message::Prompt("Recording @a !".to_string()),
+        Message::Due,
+        Message::DismissBanner,
+        Message::BannerJump,
+        Message::Exit,
+    ] {
+        macros.record(msg).unwrap();
+    }
+    macros.record(Message::Edit(key('1'))).unwrap();
+    macros.stop();
+
+    assert_eq!(
+        macros.replay('a').unwrap(),
+        [Message::Due, Message::Edit(key('1'))]
+    );
+}
+
+#[test]
+fn recording_again_replaces_the_register() {
+    let mut macros = Macros::default();
+    macros.start('a').unwrap();
+    macros.record(Message::Complete).unwrap();
+    macros.stop();
+    macros.start('a').unwrap();
+    macros.record(Message::Todo).unwrap();
+
+    // the old content stays until the new recording is stopped, but can't be replayed into itself
+    assert_eq!(macros.replay('a'), Err(MacroError::Recursive('a')));
+    macros.stop();
+    assert_eq!(macros.replay('a').unwrap(), [Message::Todo]);
+}
+
+#[test]
+fn overlong_recording_is_dropped() {
+    let mut macros = Macros::default();
+    macros.start('b').unwrap();
+    macros.record(Message::Complete).unwrap();
+    macros.stop();
+
+    macros.start('b').unwrap();
+    for _ in 0..MAX_STEPS {
+        macros.record(Message::MoveDown).unwrap();
+    }
+    assert_eq!(
+        macros.record(Message::MoveDown),
+        Err(MacroError::TooLong('b'))
+    );
+    assert_eq!(macros.recording(), None);
+    assert_eq!(macros.replay('b').unwrap(), [Message::Complete]);
+}
+
+#[tokio::test]
+async fn replay_sends_the_steps_and_records_them_inline() {
+    let (tx, mut rx) = mpsc::channel(16);
+    let (input_tx, _input_rx) = mpsc::channel(16);
+    let mut out = Recorder::new(tx, input_tx);
+
+    out.start('a').await;
+    out.send(Message::Complete).await.unwrap();
+    out.send(Message::Due).await.unwrap();
+    out.send(Message::Edit(key('1'))).await.unwrap();
+    out.stop().await;
+
+    out.start('b').await;
+    out.send(Message::MoveDown).await.unwrap();
+    out.replay('a').await;
+    out.stop().await;
+
+    drop(out);
+    let mut sent = Vec::new();
+    while let Some(msg) = rx.recv().await {
+        if !matches!(msg, Message::Prompt(_)) {
+            sent.push(msg);
+        }
+    }
+    assert_eq!(
+        sent,
+        [
+            Message::Complete,
+            Message::Due,
+            Message::Edit(key('1')),
+            Message::MoveDown,
+            Message::Complete,
+            Message::Due,
+            Message::Edit(key('1')),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn nested_register_holds_the_steps_not_a_reference() {
+    let (tx, _rx) = mpsc::channel(64);
+    let (input_tx, _input_rx) = mpsc::channel(64);
+    let mut out = Recorder::new(tx, input_tx);
+
+    out.start('a').await;
+    out.send(Message::Complete).await.unwrap();
+    out.stop().await;
+    out.start('b').await;
+    out.replay('a').await;
+    out.stop().await;
+    // re-recording `a` as a replay of `b` replays the old steps, it can't loop
+    out.start('a').await;
+    out.replay('b').await;
+    out.replay('a').await;
+    out.stop().await;
+
+    assert_eq!(out.macros.replay('a').unwrap(), [Message::Complete]);
+}
@@ -0,0 +1,214 @@
+//! Tests of the Markdown checklist import
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+use todo::app::{
+    cli::Cli,
+    data::{self, Datas, OnClash},
+    markdown::{self, Item},
+    ui::todolistwidget::{Task, TaskStatus},
+};
+
+/// The tasks as `(depth, desc, status)` in tree order
+fn outline(tasks: &[Rc<RefCell<Task>>]) -> Vec<(usize, String, TaskStatus)> {
+    fn walk(tasks: &[Rc<RefCell<Task>>], depth: usize, out: &mut Vec<(usize, String, TaskStatus)>) {
+        for task in tasks {
+            let task = task.borrow();
+            out.push((depth, task.desc.clone(), task.status.clone()));
+            walk(&task.children, depth + 1, out);
+        }
+    }
+    let mut out = Vec::new();
+    walk(tasks, 0, &mut out);
+    out
+}
+
+fn row(depth: usize, desc: &str, status: TaskStatus) -> (usize, String, TaskStatus) {
+    (depth, desc.to_string(), status)
+}
+
+#[test]
+fn items_take_their_status_from_the_box() {
+    let item = |line| markdown::parse_item(line).map(|item| item.status);
+
+    assert_eq!(item("- [ ] a"), Some(TaskStatus::Todo));
+    assert_eq!(item("- [x] a"), Some(TaskStatus::Finished));
+    assert_eq!(item("* [X] a"), Some(TaskStatus::Finished));
+    assert_eq!(item("+ [~] a"), Some(TaskStatus::InProcess));
+    assert_eq!(item("1. [-] a"), Some(TaskStatus::Deprecated));
+    assert_eq!(item("2) plain item"), Some(TaskStatus::Todo));
+}
+
+#[test]
+fn lines_which_are_not_items_are_skipped() {
+    for line in [
+        "",
+        "# Groceries",
+        "Some text - with a dash",
+        "**bold**",
+        "---",
+        "-no space",
+        "- [ ]",
+        "- ",
+        "12.5 kg",
+    ] {
+        assert_eq!(markdown::parse_item(line), None, "{:?}", line);
+    }
+}
+
+#[test]
+fn trailing_dates_become_due_dates() {
+    let due = NaiveDate::from_ymd_opt(2025, 7, 1);
+    let item = |line| markdown::parse_item(line).unwrap();
+
+    assert_eq!(
+        item("- [ ] buy milk (2025-07-01)"),
+        Item {
+            indent: 0,
+            desc: "buy milk".to_string(),
+            status: TaskStatus::Todo,
+            due,
+        }
+    );
+    assert_eq!(item("- [ ] buy milk (due 2025-07-01)").due, due);
+    assert_eq!(item("- [ ] buy milk  ( due: 2025-07-01 )").due, due);
+    let kept = item("- [ ] call (the bank)");
+    assert_eq!((kept.desc.as_str(), kept.due), ("call (the bank)", None));
+    let invalid = item("- [ ] pay (2025-13-01)");
+    assert_eq!(invalid.desc, "pay (2025-13-01)");
+}
+
+#[test]
+fn tabs_and_spaces_both_nest() {
+    let text = "- [ ] parent\n\t- [x] tab child\n    - [ ] space child\n\t\t- [ ] grandchild\n";
+
+    let tasks = markdown::parse_checklist(text);
+
+    assert_eq!(
+        outline(&tasks),
+        [
+            row(0, "parent", TaskStatus::Todo),
+            row(1, "tab child", TaskStatus::Finished),
+            row(1, "space child", TaskStatus::Todo),
+            row(2, "grandchild", TaskStatus::Todo),
+        ]
+    );
+}
+
+#[test]
+fn a_messy_real_world_checklist() {
+    let text = "\
+## Release 1.2
+
+Things to do before Friday:
+
+* [x] Write changelog
+* [ ] Tag the release (due 2025-07-04)
+   * [ ] bump version
+     - [X] Cargo.toml
+     - [ ]   README badge
+   * [~] run the benchmarks
+1. [ ] Announce
+  2. [-] on the old forum\r
+  3. [ ] on the blog
+
+> quoted - [ ] not an item
+- [ ] Celebrate
+";
+
+    let tasks = markdown::parse_checklist(text);
+
+    assert_eq!(
+        outline(&tasks),
+        [
+            row(0, "Write changelog", TaskStatus::Finished),
+            row(0, "Tag the release", TaskStatus::Todo),
+            row(1, "bump version", TaskStatus::Todo),
+            row(2, "Cargo.toml", TaskStatus::Finished),
+            row(2, "README badge", TaskStatus::Todo),
+            row(1, "run the benchmarks", TaskStatus::InProcess),
+            row(0, "Announce", TaskStatus::Todo),
+            row(1, "on the old forum", TaskStatus::Deprecated),
+            row(1, "on the blog", TaskStatus::Todo),
+            row(0, "Celebrate", TaskStatus::Todo),
+        ]
+    );
+    assert_eq!(tasks[1].borrow().due, NaiveDate::from_ymd_opt(2025, 7, 4));
+    assert_eq!(markdown::count(&tasks), 10);
+}
+
+#[test]
+fn a_dedent_between_two_levels_attaches_to_the_closest_shallower_item() {
+    let text = "- a\n        - deep\n    - middle\n";
+
+    let tasks = markdown::parse_checklist(text);
+
+    assert_eq!(
+        outline(&tasks),
+        [
+            row(0, "a", TaskStatus::Todo),
+            row(1, "deep", TaskStatus::Todo),
+            row(1, "middle", TaskStatus::Todo),
+        ]
+    );
+}
+
+#[test]
+fn import_appends_to_the_workspace_or_the_inbox() {
+    let mut datas = data::demo_datas();
+    let home = datas.find_workspace("Home").unwrap();
+    let before = datas.todolist.list_of(home).unwrap().borrow().tasks.len();
+
+    let (workspace, imported) = markdown::import(
+        &mut datas,
+        Some("Home"),
+        "- [ ] a\n  - [x] b\n",
+        OnClash::Merge,
+    )
+    .unwrap();
+
+    assert_eq!((workspace.as_str(), imported), ("Work / Home", 2));
+    let list = datas.todolist.list_of(home).unwrap();
+    assert_eq!(list.borrow().tasks.len(), before + 1);
+    assert_eq!(list.borrow().tasks[before].borrow().desc, "a");
+
+    let mut empty = Datas::default();
+    let (workspace, _) = markdown::import(&mut empty, None, "- x", OnClash::Merge).unwrap();
+    assert_eq!(workspace, data::INBOX_NAME);
+    assert!(markdown::import(&mut empty, None, "# nothing here", OnClash::Merge).is_err());
+    assert!(markdown::import(&mut empty, Some("Nowhere"), "- x", OnClash::Merge).is_err());
+}
+
+#[test]
+fn import_md_subcommand_takes_a_file_and_a_workspace() {
+    let args = |args: &[&str]| Cli::parse(args.iter().map(|arg| arg.to_string()));
+
+    let cli = args(&["import-md", "notes.md", "--workspace", "Home"]).unwrap();
+    assert_eq!(
+        cli.import_md,
+        Some(("notes.md".into(), Some("Home".to_string())))
+    );
+    let cli = args(&["import-md", "--workspace", "Home", "notes.md"]).unwrap();
+    assert_eq!(
+        cli.import_md,
+        Some(("notes.md".into(), Some("Home".to_string())))
+    );
+    assert_eq!(
+        args(&["import-md", "notes.md"]).unwrap().import_md,
+        Some(("notes.md".into(), None))
+    );
+    assert!(args(&["import-md"]).is_err());
+    assert!(args(&["import-md", "notes.md", "--workspace"]).is_err());
+    assert!(args(&["import-md", "a.md", "b.md"]).is_err());
+    assert_eq!(cli.on_clash, OnClash::Merge);
+    let cli = args(&[
+        "import-md",
+        "notes.md",
+        "--workspace",
+        "Home",
+        "--keep-both",
+    ])
+    .unwrap();
+    assert_eq!(cli.on_clash, OnClash::KeepBoth);
+}
@@ -0,0 +1,157 @@
+//! Tests of the names given to the workspaces and tasks added with nothing typed
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{code, task, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    config::Config,
+    names::{EmptyName, unique_name},
+    ui::{Ui, UiMessage, WidgetAction, todolistwidget::TodoList},
+};
+use tokio::sync::mpsc;
+
+/// Run the actions, each answered by the keys given with it, and return the Ui
+async fn run(ui: Ui, actions: &[(WidgetAction, &[KeyCode])]) -> Ui {
+    let mut ui = ui;
+    let (ui_tx, ui_rx) = mpsc::channel(8);
+    let (input_tx, input_rx) = mpsc::channel(16);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    for (action, keys) in actions {
+        for key in keys.iter() {
+            input_tx.send(code(*key)).await.unwrap();
+        }
+        ui_tx
+            .send(UiMessage::WAction(action.clone()))
+            .await
+            .unwrap();
+    }
+    drop(ui_tx);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+    ui
+}
+
+/// A Ui with the workspace `Work` open, holding `Task 1`, selected, and `Task 2`
+fn work(empty_name: EmptyName) -> Ui {
+    let (mut ui, _, _) = ui_with(
+        vec![workspace("Work")],
+        vec![task("Task 1"), task("Task 2")],
+    );
+    ui.config = Config {
+        empty_name,
+        ..Config::default()
+    };
+    ui
+}
+
+fn task_names(ui: &Ui) -> Vec<String> {
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let list = list.borrow();
+    list.tasks
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+fn workspace_names(ui: &Ui) -> Vec<String> {
+    (ui.workspace.workspaces.iter())
+        .map(|ws| ws.borrow().desc.clone())
+        .collect()
+}
+
+#[test]
+fn the_unique_name_takes_the_smallest_free_number() {
+    assert_eq!(unique_name("Task", ["Task 2", "Task 3"]), "Task 1");
+    assert_eq!(
+        unique_name("Task", ["Task 1", "Task 2", "task 3", "Task 3 "]),
+        "Task 3"
+    );
+    // only the exact names are taken, a name typed as the base leaves `1` free
+    assert_eq!(unique_name("Workspace", ["Workspace"]), "Workspace 1");
+    let taken: Vec<String> = (1..=100).map(|n| format!("Task {}", n)).collect();
+    assert_eq!(unique_name("Task", &taken), "Task 101");
+}
+
+#[tokio::test]
+async fn nothing_typed_cancels_the_add_by_default() {
+    let ui = run(
+        work(EmptyName::default()),
+        &[
+            (WidgetAction::AddTask, &[KeyCode::Enter]),
+            (
+                WidgetAction::AddWorkspace,
+                &[KeyCode::Char(' '), KeyCode::Enter],
+            ),
+        ],
+    )
+    .await;
+    assert_eq!(task_names(&ui), ["Task 1", "Task 2"]);
+    assert_eq!(workspace_names(&ui), ["Work"]);
+    assert_eq!(ui.prompt.desc, "Cancelled — empty name");
+}
+
+#[tokio::test]
+async fn esc_cancels_the_add_without_a_message() {
+    let ui = run(
+        work(EmptyName::Generate),
+        &[(WidgetAction::AddTask, &[KeyCode::Esc])],
+    )
+    .await;
+    assert_eq!(task_names(&ui), ["Task 1", "Task 2"]);
+    assert_ne!(ui.prompt.desc, "Cancelled — empty name");
+}
+
+#[tokio::test]
+async fn nothing_typed_generates_a_name_unique_among_the_siblings() {
+    let ui = run(
+        work(EmptyName::Generate),
+        &[
+            (WidgetAction::AddTask, &[KeyCode::Enter]),
+            (WidgetAction::AddTaskChild, &[KeyCode::Enter]),
+            (WidgetAction::AddWorkspace, &[KeyCode::Enter]),
+            (WidgetAction::AddWorkspaceChild, &[KeyCode::Enter]),
+        ],
+    )
+    .await;
+    assert_eq!(task_names(&ui), ["Task 1", "Task 2", "Task 3"]);
+    // the subtask has no siblings under the selected task
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let first = list.borrow().tasks[0].clone();
+    assert_eq!(first.borrow().children[0].borrow().desc, "Task 1");
+    assert_eq!(workspace_names(&ui), ["Work", "Workspace 1"]);
+    let work = ui.workspace.workspaces[0].clone();
+    assert_eq!(work.borrow().children[0].borrow().desc, "Workspace 1");
+}
+
+#[tokio::test]
+async fn the_workspace_made_for_a_task_added_with_nothing_open_gets_a_free_name() {
+    let (mut ui, _, _) = ui_with(vec![], vec![]);
+    for desc in ["Workspace", "Workspace 1"] {
+        let ws = workspace(desc);
+        ui.todolist
+            .add_list(Rc::new(RefCell::new(TodoList::new(ws.borrow().id))));
+        ui.workspace.add_workspace(ws);
+    }
+    let typed: Vec<KeyCode> = "Call mom"
+        .chars()
+        .map(KeyCode::Char)
+        .chain([KeyCode::Enter])
+        .collect();
+    let ui = run(ui, &[(WidgetAction::AddTask, &typed)]).await;
+    assert_eq!(
+        workspace_names(&ui),
+        ["Workspace", "Workspace 1", "Workspace 2"]
+    );
+    assert_eq!(task_names(&ui), ["Call mom"]);
+}
@@ -0,0 +1,189 @@
+//! Tests of the notes written about a task
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{code, key, task, ui_with, workspace};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    data::{self, Datas},
+    ui::{
+        Ui, UiMessage, WidgetAction, glyphs,
+        todolistwidget::{Task, TodoWidget},
+        workspacewidget::WorkspaceType,
+    },
+};
+use tokio::sync::mpsc;
+
+/// A Ui on the workspace `Work` with the task `Plan` selected
+fn plan() -> (Ui, Rc<RefCell<Task>>) {
+    let plan = task("Plan");
+    let (ui, _, _) = ui_with(vec![workspace("Work")], vec![plan.clone()]);
+    (ui, plan)
+}
+
+fn typed(text: &str) -> Vec<KeyEvent> {
+    (text.chars())
+        .map(|c| match c {
+            '\n' => code(KeyCode::Enter),
+            c => key(c),
+        })
+        .collect()
+}
+
+/// Open the notes of the selected task and press the keys
+async fn write(ui: &mut Ui, keys: Vec<KeyEvent>) {
+    send(ui, WidgetAction::EditNotes, keys).await;
+}
+
+/// Send the action to the UI loop with the keys waiting for its popup
+async fn send(ui: &mut Ui, action: WidgetAction, keys: Vec<KeyEvent>) {
+    let (ui_tx, ui_rx) = mpsc::channel(1);
+    let (input_tx, input_rx) = mpsc::channel(128);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    for key in keys {
+        input_tx.send(key).await.unwrap();
+    }
+    ui_tx.send(UiMessage::WAction(action)).await.unwrap();
+    drop(ui_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(appstate)))
+        .await;
+}
+
+#[tokio::test]
+async fn enter_starts_a_new_line_and_ctrl_s_keeps_the_notes() {
+    let (mut ui, plan) = plan();
+    let mut keys = typed("Ask Ann first.\nThen book the room.\n\n");
+    keys.push(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+
+    write(&mut ui, keys).await;
+
+    assert_eq!(plan.borrow().notes, "Ask Ann first.\nThen book the room.");
+    assert_eq!(ui.prompt.desc, "Notes Saved !");
+    assert!(ui.dirty);
+}
+
+#[tokio::test]
+async fn enter_on_a_task_without_subtasks_opens_its_notes() {
+    let (mut ui, plan) = plan();
+    let mut keys = typed("Book the room.");
+    keys.push(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+
+    send(&mut ui, WidgetAction::EnterTask, keys).await;
+
+    assert_eq!(plan.borrow().notes, "Book the room.");
+    assert_eq!(ui.prompt.desc, "Notes Saved !");
+    assert!(plan.borrow().expanded);
+}
+
+#[tokio::test]
+async fn esc_drops_the_edit_and_ctrl_s_keeps_it() {
+    let (mut ui, plan) = plan();
+    plan.borrow_mut().notes = "Call".to_string();
+    let mut keys = typed(" Bob");
+    keys.push(code(KeyCode::Esc));
+
+    write(&mut ui, keys).await;
+    assert_eq!(plan.borrow().notes, "Call");
+
+    let mut keys = typed(" Bob");
+    keys.push(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    write(&mut ui, keys).await;
+    assert_eq!(plan.borrow().notes, "Call Bob");
+
+    let mut keys = vec![code(KeyCode::Backspace); 8];
+    keys.push(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    write(&mut ui, keys).await;
+    assert_eq!(plan.borrow().notes, "");
+    assert_eq!(ui.prompt.desc, "Notes Cleared !");
+}
+
+#[tokio::test]
+async fn home_end_and_delete_edit_the_line_around_the_cursor() {
+    let (mut ui, plan) = plan();
+    plan.borrow_mut().notes = "first\nxxsecond".to_string();
+    // the popup opens at the end of the last line
+    let mut keys = vec![code(KeyCode::Home)];
+    keys.extend(vec![code(KeyCode::Delete); 2]);
+    keys.push(code(KeyCode::Up));
+    keys.push(code(KeyCode::End));
+    keys.extend(typed(" line"));
+    keys.push(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+
+    write(&mut ui, keys).await;
+
+    assert_eq!(plan.borrow().notes, "first line\nsecond");
+}
+
+#[tokio::test]
+async fn the_notes_of_an_archived_task_are_shown_read_only() {
+    let (mut ui, plan) = plan();
+    plan.borrow_mut().notes = "Kept".to_string();
+    ui.todolist.origin = WorkspaceType::Archived;
+    let mut keys = typed("typed\n");
+    keys.push(code(KeyCode::Delete));
+    keys.push(code(KeyCode::Backspace));
+    keys.push(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    keys.push(code(KeyCode::Esc));
+
+    write(&mut ui, keys).await;
+
+    assert_eq!(plan.borrow().notes, "Kept");
+    assert_ne!(
+        ui.prompt.desc,
+        "Archived Tasks Are Read Only, Press R To Recover !"
+    );
+}
+
+#[test]
+fn the_notes_survive_a_save_and_a_load() {
+    let (ui, plan) = plan();
+    let notes = "First line\n\n  indented line";
+    assert!(plan.borrow_mut().set_notes(notes));
+    assert!(!plan.borrow_mut().set_notes(notes));
+    let path = std::env::temp_dir().join(format!("todo-notes-{}.json", std::process::id()));
+    let datas = Datas {
+        workspace: ui.workspace.clone(),
+        todolist: ui.todolist.clone(),
+        archived_ws: ui.archived_ws.clone(),
+    };
+
+    data::save_data(&path, &datas).unwrap();
+    let loaded = data::load_data(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(data::archive_path(&path));
+
+    let list = loaded.todolist.todolists[0].borrow();
+    assert_eq!(list.tasks[0].borrow().notes, notes);
+    // a task saved before the notes has none
+    let json = serde_json::to_value(&*plan.borrow()).unwrap();
+    let mut json = json.as_object().unwrap().clone();
+    json.remove("notes");
+    let old: Task = serde_json::from_value(json.into()).unwrap();
+    assert_eq!(old.notes, "");
+}
+
+#[test]
+fn a_task_with_notes_is_marked_and_the_rows_stay_aligned() {
+    glyphs::select_set(&glyphs::PLAIN);
+    let (_, plan) = plan();
+    let tasks = vec![plan.clone()];
+    assert_eq!(TodoWidget::notes_span(&plan.borrow()).content, "");
+    let without = TodoWidget::find_max_tasks_len(&tasks, 0);
+
+    plan.borrow_mut().set_notes("More");
+
+    assert_eq!(TodoWidget::notes_span(&plan.borrow()).content, " =");
+    assert_eq!(TodoWidget::find_max_tasks_len(&tasks, 0), without + 2);
+}
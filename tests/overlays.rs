@@ -0,0 +1,79 @@
+//! Tests of the layers drawn over the panels
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{code, key, rows, task, ui_with};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    ui::{
+        DueBanner, UiMessage, WidgetAction,
+        overlay::{Layer, Overlays},
+    },
+};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+#[test]
+fn the_layers_are_drawn_in_their_order_whichever_opened_first() {
+    let mut overlays = Overlays::default();
+    overlays.open(Layer::Calendar);
+    overlays.open(Layer::Popup);
+    overlays.open(Layer::Help);
+    overlays.set(Layer::Banner, true);
+    overlays.set(Layer::Banner, true);
+    assert_eq!(
+        overlays.drawn(),
+        [Layer::Banner, Layer::Help, Layer::Popup, Layer::Calendar]
+    );
+
+    overlays.close(Layer::Calendar);
+    overlays.set(Layer::Banner, false);
+    assert_eq!(overlays.drawn(), [Layer::Help, Layer::Popup]);
+
+    let depth = overlays.depth();
+    overlays.open(Layer::Popup);
+    overlays.open(Layer::Calendar);
+    overlays.truncate(depth);
+    assert_eq!(overlays.drawn(), [Layer::Help, Layer::Popup]);
+}
+
+#[tokio::test]
+async fn a_popup_opened_over_the_help_page_is_drawn_on_top() {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![], vec![]);
+    ui.banner = Some(DueBanner {
+        workspace: Uuid::nil(),
+        task: task("Pay rent"),
+        text: "'Pay rent' is due today".to_string(),
+        shown_at: Instant::now(),
+    });
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::Help))
+        .await
+        .unwrap();
+    drop(ui_tx);
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    ui.handle_uimsg(&mut terminal, appstate).await;
+    assert!(rows(&terminal).concat().contains("Help Page"));
+
+    for key in "Groceries".chars().map(key).chain([code(KeyCode::Esc)]) {
+        input_tx.send(key).await.unwrap();
+    }
+    let rx = ui.input_rx.clone();
+    ui.get_input(rx, &mut terminal, "Add Task".to_string())
+        .await;
+
+    // the help page stays under the popup, and the popup is whole over it
+    let rows = rows(&terminal);
+    assert!(rows.concat().contains("Help Page"));
+    assert!(rows.iter().any(|row| row.contains("Add Task")));
+    assert!(rows.iter().any(|row| row.contains("Groceries")));
+    assert!(rows[0].contains("Pay rent"));
+
+    // closed, the popup leaves the help page open
+    assert_eq!(ui.overlays.drawn(), [Layer::Banner, Layer::Help]);
+}
@@ -0,0 +1,112 @@
+//! Tests of the command palette
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{code, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus, Message},
+    ui::{
+        UiMessage, WidgetAction,
+        keymap::{KeymapWidget, fuzzy_match},
+    },
+};
+
+#[test]
+fn query_matches_the_name_in_order_ignoring_case_and_spaces() {
+    assert!(fuzzy_match("", "Save the data"));
+    assert!(fuzzy_match("SAVE data", "Save the data"));
+    assert!(fuzzy_match("svdt", "Save the data"));
+    assert!(!fuzzy_match("data save", "Save the data"));
+}
+
+#[test]
+fn every_command_runs_a_message_and_keys_are_not_repeated() {
+    let keymap = KeymapWidget::default();
+    for focus in [
+        CurrentFocus::Workspace,
+        CurrentFocus::TodoList,
+        CurrentFocus::ArchivedWorkspace,
+    ] {
+        let commands = keymap.commands(&focus);
+        assert!(commands.iter().all(|command| command.message.is_some()));
+        let mut keys: Vec<&str> = commands
+            .iter()
+            .map(|command| command.key.as_str())
+            .collect();
+        let count = keys.len();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), count);
+    }
+    let names: Vec<String> = keymap
+        .commands(&CurrentFocus::TodoList)
+        .iter()
+        .map(|command| command.name())
+        .collect();
+    assert!(names.contains(&"Pick the rule to sort the list by".to_string()));
+}
+
+#[tokio::test]
+async fn palette_runs_the_picked_action() {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![workspace("done")], vec![]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::Palette))
+        .await
+        .unwrap();
+    for c in "archive".chars() {
+        input_tx.send(code(KeyCode::Char(c))).await.unwrap();
+    }
+    input_tx.send(code(KeyCode::Enter)).await.unwrap();
+    // the confirmation of the archive
+    input_tx.send(code(KeyCode::Char('y'))).await.unwrap();
+    drop(ui_tx);
+    drop(input_tx);
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    ui.handle_uimsg(&mut terminal, appstate).await;
+
+    assert!(ui.workspace.workspaces.is_empty());
+    assert_eq!(ui.archived_ws.workspaces[0].borrow().desc, "done");
+}
+
+#[tokio::test]
+async fn palette_does_nothing_on_esc() {
+    let (mut ui, _ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    for key in [KeyCode::Char('s'), KeyCode::Esc] {
+        input_tx.send(code(key)).await.unwrap();
+    }
+
+    let rx = ui.input_rx.clone();
+    let picked = ui
+        .pick_command(rx, &mut terminal, &CurrentFocus::Workspace)
+        .await;
+
+    assert!(picked.is_none());
+}
+
+#[tokio::test]
+async fn palette_picks_the_selected_match() {
+    let (mut ui, _ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    for key in [
+        KeyCode::Char('s'),
+        KeyCode::Char('a'),
+        KeyCode::Char('v'),
+        KeyCode::Enter,
+    ] {
+        input_tx.send(code(key)).await.unwrap();
+    }
+
+    let rx = ui.input_rx.clone();
+    let picked = ui
+        .pick_command(rx, &mut terminal, &CurrentFocus::TodoList)
+        .await;
+
+    assert!(matches!(picked, Some(Message::SaveData)));
+}
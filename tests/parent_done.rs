@@ -0,0 +1,172 @@
+//! Tests of what happens to a parent task when its last open subtask is finished
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{key, task, ui_with, workspace};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus, CurrentMode},
+    config::Config,
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{ParentDone, Task, TaskStatus},
+    },
+};
+use tokio::sync::mpsc;
+
+/// `Launch` > `release 1.2` > finished `Notes` and `Tag`, `Tag` selected, as `parent_done` says
+fn work(mode: ParentDone) -> (Ui, Rc<RefCell<Task>>, Rc<RefCell<Task>>) {
+    let (launch, release, notes, tag) = (
+        task("Launch"),
+        task("release 1.2"),
+        task("Notes"),
+        task("Tag"),
+    );
+    notes.borrow_mut().status = TaskStatus::Finished;
+    release.borrow_mut().add_child(notes);
+    release.borrow_mut().add_child(tag.clone());
+    launch.borrow_mut().add_child(release.clone());
+    let (mut ui, _, _) = ui_with(vec![workspace("Work")], vec![launch.clone()]);
+    ui.config.parent_done = mode;
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    list.borrow_mut().select_task(&tag);
+    (ui, release, launch)
+}
+
+/// Finish the selected task, with the keys pressed meanwhile, and the status line asked
+async fn finish(ui: &mut Ui, keys: &[char]) -> Vec<String> {
+    let (ui_tx, ui_rx) = mpsc::channel(1);
+    let (input_tx, input_rx) = mpsc::channel(8);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    for c in keys {
+        input_tx.send(key(*c)).await.unwrap();
+    }
+    drop(input_tx);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::MarkTaskStatus(
+            TaskStatus::Finished,
+        )))
+        .await
+        .unwrap();
+    drop(ui_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let appstate = Arc::new(Mutex::new(appstate));
+    let mut terminal = Terminal::new(TestBackend::new(120, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+    assert_eq!(appstate.lock().unwrap().current_mode, CurrentMode::Normal);
+    ui.prompt
+        .history
+        .iter()
+        .map(|entry| entry.desc.clone())
+        .collect()
+}
+
+fn status(task: &Rc<RefCell<Task>>) -> TaskStatus {
+    task.borrow().status.clone()
+}
+
+#[tokio::test]
+async fn off_leaves_the_parent_open() {
+    let (mut ui, release, _) = work(ParentDone::Off);
+    finish(&mut ui, &['y']).await;
+    assert_eq!(status(&release), TaskStatus::Todo);
+}
+
+#[tokio::test]
+async fn auto_finishes_the_parents_up_the_tree() {
+    let (mut ui, release, launch) = work(ParentDone::Auto);
+    let messages = finish(&mut ui, &[]).await;
+    assert_eq!(status(&release), TaskStatus::Finished);
+    assert_eq!(status(&launch), TaskStatus::Finished);
+    assert_eq!(
+        messages,
+        [
+            "'release 1.2' Done With Its Subtasks !",
+            "'Launch' Done With Its Subtasks !"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn ask_finishes_the_parent_on_y_only() {
+    let (mut ui, release, launch) = work(ParentDone::Ask);
+    ui.prompt.set("Saved !");
+    // any other key is ignored, `n` keeps the grand parent open
+    finish(&mut ui, &['x', 'y', 'n']).await;
+    assert_eq!(status(&release), TaskStatus::Finished);
+    assert_eq!(status(&launch), TaskStatus::Todo);
+    assert_eq!(ui.prompt.desc, "'release 1.2' Done With Its Subtasks !");
+
+    let (mut ui, release, _) = work(ParentDone::Ask);
+    ui.prompt.set("Saved !");
+    finish(&mut ui, &['n']).await;
+    assert_eq!(status(&release), TaskStatus::Todo);
+    assert_eq!(ui.prompt.desc, "Saved !");
+}
+
+#[tokio::test]
+async fn the_question_is_asked_in_the_status_line() {
+    let (mut ui, _, _) = work(ParentDone::Ask);
+    let (ui_tx, ui_rx) = mpsc::channel(1);
+    let (_input_tx, input_rx) = mpsc::channel(1);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::MarkTaskStatus(
+            TaskStatus::Finished,
+        )))
+        .await
+        .unwrap();
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    let mut terminal = Terminal::new(TestBackend::new(120, 24)).unwrap();
+    // no key comes, so the question stays
+    let open = tokio::time::timeout(
+        std::time::Duration::from_millis(200),
+        ui.handle_uimsg(&mut terminal, appstate.clone()),
+    )
+    .await;
+    assert!(open.is_err());
+    assert!(appstate.lock().unwrap().in_popup());
+    let buffer = terminal.backend().buffer();
+    let last: String = (0..buffer.area.width)
+        .map(|x| buffer[(x, buffer.area.height - 1)].symbol())
+        .collect();
+    assert!(
+        last.contains("All subtasks of 'release 1.2' done — mark parent done? y/n"),
+        "{}",
+        last
+    );
+}
+
+#[test]
+fn only_the_last_open_subtask_completes_its_parent() {
+    let (ui, release, launch) = work(ParentDone::Off);
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let tag = release.borrow().children[1].clone();
+    let roots = list.borrow().tasks.clone();
+    assert!(Task::completed_parent(&roots, &tag).is_none());
+
+    tag.borrow_mut().status = TaskStatus::Finished;
+    let parent = Task::completed_parent(&roots, &tag).unwrap();
+    assert!(Rc::ptr_eq(&parent, &release));
+    // a top level task has no parent, a parent already done isn't completed again
+    assert!(Task::completed_parent(&roots, &launch).is_none());
+    release.borrow_mut().status = TaskStatus::Deprecated;
+    assert!(Task::completed_parent(&roots, &tag).is_none());
+}
+
+#[test]
+fn the_mode_is_read_from_the_config() {
+    let config: Config = serde_json::from_str(r#"{"parent_done": "auto"}"#).unwrap();
+    assert_eq!(config.parent_done, ParentDone::Auto);
+    let config: Config = serde_json::from_str("{}").unwrap();
+    assert_eq!(config.parent_done, ParentDone::Off);
+}
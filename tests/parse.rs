@@ -0,0 +1,157 @@
+//! Tests of the grammar of the dates typed by the user
+
+use chrono::NaiveDate;
+use proptest::prelude::*;
+use todo::app::{
+    markdown,
+    parse::{self, iso_date},
+    sources::FixedClock,
+    ui::todolistwidget::DueChange,
+};
+
+fn on(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(y, m, d)
+}
+
+/// A Wednesday
+fn clock() -> FixedClock {
+    FixedClock(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap())
+}
+
+#[test]
+fn iso_dates_are_read_as_they_are() {
+    for (input, expected) in [
+        ("2025-07-01", on(2025, 7, 1)),
+        ("2024-02-29", on(2024, 2, 29)),
+        (" 2025-12-24 ", on(2025, 12, 24)),
+        ("1999-1-5", on(1999, 1, 5)),
+    ] {
+        assert_eq!(parse::due(input, &clock()), expected, "{:?}", input);
+    }
+    // only the exact form is an iso date, the rest is left to the other rules
+    assert_eq!(iso_date("2025-02-29"), None);
+    assert_eq!(iso_date("2025/07/01"), None);
+    assert_eq!(parse::due("2025-02-29", &clock()), on(2025, 7, 2));
+}
+
+#[test]
+fn relative_offsets_count_from_today() {
+    for (input, expected) in [
+        ("+0d", on(2025, 7, 2)),
+        ("+3d", on(2025, 7, 5)),
+        ("+30d", on(2025, 8, 1)),
+        ("+2w", on(2025, 7, 16)),
+        ("+1m", on(2025, 8, 2)),
+        ("+12m", on(2026, 7, 2)),
+        ("1 day", on(2025, 7, 3)),
+        ("3 days", on(2025, 7, 5)),
+        ("in 3 days", on(2025, 7, 5)),
+        ("1 week", on(2025, 7, 9)),
+        ("2 weeks", on(2025, 7, 16)),
+        ("1 month", on(2025, 8, 2)),
+        ("6 months", on(2026, 1, 2)),
+        ("tomorrow", on(2025, 7, 3)),
+    ] {
+        assert_eq!(parse::due(input, &clock()), expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn weekday_names_are_the_next_one_to_come() {
+    for (input, expected) in [
+        ("wed", on(2025, 7, 2)),
+        ("thu", on(2025, 7, 3)),
+        ("fri", on(2025, 7, 4)),
+        ("Friday", on(2025, 7, 4)),
+        ("SAT", on(2025, 7, 5)),
+        ("sunday", on(2025, 7, 6)),
+        ("mon", on(2025, 7, 7)),
+        ("tue", on(2025, 7, 8)),
+    ] {
+        assert_eq!(parse::due(input, &clock()), expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn none_and_nothing_clear_the_due_date() {
+    for input in ["none", "None", "NONE", "", "   "] {
+        assert_eq!(parse::due(input, &clock()), None, "{:?}", input);
+    }
+}
+
+#[test]
+fn anything_else_is_today_and_out_of_the_calendar_is_no_date() {
+    for input in ["whenever", "+3x", "-3d", "3d", "next friday", "tomorow"] {
+        assert_eq!(parse::due(input, &clock()), on(2025, 7, 2), "{:?}", input);
+    }
+    for input in ["+99999999d", "+99999999999999999999999d", "+4000000000m"] {
+        assert_eq!(parse::due(input, &clock()), None, "{:?}", input);
+    }
+}
+
+#[test]
+fn reminders_end_the_text() {
+    assert_eq!(parse::reminder("fri remind 3d"), ("fri", Some(Some(3))));
+    assert_eq!(parse::reminder("+1w REMIND 2 "), ("+1w", Some(Some(2))));
+    assert_eq!(parse::reminder("remind none"), ("", Some(None)));
+    assert_eq!(parse::reminder("remind 0d"), ("", Some(None)));
+    assert_eq!(parse::reminder("remind 900d"), ("", Some(Some(u8::MAX))));
+    assert_eq!(parse::reminder("remind me fri"), ("remind me fri", None));
+    assert_eq!(parse::reminder("fri remind"), ("fri remind", None));
+}
+
+#[test]
+fn shifts_are_told_apart_from_dates() {
+    let clock = clock();
+    assert_eq!(parse::due_change("-3d", &clock), DueChange::ShiftDays(-3));
+    assert_eq!(parse::due_change(" +1w ", &clock), DueChange::ShiftDays(7));
+    assert_eq!(parse::due_change("-2m", &clock), DueChange::ShiftMonths(-2));
+    assert_eq!(
+        parse::due_change("2025-09-01", &clock),
+        DueChange::Set(on(2025, 9, 1))
+    );
+    assert_eq!(
+        parse::due_change("tomorrow", &clock),
+        DueChange::Set(on(2025, 7, 3))
+    );
+    // a shift out of the calendar leaves the due date as it is
+    let huge = parse::due_change("+99999999999999999999d", &clock);
+    assert_eq!(huge.apply(on(2025, 7, 2)), None);
+}
+
+#[test]
+fn the_markdown_checklist_reads_iso_dates_only() {
+    let item = markdown::parse_item("- [ ] Ship (due 2025-07-01)").unwrap();
+    assert_eq!(item.due, on(2025, 7, 1));
+    let item = markdown::parse_item("- [ ] Ship (due fri)").unwrap();
+    assert_eq!((item.desc.as_str(), item.due), ("Ship (due fri)", None));
+}
+
+proptest! {
+    #[test]
+    fn parsing_never_panics(text in any::<String>()) {
+        let clock = clock();
+        let _ = parse::due(&text, &clock);
+        let _ = parse::reminder(&text);
+        let _ = iso_date(&text);
+        parse::due_change(&text, &clock).apply(on(2025, 7, 2));
+    }
+
+    #[test]
+    fn parsing_numbers_never_panics(
+        sign in "[+-]?",
+        num in "[0-9]{1,30}",
+        unit in prop::sample::select(vec!["d", "w", "m", " days", " weeks", " months"]),
+        remind in prop::option::of("[0-9]{1,30}d?"),
+    ) {
+        let text = format!("{}{}{}", sign, num, unit);
+        let clock = clock();
+        let _ = parse::due(&text, &clock);
+        parse::due_change(&text, &clock).apply(on(2025, 7, 2));
+        if let Some(remind) = remind {
+            let typed = format!("{} remind {}", text, remind);
+            let (date, _) = parse::reminder(&typed);
+            prop_assert_eq!(date, text.as_str());
+        }
+    }
+}
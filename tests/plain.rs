@@ -0,0 +1,101 @@
+//! Tests of the plain output for screen readers
+
+mod common;
+
+use common::{rows, task, ui_with, workspace};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    cli::Cli,
+    config::Config,
+    ui::{glyphs, todolistwidget::TaskStatus},
+};
+
+/// Draw a workspace with a task of every status, the second one selected
+fn screen() -> Vec<String> {
+    glyphs::select_set(&glyphs::PLAIN);
+    let tasks: Vec<_> = [
+        ("Write", TaskStatus::Todo),
+        ("Review", TaskStatus::InProcess),
+        ("Ship", TaskStatus::Finished),
+        ("Blog", TaskStatus::Deprecated),
+    ]
+    .into_iter()
+    .map(|(desc, status)| {
+        let task = task(desc);
+        task.borrow_mut().status = status;
+        task
+    })
+    .collect();
+    let review = tasks[1].clone();
+    let (mut ui, _, _) = ui_with(vec![workspace("Work")], tasks);
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    list.borrow_mut().select_task(&review);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    terminal.draw(|f| ui.update(f)).unwrap();
+    rows(&terminal)
+}
+
+#[test]
+fn statuses_are_spelled_out_and_the_selection_is_marked() {
+    let rows = screen();
+    let row = |desc: &str| rows.iter().find(|row| row.contains(desc)).unwrap().clone();
+
+    assert!(row("Write").contains("[TODO] "), "{}", row("Write"));
+    assert!(row("Review").contains("> [DOING]"), "{}", row("Review"));
+    assert!(row("Ship").contains("[DONE] "), "{}", row("Ship"));
+    assert!(row("Blog").contains("[DROP] "), "{}", row("Blog"));
+    assert!(!row("Write").contains('>'));
+}
+
+/// Draw `Work`, collapsed over `Sub`, selected in the workspace panel, and `Parent`,
+/// collapsed over `Child`, selected in the todo list
+fn collapsed_screen() -> Vec<String> {
+    glyphs::select_set(&glyphs::PLAIN);
+    let ws = workspace("Work");
+    ws.borrow_mut().add_children(vec![workspace("Sub")]);
+    ws.borrow_mut().expanded = false;
+    let parent = task("Parent");
+    parent.borrow_mut().add_child(task("Child"));
+    parent.borrow_mut().expanded = false;
+    let (mut ui, _, _) = ui_with(vec![ws], vec![parent]);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    terminal.draw(|f| ui.update(f)).unwrap();
+    rows(&terminal)
+}
+
+#[test]
+fn a_selected_collapsed_row_is_told_apart_from_an_expanded_one() {
+    let rows = collapsed_screen();
+    let row = |desc: &str| rows.iter().find(|row| row.contains(desc)).unwrap().clone();
+
+    assert!(row(" Work ").contains("> * + Work"), "{}", row(" Work "));
+    assert!(row("Parent").contains("> [TODO]"), "{}", row("Parent"));
+    assert!(
+        row("Parent").contains("Parent (0/1) + "),
+        "{}",
+        row("Parent")
+    );
+    assert!(
+        !rows
+            .iter()
+            .any(|row| row.contains("Sub") || row.contains("Child"))
+    );
+}
+
+#[test]
+fn the_borders_are_ascii() {
+    let rows = screen();
+    assert!(rows[0].starts_with("+ <1> Workspace -"), "{}", rows[0]);
+    assert!(rows.iter().all(|row| !row.contains(['─', '│', '┌', '└'])));
+}
+
+#[test]
+fn plain_is_asked_for_on_the_command_line_or_in_the_config() {
+    assert!(Cli::parse(["--plain".to_string()]).unwrap().plain);
+    assert!(!Cli::parse([]).unwrap().plain);
+    assert!(!Config::default().plain);
+    let config: Config = serde_json::from_str(r#"{"plain": true}"#).unwrap();
+    assert!(config.plain);
+}
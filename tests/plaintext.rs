@@ -0,0 +1,338 @@
+//! Tests of the plain text list import
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    path::Path,
+    process::{Command, Stdio},
+    rc::Rc,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use common::{code, key, task, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    cli::Cli,
+    data::{self, Datas, OnClash},
+    plaintext,
+    ui::{
+        UiMessage, WidgetAction,
+        todolistwidget::{Task, TaskStatus, TodoList},
+    },
+};
+
+/// The tasks as `(depth, desc)` in tree order
+fn outline(tasks: &[Rc<RefCell<Task>>]) -> Vec<(usize, String)> {
+    fn walk(tasks: &[Rc<RefCell<Task>>], depth: usize, out: &mut Vec<(usize, String)>) {
+        for task in tasks {
+            let task = task.borrow();
+            out.push((depth, task.desc.clone()));
+            walk(&task.children, depth + 1, out);
+        }
+    }
+    let mut out = Vec::new();
+    walk(tasks, 0, &mut out);
+    out
+}
+
+fn rows(rows: &[(usize, &str)]) -> Vec<(usize, String)> {
+    rows.iter()
+        .map(|(depth, desc)| (*depth, desc.to_string()))
+        .collect()
+}
+
+#[test]
+fn tabs_and_spaces_both_nest() {
+    let text = "parent\n\ttab child\n    space child\n\t\tgrandchild\n  \tmixed grandchild\n";
+
+    assert_eq!(
+        outline(&plaintext::parse_list(text)),
+        rows(&[
+            (0, "parent"),
+            (1, "tab child"),
+            (1, "space child"),
+            (2, "grandchild"),
+            // two spaces and a tab reach the same column as one tab
+            (1, "mixed grandchild"),
+        ])
+    );
+}
+
+#[test]
+fn trailing_whitespace_is_dropped() {
+    let text = "buy milk  \t\n  \n\tcall the bank \r\nwater the plants\r\n";
+
+    assert_eq!(
+        outline(&plaintext::parse_list(text)),
+        rows(&[
+            (0, "buy milk"),
+            (0, "call the bank"),
+            (0, "water the plants"),
+        ])
+    );
+}
+
+#[test]
+fn blank_lines_separate_the_groups() {
+    let text = "\
+Groceries
+  milk
+  eggs
+
+    Garage
+        oil change
+Laundry
+";
+
+    let tasks = plaintext::parse_list(text);
+
+    assert_eq!(
+        outline(&tasks),
+        rows(&[
+            (0, "Groceries"),
+            (1, "milk"),
+            (1, "eggs"),
+            (0, "Garage"),
+            (1, "oil change"),
+            (0, "Laundry"),
+        ])
+    );
+    assert!(plaintext::parse_list("\n  \n\t\n").is_empty());
+}
+
+#[test]
+fn nothing_in_a_line_is_read_as_a_status_or_a_date() {
+    let tasks = plaintext::parse_list("- [x] pay (2025-07-01)");
+
+    let task = tasks[0].borrow();
+    assert_eq!(task.desc, "- [x] pay (2025-07-01)");
+    assert_eq!(task.due, None);
+}
+
+#[test]
+fn import_adds_the_workspace_if_missing() {
+    let mut datas = data::demo_datas();
+    let home = datas.find_workspace("Home").unwrap();
+    let before = datas.todolist.list_of(home).unwrap().borrow().tasks.len();
+
+    let (workspace, imported) =
+        plaintext::import(&mut datas, Some("Home"), "a\n  b\n", OnClash::Merge).unwrap();
+    assert_eq!((workspace.as_str(), imported), ("Work / Home", 2));
+    assert_eq!(
+        datas.todolist.list_of(home).unwrap().borrow().tasks.len(),
+        before + 1
+    );
+
+    let (workspace, imported) =
+        plaintext::import(&mut datas, Some("Reading"), "x\ny", OnClash::Merge).unwrap();
+    assert_eq!((workspace.as_str(), imported), ("Reading", 2));
+    let reading = datas.find_workspace("Reading").unwrap();
+    assert_eq!(
+        datas
+            .todolist
+            .list_of(reading)
+            .unwrap()
+            .borrow()
+            .tasks
+            .len(),
+        2
+    );
+
+    let mut empty = Datas::default();
+    let (workspace, _) = plaintext::import(&mut empty, None, "x", OnClash::Merge).unwrap();
+    assert_eq!(workspace, data::INBOX_NAME);
+    assert!(plaintext::import(&mut empty, Some("Nowhere"), "\n\n", OnClash::Merge).is_err());
+    assert!(empty.find_workspace("Nowhere").is_err());
+}
+
+#[test]
+fn import_txt_subcommand_takes_a_file_and_a_workspace() {
+    let args = |args: &[&str]| Cli::parse(args.iter().map(|arg| arg.to_string()));
+
+    let cli = args(&["import-txt", "list.txt", "--workspace", "Home"]).unwrap();
+    assert_eq!(
+        cli.import_txt,
+        Some(("list.txt".into(), Some("Home".to_string())))
+    );
+    assert_eq!(cli.import_md, None);
+    assert_eq!(
+        args(&["import-txt", "list.txt"]).unwrap().import_txt,
+        Some(("list.txt".into(), None))
+    );
+    let err = args(&["import-txt"]).unwrap_err();
+    assert_eq!(err, "'import-txt' expects the path of a text file");
+    assert!(args(&["import-txt", "a.txt", "b.txt"]).is_err());
+}
+
+#[test]
+fn the_import_popup_selects_the_first_imported_task() {
+    let dir = std::env::temp_dir().join(format!("todo-plaintext-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (txt, md) = (dir.join("list.txt"), dir.join("list.md"));
+    std::fs::write(&txt, "Pack\n\tsocks\n").unwrap();
+    std::fs::write(&md, "# Trip\n- [x] Book\n").unwrap();
+
+    let (mut ui, _, _) = ui_with(vec![], vec![]);
+    let mut list = TodoList::new(uuid::Uuid::new_v4());
+    list.add_task(task("Write"));
+    let list = Rc::new(RefCell::new(list));
+
+    assert_eq!(ui.import_file(&list, &txt).unwrap(), 2);
+    let current = list.borrow().current_task.clone().unwrap();
+    assert_eq!(current.borrow().desc, "Pack");
+    assert_eq!(list.borrow().state.selected(), Some(1));
+
+    // a checklist keeps its boxes
+    assert_eq!(ui.import_file(&list, &md).unwrap(), 1);
+    let current = list.borrow().current_task.clone().unwrap();
+    assert_eq!(current.borrow().desc, "Book");
+    assert_eq!(current.borrow().status, TaskStatus::Finished);
+    assert!(ui.import_file(&list, Path::new("/no/such/file")).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_line_is_split_on_the_delimiter_outside_quotes() {
+    assert_eq!(
+        plaintext::split_tasks("write tests; update docs;; bump version ; ", ';'),
+        ["write tests", "update docs", "bump version"]
+    );
+    assert_eq!(
+        plaintext::split_tasks(r#"read "Dune; Messiah"; "a"; b"#, ';'),
+        [r#"read "Dune; Messiah""#, r#""a""#, "b"]
+    );
+    // an unclosed quote keeps the rest of the line
+    assert_eq!(
+        plaintext::split_tasks(r#"say "hi; bye"#, ';'),
+        [r#"say "hi; bye"#]
+    );
+    assert_eq!(plaintext::split_tasks("a, b; c", ','), ["a", "b; c"]);
+    assert!(plaintext::split_tasks(" ; ;", ';').is_empty());
+}
+
+#[test]
+fn add_splits_only_with_multi() {
+    let mut datas = Datas::default();
+
+    let (workspace, added) =
+        plaintext::add(&mut datas, None, "write tests; update docs", Some(';')).unwrap();
+    assert_eq!((workspace.as_str(), added), (data::INBOX_NAME, 2));
+    let (_, added) = plaintext::add(&mut datas, Some("Home"), "a; b ", None).unwrap();
+    assert_eq!(added, 1);
+    let home = datas.find_workspace("Home").unwrap();
+    let list = datas.todolist.list_of(home).unwrap();
+    assert_eq!(list.borrow().tasks[0].borrow().desc, "a; b");
+    assert!(plaintext::add(&mut datas, None, " ; ", Some(';')).is_err());
+    assert!(plaintext::add(&mut datas, None, "  ", None).is_err());
+}
+
+#[test]
+fn add_subcommand_takes_a_text_a_workspace_and_multi() {
+    let args = |args: &[&str]| Cli::parse(args.iter().map(|arg| arg.to_string()));
+
+    let cli = args(&["add", "a; b", "--multi", "--workspace", "Home"]).unwrap();
+    assert_eq!(
+        cli.add,
+        Some(("a; b".to_string(), Some("Home".to_string()), true))
+    );
+    assert_eq!(
+        args(&["add", "a; b"]).unwrap().add,
+        Some(("a; b".to_string(), None, false))
+    );
+    assert_eq!(
+        args(&["add"]).unwrap_err(),
+        "'add' expects the text of a task"
+    );
+    assert!(args(&["add", "a", "b"]).is_err());
+}
+
+#[tokio::test]
+async fn the_add_popup_adds_a_task_per_part_of_a_line_ending_with_the_delimiter() {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![workspace("Work")], vec![]);
+    for typed in ["write tests; update docs; bump version;", "a; b"] {
+        for c in typed.chars() {
+            input_tx.send(key(c)).await.unwrap();
+        }
+        input_tx.send(code(KeyCode::Enter)).await.unwrap();
+        ui_tx
+            .send(UiMessage::WAction(WidgetAction::AddTask))
+            .await
+            .unwrap();
+    }
+    drop(ui_tx);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let descs: Vec<String> = list
+        .borrow()
+        .tasks
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect();
+    assert_eq!(
+        descs,
+        ["write tests", "update docs", "bump version", "a; b"]
+    );
+    // the last add was a single task, the first of the three stays selected
+    let current = list.borrow().current_task.clone().unwrap();
+    assert_eq!(current.borrow().desc, "write tests");
+    assert_eq!(ui.prompt.desc, "Task Added !");
+    assert_eq!(
+        ui.prompt.history[ui.prompt.history.len() - 2].desc,
+        "Added 3 Tasks !"
+    );
+}
+
+#[test]
+fn the_add_command_waits_for_the_lock_of_the_data_file() {
+    let dir = std::env::temp_dir().join(format!("todo-add-lock-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("data.json");
+    let lock = data::lock_data(&path).unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_todo"))
+        .args(["add", "buy milk"])
+        .env(data::DATA_PATH_ENV, &path)
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert!(child.try_wait().unwrap().is_none());
+    assert!(!path.exists());
+
+    drop(lock);
+    assert!(child.wait().unwrap().success());
+    let datas = data::load_data(&path).unwrap();
+    let tasks = &datas.todolist.todolists[0].borrow().tasks;
+    assert_eq!(tasks[0].borrow().desc, "buy milk");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn the_add_command_fails_on_a_corrupt_data_file() {
+    let dir = std::env::temp_dir().join(format!("todo-add-corrupt-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("data.json");
+    std::fs::write(&path, "not a save").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_todo"))
+        .args(["add", "buy milk"])
+        .env(data::DATA_PATH_ENV, &path)
+        .output()
+        .unwrap();
+
+    // an error of the command, not a panic
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("failed to load the data"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "not a save");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,173 @@
+//! Tests of the border of the popups
+//!
+//! Each popup draws to a `TestBackend` until its key sender drops, and its last frame is
+//! checked for the badge of its kind in the bottom border and the border in its color.
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::{key, task, ui_with};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer};
+use todo::app::{
+    appstate::CurrentFocus,
+    ui::{Ui, chrome::PopupKind, glyphs, todolistwidget::TodoList},
+};
+
+/// A UI with a todo list, its keys sent and their sender dropped, and a terminal to draw it to
+async fn setup(keys: Vec<KeyEvent>) -> (Ui, Terminal<TestBackend>) {
+    let (mut ui, _, input_tx) = ui_with(vec![], vec![]);
+    for key in keys {
+        input_tx.send(key).await.unwrap();
+    }
+    let mut list = TodoList::new(uuid::Uuid::new_v4());
+    list.add_task(task("Write"));
+    ui.todolist.current_todolist = Some(Rc::new(RefCell::new(list)));
+    (ui, Terminal::new(TestBackend::new(80, 24)).unwrap())
+}
+
+/// The bottom border row of the popup of a kind from its left corner, checking the badge is
+/// next to the corner and the border is in the color of the kind
+fn bottom_border(buffer: &Buffer, kind: PopupKind) -> String {
+    let (name, color) = kind.badge();
+    let badge = format!(" {} ", name);
+    let width = buffer.area.width as usize;
+    let rows: Vec<String> = buffer
+        .content()
+        .chunks(width)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+        .collect();
+    let (y, row) = rows
+        .iter()
+        .enumerate()
+        .find(|(_, row)| row.contains(&badge))
+        .unwrap_or_else(|| panic!("no {} badge in\n{}", name, rows.join("\n")));
+    let x = row[..row.find(&badge).unwrap()].chars().count() as u16;
+    let corner = &buffer[(x - 1, y as u16)];
+    assert_eq!(corner.fg, color, "the corner of the {} popup", name);
+    assert_eq!(buffer[(x, y as u16)].bg, color, "the {} badge", name);
+    row.chars().skip(x as usize - 1).collect()
+}
+
+#[tokio::test]
+async fn the_input_popup_is_an_insert() {
+    let (mut ui, mut terminal) = setup(vec![]).await;
+    let rx = ui.input_rx.clone();
+    ui.get_input(rx, &mut terminal, "Add Task".to_string())
+        .await;
+    let row = bottom_border(terminal.backend().buffer(), PopupKind::Insert);
+    let border = glyphs::current().border;
+    assert!(
+        row.starts_with(&format!(
+            "{} INS {}",
+            border.bottom_left, border.horizontal_bottom
+        )),
+        "{}",
+        row
+    );
+}
+
+#[tokio::test]
+async fn the_name_popup_counts_the_columns_against_the_panel() {
+    let typed = "Write".chars().map(key);
+    let (mut ui, mut terminal) = setup(typed.clone().collect()).await;
+    let rx = ui.input_rx.clone();
+    ui.get_name(rx, &mut terminal, "Add Task".to_string(), 40)
+        .await;
+    let row = bottom_border(terminal.backend().buffer(), PopupKind::Insert);
+    assert!(row.contains(" 5 cols "), "{}", row);
+    // the todo list takes 80% of the frame, less its border and padding
+    assert_eq!(ui.todolist.width, 60);
+
+    let (mut ui, mut terminal) = setup(typed.collect()).await;
+    let rx = ui.input_rx.clone();
+    ui.get_name(rx, &mut terminal, "Rename".to_string(), 4)
+        .await;
+    let buffer = terminal.backend().buffer();
+    let row = bottom_border(buffer, PopupKind::Insert);
+    assert!(row.contains(" 5/4 cols "), "{}", row);
+}
+
+#[tokio::test]
+async fn the_due_date_popup_keeps_its_hint_after_the_badge() {
+    let (mut ui, mut terminal) = setup(vec![]).await;
+    let rx = ui.input_rx.clone();
+    ui.input_due_date(rx, &mut terminal, "Due".to_string(), None)
+        .await;
+    let row = bottom_border(terminal.backend().buffer(), PopupKind::Insert);
+    assert!(row.contains("'None' for unset"), "{}", row);
+}
+
+#[tokio::test]
+async fn the_calendar_popup_is_a_calendar() {
+    let ctrl_o = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+    let (mut ui, mut terminal) = setup(vec![ctrl_o]).await;
+    let rx = ui.input_rx.clone();
+    ui.input_due_date(rx, &mut terminal, "Due".to_string(), None)
+        .await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Calendar);
+}
+
+#[tokio::test]
+async fn the_confirmations_are_confirms() {
+    let (mut ui, mut terminal) = setup(vec![]).await;
+    let rx = ui.input_rx.clone();
+    ui.delete_item(rx, &mut terminal).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Confirm);
+
+    let rx = ui.input_rx.clone();
+    ui.confirm_clear_done(rx, &mut terminal, 2).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Confirm);
+}
+
+#[tokio::test]
+async fn the_filter_popup_is_an_insert() {
+    let (mut ui, mut terminal) = setup(vec![]).await;
+    let rx = ui.input_rx.clone();
+    ui.filter_find(rx, &mut terminal).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Insert);
+}
+
+#[tokio::test]
+async fn the_lists_to_pick_from_are_picks() {
+    let (mut ui, mut terminal) = setup(vec![]).await;
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let rx = ui.input_rx.clone();
+    ui.pick_sort(rx, &mut terminal, &list).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Pick);
+
+    let rx = ui.input_rx.clone();
+    ui.pick_link(rx, &mut terminal, &["https://example.com"])
+        .await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Pick);
+
+    let rx = ui.input_rx.clone();
+    ui.triage(rx, &mut terminal, &list).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Pick);
+
+    let rx = ui.input_rx.clone();
+    ui.key_help(rx, &mut terminal).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Pick);
+}
+
+#[tokio::test]
+async fn the_messages_and_the_about_are_views_and_the_palette_and_the_search_inserts() {
+    let (mut ui, mut terminal) = setup(vec![]).await;
+    let rx = ui.input_rx.clone();
+    ui.show_messages(rx, &mut terminal).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::View);
+
+    let rx = ui.input_rx.clone();
+    ui.show_about(rx, &mut terminal).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::View);
+
+    let rx = ui.input_rx.clone();
+    ui.pick_command(rx, &mut terminal, &CurrentFocus::TodoList)
+        .await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Insert);
+
+    let rx = ui.input_rx.clone();
+    ui.search_everywhere(rx, &mut terminal).await;
+    bottom_border(terminal.backend().buffer(), PopupKind::Insert);
+}
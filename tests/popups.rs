@@ -0,0 +1,325 @@
+//! Tests of the popups
+//!
+//! The popups draw to a `TestBackend` and are fed key events directly, or through the UI loop
+//! as `UiMessage::Edit`. When the key sender drops part-way through, the popup is expected to
+//! be cancelled with the key receiver released for the next popup, and releases or Ctrl
+//! chords must never be typed.
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use common::{code, key, ui_with};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer};
+use todo::app::{
+    appstate::{AppState, CurrentFocus, CurrentMode, Message},
+    reduce,
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{Task, TodoList},
+        workspacewidget::Workspace,
+    },
+};
+use tokio::sync::mpsc;
+
+/// A UI with its message and key senders, and a terminal to draw it to
+fn setup() -> (
+    Ui,
+    mpsc::Sender<UiMessage>,
+    mpsc::Sender<KeyEvent>,
+    Terminal<TestBackend>,
+) {
+    let (ui, ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    (ui, ui_tx, input_tx, terminal)
+}
+
+/// Fail rather than hang if a popup doesn't notice the closed channel
+async fn within<T>(popup: impl Future<Output = T>) -> T {
+    tokio::time::timeout(Duration::from_secs(5), popup)
+        .await
+        .expect("the popup should be cancelled")
+}
+
+#[tokio::test]
+async fn input_popup_is_cancelled_when_the_sender_drops_mid_typing() {
+    let (mut ui, _, input_tx, mut terminal) = setup();
+    input_tx.send(key('a')).await.unwrap();
+    input_tx.send(key('b')).await.unwrap();
+    drop(input_tx);
+
+    let input_rx = ui.input_rx.clone();
+    let result = within(ui.get_input(input_rx, &mut terminal, "Add Task".to_string())).await;
+
+    assert_eq!(result, "");
+    assert!(ui.input_rx.try_lock().is_ok());
+}
+
+#[tokio::test]
+async fn every_popup_is_cancelled_on_a_closed_channel() {
+    let (mut ui, _, input_tx, mut terminal) = setup();
+    drop(input_tx);
+
+    let rx = ui.input_rx.clone();
+    let due = within(ui.input_due_date(rx, &mut terminal, "Due".to_string(), None)).await;
+    assert_eq!(due, "");
+    let rx = ui.input_rx.clone();
+    assert!(!within(ui.delete_item(rx, &mut terminal)).await);
+    let rx = ui.input_rx.clone();
+    assert!(!within(ui.confirm_delete(rx, &mut terminal, CurrentFocus::Workspace)).await);
+    let rx = ui.input_rx.clone();
+    within(ui.show_messages(rx, &mut terminal)).await;
+    assert!(ui.input_rx.try_lock().is_ok());
+}
+
+#[tokio::test]
+async fn a_confirmation_answers_to_its_own_keys_only() {
+    let (mut ui, _, input_tx, mut terminal) = setup();
+    for c in ['y', 'n', 'k'] {
+        input_tx.send(key(c)).await.unwrap();
+    }
+    input_tx.send(key('c')).await.unwrap();
+    drop(input_tx);
+    let question = || ratatui::text::Line::from("Keep both ?");
+
+    let rx = ui.input_rx.clone();
+    let kept = within(ui.confirm(rx, &mut terminal, "Warn", question(), ('k', 'c'))).await;
+    assert!(kept);
+    let rx = ui.input_rx.clone();
+    let kept = within(ui.confirm(rx, &mut terminal, "Warn", question(), ('k', 'c'))).await;
+    assert!(!kept);
+    assert!(ui.input_rx.try_lock().is_ok());
+}
+
+#[tokio::test]
+async fn press_and_release_pairs_are_typed_once() {
+    let (mut ui, _, input_tx, mut terminal) = setup();
+    tokio::spawn(async move {
+        for code in [KeyCode::Char('a'), KeyCode::Char('b'), KeyCode::Enter] {
+            for kind in [KeyEventKind::Press, KeyEventKind::Release] {
+                let event = KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind);
+                let _ = input_tx.send(event).await;
+            }
+        }
+    });
+
+    let input_rx = ui.input_rx.clone();
+    let result = within(ui.get_input(input_rx, &mut terminal, "Add Task".to_string())).await;
+
+    assert_eq!(result, "ab");
+}
+
+#[tokio::test]
+async fn ctrl_chords_are_not_typed() {
+    let (mut ui, _, input_tx, mut terminal) = setup();
+    tokio::spawn(async move {
+        let _ = input_tx.send(key('a')).await;
+        let ctrl_x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        let _ = input_tx.send(ctrl_x).await;
+        let alt_y = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::ALT);
+        let _ = input_tx.send(alt_y).await;
+        let shift_b = KeyEvent::new(KeyCode::Char('B'), KeyModifiers::SHIFT);
+        let _ = input_tx.send(shift_b).await;
+        let enter = code(KeyCode::Enter);
+        let _ = input_tx.send(enter).await;
+    });
+
+    let input_rx = ui.input_rx.clone();
+    let result = within(ui.get_input(input_rx, &mut terminal, "Add Task".to_string())).await;
+
+    assert_eq!(result, "aB");
+}
+
+/// Run a message the way the message handler does and cancel its popup with Esc, returning
+/// the mode it leaves the app in
+async fn mode_after_cancel(msg: Message, from: CurrentMode) -> CurrentMode {
+    let (mut ui, ui_tx, input_tx, mut terminal) = setup();
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    appstate.lock().unwrap().current_mode = from;
+
+    let (ui_msgs, change) = reduce(msg, &appstate.lock().unwrap());
+    change.unwrap().apply(&mut appstate.lock().unwrap());
+    assert!(appstate.lock().unwrap().in_popup());
+    for ui_msg in ui_msgs {
+        ui_tx.send(ui_msg).await.unwrap();
+    }
+    drop(ui_tx);
+    input_tx.send(code(KeyCode::Esc)).await.unwrap();
+    drop(input_tx);
+    within(ui.handle_uimsg(&mut terminal, appstate.clone())).await;
+    appstate.lock().unwrap().current_mode
+}
+
+#[tokio::test]
+async fn a_cancelled_popup_goes_back_to_the_mode_it_was_opened_from() {
+    for msg in [
+        Message::AddItem,
+        Message::AddChild,
+        Message::Messages,
+        Message::About,
+        Message::Palette,
+        Message::KeyHelp,
+        Message::SearchAll,
+        // skipped without a selection, its popup is never shown
+        Message::DeleteItem,
+    ] {
+        for from in [CurrentMode::Normal, CurrentMode::Search] {
+            assert_eq!(
+                mode_after_cancel(msg.clone(), from).await,
+                from,
+                "{:?} from {:?}",
+                msg,
+                from
+            );
+        }
+    }
+}
+
+/// The UI as drawn, with the state the popups may change but the frame doesn't show
+fn snapshot(
+    ui: &mut Ui,
+    terminal: &mut Terminal<TestBackend>,
+) -> (Buffer, String, CurrentMode, bool) {
+    terminal.draw(|f| ui.update(f)).unwrap();
+    (
+        terminal.backend().buffer().clone(),
+        ui.prompt.desc.clone(),
+        ui.helpwidget.keymap.mode,
+        ui.helpwidget.keymap.calendar,
+    )
+}
+
+/// A UI with a workspace and its list selected, a message in the prompt, and the keys of a
+/// popup typed before Esc
+async fn with_keys(keys: &[KeyEvent]) -> (Ui, Terminal<TestBackend>) {
+    let (mut ui, _, input_tx, terminal) = setup();
+    let ws = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+    let mut list = TodoList::new(ws.borrow().id);
+    list.add_task(Rc::new(RefCell::new(Task::new("Write".to_string(), None))));
+    ui.workspace.add_workspace(ws.clone());
+    ui.todolist.add_list(Rc::new(RefCell::new(list)));
+    ui.workspace.select(Some(ws.clone()));
+    ui.todolist.change_current_list(&Some(ws));
+    ui.prompt.set("Data Saved !");
+    for key in keys {
+        input_tx.send(*key).await.unwrap();
+    }
+    input_tx.send(code(KeyCode::Esc)).await.unwrap();
+    (ui, terminal)
+}
+
+#[tokio::test]
+async fn esc_from_every_popup_leaves_the_ui_as_it_was() {
+    let ctrl_o = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+    let esc = code(KeyCode::Esc);
+
+    let (mut ui, mut terminal) = with_keys(&[key('a')]).await;
+    let before = snapshot(&mut ui, &mut terminal);
+    let rx = ui.input_rx.clone();
+    within(ui.get_input(rx, &mut terminal, "Rename".to_string())).await;
+    assert!(snapshot(&mut ui, &mut terminal) == before, "get_input");
+
+    // the calendar tells it's opened in the prompt
+    let (mut ui, mut terminal) = with_keys(&[key('1'), ctrl_o, esc]).await;
+    let before = snapshot(&mut ui, &mut terminal);
+    let rx = ui.input_rx.clone();
+    within(ui.input_due_date(rx, &mut terminal, "Due".to_string(), None)).await;
+    assert!(snapshot(&mut ui, &mut terminal) == before, "input_due_date");
+
+    let (mut ui, mut terminal) = with_keys(&[]).await;
+    let before = snapshot(&mut ui, &mut terminal);
+    let rx = ui.input_rx.clone();
+    assert!(!within(ui.delete_item(rx, &mut terminal)).await);
+    assert!(snapshot(&mut ui, &mut terminal) == before, "delete_item");
+
+    let (mut ui, mut terminal) = with_keys(&[]).await;
+    let before = snapshot(&mut ui, &mut terminal);
+    let rx = ui.input_rx.clone();
+    assert!(!within(ui.confirm_delete(rx, &mut terminal, CurrentFocus::TodoList)).await);
+    assert!(snapshot(&mut ui, &mut terminal) == before, "confirm_delete");
+
+    let (mut ui, mut terminal) = with_keys(&[]).await;
+    let before = snapshot(&mut ui, &mut terminal);
+    let rx = ui.input_rx.clone();
+    assert!(!within(ui.confirm_replay(rx, &mut terminal, 3)).await);
+    assert!(snapshot(&mut ui, &mut terminal) == before, "confirm_replay");
+
+    let (mut ui, mut terminal) = with_keys(&[]).await;
+    let before = snapshot(&mut ui, &mut terminal);
+    let rx = ui.input_rx.clone();
+    assert!(!within(ui.confirm_renew_ids(rx, &mut terminal, 2)).await);
+    assert!(
+        snapshot(&mut ui, &mut terminal) == before,
+        "confirm_renew_ids"
+    );
+
+    let (mut ui, mut terminal) = with_keys(&[key('W')]).await;
+    let before = snapshot(&mut ui, &mut terminal);
+    let rx = ui.input_rx.clone();
+    assert_eq!(within(ui.filter_find(rx, &mut terminal)).await, "");
+    assert!(snapshot(&mut ui, &mut terminal) == before, "filter_find");
+}
+
+#[tokio::test]
+async fn a_confirmed_popup_keeps_its_prompt() {
+    let ctrl_o = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+    let enter = code(KeyCode::Enter);
+    let (mut ui, mut terminal) = with_keys(&[ctrl_o, enter]).await;
+    let mode = ui.helpwidget.keymap.mode;
+
+    let rx = ui.input_rx.clone();
+    let due = within(ui.input_due_date(rx, &mut terminal, "Due".to_string(), None)).await;
+
+    assert!(!due.is_empty());
+    assert_eq!(ui.prompt.desc, "Calendar Selection !");
+    assert_eq!(ui.helpwidget.keymap.mode, mode);
+    assert!(!ui.helpwidget.keymap.calendar);
+}
+
+#[tokio::test]
+async fn a_cancelled_add_says_nothing_was_added() {
+    for msg in [Message::AddItem, Message::AddChild] {
+        let (mut ui, ui_tx, input_tx, mut terminal) = setup();
+        ui.prompt.set("Data Saved !");
+        let appstate = Arc::new(Mutex::new(AppState::new()));
+        let (ui_msgs, change) = reduce(msg.clone(), &appstate.lock().unwrap());
+        change.unwrap().apply(&mut appstate.lock().unwrap());
+        for ui_msg in ui_msgs {
+            ui_tx.send(ui_msg).await.unwrap();
+        }
+        drop(ui_tx);
+        input_tx.send(code(KeyCode::Esc)).await.unwrap();
+        drop(input_tx);
+        within(ui.handle_uimsg(&mut terminal, appstate)).await;
+        assert_eq!(ui.prompt.desc, "Data Saved !", "{:?}", msg);
+    }
+}
+
+#[tokio::test]
+async fn a_popup_is_typed_into_through_the_ui_messages() {
+    let (mut ui, ui_tx, input_tx, mut terminal) = setup();
+    drop(input_tx);
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    appstate.lock().unwrap().open_popup(CurrentMode::Insert);
+
+    // the keys reaching the ui loop are kept for the popup of the next action
+    for key in [key('H'), key('i'), KeyEvent::from(KeyCode::Enter)] {
+        ui_tx.send(UiMessage::Edit(key)).await.unwrap();
+    }
+    let action = UiMessage::WAction(WidgetAction::AddWorkspace);
+    ui_tx.send(action).await.unwrap();
+    drop(ui_tx);
+    within(ui.handle_uimsg(&mut terminal, appstate.clone())).await;
+
+    let names: Vec<_> = (ui.workspace.workspaces.iter())
+        .map(|ws| ws.borrow().desc.clone())
+        .collect();
+    assert_eq!(names, ["Hi"]);
+    assert_eq!(appstate.lock().unwrap().current_mode, CurrentMode::Normal);
+}
@@ -0,0 +1,168 @@
+//! Tests of the feedback given by the actions which need a selection when there is none
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{task, ui_with, workspace};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus, CurrentMode},
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{TaskStatus, TodoList},
+        workspacewidget::WorkspaceType,
+    },
+};
+use tokio::sync::mpsc;
+
+/// Send one action to the UI loop in the mode of its popup, as the reducer leaves it, and
+/// return the prompt message and the mode afterwards
+async fn feedback(ui: &mut Ui, action: WidgetAction) -> (String, CurrentMode) {
+    let (ui_tx, ui_rx) = mpsc::channel(8);
+    let (_input_tx, input_rx) = mpsc::channel(8);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    if let Some(mode) = action.popup_mode() {
+        appstate.lock().unwrap().open_popup(mode);
+    }
+    ui_tx.send(UiMessage::WAction(action)).await.unwrap();
+    drop(ui_tx);
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+    let mode = appstate.lock().unwrap().current_mode;
+    (ui.prompt.desc.clone(), mode)
+}
+
+fn empty_ui() -> Ui {
+    ui_with(vec![], vec![]).0
+}
+
+/// A UI with a workspace entered, whose list has the tasks and none selected
+fn entered_ui(tasks: usize) -> Ui {
+    let mut ui = empty_ui();
+    let ws = workspace("ws");
+    let mut list = TodoList::new(ws.borrow().id);
+    (0..tasks).for_each(|i| list.add_task(task(&i.to_string())));
+    let list = Rc::new(RefCell::new(list));
+    ui.todolist.add_list(list.clone());
+    ui.workspace.add_workspace(ws);
+    ui.todolist.current_todolist = Some(list);
+    ui
+}
+
+#[tokio::test]
+async fn task_actions_on_an_empty_list_say_how_to_add_one() {
+    for action in [
+        WidgetAction::MarkTaskStatus(TaskStatus::Finished),
+        WidgetAction::Due,
+        WidgetAction::Rename(CurrentFocus::TodoList),
+        WidgetAction::DeleteTask,
+        WidgetAction::EnterTask,
+    ] {
+        let mut ui = entered_ui(0);
+        let (prompt, mode) = feedback(&mut ui, action.clone()).await;
+        assert_eq!(
+            prompt, "Todo List Is Empty, Press a To Add One !",
+            "{:?}",
+            action
+        );
+        assert_eq!(mode, CurrentMode::Normal);
+        assert!(!ui.dirty);
+    }
+}
+
+#[tokio::test]
+async fn task_actions_without_a_selection_say_so() {
+    for action in [
+        WidgetAction::MarkTaskStatus(TaskStatus::Todo),
+        WidgetAction::Due,
+        WidgetAction::DueInline,
+        WidgetAction::Rename(CurrentFocus::TodoList),
+        WidgetAction::DeleteTask,
+        WidgetAction::EnterTask,
+    ] {
+        let mut ui = entered_ui(2);
+        let (prompt, _) = feedback(&mut ui, action.clone()).await;
+        assert_eq!(prompt, "No Task Selected !", "{:?}", action);
+        assert_eq!(
+            ui.todolist
+                .current_todolist
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .tasks
+                .len(),
+            2
+        );
+    }
+}
+
+#[tokio::test]
+async fn workspace_actions_without_a_selection_say_so() {
+    for (action, expected) in [
+        (WidgetAction::DeleteWorkspace, "No Workspace Selected !"),
+        (
+            WidgetAction::Rename(CurrentFocus::Workspace),
+            "No Workspace Selected !",
+        ),
+        (WidgetAction::ArchiveWS, "No Workspace Selected !"),
+        (
+            WidgetAction::DeleteArchivedWorkspace,
+            "No Archived Workspace Selected !",
+        ),
+        (WidgetAction::RecoveryWS, "No Archived Workspace Selected !"),
+        (WidgetAction::Sort, "No Todo List Open, Enter A Workspace !"),
+    ] {
+        let mut ui = empty_ui();
+        let (prompt, _) = feedback(&mut ui, action.clone()).await;
+        assert_eq!(prompt, expected, "{:?}", action);
+    }
+}
+
+#[tokio::test]
+async fn task_edits_in_an_archived_list_say_how_to_recover_it() {
+    for action in [
+        WidgetAction::AddTask,
+        WidgetAction::MarkTaskStatus(TaskStatus::Finished),
+        WidgetAction::DeleteTask,
+        WidgetAction::IncreaseUrgency,
+        WidgetAction::Sort,
+    ] {
+        let mut ui = entered_ui(1);
+        let list = ui.todolist.current_todolist.clone().unwrap();
+        let task = list.borrow().tasks[0].clone();
+        list.borrow_mut().current_task = Some(task.clone());
+        ui.todolist.origin = WorkspaceType::Archived;
+        let (prompt, mode) = feedback(&mut ui, action.clone()).await;
+        assert_eq!(
+            prompt, "Archived Tasks Are Read Only, Press R To Recover !",
+            "{:?}",
+            action
+        );
+        assert_eq!(mode, CurrentMode::Normal);
+        assert_eq!(list.borrow().tasks.len(), 1);
+        assert_eq!(task.borrow().status, TaskStatus::Todo);
+        assert!(!ui.dirty);
+    }
+
+    // reading the list still works
+    let mut ui = entered_ui(1);
+    ui.todolist.origin = WorkspaceType::Archived;
+    assert_eq!(ui.unmet_precondition(&WidgetAction::Filter), None);
+}
+
+#[test]
+fn selected_task_meets_the_precondition() {
+    let ui = entered_ui(1);
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let task = list.borrow().tasks[0].clone();
+    list.borrow_mut().current_task = Some(task);
+    assert_eq!(ui.unmet_precondition(&WidgetAction::Due), None);
+    assert_eq!(ui.unmet_precondition(&WidgetAction::AddTaskChild), None);
+}
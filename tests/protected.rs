@@ -0,0 +1,102 @@
+//! Tests of the protection of workspaces from deletion and archiving
+//!
+//! The delete and archive actions are sent to the UI loop the way the message handler does,
+//! with a `y` queued for every confirmation, so an unguarded action would go through.
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{key, task, ui_with};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    data::Datas,
+    ui::{UiMessage, WidgetAction, workspacewidget::Workspace},
+};
+
+/// Run the UI loop on a workspace with the actions, and return the names of the workspaces
+/// left in the workspace panel and in the archived panel
+async fn attempt(workspace: Workspace, actions: &[WidgetAction]) -> (Vec<String>, Vec<String>) {
+    let ws = Rc::new(RefCell::new(workspace));
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![ws], vec![task("task")]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+    for action in actions {
+        ui_tx
+            .send(UiMessage::WAction(action.clone()))
+            .await
+            .unwrap();
+    }
+    for _ in 0..6 {
+        input_tx.send(key('y')).await.unwrap();
+    }
+    drop(ui_tx);
+    drop(input_tx);
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    ui.handle_uimsg(&mut terminal, appstate).await;
+
+    let names = |workspaces: &[Rc<RefCell<Workspace>>]| -> Vec<String> {
+        workspaces
+            .iter()
+            .map(|ws| ws.borrow().desc.clone())
+            .collect()
+    };
+    (
+        names(&ui.workspace.workspaces),
+        names(&ui.archived_ws.workspaces),
+    )
+}
+
+#[tokio::test]
+async fn protected_workspace_survives_delete_and_archive() {
+    let mut ws = Workspace::new("keep".to_string());
+    ws.protected = true;
+
+    let (active, archived) = attempt(
+        ws,
+        &[WidgetAction::DeleteWorkspace, WidgetAction::ArchiveWS],
+    )
+    .await;
+
+    assert_eq!(active, ["keep"]);
+    assert!(archived.is_empty());
+}
+
+#[tokio::test]
+async fn protected_child_protects_its_parent() {
+    let mut parent = Workspace::new("parent".to_string());
+    let mut child = Workspace::new("child".to_string());
+    child.protected = true;
+    parent.add_child(Rc::new(RefCell::new(child)));
+
+    let (active, _) = attempt(parent, &[WidgetAction::DeleteWorkspace]).await;
+
+    assert_eq!(active, ["parent"]);
+}
+
+#[tokio::test]
+async fn unprotected_workspace_is_deleted() {
+    let (active, _) = attempt(
+        Workspace::new("gone".to_string()),
+        &[WidgetAction::DeleteWorkspace],
+    )
+    .await;
+
+    assert!(active.is_empty());
+}
+
+#[test]
+fn inbox_is_protected() {
+    let mut datas = Datas::default();
+    let inbox = datas.ensure_inbox();
+
+    let ws = datas.workspace.workspaces[0].clone();
+    assert_eq!(ws.borrow().id, inbox);
+    assert!(ws.borrow().protected);
+    assert_eq!(Workspace::find_protected(&ws), Some("Inbox".to_string()));
+}
@@ -0,0 +1,245 @@
+//! Tests of recovering a workspace beside another of the same name
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{code, task, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    data::{Datas, OnClash},
+    markdown, names, plaintext,
+    ui::{
+        Recovery, Ui, UiMessage, WidgetAction, todolistwidget::TodoList, workspacewidget::Workspace,
+    },
+};
+use tokio::sync::mpsc;
+
+/// An active `Website` with the task `new`, and an archived `Website` selected, with the
+/// task `old` and its subtask `old child`, and the sub workspace `Docs`
+fn websites() -> (Ui, Rc<RefCell<Workspace>>, Rc<RefCell<Workspace>>) {
+    let (mut ui, _, _) = ui_with(vec![], vec![]);
+    let active = workspace("Website");
+    let mut list = TodoList::new(active.borrow().id);
+    list.add_task(task("new"));
+    ui.todolist.add_list(Rc::new(RefCell::new(list)));
+    ui.workspace.add_workspace(active.clone());
+
+    let archived = workspace("Website");
+    archived.borrow_mut().add_child(workspace("Docs"));
+    let mut list = TodoList::new(archived.borrow().id);
+    let old = task("old");
+    old.borrow_mut().add_child(task("old child"));
+    list.add_task(old);
+    ui.todolist.add_list(Rc::new(RefCell::new(list)));
+    ui.archived_ws.add_workspace(archived.clone());
+    ui.archived_ws.select(Some(archived.clone()));
+    (ui, active, archived)
+}
+
+fn names_of(level: &[Rc<RefCell<Workspace>>]) -> Vec<String> {
+    level.iter().map(|ws| ws.borrow().desc.clone()).collect()
+}
+
+/// The descriptions of the tasks of a workspace, depth first
+fn tasks_of(ui: &Ui, ws: &Rc<RefCell<Workspace>>) -> Vec<String> {
+    let list = ui.todolist.list_of(ws.borrow().id).unwrap();
+    let list = list.borrow();
+    list.flattened()
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+/// Recover the archived workspace selected with `R`, pressing the key in the dialog
+async fn recover(ui: &mut Ui, key: KeyCode) {
+    let (ui_tx, ui_rx) = mpsc::channel(1);
+    let (input_tx, input_rx) = mpsc::channel(8);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    input_tx.send(code(key)).await.unwrap();
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::RecoveryWS))
+        .await
+        .unwrap();
+    drop(ui_tx);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+}
+
+#[tokio::test]
+async fn keeping_both_names_the_recovered_one_apart() {
+    let (mut ui, active, archived) = websites();
+
+    recover(&mut ui, KeyCode::Char('k')).await;
+
+    assert_eq!(
+        names_of(&ui.workspace.workspaces),
+        ["Website", "Website (recovered)"]
+    );
+    assert!(ui.archived_ws.workspaces.is_empty());
+    assert_eq!(tasks_of(&ui, &active), ["new"]);
+    assert_eq!(tasks_of(&ui, &archived), ["old", "old child"]);
+    assert_eq!(ui.prompt.desc, "Recovered As 'Website (recovered)' !");
+    assert!(ui.dirty);
+}
+
+#[tokio::test]
+async fn merging_appends_the_recovered_tasks_and_sub_workspaces() {
+    let (mut ui, active, archived) = websites();
+
+    recover(&mut ui, KeyCode::Char('m')).await;
+
+    assert_eq!(names_of(&ui.workspace.workspaces), ["Website"]);
+    assert_eq!(names_of(&active.borrow().children), ["Docs"]);
+    assert!(ui.archived_ws.workspaces.is_empty());
+    assert_eq!(tasks_of(&ui, &active), ["new", "old", "old child"]);
+    assert!(ui.todolist.list_of(archived.borrow().id).is_none());
+    assert!(Rc::ptr_eq(
+        ui.workspace.current_workspace.as_ref().unwrap(),
+        &active
+    ));
+    assert_eq!(ui.prompt.desc, "Merged 2 Tasks Into 'Website' !");
+}
+
+#[tokio::test]
+async fn merging_merges_the_sub_workspaces_sharing_a_name() {
+    let (mut ui, active, archived) = websites();
+    let blog = |ui: &mut Ui, ws: &Rc<RefCell<Workspace>>, task_desc: &str| {
+        let blog = self::workspace("Blog");
+        let mut list = TodoList::new(blog.borrow().id);
+        list.add_task(task(task_desc));
+        ui.todolist.add_list(Rc::new(RefCell::new(list)));
+        ws.borrow_mut().add_child(blog.clone());
+        blog
+    };
+    let active_blog = blog(&mut ui, &active, "post");
+    let archived_blog = blog(&mut ui, &archived, "draft");
+    archived_blog
+        .borrow_mut()
+        .add_child(self::workspace("Ideas"));
+
+    recover(&mut ui, KeyCode::Char('m')).await;
+
+    assert_eq!(names_of(&active.borrow().children), ["Blog", "Docs"]);
+    assert!(Rc::ptr_eq(&active.borrow().children[0], &active_blog));
+    assert_eq!(names_of(&active_blog.borrow().children), ["Ideas"]);
+    assert_eq!(tasks_of(&ui, &active_blog), ["post", "draft"]);
+    assert!(ui.todolist.list_of(archived_blog.borrow().id).is_none());
+    assert_eq!(ui.prompt.desc, "Merged 3 Tasks Into 'Website' !");
+}
+
+#[tokio::test]
+async fn cancelling_leaves_the_workspace_archived() {
+    let (mut ui, active, archived) = websites();
+
+    recover(&mut ui, KeyCode::Esc).await;
+
+    assert_eq!(names_of(&ui.workspace.workspaces), ["Website"]);
+    assert!(Rc::ptr_eq(&ui.archived_ws.workspaces[0], &archived));
+    assert_eq!(tasks_of(&ui, &active), ["new"]);
+    assert_eq!(tasks_of(&ui, &archived), ["old", "old child"]);
+    assert_eq!(ui.prompt.desc, "Recovery Cancelled !");
+}
+
+#[test]
+fn a_workspace_without_a_namesake_is_recovered_as_it_is() {
+    let (mut ui, active, archived) = websites();
+    active.borrow_mut().desc = "Blog".to_string();
+
+    assert_eq!(ui.recover_current(Recovery::Cancel), None);
+
+    assert_eq!(names_of(&ui.workspace.workspaces), ["Blog", "Website"]);
+    assert_eq!(tasks_of(&ui, &archived), ["old", "old child"]);
+    assert!(ui.archived_ws.workspaces.is_empty());
+}
+
+#[test]
+fn a_second_recovered_namesake_is_numbered() {
+    let siblings = ["Website", "Website (recovered)", "Website (recovered 2)"];
+    assert_eq!(
+        names::recovered_name("Website", siblings),
+        "Website (recovered 3)"
+    );
+    assert_eq!(names::recovered_name("Blog", siblings), "Blog (recovered)");
+}
+
+#[test]
+fn an_import_adds_to_the_top_level_workspace_at_its_path() {
+    let mut datas = Datas::default();
+    let website = datas.find_or_add_workspace("Website").unwrap();
+    // a sub workspace of the same name doesn't make the name ambiguous
+    let parent = workspace("Work");
+    parent.borrow_mut().add_child(workspace("Website"));
+    datas.workspace.add_workspace(parent);
+
+    let (path, imported) =
+        plaintext::import(&mut datas, Some("Website"), "one\ntwo", OnClash::Merge).unwrap();
+
+    assert_eq!((path.as_str(), imported), ("Website", 2));
+    assert_eq!(names_of(&datas.workspace.workspaces), ["Website", "Work"]);
+    let list = datas.todolist.list_of(website).unwrap();
+    assert_eq!(list.borrow().tasks.len(), 2);
+}
+
+#[test]
+fn an_import_kept_beside_its_namesake_is_renamed() {
+    let mut datas = Datas::default();
+    let website = datas.find_or_add_workspace("Website").unwrap();
+
+    let (path, imported) =
+        plaintext::import(&mut datas, Some("Website"), "one", OnClash::KeepBoth).unwrap();
+    assert_eq!((path.as_str(), imported), ("Website (imported)", 1));
+    let (path, _) =
+        plaintext::import(&mut datas, Some("Website"), "two", OnClash::KeepBoth).unwrap();
+    assert_eq!(path, "Website (imported 2)");
+    let (path, _) =
+        plaintext::import(&mut datas, Some("Blog"), "three", OnClash::KeepBoth).unwrap();
+    assert_eq!(path, "Blog");
+
+    assert_eq!(
+        names_of(&datas.workspace.workspaces),
+        [
+            "Website",
+            "Website (imported)",
+            "Website (imported 2)",
+            "Blog"
+        ]
+    );
+    let list = datas.todolist.list_of(website).unwrap();
+    assert!(list.borrow().tasks.is_empty());
+}
+
+#[test]
+fn an_import_into_an_ambiguous_workspace_is_refused() {
+    let mut datas = Datas::default();
+    // two top level namesakes, e.g. recovered before the clash was asked about
+    datas.workspace.add_workspace(workspace("Website"));
+    datas.workspace.add_workspace(workspace("Website"));
+    let work = workspace("Work");
+    work.borrow_mut().add_child(workspace("Docs"));
+    work.borrow_mut().add_child(workspace("Docs"));
+    datas.workspace.add_workspace(work);
+
+    let err = plaintext::import(&mut datas, Some("Website"), "one", OnClash::Merge).unwrap_err();
+    assert!(err.contains("several workspaces"), "{}", err);
+    let err = plaintext::import(&mut datas, Some("Docs"), "one", OnClash::Merge).unwrap_err();
+    assert!(err.contains("several workspaces"), "{}", err);
+    assert!(markdown::import(&mut datas, Some("Website"), "- one", OnClash::Merge).is_err());
+    assert_eq!(
+        names_of(&datas.workspace.workspaces),
+        ["Website", "Website", "Work"]
+    );
+
+    // keeping both needs no pick among them
+    let (path, _) =
+        markdown::import(&mut datas, Some("Website"), "- one", OnClash::KeepBoth).unwrap();
+    assert_eq!(path, "Website (imported)");
+}
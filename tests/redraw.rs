@@ -0,0 +1,73 @@
+//! Tests of drawing one frame for a burst of messages
+
+use std::sync::{Arc, Mutex};
+
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    data,
+    ui::{
+        SelectAction, Ui, UiMessage, WidgetAction,
+        todolistwidget::{TaskStatus, TodoWidget},
+    },
+};
+use tokio::sync::mpsc;
+
+/// The frames drawn so far, the one drawn to count them left out
+fn frames(terminal: &mut Terminal<TestBackend>) -> usize {
+    let mut count = 0;
+    terminal.draw(|f| count = f.count()).unwrap();
+    count
+}
+
+async fn handle(messages: Vec<UiMessage>) -> (Ui, usize) {
+    let (ui_tx, ui_rx) = mpsc::channel(messages.len().max(1));
+    let (_input_tx, input_rx) = mpsc::channel(1);
+    let mut ui = Ui::new(ui_rx, input_rx);
+    let datas = data::generate_datas(5000);
+    ui.workspace = datas.workspace;
+    ui.todolist = datas.todolist;
+    ui.archived_ws = datas.archived_ws;
+    let first = ui.workspace.workspaces[0].clone();
+    ui.workspace.select(Some(first));
+    for msg in messages {
+        ui_tx.send(msg).await.unwrap();
+    }
+    drop(ui_tx);
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+    let frames = frames(&mut terminal);
+    (ui, frames)
+}
+
+#[tokio::test]
+async fn completing_500_tasks_draws_a_handful_of_frames() {
+    let mut messages = vec![UiMessage::WAction(WidgetAction::EnterWorkspace)];
+    for _ in 0..500 {
+        messages.push(UiMessage::WAction(WidgetAction::MarkTaskStatus(
+            TaskStatus::Finished,
+        )));
+        messages.push(UiMessage::WAction(WidgetAction::SelectDown));
+    }
+
+    let (ui, frames) = handle(messages).await;
+
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let finished = TodoWidget::get_flattened(&list.borrow().tasks)
+        .iter()
+        .filter(|task| task.borrow().status == TaskStatus::Finished)
+        .count();
+    assert_eq!(finished, 500);
+    assert!(frames <= 3, "{} frames", frames);
+}
+
+#[tokio::test]
+async fn a_single_message_is_still_drawn() {
+    let (_, frames) = handle(vec![UiMessage::Update]).await;
+    assert_eq!(frames, 1);
+
+    let (_, frames) = handle(vec![]).await;
+    assert_eq!(frames, 0);
+}
@@ -0,0 +1,532 @@
+//! Table-driven tests of the message reducer
+//!
+//! Every message is reduced in every focus and mode combination and checked against the
+//! expected UI messages and state change. None of the messages but the keys of the popups
+//! depend on the mode, so each row lists the expectation per focus only.
+
+mod common;
+
+use common::key;
+use todo::app::{
+    appstate::{AppState, CurrentFocus, CurrentMode, Message, StateChange},
+    reduce,
+    ui::{
+        SearchEvent, SelectBF, UiMessage, WidgetAction,
+        todolistwidget::TaskStatus,
+        tree::{Fold, Jump},
+        workspacewidget::WorkspaceType,
+    },
+};
+
+const FOCUSES: [CurrentFocus; 3] = [
+    CurrentFocus::Workspace,
+    CurrentFocus::TodoList,
+    CurrentFocus::ArchivedWorkspace,
+];
+
+const MODES: [CurrentMode; 6] = [
+    CurrentMode::Normal,
+    CurrentMode::Insert,
+    CurrentMode::Search,
+    CurrentMode::Help,
+    CurrentMode::Sort,
+    CurrentMode::Tour,
+];
+
+type Expected = (Vec<UiMessage>, Option<StateChange>);
+
+/// A message and what it is expected to give with the workspace, todo list and archived focus
+type Row = (fn() -> Message, [Expected; 3]);
+
+fn act(action: WidgetAction) -> Vec<UiMessage> {
+    vec![UiMessage::WAction(action)]
+}
+
+fn mode(mode: CurrentMode) -> Option<StateChange> {
+    Some(StateChange::Mode(mode))
+}
+
+fn popup(mode: CurrentMode) -> Option<StateChange> {
+    Some(StateChange::Popup(mode))
+}
+
+/// The same expectation whatever the focus
+fn any_focus(expected: Expected) -> [Expected; 3] {
+    [expected.clone(), expected.clone(), expected]
+}
+
+fn table() -> Vec<Row> {
+    vec![
+        (
+            || Message::Update,
+            any_focus((vec![UiMessage::UpdateUi], None)),
+        ),
+        (
+            || Message::ChangeMode(CurrentMode::Search),
+            any_focus((vec![], mode(CurrentMode::Search))),
+        ),
+        (
+            || Message::ChangeFocus(CurrentFocus::TodoList),
+            any_focus((
+                act(WidgetAction::FocusTodolist),
+                Some(StateChange::Focus(CurrentFocus::TodoList)),
+            )),
+        ),
+        (
+            || Message::ChangeFocus(CurrentFocus::ArchivedWorkspace),
+            any_focus((
+                act(WidgetAction::FocusArchivedWorkspace),
+                Some(StateChange::Focus(CurrentFocus::ArchivedWorkspace)),
+            )),
+        ),
+        (
+            || Message::SelectWorkspace,
+            [
+                (act(WidgetAction::EnterWorkspace), None),
+                (vec![], None),
+                (act(WidgetAction::EnterArchivedWorkspace), None),
+            ],
+        ),
+        (
+            || Message::FocusBack,
+            any_focus((act(WidgetAction::FocusBack), None)),
+        ),
+        (
+            || Message::AddItem,
+            [
+                (act(WidgetAction::AddWorkspace), popup(CurrentMode::Insert)),
+                (act(WidgetAction::AddTask), popup(CurrentMode::Insert)),
+                (vec![], None),
+            ],
+        ),
+        (
+            || Message::AddChild,
+            [
+                (
+                    act(WidgetAction::AddWorkspaceChild),
+                    popup(CurrentMode::Insert),
+                ),
+                (act(WidgetAction::AddTaskChild), popup(CurrentMode::Insert)),
+                (vec![], None),
+            ],
+        ),
+        (
+            || Message::DeleteItem,
+            [
+                (
+                    act(WidgetAction::DeleteWorkspace),
+                    popup(CurrentMode::Insert),
+                ),
+                (act(WidgetAction::DeleteTask), popup(CurrentMode::Insert)),
+                (
+                    act(WidgetAction::DeleteArchivedWorkspace),
+                    popup(CurrentMode::Insert),
+                ),
+            ],
+        ),
+        (
+            || Message::MoveUp,
+            any_focus((act(WidgetAction::SelectUp), None)),
+        ),
+        (
+            || Message::MoveDown,
+            any_focus((act(WidgetAction::SelectDown), None)),
+        ),
+        (
+            || Message::Exit,
+            any_focus((vec![], Some(StateChange::Exit))),
+        ),
+        (
+            || Message::Archive,
+            any_focus((act(WidgetAction::ArchiveWS), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::Recovery,
+            any_focus((act(WidgetAction::RecoveryWS), None)),
+        ),
+        (
+            || Message::Complete,
+            any_focus((
+                act(WidgetAction::MarkTaskStatus(TaskStatus::Finished)),
+                None,
+            )),
+        ),
+        (
+            || Message::InProcess,
+            any_focus((
+                act(WidgetAction::MarkTaskStatus(TaskStatus::InProcess)),
+                None,
+            )),
+        ),
+        (
+            || Message::Todo,
+            any_focus((act(WidgetAction::MarkTaskStatus(TaskStatus::Todo)), None)),
+        ),
+        (
+            || Message::Deprecated,
+            any_focus((
+                act(WidgetAction::MarkTaskStatus(TaskStatus::Deprecated)),
+                popup(CurrentMode::Insert),
+            )),
+        ),
+        (
+            || Message::Rename,
+            [
+                (
+                    act(WidgetAction::Rename(CurrentFocus::Workspace)),
+                    popup(CurrentMode::Insert),
+                ),
+                (
+                    act(WidgetAction::Rename(CurrentFocus::TodoList)),
+                    popup(CurrentMode::Insert),
+                ),
+                (
+                    act(WidgetAction::Rename(CurrentFocus::ArchivedWorkspace)),
+                    popup(CurrentMode::Insert),
+                ),
+            ],
+        ),
+        (
+            || Message::Filter,
+            any_focus((act(WidgetAction::Filter), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::SearchMsg(SearchEvent::Exit),
+            any_focus((act(WidgetAction::ExitFilter), mode(CurrentMode::Normal))),
+        ),
+        (
+            || Message::SearchMsg(SearchEvent::Next),
+            any_focus((act(WidgetAction::SelectDown), None)),
+        ),
+        (
+            || Message::SearchMsg(SearchEvent::Previous),
+            any_focus((act(WidgetAction::SelectUp), None)),
+        ),
+        (
+            || Message::Help,
+            any_focus((act(WidgetAction::Help), mode(CurrentMode::Help))),
+        ),
+        (
+            || Message::ExitHelp,
+            any_focus((act(WidgetAction::ExitHelp), mode(CurrentMode::Normal))),
+        ),
+        (
+            || Message::NextHelpTab,
+            any_focus((act(WidgetAction::NextHelpTab), None)),
+        ),
+        (
+            || Message::PrevHelpTab,
+            any_focus((act(WidgetAction::PrevHelpTab), None)),
+        ),
+        (
+            || Message::Due,
+            any_focus((act(WidgetAction::Due), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::DueInline,
+            any_focus((act(WidgetAction::DueInline), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::WorkspaceStats,
+            any_focus((
+                act(WidgetAction::WorkspaceStats),
+                popup(CurrentMode::Insert),
+            )),
+        ),
+        (
+            || Message::TaskColor,
+            any_focus((act(WidgetAction::TaskColor), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::EditNotes,
+            any_focus((act(WidgetAction::EditNotes), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::BannerJump,
+            any_focus((act(WidgetAction::BannerJump), None)),
+        ),
+        (
+            || Message::DismissBanner,
+            any_focus((act(WidgetAction::DismissBanner), None)),
+        ),
+        (
+            || Message::DueMatching,
+            any_focus((act(WidgetAction::DueMatching), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::SaveData,
+            any_focus((vec![UiMessage::SaveData], None)),
+        ),
+        // the urgency is only changed from the todo list
+        (
+            || Message::IncreaseUrgency,
+            [
+                (vec![], None),
+                (act(WidgetAction::IncreaseUrgency), None),
+                (vec![], None),
+            ],
+        ),
+        (
+            || Message::DecreaseUrgency,
+            [
+                (vec![], None),
+                (act(WidgetAction::DecreaseUrgency), None),
+                (vec![], None),
+            ],
+        ),
+        (
+            || Message::Sort,
+            [
+                (act(WidgetAction::Sort), popup(CurrentMode::Sort)),
+                (act(WidgetAction::Sort), popup(CurrentMode::Sort)),
+                (act(WidgetAction::ToggleArchivedOrder), None),
+            ],
+        ),
+        (
+            || Message::ShiftUp,
+            [
+                (
+                    act(WidgetAction::ShiftWorkspace(
+                        WorkspaceType::Normal,
+                        SelectBF::Back,
+                    )),
+                    None,
+                ),
+                (vec![], None),
+                (
+                    act(WidgetAction::ShiftWorkspace(
+                        WorkspaceType::Archived,
+                        SelectBF::Back,
+                    )),
+                    None,
+                ),
+            ],
+        ),
+        (
+            || Message::ShiftDown,
+            [
+                (
+                    act(WidgetAction::ShiftWorkspace(
+                        WorkspaceType::Normal,
+                        SelectBF::Forward,
+                    )),
+                    None,
+                ),
+                (vec![], None),
+                (
+                    act(WidgetAction::ShiftWorkspace(
+                        WorkspaceType::Archived,
+                        SelectBF::Forward,
+                    )),
+                    None,
+                ),
+            ],
+        ),
+        (
+            || Message::Tour,
+            any_focus((act(WidgetAction::Tour), mode(CurrentMode::Tour))),
+        ),
+        (
+            || Message::TourNext,
+            any_focus((act(WidgetAction::TourNext), None)),
+        ),
+        (
+            || Message::TourSkip,
+            any_focus((act(WidgetAction::TourSkip), None)),
+        ),
+        (
+            || Message::Messages,
+            any_focus((act(WidgetAction::Messages), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::About,
+            any_focus((act(WidgetAction::About), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::ToggleArchived,
+            any_focus((act(WidgetAction::ToggleArchived), None)),
+        ),
+        (
+            || Message::Triage,
+            any_focus((act(WidgetAction::Triage), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::EnterTask,
+            any_focus((act(WidgetAction::EnterTask), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::CopyWorkspace,
+            any_focus((act(WidgetAction::CopyWorkspace), None)),
+        ),
+        (
+            || Message::OpenLink,
+            any_focus((act(WidgetAction::OpenLink), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::ToggleRowNumbers,
+            any_focus((act(WidgetAction::ToggleRowNumbers), None)),
+        ),
+        (
+            || Message::GoToRow,
+            any_focus((act(WidgetAction::GoToRow), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::ImportFile,
+            any_focus((act(WidgetAction::ImportFile), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::ClearDone,
+            any_focus((act(WidgetAction::ClearDone), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::Palette,
+            any_focus((act(WidgetAction::Palette), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::KeyHelp,
+            any_focus((act(WidgetAction::KeyHelp), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::SearchAll,
+            any_focus((act(WidgetAction::SearchAll), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::Heatmap,
+            any_focus((act(WidgetAction::Heatmap), popup(CurrentMode::Insert))),
+        ),
+        (
+            || Message::Prompt("@a Is Empty !".to_string()),
+            any_focus((act(WidgetAction::Prompt("@a Is Empty !".to_string())), None)),
+        ),
+        (
+            || Message::ToggleProtected,
+            any_focus((act(WidgetAction::ToggleProtected), None)),
+        ),
+        (
+            || Message::Jump(Jump::Parent),
+            any_focus((act(WidgetAction::Jump(Jump::Parent)), None)),
+        ),
+        (
+            || Message::Fold(Fold::CloseAll),
+            any_focus((act(WidgetAction::Fold(Fold::CloseAll)), None)),
+        ),
+    ]
+}
+
+#[test]
+fn every_message_in_every_focus_and_mode() {
+    for (msg, expected) in table() {
+        for (focus, expected) in FOCUSES.iter().zip(expected) {
+            for current_mode in MODES {
+                let state = AppState {
+                    current_focus: focus.clone(),
+                    current_mode,
+                    exit: false,
+                    archived_visible: true,
+                    recording: None,
+                    banner: false,
+                    resume_mode: CurrentMode::Normal,
+                };
+                assert_eq!(
+                    reduce(msg(), &state),
+                    expected,
+                    "{:?} with focus {:?} in mode {:?}",
+                    msg(),
+                    focus,
+                    current_mode
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn hidden_archived_panel_cannot_take_the_focus() {
+    let mut state = AppState::new();
+    state.archived_visible = false;
+
+    assert_eq!(
+        reduce(
+            Message::ChangeFocus(CurrentFocus::ArchivedWorkspace),
+            &state
+        ),
+        (vec![], None)
+    );
+    assert_eq!(
+        reduce(Message::ChangeFocus(CurrentFocus::TodoList), &state),
+        (
+            act(WidgetAction::FocusTodolist),
+            Some(StateChange::Focus(CurrentFocus::TodoList))
+        )
+    );
+}
+
+#[test]
+fn state_changes_apply_to_the_state() {
+    let mut state = AppState::new();
+
+    StateChange::Mode(CurrentMode::Insert).apply(&mut state);
+    StateChange::Focus(CurrentFocus::TodoList).apply(&mut state);
+    assert_eq!(state.current_mode, CurrentMode::Insert);
+    assert_eq!(state.current_focus, CurrentFocus::TodoList);
+    assert!(!state.exit);
+
+    StateChange::Exit.apply(&mut state);
+    assert!(state.exit);
+}
+
+#[test]
+fn a_popup_resumes_the_mode_it_was_opened_from() {
+    let mut state = AppState::new();
+    state.current_mode = CurrentMode::Search;
+
+    StateChange::Popup(CurrentMode::Insert).apply(&mut state);
+    assert_eq!(state.current_mode, CurrentMode::Insert);
+    // a popup opened from the open one, like an action of the palette
+    StateChange::Popup(CurrentMode::Sort).apply(&mut state);
+    state.close_popup();
+    assert_eq!(state.current_mode, CurrentMode::Search);
+
+    // the action of the popup switched to another mode, which is kept
+    StateChange::Popup(CurrentMode::Insert).apply(&mut state);
+    StateChange::Mode(CurrentMode::Help).apply(&mut state);
+    state.close_popup();
+    assert_eq!(state.current_mode, CurrentMode::Help);
+}
+
+#[test]
+fn popup_keys_go_to_the_open_popup_only() {
+    let key = key('x');
+    for current_mode in MODES {
+        let mut state = AppState::new();
+        state.current_mode = current_mode;
+        let expected = match current_mode {
+            CurrentMode::Insert | CurrentMode::Sort => vec![UiMessage::Edit(key)],
+            _ => vec![],
+        };
+        assert_eq!(
+            reduce(Message::Edit(key), &state),
+            (expected, None),
+            "in mode {:?}",
+            current_mode
+        );
+    }
+}
+
+#[test]
+fn a_key_typed_after_opening_a_popup_is_edited_not_run() {
+    let key = key('x');
+    let mut state = AppState::new();
+    state.current_focus = CurrentFocus::TodoList;
+
+    let (_, change) = reduce(Message::AddItem, &state);
+    change.unwrap().apply(&mut state);
+    assert_eq!(
+        reduce(Message::Edit(key), &state),
+        (vec![UiMessage::Edit(key)], None)
+    );
+
+    // once cancelled, the keys left over are dropped
+    state.close_popup();
+    assert_eq!(reduce(Message::Edit(key), &state), (vec![], None));
+}
@@ -0,0 +1,169 @@
+//! Tests of the reminders set some days before the due date of a task
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+use common::{ui_with, workspace};
+use ratatui::style::Color;
+use todo::app::{
+    parse,
+    sources::FixedClock,
+    ui::{
+        Ui,
+        todolistwidget::{DEFAULT_ABSOLUTE_DUE_AFTER_DAYS, Task, TaskStatus, TodoWidget},
+    },
+};
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn reminded(due: NaiveDate, days: u8) -> Task {
+    let mut task = Task::new("Pay rent".to_string(), Some(due));
+    task.remind_days_before = Some(days);
+    task
+}
+
+#[test]
+fn the_reminder_day_is_counted_back_across_months_and_years() {
+    let cases = [
+        (date(2025, 3, 2), 3, date(2025, 2, 27)),
+        (date(2024, 3, 1), 1, date(2024, 2, 29)),
+        (date(2025, 3, 1), 1, date(2025, 2, 28)),
+        (date(2025, 1, 2), 5, date(2024, 12, 28)),
+        (date(2025, 5, 31), 31, date(2025, 4, 30)),
+    ];
+    for (due, days, remind_on) in cases {
+        assert_eq!(reminded(due, days).remind_on(), Some(remind_on), "{}", due);
+    }
+    let mut task = reminded(date(2025, 3, 2), 3);
+    task.due = None;
+    assert_eq!(task.remind_on(), None);
+}
+
+#[test]
+fn an_open_task_is_reminded_of_until_the_day_before_it_is_due() {
+    let mut task = reminded(date(2025, 3, 2), 3);
+    assert!(!task.reminding(date(2025, 2, 26)));
+    assert!(task.reminding(date(2025, 2, 27)));
+    assert!(task.reminding(date(2025, 3, 1)));
+    // due, the due date takes over
+    assert!(!task.reminding(date(2025, 3, 2)));
+
+    task.status = TaskStatus::Finished;
+    assert!(!task.reminding(date(2025, 2, 28)));
+}
+
+#[test]
+fn a_reminded_task_takes_the_attention_color() {
+    let clock = FixedClock(date(2025, 2, 27));
+    let span = |due, remind| {
+        TodoWidget::due_span(
+            due,
+            &TaskStatus::Todo,
+            &clock,
+            DEFAULT_ABSOLUTE_DUE_AFTER_DAYS,
+            remind,
+        )
+    };
+
+    let near = span(date(2025, 3, 4), Some(5));
+    assert_eq!(near.content, " 5 day left ! ");
+    assert_eq!(near.style.fg, Some(Color::Yellow));
+    assert_eq!(
+        span(date(2025, 3, 4), None).style.fg,
+        Some(Color::LightBlue)
+    );
+
+    // a reminder past the threshold counts the days rather than showing the date
+    let far = span(date(2025, 3, 19), Some(20));
+    assert_eq!(far.content, " 20 day left ! ");
+    assert_eq!(far.style.fg, Some(Color::Yellow));
+    assert_eq!(span(date(2025, 3, 19), Some(10)).content, " 2025-03-19 ");
+
+    assert_eq!(
+        span(date(2025, 2, 28), Some(5)).style.fg,
+        Some(Color::LightRed)
+    );
+}
+
+#[test]
+fn the_bell_shows_with_a_reminder_on_an_open_task() {
+    let today = date(2025, 2, 20);
+    let mut task = reminded(date(2025, 3, 2), 3);
+    let bell = TodoWidget::reminder_span(&task, today);
+    assert!(!bell.content.is_empty());
+    assert_eq!(bell.style.fg, Some(Color::DarkGray));
+    assert_eq!(
+        TodoWidget::reminder_span(&task, date(2025, 2, 28)).style.fg,
+        Some(Color::Yellow)
+    );
+
+    task.status = TaskStatus::Deprecated;
+    assert!(TodoWidget::reminder_span(&task, today).content.is_empty());
+    task.status = TaskStatus::Todo;
+    task.remind_days_before = None;
+    assert!(TodoWidget::reminder_span(&task, today).content.is_empty());
+}
+
+#[test]
+fn the_reminder_is_typed_after_the_due_date() {
+    let clock = FixedClock(date(2025, 7, 2));
+    let mut task = Task::new("Pay rent".to_string(), None);
+
+    task.set_due_typed("2025-07-31 remind 3d", &clock);
+    assert_eq!(task.due, Some(date(2025, 7, 31)));
+    assert_eq!(task.remind_days_before, Some(3));
+
+    // the reminder stays when only the date changes, and alone it keeps the date
+    task.set_due_typed("+1w", &clock);
+    assert_eq!(task.due, Some(date(2025, 7, 9)));
+    assert_eq!(task.remind_days_before, Some(3));
+    task.set_due_typed("remind 1", &clock);
+    assert_eq!(task.due, Some(date(2025, 7, 9)));
+    assert_eq!(task.remind_days_before, Some(1));
+
+    task.set_due_typed("remind 0d", &clock);
+    assert_eq!(task.remind_days_before, None);
+    assert_eq!(parse::reminder("remind 900d"), ("", Some(Some(u8::MAX))));
+    assert_eq!(parse::reminder("reminder"), ("reminder", None));
+}
+
+#[test]
+fn the_reminder_is_saved_with_the_task() {
+    let task = reminded(date(2025, 3, 2), 3);
+    let json = serde_json::to_string(&task).unwrap();
+    let loaded: Task = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.remind_days_before, Some(3));
+
+    // saved before reminders existed
+    let old = json.replace(",\"remind_days_before\":3", "");
+    assert!(!old.contains("remind"));
+    let loaded: Task = serde_json::from_str(&old).unwrap();
+    assert_eq!(loaded.remind_days_before, None);
+}
+
+#[test]
+fn the_banner_announces_a_task_from_its_reminder_day() {
+    let rent = Rc::new(RefCell::new(reminded(date(2025, 3, 2), 3)));
+    let (mut ui, _, _) = ui_with(vec![workspace("Work")], vec![rent]);
+    let text = |ui: &Ui| ui.banner.as_ref().map(|banner| banner.text.clone());
+
+    ui.announce_due(&FixedClock(date(2025, 2, 26)));
+    assert_eq!(text(&ui), None);
+
+    ui.announce_due(&FixedClock(date(2025, 2, 27)));
+    assert_eq!(
+        text(&ui).as_deref(),
+        Some("'Pay rent' is due in 3 days — press O to jump")
+    );
+
+    ui.banner = None;
+    ui.announce_due(&FixedClock(date(2025, 3, 1)));
+    assert_eq!(
+        text(&ui).as_deref(),
+        Some("'Pay rent' is due tomorrow — press O to jump")
+    );
+}
@@ -0,0 +1,206 @@
+//! Tests of the popups drawn again when the terminal is resized while they are open
+
+mod common;
+
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    io,
+    rc::Rc,
+    time::Duration,
+};
+
+use common::{code, key, ui_with};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Terminal,
+    backend::{Backend, ClearType, TestBackend, WindowSize},
+    buffer::Buffer,
+    layout::{Position, Size},
+};
+use todo::app::ui::{Ui, keys};
+use tokio::sync::mpsc;
+
+/// A test backend shared with the test, which resizes it while a popup holds the terminal
+#[derive(Clone)]
+struct Shared(Rc<RefCell<TestBackend>>);
+
+impl Shared {
+    fn new(width: u16, height: u16) -> Self {
+        Shared(Rc::new(RefCell::new(TestBackend::new(width, height))))
+    }
+
+    fn buffer(&self) -> Buffer {
+        self.0.borrow().buffer().clone()
+    }
+}
+
+impl Backend for Shared {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a ratatui::buffer::Cell)>,
+    {
+        self.0.borrow_mut().draw(content)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.0.borrow_mut().hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.0.borrow_mut().show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.0.borrow_mut().get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.0.borrow_mut().set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.0.borrow_mut().clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.0.borrow_mut().clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.0.borrow().size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.0.borrow_mut().window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.borrow_mut().flush()
+    }
+}
+
+fn fresh_ui() -> (Ui, mpsc::Sender<KeyEvent>) {
+    let (ui, _, input_tx) = ui_with(vec![], vec![]);
+    (ui, input_tx)
+}
+
+/// Type into the popup, resize the terminal from 60x20 to 100x30 as the key thread does, then
+/// close the popup with a key once it's drawn again
+///
+/// # Returns
+///
+/// - `F::Output` - what the popup returned
+async fn resize_during<F: Future>(
+    popup: F,
+    backend: &Shared,
+    input_tx: &mpsc::Sender<KeyEvent>,
+    typed: &str,
+    close: KeyCode,
+) -> F::Output {
+    let open = Cell::new(true);
+    let popup = async {
+        let output = popup.await;
+        open.set(false);
+        output
+    };
+    let keys = async {
+        for c in typed.chars() {
+            input_tx.send(key(c)).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        backend.0.borrow_mut().resize(100, 30);
+        input_tx.send(keys::RESIZE).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(open.get(), "the popup closed on the resize");
+        input_tx.send(code(close)).await.unwrap();
+    };
+    tokio::join!(popup, keys).0
+}
+
+#[tokio::test]
+async fn the_input_popup_is_centered_again_once_the_terminal_is_resized() {
+    let backend = Shared::new(60, 20);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+    let (mut ui, input_tx) = fresh_ui();
+    let rx = ui.input_rx.clone();
+    let typed = resize_during(
+        ui.get_input(rx, &mut terminal, "Add Task".to_string()),
+        &backend,
+        &input_tx,
+        "Groceries",
+        KeyCode::Enter,
+    )
+    .await;
+    assert_eq!(typed, "Groceries");
+
+    // drawn as the popup is on a terminal of that size from the start
+    let (mut ui, input_tx) = fresh_ui();
+    for key in "Groceries".chars().map(KeyCode::Char).chain([KeyCode::Esc]) {
+        input_tx.send(code(key)).await.unwrap();
+    }
+    let mut expected = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let rx = ui.input_rx.clone();
+    ui.get_input(rx, &mut expected, "Add Task".to_string())
+        .await;
+    assert_eq!(&backend.buffer(), expected.backend().buffer());
+}
+
+#[tokio::test]
+async fn a_confirm_dialog_is_centered_again_once_the_terminal_is_resized() {
+    let backend = Shared::new(60, 20);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+    let (mut ui, input_tx) = fresh_ui();
+    let rx = ui.input_rx.clone();
+    let confirmed = resize_during(
+        ui.confirm_archive(rx, &mut terminal, "Garden", 3),
+        &backend,
+        &input_tx,
+        "",
+        KeyCode::Char('n'),
+    )
+    .await;
+    assert!(!confirmed);
+
+    let (mut ui, input_tx) = fresh_ui();
+    input_tx.send(key('n')).await.unwrap();
+    let mut expected = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let rx = ui.input_rx.clone();
+    ui.confirm_archive(rx, &mut expected, "Garden", 3).await;
+    assert_eq!(&backend.buffer(), expected.backend().buffer());
+}
+
+#[tokio::test]
+async fn a_popup_any_key_closes_stays_open_on_a_resize() {
+    let backend = Shared::new(60, 20);
+    let mut terminal = Terminal::new(backend.clone()).unwrap();
+    let (mut ui, input_tx) = fresh_ui();
+    let rx = ui.input_rx.clone();
+    resize_during(
+        ui.show_about(rx, &mut terminal),
+        &backend,
+        &input_tx,
+        "",
+        KeyCode::Char('x'),
+    )
+    .await;
+
+    let (mut ui, input_tx) = fresh_ui();
+    input_tx.send(key('x')).await.unwrap();
+    let mut expected = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let rx = ui.input_rx.clone();
+    ui.show_about(rx, &mut expected).await;
+    assert_eq!(&backend.buffer(), expected.backend().buffer());
+}
+
+#[tokio::test]
+async fn a_resize_is_never_kept_with_the_keys_of_an_action() {
+    let (input_tx, mut input_rx) = mpsc::channel(4);
+    for key in [keys::RESIZE, key('y')] {
+        input_tx.send(key).await.unwrap();
+    }
+    keys::tap();
+    assert_eq!(keys::next_press(&mut input_rx).await, Some(keys::RESIZE));
+    keys::next_press(&mut input_rx).await;
+    assert_eq!(keys::untap(), [key('y')]);
+}
@@ -0,0 +1,344 @@
+//! Tests of the data file rolled back or changed under the app
+//!
+//! The app saves to a file in a temporary directory, then an older copy of the file is put
+//! back with an older modification time, as a sync tool restoring it would, before the next
+//! save. Or `todo serve` changes the file while the app has it loaded.
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    fs::{self, File},
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use common::{key, rows, ui_with};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    data::{self, Datas, FileStamp},
+    errors::Errors,
+    serve,
+    sources::{Storage, SystemClock},
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{Staleness, Task, TaskStatus, TodoList},
+        workspacewidget::Workspace,
+    },
+};
+use tokio::sync::{Mutex as AsyncMutex, mpsc};
+
+/// A data file in a directory of its own, holding `Work` with a `Write` task
+fn data_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("todo-rollback-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("data.json");
+    let mut datas = Datas::default();
+    let ws = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+    let mut list = TodoList::new(ws.borrow().id);
+    list.add_task(Rc::new(RefCell::new(Task::new("Write".to_string(), None))));
+    datas.workspace.add_workspace(ws);
+    datas.todolist.add_list(Rc::new(RefCell::new(list)));
+    data::save_data(&path, &datas).unwrap();
+    path
+}
+
+/// The app on the data file, with `Write` selected in the todo list
+fn ui(path: &Path) -> Ui {
+    let (mut ui, _, _) = ui_with(vec![], vec![]);
+    let datas = data::load_data(path).unwrap();
+    ui.data_path = path.to_path_buf();
+    ui.saved_stamp = FileStamp::read(path);
+    ui.workspace = datas.workspace;
+    ui.todolist = datas.todolist;
+    ui.archived_ws = datas.archived_ws;
+    let ws = ui.workspace.workspaces[0].clone();
+    ui.workspace.select(Some(ws.clone()));
+    ui.todolist.change_current_list(&Some(ws));
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let task = list.borrow().tasks[0].clone();
+    list.borrow_mut().select_task(&task);
+    ui.todolist.focused = true;
+    ui
+}
+
+/// Mark `Write` with a status and save, the keys going to the popups
+async fn mark_and_save(ui: &mut Ui, status: TaskStatus, keys: &[char]) -> Terminal<TestBackend> {
+    let (ui_tx, ui_rx) = mpsc::channel(4);
+    let (input_tx, input_rx) = mpsc::channel(8);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(AsyncMutex::new(input_rx));
+    for c in keys {
+        input_tx.send(key(*c)).await.unwrap();
+    }
+    drop(input_tx);
+    let action = UiMessage::WAction(WidgetAction::MarkTaskStatus(status));
+    ui_tx.send(action).await.unwrap();
+    ui_tx.send(UiMessage::SaveData).await.unwrap();
+    drop(ui_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(appstate)))
+        .await;
+    terminal
+}
+
+/// Put `content` back in the file, modified an hour before the last save
+fn restore(ui: &Ui, content: &str) {
+    fs::write(&ui.data_path, content).unwrap();
+    let saved = ui.saved_stamp.unwrap().modified;
+    File::options()
+        .write(true)
+        .open(&ui.data_path)
+        .unwrap()
+        .set_modified(saved - Duration::from_secs(3600))
+        .unwrap();
+}
+
+fn saved_status(path: &Path) -> TaskStatus {
+    let datas = data::load_data(path).unwrap();
+    let list = datas.todolist.todolists[0].clone();
+    let task = list.borrow().tasks[0].clone();
+    task.borrow().status.clone()
+}
+
+fn shown(terminal: &Terminal<TestBackend>, text: &str) -> bool {
+    rows(terminal).iter().any(|row| row.contains(text))
+}
+
+#[tokio::test]
+async fn ours_is_kept_over_a_rolled_back_file() {
+    let path = data_file("keep");
+    let old = fs::read_to_string(&path).unwrap();
+    let mut ui = ui(&path);
+    mark_and_save(&mut ui, TaskStatus::Finished, &[]).await;
+    assert_eq!(saved_status(&path), TaskStatus::Finished);
+    assert!(!ui.rolled_back());
+
+    restore(&ui, &old);
+    assert!(ui.rolled_back());
+    mark_and_save(&mut ui, TaskStatus::InProcess, &['o']).await;
+
+    assert_eq!(saved_status(&path), TaskStatus::InProcess);
+    assert!(!ui.rolled_back());
+    assert!(!ui.dirty);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn theirs_is_reloaded_dropping_the_changes() {
+    let path = data_file("reload");
+    let old = fs::read_to_string(&path).unwrap();
+    let mut ui = ui(&path);
+    mark_and_save(&mut ui, TaskStatus::Finished, &[]).await;
+
+    restore(&ui, &old);
+    mark_and_save(&mut ui, TaskStatus::InProcess, &['t']).await;
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), old);
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    let task = list.borrow().tasks[0].clone();
+    assert_eq!(task.borrow().status, TaskStatus::Todo);
+    // the selection of the app is kept
+    assert!(Rc::ptr_eq(
+        list.borrow().current_task.as_ref().unwrap(),
+        &task
+    ));
+    assert!(ui.todolist.focused);
+    assert!(!ui.dirty);
+    assert_eq!(ui.changes, 0);
+    assert_eq!(ui.prompt.desc, "Data Reloaded From The File !");
+    // the file reloaded is the one the next save compares to
+    assert!(!ui.rolled_back());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn the_diff_shows_what_keeping_ours_changes_and_nothing_is_saved_meanwhile() {
+    let path = data_file("diff");
+    let old = fs::read_to_string(&path).unwrap();
+    let mut ui = ui(&path);
+    mark_and_save(&mut ui, TaskStatus::Finished, &[]).await;
+
+    restore(&ui, &old);
+    let terminal = mark_and_save(&mut ui, TaskStatus::InProcess, &['d']).await;
+
+    assert!(shown(&terminal, "~ Write: status Todo -> InProcess"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), old);
+    assert!(ui.dirty);
+    assert_eq!(ui.prompt.desc, "Not Saved, The Data File Was Rolled Back !");
+    assert!(ui.rolled_back());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn only_an_older_file_with_another_content_is_rolled_back() {
+    let path = data_file("newer");
+    let old = fs::read_to_string(&path).unwrap();
+    let mut ui = ui(&path);
+    mark_and_save(&mut ui, TaskStatus::Finished, &[]).await;
+    let saved = fs::read_to_string(&path).unwrap();
+
+    // a backup of the last save put back loses nothing
+    restore(&ui, &saved);
+    assert!(!ui.rolled_back());
+
+    // a file written after the save was changed, not rolled back
+    fs::write(&path, &old).unwrap();
+    File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(ui.saved_stamp.unwrap().modified + Duration::from_secs(60))
+        .unwrap();
+    assert!(!ui.rolled_back());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn the_exit_save_sets_the_changes_aside_over_a_rolled_back_file() {
+    let path = data_file("exit");
+    let old = fs::read_to_string(&path).unwrap();
+    let mut ui = ui(&path);
+    mark_and_save(&mut ui, TaskStatus::Finished, &[]).await;
+
+    restore(&ui, &old);
+    // no choice, the file is left for the next save
+    mark_and_save(&mut ui, TaskStatus::InProcess, &[]).await;
+    assert!(ui.dirty);
+
+    let err = ui.exit_save().unwrap_err();
+    assert!(matches!(err, Errors::ExitSaveError { .. }));
+    assert!(err.to_string().contains("rolled back"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), old);
+
+    // nothing to keep, the file is left alone without an error
+    ui.dirty = false;
+    ui.exit_save().unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), old);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+/// A data file in memory, which the tests put back or change under the app
+#[derive(Debug)]
+struct Memory {
+    file: Mutex<(SystemTime, String)>,
+}
+
+impl Storage for Memory {
+    fn write(&self, _path: &Path, content: &str) -> Result<(), Errors> {
+        *self.file.lock().unwrap() = (SystemTime::now(), content.to_string());
+        Ok(())
+    }
+
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        Some(self.file.lock().unwrap().0)
+    }
+
+    fn read(&self, _path: &Path) -> Option<String> {
+        Some(self.file.lock().unwrap().1.clone())
+    }
+}
+
+#[test]
+fn the_file_is_compared_through_the_storage() {
+    let (mut ui, _, _) = ui_with(vec![], vec![]);
+    let saved = SystemTime::now();
+    let storage = Arc::new(Memory {
+        file: Mutex::new((saved, "saved".to_string())),
+    });
+    ui.storage = storage.clone();
+    // no file is there, the storage alone answers
+    ui.data_path = PathBuf::from("nowhere/data.json");
+    ui.saved_stamp = Some(FileStamp::new(saved, "saved"));
+    assert!(!ui.rolled_back());
+    assert!(!ui.changed_elsewhere());
+
+    *storage.file.lock().unwrap() = (saved - Duration::from_secs(3600), "old".to_string());
+    assert!(ui.rolled_back());
+    assert!(!ui.changed_elsewhere());
+
+    *storage.file.lock().unwrap() = (saved + Duration::from_secs(60), "theirs".to_string());
+    assert!(!ui.rolled_back());
+    assert!(ui.changed_elsewhere());
+}
+
+/// Add `buy milk` to `Work` as `todo serve` does, the response
+fn serve_add(path: &Path) -> String {
+    let request = r#"{"cmd":"add_task","workspace":"Work","desc":"buy milk"}"#;
+    serve::handle_line(path, request, Staleness::new(&SystemClock, 0))
+}
+
+fn descs(list: &TodoList) -> Vec<String> {
+    let tasks = list.tasks.iter();
+    tasks.map(|task| task.borrow().desc.clone()).collect()
+}
+
+fn saved_descs(path: &Path) -> Vec<String> {
+    let datas = data::load_data(path).unwrap();
+    descs(&datas.todolist.todolists[0].borrow())
+}
+
+#[tokio::test]
+async fn a_change_of_serve_is_not_saved_over() {
+    let path = data_file("serve");
+    let mut ui = ui(&path);
+    assert!(!ui.changed_elsewhere());
+
+    assert!(serve_add(&path).contains(r#""ok":true"#));
+    assert!(ui.changed_elsewhere());
+    assert!(!ui.rolled_back());
+    let terminal = mark_and_save(&mut ui, TaskStatus::Finished, &[]).await;
+
+    assert!(shown(&terminal, "Data File Changed"));
+    assert_eq!(saved_descs(&path), ["Write", "buy milk"]);
+    assert_eq!(saved_status(&path), TaskStatus::Todo);
+    assert!(ui.dirty);
+    assert_eq!(ui.prompt.desc, "Not Saved, The Data File Was Changed !");
+
+    mark_and_save(&mut ui, TaskStatus::Finished, &['t']).await;
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    assert_eq!(descs(&list.borrow()), ["Write", "buy milk"]);
+    // the file reloaded is the one the next save compares to
+    assert!(!ui.changed_elsewhere());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn ours_is_kept_over_a_change_of_serve() {
+    let path = data_file("serve-keep");
+    let mut ui = ui(&path);
+    serve_add(&path);
+
+    mark_and_save(&mut ui, TaskStatus::Finished, &['o']).await;
+
+    assert_eq!(saved_descs(&path), ["Write"]);
+    assert_eq!(saved_status(&path), TaskStatus::Finished);
+    assert!(!ui.changed_elsewhere());
+    assert!(!ui.dirty);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn serve_waits_for_the_lock_of_the_app() {
+    let path = data_file("lock");
+    let lock = data::lock_data(&path).unwrap();
+
+    let serving = thread::spawn({
+        let path = path.clone();
+        move || serve_add(&path)
+    });
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(saved_descs(&path), ["Write"]);
+
+    drop(lock);
+    assert!(serving.join().unwrap().contains(r#""ok":true"#));
+    assert_eq!(saved_descs(&path), ["Write", "buy milk"]);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,136 @@
+//! Tests of the numbers before the rows of the task list and the jump to a row
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{code, node, rows, ui_with};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    ui::{
+        UiMessage, WidgetAction,
+        todolistwidget::{RowNumbers, TodoList, TodoWidget},
+        workspacewidget::Workspace,
+    },
+};
+
+/// `Alpha` with `Child`, then `Beta` and `Gamma`, with `Beta` selected
+fn list() -> Rc<RefCell<TodoList>> {
+    let ws = Workspace::new("Work".to_string());
+    let mut list = TodoList::new(ws.id);
+    list.add_task(node("Alpha", vec![node("Child", vec![])]));
+    let beta = node("Beta", vec![]);
+    list.add_task(beta.clone());
+    list.add_task(node("Gamma", vec![]));
+    list.current_task = Some(beta);
+    list.state.select(Some(2));
+    Rc::new(RefCell::new(list))
+}
+
+fn widget() -> TodoWidget {
+    let mut widget = TodoWidget::new();
+    widget.current_todolist = Some(list());
+    widget
+}
+
+/// The number column of the row of each task, as drawn
+fn numbers(widget: &mut TodoWidget, descs: &[&str]) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+    terminal
+        .draw(|f| f.render_widget(&mut *widget, f.area()))
+        .unwrap();
+    let rows = rows(&terminal);
+    descs
+        .iter()
+        .map(|desc| {
+            let row = rows.iter().find(|row| row.contains(desc)).unwrap();
+            // the border and the padding come first
+            row.chars().skip(2).take(2).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn the_shown_rows_are_numbered() {
+    let mut widget = widget();
+    let descs = ["Alpha", "Child", "Beta", "Gamma"];
+    let plain = numbers(&mut widget, &descs);
+    assert!(plain.iter().all(|cell| !cell.contains(char::is_numeric)));
+
+    widget.row_numbers = widget.row_numbers.next();
+    assert_eq!(widget.row_numbers, RowNumbers::Absolute);
+    assert_eq!(numbers(&mut widget, &descs), ["1 ", "2 ", "3 ", "4 "]);
+
+    widget.row_numbers = widget.row_numbers.next();
+    assert_eq!(numbers(&mut widget, &descs), ["2 ", "1 ", "3 ", "1 "]);
+
+    // a folded task hides the row of its subtask, the numbers follow
+    widget.row_numbers = RowNumbers::Absolute;
+    let list = widget.current_todolist.clone().unwrap();
+    list.borrow().tasks[0].borrow_mut().expanded = false;
+    list.borrow_mut().state.select(Some(1));
+    assert_eq!(
+        numbers(&mut widget, &["Alpha", "Beta", "Gamma"]),
+        ["1 ", "2 ", "3 "]
+    );
+}
+
+#[test]
+fn while_filtering_the_matching_rows_are_the_ones_numbered() {
+    let mut widget = widget();
+    widget.search_string = "Gamma".to_string();
+    let shown = widget.shown_tasks();
+    assert_eq!(shown.len(), 1);
+
+    assert!(widget.select_row(0));
+    let list = widget.current_todolist.clone().unwrap();
+    assert!(Rc::ptr_eq(
+        list.borrow().current_task.as_ref().unwrap(),
+        &shown[0]
+    ));
+    assert!(!widget.select_row(1));
+}
+
+/// Go to the row typed in the popup, with `Beta` selected
+async fn go_to(typed: &str) -> (String, String) {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let list = list();
+    ui.todolist.current_todolist = Some(list.clone());
+    ui.todolist.focused = true;
+
+    for c in typed.chars().map(KeyCode::Char).chain([KeyCode::Enter]) {
+        input_tx.send(code(c)).await.unwrap();
+    }
+    drop(input_tx);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::GoToRow))
+        .await
+        .unwrap();
+    drop(ui_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(appstate)))
+        .await;
+
+    let current = list.borrow().current_task.clone().unwrap();
+    let desc = current.borrow().desc.clone();
+    (desc, ui.prompt.desc.clone())
+}
+
+#[tokio::test]
+async fn a_row_is_gone_to_by_number_or_distance() {
+    assert_eq!(go_to("1").await.0, "Alpha");
+    assert_eq!(go_to("+1").await.0, "Gamma");
+    assert_eq!(go_to("-1").await.0, "Child");
+
+    let (desc, prompt) = go_to("9").await;
+    assert_eq!(desc, "Beta");
+    assert_eq!(prompt, "No Row 9 !");
+}
@@ -0,0 +1,206 @@
+//! Tests of the batching of the saves
+//!
+//! The UI writes through a storage recording the writes, so the tests count them and check
+//! that two never run at once.
+
+mod common;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use common::{task, ui_with, workspace};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    errors::Errors,
+    sources::Storage,
+    ui::{Autosave, SAVE_WINDOW, Ui, UiMessage, WidgetAction, todolistwidget::Urgency},
+};
+use tokio::sync::mpsc;
+
+/// A storage keeping the writes in memory, each taking `delay`
+#[derive(Debug, Default)]
+struct Recorder {
+    writes: Mutex<Vec<(PathBuf, String)>>,
+    writing: AtomicBool,
+    overlapped: AtomicBool,
+    delay: Duration,
+}
+
+impl Storage for Recorder {
+    fn write(&self, path: &Path, content: &str) -> Result<(), Errors> {
+        if self.writing.swap(true, Ordering::SeqCst) {
+            self.overlapped.store(true, Ordering::SeqCst);
+        }
+        std::thread::sleep(self.delay);
+        self.writes
+            .lock()
+            .unwrap()
+            .push((path.to_path_buf(), content.to_string()));
+        self.writing.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// `Work` open in the todo list, with a selected `Write` task, writing to `storage`
+fn ui(storage: Arc<Recorder>) -> (Ui, mpsc::Sender<UiMessage>) {
+    let (mut ui, ui_tx, _) = ui_with(vec![workspace("Work")], vec![task("Write")]);
+    ui.storage = storage;
+    ui.data_path = PathBuf::from("data.json");
+    (ui, ui_tx)
+}
+
+async fn run(ui: &mut Ui) {
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(appstate)))
+        .await;
+}
+
+fn urgency(content: &str) -> Option<Urgency> {
+    let datas: todo::app::data::Datas = serde_json::from_str(content).unwrap();
+    let list = datas.todolist.todolists[0].clone();
+    let task = list.borrow().tasks[0].clone();
+    task.borrow().urgency.clone()
+}
+
+#[tokio::test]
+async fn rapid_saves_are_written_once() {
+    let storage = Arc::new(Recorder::default());
+    let (mut ui, ui_tx) = ui(storage.clone());
+    for _ in 0..3 {
+        ui_tx
+            .send(UiMessage::WAction(WidgetAction::IncreaseUrgency))
+            .await
+            .unwrap();
+        for _ in 0..10 {
+            ui_tx.send(UiMessage::SaveData).await.unwrap();
+        }
+    }
+    drop(ui_tx);
+    run(&mut ui).await;
+
+    let writes = storage.writes.lock().unwrap();
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[0].0, PathBuf::from("data.json"));
+    assert_eq!(urgency(&writes[0].1), Some(Urgency::Critical));
+    assert_eq!(ui.prompt.desc, "Data Saved (3 Changes) !");
+    assert!(!ui.dirty);
+    assert_eq!(ui.changes, 0);
+}
+
+#[tokio::test]
+async fn a_save_asked_for_while_writing_follows_with_the_last_state() {
+    let storage = Arc::new(Recorder {
+        delay: SAVE_WINDOW * 2,
+        ..Default::default()
+    });
+    let (mut ui, ui_tx) = ui(storage.clone());
+    tokio::spawn(async move {
+        let action = || UiMessage::WAction(WidgetAction::IncreaseUrgency);
+        ui_tx.send(action()).await.unwrap();
+        ui_tx.send(UiMessage::SaveData).await.unwrap();
+        // the first write is running
+        tokio::time::sleep(SAVE_WINDOW + SAVE_WINDOW / 2).await;
+        ui_tx.send(action()).await.unwrap();
+        ui_tx.send(UiMessage::SaveData).await.unwrap();
+        ui_tx.send(action()).await.unwrap();
+        ui_tx.send(UiMessage::SaveData).await.unwrap();
+        tokio::time::sleep(SAVE_WINDOW * 4).await;
+    });
+    run(&mut ui).await;
+
+    let writes = storage.writes.lock().unwrap();
+    assert_eq!(writes.len(), 2);
+    assert!(!storage.overlapped.load(Ordering::SeqCst));
+    assert_eq!(urgency(&writes[0].1), Some(Urgency::Common));
+    assert_eq!(urgency(&writes[1].1), Some(Urgency::Critical));
+    assert_eq!(ui.prompt.desc, "Data Saved (2 Changes) !");
+}
+
+#[tokio::test]
+async fn a_requested_save_is_written_when_the_channel_closes() {
+    let storage = Arc::new(Recorder::default());
+    let (mut ui, ui_tx) = ui(storage.clone());
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::IncreaseUrgency))
+        .await
+        .unwrap();
+    ui_tx.send(UiMessage::SaveData).await.unwrap();
+    drop(ui_tx);
+    let started = tokio::time::Instant::now();
+    run(&mut ui).await;
+
+    assert_eq!(storage.writes.lock().unwrap().len(), 1);
+    assert!(started.elapsed() < SAVE_WINDOW);
+    assert_eq!(ui.prompt.desc, "Data Saved (1 Change) !");
+}
+
+/// Run the messages with the autosave set, returning the writes of the data file
+async fn autosaved(autosave: Autosave, msgs: Vec<UiMessage>) -> usize {
+    let storage = Arc::new(Recorder::default());
+    let (mut ui, ui_tx) = ui(storage.clone());
+    ui.config.autosave = autosave;
+    for msg in msgs {
+        ui_tx.send(msg).await.unwrap();
+    }
+    drop(ui_tx);
+    run(&mut ui).await;
+    let writes = storage.writes.lock().unwrap();
+    writes
+        .iter()
+        .filter(|(path, _)| path == Path::new("data.json"))
+        .count()
+}
+
+#[tokio::test]
+async fn leaving_a_panel_saves_the_changes_once_on_blur() {
+    let action = |action| UiMessage::WAction(action);
+    let cycled = || {
+        vec![
+            action(WidgetAction::IncreaseUrgency),
+            action(WidgetAction::FocusWorkspace),
+            action(WidgetAction::FocusTodolist),
+            action(WidgetAction::FocusWorkspace),
+        ]
+    };
+    // the saves asked for while cycling through the panels are written at once
+    assert_eq!(autosaved(Autosave::OnBlur, cycled()).await, 1);
+    assert_eq!(autosaved(Autosave::Off, cycled()).await, 0);
+    assert_eq!(autosaved(Autosave::Interval, cycled()).await, 0);
+    // nothing changed, nothing is written
+    let unchanged = vec![
+        action(WidgetAction::FocusWorkspace),
+        action(WidgetAction::FocusTodolist),
+    ];
+    assert_eq!(autosaved(Autosave::OnBlur, unchanged).await, 0);
+    // a change made in the todo list shown is saved on opening the help page
+    let help = vec![
+        action(WidgetAction::IncreaseUrgency),
+        action(WidgetAction::Help),
+    ];
+    assert_eq!(autosaved(Autosave::OnBlur, help).await, 1);
+}
+
+#[tokio::test]
+async fn the_tick_saves_the_changes_on_interval() {
+    let changed = || {
+        vec![
+            UiMessage::WAction(WidgetAction::IncreaseUrgency),
+            UiMessage::UpdateUi,
+        ]
+    };
+    assert_eq!(autosaved(Autosave::Interval, changed()).await, 1);
+    assert_eq!(autosaved(Autosave::OnBlur, changed()).await, 0);
+    assert_eq!(
+        autosaved(Autosave::Interval, vec![UiMessage::UpdateUi]).await,
+        0
+    );
+}
@@ -0,0 +1,114 @@
+//! Tests of saving the data file
+
+use chrono::NaiveDate;
+use ratatui::widgets::ListItem;
+use todo::app::{
+    data::{self, Datas},
+    errors::Errors,
+    sources::FixedClock,
+    ui::{
+        SelectAction,
+        todolistwidget::{RowOptions, SortRule, TodoList, TodoWidget},
+    },
+};
+
+#[test]
+fn save_and_load_round_trip() {
+    let path = std::env::temp_dir().join(format!("todo-save-{}.json", std::process::id()));
+    let datas = data::demo_datas();
+
+    data::save_data(&path, &datas).unwrap();
+    let loaded = data::load_data(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(data::archive_path(&path));
+
+    assert_eq!(data::serialize_data(&loaded), data::serialize_data(&datas));
+}
+
+#[test]
+fn save_failure_reports_the_path_and_reason() {
+    let path = std::env::temp_dir()
+        .join(format!("todo-missing-dir-{}", std::process::id()))
+        .join("data.json");
+
+    let err = data::save_data(&path, &Datas::default()).unwrap_err();
+
+    assert!(matches!(&err, Errors::SaveError { path: p, .. } if *p == path));
+    let message = err.to_string();
+    assert!(message.contains(&path.display().to_string()), "{}", message);
+}
+
+#[test]
+fn exit_save_failure_writes_an_emergency_copy() {
+    let path = std::env::temp_dir()
+        .join(format!("todo-missing-dir-exit-{}", std::process::id()))
+        .join("data.json");
+    let datas = data::demo_datas();
+
+    let err = data::save_data_on_exit(&path, &datas, None).unwrap_err();
+
+    let Errors::ExitSaveError { emergency, .. } = &err else {
+        panic!("unexpected error {:?}", err);
+    };
+    let emergency = emergency.as_ref().expect("an emergency copy");
+    assert_eq!(*emergency, data::emergency_path());
+    // a single file with the archived workspaces too
+    assert_eq!(
+        std::fs::read_to_string(emergency).unwrap(),
+        serde_json::to_string_pretty(&datas).unwrap()
+    );
+    assert!(err.to_string().contains(&emergency.display().to_string()));
+}
+
+#[test]
+fn sort_rule_and_manual_order_survive_a_round_trip() {
+    let path = std::env::temp_dir().join(format!("todo-order-{}.json", std::process::id()));
+    let datas = data::demo_datas();
+    datas
+        .todolist
+        .todolists
+        .iter()
+        .for_each(|list| list.borrow_mut().set_sort_rule(SortRule::Smart));
+    let rendered = |datas: &Datas| -> Vec<Vec<ListItem<'static>>> {
+        datas
+            .todolist
+            .todolists
+            .iter()
+            .map(|list| {
+                let list = list.borrow();
+                let max_len = TodoWidget::find_max_tasks_len(&list.tasks, 1);
+                TodoWidget::get_task_list_item(
+                    &list.tasks,
+                    list.sort_rule,
+                    0,
+                    max_len,
+                    &FixedClock(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
+                    RowOptions::default(),
+                    None,
+                )
+            })
+            .collect()
+    };
+
+    data::save_data(&path, &datas).unwrap();
+    let loaded = data::load_data(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(data::archive_path(&path));
+
+    assert_eq!(rendered(&loaded), rendered(&datas));
+    for (before, after) in datas
+        .todolist
+        .todolists
+        .iter()
+        .zip(&loaded.todolist.todolists)
+    {
+        let ids = |list: &std::rc::Rc<std::cell::RefCell<TodoList>>| -> Vec<uuid::Uuid> {
+            TodoWidget::get_flattened(&list.borrow().tasks)
+                .iter()
+                .map(|task| task.borrow().id)
+                .collect()
+        };
+        assert_eq!(ids(after), ids(before));
+        assert_eq!(after.borrow().sort_rule, SortRule::Smart);
+    }
+}
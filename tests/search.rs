@@ -0,0 +1,148 @@
+//! Tests of the search and the filter, typed or given on the command line
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::{task, ui_with, workspace};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    cli::Cli,
+    ui::{Ui, todolistwidget::TodoList},
+};
+
+/// `Home` with `Paint`, open, and `Work` with `Plan` holding a folded `Ship release` subtask
+fn ui() -> Ui {
+    let (mut ui, _, _) = ui_with(vec![], vec![]);
+    let (home, work) = (workspace("Home"), workspace("Work"));
+    let mut home_list = TodoList::new(home.borrow().id);
+    home_list.add_task(task("Paint"));
+    let mut work_list = TodoList::new(work.borrow().id);
+    let plan = task("Plan");
+    plan.borrow_mut().add_child(task("Ship release"));
+    plan.borrow_mut().expanded = false;
+    work_list.add_task(plan);
+    let home_list = Rc::new(RefCell::new(home_list));
+    ui.todolist.add_list(home_list.clone());
+    ui.todolist.add_list(Rc::new(RefCell::new(work_list)));
+    ui.workspace.add_workspace(home.clone());
+    ui.workspace.add_workspace(work);
+    ui.workspace.select(Some(home));
+    ui.todolist.current_todolist = Some(home_list);
+    ui.workspace.focused = true;
+    ui
+}
+
+fn current_desc(ui: &Ui) -> Option<String> {
+    let list = ui.todolist.current_todolist.as_ref()?.borrow();
+    list.current_task
+        .as_ref()
+        .map(|task| task.borrow().desc.clone())
+}
+
+#[test]
+fn the_search_opens_the_first_match_of_any_workspace() {
+    let mut ui = ui();
+    assert_eq!(ui.search_tasks("release").len(), 1);
+
+    assert!(ui.start_search("release"));
+
+    assert_eq!(
+        ui.workspace
+            .current_workspace
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .desc,
+        "Work"
+    );
+    assert_eq!(current_desc(&ui).as_deref(), Some("Ship release"));
+    // the folded parent is opened so the match is shown
+    let list = ui.todolist.current_todolist.as_ref().unwrap().borrow();
+    assert!(list.tasks[0].borrow().expanded);
+    assert_eq!(list.state.selected(), Some(1));
+    assert!(ui.todolist.focused && !ui.workspace.focused);
+}
+
+#[test]
+fn a_search_without_matches_starts_as_usual() {
+    let mut ui = ui();
+
+    assert!(!ui.start_search("holiday"));
+
+    assert_eq!(ui.prompt.desc, "No Matches For 'holiday' !");
+    assert_eq!(
+        ui.workspace
+            .current_workspace
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .desc,
+        "Home"
+    );
+    assert!(ui.workspace.focused);
+}
+
+#[test]
+fn the_filter_applies_to_the_open_workspace() {
+    let mut ui = ui();
+
+    // the search finds it, but it's in another workspace
+    assert!(!ui.start_filter("release"));
+    assert_eq!(ui.prompt.desc, "No Matches For 'release' !");
+    assert!(ui.todolist.search_string.is_empty());
+
+    assert!(ui.start_filter("Paint"));
+    assert_eq!(ui.todolist.search_string, "Paint");
+    assert_eq!(current_desc(&ui).as_deref(), Some("Paint"));
+    assert!(ui.todolist.focused);
+}
+
+#[test]
+fn search_and_filter_take_the_words_to_look_for() {
+    let cli = Cli::parse(["--search".to_string(), "release".to_string()]).unwrap();
+    assert_eq!(cli.search.as_deref(), Some("release"));
+    assert_eq!(cli.filter, None);
+    let cli = Cli::parse(["--filter".to_string(), "a b".to_string()]).unwrap();
+    assert_eq!(cli.filter.as_deref(), Some("a b"));
+    assert!(Cli::parse(["--search".to_string()]).is_err());
+    assert!(Cli::parse(["--filter".to_string(), String::new()]).is_err());
+}
+
+#[test]
+fn a_filter_matching_nothing_says_so_and_the_title_counts_the_matches() {
+    let mut ui = ui();
+    ui.todolist.focused = true;
+    let screen = |ui: &mut Ui| -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|f| ui.update(f)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    };
+
+    assert!(!ui.todolist.apply_filter("release".to_string()));
+    assert!(
+        ui.todolist
+            .title()
+            .to_string()
+            .ends_with("% filtered: 0/1 ")
+    );
+    let shown = screen(&mut ui);
+    assert!(
+        shown.contains("No tasks match 'release' — press Esc to clear the filter"),
+        "{}",
+        shown
+    );
+
+    assert!(ui.todolist.apply_filter("Pai".to_string()));
+    assert!(ui.todolist.title().to_string().ends_with("filtered: 1/1 "));
+    assert!(!screen(&mut ui).contains("No tasks match"));
+
+    ui.todolist.apply_filter(String::new());
+    assert!(!ui.todolist.title().to_string().contains("filtered"));
+}
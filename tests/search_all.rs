@@ -0,0 +1,148 @@
+//! Tests of the search of every workspace, matching the tasks and the workspace names
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{code, key, task, ui_with, workspace};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    ui::{
+        SearchHit, Ui, UiMessage, WidgetAction, todolistwidget::TodoList,
+        workspacewidget::WorkspaceType,
+    },
+};
+use tokio::sync::mpsc;
+
+/// `Home` with `Plan the garden`, `Garden` with `Water`, `Projects` with a folded `Garden shed`
+/// subworkspace, and an archived `Old garden`; `Home` is open
+fn ui() -> (Ui, mpsc::Sender<UiMessage>, mpsc::Sender<KeyEvent>) {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let home = workspace("Home");
+    let garden = workspace("Garden");
+    let projects = workspace("Projects");
+    projects.borrow_mut().add_child(workspace("Garden shed"));
+    projects.borrow_mut().expanded = false;
+    let mut home_list = TodoList::new(home.borrow().id);
+    home_list.add_task(task("Plan the garden"));
+    let mut garden_list = TodoList::new(garden.borrow().id);
+    garden_list.add_task(task("Water"));
+    let home_list = Rc::new(RefCell::new(home_list));
+    ui.todolist.add_list(home_list.clone());
+    ui.todolist.add_list(Rc::new(RefCell::new(garden_list)));
+    for ws in [home.clone(), garden, projects] {
+        ui.workspace.add_workspace(ws);
+    }
+    ui.archived_ws.add_workspace(workspace("Old garden"));
+    ui.workspace.select(Some(home));
+    ui.todolist.current_todolist = Some(home_list);
+    ui.workspace.focused = true;
+    (ui, ui_tx, input_tx)
+}
+
+fn names(hits: &[SearchHit]) -> Vec<String> {
+    hits.iter()
+        .map(|hit| match hit {
+            SearchHit::Task(_, task) => format!("task {}", task.borrow().desc),
+            SearchHit::Workspace(WorkspaceType::Normal, ws) => format!("ws {}", ws.borrow().desc),
+            SearchHit::Workspace(WorkspaceType::Archived, ws) => {
+                format!("archived {}", ws.borrow().desc)
+            }
+        })
+        .collect()
+}
+
+fn open_list(ui: &Ui) -> Option<String> {
+    let id = ui.todolist.current_todolist.as_ref()?.borrow().workspace;
+    let ws = ui.workspace.current_workspace.clone();
+    ws.filter(|ws| ws.borrow().id == id)
+        .map(|ws| ws.borrow().desc.clone())
+}
+
+#[test]
+fn the_tasks_come_before_the_workspaces_and_prefix_matches_first() {
+    let (ui, _, _) = ui();
+    assert_eq!(
+        names(&ui.search_all("arden")),
+        [
+            "task Plan the garden",
+            "ws Garden",
+            "ws Garden shed",
+            "archived Old garden"
+        ]
+    );
+    assert_eq!(
+        names(&ui.search_all("Garden")),
+        ["ws Garden", "ws Garden shed"]
+    );
+    assert_eq!(names(&ui.search_all("Wat")), ["task Water"]);
+    assert!(ui.search_all("  ").is_empty());
+}
+
+#[test]
+fn a_workspace_found_is_opened_with_its_parents_expanded() {
+    let (mut ui, _, _) = ui();
+    let Some(SearchHit::Workspace(kind, shed)) = ui.search_all("shed").into_iter().next() else {
+        panic!("no workspace found");
+    };
+    assert!(ui.reveal_workspace(kind, &shed));
+    assert!(ui.workspace.workspaces[2].borrow().expanded);
+    assert!(ui.todolist.focused);
+    assert!(!ui.workspace.focused);
+    assert!(Rc::ptr_eq(
+        ui.workspace.current_workspace.as_ref().unwrap(),
+        &shed
+    ));
+
+    // not a workspace of the panel
+    assert!(!ui.reveal_workspace(WorkspaceType::Archived, &shed));
+}
+
+#[test]
+fn an_archived_workspace_found_shows_the_archived_panel() {
+    let (mut ui, _, _) = ui();
+    ui.archived_shown = Some(false);
+    let Some(SearchHit::Workspace(kind, old)) = ui.search_all("Old").into_iter().next() else {
+        panic!("no workspace found");
+    };
+    assert_eq!(kind, WorkspaceType::Archived);
+    assert!(ui.reveal_workspace(kind, &old));
+    assert_eq!(ui.archived_shown, Some(true));
+    assert!(Rc::ptr_eq(
+        ui.archived_ws.current_workspace.as_ref().unwrap(),
+        &old
+    ));
+}
+
+#[tokio::test]
+async fn the_result_picked_is_jumped_to() {
+    let (mut ui, ui_tx, input_tx) = ui();
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::SearchAll))
+        .await
+        .unwrap();
+    drop(ui_tx);
+    // "arden" finds a task and three workspaces, the second result is `Garden`
+    for c in "arden".chars() {
+        input_tx.send(key(c)).await.unwrap();
+    }
+    for c in [KeyCode::Down, KeyCode::Enter] {
+        input_tx.send(code(c)).await.unwrap();
+    }
+    drop(input_tx);
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+
+    assert_eq!(open_list(&ui).as_deref(), Some("Garden"));
+    assert_eq!(
+        appstate.lock().unwrap().current_focus,
+        CurrentFocus::TodoList
+    );
+}
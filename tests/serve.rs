@@ -0,0 +1,224 @@
+//! Tests of the stdio command mode
+
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use serde_json::Value;
+use todo::app::{
+    cli::Cli,
+    data::{self, Datas},
+    serve,
+    sources::{Clock, SystemClock},
+    ui::todolistwidget::Staleness,
+};
+
+fn data_file(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("todo-serve-{}-{}.json", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// Run requests against a data file, one response per request
+fn run(path: &Path, requests: &[&str]) -> Vec<Value> {
+    let mut output = Vec::new();
+    let input = requests.join("\n");
+    serve::serve(
+        path,
+        Staleness::new(&SystemClock, 0),
+        input.as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn added_tasks_are_saved_and_listed() {
+    let path = data_file("add");
+    data::save_data(&path, &data::demo_datas()).unwrap();
+
+    let responses = run(
+        &path,
+        &[
+            r#"{"cmd":"add_task","workspace":"Home","desc":"buy milk","due":"2025-07-01","id":1}"#,
+            r#"{"cmd":"add_task","desc":"call the bank"}"#,
+            r#"{"cmd":"list_tasks","workspace":"Home"}"#,
+        ],
+    );
+
+    assert_eq!(responses[0]["ok"], true);
+    assert_eq!(responses[0]["id"], 1);
+    // Home is found by its name alone, and answered with its path
+    assert_eq!(responses[0]["task"]["workspace"], "Work / Home");
+    assert_eq!(responses[0]["task"]["due"], "2025-07-01");
+    assert_eq!(responses[1]["task"]["workspace"], data::INBOX_NAME);
+    let listed = responses[2]["tasks"].as_array().unwrap();
+    assert!(listed.iter().all(|task| task["workspace"] == "Work / Home"));
+    assert!(listed.iter().any(|task| task["desc"] == "buy milk"));
+
+    let saved = data::load_data(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(
+        saved.todolist.todolists.len(),
+        data::demo_datas().todolist.todolists.len() + 1
+    );
+}
+
+#[test]
+fn due_dates_are_read_like_the_due_popup() {
+    let path = data_file("relative_due");
+    data::save_data(&path, &Datas::default()).unwrap();
+
+    let responses = run(
+        &path,
+        &[
+            r#"{"cmd":"add_task","desc":"a","due":"+3d"}"#,
+            r#"{"cmd":"add_task","desc":"b","due":"tomorrow"}"#,
+            r#"{"cmd":"add_task","desc":"c","due":"none"}"#,
+        ],
+    );
+    let _ = std::fs::remove_file(&path);
+
+    let today = SystemClock.today();
+    let day = |days| (today + chrono::Days::new(days)).to_string();
+    assert_eq!(responses[0]["task"]["due"], day(3));
+    assert_eq!(responses[1]["task"]["due"], day(1));
+    assert_eq!(responses[2]["task"]["due"], Value::Null);
+}
+
+#[test]
+fn completing_a_task_finishes_its_subtasks() {
+    let path = data_file("complete");
+    data::save_data(&path, &data::demo_datas()).unwrap();
+    let listed = run(
+        &path,
+        &[r#"{"cmd":"list_tasks","workspace":"Work / Backend"}"#],
+    );
+    let tasks = listed[0]["tasks"].as_array().unwrap().clone();
+    let parent = tasks
+        .iter()
+        .find(|task| tasks.iter().any(|child| child["parent"] == task["id"]))
+        .unwrap();
+
+    let request = format!(r#"{{"cmd":"complete_task","task":{}}}"#, parent["id"]);
+    let responses = run(&path, &[&request, r#"{"cmd":"list_tasks"}"#]);
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(responses[0]["task"]["status"], "Finished");
+    let children: Vec<&Value> = responses[1]["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|task| task["parent"] == parent["id"])
+        .collect();
+    assert!(!children.is_empty());
+    assert!(children.iter().all(|task| task["status"] == "Finished"));
+}
+
+#[test]
+fn query_uses_the_search_filter() {
+    let path = data_file("query");
+    data::save_data(&path, &data::demo_datas()).unwrap();
+
+    let responses = run(&path, &[r#"{"cmd":"query","filter":"release"}"#]);
+    let _ = std::fs::remove_file(&path);
+
+    let found = responses[0]["tasks"].as_array().unwrap();
+    assert!(!found.is_empty());
+    assert!(found.iter().any(|task| task["desc"] == "Ship release 1.2"));
+}
+
+#[test]
+fn failed_requests_answer_an_error_and_change_nothing() {
+    let path = data_file("errors");
+    data::save_data(&path, &Datas::default()).unwrap();
+    let before = std::fs::read_to_string(&path).unwrap();
+
+    let responses = run(
+        &path,
+        &[
+            "not json",
+            r#"{"cmd":"fly"}"#,
+            r#"{"cmd":"add_task","workspace":"Nowhere","desc":"x","id":"a"}"#,
+            r#"{"cmd":"add_task","desc":"  "}"#,
+            r#"{"cmd":"complete_task","task":"00000000-0000-0000-0000-000000000001"}"#,
+        ],
+    );
+    let after = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(responses.len(), 5);
+    assert!(responses.iter().all(|response| response["ok"] == false));
+    assert_eq!(responses[2]["id"], "a");
+    assert_eq!(responses[2]["error"], "no workspace named 'Nowhere'");
+    assert_eq!(before, after);
+}
+
+#[test]
+fn a_corrupt_data_file_answers_an_error_and_is_kept() {
+    let path = data_file("corrupt");
+    std::fs::write(&path, "{\"workspace\": [").unwrap();
+
+    let responses = run(
+        &path,
+        &[
+            r#"{"cmd":"list_tasks","id":1}"#,
+            r#"{"cmd":"add_task","desc":"x","id":2}"#,
+        ],
+    );
+    let after = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(responses.len(), 2);
+    assert!(responses.iter().all(|response| response["ok"] == false));
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[1]["error"], "failed to load the data");
+    assert_eq!(after, "{\"workspace\": [");
+}
+
+#[test]
+fn serve_subcommand_needs_stdio() {
+    let args = |args: &[&str]| Cli::parse(args.iter().map(|arg| arg.to_string()));
+
+    assert!(args(&["serve", "--stdio"]).unwrap().serve);
+    assert!(args(&["serve"]).is_err());
+    assert!(args(&["serve", "--tcp"]).is_err());
+}
+
+#[test]
+fn the_binary_answers_on_stdout() {
+    let path = data_file("binary");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_todo"))
+        .args(["serve", "--stdio"])
+        .env(data::DATA_PATH_ENV, &path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"{\"cmd\":\"add_task\",\"desc\":\"from a script\"}\n{\"cmd\":\"list_tasks\"}\n")
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert!(output.status.success());
+    let lines: Vec<Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["tasks"][0]["desc"], "from a script");
+}
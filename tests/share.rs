@@ -0,0 +1,116 @@
+//! Tests of copying a workspace as a message
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+use common::{ui_with, workspace};
+use todo::app::{
+    config::Config,
+    share,
+    sources::FixedClock,
+    ui::{
+        clipboard::osc52,
+        todolistwidget::{SortRule, Task, TaskStatus},
+    },
+};
+
+/// Wednesday
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 7, 2).unwrap()
+}
+
+fn task(desc: &str, status: TaskStatus, due: Option<u32>) -> Rc<RefCell<Task>> {
+    let due = due.map(|d| NaiveDate::from_ymd_opt(2025, 7, d).unwrap());
+    let mut task = Task::new(desc.to_string(), due);
+    task.status = status;
+    Rc::new(RefCell::new(task))
+}
+
+/// Pending, finished and deprecated tasks, due on every kind of day, with subtasks
+fn tasks() -> Vec<Rc<RefCell<Task>>> {
+    let report = task("Write the report", TaskStatus::InProcess, Some(4));
+    report
+        .borrow_mut()
+        .add_child(task("Collect the numbers", TaskStatus::Todo, Some(2)));
+    report
+        .borrow_mut()
+        .add_child(task("Proofread", TaskStatus::Finished, None));
+    let old = task("Old plan", TaskStatus::Deprecated, None);
+    old.borrow_mut()
+        .add_child(task("Left under a dropped task", TaskStatus::Todo, None));
+    vec![
+        report,
+        task("Shipped", TaskStatus::Finished, Some(1)),
+        old,
+        task("Call the bank", TaskStatus::Todo, Some(1)),
+        task("Book the trip", TaskStatus::Todo, Some(3)),
+        task("Renew the passport", TaskStatus::Todo, Some(20)),
+    ]
+}
+
+#[test]
+fn the_pending_tasks_are_written_as_a_checklist() {
+    let message = share::message("Work", &tasks(), SortRule::Manual, 0, today());
+
+    assert_eq!(
+        message,
+        "Work\n\
+         • Write the report (due Fri)\n  \
+         • Collect the numbers (due today)\n\
+         • Call the bank (overdue Jul 1)\n\
+         • Book the trip (due tomorrow)\n\
+         • Renew the passport (due Jul 20)"
+    );
+}
+
+#[test]
+fn a_long_list_is_cut_with_the_count_left() {
+    let message = share::message("Work", &tasks(), SortRule::Manual, 2, today());
+
+    assert_eq!(
+        message,
+        "Work\n\
+         • Write the report (due Fri)\n  \
+         • Collect the numbers (due today)\n\
+         …and 3 more"
+    );
+    assert_eq!(
+        share::message("Work", &tasks(), SortRule::Manual, 5, today()),
+        share::message("Work", &tasks(), SortRule::Manual, 0, today())
+    );
+}
+
+#[test]
+fn the_tasks_follow_the_order_of_the_list() {
+    let message = share::message("Work", &tasks(), SortRule::Name, 0, today());
+    let lines: Vec<&str> = message.lines().collect();
+    assert_eq!(lines[1], "• Book the trip (due tomorrow)");
+    assert_eq!(
+        share::message("Home", &[], SortRule::Manual, 0, today()),
+        "Home\nNothing pending"
+    );
+}
+
+#[test]
+fn the_ui_copies_the_list_of_the_workspace_with_the_configured_limit() {
+    let ws = workspace("Work");
+    let (mut ui, _, _) = ui_with(vec![ws.clone()], tasks());
+    ui.config.share_max_items = 1;
+
+    assert_eq!(
+        ui.share_message(&ws, &FixedClock(today())),
+        "Work\n• Write the report (due Fri)\n…and 4 more"
+    );
+    assert_eq!(Config::default().share_max_items, 20);
+}
+
+#[test]
+fn the_clipboard_gets_the_text_in_base64() {
+    assert_eq!(osc52(""), "\x1b]52;c;\x07");
+    assert_eq!(osc52("f"), "\x1b]52;c;Zg==\x07");
+    assert_eq!(osc52("foob"), "\x1b]52;c;Zm9vYg==\x07");
+    assert_eq!(osc52("foobar"), "\x1b]52;c;Zm9vYmFy\x07");
+    assert_eq!(osc52("• a"), "\x1b]52;c;4oCiIGE=\x07");
+}
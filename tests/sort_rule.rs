@@ -0,0 +1,254 @@
+//! Tests of the sort rules and the sort picker
+
+mod common;
+
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+
+use chrono::NaiveDate;
+use common::{code, key, ui_with};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::ui::{
+    todolistwidget::{SortRule, Task, TodoList, TodoWidget, Urgency},
+    tree::Jump,
+};
+use uuid::Uuid;
+
+fn list(descs: &[&str]) -> Rc<RefCell<TodoList>> {
+    let mut list = TodoList::new(Uuid::nil());
+    descs
+        .iter()
+        .for_each(|desc| list.add_task(Rc::new(RefCell::new(Task::new(desc.to_string(), None)))));
+    Rc::new(RefCell::new(list))
+}
+
+fn descs(list: &Rc<RefCell<TodoList>>) -> Vec<String> {
+    list.borrow()
+        .tasks
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+fn shown(list: &Rc<RefCell<TodoList>>) -> Vec<String> {
+    list.borrow()
+        .flattened()
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+#[test]
+fn every_rule_has_a_name_and_manual_keeps_the_order() {
+    let names: Vec<String> = SortRule::ALL.iter().map(|rule| rule.to_string()).collect();
+    assert_eq!(
+        names,
+        [
+            "smart",
+            "due date",
+            "status",
+            "urgency",
+            "name",
+            "creation date",
+            "manual"
+        ]
+    );
+    assert!(SortRule::Manual.compare().is_none());
+    assert_eq!(SortRule::default(), SortRule::Manual);
+}
+
+#[test]
+fn urgency_name_and_due_rules() {
+    let mut critical = Task::new("b".to_string(), None);
+    critical.urgency = Some(Urgency::Critical);
+    let mut common = Task::new("A".to_string(), NaiveDate::from_ymd_opt(2025, 1, 1));
+    common.urgency = Some(Urgency::Common);
+    let plain = Task::new("c".to_string(), None);
+
+    let urgency = SortRule::Urgency.compare().unwrap();
+    assert_eq!(urgency(&critical, &common), Ordering::Less);
+    assert_eq!(urgency(&common, &plain), Ordering::Less);
+    let name = SortRule::Name.compare().unwrap();
+    assert_eq!(name(&common, &critical), Ordering::Less);
+    let due = SortRule::Due.compare().unwrap();
+    assert_eq!(due(&common, &plain), Ordering::Less);
+}
+
+#[test]
+fn created_rule_puts_tasks_without_a_creation_time_last() {
+    let old = Task::new("old".to_string(), None);
+    let mut unknown = Task::new("unknown".to_string(), None);
+    unknown.created = None;
+
+    let created = SortRule::Created.compare().unwrap();
+    assert_eq!(created(&old, &unknown), Ordering::Less);
+}
+
+#[test]
+fn manual_order_comes_back_after_a_sort() {
+    let list = list(&["b", "a"]);
+    let parent = list.borrow().tasks[0].clone();
+    parent
+        .borrow_mut()
+        .add_child(Rc::new(RefCell::new(Task::new("y".to_string(), None))));
+    parent
+        .borrow_mut()
+        .add_child(Rc::new(RefCell::new(Task::new("x".to_string(), None))));
+
+    list.borrow_mut().set_sort_rule(SortRule::Name);
+    assert_eq!(shown(&list), ["a", "b", "x", "y"]);
+    assert_eq!(descs(&list), ["b", "a"]);
+
+    list.borrow_mut().set_sort_rule(SortRule::Manual);
+    assert_eq!(shown(&list), ["b", "y", "x", "a"]);
+}
+
+#[test]
+fn the_due_rule_sorts_every_level_keeping_ties_and_the_selection() {
+    let on = |day| NaiveDate::from_ymd_opt(2025, 7, day);
+    let task = |desc: &str, due| Rc::new(RefCell::new(Task::new(desc.to_string(), due)));
+    let list = list(&[]);
+    let later = task("later", on(9));
+    let undated = task("undated", None);
+    let tie = task("tie", on(3));
+    [
+        undated.clone(),
+        later.clone(),
+        task("sooner", on(3)),
+        tie.clone(),
+    ]
+    .into_iter()
+    .for_each(|task| list.borrow_mut().add_task(task));
+    later.borrow_mut().add_child(task("child none", None));
+    later.borrow_mut().add_child(task("child 8", on(8)));
+    later.borrow_mut().add_child(task("child 1", on(1)));
+    list.borrow_mut().select_task(&tie);
+
+    list.borrow_mut().set_sort_rule(SortRule::Due);
+
+    assert_eq!(
+        shown(&list),
+        [
+            "sooner",
+            "tie",
+            "later",
+            "child 1",
+            "child 8",
+            "child none",
+            "undated"
+        ]
+    );
+    assert_eq!(descs(&list), ["undated", "later", "sooner", "tie"]);
+    let list = list.borrow();
+    assert!(Rc::ptr_eq(list.current_task.as_ref().unwrap(), &tie));
+    assert_eq!(list.state.selected(), Some(1));
+}
+
+#[test]
+fn a_kept_sort_reorders_every_level_in_place() {
+    let on = |day| NaiveDate::from_ymd_opt(2025, 7, day);
+    let task = |desc: &str, due| Rc::new(RefCell::new(Task::new(desc.to_string(), due)));
+    let list = list(&[]);
+    let later = task("later", on(9));
+    let tie = task("tie", on(3));
+    [
+        task("undated", None),
+        later.clone(),
+        task("sooner", on(3)),
+        tie.clone(),
+    ]
+    .into_iter()
+    .for_each(|task| list.borrow_mut().add_task(task));
+    later.borrow_mut().add_child(task("child none", None));
+    later.borrow_mut().add_child(task("child 1", on(1)));
+    list.borrow_mut().select_task(&tie);
+
+    list.borrow_mut().keep_sorted(SortRule::Due);
+
+    assert_eq!(descs(&list), ["sooner", "tie", "later", "undated"]);
+    let children: Vec<String> = (later.borrow().children.iter())
+        .map(|task| task.borrow().desc.clone())
+        .collect();
+    assert_eq!(children, ["child 1", "child none"]);
+    let list = list.borrow();
+    assert_eq!(list.sort_rule, SortRule::Manual);
+    assert!(Rc::ptr_eq(list.current_task.as_ref().unwrap(), &tie));
+    assert_eq!(list.state.selected(), Some(1));
+}
+
+#[test]
+fn selection_follows_the_task_and_jumps_follow_the_view() {
+    let list = list(&["c", "a", "b"]);
+    let c = list.borrow().tasks[0].clone();
+    let mut widget = TodoWidget::new();
+    widget.current_todolist = Some(list.clone());
+    list.borrow_mut().current_task = Some(c.clone());
+    list.borrow_mut().state.select(Some(0));
+
+    list.borrow_mut().set_sort_rule(SortRule::Name);
+    assert_eq!(list.borrow().state.selected(), Some(2));
+
+    widget.jump(Jump::PrevSibling);
+    let selected = list.borrow().current_task.clone().unwrap();
+    assert_eq!(selected.borrow().desc, "b");
+    assert_eq!(list.borrow().state.selected(), Some(1));
+}
+
+#[tokio::test]
+async fn picker_sorts_the_tasks_in_place_on_enter() {
+    let (mut ui, _ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let list = list(&["b", "c", "a"]);
+    let c = list.borrow().tasks[1].clone();
+    list.borrow_mut().current_task = Some(c.clone());
+    list.borrow_mut().state.select(Some(1));
+    // the picker starts on the manual rule, the last one, and name is two rules up
+    for key in [KeyCode::Up, KeyCode::Up, KeyCode::Enter] {
+        input_tx.send(code(key)).await.unwrap();
+    }
+
+    let rx = ui.input_rx.clone();
+    let rule = ui.pick_sort(rx, &mut terminal, &list).await;
+
+    assert_eq!(rule, Some((SortRule::Name, true)));
+    assert_eq!(list.borrow().sort_rule, SortRule::Manual);
+    assert_eq!(descs(&list), ["a", "b", "c"]);
+    assert_eq!(shown(&list), ["a", "b", "c"]);
+    assert!(Rc::ptr_eq(list.borrow().current_task.as_ref().unwrap(), &c));
+    assert_eq!(list.borrow().state.selected(), Some(2));
+}
+
+#[tokio::test]
+async fn picker_reverts_the_preview_on_esc() {
+    let (mut ui, _ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let list = list(&["b", "c", "a"]);
+    for key in [KeyCode::Up, KeyCode::Up, KeyCode::Esc] {
+        input_tx.send(code(key)).await.unwrap();
+    }
+
+    let rx = ui.input_rx.clone();
+    let rule = ui.pick_sort(rx, &mut terminal, &list).await;
+
+    assert_eq!(rule, None);
+    assert_eq!(list.borrow().sort_rule, SortRule::Manual);
+    assert_eq!(shown(&list), ["b", "c", "a"]);
+}
+
+#[tokio::test]
+async fn picker_only_shows_the_rule_on_v() {
+    let (mut ui, _ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let list = list(&["b", "c", "a"]);
+    for key in [code(KeyCode::Up), code(KeyCode::Up), key('v')] {
+        input_tx.send(key).await.unwrap();
+    }
+
+    let rx = ui.input_rx.clone();
+    let rule = ui.pick_sort(rx, &mut terminal, &list).await;
+
+    assert_eq!(rule, Some((SortRule::Name, false)));
+    assert_eq!(list.borrow().sort_rule, SortRule::Name);
+    assert_eq!(shown(&list), ["a", "b", "c"]);
+    assert_eq!(descs(&list), ["b", "c", "a"]);
+}
@@ -0,0 +1,121 @@
+//! Tests of the stale tasks: the change time, the check, the filter and the count
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::{Duration, NaiveDate};
+use todo::app::{
+    sources::FixedClock,
+    ui::todolistwidget::{STALE_FILTER, Staleness, Task, TaskStatus, TodoWidget},
+};
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 6, 1).unwrap()
+}
+
+fn stale() -> Staleness {
+    Staleness::new(&FixedClock(today()), 30)
+}
+
+/// A task created some days before [`today`]
+fn task_aged(desc: &str, days: i64) -> Task {
+    let mut task = Task::new(desc.to_string(), None);
+    task.created = Some(
+        (today() - Duration::days(days))
+            .and_hms_opt(9, 0, 0)
+            .unwrap(),
+    );
+    task
+}
+
+#[test]
+fn open_tasks_become_stale_after_the_threshold() {
+    assert_eq!(stale().days(&task_aged("fresh", 29)), None);
+    assert_eq!(stale().days(&task_aged("old", 30)), Some(30));
+
+    let mut doing = task_aged("doing", 45);
+    doing.status = TaskStatus::InProcess;
+    assert_eq!(stale().days(&doing), Some(45));
+    for status in [TaskStatus::Finished, TaskStatus::Deprecated] {
+        let mut closed = task_aged("closed", 45);
+        closed.status = status;
+        assert_eq!(stale().days(&closed), None);
+    }
+}
+
+#[test]
+fn unknown_times_and_a_zero_threshold_are_never_stale() {
+    let mut unknown = task_aged("unknown", 45);
+    unknown.created = None;
+    assert_eq!(stale().days(&unknown), None);
+    assert_eq!(
+        Staleness::new(&FixedClock(today()), 0).days(&task_aged("old", 45)),
+        None
+    );
+}
+
+#[test]
+fn every_change_touches_the_task() {
+    let changes: [fn(&mut Task); 4] = [
+        |task| task.rename("renamed".to_string()),
+        |task| task.set_due(NaiveDate::from_ymd_opt(2030, 1, 1)),
+        |task| task.increase_urgency(),
+        |task| task.decrease_urgency(),
+    ];
+    for change in changes {
+        let mut task = task_aged("old", 400);
+        assert!(task.updated.is_none());
+        change(&mut task);
+        assert!(task.updated.is_some());
+        assert!(task.last_touched() > task.created);
+    }
+
+    let parent = Rc::new(RefCell::new(task_aged("parent", 400)));
+    let child = Rc::new(RefCell::new(task_aged("child", 400)));
+    parent.borrow_mut().add_child(child.clone());
+    Task::set_task_status(&parent, TaskStatus::Finished);
+    assert!(parent.borrow().updated.is_some());
+    assert!(child.borrow().updated.is_some());
+}
+
+#[test]
+fn stale_filter_keeps_the_stale_tasks_and_their_parents() {
+    let old = task_aged("write report", 45);
+    let fresh = task_aged("write docs", 1);
+    assert!(old.is_target(STALE_FILTER.to_string(), stale()));
+    assert!(!fresh.is_target(STALE_FILTER.to_string(), stale()));
+    assert!(old.is_target(format!("report {}", STALE_FILTER), stale()));
+    assert!(!old.is_target(format!("docs {}", STALE_FILTER), stale()));
+    assert!(fresh.is_target("docs".to_string(), stale()));
+
+    let mut parent = task_aged("project", 1);
+    parent.add_child(Rc::new(RefCell::new(old)));
+    assert!(parent.is_target(STALE_FILTER.to_string(), stale()));
+}
+
+#[test]
+fn stale_count_and_suffix() {
+    let mut parent = task_aged("project", 40);
+    parent.add_child(Rc::new(RefCell::new(task_aged("child", 50))));
+    parent.add_child(Rc::new(RefCell::new(task_aged("fresh", 2))));
+    let tasks = vec![
+        Rc::new(RefCell::new(parent)),
+        Rc::new(RefCell::new(task_aged("new", 0))),
+    ];
+    assert_eq!(stale().count(&tasks), 2);
+
+    let span = TodoWidget::stale_span(&tasks[0].borrow(), stale());
+    assert_eq!(span.content, " · stale 40d");
+    assert_eq!(
+        TodoWidget::stale_span(&tasks[1].borrow(), stale()).content,
+        ""
+    );
+}
+
+#[test]
+fn old_tasks_without_the_change_time_still_load() {
+    let json = r#"{"desc":"old","status":"Todo","expanded":true,"due":null,"children":[],
+        "id":"00000000-0000-0000-0000-000000000001","urgency":null}"#;
+    let task: Task = serde_json::from_str(json).unwrap();
+    assert!(task.updated.is_none());
+    assert!(task.created.is_none());
+}
@@ -0,0 +1,121 @@
+//! Tests of the translations of the user interface
+
+mod common;
+
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use common::ui_with;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    config::Config,
+    ui::{
+        UiMessage, WidgetAction,
+        strings::{self, Messages},
+    },
+};
+
+fn shipped() -> Messages {
+    Messages::load(Path::new("lang/zh.json")).unwrap()
+}
+
+/// Every string literal of the sources
+fn literals() -> String {
+    let mut sources = String::new();
+    let mut dirs = vec![Path::new("src").to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                sources.push_str(&fs::read_to_string(path).unwrap());
+            }
+        }
+    }
+    sources
+}
+
+#[test]
+fn a_missing_string_falls_back_to_english() {
+    let messages = shipped();
+    assert_eq!(messages.get("Workspace Added !"), "工作区已添加 !");
+    assert_eq!(messages.get("No Such String !"), "No Such String !");
+    assert_eq!(
+        Messages::english().format("Triage Done, {} Left !", &[&2]),
+        "Triage Done, 2 Left !"
+    );
+}
+
+#[test]
+fn the_placeholders_are_filled_in_order() {
+    let mut messages = Messages::english();
+    messages
+        .translations
+        .insert("{} of {}".to_string(), "{} / {}".to_string());
+    messages
+        .translations
+        .insert("{} !".to_string(), "{} {} !".to_string());
+
+    assert_eq!(messages.format("{} of {}", &[&1, &"2"]), "1 / 2");
+    // a placeholder too many is left, a value too many is dropped
+    assert_eq!(messages.format("{} !", &[&1]), "1 {} !");
+    assert_eq!(messages.format("Done", &[&1]), "Done");
+}
+
+#[test]
+fn a_broken_file_is_refused() {
+    let path = std::env::temp_dir().join(format!("todo-lang-{}.json", std::process::id()));
+    fs::write(&path, r#"{"Task Added !": 3}"#).unwrap();
+    assert!(Messages::load(&path).is_err());
+    fs::remove_file(&path).unwrap();
+    assert!(Messages::load(&path).is_err());
+}
+
+#[test]
+fn the_shipped_translation_only_translates_strings_of_the_code() {
+    let sources = literals();
+    for (english, translated) in shipped().translations.iter() {
+        assert!(
+            sources.contains(&format!("{:?}", english)),
+            "{:?} isn't a string of the code",
+            english
+        );
+        assert_eq!(
+            english.matches("{}").count(),
+            translated.matches("{}").count(),
+            "{:?}",
+            english
+        );
+    }
+}
+
+#[test]
+fn the_language_is_english_unless_configured() {
+    assert_eq!(Config::default().language, None);
+    let config: Config = serde_json::from_str(r#"{"language": "zh"}"#).unwrap();
+    assert_eq!(config.language.as_deref(), Some("zh"));
+}
+
+#[tokio::test]
+async fn the_prompt_shows_the_selected_translation() {
+    strings::select(shipped());
+    let (mut ui, ui_tx, _input_tx) = ui_with(vec![], vec![]);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::Sort))
+        .await
+        .unwrap();
+    drop(ui_tx);
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+
+    assert_eq!(ui.prompt.desc, "没有打开的任务列表, 请进入一个工作区 !");
+    assert_eq!(strings::tr("quit"), "退出");
+    assert_eq!(strings::tr("replay"), "replay");
+}
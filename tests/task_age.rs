@@ -0,0 +1,136 @@
+//! Tests of the age column of the task list
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{List, Widget},
+};
+use todo::app::{
+    config::Config,
+    sources::FixedClock,
+    ui::{
+        glyphs,
+        todolistwidget::{AGE_WIDTH, RowOptions, SortRule, Task, TaskStatus, TodoWidget},
+    },
+};
+
+fn day(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+}
+
+fn created(m: u32, d: u32) -> Option<NaiveDateTime> {
+    day(m, d).and_hms_opt(18, 30, 0)
+}
+
+fn task(desc: &str, status: TaskStatus, m: u32, d: u32) -> Rc<RefCell<Task>> {
+    let mut task = Task::new(desc.to_string(), Some(day(7, 10)));
+    task.status = status;
+    task.created = created(m, d);
+    Rc::new(RefCell::new(task))
+}
+
+#[test]
+fn the_age_goes_from_days_to_weeks_to_years() {
+    let today = day(7, 2);
+    let age = |m: u32, d: u32| {
+        let task = task("t", TaskStatus::Todo, m, d);
+        TodoWidget::age_span(&task.borrow(), today)
+            .content
+            .to_string()
+    };
+    assert_eq!(age(7, 2), "    ");
+    assert_eq!(age(7, 1), "  1d");
+    assert_eq!(age(6, 26), "  6d");
+    assert_eq!(age(6, 25), "  1w");
+    assert_eq!(age(1, 2), " 25w");
+    let old = Task {
+        created: NaiveDate::from_ymd_opt(2023, 7, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0),
+        ..Task::new("old".to_string(), None)
+    };
+    assert_eq!(TodoWidget::age_span(&old, today).content, "  2y");
+}
+
+#[test]
+fn closed_and_undated_tasks_get_blank_cells() {
+    let today = day(7, 2);
+    let finished = task("t", TaskStatus::Finished, 6, 1);
+    let deprecated = task("t", TaskStatus::Deprecated, 6, 1);
+    let legacy = task("t", TaskStatus::InProcess, 6, 1);
+    legacy.borrow_mut().created = None;
+    for task in [finished, deprecated, legacy] {
+        let span = TodoWidget::age_span(&task.borrow(), today);
+        assert_eq!(span.content, " ".repeat(AGE_WIDTH));
+    }
+}
+
+/// Render the rows of the tasks and return them as text
+fn rows(tasks: &[Rc<RefCell<Task>>], options: RowOptions) -> Vec<String> {
+    let max_len = TodoWidget::find_max_tasks_len(tasks, 1);
+    let items = TodoWidget::get_task_list_item(
+        tasks,
+        SortRule::Manual,
+        0,
+        max_len,
+        &FixedClock(day(7, 2)),
+        options,
+        None,
+    );
+    let area = Rect::new(0, 0, 60, tasks.len() as u16);
+    let mut buf = Buffer::empty(area);
+    List::new(items).render(area, &mut buf);
+    (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn the_age_column_sits_before_the_aligned_due_dates() {
+    glyphs::select(true);
+    let tasks = [
+        task("Write", TaskStatus::Todo, 6, 29),
+        task("Review the draft", TaskStatus::InProcess, 5, 1),
+        task("Ship", TaskStatus::Finished, 1, 1),
+    ];
+
+    let on = rows(
+        &tasks,
+        RowOptions {
+            show_age: true,
+            ..Default::default()
+        },
+    );
+    let off = rows(&tasks, RowOptions::default());
+
+    assert!(on[0].contains("   3d     8 day left"), "{:?}", on);
+    assert!(on[1].contains("   8w     8 day left"), "{:?}", on);
+    let due_column = |rows: &[String]| -> Vec<usize> {
+        rows[..2]
+            .iter()
+            .map(|row| row.find("8 day").unwrap())
+            .collect()
+    };
+    assert_eq!(due_column(&on)[0], due_column(&on)[1]);
+    assert_eq!(due_column(&on)[0], due_column(&off)[0] + AGE_WIDTH);
+    assert!(
+        !off.iter()
+            .any(|row| row.contains("3d") || row.contains("8w"))
+    );
+}
+
+#[test]
+fn the_age_is_shown_unless_configured_off() {
+    assert!(Config::default().show_task_age);
+    let config: Config = serde_json::from_str(r#"{"show_task_age": false}"#).unwrap();
+    assert!(!config.show_task_age);
+}
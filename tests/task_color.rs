@@ -0,0 +1,129 @@
+//! Tests of the color picked for a task
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use common::{code, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    style::{Color, Modifier, Style},
+};
+use todo::app::{
+    appstate::AppState,
+    ui::{
+        UiMessage, WidgetAction,
+        todolistwidget::{TASK_COLORS, Task, TaskStatus, TodoWidget, Urgency},
+    },
+};
+
+fn task(status: TaskStatus, color: Option<&str>) -> Task {
+    let mut task = Task::new("task".to_string(), None);
+    task.status = status;
+    task.urgency = Some(Urgency::Critical);
+    task.color = color.map(str::to_string);
+    task
+}
+
+#[test]
+fn the_status_comes_before_the_color() {
+    assert_eq!(
+        TodoWidget::desc_style(&task(TaskStatus::Finished, Some("magenta"))),
+        Style::new().fg(Color::LightGreen)
+    );
+    assert_eq!(
+        TodoWidget::desc_style(&task(TaskStatus::Deprecated, Some("magenta"))),
+        Style::new()
+            .add_modifier(Modifier::CROSSED_OUT)
+            .fg(Color::Red)
+    );
+}
+
+#[test]
+fn the_color_comes_before_the_urgency_and_the_default() {
+    assert_eq!(
+        TodoWidget::desc_style(&task(TaskStatus::Todo, Some("magenta"))),
+        Style::new().fg(Color::Magenta)
+    );
+    assert_eq!(
+        TodoWidget::desc_style(&task(TaskStatus::InProcess, Some("cyan"))),
+        Style::new().fg(Color::Cyan)
+    );
+    // the urgency has its own column
+    assert_eq!(
+        TodoWidget::desc_style(&task(TaskStatus::Todo, None)),
+        Style::default()
+    );
+    // e.g. typed into the data file by hand
+    assert_eq!(
+        TodoWidget::desc_style(&task(TaskStatus::Todo, Some("sparkly"))),
+        Style::default()
+    );
+    assert!(
+        TASK_COLORS
+            .iter()
+            .all(|color| color.parse::<Color>().is_ok())
+    );
+}
+
+#[test]
+fn the_color_is_saved_and_old_saves_have_none() {
+    let saved = serde_json::to_string(&task(TaskStatus::Todo, Some("red"))).unwrap();
+    let loaded: Task = serde_json::from_str(&saved).unwrap();
+    assert_eq!(loaded.color.as_deref(), Some("red"));
+
+    let old = saved.replace(",\"color\":\"red\"", "");
+    assert!(!old.contains("color"));
+    let loaded: Task = serde_json::from_str(&old).unwrap();
+    assert_eq!(loaded.color, None);
+}
+
+/// Pick a color for a selected task with the keys, returning the color and the prompt
+async fn pick(color: Option<&str>, keys: &[KeyCode]) -> (Option<String>, String) {
+    let task = Rc::new(RefCell::new(task(TaskStatus::Todo, color)));
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![workspace("ws")], vec![task.clone()]);
+
+    for c in keys {
+        input_tx.send(code(*c)).await.unwrap();
+    }
+    drop(input_tx);
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::TaskColor))
+        .await
+        .unwrap();
+    drop(ui_tx);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+
+    let color = task.borrow().color.clone();
+    (color, ui.prompt.desc.clone())
+}
+
+#[tokio::test]
+async fn the_picker_sets_clears_or_keeps_the_color() {
+    // the picker opens on the color of the task, below the choice which clears it
+    let (color, prompt) = pick(None, &[KeyCode::Char('j'), KeyCode::Enter]).await;
+    assert_eq!(
+        (color.as_deref(), prompt.as_str()),
+        (Some("red"), "Colored red !")
+    );
+
+    let (color, _) = pick(Some("red"), &[KeyCode::Char('j'), KeyCode::Enter]).await;
+    assert_eq!(color.as_deref(), Some("green"));
+
+    let (color, prompt) = pick(Some("red"), &[KeyCode::Char('k'), KeyCode::Enter]).await;
+    assert_eq!((color, prompt.as_str()), (None, "Color Cleared !"));
+
+    let (color, prompt) = pick(Some("blue"), &[KeyCode::Char('j'), KeyCode::Esc]).await;
+    assert_eq!(
+        (color.as_deref(), prompt.as_str()),
+        (Some("blue"), "Color Unchanged !")
+    );
+}
@@ -0,0 +1,188 @@
+//! Tests of the actions which change the selected task after a popup
+//!
+//! The task is found again by its id once the popup is closed, and only borrowed while it is
+//! changed, so a subtask deep in the tree can be deleted, renamed or given a due date while
+//! it's selected.
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use chrono::NaiveDate;
+use common::{code, key, task, ui_with, workspace};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentFocus},
+    data::{self, Datas},
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{Task, TaskStatus, TodoList, Urgency},
+    },
+};
+use tokio::sync::mpsc;
+
+/// A UI on `Plan` > `Draft` > `Outline` with `Outline` selected, and `Ship` after `Plan`
+fn ui() -> (Ui, Rc<RefCell<TodoList>>, Rc<RefCell<Task>>) {
+    let (plan, draft, outline) = (task("Plan"), task("Draft"), task("Outline"));
+    draft.borrow_mut().add_child(outline.clone());
+    plan.borrow_mut().add_child(draft);
+    let (ui, _, _) = ui_with(vec![workspace("Work")], vec![plan, task("Ship")]);
+    let list = ui.todolist.current_todolist.clone().unwrap();
+    list.borrow_mut().select_task(&outline);
+    (ui, list, outline)
+}
+
+/// Run an action with the text typed into its popup, then Enter
+async fn run(ui: &mut Ui, action: WidgetAction, typed: &str) {
+    let (ui_tx, ui_rx) = mpsc::channel(1);
+    let (input_tx, input_rx) = mpsc::channel(64);
+    ui.ui_rx = ui_rx;
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    for c in typed.chars() {
+        input_tx.send(key(c)).await.unwrap();
+    }
+    input_tx.send(code(KeyCode::Enter)).await.unwrap();
+    drop(input_tx);
+    ui_tx.send(UiMessage::WAction(action)).await.unwrap();
+    drop(ui_tx);
+    let mut appstate = AppState::new();
+    appstate.current_focus = CurrentFocus::TodoList;
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(appstate)))
+        .await;
+}
+
+fn descs(list: &Rc<RefCell<TodoList>>) -> Vec<String> {
+    list.borrow()
+        .flattened()
+        .iter()
+        .map(|task| task.borrow().desc.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn the_selected_grandchild_is_deleted() {
+    let (mut ui, list, _) = ui();
+
+    run(&mut ui, WidgetAction::DeleteTask, "y").await;
+
+    assert_eq!(descs(&list), ["Plan", "Draft", "Ship"]);
+    assert!(list.borrow().current_task.is_none());
+    assert_eq!(ui.prompt.desc, "Task Deleted !");
+}
+
+#[tokio::test]
+async fn a_parent_is_deleted_with_its_subtasks_once_confirmed_twice() {
+    let (mut ui, list, _) = ui();
+    let plan = list.borrow().tasks[0].clone();
+    list.borrow_mut().select_task(&plan);
+
+    run(&mut ui, WidgetAction::DeleteTask, "yy").await;
+
+    assert_eq!(descs(&list), ["Ship"]);
+    assert!(list.borrow().current_task.is_none());
+}
+
+#[tokio::test]
+async fn a_parent_is_deprecated_with_its_open_subtasks_once_confirmed() {
+    let (mut ui, list, outline) = ui();
+    let plan = list.borrow().tasks[0].clone();
+    list.borrow_mut().select_task(&plan);
+    let deprecate = || WidgetAction::MarkTaskStatus(TaskStatus::Deprecated);
+
+    run(&mut ui, deprecate(), "n").await;
+    assert_eq!(plan.borrow().status, TaskStatus::Todo);
+    assert_eq!(outline.borrow().status, TaskStatus::Todo);
+
+    run(&mut ui, deprecate(), "y").await;
+    assert_eq!(plan.borrow().status, TaskStatus::Deprecated);
+    assert_eq!(outline.borrow().status, TaskStatus::Deprecated);
+}
+
+#[tokio::test]
+async fn the_selected_grandchild_is_renamed() {
+    let (mut ui, list, outline) = ui();
+
+    run(
+        &mut ui,
+        WidgetAction::Rename(CurrentFocus::TodoList),
+        "Notes",
+    )
+    .await;
+
+    assert_eq!(outline.borrow().desc, "Notes");
+    assert!(Rc::ptr_eq(
+        list.borrow().current_task.as_ref().unwrap(),
+        &outline
+    ));
+}
+
+#[tokio::test]
+async fn the_selected_grandchild_gets_a_due_date() {
+    let (mut ui, _, outline) = ui();
+
+    run(&mut ui, WidgetAction::Due, "2025-07-01").await;
+    assert_eq!(outline.borrow().due, NaiveDate::from_ymd_opt(2025, 7, 1));
+
+    run(&mut ui, WidgetAction::DueInline, "none").await;
+    assert_eq!(outline.borrow().due, None);
+    assert_eq!(ui.prompt.desc, "Set Due Date !");
+}
+
+#[tokio::test]
+async fn the_urgency_of_the_selected_grandchild_is_raised_and_saved() {
+    let (mut ui, _, outline) = ui();
+    outline.borrow_mut().urgency = Some(Urgency::Common);
+
+    run(&mut ui, WidgetAction::IncreaseUrgency, "").await;
+    run(&mut ui, WidgetAction::IncreaseUrgency, "").await;
+    assert_eq!(outline.borrow().urgency, Some(Urgency::Critical));
+    // Critical is the top, raising it again keeps it there
+    run(&mut ui, WidgetAction::IncreaseUrgency, "").await;
+    assert_eq!(outline.borrow().urgency, Some(Urgency::Critical));
+    run(&mut ui, WidgetAction::DecreaseUrgency, "").await;
+    assert_eq!(outline.borrow().urgency, Some(Urgency::Important));
+
+    let path = std::env::temp_dir().join(format!(
+        "todo-task-edits-urgency-{}-{}.json",
+        std::process::id(),
+        uuid::Uuid::new_v4()
+    ));
+    let datas = Datas {
+        workspace: ui.workspace.clone(),
+        todolist: ui.todolist.clone(),
+        archived_ws: ui.archived_ws.clone(),
+    };
+    data::save_data(&path, &datas).unwrap();
+    let loaded = data::load_data(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(data::archive_path(&path));
+
+    let list = loaded.todolist.todolists[0].borrow();
+    let tasks = list.flattened();
+    let saved = tasks
+        .iter()
+        .find(|task| task.borrow().id == outline.borrow().id);
+    assert_eq!(saved.unwrap().borrow().urgency, Some(Urgency::Important));
+}
+
+#[test]
+fn edit_task_works_while_an_ancestor_is_borrowed() {
+    let (_, list, outline) = ui();
+    let id = outline.borrow().id;
+    let plan = list.borrow().tasks[0].clone();
+    let _plan = plan.borrow();
+
+    let list = list.borrow();
+    assert_eq!(
+        list.edit_task(id, |task| task.rename("Notes".to_string())),
+        Some(())
+    );
+    assert_eq!(outline.borrow().desc, "Notes");
+    assert!(list.edit_task(uuid::Uuid::nil(), |_| ()).is_none());
+}
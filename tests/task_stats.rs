@@ -0,0 +1,117 @@
+//! Tests of the task counts behind the count badges
+
+use std::{cell::RefCell, rc::Rc};
+
+use ratatui::style::{Color, Stylize};
+use todo::app::ui::todolistwidget::{Task, TaskStats, TaskStatus, TodoWidget};
+
+fn task(status: TaskStatus, children: Vec<Rc<RefCell<Task>>>) -> Rc<RefCell<Task>> {
+    let mut task = Task::new("task".to_string(), None);
+    task.status = status;
+    children.into_iter().for_each(|child| task.add_child(child));
+    Rc::new(RefCell::new(task))
+}
+
+#[test]
+fn stats_count_the_whole_tree_without_deprecated() {
+    let tree = vec![
+        task(
+            TaskStatus::Todo,
+            vec![
+                task(
+                    TaskStatus::Finished,
+                    vec![task(TaskStatus::Finished, vec![])],
+                ),
+                task(TaskStatus::Deprecated, vec![]),
+            ],
+        ),
+        task(TaskStatus::InProcess, vec![]),
+    ];
+
+    assert_eq!(
+        TaskStats::of(&tree),
+        TaskStats {
+            finished: 2,
+            total: 4
+        }
+    );
+    assert!(!TaskStats::of(&tree).is_complete());
+    assert!(!TaskStats::default().is_complete());
+}
+
+#[test]
+fn badge_counts_subtasks_and_turns_green_when_done() {
+    let leaf = task(TaskStatus::Todo, vec![]);
+    assert_eq!(TodoWidget::count_badge(&leaf.borrow()).content, "");
+
+    let parent = task(
+        TaskStatus::Todo,
+        vec![
+            task(TaskStatus::Finished, vec![]),
+            task(TaskStatus::Todo, vec![]),
+        ],
+    );
+    let badge = TodoWidget::count_badge(&parent.borrow());
+    assert_eq!(badge.content, " (1/2)");
+    assert_ne!(badge.style.fg, Some(Color::LightGreen));
+
+    parent.borrow().children[1].borrow_mut().status = TaskStatus::Finished;
+    let badge = TodoWidget::count_badge(&parent.borrow());
+    assert_eq!(badge.content, " (2/2)");
+    assert_eq!(badge, " (2/2)".light_green());
+}
+
+#[test]
+fn title_shows_the_progress_of_the_list() {
+    use todo::app::ui::{glyphs, todolistwidget::TodoList, workspacewidget::Workspace};
+
+    glyphs::select(true);
+    let ws = Rc::new(RefCell::new(Workspace::new("ws".to_string())));
+    let list = Rc::new(RefCell::new(TodoList::new(ws.borrow().id)));
+    let mut widget = TodoWidget::new();
+    widget.add_list(list.clone());
+    widget.change_current_list(&Some(ws));
+    let title = |widget: &TodoWidget| widget.title().to_string();
+
+    assert_eq!(title(&widget), " <3> Todo List ");
+
+    list.borrow_mut()
+        .add_task(task(TaskStatus::Finished, vec![]));
+    list.borrow_mut()
+        .add_task(task(TaskStatus::Deprecated, vec![]));
+    list.borrow_mut().add_task(task(
+        TaskStatus::Todo,
+        vec![task(TaskStatus::Finished, vec![])],
+    ));
+    assert_eq!(title(&widget), " <3> Todo List ###-- 66% ");
+
+    list.borrow().tasks[2].borrow_mut().status = TaskStatus::Finished;
+    assert_eq!(title(&widget), " <3> Todo List ##### 100% ");
+}
+
+#[test]
+fn a_deprecated_task_leaves_its_subtree_out() {
+    let tree = vec![
+        task(
+            TaskStatus::Deprecated,
+            vec![task(
+                TaskStatus::Todo,
+                vec![task(TaskStatus::InProcess, vec![])],
+            )],
+        ),
+        task(TaskStatus::Todo, vec![task(TaskStatus::Finished, vec![])]),
+    ];
+
+    assert_eq!(
+        TaskStats::of(&tree),
+        TaskStats {
+            finished: 1,
+            total: 2
+        }
+    );
+    assert_eq!(TaskStats::of(&tree).open(), 1);
+    let open = TaskStats::open_tasks(&tree);
+    assert_eq!(open.len(), 1);
+    assert!(Rc::ptr_eq(&open[0], &tree[1]));
+    assert_eq!(TodoWidget::count_badge(&tree[0].borrow()).content, "");
+}
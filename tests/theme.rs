@@ -0,0 +1,117 @@
+//! Tests of the colors adapted to the terminal
+
+mod common;
+
+use common::{task, ui_with, workspace};
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    buffer::Cell,
+    style::{Color, Modifier},
+};
+use todo::app::{
+    config::Config,
+    ui::theme::{ColorSupport, ThemedBackend},
+};
+
+fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+    move |name| {
+        vars.iter()
+            .find(|(var, _)| *var == name)
+            .map(|(_, value)| value.to_string())
+    }
+}
+
+fn cell(fg: Color, bg: Color) -> Cell {
+    let mut cell = Cell::new("x");
+    cell.set_fg(fg).set_bg(bg);
+    cell
+}
+
+#[test]
+fn the_support_is_detected_from_the_environment() {
+    let detect = |vars: &[(&str, &str)]| ColorSupport::detect(env(vars));
+    let xterm = ("TERM", "xterm-256color");
+
+    assert_eq!(detect(&[xterm]), ColorSupport::Ansi256);
+    assert_eq!(
+        detect(&[xterm, ("COLORTERM", "truecolor")]),
+        ColorSupport::TrueColor
+    );
+    assert_eq!(detect(&[("TERM", "linux")]), ColorSupport::Ansi16);
+    assert_eq!(detect(&[("TERM", "dumb")]), ColorSupport::NoColor);
+    // NO_COLOR wins, unless it's empty
+    assert_eq!(
+        detect(&[xterm, ("COLORTERM", "24bit"), ("NO_COLOR", "1")]),
+        ColorSupport::NoColor
+    );
+    assert_eq!(detect(&[xterm, ("NO_COLOR", "")]), ColorSupport::Ansi256);
+}
+
+#[test]
+fn the_config_overrides_the_detection() {
+    assert_eq!(Config::default().color, None);
+    let config: Config = serde_json::from_str(r#"{"color": "16"}"#).unwrap();
+    assert_eq!(config.color, Some(ColorSupport::Ansi16));
+    let config: Config = serde_json::from_str(r#"{"color": "none"}"#).unwrap();
+    assert_eq!(config.color, Some(ColorSupport::NoColor));
+}
+
+#[test]
+fn rgb_colors_become_the_closest_the_terminal_shows() {
+    let selection = cell(Color::White, Color::Rgb(66, 80, 102));
+
+    let adapted = ColorSupport::Ansi256.adapt(&selection);
+    assert_eq!(adapted.fg, Color::White);
+    assert_eq!(adapted.bg, Color::Indexed(239));
+
+    // a background is one of the 8 normal colors, never black for a colored one
+    let adapted = ColorSupport::Ansi16.adapt(&selection);
+    assert_eq!(adapted.fg, Color::White);
+    assert_eq!(adapted.bg, Color::Blue);
+    let adapted = ColorSupport::Ansi16.adapt(&cell(Color::Rgb(250, 10, 10), Color::Reset));
+    assert_eq!(adapted.fg, Color::LightRed);
+    assert_eq!(adapted.bg, Color::Reset);
+
+    assert_eq!(ColorSupport::TrueColor.adapt(&selection), selection);
+}
+
+#[test]
+fn without_color_the_selection_is_reversed() {
+    let adapted = ColorSupport::NoColor.adapt(&cell(Color::Green, Color::Rgb(66, 80, 102)));
+    assert_eq!((adapted.fg, adapted.bg), (Color::Reset, Color::Reset));
+    assert!(adapted.modifier.contains(Modifier::REVERSED));
+
+    let adapted = ColorSupport::NoColor.adapt(&cell(Color::Green, Color::Reset));
+    assert_eq!(adapted.fg, Color::Reset);
+    assert!(!adapted.modifier.contains(Modifier::REVERSED));
+}
+
+#[test]
+fn the_backend_draws_the_adapted_colors() {
+    let (mut ui, _, _) = ui_with(vec![workspace("Work")], vec![task("Write")]);
+
+    let mut backend = ThemedBackend::new(TestBackend::new(100, 20));
+    backend.support = ColorSupport::NoColor;
+    let mut terminal = Terminal::new(backend).unwrap();
+    terminal.draw(|f| ui.update(f)).unwrap();
+
+    let buffer = terminal.backend().inner.buffer();
+    assert!(
+        buffer
+            .content()
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+    );
+    let (x, y) = buffer
+        .content()
+        .chunks(100)
+        .enumerate()
+        .find_map(|(y, row)| {
+            let line: String = row.iter().map(Cell::symbol).collect();
+            line.find("Write")
+                .map(|x| (line[..x].chars().count() as u16, y as u16))
+        })
+        .unwrap();
+    assert!(buffer[(x, y)].modifier.contains(Modifier::REVERSED));
+}
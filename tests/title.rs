@@ -0,0 +1,91 @@
+//! Tests of the title of the terminal window
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::{day, task_due, ui_with, workspace};
+use todo::app::{
+    config::Config,
+    sources::FixedClock,
+    ui::{
+        Ui,
+        todolistwidget::{TaskStatus, TodoList},
+        workspacewidget::Workspace,
+    },
+};
+
+/// `Work` with a `Backend` child holding three tasks due on the 3rd, one of them finished,
+/// `Home` with one due on the 3rd and one on the 4th, and an archived workspace with one
+/// due on the 3rd
+fn ui() -> (Ui, Rc<RefCell<Workspace>>) {
+    let (mut ui, _, _) = ui_with(vec![], vec![]);
+    let (work, backend) = (workspace("Work"), workspace("Backend"));
+    let (home, old) = (workspace("Home"), workspace("Old"));
+    work.borrow_mut().add_child(backend.clone());
+
+    let lists = [
+        (
+            &backend,
+            vec![
+                ("Deploy", TaskStatus::Todo, 3),
+                ("Tag", TaskStatus::InProcess, 3),
+            ],
+        ),
+        (&backend, vec![("Notes", TaskStatus::Finished, 3)]),
+        (
+            &home,
+            vec![
+                ("Groceries", TaskStatus::Todo, 3),
+                ("Bills", TaskStatus::Todo, 4),
+            ],
+        ),
+        (&old, vec![("Forgotten", TaskStatus::Todo, 3)]),
+    ];
+    for (ws, tasks) in lists {
+        let list = match ui.todolist.list_of(ws.borrow().id) {
+            Some(list) => list,
+            None => {
+                let list = Rc::new(RefCell::new(TodoList::new(ws.borrow().id)));
+                ui.todolist.add_list(list.clone());
+                list
+            }
+        };
+        for (desc, status, due) in tasks {
+            list.borrow_mut().add_task(task_due(desc, status, due));
+        }
+    }
+    ui.workspace.add_workspace(work);
+    ui.workspace.add_workspace(home);
+    ui.archived_ws.add_workspace(old);
+    (ui, backend)
+}
+
+#[test]
+fn the_title_counts_the_open_tasks_due_today() {
+    let (ui, _) = ui();
+
+    assert_eq!(ui.terminal_title(&FixedClock(day(3))), "todo (3 due today)");
+    assert_eq!(ui.terminal_title(&FixedClock(day(4))), "todo (1 due today)");
+    assert_eq!(ui.terminal_title(&FixedClock(day(5))), "todo");
+}
+
+#[test]
+fn the_title_names_the_open_workspace_with_its_ancestors() {
+    let (mut ui, backend) = ui();
+    ui.workspace.select(Some(backend));
+    ui.todolist
+        .change_current_list(&ui.workspace.current_workspace.clone());
+
+    assert_eq!(
+        ui.terminal_title(&FixedClock(day(3))),
+        "todo — Work/Backend (3 due today)"
+    );
+}
+
+#[test]
+fn the_title_is_on_unless_configured() {
+    assert!(Config::default().terminal_title);
+    let config: Config = serde_json::from_str(r#"{"terminal_title": false}"#).unwrap();
+    assert!(!config.terminal_title);
+}
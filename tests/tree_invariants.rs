@@ -0,0 +1,176 @@
+//! Property tests for the recursive delete and flatten functions
+//!
+//! Random nested trees of tasks and workspaces are generated, a random node is deleted and
+//! the tree is checked against a reference model built before the deletion. Descriptions
+//! are drawn from a tiny alphabet so duplicates are common, and a shared id can be forced
+//! on every node, so the deletion has to go by the node itself rather than its content.
+
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use proptest::{prelude::*, sample::Index};
+use todo::app::ui::{
+    SelectAction,
+    todolistwidget::{Task, TodoList, TodoWidget},
+    workspacewidget::{Workspace, WorkspaceWidget},
+};
+use uuid::Uuid;
+
+/// The shape of a generated tree: a description and the children of every node
+#[derive(Debug, Clone)]
+struct Shape {
+    desc: u8,
+    children: Vec<Shape>,
+}
+
+fn shapes() -> impl Strategy<Value = Vec<Shape>> {
+    let leaf = (0..3u8).prop_map(|desc| Shape {
+        desc,
+        children: Vec::new(),
+    });
+    let node = leaf.prop_recursive(4, 48, 4, |inner| {
+        ((0..3u8), prop::collection::vec(inner, 0..4))
+            .prop_map(|(desc, children)| Shape { desc, children })
+    });
+    prop::collection::vec(node, 1..5)
+}
+
+/// A node of the reference model, in pre-order: its id and the pre-order range of its subtree
+struct Reference {
+    id: Uuid,
+    subtree: std::ops::Range<usize>,
+}
+
+fn reference(shapes: &[Shape], out: &mut Vec<Reference>) {
+    for shape in shapes {
+        let start = out.len();
+        out.push(Reference {
+            id: Uuid::nil(),
+            subtree: start..start,
+        });
+        reference(&shape.children, out);
+        out[start].subtree = start..out.len();
+    }
+}
+
+fn build_tasks(shapes: &[Shape], shared_id: Option<Uuid>) -> Vec<Rc<RefCell<Task>>> {
+    shapes
+        .iter()
+        .map(|shape| {
+            let mut task = Task::new(format!("task {}", shape.desc), None);
+            if let Some(id) = shared_id {
+                task.id = id;
+            }
+            build_tasks(&shape.children, shared_id)
+                .into_iter()
+                .for_each(|child| task.add_child(child));
+            Rc::new(RefCell::new(task))
+        })
+        .collect()
+}
+
+fn build_workspaces(shapes: &[Shape], shared_id: Option<Uuid>) -> Vec<Rc<RefCell<Workspace>>> {
+    shapes
+        .iter()
+        .map(|shape| {
+            let mut ws = Workspace::new(format!("workspace {}", shape.desc));
+            if let Some(id) = shared_id {
+                ws.id = id;
+            }
+            ws.add_children(build_workspaces(&shape.children, shared_id));
+            Rc::new(RefCell::new(ws))
+        })
+        .collect()
+}
+
+/// Check the tree left after deleting `target` against the flattened tree before the deletion
+fn check_deletion<T>(
+    before: &[Rc<RefCell<T>>],
+    after: &[Rc<RefCell<T>>],
+    refs: &[Reference],
+    target: usize,
+    id_of: impl Fn(&T) -> Uuid,
+    shared_id: bool,
+) -> Result<(), TestCaseError> {
+    let removed = &refs[target].subtree;
+    let remaining: HashSet<*const RefCell<T>> = after.iter().map(Rc::as_ptr).collect();
+
+    prop_assert_eq!(after.len(), before.len() - removed.len());
+    for (i, node) in before.iter().enumerate() {
+        prop_assert_eq!(
+            remaining.contains(&Rc::as_ptr(node)),
+            !removed.contains(&i),
+            "node {} of the flattened tree",
+            i
+        );
+    }
+    if !shared_id {
+        let deleted_id = refs[target].id;
+        prop_assert!(after.iter().all(|node| id_of(&node.borrow()) != deleted_id));
+        let lost = refs
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !removed.contains(i))
+            .filter(|(_, r)| !after.iter().any(|node| id_of(&node.borrow()) == r.id))
+            .count();
+        prop_assert_eq!(lost, 0);
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn flatten_tasks_matches_reference(shapes in shapes()) {
+        let mut refs = Vec::new();
+        reference(&shapes, &mut refs);
+        let tasks = build_tasks(&shapes, None);
+
+        prop_assert_eq!(TodoWidget::get_flattened(&tasks).len(), refs.len());
+    }
+
+    #[test]
+    fn flatten_workspaces_matches_reference(shapes in shapes()) {
+        let mut refs = Vec::new();
+        reference(&shapes, &mut refs);
+        let workspaces = build_workspaces(&shapes, None);
+
+        prop_assert_eq!(WorkspaceWidget::get_flattened(&workspaces).len(), refs.len());
+    }
+
+    #[test]
+    fn delete_task_removes_exactly_its_subtree(
+        shapes in shapes(),
+        target in any::<Index>(),
+        shared_id in any::<bool>(),
+    ) {
+        let mut refs = Vec::new();
+        reference(&shapes, &mut refs);
+        let mut tasks = build_tasks(&shapes, shared_id.then(Uuid::new_v4));
+        let before = TodoWidget::get_flattened(&tasks);
+        before.iter().zip(refs.iter_mut()).for_each(|(t, r)| r.id = t.borrow().id);
+        let target = target.index(before.len());
+
+        TodoList::delete_item(&before[target], &mut tasks);
+
+        let after = TodoWidget::get_flattened(&tasks);
+        check_deletion(&before, &after, &refs, target, |t: &Task| t.id, shared_id)?;
+    }
+
+    #[test]
+    fn delete_workspace_removes_exactly_its_subtree(
+        shapes in shapes(),
+        target in any::<Index>(),
+        shared_id in any::<bool>(),
+    ) {
+        let mut refs = Vec::new();
+        reference(&shapes, &mut refs);
+        let mut workspaces = build_workspaces(&shapes, shared_id.then(Uuid::new_v4));
+        let before = WorkspaceWidget::get_flattened(&workspaces);
+        before.iter().zip(refs.iter_mut()).for_each(|(w, r)| r.id = w.borrow().id);
+        let target = target.index(before.len());
+
+        WorkspaceWidget::delete_item(&mut workspaces, &before[target]);
+
+        let after = WorkspaceWidget::get_flattened(&workspaces);
+        check_deletion(&before, &after, &refs, target, |w: &Workspace| w.id, shared_id)?;
+    }
+}
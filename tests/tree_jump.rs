@@ -0,0 +1,89 @@
+//! Tests of the parent, child and sibling jumps
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::node;
+use todo::app::ui::{
+    todolistwidget::Task,
+    tree::{self, Jump},
+    workspacewidget::Workspace,
+};
+
+type Node = Rc<RefCell<Task>>;
+
+/// A folded task holding the `children`
+fn folded(desc: &str, children: Vec<Node>) -> Node {
+    let task = node(desc, children);
+    task.borrow_mut().expanded = false;
+    task
+}
+
+fn desc(node: Option<Node>) -> Option<String> {
+    node.map(|n| n.borrow().desc.clone())
+}
+
+/// `a` with children `a1` (with `a1x`) and `a2`, then `b`
+fn fixture() -> (Vec<Node>, Node, Node) {
+    let a1x = folded("a1x", vec![]);
+    let a1 = folded("a1", vec![a1x.clone()]);
+    let a = folded("a", vec![a1.clone(), folded("a2", vec![])]);
+    (vec![a, folded("b", vec![])], a1, a1x)
+}
+
+#[test]
+fn parent_and_first_child() {
+    let (roots, a1, a1x) = fixture();
+
+    assert_eq!(
+        desc(tree::jump(&roots, &a1x, Jump::Parent)),
+        Some("a1".into())
+    );
+    assert_eq!(
+        desc(tree::jump(&roots, &a1, Jump::Parent)),
+        Some("a".into())
+    );
+    assert_eq!(desc(tree::jump(&roots, &roots[0], Jump::Parent)), None);
+
+    assert_eq!(
+        desc(tree::jump(&roots, &a1, Jump::FirstChild)),
+        Some("a1x".into())
+    );
+    assert!(a1.borrow().expanded);
+    assert_eq!(desc(tree::jump(&roots, &roots[1], Jump::FirstChild)), None);
+    assert!(!roots[1].borrow().expanded);
+}
+
+#[test]
+fn siblings_skip_descendants() {
+    let (roots, a1, a1x) = fixture();
+
+    assert_eq!(
+        desc(tree::jump(&roots, &roots[0], Jump::NextSibling)),
+        Some("b".into())
+    );
+    assert_eq!(
+        desc(tree::jump(&roots, &roots[1], Jump::PrevSibling)),
+        Some("a".into())
+    );
+    assert_eq!(
+        desc(tree::jump(&roots, &a1, Jump::NextSibling)),
+        Some("a2".into())
+    );
+    assert_eq!(desc(tree::jump(&roots, &a1, Jump::PrevSibling)), None);
+    assert_eq!(desc(tree::jump(&roots, &a1x, Jump::NextSibling)), None);
+    assert_eq!(desc(tree::jump(&roots, &roots[1], Jump::NextSibling)), None);
+}
+
+#[test]
+fn workspaces_share_the_helpers() {
+    let child = Rc::new(RefCell::new(Workspace::new("child".to_string())));
+    let mut parent = Workspace::new("parent".to_string());
+    parent.add_children(vec![child.clone()]);
+    let roots = vec![Rc::new(RefCell::new(parent))];
+
+    let found = tree::find_parent(&roots, &child).unwrap();
+    assert!(Rc::ptr_eq(&found, &roots[0]));
+    assert_eq!(tree::siblings(&roots, &child).len(), 1);
+}
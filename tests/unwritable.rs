@@ -0,0 +1,153 @@
+//! Tests of starting with a data file which can't be written
+
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use common::{code, ui_with};
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::AppState,
+    config::Config,
+    data,
+    errors::Errors,
+    ui::{Ui, UiMessage, Unwritable},
+};
+use tokio::sync::mpsc;
+
+/// A fresh directory of the test, removed first if a previous run left it
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("todo-unwritable-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A data file whose directory is a file, which can't be written even as root
+fn blocked(dir: &Path) -> PathBuf {
+    fs::write(dir.join("file"), "").unwrap();
+    dir.join("file").join("data.json")
+}
+
+async fn resolve(ui: &mut Ui, keys: &[KeyCode]) -> Unwritable {
+    let (input_tx, input_rx) = mpsc::channel(256);
+    ui.input_rx = Arc::new(tokio::sync::Mutex::new(input_rx));
+    for c in keys {
+        input_tx.send(code(*c)).await.unwrap();
+    }
+    drop(input_tx);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let err = Errors::SaveError {
+        path: PathBuf::from("/nowhere"),
+        reason: "Permission denied".to_string(),
+    };
+    ui.resolve_unwritable(ui.input_rx.clone(), &mut terminal, &err)
+        .await
+}
+
+fn ui() -> Ui {
+    ui_with(vec![], vec![]).0
+}
+
+fn typed(text: &str) -> Vec<KeyCode> {
+    text.chars().map(KeyCode::Char).collect()
+}
+
+#[test]
+fn a_writable_directory_passes_and_is_left_clean() {
+    let dir = scratch("ok");
+    let path = dir.join("nested").join("data.json");
+
+    data::check_writable(&path).unwrap();
+
+    assert!(dir.join("nested").is_dir());
+    assert_eq!(fs::read_dir(dir.join("nested")).unwrap().count(), 0);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn a_directory_which_cant_be_created_is_reported() {
+    let dir = scratch("blocked");
+    let path = blocked(&dir);
+
+    match data::check_writable(&path) {
+        Err(Errors::SaveError { path: at, .. }) => assert_eq!(at, dir.join("file")),
+        other => panic!("{:?}", other),
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn the_picked_path_is_kept_in_the_config() {
+    assert_eq!(Config::default().data_path, None);
+    let config: Config = serde_json::from_str(r#"{"data_path": "/mnt/todo.json"}"#).unwrap();
+    assert_eq!(config.data_path, Some(PathBuf::from("/mnt/todo.json")));
+}
+
+#[tokio::test]
+async fn read_only_turns_saving_off() {
+    let dir = scratch("readonly");
+    let mut ui = ui();
+    ui.data_path = dir.join("data.json");
+
+    assert_eq!(
+        resolve(&mut ui, &[KeyCode::Char('r')]).await,
+        Unwritable::ReadOnly
+    );
+    assert!(ui.read_only);
+
+    let (ui_tx, ui_rx) = mpsc::channel(1);
+    ui.ui_rx = ui_rx;
+    ui_tx.send(UiMessage::SaveData).await.unwrap();
+    drop(ui_tx);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    ui.handle_uimsg(&mut terminal, Arc::new(Mutex::new(AppState::new())))
+        .await;
+    assert_eq!(ui.prompt.desc, "Read-Only, Nothing Is Saved !");
+    assert!(!ui.data_path.exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn another_path_is_used_for_the_saves() {
+    let dir = scratch("moved");
+    let mut ui = ui();
+    ui.data_path = blocked(&dir);
+
+    let mut keys = vec![KeyCode::Char('p')];
+    keys.extend(typed(dir.to_str().unwrap()));
+    keys.extend([KeyCode::Enter, KeyCode::Char('n')]);
+    let choice = resolve(&mut ui, &keys).await;
+
+    assert_eq!(choice, Unwritable::Moved(dir.join("data.json")));
+    assert_eq!(ui.data_path, dir.join("data.json"));
+    assert!(!ui.read_only);
+    assert_eq!(ui.config.data_path, None);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn a_path_which_fails_too_is_refused_before_quitting() {
+    let dir = scratch("refused");
+    let mut ui = ui();
+    let origin = blocked(&dir);
+    ui.data_path = origin.clone();
+    fs::write(dir.join("taken.json"), "{}").unwrap();
+
+    let mut keys = vec![KeyCode::Char('p')];
+    keys.extend(typed(dir.join("taken.json").to_str().unwrap()));
+    keys.extend([KeyCode::Enter, KeyCode::Char('p')]);
+    keys.extend(typed(origin.to_str().unwrap()));
+    keys.extend([KeyCode::Enter, KeyCode::Char('q')]);
+    let choice = resolve(&mut ui, &keys).await;
+
+    assert_eq!(choice, Unwritable::Quit);
+    assert_eq!(ui.data_path, origin);
+    assert_eq!(fs::read_to_string(dir.join("taken.json")).unwrap(), "{}");
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,115 @@
+//! Tests of the urgency marker drawn in the rows of the task list
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    widgets::{List, ListItem},
+};
+use todo::app::{
+    sources::FixedClock,
+    ui::{
+        glyphs,
+        todolistwidget::{RowOptions, SortRule, Task, TaskStatus, TodoWidget, Urgency},
+    },
+};
+
+fn clock() -> FixedClock {
+    FixedClock(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap())
+}
+
+/// Four tasks due the same day, one for each urgency and one without, the last with a subtask
+fn tasks() -> Vec<Rc<RefCell<Task>>> {
+    let due = NaiveDate::from_ymd_opt(2025, 7, 5);
+    let urgencies = [
+        Some(Urgency::Critical),
+        Some(Urgency::Important),
+        Some(Urgency::Common),
+        None,
+    ];
+    let tasks: Vec<_> = (urgencies.into_iter().enumerate())
+        .map(|(i, urgency)| {
+            let mut task = Task::new(format!("task {}", "x".repeat(i)), due);
+            task.urgency = urgency;
+            Rc::new(RefCell::new(task))
+        })
+        .collect();
+    let mut child = Task::new("sub".to_string(), due);
+    child.urgency = Some(Urgency::Critical);
+    tasks[3]
+        .borrow_mut()
+        .add_child(Rc::new(RefCell::new(child)));
+    tasks
+}
+
+fn rows(items: Vec<ListItem>) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(60, 5)).unwrap();
+    terminal
+        .draw(|f| f.render_widget(List::new(items), f.area()))
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..5)
+        .map(|y| (0..60).map(|x| buffer[(x, y)].symbol()).collect())
+        .collect()
+}
+
+fn check(rows: &[String]) {
+    let status = glyphs::PLAIN.todo.chars().count();
+    let marker = |row: &String| row.chars().skip(status).take(3).collect::<String>();
+    let markers: Vec<String> = rows.iter().map(marker).collect();
+    assert_eq!(markers, [" # ", " ! ", " . ", "   ", " # "]);
+    let due = TodoWidget::due_span(
+        NaiveDate::from_ymd_opt(2025, 7, 5).unwrap(),
+        &TaskStatus::Todo,
+        &clock(),
+        0,
+        None,
+    );
+    let columns: Vec<_> = (rows.iter())
+        .map(|row| row.find(due.content.as_ref()))
+        .collect();
+    assert!(columns[0].is_some(), "{:?}", rows);
+    assert!(
+        columns.iter().all(|column| *column == columns[0]),
+        "{:?}",
+        rows
+    );
+}
+
+#[test]
+fn the_marker_sits_between_the_status_and_the_description() {
+    glyphs::select_set(&glyphs::PLAIN);
+    let tasks = tasks();
+    let max_len = TodoWidget::find_max_tasks_len(&tasks, 0);
+    let items = TodoWidget::get_task_list_item(
+        &tasks,
+        SortRule::Manual,
+        0,
+        max_len,
+        &clock(),
+        RowOptions::default(),
+        None,
+    );
+
+    check(&rows(items));
+}
+
+#[test]
+fn the_rows_of_a_search_have_the_same_markers() {
+    glyphs::select_set(&glyphs::PLAIN);
+    let tasks = tasks();
+    let max_len = TodoWidget::find_max_tasks_len(&tasks, 0);
+    let items = TodoWidget::get_search_list_item(
+        "task".to_string(),
+        &tasks,
+        SortRule::Manual,
+        0,
+        max_len,
+        &clock(),
+        RowOptions::default(),
+    );
+
+    check(&rows(items));
+}
@@ -0,0 +1,179 @@
+//! Tests of the order every reader of the data walks the tasks in
+
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::NaiveDate;
+use todo::app::{
+    data::Datas,
+    diff::{self, Change},
+    serve::{self, Command},
+    sources::SystemClock,
+    ui::{
+        todolistwidget::{SortRule, Staleness, Task, TaskStatus, TodoList},
+        workspacewidget::Workspace,
+    },
+    visit::{NO_WORKSPACE, VisitOptions, visit_tasks},
+};
+use uuid::Uuid;
+
+fn task(desc: &str, due: u32) -> Rc<RefCell<Task>> {
+    let due = NaiveDate::from_ymd_opt(2025, 7, due);
+    Rc::new(RefCell::new(Task::new(desc.to_string(), due)))
+}
+
+fn list(ws: Uuid, tasks: &[Rc<RefCell<Task>>]) -> Rc<RefCell<TodoList>> {
+    let mut list = TodoList::new(ws);
+    tasks.iter().for_each(|task| list.add_task(task.clone()));
+    Rc::new(RefCell::new(list))
+}
+
+/// `Work` with a `Backend` child sorted by due date, `Home` stored before them, an archived
+/// `Old` workspace and a list whose workspace is gone
+///
+/// `Release` is collapsed and holds a deprecated `Notes` holding `Typos`, and `Backend`
+/// shows `Deploy` before `Migrate` though it stores them the other way round.
+fn datas() -> Datas {
+    let mut datas = Datas::default();
+    let work = Rc::new(RefCell::new(Workspace::new("Work".to_string())));
+    let backend = Rc::new(RefCell::new(Workspace::new("Backend".to_string())));
+    let home = Rc::new(RefCell::new(Workspace::new("Home".to_string())));
+    let old = Rc::new(RefCell::new(Workspace::new("Old".to_string())));
+    work.borrow_mut().add_child(backend.clone());
+    datas.workspace.add_workspace(work.clone());
+    datas.workspace.add_workspace(home.clone());
+    datas.archived_ws.add_workspace(old.clone());
+
+    let release = task("Release", 9);
+    let notes = task("Notes", 9);
+    notes.borrow_mut().status = TaskStatus::Deprecated;
+    notes.borrow_mut().add_child(task("Typos", 9));
+    release.borrow_mut().add_child(notes);
+    release.borrow_mut().add_child(task("Tag", 9));
+    release.borrow_mut().expanded = false;
+    let backend_list = list(
+        backend.borrow().id,
+        &[task("Migrate", 5), task("Deploy", 2)],
+    );
+    backend_list.borrow_mut().sort_rule = SortRule::Due;
+
+    for list in [
+        list(Uuid::new_v4(), &[task("Lost", 1)]),
+        list(home.borrow().id, &[task("Groceries", 1)]),
+        list(old.borrow().id, &[task("Archived", 1)]),
+        backend_list,
+        list(work.borrow().id, &[release, task("Review", 3)]),
+    ] {
+        datas.todolist.add_list(list);
+    }
+    datas
+}
+
+fn visited(datas: &Datas, options: VisitOptions) -> Vec<String> {
+    let mut visited = Vec::new();
+    visit_tasks(datas, options, |visit| {
+        visited.push(format!(
+            "{}{} @ {}",
+            "  ".repeat(visit.depth),
+            visit.task.desc,
+            visit.workspace_path
+        ))
+    });
+    visited
+}
+
+#[test]
+fn the_tasks_are_visited_in_the_order_of_the_screen() {
+    assert_eq!(
+        visited(&datas(), VisitOptions::everything()),
+        [
+            "Release @ Work",
+            "  Notes @ Work",
+            "    Typos @ Work",
+            "  Tag @ Work",
+            "Review @ Work",
+            "Deploy @ Work / Backend",
+            "Migrate @ Work / Backend",
+            "Groceries @ Home",
+            "Archived @ Old [archived]",
+            &format!("Lost @ {}", NO_WORKSPACE),
+        ]
+    );
+}
+
+#[test]
+fn the_options_leave_out_archived_deprecated_and_deep_tasks() {
+    let datas = datas();
+
+    let active = visited(&datas, VisitOptions::default());
+    assert_eq!(active.len(), 8);
+    assert!(!active.iter().any(|row| row.contains("Archived")));
+    assert!(!active.iter().any(|row| row.contains("Lost")));
+
+    let current = visited(
+        &datas,
+        VisitOptions {
+            deprecated: false,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        current[..3],
+        ["Release @ Work", "  Tag @ Work", "Review @ Work"]
+    );
+
+    let top = visited(
+        &datas,
+        VisitOptions {
+            max_depth: Some(0),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        top,
+        [
+            "Release @ Work",
+            "Review @ Work",
+            "Deploy @ Work / Backend",
+            "Migrate @ Work / Backend",
+            "Groceries @ Home",
+        ]
+    );
+}
+
+#[test]
+fn every_reader_lists_the_same_sequence() {
+    let mut datas = datas();
+    let mut expected = Vec::new();
+    visit_tasks(&datas, VisitOptions::everything(), |visit| {
+        expected.push((visit.workspace_path.to_string(), visit.task.desc.clone()))
+    });
+
+    let command = Command::ListTasks { workspace: None };
+    let (response, _) =
+        serve::dispatch(&mut datas, command, Staleness::new(&SystemClock, 30)).unwrap();
+    let listed: Vec<(String, String)> = response
+        .tasks
+        .unwrap()
+        .into_iter()
+        .map(|task| (task.workspace, task.desc))
+        .collect();
+    assert_eq!(listed, expected);
+
+    // the diff groups its changes by workspace, in the order of the tasks within each
+    let added = diff::diff(&Datas::default(), &datas);
+    for (workspace, changes) in added.workspaces {
+        let descs: Vec<String> = changes
+            .into_iter()
+            .map(|change| match change {
+                Change::Added(desc) => desc,
+                other => panic!("{:?}", other),
+            })
+            .collect();
+        let visited: Vec<String> = expected
+            .iter()
+            .filter(|(path, _)| *path == workspace)
+            .map(|(_, desc)| desc.clone())
+            .collect();
+        assert_eq!(descs, visited, "{}", workspace);
+    }
+}
@@ -0,0 +1,158 @@
+//! Tests of the quick stats popup of a workspace
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use chrono::NaiveDateTime;
+use common::{day, key, rows, ui_with, workspace};
+use crossterm::event::KeyEvent;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::{
+    appstate::{AppState, CurrentMode},
+    sources::FixedClock,
+    ui::{
+        Ui, UiMessage, WidgetAction,
+        todolistwidget::{Task, TaskStats, TaskStatus, TodoList, WorkspaceStats},
+        workspacewidget::Workspace,
+    },
+};
+use tokio::sync::mpsc;
+
+fn at(d: u32, h: u32) -> NaiveDateTime {
+    day(d).and_hms_opt(h, 0, 0).unwrap()
+}
+
+fn task(status: TaskStatus, due: Option<u32>, touched: u32) -> Rc<RefCell<Task>> {
+    let mut task = Task::new(format!("{:?}", status), due.map(day));
+    task.status = status;
+    task.created = Some(at(touched, 9));
+    Rc::new(RefCell::new(task))
+}
+
+/// `Work` with a list, a `Backend` child with a list and a `Docs` grandchild without one,
+/// next to a `Home` workspace left out of the stats, with the senders of the UI messages and
+/// keys
+fn ui() -> (
+    Ui,
+    Rc<RefCell<Workspace>>,
+    mpsc::Sender<UiMessage>,
+    mpsc::Sender<KeyEvent>,
+) {
+    let (mut ui, ui_tx, input_tx) = ui_with(vec![], vec![]);
+    let (work, backend) = (workspace("Work"), workspace("Backend"));
+    backend.borrow_mut().add_child(workspace("Docs"));
+    work.borrow_mut().add_child(backend.clone());
+    let home = workspace("Home");
+
+    let mut work_list = TodoList::new(work.borrow().id);
+    let parent = task(TaskStatus::InProcess, Some(3), 1);
+    parent
+        .borrow_mut()
+        .add_child(task(TaskStatus::Todo, Some(1), 2));
+    work_list.add_task(parent);
+    work_list.add_task(task(TaskStatus::Finished, Some(1), 5));
+    let mut backend_list = TodoList::new(backend.borrow().id);
+    backend_list.add_task(task(TaskStatus::Todo, Some(9), 3));
+    backend_list.add_task(task(TaskStatus::Deprecated, Some(1), 4));
+    let mut home_list = TodoList::new(home.borrow().id);
+    home_list.add_task(task(TaskStatus::Todo, Some(2), 20));
+    for list in [work_list, backend_list, home_list] {
+        ui.todolist.add_list(Rc::new(RefCell::new(list)));
+    }
+    ui.workspace.add_workspace(work.clone());
+    ui.workspace.add_workspace(home);
+    (ui, work, ui_tx, input_tx)
+}
+
+#[test]
+fn the_stats_cover_the_sub_workspaces_only() {
+    let (ui, work, _, _) = ui();
+
+    let stats = ui.todolist.workspace_stats(&work, &FixedClock(day(2)));
+
+    assert_eq!(
+        stats,
+        WorkspaceStats {
+            tasks: TaskStats {
+                finished: 1,
+                total: 4
+            },
+            in_process: 1,
+            overdue: 1,
+            next_due: Some(day(3)),
+            last_activity: Some(at(5, 9)),
+            sub_workspaces: 2,
+        }
+    );
+}
+
+#[test]
+fn an_empty_workspace_has_no_dates() {
+    let (ui, _, _, _) = ui();
+    let empty = workspace("Empty");
+
+    let stats = ui.todolist.workspace_stats(&empty, &FixedClock(day(2)));
+
+    assert_eq!(stats, WorkspaceStats::default());
+}
+
+#[tokio::test]
+async fn the_popup_closes_on_any_key() {
+    let (mut ui, work, ui_tx, input_tx) = ui();
+    ui.workspace.select(Some(work));
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::WorkspaceStats))
+        .await
+        .unwrap();
+    input_tx.send(key('x')).await.unwrap();
+    drop(ui_tx);
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    ui.handle_uimsg(&mut terminal, appstate.clone()).await;
+
+    // the key closed the popup, it didn't delete anything
+    assert_eq!(ui.workspace.workspaces.len(), 2);
+    assert_eq!(appstate.lock().unwrap().current_mode, CurrentMode::Normal);
+    assert!(!ui.dirty);
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect();
+    assert!(!screen.contains("Sub workspaces"));
+}
+
+#[tokio::test]
+async fn the_popup_shows_the_vitals_of_the_selected_workspace() {
+    let (mut ui, work, ui_tx, _input_tx) = ui();
+    ui.workspace.select(Some(work));
+    ui_tx
+        .send(UiMessage::WAction(WidgetAction::WorkspaceStats))
+        .await
+        .unwrap();
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let appstate = Arc::new(Mutex::new(AppState::new()));
+    // no key comes, so the popup stays open
+    let open = tokio::time::timeout(
+        std::time::Duration::from_millis(200),
+        ui.handle_uimsg(&mut terminal, appstate),
+    )
+    .await;
+    assert!(open.is_err());
+
+    let rows = rows(&terminal);
+    let row = |label: &str| rows.iter().find(|row| row.contains(label)).unwrap().clone();
+    assert!(row("Tasks").contains("4"));
+    assert!(row("Done").contains("1"));
+    assert!(row("Sub workspaces").contains("2"));
+    assert!(row("Last activity").contains("2025-07-05 09:00"));
+}
@@ -0,0 +1,131 @@
+//! Tests of the tree guides of the workspace panel
+
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use common::ws_node;
+use ratatui::{Terminal, backend::TestBackend};
+use todo::app::ui::{
+    glyphs,
+    workspacewidget::{Workspace, WorkspaceType, WorkspaceWidget},
+};
+
+/// `Work` with `Backend`, which has `API`, and `Home`, then a protected `Personal`
+fn widget() -> WorkspaceWidget {
+    glyphs::select(true);
+    let mut widget = WorkspaceWidget::new(WorkspaceType::Normal);
+    let backend = ws_node("Backend", vec![ws_node("API", vec![])]);
+    widget.add_workspace(ws_node("Work", vec![backend, ws_node("Home", vec![])]));
+    let personal = ws_node("Personal", vec![]);
+    personal.borrow_mut().protected = true;
+    widget.add_workspace(personal);
+    widget
+}
+
+fn find(widget: &WorkspaceWidget, desc: &str) -> Rc<RefCell<Workspace>> {
+    let mut stack = widget.workspaces.clone();
+    while let Some(ws) = stack.pop() {
+        if ws.borrow().desc == desc {
+            return ws;
+        }
+        stack.extend(ws.borrow().children.iter().cloned());
+    }
+    panic!("no workspace {}", desc);
+}
+
+/// The rows drawn inside the border and the padding of the panel
+fn render(widget: &mut WorkspaceWidget) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(24, 10)).unwrap();
+    terminal
+        .draw(|f| f.render_widget(&mut *widget, f.area()))
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    (2..8)
+        .map(|y| {
+            (2..22)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn nested_workspaces_hang_from_guides() {
+    let mut widget = widget();
+    widget.active = Some(find(&widget, "Home").borrow().id);
+
+    assert_eq!(
+        render(&mut widget),
+        [
+            "  v Work",
+            "  |-v Backend",
+            "  | `-API",
+            "* `-Home",
+            "  Personal L",
+            "",
+        ]
+    );
+}
+
+#[test]
+fn a_last_child_leaves_its_column_blank_below_it() {
+    glyphs::select(true);
+    let mut widget = WorkspaceWidget::new(WorkspaceType::Normal);
+    let backend = ws_node(
+        "Backend",
+        vec![ws_node("API", vec![]), ws_node("DB", vec![])],
+    );
+    widget.add_workspace(ws_node("Work", vec![ws_node("Home", vec![]), backend]));
+    widget.add_workspace(ws_node("Personal", vec![]));
+
+    assert_eq!(
+        render(&mut widget),
+        [
+            "  v Work",
+            "  |-Home",
+            "  `-v Backend",
+            "    |-API",
+            "    `-DB",
+            "  Personal",
+        ]
+    );
+}
+
+#[test]
+fn the_selection_follows_the_rows_of_a_collapsed_tree() {
+    let mut widget = widget();
+    widget.workspaces[0].borrow_mut().expanded = false;
+    let personal = find(&widget, "Personal");
+    widget.select(Some(personal));
+    // the hidden children aren't counted
+    assert_eq!(widget.ws_state.selected(), Some(1));
+
+    let rows = render(&mut widget);
+
+    assert_eq!(rows[..3], ["  > Work", "  Personal L", ""]);
+    assert_eq!(widget.ws_state.selected(), Some(1));
+
+    // selecting a hidden workspace expands the workspaces above it
+    widget.select(Some(find(&widget, "API")));
+    render(&mut widget);
+    assert_eq!(widget.ws_state.selected(), Some(2));
+}
+
+#[test]
+fn rows_match_the_shown_workspaces() {
+    let widget = widget();
+
+    let ids: Vec<_> = WorkspaceWidget::get_ws_list(&widget.workspaces, None)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+
+    let expected: Vec<_> = ["Work", "Backend", "API", "Home", "Personal"]
+        .iter()
+        .map(|desc| find(&widget, desc).borrow().id)
+        .collect();
+    assert_eq!(ids, expected);
+}